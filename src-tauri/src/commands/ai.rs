@@ -0,0 +1,62 @@
+// src-tauri/src/commands/ai.rs
+// Anthropic Messages API — the one-shot text completion shared by the in-app
+// AI features (task assist, meeting notes, reply drafts, error explanations).
+// Callers pick the model, token budget and temperature.
+
+use crate::commands::error::{CmdResult, CommandError};
+use serde_json::json;
+
+const MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+
+/// Send one user message with a system prompt and return the reply's text
+/// blocks joined and trimmed. Non-2xx responses come back as
+/// `CommandError::Http`; an empty reply is a parse error.
+pub async fn anthropic_messages(
+    api_key: &str,
+    model: &str,
+    system: &str,
+    user: &str,
+    max_tokens: u32,
+    temperature: f64,
+) -> CmdResult<String> {
+    let response = crate::HTTP_CLIENT
+        .post(MESSAGES_URL)
+        .header("Content-Type", "application/json")
+        .header("x-api-key", api_key)
+        .header("anthropic-version", API_VERSION)
+        .json(&json!({
+            "model": model,
+            "max_tokens": max_tokens,
+            "temperature": temperature,
+            "system": system,
+            "messages": [{ "role": "user", "content": user }],
+        }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        return Err(CommandError::Http {
+            status,
+            body: body.chars().take(500).collect(),
+        });
+    }
+
+    let value: serde_json::Value = response.json().await?;
+    let text = value["content"]
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|b| b["text"].as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    if text.trim().is_empty() {
+        return Err(CommandError::Parse("Empty response from model".to_string()));
+    }
+    Ok(text.trim().to_string())
+}
@@ -0,0 +1,562 @@
+// Analytics data retention — roll up and archive old page views.
+//
+// Raw analytics_page_views rows older than `keep_months` are, one month at a
+// time: written to a gzipped JSONL file (knowledge folder or S3, via the
+// backup module's Store), rolled up into analytics_page_view_rollups, recorded
+// in analytics_archives, and deleted. `analytics_archive_restore` re-imports a
+// month. Config and last-run state live in ~/.tv-client/analytics/.
+
+use chrono::{Datelike, Months, NaiveDate};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{Emitter, Manager};
+
+use crate::commands::backup::store::Store;
+use crate::commands::backup::types::BackupDestination;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use crate::AppState;
+
+const UPSERT_CHUNK: usize = 500;
+const PAGE_VIEW_CONFLICT: &str = "source,page_path,user_id,view_date";
+const ROLLUP_CONFLICT: &str = "period,period_start,source,domain,page_path,is_internal";
+
+/// Only one archive or restore at a time
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+struct RunGuard;
+
+impl RunGuard {
+    fn acquire() -> CmdResult<Self> {
+        if RUNNING.swap(true, Ordering::SeqCst) {
+            return Err(CommandError::Internal("An analytics archive or restore is already running".to_string()));
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ArchiveConfig {
+    /// Run automatically once a month
+    pub enabled: bool,
+    /// Whole months of raw rows kept in Supabase (the current month always is)
+    pub keep_months: u32,
+    /// "week" or "month" aggregates
+    pub rollup_period: String,
+    /// Local path defaults to <knowledge>/_archive/analytics
+    pub destination: BackupDestination,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep_months: 12,
+            rollup_period: "month".to_string(),
+            destination: BackupDestination::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ArchiveState {
+    pub last_run_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsArchive {
+    pub id: String,
+    pub month: NaiveDate,
+    pub file_name: String,
+    pub location: String,
+    pub destination_kind: String,
+    pub row_count: i64,
+    pub total_views: i64,
+    pub size_bytes: i64,
+    pub archived_at: String,
+    pub restored_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedMonth {
+    pub month: NaiveDate,
+    pub row_count: usize,
+    pub total_views: i64,
+    pub rollup_rows: usize,
+    /// None on a dry run
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveRunResult {
+    /// Rows with view_date before this were archived
+    pub cutoff: NaiveDate,
+    pub dry_run: bool,
+    pub months: Vec<ArchivedMonth>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResult {
+    pub month: NaiveDate,
+    pub rows_restored: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawView {
+    source: String,
+    domain: Option<String>,
+    page_path: String,
+    user_id: Option<String>,
+    view_date: NaiveDate,
+    views: i64,
+    #[serde(default)]
+    is_internal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Rollup {
+    period: &'static str,
+    period_start: NaiveDate,
+    source: String,
+    domain: String,
+    page_path: String,
+    is_internal: bool,
+    views: i64,
+    unique_users: i64,
+}
+
+// ============================================================================
+// Config + state
+// ============================================================================
+
+fn analytics_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("analytics")
+}
+
+fn load_config() -> ArchiveConfig {
+    std::fs::read_to_string(analytics_dir().join("archive_config.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn load_state() -> ArchiveState {
+    std::fs::read_to_string(analytics_dir().join("archive_state.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &ArchiveState) {
+    let _ = std::fs::create_dir_all(analytics_dir());
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(analytics_dir().join("archive_state.json"), json);
+    }
+}
+
+fn open_store(config: &ArchiveConfig, knowledge_path: &str) -> CmdResult<Store> {
+    let mut dest = config.destination.clone();
+    if dest.kind == "local" && dest.path.as_deref().map_or(true, |p| p.is_empty()) {
+        if knowledge_path.is_empty() {
+            return Err(CommandError::Config("Archive folder not set and no knowledge folder configured".to_string()));
+        }
+        let default = Path::new(knowledge_path).join("_archive").join("analytics");
+        dest.path = Some(default.to_string_lossy().to_string());
+    }
+    Store::from_config(&dest)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn next_month(month: NaiveDate) -> NaiveDate {
+    month.checked_add_months(Months::new(1)).unwrap_or(month)
+}
+
+fn archive_file_name(month: NaiveDate) -> String {
+    format!("analytics-page-views-{}.jsonl.gz", month.format("%Y-%m"))
+}
+
+/// Aggregate one month of raw rows. Weekly buckets are clipped at the month
+/// start so a week spanning two archived months yields two distinct rows
+/// instead of the second month's upsert overwriting the first.
+fn rollup_rows(rows: &[RawView], period: &str, month: NaiveDate) -> Vec<Rollup> {
+    let weekly = period == "week";
+    let mut buckets: HashMap<(NaiveDate, &str, &str, &str, bool), (i64, HashSet<&str>)> = HashMap::new();
+    for r in rows {
+        let start = if weekly {
+            let monday = r.view_date - chrono::Duration::days(r.view_date.weekday().num_days_from_monday() as i64);
+            monday.max(month)
+        } else {
+            month_start(r.view_date)
+        };
+        let key = (start, r.source.as_str(), r.domain.as_deref().unwrap_or(""), r.page_path.as_str(), r.is_internal);
+        let entry = buckets.entry(key).or_default();
+        entry.0 += r.views;
+        if let Some(user) = r.user_id.as_deref().filter(|u| !u.is_empty()) {
+            entry.1.insert(user);
+        }
+    }
+
+    let mut rollups: Vec<Rollup> = buckets
+        .into_iter()
+        .map(|((period_start, source, domain, page_path, is_internal), (views, users))| Rollup {
+            period: if weekly { "week" } else { "month" },
+            period_start,
+            source: source.to_string(),
+            domain: domain.to_string(),
+            page_path: page_path.to_string(),
+            is_internal,
+            views,
+            unique_users: users.len() as i64,
+        })
+        .collect();
+    rollups.sort_by(|a, b| (a.period_start, &a.page_path).cmp(&(b.period_start, &b.page_path)));
+    rollups
+}
+
+fn write_jsonl_gz(rows: &[serde_json::Value], path: &Path) -> CmdResult<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut encoder = GzEncoder::new(std::fs::File::create(path)?, flate2::Compression::default());
+    for row in rows {
+        serde_json::to_writer(&mut encoder, row)?;
+        encoder.write_all(b"\n")?;
+    }
+    encoder.finish()?;
+    Ok(())
+}
+
+fn read_jsonl_gz(path: &Path) -> CmdResult<Vec<serde_json::Value>> {
+    let reader = BufReader::new(GzDecoder::new(std::fs::File::open(path)?));
+    let mut rows = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            rows.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(rows)
+}
+
+async fn load_month(client: &SupabaseClient, month: NaiveDate) -> CmdResult<Vec<serde_json::Value>> {
+    let filter = format!(
+        "view_date=gte.{}&view_date=lt.{}&order=view_date.asc,source.asc,page_path.asc,user_id.asc",
+        month,
+        next_month(month)
+    );
+    client.select_all("analytics_page_views", &filter).await
+}
+
+async fn upsert_chunks<T: Serialize>(client: &SupabaseClient, table: &str, rows: &[T], on_conflict: &str) -> CmdResult<()> {
+    for chunk in rows.chunks(UPSERT_CHUNK) {
+        let _: serde_json::Value = client.upsert_on(table, &chunk, Some(on_conflict)).await?;
+    }
+    Ok(())
+}
+
+async fn run_archive(config: &ArchiveConfig, knowledge_path: &str, dry_run: bool) -> CmdResult<ArchiveRunResult> {
+    let _guard = RunGuard::acquire()?;
+    if config.rollup_period != "week" && config.rollup_period != "month" {
+        return Err(CommandError::Validation {
+            message: format!("Unknown rollup period: {} (expected week or month)", config.rollup_period),
+            fields: vec!["rollup_period".to_string()],
+        });
+    }
+    let today = chrono::Local::now().date_naive();
+    let cutoff = month_start(today)
+        .checked_sub_months(Months::new(config.keep_months))
+        .unwrap_or(today);
+    let mut result = ArchiveRunResult { cutoff, dry_run, months: Vec::new() };
+
+    let client = get_client().await?;
+    let oldest: Option<serde_json::Value> = client
+        .select_single("analytics_page_views", "select=view_date&order=view_date.asc&limit=1")
+        .await?;
+    let Some(oldest) = oldest
+        .and_then(|v| v["view_date"].as_str().map(String::from))
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+    else {
+        return Ok(result);
+    };
+
+    let store = if dry_run { None } else { Some(open_store(config, knowledge_path)?) };
+    let scratch = analytics_dir().join("archive-tmp");
+    let mut month = month_start(oldest);
+    while month < cutoff {
+        let rows = load_month(&client, month).await?;
+        if rows.is_empty() {
+            month = next_month(month);
+            continue;
+        }
+        let raw: Vec<RawView> = rows.iter().filter_map(|r| serde_json::from_value(r.clone()).ok()).collect();
+        let rollups = rollup_rows(&raw, &config.rollup_period, month);
+        let total_views: i64 = raw.iter().map(|r| r.views).sum();
+
+        let location = match &store {
+            None => None,
+            Some(store) => {
+                let file_name = archive_file_name(month);
+                let tmp = scratch.join(&file_name);
+                write_jsonl_gz(&rows, &tmp)?;
+                let size_bytes = std::fs::metadata(&tmp).map(|m| m.len()).unwrap_or(0);
+                let location = store.put_as(&tmp, &file_name, "application/gzip").await.inspect_err(|_| {
+                    let _ = std::fs::remove_file(&tmp);
+                })?;
+
+                upsert_chunks(&client, "analytics_page_view_rollups", &rollups, ROLLUP_CONFLICT).await?;
+                let manifest = serde_json::json!({
+                    "month": month,
+                    "file_name": file_name,
+                    "location": location,
+                    "destination_kind": if store.is_remote() { "s3" } else { "local" },
+                    "row_count": rows.len(),
+                    "total_views": total_views,
+                    "size_bytes": size_bytes,
+                    "archived_at": chrono::Utc::now().to_rfc3339(),
+                    "restored_at": null,
+                });
+                let _: serde_json::Value = client.upsert_on("analytics_archives", &manifest, Some("month")).await?;
+
+                // Raw rows go only once the archive and rollups are safely written
+                client
+                    .delete(
+                        "analytics_page_views",
+                        &format!("view_date=gte.{}&view_date=lt.{}", month, next_month(month)),
+                    )
+                    .await?;
+                Some(location)
+            }
+        };
+
+        result.months.push(ArchivedMonth {
+            month,
+            row_count: rows.len(),
+            total_views,
+            rollup_rows: rollups.len(),
+            location,
+        });
+        month = next_month(month);
+    }
+
+    if !dry_run {
+        save_state(&ArchiveState { last_run_at: Some(chrono::Utc::now().to_rfc3339()), last_error: None });
+    }
+    Ok(result)
+}
+
+/// Monthly archive check (daily). Call from main.rs setup hook; no-op unless enabled.
+pub fn start_archive_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+        loop {
+            let config = load_config();
+            let state = load_state();
+            let this_month = month_start(chrono::Local::now().date_naive());
+            let ran_this_month = state
+                .last_run_at
+                .as_deref()
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                .is_some_and(|t| t.with_timezone(&chrono::Local).date_naive() >= this_month);
+
+            if config.enabled && !ran_this_month {
+                let tracking_id = format!("analytics-archive-{}", chrono::Utc::now().timestamp_millis());
+                let started_at = chrono::Utc::now().to_rfc3339();
+                let name = "Scheduled: Analytics archive";
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &tracking_id, "name": name, "status": "running",
+                    "message": "Archiving old analytics rows", "startedAt": &started_at,
+                }));
+
+                let knowledge_path = app_handle.state::<AppState>().knowledge_path.clone();
+                let (status, message) = match run_archive(&config, &knowledge_path, false).await {
+                    Ok(r) => (
+                        "completed",
+                        format!(
+                            "Archived {} month(s), {} rows",
+                            r.months.len(),
+                            r.months.iter().map(|m| m.row_count).sum::<usize>()
+                        ),
+                    ),
+                    Err(e) => {
+                        eprintln!("[analytics:archive] Scheduled archive failed: {}", e);
+                        // Record the attempt so a failing archive doesn't retry every day
+                        save_state(&ArchiveState {
+                            last_run_at: Some(chrono::Utc::now().to_rfc3339()),
+                            last_error: Some(e.to_string()),
+                        });
+                        ("failed", format!("Analytics archive failed: {}", e))
+                    }
+                };
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &tracking_id, "name": name, "status": status,
+                    "message": message, "startedAt": &started_at,
+                }));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(24 * 3600)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn analytics_archive_get_config() -> CmdResult<ArchiveConfig> {
+    Ok(load_config())
+}
+
+#[tauri::command]
+pub async fn analytics_archive_save_config(
+    state: tauri::State<'_, AppState>,
+    config: ArchiveConfig,
+) -> CmdResult<ArchiveConfig> {
+    // Validate the destination before saving
+    open_store(&config, &state.knowledge_path)?;
+    std::fs::create_dir_all(analytics_dir())?;
+    std::fs::write(analytics_dir().join("archive_config.json"), serde_json::to_string_pretty(&config)?)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn analytics_archive_get_status() -> CmdResult<ArchiveState> {
+    Ok(load_state())
+}
+
+/// Archive and roll up every whole month older than `keep_months`.
+/// `dry_run` reports what would be archived without writing or deleting.
+#[tauri::command]
+pub async fn analytics_archive_run(
+    state: tauri::State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> CmdResult<ArchiveRunResult> {
+    let knowledge_path = state.knowledge_path.clone();
+    run_archive(&load_config(), &knowledge_path, dry_run.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn analytics_archive_list() -> CmdResult<Vec<AnalyticsArchive>> {
+    let client = get_client().await?;
+    client.select("analytics_archives", "order=month.desc").await
+}
+
+/// Re-import an archived month into analytics_page_views. Rollups are left
+/// in place; the archive file is kept so the month can be archived again.
+#[tauri::command]
+pub async fn analytics_archive_restore(
+    state: tauri::State<'_, AppState>,
+    month: NaiveDate,
+) -> CmdResult<RestoreResult> {
+    let _guard = RunGuard::acquire()?;
+    let month = month_start(month);
+    let client = get_client().await?;
+    let archive: AnalyticsArchive = client
+        .select_single("analytics_archives", &format!("month=eq.{}", month))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("No archive for {}", month.format("%Y-%m"))))?;
+
+    let store = open_store(&load_config(), &state.knowledge_path)?;
+    let kind = if store.is_remote() { "s3" } else { "local" };
+    if kind != archive.destination_kind {
+        return Err(CommandError::Config(format!(
+            "{} was archived to {} ({}) but the configured destination is {}",
+            archive.file_name, archive.destination_kind, archive.location, kind
+        )));
+    }
+
+    let scratch = analytics_dir().join("archive-tmp");
+    let path = store.fetch(&archive.file_name, &scratch).await?;
+    let rows = read_jsonl_gz(&path);
+    if store.is_remote() {
+        let _ = std::fs::remove_file(&path);
+    }
+    let rows = rows?;
+
+    upsert_chunks(&client, "analytics_page_views", &rows, PAGE_VIEW_CONFLICT).await?;
+    let _: serde_json::Value = client
+        .update(
+            "analytics_archives",
+            &format!("id=eq.{}", archive.id),
+            &serde_json::json!({ "restored_at": chrono::Utc::now().to_rfc3339() }),
+        )
+        .await?;
+
+    Ok(RestoreResult { month, rows_restored: rows.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(date: &str, page: &str, user: &str, views: i64) -> RawView {
+        RawView {
+            source: "ga4".into(),
+            domain: Some("koi".into()),
+            page_path: page.into(),
+            user_id: Some(user.into()),
+            view_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            views,
+            is_internal: false,
+        }
+    }
+
+    #[test]
+    fn rolls_up_by_period_and_round_trips_archives() {
+        let month = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        // 2025-09-01 is a Monday; 09-03 is in the same week, 09-10 the next
+        let rows = vec![
+            view("2025-09-01", "/home", "a", 2),
+            view("2025-09-03", "/home", "b", 3),
+            view("2025-09-03", "/home", "a", 1),
+            view("2025-09-10", "/home", "a", 4),
+        ];
+        let monthly = rollup_rows(&rows, "month", month);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!((monthly[0].views, monthly[0].unique_users), (10, 2));
+
+        let weekly = rollup_rows(&rows, "week", month);
+        assert_eq!(weekly.iter().map(|r| r.views).collect::<Vec<_>>(), vec![6, 4]);
+
+        // A week starting in August is clipped to the archived month
+        let october = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let clipped = rollup_rows(&[view("2025-10-02", "/x", "a", 1)], "week", october);
+        assert_eq!(clipped[0].period_start, october);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("archive.jsonl.gz");
+        let data = vec![serde_json::json!({"page_path": "/home", "views": 2}), serde_json::json!({"page_path": "/x"})];
+        write_jsonl_gz(&data, &path).unwrap();
+        assert_eq!(read_jsonl_gz(&path).unwrap(), data);
+    }
+}
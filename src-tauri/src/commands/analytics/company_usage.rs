@@ -0,0 +1,330 @@
+// Analytics company usage — product usage per CRM customer.
+//
+// Page views carry the tenant `domain` they were recorded on; the
+// analytics_domain_companies table maps each domain to a crm_companies row so
+// usage can be rolled up per customer for CS and renewal conversations.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::senders::period_days;
+use crate::commands::supabase::get_client;
+
+const TOP_PAGES: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainCompanyMapping {
+    pub id: String,
+    pub domain: String,
+    pub company_id: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageUsage {
+    pub page_path: String,
+    pub views: i64,
+    pub users: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub views: i64,
+    pub users: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanyUsage {
+    pub company_id: String,
+    pub period: String,
+    pub since: NaiveDate,
+    pub domains: Vec<String>,
+    pub total_views: i64,
+    pub active_users: usize,
+    /// Days in the period with at least one view
+    pub active_days: usize,
+    /// Same-length period immediately before `since`
+    pub previous_views: i64,
+    pub previous_active_users: usize,
+    /// None when the previous period had no views
+    pub views_change_pct: Option<f64>,
+    pub last_seen: Option<NaiveDate>,
+    pub top_pages: Vec<PageUsage>,
+    pub daily: Vec<DailyUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmappedDomain {
+    pub domain: String,
+    pub views: i64,
+    pub last_seen: NaiveDate,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageRow {
+    pub page_path: String,
+    pub user_id: Option<String>,
+    pub view_date: NaiveDate,
+    pub views: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct DomainRow {
+    domain: Option<String>,
+    view_date: NaiveDate,
+    views: i32,
+}
+
+pub(super) fn normalize_domain(domain: &str) -> String {
+    domain
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+/// Roll page views up into usage for [since, today], compared against
+/// [prev_since, since)
+pub fn aggregate_usage(rows: &[UsageRow], since: NaiveDate, prev_since: NaiveDate) -> CompanyUsage {
+    let mut total_views = 0i64;
+    let mut users: HashSet<&str> = HashSet::new();
+    let mut previous_views = 0i64;
+    let mut previous_users: HashSet<&str> = HashSet::new();
+    let mut pages: HashMap<&str, (i64, HashSet<&str>)> = HashMap::new();
+    let mut days: BTreeMap<NaiveDate, (i64, HashSet<&str>)> = BTreeMap::new();
+    let mut last_seen: Option<NaiveDate> = None;
+
+    for row in rows {
+        let user = row.user_id.as_deref().filter(|u| !u.is_empty());
+        let views = row.views as i64;
+        if row.view_date >= since {
+            total_views += views;
+            let page = pages.entry(row.page_path.as_str()).or_default();
+            page.0 += views;
+            let day = days.entry(row.view_date).or_default();
+            day.0 += views;
+            if let Some(u) = user {
+                users.insert(u);
+                page.1.insert(u);
+                day.1.insert(u);
+            }
+            last_seen = last_seen.max(Some(row.view_date));
+        } else if row.view_date >= prev_since {
+            previous_views += views;
+            if let Some(u) = user {
+                previous_users.insert(u);
+            }
+        }
+    }
+
+    let mut top_pages: Vec<PageUsage> = pages
+        .into_iter()
+        .map(|(path, (views, users))| PageUsage {
+            page_path: path.to_string(),
+            views,
+            users: users.len(),
+        })
+        .collect();
+    top_pages.sort_by(|a, b| b.views.cmp(&a.views).then_with(|| a.page_path.cmp(&b.page_path)));
+    top_pages.truncate(TOP_PAGES);
+
+    CompanyUsage {
+        company_id: String::new(),
+        period: String::new(),
+        since,
+        domains: Vec::new(),
+        total_views,
+        active_users: users.len(),
+        active_days: days.len(),
+        previous_views,
+        previous_active_users: previous_users.len(),
+        views_change_pct: if previous_views > 0 {
+            Some((total_views - previous_views) as f64 / previous_views as f64 * 100.0)
+        } else {
+            None
+        },
+        last_seen,
+        top_pages,
+        daily: days
+            .into_iter()
+            .map(|(date, (views, users))| DailyUsage {
+                date,
+                views,
+                users: users.len(),
+            })
+            .collect(),
+    }
+}
+
+// ============================================================================
+// Commands — mapping
+// ============================================================================
+
+/// List domain → company mappings, optionally for one company
+#[tauri::command]
+pub async fn analytics_list_domain_mappings(company_id: Option<String>) -> CmdResult<Vec<DomainCompanyMapping>> {
+    let client = get_client().await?;
+    let mut query = "order=domain.asc".to_string();
+    if let Some(id) = company_id {
+        query.push_str(&format!("&company_id=eq.{}", id));
+    }
+    client.select("analytics_domain_companies", &query).await
+}
+
+/// Map an analytics domain to a CRM company (re-mapping moves it)
+#[tauri::command]
+pub async fn analytics_set_domain_mapping(domain: String, company_id: String) -> CmdResult<DomainCompanyMapping> {
+    let domain = normalize_domain(&domain);
+    if domain.is_empty() {
+        return Err(CommandError::Config("Domain is required".to_string()));
+    }
+    let client = get_client().await?;
+    client
+        .upsert_on(
+            "analytics_domain_companies",
+            &serde_json::json!({ "domain": domain, "company_id": company_id }),
+            Some("domain"),
+        )
+        .await
+}
+
+/// Remove a domain mapping
+#[tauri::command]
+pub async fn analytics_remove_domain_mapping(domain: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    client
+        .delete(
+            "analytics_domain_companies",
+            &format!("domain=eq.{}", urlencoding::encode(&normalize_domain(&domain))),
+        )
+        .await
+}
+
+/// Domains with external page views in the last 90 days that no company claims yet
+#[tauri::command]
+pub async fn analytics_list_unmapped_domains() -> CmdResult<Vec<UnmappedDomain>> {
+    let client = get_client().await?;
+    let mapped: HashSet<String> = analytics_list_domain_mappings(None)
+        .await?
+        .into_iter()
+        .map(|m| m.domain)
+        .collect();
+
+    let since = chrono::Local::now().date_naive() - Duration::days(90);
+    let rows: Vec<DomainRow> = client
+        .select_all(
+            "analytics_page_views",
+            &format!(
+                "select=domain,view_date,views&is_internal=eq.false&domain=not.is.null&view_date=gte.{}&order=view_date.asc,source.asc,page_path.asc,user_id.asc",
+                since
+            ),
+        )
+        .await?;
+    let mut totals: HashMap<String, (i64, NaiveDate)> = HashMap::new();
+    for row in rows {
+        let Some(domain) = row.domain.map(|d| normalize_domain(&d)).filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        let entry = totals.entry(domain).or_insert((0, row.view_date));
+        entry.0 += row.views as i64;
+        entry.1 = entry.1.max(row.view_date);
+    }
+
+    let mut unmapped: Vec<UnmappedDomain> = totals
+        .into_iter()
+        .filter(|(domain, _)| !mapped.contains(domain))
+        .map(|(domain, (views, last_seen))| UnmappedDomain { domain, views, last_seen })
+        .collect();
+    unmapped.sort_by(|a, b| b.views.cmp(&a.views));
+    Ok(unmapped)
+}
+
+// ============================================================================
+// Commands — usage
+// ============================================================================
+
+/// Product usage for a CRM company across its mapped domains.
+/// `period` is "week", "month" (default), "quarter" or "year"; external
+/// (non-internal) views only.
+#[tauri::command]
+pub async fn analytics_get_company_usage(company_id: String, period: Option<String>) -> CmdResult<CompanyUsage> {
+    let period = period.unwrap_or_else(|| "month".to_string());
+    let days = period_days(&period)?;
+    let today = chrono::Local::now().date_naive();
+    // Period includes today, so it starts days-1 back
+    let since = today - Duration::days(days - 1);
+    let prev_since = since - Duration::days(days);
+
+    let domains: Vec<String> = analytics_list_domain_mappings(Some(company_id.clone()))
+        .await?
+        .into_iter()
+        .map(|m| m.domain)
+        .collect();
+
+    let mut rows: Vec<UsageRow> = Vec::new();
+    if !domains.is_empty() {
+        let client = get_client().await?;
+        let domain_list = domains
+            .iter()
+            .map(|d| format!("\"{}\"", d))
+            .collect::<Vec<_>>()
+            .join(",");
+        rows = client
+            .select_all(
+                "analytics_page_views",
+                &format!(
+                    "select=page_path,user_id,view_date,views&is_internal=eq.false&domain=in.({})&view_date=gte.{}&order=view_date.asc,source.asc,page_path.asc,user_id.asc",
+                    urlencoding::encode(&domain_list),
+                    prev_since
+                ),
+            )
+            .await?;
+    }
+
+    let mut usage = aggregate_usage(&rows, since, prev_since);
+    usage.company_id = company_id;
+    usage.period = period;
+    usage.domains = domains;
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(path: &str, user: &str, date: NaiveDate, views: i32) -> UsageRow {
+        UsageRow {
+            page_path: path.to_string(),
+            user_id: Some(user.to_string()),
+            view_date: date,
+            views,
+        }
+    }
+
+    #[test]
+    fn aggregates_current_and_previous_periods() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        let rows = vec![
+            row("/home", "a", d(1), 4),
+            row("/home", "a", d(8), 3),
+            row("/reports", "b", d(8), 5),
+            row("/home", "b", d(10), 2),
+            row("/old", "c", d(2), 1),
+        ];
+        let usage = aggregate_usage(&rows, d(8), d(1));
+        assert_eq!(usage.total_views, 10);
+        assert_eq!(usage.active_users, 2);
+        assert_eq!(usage.active_days, 2);
+        assert_eq!(usage.previous_views, 5);
+        assert_eq!(usage.previous_active_users, 2);
+        assert_eq!(usage.views_change_pct, Some(100.0));
+        assert_eq!(usage.last_seen, Some(d(10)));
+        assert_eq!(usage.top_pages[0].page_path, "/home");
+        assert_eq!(usage.top_pages[0].users, 2);
+        assert_eq!(normalize_domain("https://Acme.thinkval.io/"), "acme.thinkval.io");
+    }
+}
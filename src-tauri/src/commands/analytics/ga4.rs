@@ -34,20 +34,20 @@ pub struct Ga4ConfigStatus {
 // GA4 API response
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct Ga4RunReportResponse {
-    rows: Option<Vec<Ga4Row>>,
+pub(super) struct Ga4RunReportResponse {
+    pub(super) rows: Option<Vec<Ga4Row>>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct Ga4Row {
-    dimension_values: Vec<Ga4Value>,
-    metric_values: Vec<Ga4Value>,
+pub(super) struct Ga4Row {
+    pub(super) dimension_values: Vec<Ga4Value>,
+    pub(super) metric_values: Vec<Ga4Value>,
 }
 
 #[derive(Debug, Deserialize)]
-struct Ga4Value {
-    value: String,
+pub(super) struct Ga4Value {
+    pub(super) value: String,
 }
 
 /// Domains to exclude from analytics (internal usage)
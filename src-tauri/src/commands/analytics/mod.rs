@@ -4,9 +4,14 @@
 // AnalyticsPageView structs, which get upserted to the Supabase
 // analytics_page_views table.
 
+pub mod archive;
 pub mod auth;
 pub mod background;
+pub mod company_usage;
 pub mod ga4;
+pub mod realtime;
+pub mod report;
+pub mod retention;
 pub mod types;
 
 use chrono::{NaiveDate, Utc};
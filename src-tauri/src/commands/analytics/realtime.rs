@@ -0,0 +1,185 @@
+// GA4 Realtime — live active-users snapshot for the dashboard widget
+//
+// API: GA4 Data API v1beta runRealtimeReport (last 30 minutes)
+// Snapshots are cached per domain for CACHE_TTL so every open window can
+// poll without each one hitting the API.
+//
+// `domain` is a VAL tenant domain (filtered on the Domain custom dimension),
+// "website" for the website property, or empty for the whole VAL property.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use super::auth;
+use super::ga4::{Ga4Row, Ga4RunReportResponse};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings;
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
+const TOP_LIMIT: usize = 10;
+const DOMAIN_DIMENSION: &str = "customEvent:ua_dimension_1";
+const SOURCE_DIMENSION: &str = "firstUserSource";
+
+/// Held across the fetch so concurrent callers wait for one request instead of racing
+static CACHE: LazyLock<tokio::sync::Mutex<HashMap<String, (Instant, RealtimeSnapshot)>>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealtimeCount {
+    pub name: String,
+    pub active_users: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealtimeSnapshot {
+    pub domain: String,
+    pub active_users: i64,
+    pub top_pages: Vec<RealtimeCount>,
+    pub sources: Vec<RealtimeCount>,
+    pub fetched_at: String,
+    /// Dimensions the property couldn't report (e.g. no domain filter in realtime)
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// GA4 API
+// ============================================================================
+
+/// Run one realtime report. Err(body) when GA4 rejects the request.
+async fn run_realtime(
+    access_token: &str,
+    property_id: &str,
+    dimension: Option<&str>,
+    domain_filter: Option<&str>,
+) -> CmdResult<Result<Vec<Ga4Row>, String>> {
+    let url = format!(
+        "https://analyticsdata.googleapis.com/v1beta/properties/{}:runRealtimeReport",
+        property_id
+    );
+
+    let mut body = serde_json::json!({
+        "metrics": [{ "name": "activeUsers" }],
+        "limit": TOP_LIMIT.to_string(),
+    });
+    if let Some(dim) = dimension {
+        body["dimensions"] = serde_json::json!([{ "name": dim }]);
+        body["orderBys"] = serde_json::json!([{ "metric": { "metricName": "activeUsers" }, "desc": true }]);
+    }
+    if let Some(domain) = domain_filter {
+        body["dimensionFilter"] = serde_json::json!({
+            "filter": {
+                "fieldName": DOMAIN_DIMENSION,
+                "stringFilter": { "matchType": "EXACT", "value": domain, "caseSensitive": false }
+            }
+        });
+    }
+
+    let resp = crate::HTTP_CLIENT
+        .post(&url)
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Ok(Err(resp.text().await.unwrap_or_default()));
+    }
+    let report: Ga4RunReportResponse = resp.json().await?;
+    Ok(Ok(report.rows.unwrap_or_default()))
+}
+
+fn metric(row: &Ga4Row) -> i64 {
+    row.metric_values.first().and_then(|v| v.value.parse().ok()).unwrap_or(0)
+}
+
+fn counts(rows: &[Ga4Row]) -> Vec<RealtimeCount> {
+    rows.iter()
+        .filter_map(|row| {
+            let name = row.dimension_values.first()?.value.clone();
+            Some(RealtimeCount { name, active_users: metric(row) })
+        })
+        .collect()
+}
+
+async fn fetch_snapshot(domain: &str) -> CmdResult<RealtimeSnapshot> {
+    let s = settings::load_settings()?;
+    let (key, filter) = match domain {
+        "website" => (settings::KEY_GA4_WEBSITE_PROPERTY_ID, None),
+        "" => (settings::KEY_GA4_PROPERTY_ID, None),
+        d => (settings::KEY_GA4_PROPERTY_ID, Some(d)),
+    };
+    let property_id = s
+        .keys
+        .get(key)
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| CommandError::Config("GA4 property ID not configured".into()))?
+        .clone();
+    let access_token = auth::get_valid_token().await?;
+    let mut warnings = Vec::new();
+
+    // Realtime only supports a subset of dimensions — fall back to the whole
+    // property if the domain dimension isn't available there
+    let mut filter = filter;
+    let totals = match run_realtime(&access_token, &property_id, None, filter).await? {
+        Ok(rows) => rows,
+        Err(body) if filter.is_some() && body.contains("not a valid dimension") => {
+            warnings.push(format!("Realtime can't filter by domain; showing all of property {}", property_id));
+            filter = None;
+            run_realtime(&access_token, &property_id, None, None)
+                .await?
+                .map_err(|b| CommandError::Network(format!("GA4 realtime error: {}", b)))?
+        }
+        Err(body) => return Err(CommandError::Network(format!("GA4 realtime error: {}", body))),
+    };
+
+    let top_pages = run_realtime(&access_token, &property_id, Some("unifiedScreenName"), filter)
+        .await?
+        .map_err(|b| CommandError::Network(format!("GA4 realtime error: {}", b)))?;
+
+    let sources = match run_realtime(&access_token, &property_id, Some(SOURCE_DIMENSION), filter).await? {
+        Ok(rows) => counts(&rows),
+        Err(_) => {
+            warnings.push("Traffic sources aren't available in realtime for this property".to_string());
+            Vec::new()
+        }
+    };
+
+    Ok(RealtimeSnapshot {
+        domain: domain.to_string(),
+        active_users: totals.first().map(metric).unwrap_or(0),
+        top_pages: counts(&top_pages),
+        sources,
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+        warnings,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Active users, top pages and sources over the last 30 minutes (cached 60s)
+#[command]
+pub async fn analytics_get_realtime(domain: String) -> CmdResult<RealtimeSnapshot> {
+    let domain = domain.trim().to_lowercase();
+    let mut cache = CACHE.lock().await;
+    if let Some((at, snapshot)) = cache.get(&domain) {
+        if at.elapsed() < CACHE_TTL {
+            return Ok(snapshot.clone());
+        }
+    }
+    let snapshot = fetch_snapshot(&domain).await?;
+    cache.retain(|_, (at, _)| at.elapsed() < CACHE_TTL);
+    cache.insert(domain, (Instant::now(), snapshot.clone()));
+    Ok(snapshot)
+}
@@ -0,0 +1,438 @@
+// Analytics dashboard snapshot — a branded PDF for the monthly management pack.
+//
+// One domain's external page views for the period (and the period before it,
+// for comparison) are rolled up with the company-usage aggregation, the daily
+// trend and top pages are drawn as inline SVG, days that break sharply from
+// their trailing baseline are flagged as anomalies, and the whole report is
+// rendered through the docgen Chrome pipeline with the resolved theme.
+
+use chrono::{Duration, NaiveDate};
+use std::path::Path;
+
+use super::company_usage::{aggregate_usage, normalize_domain, CompanyUsage, UsageRow};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+use crate::commands::tools::docgen::{generate_proposal_pdf, markdown_table_cell};
+use crate::commands::tools::docgen_theme::{escape_html, resolve_theme};
+use crate::commands::work::status_report::{resolve_period, ReportPeriod};
+
+/// Trailing days a day is compared against when looking for anomalies
+const BASELINE_DAYS: usize = 14;
+/// Fewer baseline days than this and a day isn't judged at all
+const MIN_BASELINE_DAYS: usize = 7;
+/// Standard deviations from the baseline mean that count as unusual
+const ANOMALY_Z: f64 = 2.5;
+/// ...and the absolute swing in views, so a quiet site going 1 → 4 isn't flagged
+const MIN_ANOMALY_DELTA: f64 = 10.0;
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 200.0;
+const BAR_ROW_HEIGHT: f64 = 22.0;
+const BRAND: &str = "var(--brand-primary, #00A0E3)";
+
+#[derive(Debug, Clone, PartialEq)]
+struct TrafficAnomaly {
+    date: NaiveDate,
+    views: i64,
+    /// Trailing baseline mean
+    expected: f64,
+    z_score: f64,
+}
+
+/// Views per day in [from, to], zero-filled
+fn daily_series(rows: &[UsageRow], from: NaiveDate, to: NaiveDate) -> Vec<(NaiveDate, i64)> {
+    let days = (to - from).num_days().max(-1) + 1;
+    let mut series: Vec<(NaiveDate, i64)> = (0..days).map(|i| (from + Duration::days(i), 0)).collect();
+    for row in rows {
+        let i = (row.view_date - from).num_days();
+        if (0..days).contains(&i) {
+            series[i as usize].1 += row.views as i64;
+        }
+    }
+    series
+}
+
+/// Days from `since` on whose views sit more than ANOMALY_Z standard
+/// deviations from the mean of the BASELINE_DAYS before them
+fn detect_anomalies(series: &[(NaiveDate, i64)], since: NaiveDate) -> Vec<TrafficAnomaly> {
+    series
+        .iter()
+        .enumerate()
+        .filter(|(i, (date, _))| *date >= since && *i >= MIN_BASELINE_DAYS)
+        .filter_map(|(i, (date, views))| {
+            let window = &series[i.saturating_sub(BASELINE_DAYS)..i];
+            let n = window.len() as f64;
+            let mean = window.iter().map(|(_, v)| *v as f64).sum::<f64>() / n;
+            let variance = window.iter().map(|(_, v)| (*v as f64 - mean).powi(2)).sum::<f64>() / n;
+            // A perfectly flat baseline would make any change infinitely unusual
+            let std_dev = variance.sqrt().max(1.0);
+            let delta = *views as f64 - mean;
+            let z_score = delta / std_dev;
+            (z_score.abs() >= ANOMALY_Z && delta.abs() >= MIN_ANOMALY_DELTA).then_some(TrafficAnomaly {
+                date: *date,
+                views: *views,
+                expected: mean,
+                z_score,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn pretty_date(d: NaiveDate) -> String {
+    d.format("%-d %b %Y").to_string()
+}
+
+/// 12345 → "12,345"
+fn thousands(n: i64) -> String {
+    let digits = n.abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if n < 0 {
+        out.insert(0, '-');
+    }
+    out
+}
+
+fn change(current: i64, previous: i64) -> String {
+    if previous == 0 {
+        return "—".to_string();
+    }
+    format!("{:+.1}%", (current - previous) as f64 / previous as f64 * 100.0)
+}
+
+/// Round an axis maximum up to 1, 2 or 5 × a power of ten
+fn nice_ceiling(value: f64) -> f64 {
+    if value <= 1.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powi(value.log10().floor() as i32);
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|step| step * magnitude)
+        .find(|c| *c >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Daily views line chart; the previous period is overlaid dashed (aligned by
+/// day) and anomalies are ringed. Kept free of blank lines so markdown passes
+/// it through as one HTML block.
+fn trend_svg(current: &[(NaiveDate, i64)], previous: &[(NaiveDate, i64)], anomalies: &[TrafficAnomaly]) -> String {
+    let (left, right, top, bottom) = (48.0, 10.0, 10.0, 24.0);
+    let plot_w = CHART_WIDTH - left - right;
+    let plot_h = CHART_HEIGHT - top - bottom;
+    let peak = current.iter().chain(previous).map(|(_, v)| *v).max().unwrap_or(0);
+    let max = nice_ceiling(peak as f64);
+    let step = plot_w / (current.len().max(2) - 1) as f64;
+    let x = |i: usize| left + i as f64 * step;
+    let y = |v: i64| top + plot_h * (1.0 - v as f64 / max);
+    let points = |series: &[(NaiveDate, i64)]| {
+        series
+            .iter()
+            .take(current.len())
+            .enumerate()
+            .map(|(i, (_, v))| format!("{:.1},{:.1}", x(i), y(*v)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut svg = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="100%" font-size="9" font-family="inherit">"#,
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT
+    )];
+    for fraction in [0.0, 0.5, 1.0] {
+        let gy = top + plot_h * (1.0 - fraction);
+        svg.push(format!(
+            r##"<line x1="{left}" y1="{gy:.1}" x2="{x2}" y2="{gy:.1}" stroke="#e2e8f0" stroke-width="1"/><text x="{tx}" y="{ty:.1}" text-anchor="end" fill="#64748b">{label}</text>"##,
+            x2 = CHART_WIDTH - right,
+            tx = left - 6.0,
+            ty = gy + 3.0,
+            label = thousands((max * fraction) as i64),
+        ));
+    }
+    if !previous.is_empty() {
+        svg.push(format!(
+            r##"<polyline points="{}" fill="none" stroke="#94a3b8" stroke-width="1.2" stroke-dasharray="4 3"/>"##,
+            points(previous)
+        ));
+    }
+    if let (Some((first, _)), Some((last, _))) = (current.first(), current.last()) {
+        svg.push(format!(
+            r#"<polyline points="{}" fill="none" style="stroke: {}" stroke-width="2"/>"#,
+            points(current),
+            BRAND
+        ));
+        svg.push(format!(
+            r##"<text x="{left}" y="{ty}" fill="#64748b">{}</text><text x="{ex}" y="{ty}" text-anchor="end" fill="#64748b">{}</text>"##,
+            first.format("%-d %b"),
+            last.format("%-d %b"),
+            ty = CHART_HEIGHT - 6.0,
+            ex = CHART_WIDTH - right,
+        ));
+    }
+    for anomaly in anomalies {
+        if let Some(i) = current.iter().position(|(d, _)| *d == anomaly.date) {
+            let color = if anomaly.z_score > 0.0 { "#dc2626" } else { "#d97706" };
+            svg.push(format!(
+                r#"<circle cx="{:.1}" cy="{:.1}" r="5" fill="none" stroke="{}" stroke-width="2"/>"#,
+                x(i),
+                y(anomaly.views),
+                color
+            ));
+        }
+    }
+    svg.push("</svg>".to_string());
+    svg.join("\n")
+}
+
+/// Horizontal bars for the top pages, longest first
+fn top_pages_svg(usage: &CompanyUsage) -> String {
+    let label_w = 250.0;
+    let value_w = 60.0;
+    let bar_w = CHART_WIDTH - label_w - value_w;
+    let max = usage.top_pages.first().map(|p| p.views).unwrap_or(1).max(1) as f64;
+    let height = usage.top_pages.len() as f64 * BAR_ROW_HEIGHT + 4.0;
+
+    let mut svg = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {height}" width="100%" font-size="9" font-family="inherit">"#,
+        w = CHART_WIDTH,
+    )];
+    for (i, page) in usage.top_pages.iter().enumerate() {
+        let row_y = i as f64 * BAR_ROW_HEIGHT + 2.0;
+        let path: String = if page.page_path.chars().count() > 44 {
+            format!("{}…", page.page_path.chars().take(43).collect::<String>())
+        } else {
+            page.page_path.clone()
+        };
+        let width = (bar_w - 8.0) * page.views as f64 / max;
+        svg.push(format!(
+            r##"<text x="0" y="{ty:.1}" fill="#1e293b">{path}</text><rect x="{label_w}" y="{row_y:.1}" width="{width:.1}" height="{bh:.1}" rx="2" style="fill: {BRAND}"/><text x="{vx:.1}" y="{ty:.1}" fill="#64748b">{views}</text>"##,
+            ty = row_y + BAR_ROW_HEIGHT / 2.0 + 2.0,
+            path = escape_html(&path),
+            bh = BAR_ROW_HEIGHT - 6.0,
+            vx = label_w + width + 6.0,
+            views = thousands(page.views),
+        ));
+    }
+    svg.push("</svg>".to_string());
+    svg.join("\n")
+}
+
+/// Build the report markdown (charts are inline HTML, which docgen passes through)
+fn compose_dashboard_report(
+    domain: &str,
+    usage: &CompanyUsage,
+    current: &[(NaiveDate, i64)],
+    previous: &[(NaiveDate, i64)],
+    anomalies: &[TrafficAnomaly],
+    period: &ReportPeriod,
+    today: NaiveDate,
+) -> String {
+    let period_days = current.len();
+    let mut md = format!("# {}\n\n## Analytics Snapshot — {}\n\n", domain, period.label);
+    md.push_str(&format!(
+        "**Period:** {} – {}  \n**Generated:** {}  \n**Scope:** external page views only\n",
+        pretty_date(period.start),
+        pretty_date(period.end),
+        pretty_date(today),
+    ));
+
+    md.push_str("\n## Summary\n\n| Metric | This period | Previous period | Change |\n|---|---|---|---|\n");
+    md.push_str(&format!(
+        "| Page views | {} | {} | {} |\n",
+        thousands(usage.total_views),
+        thousands(usage.previous_views),
+        change(usage.total_views, usage.previous_views)
+    ));
+    md.push_str(&format!(
+        "| Active users | {} | {} | {} |\n",
+        usage.active_users,
+        usage.previous_active_users,
+        change(usage.active_users as i64, usage.previous_active_users as i64)
+    ));
+    md.push_str(&format!("| Active days | {} of {} | | |\n", usage.active_days, period_days));
+    if let Some(last) = usage.last_seen {
+        md.push_str(&format!("| Last activity | {} | | |\n", pretty_date(last)));
+    }
+
+    md.push_str("\n## Traffic Trend\n\n");
+    if usage.total_views == 0 {
+        md.push_str("No external page views were recorded in this period.\n");
+    } else {
+        md.push_str(&format!("<div class=\"chart\">\n{}\n</div>\n\n", trend_svg(current, previous, anomalies)));
+        md.push_str(
+            "Daily page views. The dashed line is the previous period, day for day; \
+             ringed points are anomalies (red: spike, amber: drop).\n",
+        );
+    }
+
+    md.push_str("\n## Top Pages\n\n");
+    if usage.top_pages.is_empty() {
+        md.push_str("_No page views in this period._\n");
+    } else {
+        md.push_str(&format!("<div class=\"chart\">\n{}\n</div>\n\n", top_pages_svg(usage)));
+        md.push_str("| # | Page | Views | Users | Share |\n|---|---|---|---|---|\n");
+        for (i, page) in usage.top_pages.iter().enumerate() {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {:.1}% |\n",
+                i + 1,
+                markdown_table_cell(&page.page_path),
+                thousands(page.views),
+                page.users,
+                page.views as f64 / usage.total_views.max(1) as f64 * 100.0
+            ));
+        }
+    }
+
+    md.push_str("\n## Anomalies\n\n");
+    if anomalies.is_empty() {
+        md.push_str(&format!(
+            "No unusual days: daily views stayed within {} standard deviations of the trailing {}-day baseline.\n",
+            ANOMALY_Z, BASELINE_DAYS
+        ));
+    } else {
+        md.push_str("| Date | Views | Expected | Change | Type |\n|---|---|---|---|---|\n");
+        for a in anomalies {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                pretty_date(a.date),
+                thousands(a.views),
+                thousands(a.expected.round() as i64),
+                if a.expected >= 1.0 {
+                    format!("{:+.0}%", (a.views as f64 - a.expected) / a.expected * 100.0)
+                } else {
+                    "—".to_string()
+                },
+                if a.z_score > 0.0 { "Spike" } else { "Drop" }
+            ));
+        }
+        md.push_str(&format!(
+            "\nA day is flagged when its views sit at least {} standard deviations from the mean of the {} days before it.\n",
+            ANOMALY_Z, BASELINE_DAYS
+        ));
+    }
+    md
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Render a domain's analytics dashboard (summary, trend, top pages,
+/// anomalies) to a branded PDF at `output_path`. `period` is "week", "month"
+/// (default), "quarter" or "YYYY-MM"; external (non-internal) views only.
+/// Returns the PDF path.
+#[tauri::command]
+pub async fn analytics_export_dashboard_pdf(
+    domain: String,
+    period: Option<String>,
+    output_path: String,
+) -> CmdResult<String> {
+    let domain = normalize_domain(&domain);
+    if domain.is_empty() {
+        return Err(CommandError::Config("Domain is required".to_string()));
+    }
+    let today = chrono::Local::now().date_naive();
+    let period = resolve_period(period.as_deref().unwrap_or("month"), today)?;
+    let days = (period.end - period.start).num_days() + 1;
+    let prev_start = period.start - Duration::days(days);
+    // Enough history for both the comparison period and the anomaly baseline
+    let fetch_from = prev_start.min(period.start - Duration::days(BASELINE_DAYS as i64));
+
+    let client = get_client().await?;
+    let rows: Vec<UsageRow> = client
+        .select_all(
+            "analytics_page_views",
+            &format!(
+                "select=page_path,user_id,view_date,views&is_internal=eq.false&domain=eq.{}&view_date=gte.{}&view_date=lte.{}&order=view_date.asc,source.asc,page_path.asc,user_id.asc",
+                urlencoding::encode(&domain),
+                fetch_from,
+                period.end
+            ),
+        )
+        .await?;
+
+    let usage = aggregate_usage(&rows, period.start, prev_start);
+    let history = daily_series(&rows, fetch_from, period.end);
+    let anomalies = detect_anomalies(&history, period.start);
+    let current = daily_series(&rows, period.start, period.end);
+    let previous = daily_series(&rows, prev_start, period.start - Duration::days(1));
+    let markdown = compose_dashboard_report(&domain, &usage, &current, &previous, &anomalies, &period, today);
+
+    if let Some(dir) = Path::new(&output_path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let theme = resolve_theme(Some(Path::new(&output_path)));
+    generate_proposal_pdf(&markdown, &output_path, &theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 9, day).unwrap()
+    }
+
+    fn row(path: &str, date: NaiveDate, views: i32) -> UsageRow {
+        UsageRow {
+            page_path: path.to_string(),
+            user_id: Some("u1".to_string()),
+            view_date: date,
+            views,
+        }
+    }
+
+    #[test]
+    fn flags_spikes_and_drops_against_trailing_baseline() {
+        // Steady ~100/day with an outage on the 16th and a spike on the 24th
+        let rows: Vec<UsageRow> = (1..=30)
+            .filter(|day| *day != 16)
+            .map(|day| row("/home", d(day), if day == 24 { 400 } else { 100 + (day % 3) as i32 }))
+            .collect();
+
+        let series = daily_series(&rows, d(1), d(30));
+        assert_eq!(series.len(), 30);
+        assert_eq!(series[15], (d(16), 0));
+
+        let anomalies = detect_anomalies(&series, d(15));
+        let flagged: Vec<(NaiveDate, bool)> = anomalies.iter().map(|a| (a.date, a.z_score > 0.0)).collect();
+        assert_eq!(flagged, vec![(d(16), false), (d(24), true)]);
+        assert!((anomalies[0].expected - 101.0).abs() < 1.0);
+
+        // Small absolute swings on a quiet site aren't anomalies
+        let quiet: Vec<(NaiveDate, i64)> = (1..=20).map(|day| (d(day), if day == 18 { 6 } else { 1 })).collect();
+        assert!(detect_anomalies(&quiet, d(10)).is_empty());
+    }
+
+    #[test]
+    fn composes_report_with_inline_charts() {
+        let rows = vec![row("/home", d(1), 40), row("/reports|q3", d(2), 10), row("/home", d(9), 30)];
+        let usage = aggregate_usage(&rows, d(8), d(1));
+        let period = ReportPeriod { start: d(8), end: d(14), label: "Last 7 days".to_string() };
+        let current = daily_series(&rows, d(8), d(14));
+        let previous = daily_series(&rows, d(1), d(7));
+        let md = compose_dashboard_report("acme.thinkval.io", &usage, &current, &previous, &[], &period, d(15));
+
+        assert!(md.starts_with("# acme.thinkval.io\n"));
+        assert!(md.contains("| Page views | 30 | 50 | -40.0% |"));
+        assert!(md.contains("| Active days | 1 of 7 | | |"));
+        assert!(md.contains("No unusual days"));
+        // Each chart must stay a single HTML block for the markdown renderer
+        let chart = trend_svg(&current, &previous, &[]);
+        assert!(chart.starts_with("<svg") && !chart.contains("\n\n"));
+
+        assert_eq!(thousands(1234567), "1,234,567");
+        assert_eq!(nice_ceiling(430.0), 500.0);
+        assert_eq!(markdown_table_cell("/reports|q3"), "/reports\\|q3");
+    }
+}
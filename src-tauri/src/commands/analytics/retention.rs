@@ -0,0 +1,277 @@
+// Analytics retention — cohort matrices from analytics_page_views user IDs.
+//
+// A user's cohort is the period (week or month) of their first recorded view.
+// Cell [cohort][n] counts cohort users seen again n periods later. Results are
+// cached per (domain, period, window) for the day under ~/.tv-client/analytics.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionCohort {
+    pub cohort_start: NaiveDate,
+    pub size: usize,
+    /// Users active at period offset 0, 1, 2... (offset 0 == size)
+    pub retained: Vec<usize>,
+    /// retained / size, same offsets
+    pub rates: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionMatrix {
+    pub domain: Option<String>,
+    pub cohort_period: String,
+    pub window: usize,
+    pub computed_at: String,
+    pub total_users: usize,
+    pub cohorts: Vec<RetentionCohort>,
+    /// Size-weighted average rate per offset across cohorts that reached it
+    pub average_rates: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewRow {
+    user_id: Option<String>,
+    view_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CohortPeriod {
+    Week,
+    Month,
+}
+
+impl CohortPeriod {
+    fn parse(s: &str) -> CmdResult<Self> {
+        match s {
+            "week" | "weekly" => Ok(Self::Week),
+            "month" | "monthly" => Ok(Self::Month),
+            other => Err(CommandError::Config(format!(
+                "Unknown cohort period: {} (expected week or month)",
+                other
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// First day of the period containing `date` (weeks start Monday)
+    fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Whole periods from `from` to `to` (both period starts)
+    fn offset(self, from: NaiveDate, to: NaiveDate) -> i64 {
+        match self {
+            Self::Week => (to - from).num_days() / 7,
+            Self::Month => (to.year() as i64 * 12 + to.month() as i64) - (from.year() as i64 * 12 + from.month() as i64),
+        }
+    }
+
+    /// Start of the period `n` periods before the one containing `date`
+    fn periods_back(self, date: NaiveDate, n: usize) -> NaiveDate {
+        let start = self.start_of(date);
+        match self {
+            Self::Week => start - chrono::Duration::weeks(n as i64),
+            Self::Month => start
+                .checked_sub_months(chrono::Months::new(n as u32))
+                .unwrap_or(start),
+        }
+    }
+}
+
+/// Build the cohort matrix for the last `window` periods ending at `today`.
+/// `views` may include history before the window — it's used to find each
+/// user's true first period so returning users aren't counted as new.
+pub fn compute_retention(
+    views: &[(String, NaiveDate)],
+    period: CohortPeriod,
+    window: usize,
+    today: NaiveDate,
+) -> (Vec<RetentionCohort>, Vec<f64>) {
+    let current = period.start_of(today);
+    let earliest = period.periods_back(today, window.saturating_sub(1));
+
+    let mut first_seen: HashMap<&str, NaiveDate> = HashMap::new();
+    let mut active: HashMap<&str, HashSet<NaiveDate>> = HashMap::new();
+    for (user, date) in views {
+        let p = period.start_of(*date);
+        first_seen
+            .entry(user.as_str())
+            .and_modify(|d| *d = (*d).min(p))
+            .or_insert(p);
+        active.entry(user.as_str()).or_default().insert(p);
+    }
+
+    let mut by_cohort: BTreeMap<NaiveDate, Vec<&str>> = BTreeMap::new();
+    for (user, first) in &first_seen {
+        if *first >= earliest && *first <= current {
+            by_cohort.entry(*first).or_default().push(*user);
+        }
+    }
+
+    let cohorts: Vec<RetentionCohort> = by_cohort
+        .into_iter()
+        .map(|(cohort_start, users)| {
+            let offsets = period.offset(cohort_start, current) as usize + 1;
+            let mut retained = vec![0usize; offsets];
+            for user in &users {
+                for p in &active[user] {
+                    let n = period.offset(cohort_start, *p);
+                    if n >= 0 && (n as usize) < offsets {
+                        retained[n as usize] += 1;
+                    }
+                }
+            }
+            let size = users.len();
+            let rates = retained.iter().map(|r| *r as f64 / size as f64).collect();
+            RetentionCohort { cohort_start, size, retained, rates }
+        })
+        .collect();
+
+    let max_offsets = cohorts.iter().map(|c| c.retained.len()).max().unwrap_or(0);
+    let average_rates = (0..max_offsets)
+        .map(|n| {
+            let (kept, total) = cohorts
+                .iter()
+                .filter(|c| c.retained.len() > n)
+                .fold((0usize, 0usize), |(k, t), c| (k + c.retained[n], t + c.size));
+            if total == 0 { 0.0 } else { kept as f64 / total as f64 }
+        })
+        .collect();
+
+    (cohorts, average_rates)
+}
+
+fn cache_path(domain: Option<&str>, period: CohortPeriod, window: usize) -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("analytics")
+        .join("retention")
+        .join(format!("{}_{}_{}.json", domain.unwrap_or("all"), period.name(), window))
+}
+
+fn read_cache(path: &Path, today: NaiveDate) -> Option<RetentionMatrix> {
+    let cached: RetentionMatrix = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    let computed = chrono::DateTime::parse_from_rfc3339(&cached.computed_at).ok()?;
+    (computed.with_timezone(&chrono::Local).date_naive() == today).then_some(cached)
+}
+
+async fn load_views(domain: Option<&str>) -> CmdResult<Vec<(String, NaiveDate)>> {
+    let client = get_client().await?;
+    let mut filter = "select=user_id,view_date&is_internal=eq.false&user_id=neq.&order=view_date.asc,source.asc,page_path.asc,user_id.asc".to_string();
+    if let Some(d) = domain {
+        filter.push_str(&format!("&domain=eq.{}", d));
+    }
+
+    let rows: Vec<ViewRow> = client.select_all("analytics_page_views", &filter).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| r.user_id.filter(|u| !u.is_empty()).map(|u| (u, r.view_date)))
+        .collect())
+}
+
+/// Compute a retention matrix from page-view user IDs.
+///
+/// `cohort_period` is "week" (default) or "month"; `window` is how many
+/// cohorts to include (default 8 weeks / 6 months). Cached for the day
+/// unless `refresh` is set.
+#[tauri::command]
+pub async fn analytics_compute_retention(
+    domain: Option<String>,
+    cohort_period: Option<String>,
+    window: Option<usize>,
+    refresh: Option<bool>,
+) -> CmdResult<RetentionMatrix> {
+    let period = CohortPeriod::parse(cohort_period.as_deref().unwrap_or("week"))?;
+    let window = window
+        .unwrap_or(match period {
+            CohortPeriod::Week => 8,
+            CohortPeriod::Month => 6,
+        })
+        .clamp(1, 52);
+    let domain = domain.filter(|d| !d.is_empty() && d != "all");
+    let today = chrono::Local::now().date_naive();
+
+    let path = cache_path(domain.as_deref(), period, window);
+    if !refresh.unwrap_or(false) {
+        if let Some(cached) = read_cache(&path, today) {
+            return Ok(cached);
+        }
+    }
+
+    let views = load_views(domain.as_deref()).await?;
+    let total_users = views.iter().map(|(u, _)| u.as_str()).collect::<HashSet<_>>().len();
+    let (cohorts, average_rates) = compute_retention(&views, period, window, today);
+
+    let matrix = RetentionMatrix {
+        domain,
+        cohort_period: period.name().to_string(),
+        window,
+        computed_at: chrono::Utc::now().to_rfc3339(),
+        total_users,
+        cohorts,
+        average_rates,
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&matrix)?)?;
+
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn weekly_cohorts_use_first_seen_and_offsets() {
+        // 2026-10-05 and 2026-10-12 are Mondays
+        let views = vec![
+            ("a".to_string(), d(2026, 10, 6)),
+            ("a".to_string(), d(2026, 10, 14)),
+            ("b".to_string(), d(2026, 10, 7)),
+            ("c".to_string(), d(2026, 10, 13)),
+            // Returning user first seen long ago — not a new cohort member
+            ("old".to_string(), d(2026, 1, 5)),
+            ("old".to_string(), d(2026, 10, 13)),
+        ];
+        let (cohorts, avg) = compute_retention(&views, CohortPeriod::Week, 2, d(2026, 10, 16));
+
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!(cohorts[0].cohort_start, d(2026, 10, 5));
+        assert_eq!(cohorts[0].size, 2);
+        assert_eq!(cohorts[0].retained, vec![2, 1]);
+        assert_eq!(cohorts[1].cohort_start, d(2026, 10, 12));
+        assert_eq!(cohorts[1].retained, vec![1]);
+        assert_eq!(avg, vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn monthly_offsets_cross_year() {
+        let p = CohortPeriod::Month;
+        assert_eq!(p.offset(d(2025, 11, 1), d(2026, 2, 1)), 3);
+        assert_eq!(p.periods_back(d(2026, 1, 20), 2), d(2025, 11, 1));
+    }
+}
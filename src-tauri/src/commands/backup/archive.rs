@@ -0,0 +1,178 @@
+// Zip snapshot creation / extraction + retention selection
+
+use crate::commands::error::{CmdResult, CommandError};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::types::RetentionPolicy;
+
+pub const SNAPSHOT_PREFIX: &str = "knowledge-";
+const SNAPSHOT_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+/// Report progress every N files
+const PROGRESS_EVERY: usize = 50;
+
+fn zip_err(e: zip::result::ZipError) -> CommandError {
+    CommandError::Io(format!("Zip error: {}", e))
+}
+
+// ============================================================================
+// Naming + retention
+// ============================================================================
+
+pub fn snapshot_id(at: DateTime<Utc>) -> String {
+    format!("{}{}.zip", SNAPSHOT_PREFIX, at.format(SNAPSHOT_TIME_FORMAT))
+}
+
+/// Creation time encoded in a snapshot id (None for files we didn't name)
+pub fn parse_snapshot_time(id: &str) -> Option<DateTime<Utc>> {
+    let stamp = id.strip_prefix(SNAPSHOT_PREFIX)?.strip_suffix(".zip")?;
+    NaiveDateTime::parse_from_str(stamp, SNAPSHOT_TIME_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Snapshot ids the policy says to delete. The newest `keep_last` (at least
+/// one) are always kept; older ones go once they pass `max_age_days`.
+/// Ids that don't parse as snapshots are never touched.
+pub fn select_expired(ids: &[String], policy: &RetentionPolicy, now: DateTime<Utc>) -> Vec<String> {
+    let mut dated: Vec<(DateTime<Utc>, &String)> = ids
+        .iter()
+        .filter_map(|id| parse_snapshot_time(id).map(|t| (t, id)))
+        .collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let keep = policy.keep_last.max(1);
+    dated
+        .into_iter()
+        .skip(keep)
+        .filter(|(t, _)| match policy.max_age_days {
+            Some(days) => now - *t > chrono::Duration::days(days),
+            None => true,
+        })
+        .map(|(_, id)| id.clone())
+        .collect()
+}
+
+// ============================================================================
+// Archive
+// ============================================================================
+
+/// Files under `source`, skipping any entry whose name is in `exclude`
+fn collect_files(source: &Path, exclude: &[String]) -> Vec<PathBuf> {
+    WalkDir::new(source)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !exclude.iter().any(|x| e.file_name().to_string_lossy() == x.as_str()))
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Zip `source` into `dest`. `progress(done, total)` is called as files are added.
+/// Returns (file count, uncompressed bytes).
+pub fn create_snapshot(
+    source: &Path,
+    dest: &Path,
+    exclude: &[String],
+    progress: impl Fn(usize, usize),
+) -> CmdResult<(usize, u64)> {
+    let files = collect_files(source, exclude);
+    let total = files.len();
+    progress(0, total);
+
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut zip = zip::ZipWriter::new(fs::File::create(dest)?);
+    let mut bytes = 0u64;
+
+    for (i, path) in files.iter().enumerate() {
+        let rel = path
+            .strip_prefix(source)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        // Files can vanish mid-walk (editors, sync clients) — skip rather than fail
+        let Ok(mut file) = fs::File::open(path) else {
+            continue;
+        };
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(size >= u32::MAX as u64);
+        zip.start_file(rel.as_str(), options).map_err(zip_err)?;
+        bytes += io::copy(&mut file, &mut zip)?;
+
+        if (i + 1) % PROGRESS_EVERY == 0 || i + 1 == total {
+            progress(i + 1, total);
+        }
+    }
+
+    zip.finish().map_err(zip_err)?;
+    Ok((total, bytes))
+}
+
+/// Extract a snapshot into `target`. Entries that would escape the target
+/// (absolute paths, `..`) are skipped. Returns files written.
+pub fn extract_snapshot(zip_path: &Path, target: &Path, progress: impl Fn(usize, usize)) -> CmdResult<usize> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(zip_path)?).map_err(zip_err)?;
+    let total = archive.len();
+    let mut written = 0;
+    progress(0, total);
+
+    for i in 0..total {
+        let mut entry = archive.by_index(i).map_err(zip_err)?;
+        let Some(rel) = entry.enclosed_name() else {
+            continue;
+        };
+        let out = target.join(rel);
+        if entry.is_dir() {
+            fs::create_dir_all(&out)?;
+        } else {
+            if let Some(dir) = out.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            io::copy(&mut entry, &mut fs::File::create(&out)?)?;
+            written += 1;
+        }
+
+        if (i + 1) % PROGRESS_EVERY == 0 || i + 1 == total {
+            progress(i + 1, total);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn retention_keeps_newest_and_prunes_by_age() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap();
+        let ids: Vec<String> = [
+            now - chrono::Duration::days(1),
+            now - chrono::Duration::days(2),
+            now - chrono::Duration::days(40),
+            now - chrono::Duration::days(100),
+            now - chrono::Duration::days(200),
+        ]
+        .into_iter()
+        .map(snapshot_id)
+        .chain(std::iter::once("notes.zip".to_string()))
+        .collect();
+
+        let by_age = RetentionPolicy { keep_last: 2, max_age_days: Some(90) };
+        assert_eq!(select_expired(&ids, &by_age, now), vec![ids[3].clone(), ids[4].clone()]);
+
+        let by_count = RetentionPolicy { keep_last: 3, max_age_days: None };
+        assert_eq!(select_expired(&ids, &by_count, now), vec![ids[3].clone(), ids[4].clone()]);
+
+        let keep_zero = RetentionPolicy { keep_last: 0, max_age_days: Some(0) };
+        assert_eq!(select_expired(&ids, &keep_zero, now).len(), 4);
+    }
+}
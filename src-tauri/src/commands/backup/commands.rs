@@ -0,0 +1,309 @@
+// Backup commands + background schedule
+// Config and last-run state live in ~/.tv-client/backup/. Scheduled runs
+// report through "jobs:update"; all runs emit "backup:progress".
+
+use cron::Schedule;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{Emitter, Manager};
+
+use super::archive::{create_snapshot, extract_snapshot, select_expired, snapshot_id};
+use super::store::Store;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::scheduler::background::is_due;
+use crate::AppState;
+
+/// Only one backup or restore at a time
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+struct RunGuard;
+
+impl RunGuard {
+    fn acquire() -> CmdResult<Self> {
+        if RUNNING.swap(true, Ordering::SeqCst) {
+            return Err(CommandError::Internal("A backup or restore is already running".to_string()));
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+// ============================================================================
+// Config + state
+// ============================================================================
+
+fn backup_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("backup")
+}
+
+fn load_config() -> BackupConfig {
+    std::fs::read_to_string(backup_dir().join("config.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn load_state() -> BackupState {
+    std::fs::read_to_string(backup_dir().join("state.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &BackupState) {
+    let _ = std::fs::create_dir_all(backup_dir());
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(backup_dir().join("state.json"), json);
+    }
+}
+
+fn emit_progress(app_handle: &tauri::AppHandle, phase: &str, current: u64, total: u64, message: &str) {
+    let _ = app_handle.emit(
+        "backup:progress",
+        BackupProgress {
+            phase: phase.to_string(),
+            current,
+            total,
+            message: message.to_string(),
+        },
+    );
+}
+
+// ============================================================================
+// Run / restore
+// ============================================================================
+
+async fn run_backup(app_handle: &tauri::AppHandle, knowledge_path: &str) -> CmdResult<BackupRunResult> {
+    let _guard = RunGuard::acquire()?;
+    let config = load_config();
+    let source = PathBuf::from(knowledge_path);
+    if knowledge_path.is_empty() || !source.is_dir() {
+        return Err(CommandError::Config("Knowledge path is not set or does not exist".to_string()));
+    }
+    let store = Store::from_config(&config.destination)?;
+
+    let now = chrono::Utc::now();
+    let id = snapshot_id(now);
+    let tmp = backup_dir().join("tmp").join(&id);
+
+    emit_progress(app_handle, "scan", 0, 0, "Scanning knowledge folder");
+    let (file_count, source_bytes) = {
+        let app = app_handle.clone();
+        let (source, tmp, exclude) = (source.clone(), tmp.clone(), config.exclude.clone());
+        tokio::task::spawn_blocking(move || {
+            create_snapshot(&source, &tmp, &exclude, |done, total| {
+                emit_progress(&app, "archive", done as u64, total as u64, &format!("Archived {}/{} files", done, total));
+            })
+        })
+        .await
+        .map_err(|e| CommandError::Internal(format!("Backup task failed: {}", e)))?
+    }
+    .inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })?;
+
+    let size_bytes = std::fs::metadata(&tmp).map(|m| m.len()).unwrap_or(0);
+    if store.is_remote() {
+        emit_progress(app_handle, "upload", 0, size_bytes, "Uploading snapshot");
+    }
+    let location = store.put(&tmp, &id).await.inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })?;
+    if store.is_remote() {
+        emit_progress(app_handle, "upload", size_bytes, size_bytes, "Upload complete");
+    }
+
+    // Retention — failures here don't fail the backup itself
+    let mut pruned = Vec::new();
+    if let Ok(existing) = store.list().await {
+        let ids: Vec<String> = existing.into_iter().map(|s| s.id).collect();
+        let expired = select_expired(&ids, &config.retention, now);
+        for (i, old) in expired.iter().enumerate() {
+            emit_progress(app_handle, "prune", i as u64, expired.len() as u64, &format!("Removing {}", old));
+            match store.delete(old).await {
+                Ok(()) => pruned.push(old.clone()),
+                Err(e) => eprintln!("[backup] Failed to prune {}: {}", old, e),
+            }
+        }
+    }
+
+    save_state(&BackupState {
+        last_run_at: Some(now.to_rfc3339()),
+        last_snapshot: Some(id.clone()),
+        last_error: None,
+    });
+
+    Ok(BackupRunResult {
+        snapshot: BackupSnapshot {
+            id,
+            created_at: Some(now.to_rfc3339()),
+            size_bytes,
+            location,
+        },
+        file_count,
+        source_bytes,
+        pruned,
+    })
+}
+
+fn is_non_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Background schedule
+// ============================================================================
+
+/// Start the backup schedule check (every 60s). Call from main.rs setup hook.
+pub fn start_backup_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(45)).await;
+        loop {
+            let config = load_config();
+            let mut state = load_state();
+            let last_run = state
+                .last_run_at
+                .as_deref()
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+            if config.enabled && is_due(&config.cron, last_run) {
+                let tracking_id = format!("knowledge-backup-{}", chrono::Utc::now().timestamp_millis());
+                let started_at = chrono::Utc::now().to_rfc3339();
+                let name = "Scheduled: Knowledge backup";
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &tracking_id, "name": name, "status": "running",
+                    "message": "Backing up knowledge folder", "startedAt": &started_at,
+                }));
+
+                let knowledge_path = app_handle.state::<AppState>().knowledge_path.clone();
+                let (status, message) = match run_backup(&app_handle, &knowledge_path).await {
+                    Ok(r) => (
+                        "completed",
+                        format!("{} ({} files) → {}", r.snapshot.id, r.file_count, r.snapshot.location),
+                    ),
+                    Err(e) => {
+                        eprintln!("[backup] Scheduled backup failed: {}", e);
+                        // Record the attempt so a failing backup doesn't retry every minute
+                        state.last_run_at = Some(chrono::Utc::now().to_rfc3339());
+                        state.last_error = Some(e.to_string());
+                        save_state(&state);
+                        ("failed", format!("Knowledge backup failed: {}", e))
+                    }
+                };
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &tracking_id, "name": name, "status": status,
+                    "message": message, "startedAt": &started_at,
+                }));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn backup_get_config() -> CmdResult<BackupConfig> {
+    Ok(load_config())
+}
+
+#[tauri::command]
+pub async fn backup_save_config(config: BackupConfig) -> CmdResult<BackupConfig> {
+    let expr = crate::commands::scheduler::background::normalize_cron(&config.cron);
+    Schedule::from_str(&expr)
+        .map_err(|e| CommandError::Config(format!("Invalid cron '{}': {}", config.cron, e)))?;
+    Store::from_config(&config.destination)?;
+    std::fs::create_dir_all(backup_dir())?;
+    std::fs::write(backup_dir().join("config.json"), serde_json::to_string_pretty(&config)?)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn backup_get_status() -> CmdResult<BackupState> {
+    Ok(load_state())
+}
+
+/// Snapshot the knowledge folder now (ignores the schedule / enabled flag)
+#[tauri::command]
+pub async fn backup_run_now(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> CmdResult<BackupRunResult> {
+    let result = run_backup(&app_handle, &state.knowledge_path).await;
+    if let Err(e) = &result {
+        let mut s = load_state();
+        s.last_error = Some(e.to_string());
+        save_state(&s);
+    }
+    result
+}
+
+/// Snapshots at the configured destination, newest first
+#[tauri::command]
+pub async fn backup_list() -> CmdResult<Vec<BackupSnapshot>> {
+    Store::from_config(&load_config().destination)?.list().await
+}
+
+/// Extract `snapshot` into `target`. Refuses a non-empty target unless
+/// `overwrite` is set (existing files with the same path are replaced).
+#[tauri::command]
+pub async fn backup_restore(
+    app_handle: tauri::AppHandle,
+    snapshot: String,
+    target: String,
+    overwrite: Option<bool>,
+) -> CmdResult<BackupRestoreResult> {
+    let _guard = RunGuard::acquire()?;
+    if snapshot.contains(|c: char| c == '/' || c == '\\') {
+        return Err(CommandError::Config(format!("Invalid snapshot id: {}", snapshot)));
+    }
+    let target_path = PathBuf::from(&target);
+    if is_non_empty_dir(&target_path) && !overwrite.unwrap_or(false) {
+        return Err(CommandError::Config(format!(
+            "Restore target is not empty: {} (pass overwrite to restore into it)",
+            target
+        )));
+    }
+
+    let store = Store::from_config(&load_config().destination)?;
+    let scratch = backup_dir().join("tmp").join("restore");
+    if store.is_remote() {
+        emit_progress(&app_handle, "download", 0, 1, &format!("Downloading {}", snapshot));
+    }
+    let zip_path = store.fetch(&snapshot, &scratch).await?;
+
+    let file_count = {
+        let app = app_handle.clone();
+        let (zip_path, target_path) = (zip_path.clone(), target_path.clone());
+        tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&target_path)?;
+            extract_snapshot(&zip_path, &target_path, |done, total| {
+                emit_progress(&app, "extract", done as u64, total as u64, &format!("Restored {}/{} entries", done, total));
+            })
+        })
+        .await
+        .map_err(|e| CommandError::Internal(format!("Restore task failed: {}", e)))?
+    };
+    if store.is_remote() {
+        let _ = std::fs::remove_file(&zip_path);
+    }
+
+    Ok(BackupRestoreResult {
+        snapshot,
+        target,
+        file_count: file_count?,
+    })
+}
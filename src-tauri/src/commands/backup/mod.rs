@@ -0,0 +1,7 @@
+// Knowledge backup module - scheduled zip snapshots of the knowledge folder
+// Destinations: a local folder (external disk, NAS mount) or an S3-compatible bucket
+
+pub mod archive;
+pub mod commands;
+pub mod store;
+pub mod types;
@@ -0,0 +1,243 @@
+// Snapshot destinations — a local folder or an S3-compatible bucket
+
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::archive::{parse_snapshot_time, SNAPSHOT_PREFIX};
+use super::types::{BackupDestination, BackupSnapshot};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings::{load_settings, KEY_AWS_ACCESS_KEY_ID, KEY_AWS_SECRET_ACCESS_KEY};
+
+const DEFAULT_S3_REGION: &str = "ap-southeast-1";
+
+pub enum Store {
+    Local(PathBuf),
+    S3 {
+        client: aws_sdk_s3::Client,
+        bucket: String,
+        prefix: String,
+    },
+}
+
+fn s3_client(dest: &BackupDestination) -> CmdResult<aws_sdk_s3::Client> {
+    let settings = load_settings()?;
+    let key = |name: &str| {
+        settings
+            .keys
+            .get(name)
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .ok_or_else(|| CommandError::Config(format!("{} not configured. Go to Settings to add it.", name)))
+    };
+    let creds = Credentials::new(
+        key(KEY_AWS_ACCESS_KEY_ID)?,
+        key(KEY_AWS_SECRET_ACCESS_KEY)?,
+        None,
+        None,
+        "tv-client-settings",
+    );
+    let region = dest.region.clone().filter(|r| !r.is_empty()).unwrap_or_else(|| DEFAULT_S3_REGION.to_string());
+    let mut builder = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(region))
+        .credentials_provider(creds);
+    if let Some(endpoint) = dest.endpoint_url.as_deref().filter(|e| !e.is_empty()) {
+        // Most S3-compatible services need path-style addressing
+        builder = builder.endpoint_url(endpoint).force_path_style(true);
+    }
+    Ok(aws_sdk_s3::Client::from_conf(builder.build()))
+}
+
+fn modified_rfc3339(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+impl Store {
+    pub fn from_config(dest: &BackupDestination) -> CmdResult<Self> {
+        match dest.kind.as_str() {
+            "local" => {
+                let path = dest
+                    .path
+                    .as_deref()
+                    .filter(|p| !p.is_empty())
+                    .ok_or_else(|| CommandError::Config("Backup destination folder not set".to_string()))?;
+                Ok(Self::Local(PathBuf::from(path)))
+            }
+            "s3" => {
+                let bucket = dest
+                    .bucket
+                    .clone()
+                    .filter(|b| !b.is_empty())
+                    .ok_or_else(|| CommandError::Config("Backup bucket not set".to_string()))?;
+                let mut prefix = dest.prefix.clone().unwrap_or_default();
+                if !prefix.is_empty() && !prefix.ends_with('/') {
+                    prefix.push('/');
+                }
+                Ok(Self::S3 {
+                    client: s3_client(dest)?,
+                    bucket,
+                    prefix,
+                })
+            }
+            other => Err(CommandError::Config(format!(
+                "Unknown backup destination: {} (expected local or s3)",
+                other
+            ))),
+        }
+    }
+
+    /// Move a finished snapshot from `file` into the destination. Returns its location.
+    pub async fn put(&self, file: &Path, id: &str) -> CmdResult<String> {
+        self.put_as(file, id, "application/zip").await
+    }
+
+    /// `put` for non-zip files (content type only matters for S3)
+    pub async fn put_as(&self, file: &Path, id: &str, content_type: &str) -> CmdResult<String> {
+        match self {
+            Self::Local(dir) => {
+                fs::create_dir_all(dir)?;
+                let dest = dir.join(id);
+                // rename fails across devices (external disk) — fall back to copy
+                if fs::rename(file, &dest).is_err() {
+                    fs::copy(file, &dest)?;
+                    let _ = fs::remove_file(file);
+                }
+                Ok(dest.to_string_lossy().to_string())
+            }
+            Self::S3 { client, bucket, prefix } => {
+                let key = format!("{}{}", prefix, id);
+                let body = ByteStream::from_path(file)
+                    .await
+                    .map_err(|e| CommandError::Io(format!("Failed to read snapshot: {}", e)))?;
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(&key)
+                    .content_type(content_type)
+                    .body(body)
+                    .send()
+                    .await
+                    .map_err(|e| CommandError::Network(format!("Failed to upload snapshot: {}", e)))?;
+                let _ = fs::remove_file(file);
+                Ok(format!("s3://{}/{}", bucket, key))
+            }
+        }
+    }
+
+    /// Snapshots at the destination, newest first
+    pub async fn list(&self) -> CmdResult<Vec<BackupSnapshot>> {
+        let mut snapshots = Vec::new();
+        match self {
+            Self::Local(dir) => {
+                if !dir.exists() {
+                    return Ok(snapshots);
+                }
+                for entry in fs::read_dir(dir)?.flatten() {
+                    let id = entry.file_name().to_string_lossy().to_string();
+                    if !id.starts_with(SNAPSHOT_PREFIX) || !id.ends_with(".zip") {
+                        continue;
+                    }
+                    let path = entry.path();
+                    snapshots.push(BackupSnapshot {
+                        created_at: parse_snapshot_time(&id)
+                            .map(|t| t.to_rfc3339())
+                            .or_else(|| modified_rfc3339(&path)),
+                        size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                        location: path.to_string_lossy().to_string(),
+                        id,
+                    });
+                }
+            }
+            Self::S3 { client, bucket, prefix } => {
+                let mut token: Option<String> = None;
+                loop {
+                    let mut req = client
+                        .list_objects_v2()
+                        .bucket(bucket)
+                        .prefix(format!("{}{}", prefix, SNAPSHOT_PREFIX));
+                    if let Some(t) = &token {
+                        req = req.continuation_token(t);
+                    }
+                    let resp = req
+                        .send()
+                        .await
+                        .map_err(|e| CommandError::Network(format!("Failed to list snapshots: {}", e)))?;
+                    for obj in resp.contents() {
+                        let Some(key) = obj.key() else { continue };
+                        let id = key.strip_prefix(prefix.as_str()).unwrap_or(key).to_string();
+                        if id.contains('/') || !id.ends_with(".zip") {
+                            continue;
+                        }
+                        snapshots.push(BackupSnapshot {
+                            created_at: parse_snapshot_time(&id).map(|t| t.to_rfc3339()),
+                            size_bytes: obj.size().unwrap_or(0) as u64,
+                            location: format!("s3://{}/{}", bucket, key),
+                            id,
+                        });
+                    }
+                    match resp.next_continuation_token() {
+                        Some(t) => token = Some(t.to_string()),
+                        None => break,
+                    }
+                }
+            }
+        }
+        snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(snapshots)
+    }
+
+    /// Local path of a snapshot, downloading it into `scratch` first for S3
+    pub async fn fetch(&self, id: &str, scratch: &Path) -> CmdResult<PathBuf> {
+        match self {
+            Self::Local(dir) => {
+                let path = dir.join(id);
+                if !path.is_file() {
+                    return Err(CommandError::NotFound(format!("Snapshot not found: {}", id)));
+                }
+                Ok(path)
+            }
+            Self::S3 { client, bucket, prefix } => {
+                let resp = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(format!("{}{}", prefix, id))
+                    .send()
+                    .await
+                    .map_err(|e| CommandError::Network(format!("Failed to download snapshot {}: {}", id, e)))?;
+                fs::create_dir_all(scratch)?;
+                let path = scratch.join(id);
+                let mut file = fs::File::create(&path)?;
+                let mut body = resp.body;
+                while let Some(chunk) = body.next().await {
+                    let chunk = chunk.map_err(|e| CommandError::Network(format!("Download interrupted: {}", e)))?;
+                    file.write_all(&chunk)?;
+                }
+                Ok(path)
+            }
+        }
+    }
+
+    pub async fn delete(&self, id: &str) -> CmdResult<()> {
+        match self {
+            Self::Local(dir) => Ok(fs::remove_file(dir.join(id))?),
+            Self::S3 { client, bucket, prefix } => {
+                client
+                    .delete_object()
+                    .bucket(bucket)
+                    .key(format!("{}{}", prefix, id))
+                    .send()
+                    .await
+                    .map_err(|e| CommandError::Network(format!("Failed to delete snapshot {}: {}", id, e)))?;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Self::S3 { .. })
+    }
+}
@@ -0,0 +1,126 @@
+// Shared types for the backup module
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Config
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupDestination {
+    pub kind: String, // "local" | "s3"
+    /// Local: folder snapshots are written to
+    pub path: Option<String>,
+    /// S3: bucket + key prefix (e.g. "tv-backups/")
+    pub bucket: Option<String>,
+    pub prefix: Option<String>,
+    /// S3-compatible endpoint (MinIO, R2, B2...); None = AWS
+    pub endpoint_url: Option<String>,
+    pub region: Option<String>,
+}
+
+impl Default for BackupDestination {
+    fn default() -> Self {
+        Self {
+            kind: "local".to_string(),
+            path: None,
+            bucket: None,
+            prefix: None,
+            endpoint_url: None,
+            region: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Always keep this many newest snapshots
+    pub keep_last: usize,
+    /// Delete snapshots older than this (beyond keep_last); None = no age limit
+    pub max_age_days: Option<i64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last: 7,
+            max_age_days: Some(90),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub enabled: bool,
+    /// 5-field cron in UTC
+    pub cron: String,
+    pub destination: BackupDestination,
+    pub retention: RetentionPolicy,
+    /// File / folder names skipped anywhere in the tree
+    pub exclude: Vec<String>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: "0 2 * * *".to_string(),
+            destination: BackupDestination::default(),
+            retention: RetentionPolicy::default(),
+            exclude: vec![
+                ".git".to_string(),
+                "node_modules".to_string(),
+                ".DS_Store".to_string(),
+            ],
+        }
+    }
+}
+
+// ============================================================================
+// Snapshots + results
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSnapshot {
+    /// File name, e.g. knowledge-20261016T020000Z.zip
+    pub id: String,
+    pub created_at: Option<String>,
+    pub size_bytes: u64,
+    /// Full path or s3://bucket/key
+    pub location: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRunResult {
+    pub snapshot: BackupSnapshot,
+    pub file_count: usize,
+    pub source_bytes: u64,
+    /// Snapshot ids removed by the retention policy
+    pub pruned: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRestoreResult {
+    pub snapshot: String,
+    pub target: String,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BackupState {
+    pub last_run_at: Option<String>,
+    pub last_snapshot: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Emitted as "backup:progress" while archiving, uploading and restoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupProgress {
+    pub phase: String, // "scan" | "archive" | "upload" | "download" | "extract" | "prune"
+    pub current: u64,
+    pub total: u64,
+    pub message: String,
+}
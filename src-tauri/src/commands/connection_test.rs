@@ -0,0 +1,353 @@
+// Connection Tests - Verify integration keys right after they're entered
+// Each service gets one minimal authenticated call (no data written, nothing
+// billed) and a structured ok/fail with a diagnosis of what to fix.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings::{self, load_settings};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(15);
+/// Response body kept in diagnostics
+const BODY_SNIPPET_CHARS: usize = 300;
+
+const ANTHROPIC_API: &str = "https://api.anthropic.com/v1/models?limit=1";
+const GAMMA_API: &str = "https://public-api.gamma.app/v1.0/themes?limit=1";
+const INTERCOM_API: &str = "https://api.intercom.io/me";
+const INTERCOM_API_VERSION: &str = "2.11";
+const GITHUB_API: &str = "https://api.github.com";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionTestResult {
+    pub service: String,
+    pub ok: bool,
+    /// One-line summary suitable for the settings form
+    pub message: String,
+    /// What was called (host + path, never the key)
+    pub endpoint: Option<String>,
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    /// Settings keys that must be filled in before the test can run
+    pub missing_keys: Vec<String>,
+    /// Account info on success, or a trimmed error body on failure
+    pub detail: Option<String>,
+}
+
+impl ConnectionTestResult {
+    fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            ok: false,
+            message: String::new(),
+            endpoint: None,
+            status: None,
+            latency_ms: 0,
+            missing_keys: Vec::new(),
+            detail: None,
+        }
+    }
+
+    fn missing(service: &str, keys: Vec<String>) -> Self {
+        Self {
+            message: format!("Missing {}", keys.join(", ")),
+            missing_keys: keys,
+            ..Self::new(service)
+        }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Values for `keys`, or the names of the ones that are empty
+fn require(stored: &HashMap<String, String>, keys: &[&str]) -> Result<Vec<String>, Vec<String>> {
+    let mut values = Vec::new();
+    let mut missing = Vec::new();
+    for key in keys {
+        match stored.get(*key).map(|v| v.trim()).filter(|v| !v.is_empty()) {
+            Some(v) => values.push(v.to_string()),
+            None => missing.push(key.to_string()),
+        }
+    }
+    if missing.is_empty() {
+        Ok(values)
+    } else {
+        Err(missing)
+    }
+}
+
+fn snippet(body: &str) -> Option<String> {
+    let trimmed = body.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.chars().take(BODY_SNIPPET_CHARS).collect())
+    }
+}
+
+/// Human explanation for a failed HTTP status
+pub fn diagnose(status: u16, body: &str) -> String {
+    let lower = body.to_lowercase();
+    match status {
+        400 if lower.contains("aadsts7000215") => "Client secret is wrong — use the secret value, not its ID".to_string(),
+        400 | 401 if lower.contains("aadsts700016") => "App (client) ID not found in this tenant".to_string(),
+        400 if lower.contains("aadsts90002") => "Tenant ID not found".to_string(),
+        401 => "Key rejected (401) — check it was copied in full and hasn't been revoked".to_string(),
+        403 => "Key accepted but lacks permission for this call (403)".to_string(),
+        404 => "Endpoint not found (404) — check the URL or domain".to_string(),
+        429 => "Rate limited (429) — the key works, try again shortly".to_string(),
+        s if s >= 500 => format!("Service error ({}) — the service may be down, try again later", s),
+        s => format!("Unexpected response ({})", s),
+    }
+}
+
+fn endpoint_of(url: &str) -> String {
+    url.split('?').next().unwrap_or(url).to_string()
+}
+
+/// Send a prepared request and fill in status, latency and diagnostics.
+/// `accept` decides which statuses count as a working connection.
+async fn run(
+    service: &str,
+    url: &str,
+    request: reqwest::RequestBuilder,
+    accept: impl Fn(u16) -> bool,
+) -> (ConnectionTestResult, Option<String>) {
+    let mut result = ConnectionTestResult {
+        endpoint: Some(endpoint_of(url)),
+        ..ConnectionTestResult::new(service)
+    };
+    let start = Instant::now();
+    let response = request.timeout(TIMEOUT).send().await;
+    result.latency_ms = start.elapsed().as_millis() as u64;
+
+    match response {
+        Ok(resp) => {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            result.status = Some(status);
+            result.ok = accept(status);
+            if result.ok {
+                result.message = format!("Connected ({} ms)", result.latency_ms);
+                (result, Some(body))
+            } else {
+                result.message = diagnose(status, &body);
+                result.detail = snippet(&body);
+                (result, None)
+            }
+        }
+        Err(e) => {
+            result.message = if e.is_timeout() {
+                format!("No response within {}s", TIMEOUT.as_secs())
+            } else if e.is_connect() {
+                "Could not connect — check the URL and your network".to_string()
+            } else {
+                format!("Request failed: {}", e)
+            };
+            (result, None)
+        }
+    }
+}
+
+fn json_str(body: &str, path: &[&str]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let mut cur = &value;
+    for key in path {
+        cur = cur.get(*key)?;
+    }
+    cur.as_str().map(String::from)
+}
+
+// ============================================================================
+// Per-service checks
+// ============================================================================
+
+async fn test_supabase(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(keys, &[settings::KEY_SUPABASE_URL, settings::KEY_SUPABASE_ANON_KEY]) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("supabase", missing),
+    };
+    let (url, anon_key) = (values[0].trim_end_matches('/'), &values[1]);
+    let endpoint = format!("{}/auth/v1/settings", url);
+    let request = crate::HTTP_CLIENT.get(&endpoint).header("apikey", anon_key.as_str());
+    let (mut result, _) = run("supabase", &endpoint, request, |s| (200..300).contains(&s)).await;
+    if result.ok {
+        result.detail = Some(format!("Project {}", url.trim_start_matches("https://")));
+    }
+    result
+}
+
+async fn test_val(domain: Option<String>) -> ConnectionTestResult {
+    let Some(domain) = domain.filter(|d| !d.trim().is_empty()) else {
+        return ConnectionTestResult {
+            message: "Pick a VAL domain to test".to_string(),
+            ..ConnectionTestResult::new("val")
+        };
+    };
+    let mut result = ConnectionTestResult::new("val");
+    let start = Instant::now();
+    // Fresh login, so newly entered credentials are what gets tested
+    let outcome = crate::commands::val_sync::auth::reauth(&domain).await;
+    result.latency_ms = start.elapsed().as_millis() as u64;
+
+    match outcome {
+        Ok((_, api_domain)) => {
+            result.ok = true;
+            result.endpoint = Some(format!("https://{}.thinkval.io", api_domain));
+            result.message = format!("Logged in to {} ({} ms)", domain, result.latency_ms);
+        }
+        Err(CommandError::Config(msg)) => {
+            result.missing_keys = [format!("val_email_{}", domain), format!("val_password_{}", domain)]
+                .into_iter()
+                .filter(|key| msg.contains(key.as_str()))
+                .collect();
+            result.message = msg;
+        }
+        Err(CommandError::Http { status, body }) => {
+            result.status = Some(status);
+            result.message = diagnose(status, &body);
+            result.detail = snippet(&body);
+        }
+        Err(e) => result.message = e.to_string(),
+    }
+    result
+}
+
+async fn test_ms_graph(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(
+        keys,
+        &[
+            settings::KEY_MS_GRAPH_TENANT_ID,
+            settings::KEY_MS_GRAPH_CLIENT_ID,
+            settings::KEY_MS_GRAPH_CLIENT_SECRET,
+        ],
+    ) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("ms_graph", missing),
+    };
+    // Client-credentials token request validates tenant, app ID and secret together
+    let url = format!("https://login.microsoftonline.com/{}/oauth2/v2.0/token", values[0]);
+    let request = crate::HTTP_CLIENT.post(&url).form(&[
+        ("client_id", values[1].as_str()),
+        ("client_secret", values[2].as_str()),
+        ("grant_type", "client_credentials"),
+        ("scope", "https://graph.microsoft.com/.default"),
+    ]);
+    let (mut result, _) = run("ms_graph", &url, request, |s| (200..300).contains(&s)).await;
+    if result.ok {
+        result.detail = Some(if crate::commands::outlook::auth::load_tokens().is_some() {
+            "App credentials valid; Outlook is signed in".to_string()
+        } else {
+            "App credentials valid; Outlook not signed in yet".to_string()
+        });
+    }
+    result
+}
+
+async fn test_anthropic(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(keys, &[settings::KEY_ANTHROPIC_API]) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("anthropic", missing),
+    };
+    let request = crate::HTTP_CLIENT
+        .get(ANTHROPIC_API)
+        .header("x-api-key", values[0].as_str())
+        .header("anthropic-version", "2023-06-01");
+    let (mut result, body) = run("anthropic", ANTHROPIC_API, request, |s| (200..300).contains(&s)).await;
+    if let Some(body) = body {
+        result.detail = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v["data"][0]["id"].as_str().map(|id| format!("Models available, e.g. {}", id)));
+    }
+    result
+}
+
+async fn test_gamma(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(keys, &[settings::KEY_GAMMA_API]) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("gamma", missing),
+    };
+    let request = crate::HTTP_CLIENT.get(GAMMA_API).header("X-API-KEY", values[0].as_str());
+    run("gamma", GAMMA_API, request, |s| (200..300).contains(&s)).await.0
+}
+
+async fn test_intercom(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(keys, &[settings::KEY_INTERCOM_API]) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("intercom", missing),
+    };
+    let request = crate::HTTP_CLIENT
+        .get(INTERCOM_API)
+        .header("Authorization", format!("Bearer {}", values[0]))
+        .header("Intercom-Version", INTERCOM_API_VERSION);
+    let (mut result, body) = run("intercom", INTERCOM_API, request, |s| (200..300).contains(&s)).await;
+    if let Some(body) = body {
+        result.detail = json_str(&body, &["app", "name"]).map(|name| format!("Workspace {}", name));
+    }
+    result
+}
+
+async fn test_github(keys: &HashMap<String, String>) -> ConnectionTestResult {
+    let values = match require(keys, &[settings::KEY_GITHUB_CLIENT_ID, settings::KEY_GITHUB_CLIENT_SECRET]) {
+        Ok(v) => v,
+        Err(missing) => return ConnectionTestResult::missing("github", missing),
+    };
+    // Checking a dummy token authenticates as the OAuth app: valid app credentials
+    // get 404 (token unknown), bad ones get 401
+    let url = format!("{}/applications/{}/token", GITHUB_API, values[0]);
+    let request = crate::HTTP_CLIENT
+        .post(&url)
+        .basic_auth(&values[0], Some(&values[1]))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "tv-desktop")
+        .json(&serde_json::json!({ "access_token": "tv-client-connection-test" }));
+    let (mut result, _) = run("github", &url, request, |s| s == 404 || (200..300).contains(&s)).await;
+    if result.ok {
+        result.detail = Some("OAuth app credentials accepted".to_string());
+    }
+    result
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Test one integration with its saved keys. `service` is one of supabase, val,
+/// ms_graph, anthropic, gamma, intercom, github; `domain` is required for val.
+#[tauri::command]
+pub async fn settings_test_connection(service: String, domain: Option<String>) -> CmdResult<ConnectionTestResult> {
+    let keys = load_settings()?.keys;
+    let result = match service.as_str() {
+        "supabase" => test_supabase(&keys).await,
+        "val" => test_val(domain).await,
+        "ms_graph" | "outlook" => test_ms_graph(&keys).await,
+        "anthropic" => test_anthropic(&keys).await,
+        "gamma" => test_gamma(&keys).await,
+        "intercom" => test_intercom(&keys).await,
+        "github" => test_github(&keys).await,
+        other => return Err(CommandError::Config(format!("Unknown service: {}", other))),
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_keys_and_diagnoses_statuses() {
+        let mut keys = HashMap::new();
+        keys.insert(settings::KEY_SUPABASE_URL.to_string(), "https://x.supabase.co".to_string());
+        keys.insert(settings::KEY_SUPABASE_ANON_KEY.to_string(), "  ".to_string());
+        assert_eq!(
+            require(&keys, &[settings::KEY_SUPABASE_URL, settings::KEY_SUPABASE_ANON_KEY]),
+            Err(vec![settings::KEY_SUPABASE_ANON_KEY.to_string()])
+        );
+
+        assert!(diagnose(401, "").starts_with("Key rejected"));
+        assert!(diagnose(400, r#"{"error_description":"AADSTS7000215: Invalid client secret"}"#).contains("secret"));
+        assert!(diagnose(503, "").contains("503"));
+    }
+}
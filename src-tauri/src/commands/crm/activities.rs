@@ -2,7 +2,7 @@
 
 use super::types::*;
 use crate::commands::error::{CmdResult, CommandError};
-use crate::commands::supabase::get_client;
+use crate::commands::supabase::{get_client, SupabaseClient};
 
 /// List activities with optional filters
 #[tauri::command]
@@ -158,12 +158,15 @@ pub async fn crm_unlink_email(email_id: String, company_id: String) -> CmdResult
     client.delete("crm_email_company_links", &query).await
 }
 
-/// Auto-link email by matching sender/recipients to contacts or domains
+/// Auto-link email by matching sender/recipients to contacts or domains.
+/// A newly linked email is also checked for new-deal signals in the background
+/// (unless `detect_deal` is false); proposals land in crm_list_deal_suggestions.
 #[tauri::command]
 pub async fn crm_auto_link_email(
     email_id: String,
     sender_email: String,
     recipient_emails: Option<Vec<String>>,
+    detect_deal: Option<bool>,
 ) -> CmdResult<Option<EmailCompanyLink>> {
     let client = get_client().await?;
 
@@ -179,6 +182,32 @@ pub async fn crm_auto_link_email(
         return Ok(existing);
     }
 
+    let link = match_and_link(&client, email_id, sender_email, recipient_emails).await?;
+
+    if let (Some(link), true) = (&link, detect_deal.unwrap_or(true)) {
+        // Best-effort and in the background: detection makes an LLM call and
+        // never fails or delays the link
+        let link = link.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = match get_client().await {
+                Ok(client) => super::deal_detection::detect_deal_opportunity(&client, &link).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("[crm:deal-detection] Skipped email {}: {}", link.email_id, e);
+            }
+        });
+    }
+
+    Ok(link)
+}
+
+async fn match_and_link(
+    client: &SupabaseClient,
+    email_id: String,
+    sender_email: String,
+    recipient_emails: Option<Vec<String>>,
+) -> CmdResult<Option<EmailCompanyLink>> {
     // Strategy 1: Match sender email to contact
     let sender_lower = sender_email.to_lowercase();
     let sender_contact: Option<Contact> = client
@@ -223,12 +252,7 @@ pub async fn crm_auto_link_email(
     let sender_domain = extract_domain(&sender_email);
     if let Some(domain) = sender_domain {
         // Skip common email providers
-        let skip_domains = [
-            "gmail.com", "outlook.com", "hotmail.com", "yahoo.com",
-            "icloud.com", "me.com", "live.com", "msn.com",
-        ];
-
-        if !skip_domains.contains(&domain.as_str()) {
+        if !FREE_EMAIL_DOMAINS.contains(&domain.as_str()) {
             // Search for company with matching website domain
             let companies: Vec<Company> = client
                 .select("crm_companies", &format!("website.ilike.*{}*&limit=1", domain))
@@ -250,8 +274,14 @@ pub async fn crm_auto_link_email(
     Ok(None)
 }
 
+/// Personal mailbox providers — a shared domain says nothing about the company
+pub const FREE_EMAIL_DOMAINS: [&str; 8] = [
+    "gmail.com", "outlook.com", "hotmail.com", "yahoo.com",
+    "icloud.com", "me.com", "live.com", "msn.com",
+];
+
 /// Extract domain from email address
-fn extract_domain(email: &str) -> Option<String> {
+pub fn extract_domain(email: &str) -> Option<String> {
     email.split('@').nth(1).map(|d| d.to_lowercase())
 }
 
@@ -0,0 +1,244 @@
+// CRM Module - Won/Lost Close Reasons
+// Taxonomy lives in lookup_values (type deal_won_reason / deal_lost_reason) so it's
+// editable from Metadata → Lookups. Deals store the reason code, the competitor
+// and the stage they closed from; the report aggregates over a period.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use crate::commands::work::Project;
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct ReasonRow {
+    value: String,
+    label: String,
+}
+
+fn lookup_type(outcome: &str) -> String {
+    format!("deal_{}_reason", outcome)
+}
+
+async fn load_reasons(client: &SupabaseClient, outcome: &str) -> CmdResult<Vec<ReasonRow>> {
+    client
+        .select(
+            "lookup_values",
+            &format!("type=eq.{}&select=value,label&order=sort_order.asc", lookup_type(outcome)),
+        )
+        .await
+}
+
+/// Check `reason` is a known code for `outcome` (won | lost). Called by
+/// work_update_project when a deal closes; an empty taxonomy accepts any code.
+pub async fn validate_close_reason(client: &SupabaseClient, outcome: &str, reason: Option<&str>) -> CmdResult<()> {
+    let reasons = load_reasons(client, outcome).await?;
+    let valid: Vec<&str> = reasons.iter().map(|r| r.value.as_str()).collect();
+    match reason.map(str::trim).filter(|r| !r.is_empty()) {
+        None => Err(CommandError::Config(format!(
+            "A close reason is required to mark a deal {}{}",
+            outcome,
+            if valid.is_empty() { String::new() } else { format!(" (one of: {})", valid.join(", ")) }
+        ))),
+        Some(r) if !valid.is_empty() && !valid.contains(&r) => Err(CommandError::Config(format!(
+            "Invalid {} reason '{}'. Must be one of: {}",
+            outcome,
+            r,
+            valid.join(", ")
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Resolve a period to an inclusive [start, end] date range.
+/// Accepts "all", "Nd" (last N days), "ytd", "YYYY", "YYYY-Qn" and "YYYY-MM".
+pub fn parse_period(period: &str, today: NaiveDate) -> Result<(Option<NaiveDate>, Option<NaiveDate>), String> {
+    let p = period.trim().to_lowercase();
+    let invalid = || format!("Invalid period '{}' (use all, 90d, ytd, 2026, 2026-Q3 or 2026-07)", period);
+
+    if p.is_empty() || p == "all" {
+        return Ok((None, None));
+    }
+    if p == "ytd" {
+        return Ok((NaiveDate::from_ymd_opt(today.year(), 1, 1), Some(today)));
+    }
+    if let Some(days) = p.strip_suffix('d') {
+        let n: i64 = days.parse().map_err(|_| invalid())?;
+        return Ok((Some(today - chrono::Duration::days(n)), Some(today)));
+    }
+
+    let (year, rest) = p.split_once('-').map(|(y, r)| (y, Some(r))).unwrap_or((p.as_str(), None));
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    let (start_month, months) = match rest {
+        None => (1, 12),
+        Some(q) if q.starts_with('q') => {
+            let n: u32 = q[1..].parse().map_err(|_| invalid())?;
+            if !(1..=4).contains(&n) {
+                return Err(invalid());
+            }
+            ((n - 1) * 3 + 1, 3)
+        }
+        Some(m) => {
+            let n: u32 = m.parse().map_err(|_| invalid())?;
+            if !(1..=12).contains(&n) {
+                return Err(invalid());
+            }
+            (n, 1)
+        }
+    };
+    let start = NaiveDate::from_ymd_opt(year, start_month, 1).ok_or_else(invalid)?;
+    let end_month = start_month + months;
+    let end = if end_month > 12 {
+        NaiveDate::from_ymd_opt(year + 1, end_month - 12, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, end_month, 1)
+    }
+    .ok_or_else(invalid)?
+    .pred_opt()
+    .ok_or_else(invalid)?;
+    Ok((Some(start), Some(end)))
+}
+
+/// Close date for reporting: actual close if recorded, else when the stage last changed
+fn close_date(deal: &Project) -> Option<NaiveDate> {
+    deal.deal_actual_close
+        .as_deref()
+        .or(deal.deal_stage_changed_at.as_deref())
+        .and_then(|d| NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok())
+}
+
+fn add_to(buckets: &mut Vec<CloseReasonBucket>, key: &str, label: &str, outcome: &str, value: f64) {
+    match buckets.iter_mut().find(|b| b.key == key && b.outcome == outcome) {
+        Some(b) => {
+            b.count += 1;
+            b.value += value;
+        }
+        None => buckets.push(CloseReasonBucket {
+            outcome: outcome.to_string(),
+            key: key.to_string(),
+            label: label.to_string(),
+            count: 1,
+            value,
+        }),
+    }
+}
+
+/// List the configured close reasons for an outcome (won | lost)
+#[tauri::command]
+pub async fn crm_list_close_reasons(outcome: String) -> CmdResult<Vec<CloseReason>> {
+    if outcome != "won" && outcome != "lost" {
+        return Err(CommandError::Config(format!("Unknown outcome: {} (expected won or lost)", outcome)));
+    }
+    let client = get_client().await?;
+    Ok(load_reasons(&client, &outcome)
+        .await?
+        .into_iter()
+        .map(|r| CloseReason {
+            outcome: outcome.clone(),
+            value: r.value,
+            label: r.label,
+        })
+        .collect())
+}
+
+/// Aggregate closed deals by reason, by stage at loss and by competitor
+#[tauri::command]
+pub async fn crm_get_close_reason_report(period: Option<String>) -> CmdResult<CloseReasonReport> {
+    let period = period.unwrap_or_else(|| "90d".to_string());
+    let today = chrono::Local::now().date_naive();
+    let (start, end) = parse_period(&period, today).map_err(CommandError::Config)?;
+
+    let client = get_client().await?;
+    let deals: Vec<Project> = client
+        .select_all(
+            "projects",
+            "project_type=eq.deal&deal_stage=in.(won,lost)&archived_at=is.null&order=id.asc",
+        )
+        .await?;
+
+    let mut labels: HashMap<(String, String), String> = HashMap::new();
+    for outcome in ["won", "lost"] {
+        for r in load_reasons(&client, outcome).await? {
+            labels.insert((outcome.to_string(), r.value), r.label);
+        }
+    }
+
+    let mut report = CloseReasonReport {
+        period: period.clone(),
+        start: start.map(|d| d.to_string()),
+        end: end.map(|d| d.to_string()),
+        ..Default::default()
+    };
+
+    for deal in &deals {
+        let Some(closed) = close_date(deal) else {
+            if start.is_some() || end.is_some() {
+                continue;
+            }
+            report.undated += 1;
+            continue;
+        };
+        if start.is_some_and(|s| closed < s) || end.is_some_and(|e| closed > e) {
+            continue;
+        }
+
+        let outcome = deal.deal_stage.as_deref().unwrap_or_default();
+        let value = deal.deal_value.unwrap_or(0.0);
+        if outcome == "won" {
+            report.won_count += 1;
+            report.won_value += value;
+        } else {
+            report.lost_count += 1;
+            report.lost_value += value;
+        }
+
+        match deal.deal_close_reason.as_deref() {
+            Some(code) => {
+                let label = labels
+                    .get(&(outcome.to_string(), code.to_string()))
+                    .cloned()
+                    .unwrap_or_else(|| code.to_string());
+                add_to(&mut report.by_reason, code, &label, outcome, value);
+            }
+            None => report.missing_reason += 1,
+        }
+
+        if outcome == "lost" {
+            let stage = deal.deal_stage_at_close.as_deref().unwrap_or("unknown");
+            add_to(&mut report.lost_by_stage, stage, stage, outcome, value);
+        }
+
+        if let Some(competitor) = deal.deal_competitor.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            add_to(&mut report.by_competitor, &competitor.to_lowercase(), competitor, outcome, value);
+        }
+    }
+
+    let total = report.won_count + report.lost_count;
+    report.win_rate = if total > 0 { Some(report.won_count as f64 / total as f64) } else { None };
+    for list in [&mut report.by_reason, &mut report.lost_by_stage, &mut report.by_competitor] {
+        list.sort_by(|a, b| b.count.cmp(&a.count).then(b.value.total_cmp(&a.value)));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_periods() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+
+        assert_eq!(parse_period("all", today), Ok((None, None)));
+        assert_eq!(parse_period("30d", today), Ok((d(2026, 9, 16), Some(today))));
+        assert_eq!(parse_period("ytd", today), Ok((d(2026, 1, 1), Some(today))));
+        assert_eq!(parse_period("2025", today), Ok((d(2025, 1, 1), d(2025, 12, 31))));
+        assert_eq!(parse_period("2026-Q4", today), Ok((d(2026, 10, 1), d(2026, 12, 31))));
+        assert_eq!(parse_period("2024-02", today), Ok((d(2024, 2, 1), d(2024, 2, 29))));
+        assert!(parse_period("2026-Q5", today).is_err());
+        assert!(parse_period("lastweek", today).is_err());
+    }
+}
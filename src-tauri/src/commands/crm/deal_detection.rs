@@ -0,0 +1,309 @@
+// CRM Module - Deal detection from inbound email
+// When crm_auto_link_email links a new email to a company that has no open
+// deal, the email is checked for buying signals (pricing questions, demo or
+// trial requests, RFPs). A cheap keyword pass decides whether it's worth
+// asking the model; if the model agrees it's a new opportunity, a proposed
+// deal (name, stage, value) is queued in crm_deal_suggestions for one-click
+// acceptance. Nothing is created without a person accepting it.
+
+use super::meeting_notes::{generate, parse_model_output};
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::compose::html_to_text;
+use crate::commands::outlook::db::EmailDb;
+use crate::commands::outlook::sync::ensure_body_cached;
+use crate::commands::settings;
+use crate::commands::supabase::{get_client, SupabaseClient};
+use crate::commands::work::{work_create_project, CreateProject, Project};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Phrases per signal, matched as whole words on lowercased text
+const SIGNALS: &[(&str, &[&str])] = &[
+    ("pricing", &["pricing", "price", "prices", "how much", "cost", "costs", "budget", "rates"]),
+    ("quote", &["quote", "quotation", "rfq", "estimate"]),
+    ("demo", &["demo", "demonstration", "walkthrough", "walk through", "show us", "presentation"]),
+    ("trial", &["trial", "pilot", "poc", "proof of concept", "try it", "test it"]),
+    ("proposal", &["proposal", "rfp", "tender", "scope of work", "sow"]),
+    ("purchase", &["purchase", "buy", "order form", "subscribe", "subscription", "license", "licence", "onboard"]),
+];
+const STAGES: [&str; 4] = ["lead", "qualified", "pilot", "proposal"];
+const SOLUTIONS: [&str; 8] = [
+    "ap_automation", "ar_automation", "free_invoice_scan", "analytics",
+    "revenue_reconciliation", "professional_services", "partnership", "data_extraction",
+];
+/// Deals past these stages are closed; anything else means the company already has one open
+const CLOSED_STAGES: &str = "(won,lost,passive)";
+/// Below this the model's "yes" isn't worth a suggestion
+const MIN_CONFIDENCE: f64 = 0.5;
+const MAX_BODY_CHARS: usize = 8_000;
+
+#[derive(Debug, Deserialize)]
+struct Verdict {
+    is_opportunity: bool,
+    #[serde(default)]
+    confidence: f64,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    deal_name: Option<String>,
+    #[serde(default)]
+    stage: Option<String>,
+    #[serde(default)]
+    value: Option<f64>,
+    #[serde(default)]
+    currency: Option<String>,
+    #[serde(default)]
+    solution: Option<String>,
+}
+
+/// Signals whose phrases appear in `text`, in SIGNALS order
+fn detect_signals(text: &str) -> Vec<&'static str> {
+    let words: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let padded = format!(" {} ", words.split_whitespace().collect::<Vec<_>>().join(" "));
+    SIGNALS
+        .iter()
+        .filter(|(_, phrases)| phrases.iter().any(|p| padded.contains(&format!(" {} ", p))))
+        .map(|(signal, _)| *signal)
+        .collect()
+}
+
+/// Keep a model-proposed value only if it's one we know
+fn known(value: Option<String>, allowed: &[&str]) -> Option<String> {
+    value
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| allowed.contains(&v.as_str()))
+}
+
+/// Check a freshly linked email for a new opportunity and queue a suggestion.
+/// Returns None when the email was skipped or the model saw no opportunity.
+pub(super) async fn detect_deal_opportunity(
+    client: &SupabaseClient,
+    link: &EmailCompanyLink,
+) -> CmdResult<Option<DealSuggestion>> {
+    let existing: Option<DealSuggestion> = client
+        .select_single("crm_deal_suggestions", &format!("email_id=eq.{}", urlencoding::encode(&link.email_id)))
+        .await?;
+    if existing.is_some() {
+        return Ok(existing);
+    }
+
+    let open_deals: Vec<serde_json::Value> = client
+        .select(
+            "projects",
+            &format!(
+                "select=id&project_type=eq.deal&company_id=eq.{}&deal_stage=not.in.{}&limit=1",
+                link.company_id, CLOSED_STAGES
+            ),
+        )
+        .await?;
+    if !open_deals.is_empty() {
+        return Ok(None);
+    }
+
+    let db = EmailDb::open()?;
+    let Some(email) = db.get_email(&link.email_id)? else {
+        return Ok(None);
+    };
+    if email.folder_name.eq_ignore_ascii_case("Sent Items") {
+        return Ok(None);
+    }
+    let body = match ensure_body_cached(&db, &email.id).await {
+        Ok(html) => html_to_text(&html),
+        Err(_) => email.body_preview.clone(),
+    };
+    let body: String = body.chars().take(MAX_BODY_CHARS).collect();
+
+    let signals = detect_signals(&format!("{}\n{}", email.subject, body));
+    if signals.is_empty() {
+        return Ok(None);
+    }
+    let Some(api_key) = settings::settings_get_anthropic_key()? else {
+        return Ok(None);
+    };
+
+    let company: Option<Company> = client
+        .select_single("crm_companies", &format!("id=eq.{}", link.company_id))
+        .await?;
+    let company_name = company.as_ref().map(|c| c.name.as_str()).unwrap_or("unknown");
+    let system = format!(
+        "You review inbound sales email for a B2B finance-automation company. Decide whether the email opens a \
+        NEW sales opportunity (the sender asks about pricing, wants a demo or trial, requests a quote or proposal, \
+        or wants to buy) — not support requests, invoices, newsletters or replies about existing work. Respond \
+        with only a JSON object: {{\"is_opportunity\": bool, \"confidence\": 0-1, \"reason\": one sentence, \
+        \"deal_name\": short deal name like \"<Company> — <what they want>\", \"stage\": one of {}, \"value\": \
+        estimated deal value as a number if the email states or clearly implies one else null, \"currency\": \
+        ISO code if known else null, \"solution\": one of {} or null}}. Do not invent values.",
+        STAGES.join("|"),
+        SOLUTIONS.join("|"),
+    );
+    let prompt = format!(
+        "Company: {}\nFrom: {} <{}>\nSubject: {}\nKeyword signals: {}\n\n{}",
+        company_name,
+        email.from_name,
+        email.from_email,
+        email.subject,
+        signals.join(", "),
+        body
+    );
+    let verdict: Verdict = parse_model_output(&generate(&api_key, &system, &prompt).await?)?;
+    if !verdict.is_opportunity || verdict.confidence < MIN_CONFIDENCE {
+        return Ok(None);
+    }
+
+    let row = json!({
+        "email_id": link.email_id,
+        "company_id": link.company_id,
+        "contact_id": link.contact_id,
+        "email_subject": email.subject,
+        "sender_email": email.from_email,
+        "signals": signals,
+        "reason": verdict.reason,
+        "confidence": verdict.confidence.clamp(0.0, 1.0),
+        "suggested_name": verdict
+            .deal_name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| format!("{} — {}", company_name, email.subject)),
+        "suggested_stage": known(verdict.stage, &STAGES).unwrap_or_else(|| "lead".to_string()),
+        "suggested_value": verdict.value.filter(|v| *v > 0.0),
+        "suggested_currency": verdict.currency.map(|c| c.trim().to_uppercase()).filter(|c| c.len() == 3),
+        "suggested_solution": known(verdict.solution, &SOLUTIONS),
+    });
+    Ok(Some(client.insert("crm_deal_suggestions", &row).await?))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Deal suggestions, newest first. `status` defaults to "pending".
+#[tauri::command]
+pub async fn crm_list_deal_suggestions(
+    status: Option<String>,
+    company_id: Option<String>,
+    limit: Option<i32>,
+) -> CmdResult<Vec<DealSuggestion>> {
+    let client = get_client().await?;
+
+    let mut filters = vec![
+        "select=*,company:crm_companies(*)".to_string(),
+        format!("status=eq.{}", status.unwrap_or_else(|| "pending".to_string())),
+    ];
+    if let Some(cid) = company_id {
+        filters.push(format!("company_id=eq.{}", cid));
+    }
+    filters.push(format!("limit={}", limit.unwrap_or(50)));
+    filters.push("order=created_at.desc".to_string());
+
+    client.select("crm_deal_suggestions", &filters.join("&")).await
+}
+
+/// Create the suggested deal (with any edits), mark the suggestion accepted
+/// and attach the email's activity to the new deal
+#[tauri::command]
+pub async fn crm_accept_deal_suggestion(
+    suggestion_id: String,
+    edits: Option<AcceptDealSuggestion>,
+) -> CmdResult<Project> {
+    let client = get_client().await?;
+    let suggestion: DealSuggestion = client
+        .select_single("crm_deal_suggestions", &format!("id=eq.{}", suggestion_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Deal suggestion not found: {}", suggestion_id)))?;
+    if suggestion.status != "pending" {
+        return Err(CommandError::Validation {
+            message: format!("Suggestion is already {}", suggestion.status),
+            fields: vec!["suggestion_id".to_string()],
+        });
+    }
+
+    let edits = edits.unwrap_or_default();
+    let deal = work_create_project(CreateProject {
+        name: edits.name.unwrap_or(suggestion.suggested_name),
+        project_type: Some("deal".to_string()),
+        company_id: Some(suggestion.company_id.clone()),
+        deal_stage: Some(edits.stage.unwrap_or(suggestion.suggested_stage)),
+        deal_value: edits.value.or(suggestion.suggested_value),
+        deal_currency: edits.currency.or(suggestion.suggested_currency),
+        deal_solution: edits.solution.or(suggestion.suggested_solution),
+        deal_notes: suggestion.reason.map(|r| format!("Detected from email: {}", r)),
+        ..Default::default()
+    })
+    .await?;
+
+    let _: DealSuggestion = client
+        .update(
+            "crm_deal_suggestions",
+            &format!("id=eq.{}", suggestion_id),
+            &json!({
+                "status": "accepted",
+                "deal_id": deal.id,
+                "resolved_at": chrono::Utc::now().to_rfc3339(),
+            }),
+        )
+        .await?;
+
+    // The email activity was logged when the email was linked; file it under the deal
+    let attached: CmdResult<Activity> = client
+        .update(
+            "crm_activities",
+            &format!(
+                "email_id=eq.{}&company_id=eq.{}",
+                urlencoding::encode(&suggestion.email_id),
+                suggestion.company_id
+            ),
+            &json!({ "project_id": deal.id }),
+        )
+        .await;
+    if let Err(e) = attached {
+        eprintln!("[crm:deal-detection] Deal {} created but email activity not attached: {}", deal.id, e);
+    }
+
+    Ok(deal)
+}
+
+/// Dismiss a suggestion (kept, so the same email is never suggested again)
+#[tauri::command]
+pub async fn crm_dismiss_deal_suggestion(suggestion_id: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    let _: DealSuggestion = client
+        .update(
+            "crm_deal_suggestions",
+            &format!("id=eq.{}&status=eq.pending", suggestion_id),
+            &json!({
+                "status": "dismissed",
+                "resolved_at": chrono::Utc::now().to_rfc3339(),
+            }),
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_buying_signals_as_whole_words() {
+        assert_eq!(
+            detect_signals("Re: Pricing?\nCould you share prices and set up a demo next week?"),
+            vec!["pricing", "demo"]
+        );
+        assert_eq!(detect_signals("We'd like to run a POC — please send a proposal."), vec!["trial", "proposal"]);
+        // Substrings don't count: "pocket", "priceless", "democracy"
+        assert!(detect_signals("A priceless pocket guide to democracy").is_empty());
+        assert!(detect_signals("Invoice #1042 attached, thanks").is_empty());
+    }
+
+    #[test]
+    fn keeps_only_known_stages_and_solutions() {
+        assert_eq!(known(Some(" Pilot ".to_string()), &STAGES).as_deref(), Some("pilot"));
+        assert_eq!(known(Some("won".to_string()), &STAGES), None);
+        assert_eq!(known(Some("analytics".to_string()), &SOLUTIONS).as_deref(), Some("analytics"));
+        assert_eq!(known(None, &SOLUTIONS), None);
+    }
+}
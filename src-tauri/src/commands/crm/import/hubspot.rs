@@ -0,0 +1,1070 @@
+// CRM Module - HubSpot import
+// Pulls companies, contacts, deals and notes from HubSpot (private app token)
+// into crm_companies / crm_contacts / projects (deals) / crm_activities.
+// crm_external_ids maps HubSpot IDs to local rows so re-runs upsert instead of
+// duplicating. A record changed in HubSpot that was also edited locally since
+// the last import is left alone and reported as a conflict. Incremental pulls
+// ask the CRM search API for records modified since the last run, and the
+// cursor never moves past a record that failed or conflicted.
+
+use crate::commands::crm::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings::{self, load_settings, KEY_HUBSPOT_API};
+use crate::commands::supabase::{get_client, like_literal, SupabaseClient};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Instant;
+use tauri::Emitter;
+
+const HUBSPOT_BASE: &str = "https://api.hubapi.com";
+const SOURCE: &str = "hubspot";
+/// HubSpot list endpoints cap `limit` at 100
+const HUBSPOT_PAGE: usize = 100;
+/// The search API stops paging after this many results per query
+const SEARCH_RESULT_CAP: usize = 10_000;
+/// PostgREST default max rows
+const DB_PAGE: usize = 1000;
+
+const COMPANY_PROPS: [&str; 7] = ["name", "domain", "website", "industry", "numberofemployees", "annualrevenue", "description"];
+const CONTACT_PROPS: [&str; 6] = ["firstname", "lastname", "email", "phone", "jobtitle", "hs_linkedin_url"];
+const DEAL_PROPS: [&str; 7] = ["dealname", "amount", "dealstage", "pipeline", "closedate", "deal_currency_code", "closed_lost_reason"];
+const NOTE_PROPS: [&str; 2] = ["hs_note_body", "hs_timestamp"];
+
+// ============================================================================
+// HubSpot API
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ListResponse {
+    results: Vec<HubspotObject>,
+    paging: Option<Paging>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Paging {
+    next: Option<PagingNext>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagingNext {
+    after: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HubspotObject {
+    id: String,
+    #[serde(default)]
+    properties: HashMap<String, Option<String>>,
+    updated_at: Option<String>,
+    #[serde(default)]
+    associations: HashMap<String, AssociationList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssociationList {
+    results: Vec<AssociationRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssociationRef {
+    id: String,
+}
+
+/// Response of the v4 associations batch read
+#[derive(Debug, Deserialize)]
+struct AssociationBatch {
+    results: Vec<AssociationBatchRow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssociationBatchRow {
+    from: AssociationRef,
+    #[serde(default)]
+    to: Vec<AssociationTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssociationTarget {
+    to_object_id: u64,
+}
+
+/// Search property holding an object's last-modified time (contacts predate the hs_ prefix)
+fn modified_property(object: &str) -> &'static str {
+    if object == "contacts" {
+        "lastmodifieddate"
+    } else {
+        "hs_lastmodifieddate"
+    }
+}
+
+impl HubspotObject {
+    fn prop(&self, key: &str) -> Option<&str> {
+        self.properties
+            .get(key)
+            .and_then(|v| v.as_deref())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// IDs of associated objects of a kind ("companies", "contacts", "deals")
+    fn associated(&self, kind: &str) -> Vec<&str> {
+        self.associations
+            .get(kind)
+            .map(|a| a.results.iter().map(|r| r.id.as_str()).collect())
+            .unwrap_or_default()
+    }
+}
+
+struct HubspotClient {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl HubspotClient {
+    fn new() -> CmdResult<Self> {
+        let token = load_settings()?
+            .keys
+            .get(KEY_HUBSPOT_API)
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .ok_or_else(|| CommandError::Config("HubSpot private app token not configured".into()))?;
+        Ok(Self {
+            client: crate::HTTP_CLIENT.clone(),
+            token,
+        })
+    }
+
+    /// Page through every non-archived object of a type
+    async fn list_all(
+        &self,
+        object: &str,
+        properties: &[&str],
+        associations: &[&str],
+    ) -> CmdResult<Vec<HubspotObject>> {
+        let mut out = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let mut url = format!(
+                "{}/crm/v3/objects/{}?limit={}&archived=false&properties={}",
+                HUBSPOT_BASE,
+                object,
+                HUBSPOT_PAGE,
+                properties.join(",")
+            );
+            if !associations.is_empty() {
+                url.push_str(&format!("&associations={}", associations.join(",")));
+            }
+            if let Some(a) = &after {
+                url.push_str(&format!("&after={}", a));
+            }
+
+            let response = self.client.get(&url).bearer_auth(&self.token).send().await?;
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let body = response.text().await.unwrap_or_default();
+                return Err(CommandError::Http { status, body });
+            }
+            let page: ListResponse = response
+                .json()
+                .await
+                .map_err(|e| CommandError::Parse(format!("Failed to parse HubSpot {} page: {}", object, e)))?;
+
+            out.extend(page.results);
+            match page.paging.and_then(|p| p.next) {
+                Some(next) => after = Some(next.after),
+                None => break,
+            }
+        }
+        Ok(out)
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str, body: &Value, what: &str) -> CmdResult<T> {
+        let response = self
+            .client
+            .post(format!("{}{}", HUBSPOT_BASE, path))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status, body });
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| CommandError::Parse(format!("Failed to parse HubSpot {}: {}", what, e)))
+    }
+
+    /// Objects of a type modified after `since`, through the CRM search API.
+    /// Search results carry no associations, so those are batch-read after.
+    async fn search_modified(
+        &self,
+        object: &str,
+        properties: &[&str],
+        associations: &[&str],
+        since: &chrono::DateTime<chrono::FixedOffset>,
+    ) -> CmdResult<Vec<HubspotObject>> {
+        let property = modified_property(object);
+        let mut out: Vec<HubspotObject> = Vec::new();
+        let mut seen = HashSet::new();
+        let (mut operator, mut floor) = ("GT", since.timestamp_millis());
+        let mut after: Option<String> = None;
+        loop {
+            let mut body = json!({
+                "filterGroups": [{ "filters": [{ "propertyName": property, "operator": operator, "value": floor.to_string() }] }],
+                "sorts": [{ "propertyName": property, "direction": "ASCENDING" }],
+                "properties": properties,
+                "limit": HUBSPOT_PAGE,
+            });
+            if let Some(a) = &after {
+                body["after"] = json!(a);
+            }
+            let page: ListResponse = self
+                .post(&format!("/crm/v3/objects/{}/search", object), &body, &format!("{} search page", object))
+                .await?;
+
+            let last_modified = page
+                .results
+                .last()
+                .and_then(|o| o.updated_at.as_deref())
+                .and_then(|u| chrono::DateTime::parse_from_rfc3339(u).ok())
+                .map(|u| u.timestamp_millis());
+            out.extend(page.results.into_iter().filter(|o| seen.insert(o.id.clone())));
+            match page.paging.and_then(|p| p.next) {
+                Some(next) if next.after.parse::<usize>().unwrap_or(0) < SEARCH_RESULT_CAP => after = Some(next.after),
+                // Past the cap: start a new query from the last timestamp seen
+                Some(_) => match last_modified {
+                    Some(t) if t > floor || operator == "GT" => {
+                        (operator, floor) = ("GTE", t);
+                        after = None;
+                    }
+                    _ => break,
+                },
+                None => break,
+            }
+        }
+
+        for kind in associations {
+            let mut linked: HashMap<String, Vec<AssociationRef>> = HashMap::new();
+            for chunk in out.chunks(HUBSPOT_PAGE) {
+                let inputs: Vec<Value> = chunk.iter().map(|o| json!({ "id": o.id })).collect();
+                let batch: AssociationBatch = self
+                    .post(
+                        &format!("/crm/v4/associations/{}/{}/batch/read", object, kind),
+                        &json!({ "inputs": inputs }),
+                        &format!("{} → {} associations", object, kind),
+                    )
+                    .await?;
+                for row in batch.results {
+                    linked.entry(row.from.id).or_default().extend(
+                        row.to.into_iter().map(|t| AssociationRef { id: t.to_object_id.to_string() }),
+                    );
+                }
+            }
+            for obj in &mut out {
+                let results = linked.remove(&obj.id).unwrap_or_default();
+                obj.associations.insert(kind.to_string(), AssociationList { results });
+            }
+        }
+        Ok(out)
+    }
+
+    /// Everything (`since` = None) or only what changed since the last pull
+    async fn fetch(
+        &self,
+        object: &str,
+        properties: &[&str],
+        associations: &[&str],
+        since: Option<&chrono::DateTime<chrono::FixedOffset>>,
+    ) -> CmdResult<Vec<HubspotObject>> {
+        match since {
+            Some(since) => self.search_modified(object, properties, associations, since).await,
+            None => self.list_all(object, properties, associations).await,
+        }
+    }
+}
+
+// ============================================================================
+// Config
+// ============================================================================
+
+fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("crm")
+        .join("hubspot_import.json")
+}
+
+fn load_config() -> CmdResult<HubspotImportConfig> {
+    let path = config_path();
+    if !path.exists() {
+        return Ok(HubspotImportConfig::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(&path)?)?)
+}
+
+fn save_config(config: &HubspotImportConfig) -> CmdResult<()> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+// ============================================================================
+// Mapping helpers
+// ============================================================================
+
+/// Local deal_stage for a HubSpot pipeline/stage. HubSpot's built-in
+/// closedwon/closedlost map to won/lost unless the config says otherwise.
+pub fn map_deal_stage(config: &HubspotImportConfig, pipeline: Option<&str>, stage: Option<&str>) -> String {
+    let mapping = pipeline.and_then(|p| config.pipelines.get(p));
+    if let (Some(m), Some(s)) = (mapping, stage) {
+        if let Some(local) = m.stages.get(s) {
+            return local.clone();
+        }
+    }
+    match stage {
+        Some("closedwon") => "won".to_string(),
+        Some("closedlost") => "lost".to_string(),
+        _ => mapping
+            .and_then(|m| m.default_stage.clone())
+            .unwrap_or_else(|| config.default_deal_stage.clone()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncAction {
+    Create,
+    Update,
+    Unchanged,
+    Conflict,
+}
+
+/// Decide what to do with a HubSpot record given its link (if any) and the
+/// local row's updated_at. Unparseable timestamps err towards updating.
+pub fn decide_action(link: Option<&ExternalLink>, external_updated: Option<&str>, local_updated: Option<&str>) -> SyncAction {
+    let Some(link) = link else {
+        return SyncAction::Create;
+    };
+    let ts = |s: Option<&str>| s.and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok());
+
+    let external_changed = match (ts(external_updated), ts(link.external_updated_at.as_deref())) {
+        (Some(now), Some(before)) => now > before,
+        _ => true,
+    };
+    if !external_changed {
+        return SyncAction::Unchanged;
+    }
+    let local_edited = match (ts(local_updated), ts(Some(&link.synced_at))) {
+        (Some(local), Some(synced)) => local > synced,
+        _ => false,
+    };
+    if local_edited {
+        SyncAction::Conflict
+    } else {
+        SyncAction::Update
+    }
+}
+
+/// HubSpot note bodies are HTML — keep line breaks, drop tags
+fn note_text(html: &str) -> String {
+    static TAG_RE: once_cell::sync::Lazy<regex::Regex> =
+        once_cell::sync::Lazy::new(|| regex::Regex::new(r"<[^>]+>").unwrap());
+    let with_breaks = html
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("</p>", "\n");
+    TAG_RE
+        .replace_all(&with_breaks, "")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .trim()
+        .to_string()
+}
+
+fn str_field(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Object-only properties (skip None so updates never blank local fields)
+fn compact(pairs: Vec<(&str, Option<Value>)>) -> Value {
+    Value::Object(
+        pairs
+            .into_iter()
+            .filter_map(|(k, v)| v.map(|v| (k.to_string(), v)))
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Import run
+// ============================================================================
+
+/// What happened to one record (for the result counts)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Created,
+    Updated,
+    Linked,
+    Unchanged,
+    Skipped,
+    Conflict,
+}
+
+/// One record to write into a local table
+struct Upsert<'a> {
+    entity_type: &'a str,
+    table: &'a str,
+    name: String,
+    /// Fields written on both create and update
+    payload: Value,
+    /// Extra fields only set on create (defaults, source tracking)
+    create_extra: Value,
+    /// Filter for an unlinked local row that is the same record (e.g. same email)
+    match_filter: Option<String>,
+}
+
+struct ImportRun<'a> {
+    client: SupabaseClient,
+    config: &'a HubspotImportConfig,
+    dry_run: bool,
+    since: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Earliest HubSpot updatedAt among records that failed or conflicted;
+    /// the next incremental pull starts just before it so they're retried
+    retry_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    result: HubspotImportResult,
+}
+
+impl ImportRun<'_> {
+    async fn load_links(&self, entity_type: &str) -> CmdResult<HashMap<String, ExternalLink>> {
+        let mut out = HashMap::new();
+        let mut offset = 0;
+        loop {
+            let page: Vec<ExternalLink> = self
+                .client
+                .select(
+                    "crm_external_ids",
+                    &format!(
+                        "source=eq.{}&entity_type=eq.{}&order=id.asc&limit={}&offset={}",
+                        SOURCE, entity_type, DB_PAGE, offset
+                    ),
+                )
+                .await?;
+            let n = page.len();
+            out.extend(page.into_iter().map(|l| (l.external_id.clone(), l)));
+            if n < DB_PAGE {
+                break;
+            }
+            offset += n;
+        }
+        Ok(out)
+    }
+
+    /// id → last-modified timestamp for every row of a local table
+    async fn load_local(&self, table: &str, modified_col: &str) -> CmdResult<HashMap<String, Option<String>>> {
+        let mut out = HashMap::new();
+        let mut offset = 0;
+        loop {
+            let page: Vec<Value> = self
+                .client
+                .select(
+                    table,
+                    &format!("select=id,{}&order=id.asc&limit={}&offset={}", modified_col, DB_PAGE, offset),
+                )
+                .await?;
+            let n = page.len();
+            for row in page {
+                if let Some(id) = str_field(&row, "id") {
+                    out.insert(id, str_field(&row, modified_col));
+                }
+            }
+            if n < DB_PAGE {
+                break;
+            }
+            offset += n;
+        }
+        Ok(out)
+    }
+
+    fn modified_since(&self, obj: &HubspotObject) -> bool {
+        match (&self.since, obj.updated_at.as_deref().and_then(|u| chrono::DateTime::parse_from_rfc3339(u).ok())) {
+            (Some(since), Some(updated)) => updated > *since,
+            _ => true,
+        }
+    }
+
+    fn conflict(&mut self, entity_type: &str, obj: &HubspotObject, name: &str, local_id: Option<&str>, reason: &str) {
+        if let Some(updated) = obj.updated_at.as_deref().and_then(|u| chrono::DateTime::parse_from_rfc3339(u).ok()) {
+            self.retry_from = Some(self.retry_from.map_or(updated, |r| r.min(updated)));
+        }
+        self.result.conflicts.push(ImportConflict {
+            entity_type: entity_type.to_string(),
+            external_id: obj.id.clone(),
+            name: name.to_string(),
+            local_id: local_id.map(String::from),
+            reason: reason.to_string(),
+        });
+    }
+
+    async fn save_link(&self, entity_type: &str, obj: &HubspotObject, local_id: &str, local_updated: Option<String>) -> CmdResult<()> {
+        // synced_at mirrors the local row's own updated_at so later local edits
+        // compare against the database clock, not ours
+        let row = json!({
+            "source": SOURCE,
+            "entity_type": entity_type,
+            "external_id": obj.id,
+            "local_id": local_id,
+            "external_updated_at": obj.updated_at,
+            "synced_at": local_updated.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        });
+        let _: Value = self
+            .client
+            .upsert_on("crm_external_ids", &row, Some("source,entity_type,external_id"))
+            .await?;
+        Ok(())
+    }
+
+
+    /// Create, update, link or skip one record. Write failures are reported
+    /// as conflicts so one bad record doesn't abort a migration.
+    async fn apply(
+        &mut self,
+        obj: &HubspotObject,
+        op: Upsert<'_>,
+        links: &HashMap<String, ExternalLink>,
+        local: &HashMap<String, Option<String>>,
+    ) -> Option<String> {
+        let entity_type = op.entity_type;
+        let name = op.name.clone();
+        let (local_id, outcome) = match self.upsert(obj, op, links, local).await {
+            Ok(r) => r,
+            Err(e) => {
+                self.conflict(entity_type, obj, &name, None, &format!("Write failed: {}", e));
+                (None, Outcome::Conflict)
+            }
+        };
+        self.tally(entity_type, outcome);
+        local_id
+    }
+
+    fn tally(&mut self, entity_type: &str, outcome: Outcome) {
+        let counts = match entity_type {
+            "company" => &mut self.result.companies,
+            "contact" => &mut self.result.contacts,
+            "deal" => &mut self.result.deals,
+            _ => &mut self.result.notes,
+        };
+        match outcome {
+            Outcome::Created => counts.created += 1,
+            Outcome::Updated => counts.updated += 1,
+            Outcome::Linked => counts.linked += 1,
+            Outcome::Unchanged => counts.unchanged += 1,
+            Outcome::Skipped => counts.skipped += 1,
+            Outcome::Conflict => {} // listed in result.conflicts
+        }
+    }
+
+    async fn upsert(
+        &mut self,
+        obj: &HubspotObject,
+        op: Upsert<'_>,
+        links: &HashMap<String, ExternalLink>,
+        local: &HashMap<String, Option<String>>,
+    ) -> CmdResult<(Option<String>, Outcome)> {
+        let link = links.get(&obj.id);
+        if let Some(l) = link {
+            if !local.contains_key(&l.local_id) {
+                self.conflict(op.entity_type, obj, &op.name, Some(&l.local_id), "Linked local record was deleted");
+                return Ok((None, Outcome::Conflict));
+            }
+        }
+        let local_updated = link.and_then(|l| local.get(&l.local_id).cloned().flatten());
+
+        match decide_action(link, obj.updated_at.as_deref(), local_updated.as_deref()) {
+            SyncAction::Unchanged => Ok((link.map(|l| l.local_id.clone()), Outcome::Unchanged)),
+            SyncAction::Conflict => {
+                let local_id = link.map(|l| l.local_id.clone());
+                self.conflict(
+                    op.entity_type,
+                    obj,
+                    &op.name,
+                    local_id.as_deref(),
+                    "Changed in HubSpot and edited locally since last import",
+                );
+                Ok((local_id, Outcome::Conflict))
+            }
+            SyncAction::Update => {
+                let local_id = link.map(|l| l.local_id.clone()).unwrap_or_default();
+                if !self.dry_run {
+                    let row: Value = self
+                        .client
+                        .update(op.table, &format!("id=eq.{}", local_id), &op.payload)
+                        .await?;
+                    self.save_link(op.entity_type, obj, &local_id, str_field(&row, "updated_at")).await?;
+                }
+                Ok((Some(local_id), Outcome::Updated))
+            }
+            SyncAction::Create => {
+                if let Some(filter) = &op.match_filter {
+                    let matches: Vec<Value> = self
+                        .client
+                        .select(op.table, &format!("select=id,updated_at&{}", filter))
+                        .await?;
+                    if matches.len() > 1 {
+                        let reason = format!("{} local records match — link one manually", matches.len());
+                        self.conflict(op.entity_type, obj, &op.name, None, &reason);
+                        return Ok((None, Outcome::Conflict));
+                    }
+                    if let Some(row) = matches.first() {
+                        // Link the existing record; local data stays as-is until HubSpot changes
+                        let local_id = str_field(row, "id").unwrap_or_default();
+                        if !self.dry_run {
+                            self.save_link(op.entity_type, obj, &local_id, str_field(row, "updated_at")).await?;
+                        }
+                        return Ok((Some(local_id), Outcome::Linked));
+                    }
+                }
+                if self.dry_run {
+                    return Ok((None, Outcome::Created));
+                }
+
+                let mut data = op.payload;
+                if let (Some(fields), Value::Object(extra)) = (data.as_object_mut(), op.create_extra) {
+                    fields.extend(extra);
+                }
+                let row: Value = self.client.insert(op.table, &data).await?;
+                let local_id = str_field(&row, "id")
+                    .ok_or_else(|| CommandError::Internal(format!("Insert into {} returned no id", op.table)))?;
+                self.save_link(op.entity_type, obj, &local_id, str_field(&row, "updated_at")).await?;
+                Ok((Some(local_id), Outcome::Created))
+            }
+        }
+    }
+
+    /// Known HubSpot ID → local ID, seeded from existing links
+    fn id_map(links: &HashMap<String, ExternalLink>) -> HashMap<String, String> {
+        links.iter().map(|(k, l)| (k.clone(), l.local_id.clone())).collect()
+    }
+
+    async fn import_companies(&mut self, objects: &[HubspotObject]) -> CmdResult<HashMap<String, String>> {
+        let links = self.load_links("company").await?;
+        let local = self.load_local("crm_companies", "updated_at").await?;
+        let mut ids = Self::id_map(&links);
+
+        for obj in objects {
+            if !self.modified_since(obj) {
+                continue;
+            }
+            let Some(name) = obj.prop("name").or(obj.prop("domain")).map(String::from) else {
+                self.tally("company", Outcome::Skipped);
+                continue;
+            };
+            let website = obj
+                .prop("website")
+                .map(String::from)
+                .or_else(|| obj.prop("domain").map(|d| format!("https://{}", d)));
+            let op = Upsert {
+                entity_type: "company",
+                table: "crm_companies",
+                payload: compact(vec![
+                    ("name", Some(json!(name))),
+                    ("website", website.map(Value::from)),
+                    ("industry", obj.prop("industry").map(Value::from)),
+                    (
+                        "employee_count",
+                        obj.prop("numberofemployees")
+                            .and_then(|v| v.parse::<f64>().ok())
+                            .map(|v| json!(v as i64)),
+                    ),
+                    (
+                        "annual_revenue",
+                        obj.prop("annualrevenue").and_then(|v| v.parse::<f64>().ok()).map(Value::from),
+                    ),
+                ]),
+                create_extra: compact(vec![
+                    ("stage", Some(json!(self.config.company_stage))),
+                    ("source", Some(json!(SOURCE))),
+                    ("source_id", Some(json!(obj.id))),
+                    ("notes", obj.prop("description").map(Value::from)),
+                ]),
+                match_filter: Some(format!("name=ilike.{}", urlencoding::encode(&like_literal(&name)))),
+                name,
+            };
+            if let Some(id) = self.apply(obj, op, &links, &local).await {
+                ids.insert(obj.id.clone(), id);
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn import_contacts(
+        &mut self,
+        objects: &[HubspotObject],
+        companies: &HashMap<String, String>,
+    ) -> CmdResult<HashMap<String, String>> {
+        let links = self.load_links("contact").await?;
+        let local = self.load_local("crm_contacts", "updated_at").await?;
+        let mut ids = Self::id_map(&links);
+
+        for obj in objects {
+            if !self.modified_since(obj) {
+                continue;
+            }
+            // Local contacts are keyed by email
+            let Some(email) = obj.prop("email").map(|e| e.to_lowercase()) else {
+                self.tally("contact", Outcome::Skipped);
+                continue;
+            };
+            let full_name = [obj.prop("firstname"), obj.prop("lastname")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let name = if full_name.is_empty() { email.clone() } else { full_name };
+            let company_id = obj
+                .associated("companies")
+                .iter()
+                .find_map(|c| companies.get(*c))
+                .cloned();
+
+            let op = Upsert {
+                entity_type: "contact",
+                table: "crm_contacts",
+                payload: compact(vec![
+                    ("name", Some(json!(name))),
+                    ("email", Some(json!(email))),
+                    ("phone", obj.prop("phone").map(Value::from)),
+                    ("role", obj.prop("jobtitle").map(Value::from)),
+                    ("linkedin_url", obj.prop("hs_linkedin_url").map(Value::from)),
+                    ("company_id", company_id.map(Value::from)),
+                ]),
+                create_extra: compact(vec![
+                    ("source", Some(json!(SOURCE))),
+                    ("source_id", Some(json!(obj.id))),
+                ]),
+                match_filter: Some(format!("email=eq.{}", urlencoding::encode(&email))),
+                name,
+            };
+            if let Some(id) = self.apply(obj, op, &links, &local).await {
+                ids.insert(obj.id.clone(), id);
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn import_deals(
+        &mut self,
+        objects: &[HubspotObject],
+        companies: &HashMap<String, String>,
+        contacts: &HashMap<String, String>,
+    ) -> CmdResult<HashMap<String, String>> {
+        let links = self.load_links("deal").await?;
+        let local = self.load_local("projects", "updated_at").await?;
+        let mut ids = Self::id_map(&links);
+
+        for obj in objects {
+            if !self.modified_since(obj) {
+                continue;
+            }
+            let Some(name) = obj.prop("dealname").map(String::from) else {
+                self.tally("deal", Outcome::Skipped);
+                continue;
+            };
+            let stage = map_deal_stage(self.config, obj.prop("pipeline"), obj.prop("dealstage"));
+            let closed = stage == "won" || stage == "lost";
+            let close_date = obj.prop("closedate").map(|d| d.get(..10).unwrap_or(d).to_string());
+            let company_id = obj
+                .associated("companies")
+                .iter()
+                .find_map(|c| companies.get(*c))
+                .cloned();
+            let contact_ids: Vec<&String> = obj
+                .associated("contacts")
+                .iter()
+                .filter_map(|c| contacts.get(*c))
+                .collect();
+
+            // Imported history bypasses close-reason validation; HubSpot's free-text
+            // lost reason lands in the legacy deal_lost_reason field
+            let op = Upsert {
+                entity_type: "deal",
+                table: "projects",
+                payload: compact(vec![
+                    ("name", Some(json!(name))),
+                    ("deal_stage", Some(json!(stage))),
+                    ("deal_value", obj.prop("amount").and_then(|v| v.parse::<f64>().ok()).map(Value::from)),
+                    (
+                        "deal_currency",
+                        Some(json!(obj.prop("deal_currency_code").unwrap_or(&self.config.default_currency))),
+                    ),
+                    ("company_id", company_id.map(Value::from)),
+                    ("deal_expected_close", close_date.clone().filter(|_| !closed).map(Value::from)),
+                    ("deal_actual_close", close_date.clone().filter(|_| closed).map(Value::from)),
+                    (
+                        "deal_lost_reason",
+                        obj.prop("closed_lost_reason").filter(|_| stage == "lost").map(Value::from),
+                    ),
+                    ("deal_contact_ids", (!contact_ids.is_empty()).then(|| json!(contact_ids))),
+                ]),
+                create_extra: json!({
+                    "project_type": "deal",
+                    "slug": format!("{}-{}", crate::commands::work::slugify(&name), obj.id),
+                    "identifier_prefix": "DEAL",
+                    "status": "active",
+                    "deal_stage_changed_at": close_date
+                        .filter(|_| closed)
+                        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                }),
+                match_filter: None,
+                name,
+            };
+            if let Some(id) = self.apply(obj, op, &links, &local).await {
+                ids.insert(obj.id.clone(), id);
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn import_notes(
+        &mut self,
+        objects: &[HubspotObject],
+        companies: &HashMap<String, String>,
+        contacts: &HashMap<String, String>,
+        deals: &HashMap<String, String>,
+    ) -> CmdResult<()> {
+        let links = self.load_links("note").await?;
+        // Activities have no updated_at — local edits to imported notes aren't detected
+        let local = self.load_local("crm_activities", "created_at").await?;
+
+        for obj in objects {
+            if !self.modified_since(obj) {
+                continue;
+            }
+            let first = |kind: &str, map: &HashMap<String, String>| {
+                obj.associated(kind).iter().find_map(|id| map.get(*id)).cloned()
+            };
+            let company_id = first("companies", companies);
+            let contact_id = first("contacts", contacts);
+            let project_id = first("deals", deals);
+            let body = obj.prop("hs_note_body").map(note_text).unwrap_or_default();
+            if body.is_empty() || (company_id.is_none() && contact_id.is_none() && project_id.is_none()) {
+                self.tally("note", Outcome::Skipped);
+                continue;
+            }
+
+            let op = Upsert {
+                entity_type: "note",
+                table: "crm_activities",
+                name: body.chars().take(60).collect(),
+                payload: compact(vec![
+                    ("type", Some(json!("note"))),
+                    ("subject", Some(json!("HubSpot note"))),
+                    ("content", Some(json!(body))),
+                    ("activity_date", obj.prop("hs_timestamp").map(Value::from)),
+                    ("company_id", company_id.map(Value::from)),
+                    ("contact_id", contact_id.map(Value::from)),
+                    ("project_id", project_id.map(Value::from)),
+                ]),
+                create_extra: json!({}),
+                match_filter: None,
+            };
+            self.apply(obj, op, &links, &local).await;
+        }
+        Ok(())
+    }
+}
+
+/// Run an import. Incremental (`full` = false) only fetches records modified
+/// in HubSpot since the last run. The cursor advances to the start of this
+/// run, or to just before the earliest record that failed or conflicted.
+async fn run_import(full: bool, dry_run: bool) -> CmdResult<HubspotImportResult> {
+    let start = Instant::now();
+    let mut config = load_config()?;
+    let hubspot = HubspotClient::new()?;
+    let pull_started = chrono::Utc::now().to_rfc3339();
+    let since = if full { None } else { config.last_pull_at.clone() };
+    let since_at = since.as_deref().and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+
+    let companies = hubspot.fetch("companies", &COMPANY_PROPS, &[], since_at.as_ref()).await?;
+    let contacts = hubspot.fetch("contacts", &CONTACT_PROPS, &["companies"], since_at.as_ref()).await?;
+    let deals = hubspot.fetch("deals", &DEAL_PROPS, &["companies", "contacts"], since_at.as_ref()).await?;
+    let notes = if config.import_notes {
+        hubspot
+            .fetch("notes", &NOTE_PROPS, &["companies", "contacts", "deals"], since_at.as_ref())
+            .await?
+    } else {
+        Vec::new()
+    };
+
+    let mut run = ImportRun {
+        client: get_client().await?,
+        config: &config,
+        dry_run,
+        since: since_at,
+        retry_from: None,
+        result: HubspotImportResult {
+            dry_run,
+            since,
+            ..Default::default()
+        },
+    };
+    let company_ids = run.import_companies(&companies).await?;
+    let contact_ids = run.import_contacts(&contacts, &company_ids).await?;
+    let deal_ids = run.import_deals(&deals, &company_ids, &contact_ids).await?;
+    run.import_notes(&notes, &company_ids, &contact_ids, &deal_ids).await?;
+
+    let retry_from = run.retry_from;
+    let mut result = run.result;
+    result.duration_ms = start.elapsed().as_millis() as u64;
+
+    if !dry_run {
+        config.last_pull_at = Some(match retry_from {
+            Some(t) => (t - chrono::Duration::seconds(1)).to_rfc3339(),
+            None => pull_started,
+        });
+        save_config(&config)?;
+    }
+    Ok(result)
+}
+
+/// Periodic incremental pull. Gated by the bg_sync_hubspot toggle and only
+/// runs once an initial import has set last_pull_at. Call from main.rs setup hook.
+pub fn start_hubspot_pull(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(90)).await;
+        loop {
+            let ready = settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_HUBSPOT)
+                && HubspotClient::new().is_ok()
+                && load_config().map(|c| c.last_pull_at.is_some()).unwrap_or(false);
+
+            if ready {
+                let started_at = chrono::Utc::now().to_rfc3339();
+                let id = format!("hubspot-pull-{}", chrono::Utc::now().timestamp_millis());
+                let (status, message) = match run_import(false, false).await {
+                    Ok(r) => (
+                        "completed",
+                        format!(
+                            "{} companies, {} contacts, {} deals, {} notes changed · {} conflicts",
+                            r.companies.created + r.companies.updated,
+                            r.contacts.created + r.contacts.updated,
+                            r.deals.created + r.deals.updated,
+                            r.notes.created + r.notes.updated,
+                            r.conflicts.len()
+                        ),
+                    ),
+                    Err(e) => {
+                        eprintln!("[crm:hubspot] Pull failed: {}", e);
+                        ("failed", e.to_string())
+                    }
+                };
+                let _ = app_handle.emit("jobs:update", json!({
+                    "id": id,
+                    "name": "HubSpot pull",
+                    "status": status,
+                    "message": message,
+                    "startedAt": started_at,
+                }));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(6 * 3600)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get the HubSpot import config (pipeline/stage mapping, defaults, last pull)
+#[tauri::command]
+pub fn crm_hubspot_get_config() -> CmdResult<HubspotImportConfig> {
+    load_config()
+}
+
+/// Save the HubSpot import config. `last_pull_at` is owned by the importer
+/// and always kept from disk.
+#[tauri::command]
+pub fn crm_hubspot_save_config(config: HubspotImportConfig) -> CmdResult<HubspotImportConfig> {
+    let current = load_config()?;
+    let config = HubspotImportConfig {
+        last_pull_at: current.last_pull_at,
+        ..config
+    };
+    save_config(&config)?;
+    Ok(config)
+}
+
+/// Import from HubSpot. `full` re-processes every record (the one-time
+/// migration); otherwise only records modified since the last run.
+/// `dry_run` reports what would change without writing.
+#[tauri::command]
+pub async fn crm_hubspot_import(full: Option<bool>, dry_run: Option<bool>) -> CmdResult<HubspotImportResult> {
+    run_import(full.unwrap_or(false), dry_run.unwrap_or(false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(external_updated_at: &str, synced_at: &str) -> ExternalLink {
+        ExternalLink {
+            id: "l1".to_string(),
+            source: SOURCE.to_string(),
+            entity_type: "company".to_string(),
+            external_id: "123".to_string(),
+            local_id: "local-1".to_string(),
+            external_updated_at: Some(external_updated_at.to_string()),
+            synced_at: synced_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn decides_create_update_unchanged_and_conflict() {
+        let l = link("2026-01-01T00:00:00.000Z", "2026-01-02T00:00:00+00:00");
+        assert_eq!(decide_action(None, None, None), SyncAction::Create);
+        // HubSpot unchanged
+        assert_eq!(
+            decide_action(Some(&l), Some("2026-01-01T00:00:00Z"), Some("2026-03-01T00:00:00+00:00")),
+            SyncAction::Unchanged
+        );
+        // HubSpot changed, local untouched since sync
+        assert_eq!(
+            decide_action(Some(&l), Some("2026-02-01T00:00:00Z"), Some("2026-01-02T00:00:00+00:00")),
+            SyncAction::Update
+        );
+        // Both changed
+        assert_eq!(
+            decide_action(Some(&l), Some("2026-02-01T00:00:00Z"), Some("2026-01-05T00:00:00+00:00")),
+            SyncAction::Conflict
+        );
+    }
+
+    #[test]
+    fn maps_stages_with_config_then_builtins_then_default() {
+        let mut config = HubspotImportConfig::default();
+        config.pipelines.insert(
+            "default".to_string(),
+            HubspotPipelineMapping {
+                stages: HashMap::from([("presentationscheduled".to_string(), "proposal".to_string())]),
+                default_stage: Some("qualified".to_string()),
+            },
+        );
+        assert_eq!(map_deal_stage(&config, Some("default"), Some("presentationscheduled")), "proposal");
+        assert_eq!(map_deal_stage(&config, Some("default"), Some("closedwon")), "won");
+        assert_eq!(map_deal_stage(&config, Some("default"), Some("appointmentscheduled")), "qualified");
+        assert_eq!(map_deal_stage(&config, Some("other"), Some("custom")), "lead");
+    }
+}
@@ -0,0 +1,5 @@
+// CRM Module - Imports from external CRMs
+
+pub mod hubspot;
+
+pub use hubspot::*;
@@ -0,0 +1,297 @@
+// CRM Module - Meeting notes capture
+// One call for post-meeting admin on a deal: the raw notes are saved as
+// markdown in the company's deal folder, summarized with action items, each
+// action item becomes a follow-up task on the deal, and a meeting activity
+// links the notes file and tasks together.
+
+use super::types::*;
+use crate::commands::ai::anthropic_messages;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings;
+use crate::commands::supabase::get_client;
+use crate::commands::work::tasks::default_status_id;
+use crate::commands::work::{slugify, work_create_task, CreateTask, Project, User};
+use crate::AppState;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+const MODEL: &str = "claude-haiku-4-5-20251001";
+/// Raw notes beyond this are cut before summarizing (the file keeps them all)
+const MAX_NOTES_CHARS: usize = 30_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingActionItem {
+    pub title: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    /// Set once the Work task exists
+    #[serde(default)]
+    pub task_id: Option<String>,
+    #[serde(default)]
+    pub task_identifier: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingNotesResult {
+    pub notes_path: String,
+    pub summary: String,
+    pub action_items: Vec<MeetingActionItem>,
+    pub activity_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelOutput {
+    #[serde(default)]
+    title: Option<String>,
+    summary: String,
+    #[serde(default)]
+    action_items: Vec<MeetingActionItem>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Model output → struct, tolerating code fences and text around the JSON
+pub(super) fn parse_model_output<T: DeserializeOwned>(text: &str) -> CmdResult<T> {
+    let start = text.find('{');
+    let end = text.rfind('}');
+    let json_str = match (start, end) {
+        (Some(s), Some(e)) if e > s => &text[s..=e],
+        _ => return Err(CommandError::Parse("No JSON object in model response".into())),
+    };
+    serde_json::from_str(json_str).map_err(|e| CommandError::Parse(format!("Failed to parse model output: {}", e)))
+}
+
+/// Deal folder, else client folder, else {knowledge}/crm/{company}; notes go
+/// in a meeting-notes/ subfolder
+fn notes_dir(company: Option<&Company>, knowledge_path: &str) -> CmdResult<PathBuf> {
+    let configured = company.and_then(|c| {
+        [&c.deal_folder_path, &c.client_folder_path]
+            .into_iter()
+            .flatten()
+            .map(PathBuf::from)
+            // Folder fields sometimes point at a document inside the folder
+            .map(|p| if p.extension().is_some() { p.parent().map(Path::to_path_buf).unwrap_or(p) } else { p })
+            .find(|p| p.is_dir())
+    });
+    let base = match configured {
+        Some(dir) => dir,
+        None => {
+            if knowledge_path.is_empty() {
+                return Err(CommandError::Config(
+                    "Knowledge path not configured and the company has no deal folder".into(),
+                ));
+            }
+            let name = company.map(|c| slugify(&c.name)).unwrap_or_else(|| "unassigned".to_string());
+            Path::new(knowledge_path).join("crm").join(name)
+        }
+    };
+    Ok(base.join("meeting-notes"))
+}
+
+fn render_notes(title: &str, date: &str, deal: &Project, summary: &str, items: &[MeetingActionItem], raw: &str) -> String {
+    let mut out = format!(
+        "---\ntitle: \"{}\"\ndate: {}\ntype: meeting-notes\ndeal: \"{}\"\ndeal_id: {}\n---\n\n# {}\n\n## Summary\n\n{}\n\n## Action items\n\n",
+        title.replace('"', "'"),
+        date,
+        deal.name.replace('"', "'"),
+        deal.id,
+        title,
+        summary.trim()
+    );
+    if items.is_empty() {
+        out.push_str("_None_\n");
+    }
+    for item in items {
+        out.push_str(&format!("- [ ] {}", item.title));
+        if let Some(owner) = &item.owner {
+            out.push_str(&format!(" — {}", owner));
+        }
+        if let Some(due) = &item.due_date {
+            out.push_str(&format!(" (due {})", due));
+        }
+        if let Some(identifier) = &item.task_identifier {
+            out.push_str(&format!(" `{}`", identifier));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("\n## Raw notes\n\n{}\n", raw.trim()));
+    out
+}
+
+/// Match an owner name from the notes to a human user (full name, then first name)
+fn match_owner(owner: &str, users: &[User]) -> Option<String> {
+    let owner = owner.trim().to_lowercase();
+    if owner.is_empty() {
+        return None;
+    }
+    users
+        .iter()
+        .find(|u| u.name.to_lowercase() == owner)
+        .or_else(|| {
+            users
+                .iter()
+                .find(|u| u.name.split_whitespace().next().is_some_and(|f| f.to_lowercase() == owner))
+        })
+        .map(|u| u.id.clone())
+}
+
+pub(super) async fn generate(api_key: &str, system: &str, user: &str) -> CmdResult<String> {
+    anthropic_messages(api_key, MODEL, system, user, 2048, 0.2).await
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Capture notes from a deal meeting: save them to the knowledge folder,
+/// summarize with action items, file each action item as a follow-up task on
+/// the deal and log a meeting activity linking everything.
+#[tauri::command]
+pub async fn crm_capture_meeting_notes(
+    state: State<'_, AppState>,
+    deal_id: String,
+    raw_notes: String,
+    title: Option<String>,
+    meeting_date: Option<String>,
+) -> CmdResult<MeetingNotesResult> {
+    if raw_notes.trim().is_empty() {
+        return Err(CommandError::Config("Meeting notes are empty".into()));
+    }
+    let api_key = settings::settings_get_anthropic_key()?
+        .ok_or_else(|| CommandError::Config("Anthropic API key not configured. Go to Settings (⌘,) to add it.".into()))?;
+
+    let client = get_client().await?;
+    let deal: Project = client
+        .select_single("projects", &format!("id=eq.{}&project_type=eq.deal", deal_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Deal not found: {}", deal_id)))?;
+    let company: Option<Company> = match &deal.company_id {
+        Some(company_id) => client.select_single("crm_companies", &format!("id=eq.{}", company_id)).await?,
+        None => None,
+    };
+    let date = meeting_date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+
+    // Save the raw notes first so nothing is lost if summarizing fails
+    let dir = notes_dir(company.as_ref(), &state.knowledge_path)?;
+    std::fs::create_dir_all(&dir)?;
+    let provisional_title = title.clone().unwrap_or_else(|| format!("{} meeting", deal.name));
+    let path = dir.join(format!("{}-{}.md", date, slugify(&provisional_title)));
+    std::fs::write(&path, format!("# {}\n\n## Raw notes\n\n{}\n", provisional_title, raw_notes.trim()))?;
+
+    let system = "You turn raw meeting notes from a sales/consulting call into a record. Respond with only a JSON \
+        object: {\"title\": short meeting title, \"summary\": 3-6 sentence summary covering decisions, concerns \
+        and next steps, \"action_items\": [{\"title\": imperative task title, \"owner\": person's name if stated \
+        or null, \"due_date\": YYYY-MM-DD if a date is stated or clearly implied, else null}]}. Only include action \
+        items actually agreed or clearly needed; do not invent owners or dates.";
+    let notes_excerpt: String = raw_notes.chars().take(MAX_NOTES_CHARS).collect();
+    let prompt = format!(
+        "Deal: {}\nCompany: {}\nMeeting date: {}\n\nNotes:\n{}",
+        deal.name,
+        company.as_ref().map(|c| c.name.as_str()).unwrap_or("unknown"),
+        date,
+        notes_excerpt
+    );
+    let output: ModelOutput = parse_model_output(&generate(&api_key, system, &prompt).await?)?;
+    let title = title
+        .or(output.title)
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(provisional_title);
+
+    // Action items → follow-up tasks on the deal
+    let users: Vec<User> = client.select("users", "type=eq.human").await.unwrap_or_default();
+    let status_id = default_status_id(&client).await?;
+    let mut action_items = output.action_items;
+    for item in action_items.iter_mut().filter(|i| !i.title.trim().is_empty()) {
+        let assignee = item.owner.as_deref().and_then(|o| match_owner(o, &users));
+        let task = work_create_task(CreateTask {
+            project_id: deal.id.clone(),
+            status_id: status_id.clone(),
+            title: item.title.clone(),
+            description: Some(format!("From meeting notes: {}", path.display())),
+            priority: None,
+            due_date: item.due_date.clone(),
+            assignee_ids: assignee.map(|a| vec![a]),
+            milestone_id: None,
+            depends_on: None,
+            session_ref: None,
+            requires_review: None,
+            company_id: deal.company_id.clone(),
+            contact_id: None,
+            task_type: Some("follow_up".to_string()),
+        })
+        .await?;
+        item.task_identifier = match (&deal.identifier_prefix, task.task_number) {
+            (Some(prefix), Some(number)) => Some(format!("{}-{}", prefix, number)),
+            _ => None,
+        };
+        item.task_id = Some(task.id);
+    }
+
+    // Rewrite the file now that the summary and task links exist
+    let final_path = dir.join(format!("{}-{}.md", date, slugify(&title)));
+    std::fs::write(
+        &final_path,
+        render_notes(&title, &date, &deal, &output.summary, &action_items, &raw_notes),
+    )?;
+    if final_path != path {
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let task_lines: Vec<String> = action_items
+        .iter()
+        .filter(|i| i.task_id.is_some())
+        .map(|i| match &i.task_identifier {
+            Some(identifier) => format!("- {} {}", identifier, i.title),
+            None => format!("- {}", i.title),
+        })
+        .collect();
+    let mut content = format!("{}\n\nNotes: {}", output.summary.trim(), final_path.display());
+    if !task_lines.is_empty() {
+        content.push_str(&format!("\n\nFollow-up tasks:\n{}", task_lines.join("\n")));
+    }
+    let activity: Activity = client
+        .insert(
+            "crm_activities",
+            &json!({
+                "company_id": deal.company_id,
+                "project_id": deal.id,
+                "task_id": action_items.iter().find_map(|i| i.task_id.clone()),
+                "type": "meeting",
+                "subject": title,
+                "content": content,
+                "activity_date": format!("{}T00:00:00Z", date),
+            }),
+        )
+        .await?;
+
+    Ok(MeetingNotesResult {
+        notes_path: final_path.to_string_lossy().to_string(),
+        summary: output.summary,
+        action_items,
+        activity_id: activity.id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_model_output() {
+        let text = "Here you go:\n```json\n{\"title\": \"Pricing call\", \"summary\": \"Agreed on pilot.\", \
+            \"action_items\": [{\"title\": \"Send order form\", \"owner\": \"Mel\", \"due_date\": null}]}\n```";
+        let output: ModelOutput = parse_model_output(text).unwrap();
+        assert_eq!(output.title.as_deref(), Some("Pricing call"));
+        assert_eq!(output.action_items.len(), 1);
+        assert_eq!(output.action_items[0].owner.as_deref(), Some("Mel"));
+        assert!(output.action_items[0].task_id.is_none());
+        assert!(parse_model_output::<ModelOutput>("no json here").is_err());
+    }
+}
@@ -5,9 +5,25 @@ pub mod types;
 pub mod companies;
 pub mod contacts;
 pub mod activities;
+pub mod close_reasons;
+pub mod stage_requirements;
+pub mod import;
+pub mod privacy;
+pub mod meeting_notes;
+pub mod deal_detection;
+pub mod segments;
+pub mod pipeline_history;
 
 #[allow(unused_imports)]
 pub use types::*;
 pub use companies::*;
 pub use contacts::*;
 pub use activities::*;
+pub use close_reasons::*;
+pub use stage_requirements::*;
+pub use import::*;
+pub use privacy::*;
+pub use meeting_notes::*;
+pub use deal_detection::*;
+pub use segments::*;
+pub use pipeline_history::*;
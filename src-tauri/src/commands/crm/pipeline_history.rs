@@ -0,0 +1,263 @@
+// CRM Module - Pipeline History
+// A nightly job records per-stage totals (count, value, probability-weighted
+// value) into crm_pipeline_snapshots, so pipeline movement can be charted
+// over time instead of only the current state.
+
+use super::close_reasons::parse_period;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+use tauri::Emitter;
+
+/// How often the background loop checks whether today has been snapshotted
+const SNAPSHOT_CHECK_SECS: u64 = 60 * 60;
+/// Closed stages aren't pipeline
+const CLOSED_STAGES: [&str; 2] = ["won", "lost"];
+
+#[derive(Debug, Deserialize)]
+struct StageRow {
+    value: String,
+    weight: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DealRow {
+    deal_stage: Option<String>,
+    deal_value: Option<f64>,
+    deal_year_1_total: Option<f64>,
+}
+
+impl DealRow {
+    /// Year-1 total, falling back to deal_value for legacy deals (matches the pipeline view)
+    fn effective_value(&self) -> f64 {
+        self.deal_year_1_total.or(self.deal_value).unwrap_or(0.0)
+    }
+}
+
+/// Per-stage totals for every active stage, in stage order (stages with no deals included)
+fn stage_totals(date: NaiveDate, stages: &[StageRow], deals: &[DealRow]) -> Vec<PipelineSnapshotRow> {
+    stages
+        .iter()
+        .filter(|s| !CLOSED_STAGES.contains(&s.value.as_str()))
+        .map(|stage| {
+            let probability = stage.weight.unwrap_or(0.0);
+            let in_stage: Vec<&DealRow> = deals
+                .iter()
+                .filter(|d| d.deal_stage.as_deref() == Some(stage.value.as_str()))
+                .collect();
+            let total_value: f64 = in_stage.iter().map(|d| d.effective_value()).sum();
+            PipelineSnapshotRow {
+                snapshot_date: date.to_string(),
+                stage: stage.value.clone(),
+                deal_count: in_stage.len() as i32,
+                total_value,
+                weighted_value: total_value * probability,
+                probability,
+            }
+        })
+        .collect()
+}
+
+fn bucket_start(date: NaiveDate, granularity: &str) -> NaiveDate {
+    match granularity {
+        "week" => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+        "month" => date.with_day(1).unwrap_or(date),
+        _ => date,
+    }
+}
+
+/// Group snapshot rows into chart points. Each bucket uses its latest
+/// snapshot, since pipeline totals are a level rather than a flow.
+fn bucket_history(rows: Vec<PipelineSnapshotRow>, granularity: &str) -> Vec<PipelineHistoryPoint> {
+    let mut points: Vec<PipelineHistoryPoint> = Vec::new();
+    for row in rows {
+        let Ok(date) = NaiveDate::parse_from_str(&row.snapshot_date, "%Y-%m-%d") else {
+            continue;
+        };
+        let period_start = bucket_start(date, granularity).to_string();
+
+        let idx = match points.iter().position(|p| p.period_start == period_start) {
+            Some(i) => i,
+            None => {
+                points.push(PipelineHistoryPoint {
+                    period_start,
+                    snapshot_date: row.snapshot_date.clone(),
+                    by_stage: Vec::new(),
+                    deal_count: 0,
+                    total_value: 0.0,
+                    weighted_value: 0.0,
+                });
+                points.len() - 1
+            }
+        };
+        let point = &mut points[idx];
+        // ISO dates compare correctly as strings
+        if row.snapshot_date > point.snapshot_date {
+            point.snapshot_date = row.snapshot_date.clone();
+            point.by_stage.clear();
+        }
+        if row.snapshot_date == point.snapshot_date {
+            point.by_stage.push(row);
+        }
+    }
+
+    for point in &mut points {
+        point.deal_count = point.by_stage.iter().map(|s| s.deal_count).sum();
+        point.total_value = point.by_stage.iter().map(|s| s.total_value).sum();
+        point.weighted_value = point.by_stage.iter().map(|s| s.weighted_value).sum();
+    }
+    points.sort_by(|a, b| a.period_start.cmp(&b.period_start));
+    points
+}
+
+/// Compute today's per-stage totals and upsert them (re-running a day overwrites it)
+async fn record_snapshot(client: &SupabaseClient, date: NaiveDate) -> CmdResult<Vec<PipelineSnapshotRow>> {
+    let stages: Vec<StageRow> = client
+        .select("lookup_values", "type=eq.deal_stage&select=value,weight&order=sort_order.asc")
+        .await?;
+    let deals: Vec<DealRow> = client
+        .select(
+            "projects",
+            "project_type=eq.deal&archived_at=is.null&select=deal_stage,deal_value,deal_year_1_total",
+        )
+        .await?;
+
+    let rows = stage_totals(date, &stages, &deals);
+    if rows.is_empty() {
+        return Err(CommandError::Config("No deal stages configured in lookup_values".into()));
+    }
+    let _: PipelineSnapshotRow = client
+        .upsert_on("crm_pipeline_snapshots", &rows, Some("snapshot_date,stage"))
+        .await?;
+    Ok(rows)
+}
+
+async fn has_snapshot(client: &SupabaseClient, date: NaiveDate) -> CmdResult<bool> {
+    let rows: Vec<PipelineSnapshotRow> = client
+        .select(
+            "crm_pipeline_snapshots",
+            &format!("snapshot_date=eq.{}&limit=1", date),
+        )
+        .await?;
+    Ok(!rows.is_empty())
+}
+
+/// Start the nightly snapshot job. Checks hourly and records a snapshot the
+/// first time it finds today missing, so a laptop asleep at midnight still
+/// gets one per day it's used.
+pub fn start_pipeline_snapshots(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Wait 120s before first run (let auth + workspace settle)
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+
+        loop {
+            let today = chrono::Local::now().date_naive();
+            match get_client().await {
+                Ok(client) => match has_snapshot(&client, today).await {
+                    Ok(true) => {}
+                    Ok(false) => match record_snapshot(&client, today).await {
+                        Ok(rows) => {
+                            let _ = app_handle.emit("crm:pipeline-snapshot", &rows);
+                        }
+                        Err(e) => eprintln!("[crm:pipeline] Snapshot for {} failed: {}", today, e),
+                    },
+                    Err(e) => eprintln!("[crm:pipeline] Snapshot check failed: {}", e),
+                },
+                // Usually just "Supabase not configured" before sign-in
+                Err(e) => eprintln!("[crm:pipeline] Snapshot skipped: {}", e),
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(SNAPSHOT_CHECK_SECS)).await;
+        }
+    });
+}
+
+/// Record today's pipeline snapshot now (overwrites an earlier one from today)
+#[tauri::command]
+pub async fn crm_snapshot_pipeline() -> CmdResult<Vec<PipelineSnapshotRow>> {
+    let client = get_client().await?;
+    record_snapshot(&client, chrono::Local::now().date_naive()).await
+}
+
+/// Pipeline totals over time. `period` as in the close reason report (default
+/// 90d); `granularity` is day, week or month (default day).
+#[tauri::command]
+pub async fn crm_get_pipeline_history(period: Option<String>, granularity: Option<String>) -> CmdResult<PipelineHistory> {
+    let period = period.unwrap_or_else(|| "90d".to_string());
+    let granularity = granularity.unwrap_or_else(|| "day".to_string());
+    if !["day", "week", "month"].contains(&granularity.as_str()) {
+        return Err(CommandError::Config(format!(
+            "Unknown granularity: {} (expected day, week or month)",
+            granularity
+        )));
+    }
+    let today = chrono::Local::now().date_naive();
+    let (start, end) = parse_period(&period, today).map_err(CommandError::Config)?;
+
+    let mut query = String::from("order=snapshot_date.asc,stage.asc");
+    if let Some(start) = start {
+        query.push_str(&format!("&snapshot_date=gte.{}", start));
+    }
+    if let Some(end) = end {
+        query.push_str(&format!("&snapshot_date=lte.{}", end));
+    }
+
+    let client = get_client().await?;
+    let rows: Vec<PipelineSnapshotRow> = client.select_all("crm_pipeline_snapshots", &query).await?;
+
+    Ok(PipelineHistory {
+        period,
+        points: bucket_history(rows, &granularity),
+        granularity,
+        start: start.map(|d| d.to_string()),
+        end: end.map(|d| d.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(value: &str, weight: f64) -> StageRow {
+        StageRow { value: value.into(), weight: Some(weight) }
+    }
+
+    fn deal(stage: &str, value: Option<f64>, y1: Option<f64>) -> DealRow {
+        DealRow { deal_stage: Some(stage.into()), deal_value: value, deal_year_1_total: y1 }
+    }
+
+    #[test]
+    fn totals_and_buckets() {
+        let stages = vec![stage("lead", 0.2), stage("proposal", 0.5), stage("won", 1.0)];
+        let deals = vec![
+            deal("lead", Some(100.0), None),
+            deal("lead", Some(50.0), Some(300.0)),
+            deal("won", Some(1000.0), None),
+        ];
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        let rows = stage_totals(day(12), &stages, &deals);
+        assert_eq!(rows.iter().map(|r| r.stage.as_str()).collect::<Vec<_>>(), vec!["lead", "proposal"]);
+        assert_eq!((rows[0].deal_count, rows[0].total_value), (2, 400.0));
+        assert!((rows[0].weighted_value - 80.0).abs() < 1e-9);
+        assert_eq!(rows[1].deal_count, 0);
+
+        // Mon 12th and Wed 14th fall in one week; the later snapshot wins
+        let mut history = rows.clone();
+        let mut later = stage_totals(day(14), &stages, &deals[..1]);
+        history.append(&mut later);
+        history.extend(stage_totals(day(19), &stages, &[]));
+
+        let weekly = bucket_history(history.clone(), "week");
+        assert_eq!(weekly.len(), 2);
+        assert_eq!((weekly[0].period_start.as_str(), weekly[0].snapshot_date.as_str()), ("2026-10-12", "2026-10-14"));
+        assert_eq!((weekly[0].deal_count, weekly[0].total_value), (1, 100.0));
+        assert_eq!(weekly[1].deal_count, 0);
+
+        assert_eq!(bucket_history(history.clone(), "day").len(), 3);
+        let monthly = bucket_history(history, "month");
+        assert_eq!((monthly.len(), monthly[0].period_start.as_str()), (1, "2026-10-01"));
+    }
+}
@@ -0,0 +1,412 @@
+// CRM Module - Data subject requests (GDPR / PDPA)
+// Export everything we hold about a person, or anonymize/delete it.
+// Forgetting is two-phase: the first call previews and returns a short-lived
+// confirmation token; only a second call with that token changes data.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::db::EmailDb;
+use crate::commands::outlook::types::EmailEntry;
+use crate::commands::supabase::get_client;
+use crate::commands::tools::docgen_theme::escape_html;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Confirmation tokens expire after this many seconds
+const CONFIRM_TTL_SECS: i64 = 10 * 60;
+
+/// A previewed forget waiting for confirmation
+struct PendingForget {
+    email: String,
+    mode: String,
+    issued_at: i64,
+}
+
+/// token -> the preview it confirms
+static PENDING_FORGETS: std::sync::LazyLock<Mutex<HashMap<String, PendingForget>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonDataBundle {
+    pub subject_email: String,
+    pub exported_at: String,
+    pub contacts: Vec<Contact>,
+    pub activities: Vec<Activity>,
+    pub email_links: Vec<EmailCompanyLink>,
+    /// Metadata only — message bodies stay in the mailbox
+    pub emails: Vec<EmailEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonDataExport {
+    pub json_path: String,
+    pub pdf_path: Option<String>,
+    pub contacts: usize,
+    pub activities: usize,
+    pub email_links: usize,
+    pub emails: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgetResult {
+    /// "preview" until a valid confirmation token is supplied, then "completed"
+    pub status: String,
+    pub mode: String, // anonymize | delete
+    pub contacts: usize,
+    pub activities: usize,
+    pub email_links: usize,
+    pub local_emails: usize,
+    /// Email-match rules in the local Outlook contacts cache
+    pub local_contacts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn email_hash(email: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(email.to_lowercase().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// 16 bytes from the OS CSPRNG, hex-encoded
+fn confirmation_token() -> CmdResult<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| CommandError::Internal(format!("Failed to generate token: {}", e)))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn privacy_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("crm")
+}
+
+/// Collect everything tied to an email address across CRM tables and the local Outlook cache
+async fn collect_person_data(email: &str) -> CmdResult<PersonDataBundle> {
+    let client = get_client().await?;
+    let email = email.trim().to_lowercase();
+
+    let contacts: Vec<Contact> = client
+        .select("crm_contacts", &format!("email=eq.{}", urlencoding::encode(&email)))
+        .await?;
+    let contact_ids = contacts.iter().map(|c| c.id.as_str()).collect::<Vec<_>>().join(",");
+
+    let (activities, email_links) = if contacts.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        let activities: Vec<Activity> = client
+            .select(
+                "crm_activities",
+                &format!("contact_id=in.({})&order=activity_date.desc", contact_ids),
+            )
+            .await?;
+        let links: Vec<EmailCompanyLink> = client
+            .select("crm_email_company_links", &format!("contact_id=in.({})", contact_ids))
+            .await?;
+        (activities, links)
+    };
+
+    // Local Outlook cache is optional — not every user has connected a mailbox
+    let emails = EmailDb::open()
+        .and_then(|db| {
+            let candidates = db.scan_emails_for_entity(&[], &[email.clone()], None)?;
+            Ok(candidates
+                .iter()
+                .filter_map(|c| db.get_email(&c.email_id).ok().flatten())
+                .collect::<Vec<_>>())
+        })
+        .unwrap_or_default();
+
+    Ok(PersonDataBundle {
+        subject_email: email,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        contacts,
+        activities,
+        email_links,
+        emails,
+    })
+}
+
+fn render_bundle_html(bundle: &PersonDataBundle) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><style>\
+         body{font-family:-apple-system,Helvetica,Arial,sans-serif;font-size:11px;color:#222;margin:32px}\
+         h1{font-size:20px}h2{font-size:14px;margin-top:24px;border-bottom:1px solid #ddd}\
+         table{border-collapse:collapse;width:100%}td,th{border:1px solid #ddd;padding:4px;text-align:left;vertical-align:top}\
+         </style></head><body>",
+    );
+    html.push_str(&format!(
+        "<h1>Personal data export</h1><p>Subject: <b>{}</b><br>Exported: {}</p>",
+        escape_html(&bundle.subject_email),
+        bundle.exported_at
+    ));
+
+    html.push_str("<h2>Contact records</h2>");
+    for c in &bundle.contacts {
+        html.push_str("<table>");
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(c) {
+            for (k, v) in fields {
+                let value = match v {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>", k, escape_html(&value)));
+            }
+        }
+        html.push_str("</table><br>");
+    }
+
+    html.push_str(&format!("<h2>Activities ({})</h2><table><tr><th>Date</th><th>Type</th><th>Subject</th><th>Content</th></tr>", bundle.activities.len()));
+    for a in &bundle.activities {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            a.activity_date.as_deref().unwrap_or_default(),
+            a.activity_type,
+            escape_html(a.subject.as_deref().unwrap_or_default()),
+            escape_html(a.content.as_deref().unwrap_or_default()),
+        ));
+    }
+    html.push_str("</table>");
+
+    html.push_str(&format!("<h2>Emails ({})</h2><table><tr><th>Received</th><th>From</th><th>Subject</th></tr>", bundle.emails.len()));
+    for e in &bundle.emails {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            e.received_at,
+            escape_html(&e.from_email),
+            escape_html(&e.subject),
+        ));
+    }
+    html.push_str("</table></body></html>");
+    html
+}
+
+/// Append a PII-free audit entry (email is stored hashed)
+fn write_audit_entry(entry: serde_json::Value) -> CmdResult<()> {
+    use std::io::Write;
+    let dir = privacy_dir();
+    std::fs::create_dir_all(&dir)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("privacy_audit.jsonl"))?;
+    writeln!(file, "{}", entry)?;
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Export all data held about a person as a portable JSON bundle (and optionally a PDF)
+#[tauri::command]
+pub async fn crm_export_person_data(
+    email: String,
+    output_dir: Option<String>,
+    include_pdf: Option<bool>,
+) -> CmdResult<PersonDataExport> {
+    if !email.contains('@') {
+        return Err(CommandError::Config(format!("Not an email address: {}", email)));
+    }
+    let bundle = collect_person_data(&email).await?;
+
+    let dir = output_dir
+        .map(PathBuf::from)
+        .or_else(dirs::download_dir)
+        .unwrap_or_else(privacy_dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let stem = format!(
+        "personal-data-{}-{}",
+        &email_hash(&bundle.subject_email)[..8],
+        chrono::Utc::now().format("%Y%m%d")
+    );
+    let json_path = dir.join(format!("{}.json", stem));
+    std::fs::write(&json_path, serde_json::to_string_pretty(&bundle)?)?;
+
+    let pdf_path = if include_pdf.unwrap_or(false) {
+        let html_path = dir.join(format!("{}.html", stem));
+        std::fs::write(&html_path, render_bundle_html(&bundle))?;
+        let pdf = crate::commands::tools::docgen::html_to_pdf_cmd(html_path.to_string_lossy().to_string()).await;
+        let _ = std::fs::remove_file(&html_path);
+        Some(pdf?)
+    } else {
+        None
+    };
+
+    write_audit_entry(serde_json::json!({
+        "action": "export",
+        "subject_hash": email_hash(&bundle.subject_email),
+        "at": chrono::Utc::now().to_rfc3339(),
+        "contacts": bundle.contacts.len(),
+        "activities": bundle.activities.len(),
+        "emails": bundle.emails.len(),
+    }))?;
+
+    Ok(PersonDataExport {
+        json_path: json_path.to_string_lossy().to_string(),
+        pdf_path,
+        contacts: bundle.contacts.len(),
+        activities: bundle.activities.len(),
+        email_links: bundle.email_links.len(),
+        emails: bundle.emails.len(),
+    })
+}
+
+/// Anonymize (default) or delete a person across CRM tables, the email link
+/// table, and the local Outlook cache. Call once without a token to preview
+/// and get one; call again with the token within 10 minutes to execute.
+#[tauri::command]
+pub async fn crm_forget_person(
+    email: String,
+    mode: Option<String>,
+    confirmation_token: Option<String>,
+) -> CmdResult<ForgetResult> {
+    let mode = mode.unwrap_or_else(|| "anonymize".to_string());
+    if mode != "anonymize" && mode != "delete" {
+        return Err(CommandError::Config(format!("Unknown mode: {} (expected anonymize or delete)", mode)));
+    }
+    let bundle = collect_person_data(&email).await?;
+    let subject = bundle.subject_email.clone();
+    let now = chrono::Utc::now();
+
+    // Only cache rows the person actually sent — their address in someone else's
+    // To: line isn't theirs to erase
+    let local_sent: Vec<&EmailEntry> = bundle
+        .emails
+        .iter()
+        .filter(|e| e.from_email.eq_ignore_ascii_case(&subject))
+        .collect();
+    let local_contacts = EmailDb::open()
+        .ok()
+        .and_then(|db| db.find_contact_by_email(&subject).ok().flatten())
+        .map_or(0, |_| 1);
+
+    let mut result = ForgetResult {
+        status: "preview".to_string(),
+        mode: mode.clone(),
+        contacts: bundle.contacts.len(),
+        activities: bundle.activities.len(),
+        email_links: bundle.email_links.len(),
+        local_emails: local_sent.len(),
+        local_contacts,
+        confirmation_token: None,
+        expires_at: None,
+    };
+
+    // Phase 1: preview + token
+    let Some(token) = confirmation_token else {
+        let token = confirmation_token()?;
+        let mut pending = PENDING_FORGETS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        pending.retain(|_, p| now.timestamp() - p.issued_at < CONFIRM_TTL_SECS);
+        pending.insert(
+            token.clone(),
+            PendingForget { email: subject.clone(), mode: mode.clone(), issued_at: now.timestamp() },
+        );
+        result.confirmation_token = Some(token);
+        result.expires_at = Some((now + chrono::Duration::seconds(CONFIRM_TTL_SECS)).to_rfc3339());
+        return Ok(result);
+    };
+
+    // Phase 2: validate token (single use, bound to this email and mode — an
+    // anonymize preview doesn't confirm a delete)
+    {
+        let mut pending = PENDING_FORGETS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        match pending.remove(&token) {
+            Some(p) if p.email == subject && p.mode == mode && now.timestamp() - p.issued_at < CONFIRM_TTL_SECS => {}
+            _ => {
+                return Err(CommandError::Config(
+                    "Invalid or expired confirmation token — request a new preview".to_string(),
+                ))
+            }
+        }
+    }
+
+    let client = get_client().await?;
+    let contact_ids = bundle.contacts.iter().map(|c| c.id.as_str()).collect::<Vec<_>>().join(",");
+    let hash8 = &email_hash(&subject)[..8];
+
+    if !bundle.contacts.is_empty() {
+        client
+            .delete("crm_email_company_links", &format!("contact_id=in.({})", contact_ids))
+            .await?;
+
+        if mode == "delete" {
+            client.delete("crm_activities", &format!("contact_id=in.({})", contact_ids)).await?;
+            client.delete("crm_contacts", &format!("id=in.({})", contact_ids)).await?;
+        } else {
+            let redacted_activity = serde_json::json!({
+                "subject": "[redacted]",
+                "content": null,
+            });
+            let _: Vec<Activity> = client
+                .update("crm_activities", &format!("contact_id=in.({})", contact_ids), &redacted_activity)
+                .await?;
+
+            let redacted_contact = serde_json::json!({
+                "name": "Redacted contact",
+                "email": format!("redacted-{}@redacted.invalid", hash8),
+                "phone": null,
+                "notes": null,
+                "linkedin_url": null,
+                "linkedin_connect_msg": null,
+                "linkedin_dm_msg": null,
+                "email_outreach_msg": null,
+                "is_active": false,
+            });
+            let _: Vec<Contact> = client
+                .update("crm_contacts", &format!("id=in.({})", contact_ids), &redacted_contact)
+                .await?;
+        }
+    }
+
+    if let Ok(db) = EmailDb::open() {
+        for e in &local_sent {
+            let _ = db.delete_email(&e.id);
+        }
+        let _ = db.delete_contact_by_email(&subject);
+    }
+
+    // Leave a trace on affected companies' timelines, without the person's details
+    let company_ids: std::collections::HashSet<&str> =
+        bundle.contacts.iter().filter_map(|c| c.company_id.as_deref()).collect();
+    for company_id in company_ids {
+        let note = serde_json::json!({
+            "company_id": company_id,
+            "type": "note",
+            "subject": "Data subject request processed",
+            "content": format!("Contact data {} under a data subject request (ref {}).", if mode == "delete" { "deleted" } else { "anonymized" }, hash8),
+            "activity_date": now.to_rfc3339(),
+        });
+        let _: Result<Activity, _> = client.insert("crm_activities", &note).await;
+    }
+
+    write_audit_entry(serde_json::json!({
+        "action": mode,
+        "subject_hash": email_hash(&subject),
+        "at": now.to_rfc3339(),
+        "contacts": result.contacts,
+        "activities": result.activities,
+        "email_links": result.email_links,
+        "local_emails": result.local_emails,
+        "local_contacts": result.local_contacts,
+    }))?;
+
+    result.status = "completed".to_string();
+    Ok(result)
+}
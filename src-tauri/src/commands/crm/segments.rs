@@ -0,0 +1,585 @@
+// CRM Module - Segments / ICP scoring
+// Saved filter expressions over companies or contacts (firmographics,
+// engagement from crm_activities, product usage from analytics page views).
+// Members are materialized in crm_segment_members and refreshed in the
+// background, so bulk email and reports read a ready-made list.
+
+use super::types::*;
+use crate::commands::analytics::company_usage::analytics_list_domain_mappings;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::types::MergeRecipient;
+use crate::commands::supabase::{get_client, SupabaseClient};
+use chrono::{Duration, NaiveDate};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use tauri::Emitter;
+
+const INSERT_CHUNK: usize = 500;
+/// Engagement counts activities in this window; older activity only feeds
+/// last_activity_days when it falls inside ENGAGEMENT_LOOKBACK_DAYS
+const ENGAGEMENT_WINDOW_DAYS: i64 = 90;
+const ENGAGEMENT_LOOKBACK_DAYS: i64 = 365;
+const USAGE_WINDOW_DAYS: i64 = 30;
+/// How often the background loop looks for stale segments
+const REFRESH_CHECK_SECS: u64 = 60 * 60;
+
+const OPS: [&str; 11] = [
+    "eq", "neq", "in", "not_in", "contains", "gt", "gte", "lt", "lte", "exists", "not_exists",
+];
+
+// ============================================================================
+// Filter evaluation
+// ============================================================================
+
+fn lookup<'a>(record: &'a Value, field: &str) -> Option<&'a Value> {
+    let mut current = record;
+    for part in field.split('.') {
+        current = current.get(part)?;
+    }
+    if current.is_null() {
+        None
+    } else {
+        Some(current)
+    }
+}
+
+fn as_number(v: &Value) -> Option<f64> {
+    v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+/// Scalar equality: numbers numerically, strings case-insensitively
+fn scalar_eq(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::String(a), Value::String(b)) => a.trim().eq_ignore_ascii_case(b.trim()),
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        _ => match (as_number(actual), as_number(expected)) {
+            (Some(a), Some(b)) => (a - b).abs() < f64::EPSILON,
+            _ => actual == expected,
+        },
+    }
+}
+
+/// Arrays (tags, prospect_type) match when any element does
+fn value_eq(actual: &Value, expected: &Value) -> bool {
+    match actual {
+        Value::Array(items) => items.iter().any(|i| scalar_eq(i, expected)),
+        _ => scalar_eq(actual, expected),
+    }
+}
+
+fn compare(actual: &Value, expected: &Value) -> Option<std::cmp::Ordering> {
+    match (as_number(actual), as_number(expected)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b),
+        // ISO dates compare correctly as strings
+        _ => match (actual.as_str(), expected.as_str()) {
+            (Some(a), Some(b)) => Some(a.cmp(b)),
+            _ => None,
+        },
+    }
+}
+
+fn condition_matches(condition: &SegmentCondition, record: &Value) -> bool {
+    use std::cmp::Ordering::*;
+    let actual = lookup(record, &condition.field).filter(|v| match v {
+        Value::String(s) => !s.trim().is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        _ => true,
+    });
+    let expected = &condition.value;
+    let options = || expected.as_array().cloned().unwrap_or_else(|| vec![expected.clone()]);
+
+    match (condition.op.as_str(), actual) {
+        ("exists", a) => a.is_some(),
+        ("not_exists", a) => a.is_none(),
+        ("neq", None) | ("not_in", None) => true,
+        (_, None) => false,
+        ("eq", Some(a)) => value_eq(a, expected),
+        ("neq", Some(a)) => !value_eq(a, expected),
+        ("in", Some(a)) => options().iter().any(|o| value_eq(a, o)),
+        ("not_in", Some(a)) => !options().iter().any(|o| value_eq(a, o)),
+        ("contains", Some(a)) => {
+            let needle = expected.as_str().unwrap_or_default().to_lowercase();
+            match a {
+                Value::Array(items) => items.iter().any(|i| scalar_eq(i, expected)),
+                Value::String(s) => s.to_lowercase().contains(&needle),
+                _ => false,
+            }
+        }
+        ("gt", Some(a)) => compare(a, expected) == Some(Greater),
+        ("gte", Some(a)) => matches!(compare(a, expected), Some(Greater | Equal)),
+        ("lt", Some(a)) => compare(a, expected) == Some(Less),
+        ("lte", Some(a)) => matches!(compare(a, expected), Some(Less | Equal)),
+        _ => false,
+    }
+}
+
+/// Weighted share of matched conditions, 0-100. No conditions matches everything.
+pub fn score_record(filter: &SegmentFilter, record: &Value) -> f64 {
+    let total: f64 = filter.conditions.iter().map(|c| c.weight.unwrap_or(1.0)).sum();
+    if filter.conditions.is_empty() || total <= 0.0 {
+        return 100.0;
+    }
+    let matched: f64 = filter
+        .conditions
+        .iter()
+        .filter(|c| condition_matches(c, record))
+        .map(|c| c.weight.unwrap_or(1.0))
+        .sum();
+    matched / total * 100.0
+}
+
+fn is_member(filter: &SegmentFilter, score: f64) -> bool {
+    // Tolerance so "every condition" survives float rounding of the weights
+    score + 1e-9 >= filter.min_score.unwrap_or(100.0)
+}
+
+fn validate_segment(entity_type: &str, filter: &SegmentFilter) -> CmdResult<()> {
+    if entity_type != "company" && entity_type != "contact" {
+        return Err(CommandError::Config(format!(
+            "Invalid entity_type '{}' (company or contact)",
+            entity_type
+        )));
+    }
+    if let Some(min) = filter.min_score {
+        if !(0.0..=100.0).contains(&min) {
+            return Err(CommandError::Config("min_score must be between 0 and 100".into()));
+        }
+    }
+    for c in &filter.conditions {
+        if c.field.trim().is_empty() {
+            return Err(CommandError::Config("Every condition needs a field".into()));
+        }
+        if !OPS.contains(&c.op.as_str()) {
+            return Err(CommandError::Config(format!(
+                "Unknown operator '{}' on {} (one of: {})",
+                c.op,
+                c.field,
+                OPS.join(", ")
+            )));
+        }
+        if matches!(c.op.as_str(), "gt" | "gte" | "lt" | "lte") && !(c.value.is_number() || c.value.is_string()) {
+            return Err(CommandError::Config(format!("'{}' on {} needs a number or date", c.op, c.field)));
+        }
+        if c.weight.is_some_and(|w| w <= 0.0) {
+            return Err(CommandError::Config(format!("Weight on {} must be positive", c.field)));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Record loading
+// ============================================================================
+
+fn uses(filter: &SegmentFilter, prefix: &str) -> bool {
+    filter
+        .conditions
+        .iter()
+        .any(|c| c.field.starts_with(prefix) || c.field.contains(&format!(".{}", prefix)))
+}
+
+/// Per-company activity counts: activities_90d and last_activity_days
+async fn load_engagement(client: &SupabaseClient, today: NaiveDate) -> CmdResult<HashMap<String, Value>> {
+    let since = today - Duration::days(ENGAGEMENT_LOOKBACK_DAYS);
+    let recent = today - Duration::days(ENGAGEMENT_WINDOW_DAYS);
+    let rows: Vec<Value> = client
+        .select_all(
+            "crm_activities",
+            &format!(
+                "select=company_id,activity_date&company_id=not.is.null&activity_date=gte.{}&order=activity_date.asc,id.asc",
+                since
+            ),
+        )
+        .await?;
+
+    let mut stats: HashMap<String, (u64, Option<NaiveDate>)> = HashMap::new();
+    for row in &rows {
+        let (Some(company), Some(date)) = (
+            row.get("company_id").and_then(|v| v.as_str()),
+            row.get("activity_date")
+                .and_then(|v| v.as_str())
+                .and_then(|d| NaiveDate::parse_from_str(d.get(..10).unwrap_or(d), "%Y-%m-%d").ok()),
+        ) else {
+            continue;
+        };
+        let entry = stats.entry(company.to_string()).or_default();
+        if date >= recent {
+            entry.0 += 1;
+        }
+        entry.1 = entry.1.max(Some(date));
+    }
+    Ok(stats
+        .into_iter()
+        .map(|(company, (count, last))| {
+            let last_days = last.map(|d| (today - d).num_days());
+            (company, json!({ "activities_90d": count, "last_activity_days": last_days }))
+        })
+        .collect())
+}
+
+/// Per-company product usage over the last 30 days via analytics domain mappings
+async fn load_usage(client: &SupabaseClient, today: NaiveDate) -> CmdResult<HashMap<String, Value>> {
+    let domain_company: HashMap<String, String> = analytics_list_domain_mappings(None)
+        .await?
+        .into_iter()
+        .map(|m| (m.domain, m.company_id))
+        .collect();
+    if domain_company.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let since = today - Duration::days(USAGE_WINDOW_DAYS - 1);
+    let rows: Vec<Value> = client
+        .select_all(
+            "analytics_page_views",
+            &format!(
+                "select=domain,user_id,view_date,views&is_internal=eq.false&view_date=gte.{}&order=view_date.asc,source.asc,page_path.asc,user_id.asc",
+                since
+            ),
+        )
+        .await?;
+
+    let mut stats: HashMap<&str, (i64, HashSet<String>, Option<String>)> = HashMap::new();
+    for row in &rows {
+        let Some(company) = row
+            .get("domain")
+            .and_then(|v| v.as_str())
+            .and_then(|d| domain_company.get(d))
+        else {
+            continue;
+        };
+        let entry = stats.entry(company.as_str()).or_default();
+        entry.0 += row.get("views").and_then(|v| v.as_i64()).unwrap_or(0);
+        if let Some(user) = row.get("user_id").and_then(|v| v.as_str()).filter(|u| !u.is_empty()) {
+            entry.1.insert(user.to_string());
+        }
+        if let Some(date) = row.get("view_date").and_then(|v| v.as_str()) {
+            if entry.2.as_deref().map_or(true, |last| date > last) {
+                entry.2 = Some(date.to_string());
+            }
+        }
+    }
+    Ok(stats
+        .into_iter()
+        .map(|(company, (views, users, last_seen))| {
+            let last_seen_days = last_seen
+                .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+                .map(|d| (today - d).num_days());
+            (
+                company.to_string(),
+                json!({ "views_30d": views, "active_users_30d": users.len(), "last_seen_days": last_seen_days }),
+            )
+        })
+        .collect())
+}
+
+/// Company rows keyed by id, with `engagement` and `usage` blocks merged in
+/// when the filter refers to them
+async fn load_company_records(client: &SupabaseClient, filter: &SegmentFilter) -> CmdResult<HashMap<String, Value>> {
+    let today = chrono::Local::now().date_naive();
+    let engagement = if uses(filter, "engagement.") {
+        load_engagement(client, today).await?
+    } else {
+        HashMap::new()
+    };
+    let usage = if uses(filter, "usage.") {
+        load_usage(client, today).await?
+    } else {
+        HashMap::new()
+    };
+
+    let companies = client.select_all::<Value>("crm_companies", "select=*&order=name.asc,id.asc").await?;
+    let mut records = HashMap::new();
+    for mut company in companies {
+        let Some(id) = company.get("id").and_then(|v| v.as_str()).map(String::from) else {
+            continue;
+        };
+        if let Some(obj) = company.as_object_mut() {
+            obj.insert(
+                "engagement".into(),
+                engagement
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "activities_90d": 0, "last_activity_days": null })),
+            );
+            obj.insert(
+                "usage".into(),
+                usage
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "views_30d": 0, "active_users_30d": 0, "last_seen_days": null })),
+            );
+        }
+        records.insert(id, company);
+    }
+    Ok(records)
+}
+
+// ============================================================================
+// Recompute
+// ============================================================================
+
+/// Re-evaluate a segment and replace its materialized members
+pub async fn recompute_segment(client: &SupabaseClient, segment: &Segment) -> CmdResult<Segment> {
+    let companies = load_company_records(client, &segment.filter).await?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    // (company_id, contact_id, score)
+    let mut members: Vec<(Option<String>, Option<String>, f64)> = Vec::new();
+    if segment.entity_type == "contact" {
+        let contacts = client.select_all::<Value>("crm_contacts", "select=*&order=name.asc,id.asc").await?;
+        for mut contact in contacts {
+            let company_id = contact.get("company_id").and_then(|v| v.as_str()).map(String::from);
+            if let (Some(obj), Some(company)) = (
+                contact.as_object_mut(),
+                company_id.as_ref().and_then(|id| companies.get(id)),
+            ) {
+                obj.insert("company".into(), company.clone());
+            }
+            let score = score_record(&segment.filter, &contact);
+            if is_member(&segment.filter, score) {
+                let contact_id = contact.get("id").and_then(|v| v.as_str()).map(String::from);
+                members.push((company_id, contact_id, score));
+            }
+        }
+    } else {
+        for (id, company) in &companies {
+            let score = score_record(&segment.filter, company);
+            if is_member(&segment.filter, score) {
+                members.push((Some(id.clone()), None, score));
+            }
+        }
+    }
+    members.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    client
+        .delete("crm_segment_members", &format!("segment_id=eq.{}", segment.id))
+        .await?;
+    for chunk in members.chunks(INSERT_CHUNK) {
+        let rows: Vec<Value> = chunk
+            .iter()
+            .map(|(company_id, contact_id, score)| {
+                json!({
+                    "segment_id": segment.id,
+                    "company_id": company_id,
+                    "contact_id": contact_id,
+                    "score": (score * 10.0).round() / 10.0,
+                    "computed_at": now,
+                })
+            })
+            .collect();
+        let _: Value = client.insert("crm_segment_members", &rows).await?;
+    }
+
+    client
+        .update(
+            "crm_segments",
+            &format!("id=eq.{}", segment.id),
+            &json!({ "member_count": members.len(), "last_computed_at": now }),
+        )
+        .await
+}
+
+async fn get_segment(client: &SupabaseClient, segment_id: &str) -> CmdResult<Segment> {
+    client
+        .select_single("crm_segments", &format!("id=eq.{}", segment_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Segment not found: {}", segment_id)))
+}
+
+fn is_stale(segment: &Segment, now: chrono::DateTime<chrono::Utc>) -> bool {
+    match segment
+        .last_computed_at
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+    {
+        Some(at) => now - at.with_timezone(&chrono::Utc) >= Duration::hours(segment.refresh_interval_hours.max(1) as i64),
+        None => true,
+    }
+}
+
+/// Start the segment refresh loop. Call from main.rs setup hook.
+pub fn start_segment_refresh(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Wait 90s before first run (let auth + workspace settle)
+        tokio::time::sleep(std::time::Duration::from_secs(90)).await;
+
+        loop {
+            match get_client().await {
+                Ok(client) => {
+                    let segments: Vec<Segment> = client.select("crm_segments", "order=name.asc,id.asc").await.unwrap_or_default();
+                    let now = chrono::Utc::now();
+                    for segment in segments.iter().filter(|s| is_stale(s, now)) {
+                        match recompute_segment(&client, segment).await {
+                            Ok(updated) => {
+                                let _ = app_handle.emit("crm:segment-refreshed", &updated);
+                            }
+                            Err(e) => eprintln!("[crm:segments] Refresh of '{}' failed: {}", segment.name, e),
+                        }
+                    }
+                }
+                // Usually just "Supabase not configured" before sign-in
+                Err(e) => eprintln!("[crm:segments] Refresh skipped: {}", e),
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(REFRESH_CHECK_SECS)).await;
+        }
+    });
+}
+
+/// Segment members as bulk-email recipients: each contact of a contact
+/// segment, or every active contact with an email at a company segment's members
+pub async fn segment_recipients(segment_id: &str) -> CmdResult<Vec<MergeRecipient>> {
+    let client = get_client().await?;
+    let segment = get_segment(&client, segment_id).await?;
+    let members: Vec<SegmentMember> = client
+        .select(
+            "crm_segment_members",
+            &format!("segment_id=eq.{}&order=score.desc&limit=10000", segment_id),
+        )
+        .await?;
+
+    let contact_ids: Vec<String> = if segment.entity_type == "contact" {
+        members.into_iter().filter_map(|m| m.contact_id).collect()
+    } else {
+        let company_ids: Vec<String> = members.into_iter().filter_map(|m| m.company_id).collect();
+        let mut ids = Vec::new();
+        for chunk in company_ids.chunks(100) {
+            let contacts: Vec<Contact> = client
+                .select(
+                    "crm_contacts",
+                    &format!(
+                        "company_id=in.({})&is_active=eq.true&order=is_primary.desc,name.asc",
+                        chunk.join(",")
+                    ),
+                )
+                .await?;
+            ids.extend(contacts.into_iter().filter(|c| !c.email.trim().is_empty()).map(|c| c.id));
+        }
+        ids
+    };
+
+    Ok(contact_ids
+        .into_iter()
+        .map(|id| MergeRecipient { contact_id: Some(id), ..Default::default() })
+        .collect())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn crm_list_segments() -> CmdResult<Vec<Segment>> {
+    let client = get_client().await?;
+    client.select("crm_segments", "order=name.asc,id.asc").await
+}
+
+/// Create a segment and compute its members straight away
+#[tauri::command]
+pub async fn crm_create_segment(data: CreateSegment) -> CmdResult<Segment> {
+    if data.name.trim().is_empty() {
+        return Err(CommandError::Config("Segment name is required".into()));
+    }
+    validate_segment(&data.entity_type, &data.filter)?;
+    let client = get_client().await?;
+    let row = json!({
+        "name": data.name.trim(),
+        "description": data.description,
+        "entity_type": data.entity_type,
+        "filter": data.filter,
+        "refresh_interval_hours": data.refresh_interval_hours.unwrap_or(24).max(1),
+    });
+    let segment: Segment = client.insert("crm_segments", &row).await?;
+    recompute_segment(&client, &segment).await
+}
+
+/// Change a segment's name, filter or refresh interval; members are recomputed
+#[tauri::command]
+pub async fn crm_update_segment(segment_id: String, data: CreateSegment) -> CmdResult<Segment> {
+    if data.name.trim().is_empty() {
+        return Err(CommandError::Config("Segment name is required".into()));
+    }
+    validate_segment(&data.entity_type, &data.filter)?;
+    let client = get_client().await?;
+    let row = json!({
+        "name": data.name.trim(),
+        "description": data.description,
+        "entity_type": data.entity_type,
+        "filter": data.filter,
+        "refresh_interval_hours": data.refresh_interval_hours.unwrap_or(24).max(1),
+        "updated_at": chrono::Utc::now().to_rfc3339(),
+    });
+    let segment: Segment = client
+        .update("crm_segments", &format!("id=eq.{}", segment_id), &row)
+        .await?;
+    recompute_segment(&client, &segment).await
+}
+
+#[tauri::command]
+pub async fn crm_delete_segment(segment_id: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    client.delete("crm_segments", &format!("id=eq.{}", segment_id)).await
+}
+
+#[tauri::command]
+pub async fn crm_recompute_segment(segment_id: String) -> CmdResult<Segment> {
+    let client = get_client().await?;
+    let segment = get_segment(&client, &segment_id).await?;
+    recompute_segment(&client, &segment).await
+}
+
+/// Materialized members, highest ICP score first, with company and contact
+#[tauri::command]
+pub async fn crm_list_segment_members(segment_id: String, limit: Option<u32>) -> CmdResult<Vec<SegmentMember>> {
+    let client = get_client().await?;
+    let query = format!(
+        "select=*,company:crm_companies(*),contact:crm_contacts(*)&segment_id=eq.{}&order=score.desc&limit={}",
+        segment_id,
+        limit.unwrap_or(1000).min(10000)
+    );
+    client.select("crm_segment_members", &query).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cond(field: &str, op: &str, value: Value, weight: Option<f64>) -> SegmentCondition {
+        SegmentCondition { field: field.to_string(), op: op.to_string(), value, weight }
+    }
+
+    #[test]
+    fn scores_weighted_conditions() {
+        let company = json!({
+            "industry": "Logistics",
+            "employee_count": 250,
+            "tags": ["icp", "apac"],
+            "engagement": { "activities_90d": 4, "last_activity_days": 12 },
+            "usage": { "views_30d": 0, "last_seen_days": null }
+        });
+        let filter = SegmentFilter {
+            conditions: vec![
+                cond("industry", "in", json!(["logistics", "retail"]), Some(2.0)),
+                cond("employee_count", "gte", json!(100), None),
+                cond("tags", "contains", json!("ICP"), None),
+                cond("usage.views_30d", "gt", json!(0), None),
+            ],
+            min_score: Some(80.0),
+        };
+        let score = score_record(&filter, &company);
+        assert!((score - 80.0).abs() < 1e-9); // 4 of 5 weight
+        assert!(is_member(&filter, score));
+
+        let contact = json!({ "seniority": "director", "company": company });
+        let all = SegmentFilter {
+            conditions: vec![
+                cond("company.engagement.activities_90d", "gte", json!(3), None),
+                cond("company.usage.last_seen_days", "not_exists", Value::Null, None),
+                cond("seniority", "neq", json!("Director"), None),
+            ],
+            min_score: None,
+        };
+        assert!(!is_member(&all, score_record(&all, &contact)));
+        assert!(validate_segment("deal", &all).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+// CRM Module - Stage-gated required fields
+// Rules live in deal_stage_requirements (stage → deal columns). Moving a deal
+// into a gated stage fails with a validation error listing the empty columns,
+// so pipeline data is complete at the point it's entered.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use serde_json::Value;
+
+/// Deal columns a rule may require, with the label used in error messages
+const REQUIRABLE_FIELDS: &[(&str, &str)] = &[
+    ("deal_value", "value"),
+    ("deal_currency", "currency"),
+    ("deal_expected_close", "expected close date"),
+    ("deal_contact_ids", "decision maker contact"),
+    ("deal_solution", "solution"),
+    ("deal_proposal_path", "proposal"),
+    ("deal_order_form_path", "order form"),
+    ("deal_notes", "notes"),
+    ("company_id", "company"),
+    ("lead", "owner"),
+];
+
+fn field_label(field: &str) -> &str {
+    REQUIRABLE_FIELDS
+        .iter()
+        .find(|(f, _)| *f == field)
+        .map(|(_, label)| *label)
+        .unwrap_or(field)
+}
+
+fn is_filled(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) => !s.trim().is_empty(),
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(Value::Number(n)) => n.as_f64().map(|v| v > 0.0).unwrap_or(false),
+        Some(_) => true,
+    }
+}
+
+/// Required columns that are empty on `deal` (the current row with the
+/// pending update applied)
+pub fn missing_fields(deal: &Value, required: &[String]) -> Vec<String> {
+    required
+        .iter()
+        .filter(|f| !is_filled(deal.get(f.as_str())))
+        .cloned()
+        .collect()
+}
+
+async fn load_requirement(client: &SupabaseClient, stage: &str) -> CmdResult<Option<DealStageRequirement>> {
+    client
+        .select_single("deal_stage_requirements", &format!("stage=eq.{}", urlencoding::encode(stage)))
+        .await
+}
+
+/// Check a deal may enter `stage`. Called by work_update_project on stage
+/// change; `deal` is the current row merged with the update being applied.
+pub async fn validate_stage_requirements(client: &SupabaseClient, stage: &str, deal: &Value) -> CmdResult<()> {
+    let Some(rule) = load_requirement(client, stage).await? else {
+        return Ok(());
+    };
+    let missing = missing_fields(deal, &rule.required_fields);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let labels: Vec<&str> = missing.iter().map(|f| field_label(f)).collect();
+    Err(CommandError::Validation {
+        message: format!("Can't move deal to {} without: {}", stage, labels.join(", ")),
+        fields: missing,
+    })
+}
+
+/// Current deal row with `updates` laid over it
+async fn deal_with_updates(client: &SupabaseClient, deal_id: &str, updates: Option<Value>) -> CmdResult<Value> {
+    let mut deal: Value = client
+        .select_single("projects", &format!("id=eq.{}&project_type=eq.deal", deal_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Deal not found: {}", deal_id)))?;
+    if let (Some(obj), Some(Value::Object(updates))) = (deal.as_object_mut(), updates) {
+        obj.extend(updates);
+    }
+    Ok(deal)
+}
+
+/// List stage rules
+#[tauri::command]
+pub async fn crm_list_stage_requirements() -> CmdResult<Vec<DealStageRequirement>> {
+    let client = get_client().await?;
+    client.select("deal_stage_requirements", "order=stage.asc").await
+}
+
+/// Create or replace the rule for a stage. An empty list removes the gate.
+#[tauri::command]
+pub async fn crm_set_stage_requirement(stage: String, required_fields: Vec<String>) -> CmdResult<Option<DealStageRequirement>> {
+    let stage = stage.trim().to_lowercase();
+    if stage.is_empty() {
+        return Err(CommandError::Config("Stage is required".into()));
+    }
+    let unknown: Vec<&String> = required_fields
+        .iter()
+        .filter(|f| !REQUIRABLE_FIELDS.iter().any(|(known, _)| *known == f.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        let known: Vec<&str> = REQUIRABLE_FIELDS.iter().map(|(f, _)| *f).collect();
+        return Err(CommandError::Config(format!(
+            "Unknown field(s): {}. Must be one of: {}",
+            unknown.iter().map(|f| f.as_str()).collect::<Vec<_>>().join(", "),
+            known.join(", ")
+        )));
+    }
+
+    let client = get_client().await?;
+    if required_fields.is_empty() {
+        client
+            .delete("deal_stage_requirements", &format!("stage=eq.{}", urlencoding::encode(&stage)))
+            .await?;
+        return Ok(None);
+    }
+    let row = serde_json::json!({
+        "stage": stage,
+        "required_fields": required_fields,
+        "updated_at": chrono::Utc::now().to_rfc3339(),
+    });
+    let saved: DealStageRequirement = client.upsert_on("deal_stage_requirements", &row, Some("stage")).await?;
+    Ok(Some(saved))
+}
+
+#[tauri::command]
+pub async fn crm_delete_stage_requirement(stage: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    client
+        .delete("deal_stage_requirements", &format!("stage=eq.{}", urlencoding::encode(&stage)))
+        .await
+}
+
+/// Report which required fields a deal is missing for `stage`. `updates` are
+/// pending column changes (snake_case) to check as if already saved.
+#[tauri::command]
+pub async fn crm_check_deal_stage(deal_id: String, stage: String, updates: Option<Value>) -> CmdResult<DealStageCheck> {
+    let client = get_client().await?;
+    let deal = deal_with_updates(&client, &deal_id, updates).await?;
+    let missing_fields = match load_requirement(&client, &stage).await? {
+        Some(rule) => missing_fields(&deal, &rule.required_fields),
+        None => Vec::new(),
+    };
+    Ok(DealStageCheck { stage, missing_fields })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn empty_values_count_as_missing() {
+        let required: Vec<String> = ["deal_value", "deal_expected_close", "deal_contact_ids"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let deal = json!({ "deal_value": 0, "deal_expected_close": "2026-12-01", "deal_contact_ids": [] });
+        assert_eq!(missing_fields(&deal, &required), vec!["deal_value", "deal_contact_ids"]);
+
+        let ready = json!({ "deal_value": 12000.0, "deal_expected_close": "2026-12-01", "deal_contact_ids": ["c1"] });
+        assert!(missing_fields(&ready, &required).is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 // Data structures for companies, contacts, and activities
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============================================================================
 // Companies
@@ -271,6 +272,12 @@ pub struct Activity {
     pub created_by: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
+    /// manual | auto_capture
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Dedup key for captured activities, e.g. "meeting:{event_id}"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -316,6 +323,189 @@ pub struct UpdateActivity {
     pub task_id: Option<String>,
 }
 
+// ============================================================================
+// Close Reasons
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseReason {
+    pub outcome: String, // won | lost
+    pub value: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseReasonBucket {
+    pub outcome: String,
+    pub key: String,
+    pub label: String,
+    pub count: usize,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloseReasonReport {
+    pub period: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub won_count: usize,
+    pub lost_count: usize,
+    pub won_value: f64,
+    pub lost_value: f64,
+    pub win_rate: Option<f64>,
+    pub by_reason: Vec<CloseReasonBucket>,
+    /// Lost deals grouped by the stage they were in when lost
+    pub lost_by_stage: Vec<CloseReasonBucket>,
+    pub by_competitor: Vec<CloseReasonBucket>,
+    /// Closed deals with no reason recorded (typically closed before reasons were required)
+    pub missing_reason: usize,
+    /// Closed deals without any close date (only counted for period "all")
+    pub undated: usize,
+}
+
+// ============================================================================
+// Pipeline History
+// ============================================================================
+
+/// Row in crm_pipeline_snapshots: one active stage on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineSnapshotRow {
+    pub snapshot_date: String,
+    pub stage: String,
+    pub deal_count: i32,
+    pub total_value: f64,
+    pub weighted_value: f64,
+    pub probability: f64,
+}
+
+/// One point on the history chart: stage rows of the last snapshot in a bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineHistoryPoint {
+    /// Bucket start (the day, the Monday of the week, or the 1st of the month)
+    pub period_start: String,
+    /// Day the snapshot used for this bucket was taken
+    pub snapshot_date: String,
+    pub by_stage: Vec<PipelineSnapshotRow>,
+    pub deal_count: i32,
+    pub total_value: f64,
+    pub weighted_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineHistory {
+    pub period: String,
+    pub granularity: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub points: Vec<PipelineHistoryPoint>,
+}
+
+// ============================================================================
+// HubSpot Import
+// ============================================================================
+
+/// Stage mapping for one HubSpot pipeline (HubSpot stage ID → local deal_stage)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HubspotPipelineMapping {
+    pub stages: HashMap<String, String>,
+    /// Used for stages in this pipeline that aren't in `stages`
+    pub default_stage: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HubspotImportConfig {
+    /// Keyed by HubSpot pipeline ID ("default" for the built-in sales pipeline)
+    pub pipelines: HashMap<String, HubspotPipelineMapping>,
+    /// Fallback deal_stage when neither pipeline nor stage is mapped
+    pub default_deal_stage: String,
+    pub default_currency: String,
+    /// crm_companies.stage for newly imported companies
+    pub company_stage: String,
+    pub import_notes: bool,
+    /// Incremental cursor, set after each run — held back before any record that
+    /// failed or conflicted so the next pull retries it
+    pub last_pull_at: Option<String>,
+}
+
+impl Default for HubspotImportConfig {
+    fn default() -> Self {
+        Self {
+            pipelines: HashMap::new(),
+            default_deal_stage: "lead".to_string(),
+            default_currency: "SGD".to_string(),
+            company_stage: "prospect".to_string(),
+            import_notes: true,
+            last_pull_at: None,
+        }
+    }
+}
+
+/// Row in crm_external_ids
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalLink {
+    pub id: String,
+    pub source: String,
+    pub entity_type: String, // company | contact | deal | note
+    pub external_id: String,
+    pub local_id: String,
+    pub external_updated_at: Option<String>,
+    pub synced_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportCounts {
+    pub created: usize,
+    pub updated: usize,
+    /// Existing local records matched (by name/email) and linked to the external ID
+    pub linked: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub entity_type: String,
+    pub external_id: String,
+    pub name: String,
+    pub local_id: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HubspotImportResult {
+    pub dry_run: bool,
+    /// None for a full import, otherwise the cutoff used for the incremental pull
+    pub since: Option<String>,
+    pub companies: ImportCounts,
+    pub contacts: ImportCounts,
+    pub deals: ImportCounts,
+    pub notes: ImportCounts,
+    pub conflicts: Vec<ImportConflict>,
+    pub duration_ms: u64,
+}
+
+// ============================================================================
+// Stage Requirements
+// ============================================================================
+
+/// Deal columns that must be filled before a deal can enter `stage`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealStageRequirement {
+    pub stage: String,
+    pub required_fields: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealStageCheck {
+    pub stage: String,
+    /// Required columns that are still empty (empty = the move is allowed)
+    pub missing_fields: Vec<String>,
+}
+
 // ============================================================================
 // Email Links
 // ============================================================================
@@ -344,4 +534,133 @@ pub struct LinkEmailRequest {
     pub match_type: String,
 }
 
+// ============================================================================
+// Segments
+// ============================================================================
+
+/// One rule in a segment filter. `field` is a dotted path into the record
+/// (`industry`, `company.employee_count`, `engagement.activities_90d`,
+/// `usage.views_30d`); `op` is eq | neq | in | not_in | contains | gt | gte |
+/// lt | lte | exists | not_exists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentCondition {
+    pub field: String,
+    pub op: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+    /// ICP weight (default 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SegmentFilter {
+    #[serde(default)]
+    pub conditions: Vec<SegmentCondition>,
+    /// Minimum score (0-100) to be a member; default 100 = every condition matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub entity_type: String, // company | contact
+    pub filter: SegmentFilter,
+    pub refresh_interval_hours: i32,
+    pub member_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_computed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSegment {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub entity_type: String,
+    pub filter: SegmentFilter,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_hours: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentMember {
+    pub id: String,
+    pub segment_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+    pub score: f64,
+    pub computed_at: String,
+    // Nested data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<Box<Company>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Box<Contact>>,
+}
+
+// ============================================================================
+// Deal Suggestions
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealSuggestion {
+    pub id: String,
+    pub email_id: String,
+    pub company_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_email: Option<String>,
+    #[serde(default)]
+    pub signals: Vec<String>, // pricing | demo | quote | trial | proposal | purchase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub confidence: f64, // 0-1
+    pub suggested_name: String,
+    pub suggested_stage: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_solution: Option<String>,
+    pub status: String, // pending | accepted | dismissed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+    // Nested data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<Box<Company>>,
+}
+
+/// Edits applied when accepting a suggestion (anything unset keeps the suggestion's value)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AcceptDealSuggestion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+}
+
 // Pipeline Stats live in work/types.rs (deals are projects now)
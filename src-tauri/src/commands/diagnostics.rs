@@ -0,0 +1,247 @@
+// Diagnostics - App health snapshot and support bundle export
+// Collects version/OS info, enabled modules, recent logs, failed job runs,
+// sync statuses and masked settings into a single zip teammates can attach
+// when reporting an issue. Every section is best-effort: a failing source is
+// recorded as an error in the bundle rather than aborting the export.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings::{self, load_settings, mask_key};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// Failed job runs included in the bundle
+const MAX_FAILED_RUNS: usize = 20;
+/// Lines kept from the tail of each log file
+const LOG_TAIL_LINES: usize = 500;
+
+/// Settings keys that are safe to include unmasked
+const PLAIN_KEYS: [&str; 4] = [
+    settings::KEY_SUPABASE_URL,
+    settings::KEY_KNOWLEDGE_PATH,
+    settings::KEY_EMAIL_API_BASE_URL,
+    settings::KEY_NOTION_DEFAULT_DB,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsBundle {
+    pub path: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub collected_at: String,
+    /// Files written into the zip
+    pub files: Vec<String>,
+    /// Sections that could not be collected
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Anything that looks like a credential gets masked; toggles, paths and URLs pass through
+pub fn is_secret_setting(key: &str) -> bool {
+    if PLAIN_KEYS.contains(&key) || key.starts_with("bg_sync_") {
+        return false;
+    }
+    let k = key.to_lowercase();
+    ["key", "secret", "token", "password", "passwd", "credential", "authorization", "connection_string", "client_id"]
+        .iter()
+        .any(|s| k.contains(s))
+}
+
+fn masked_settings() -> CmdResult<Value> {
+    let settings = load_settings()?;
+    let mut keys: Vec<(&String, &String)> = settings.keys.iter().collect();
+    keys.sort_by(|a, b| a.0.cmp(b.0));
+    let map: serde_json::Map<String, Value> = keys
+        .into_iter()
+        .map(|(k, v)| {
+            let shown = if is_secret_setting(k) { mask_key(v) } else { v.clone() };
+            (k.clone(), Value::String(shown))
+        })
+        .collect();
+    Ok(Value::Object(map))
+}
+
+fn tail_lines(path: &Path, n: usize) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    Some(lines[lines.len().saturating_sub(n)..].join("\n"))
+}
+
+/// Known log files written by the app and its background jobs
+fn log_files() -> Vec<(&'static str, PathBuf)> {
+    vec![
+        ("scheduler-debug.log", PathBuf::from("/tmp/scheduler-debug.log")),
+        ("scheduler-loop-debug.log", PathBuf::from("/tmp/scheduler-loop-debug.log")),
+    ]
+}
+
+async fn sync_statuses() -> Value {
+    let outlook = crate::commands::outlook::commands::outlook_sync_status().await;
+    let outlook_push = crate::commands::outlook::push::outlook_push_status().await;
+    let notion = crate::commands::notion::commands::notion_sync_status().await;
+    let analytics = crate::commands::analytics::ga4::ga4_check_config().await;
+
+    let mut val = serde_json::Map::new();
+    match crate::commands::val_sync::config::val_sync_list_domains() {
+        Ok(domains) => {
+            for d in domains {
+                let status = crate::commands::val_sync::metadata::val_sync_get_status(d.domain.clone()).await;
+                val.insert(d.domain, to_section(status));
+            }
+        }
+        Err(e) => {
+            val.insert("error".to_string(), Value::String(e.to_string()));
+        }
+    }
+
+    json!({
+        "outlook": to_section(outlook),
+        "outlook_push": to_section(outlook_push),
+        "notion": to_section(notion),
+        "analytics": to_section(analytics),
+        "val": Value::Object(val),
+        "background_sync": {
+            "outlook_email": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_OUTLOOK_EMAIL),
+            "outlook_calendar": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_OUTLOOK_CALENDAR),
+            "notion": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_NOTION),
+            "public_data": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_PUBLIC_DATA),
+            "ga4": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_GA4),
+            "hubspot": settings::is_bg_sync_enabled(settings::KEY_BG_SYNC_HUBSPOT),
+        },
+    })
+}
+
+fn to_section<T: Serialize>(result: CmdResult<T>) -> Value {
+    match result {
+        Ok(v) => serde_json::to_value(v).unwrap_or(Value::Null),
+        Err(e) => json!({ "error": e.to_string() }),
+    }
+}
+
+async fn failed_runs() -> CmdResult<Value> {
+    use crate::commands::scheduler::types::RunStatus;
+    let runs = crate::commands::scheduler::storage::load_runs_async(None, 200).await?;
+    let failed: Vec<Value> = runs
+        .into_iter()
+        .filter(|r| r.status == RunStatus::Failed)
+        .take(MAX_FAILED_RUNS)
+        .map(|r| {
+            json!({
+                "job_name": r.job_name,
+                "started_at": r.started_at.to_rfc3339(),
+                "duration_secs": r.duration_secs,
+                "trigger": r.trigger,
+                "error": r.error,
+                "output_preview": r.output_preview,
+            })
+        })
+        .collect();
+    Ok(Value::Array(failed))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Collect a support bundle into a zip at `output_path`.
+///
+/// `enabled_modules` and `frontend_logs` come from the UI (module visibility
+/// and the console buffer live in the webview, not the backend).
+#[tauri::command]
+pub async fn diagnostics_collect(
+    app_handle: tauri::AppHandle,
+    output_path: String,
+    enabled_modules: Option<Vec<String>>,
+    frontend_logs: Option<Vec<String>>,
+) -> CmdResult<DiagnosticsBundle> {
+    let mut path = PathBuf::from(&output_path);
+    if path.extension().and_then(|e| e.to_str()) != Some("zip") {
+        path.set_extension("zip");
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let app_version = app_handle.package_info().version.to_string();
+    let collected_at = chrono::Utc::now().to_rfc3339();
+    let mut errors = Vec::new();
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    let manifest = json!({
+        "app_version": app_version,
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "family": std::env::consts::FAMILY,
+        "collected_at": collected_at,
+        "enabled_modules": enabled_modules.unwrap_or_default(),
+    });
+    entries.push(("manifest.json".to_string(), serde_json::to_string_pretty(&manifest)?));
+
+    match masked_settings() {
+        Ok(v) => entries.push(("settings.json".to_string(), serde_json::to_string_pretty(&v)?)),
+        Err(e) => errors.push(format!("settings: {}", e)),
+    }
+
+    entries.push(("sync_status.json".to_string(), serde_json::to_string_pretty(&sync_statuses().await)?));
+
+    match failed_runs().await {
+        Ok(v) => entries.push(("job_failures.json".to_string(), serde_json::to_string_pretty(&v)?)),
+        Err(e) => errors.push(format!("job failures: {}", e)),
+    }
+
+    for (name, file) in log_files() {
+        if let Some(tail) = tail_lines(&file, LOG_TAIL_LINES) {
+            entries.push((format!("logs/{}", name), tail));
+        }
+    }
+    if let Some(lines) = frontend_logs.filter(|l| !l.is_empty()) {
+        let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+        entries.push(("logs/frontend.log".to_string(), lines[start..].join("\n")));
+    }
+
+    if !errors.is_empty() {
+        entries.push(("errors.txt".to_string(), errors.join("\n")));
+    }
+
+    let zip_err = |e: zip::result::ZipError| CommandError::Io(format!("Failed to write bundle: {}", e));
+    let file = std::fs::File::create(&path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in &entries {
+        zip.start_file(name.as_str(), options).map_err(zip_err)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish().map_err(zip_err)?;
+
+    Ok(DiagnosticsBundle {
+        path: path.to_string_lossy().to_string(),
+        app_version,
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        collected_at,
+        files: entries.into_iter().map(|(name, _)| name).collect(),
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_credentials_only() {
+        assert!(is_secret_setting(settings::KEY_ANTHROPIC_API));
+        assert!(is_secret_setting(settings::KEY_MS_GRAPH_CLIENT_SECRET));
+        assert!(is_secret_setting(settings::KEY_HUBSPOT_API));
+        assert!(is_secret_setting(settings::KEY_SUPABASE_ANON_KEY));
+        assert!(!is_secret_setting(settings::KEY_SUPABASE_URL));
+        assert!(!is_secret_setting(settings::KEY_KNOWLEDGE_PATH));
+        assert!(!is_secret_setting(settings::KEY_BG_SYNC_NOTION));
+    }
+}
@@ -28,6 +28,20 @@ pub enum CommandError {
 
     #[error("{0}")]
     Internal(String),
+
+    /// Input failed validation; `fields` names what's missing or invalid
+    #[error("{message}")]
+    Validation { message: String, fields: Vec<String> },
+
+    /// A write lost a race: the target changed since the caller read it.
+    /// Carries what's on disk now so the UI can merge or overwrite.
+    #[error("{message}")]
+    Conflict {
+        message: String,
+        current_content: String,
+        current_mtime: Option<i64>,
+        current_hash: Option<String>,
+    },
 }
 
 // Tauri v2 requires the error type to implement Serialize.
@@ -45,6 +59,22 @@ impl Serialize for CommandError {
                 s.serialize_field("message", &msg)?;
                 return s.end();
             }
+            CommandError::Validation { message, fields } => {
+                let mut s = serializer.serialize_struct("CommandError", 3)?;
+                s.serialize_field("code", "validation")?;
+                s.serialize_field("message", message)?;
+                s.serialize_field("fields", fields)?;
+                return s.end();
+            }
+            CommandError::Conflict { message, current_content, current_mtime, current_hash } => {
+                let mut s = serializer.serialize_struct("CommandError", 6)?;
+                s.serialize_field("code", "conflict")?;
+                s.serialize_field("message", message)?;
+                s.serialize_field("current_content", current_content)?;
+                s.serialize_field("current_mtime", current_mtime)?;
+                s.serialize_field("current_hash", current_hash)?;
+                return s.end();
+            }
             CommandError::Parse(msg) => ("parse", msg.as_str()),
             CommandError::NotFound(msg) => ("not_found", msg.as_str()),
             CommandError::Config(msg) => ("config", msg.as_str()),
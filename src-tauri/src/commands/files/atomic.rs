@@ -0,0 +1,164 @@
+// src-tauri/src/commands/files/atomic.rs
+// Atomic writes (temp file + rename) and conflict detection for write_file.
+// Editors pass back the stamp they read the file with; if another window or
+// tool changed it since, the write is refused with a Conflict carrying the
+// current content so the UI can offer a merge instead of clobbering it.
+
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::command;
+
+/// Disambiguates temp files from concurrent writes in one process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// What a file looked like when it was read or written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileStamp {
+    /// Modification time, milliseconds since the Unix epoch
+    pub modified_ms: Option<i64>,
+    /// SHA-256 of the raw bytes, hex
+    pub hash: String,
+    pub size: u64,
+}
+
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn modified_ms(meta: &fs::Metadata) -> Option<i64> {
+    meta.modified()
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis())
+}
+
+/// Stamp for `bytes` as currently stored at `path`
+pub fn stamp_for(path: &Path, bytes: &[u8]) -> FileStamp {
+    FileStamp {
+        modified_ms: fs::metadata(path).ok().as_ref().and_then(modified_ms),
+        hash: content_hash(bytes),
+        size: bytes.len() as u64,
+    }
+}
+
+/// Refuse the write if `path` no longer matches what the caller read. The
+/// hash wins when both are given, so a touch without edits isn't a conflict.
+/// A file deleted since it was read also conflicts (with empty content).
+pub fn check_expected(path: &Path, expected_mtime: Option<i64>, expected_hash: Option<&str>) -> CmdResult<()> {
+    if expected_mtime.is_none() && expected_hash.is_none() {
+        return Ok(());
+    }
+    let current = match fs::read(path) {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(CommandError::Io(format!("Failed to read file: {}", e))),
+    };
+    let stamp = current.as_deref().map(|bytes| stamp_for(path, bytes));
+
+    let unchanged = match (&stamp, expected_hash) {
+        (None, _) => false,
+        (Some(stamp), Some(hash)) => stamp.hash.eq_ignore_ascii_case(hash),
+        (Some(stamp), None) => stamp.modified_ms == expected_mtime,
+    };
+    if unchanged {
+        return Ok(());
+    }
+
+    let what = if stamp.is_some() { "changed" } else { "was deleted" };
+    Err(CommandError::Conflict {
+        message: format!("{} {} since it was opened", path.display(), what),
+        current_content: current
+            .map(|bytes| super::encoding::decode_bytes(&bytes).content)
+            .unwrap_or_default(),
+        current_mtime: stamp.as_ref().and_then(|s| s.modified_ms),
+        current_hash: stamp.map(|s| s.hash),
+    })
+}
+
+/// Write `bytes` to a temp file beside `path`, then rename it over `path`, so
+/// readers never see a half-written file. Symlinks are written through (the
+/// link is kept) and the existing file's permissions are carried over.
+pub fn atomic_write(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = target
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let name = target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let tmp = dir.join(format!(
+        ".{}.tmp-{}-{}",
+        name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        if let Ok(meta) = fs::metadata(&target) {
+            fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        fs::rename(&tmp, &target)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Current stamp of a file, to pass back to write_file as expected_mtime / expected_hash
+#[command]
+pub async fn get_file_stamp(path: String) -> CmdResult<FileStamp> {
+    let bytes = fs::read(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    Ok(stamp_for(Path::new(&path), &bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_atomically_and_detects_conflicts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let path = dir.join("note.md");
+
+        atomic_write(&path, b"first").unwrap();
+        let read_stamp = stamp_for(&path, b"first");
+        assert!(check_expected(&path, read_stamp.modified_ms, Some(read_stamp.hash.as_str())).is_ok());
+
+        // Another window saves in between
+        atomic_write(&path, b"second").unwrap();
+        match check_expected(&path, None, Some(read_stamp.hash.as_str())) {
+            Err(CommandError::Conflict { current_content, current_hash, .. }) => {
+                assert_eq!(current_content, "second");
+                assert_eq!(current_hash, Some(content_hash(b"second")));
+            }
+            other => panic!("expected conflict, got {:?}", other),
+        }
+        assert!(check_expected(&path, None, None).is_ok());
+
+        // No temp files left behind
+        let names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["note.md"]);
+
+        #[cfg(unix)]
+        {
+            let link = dir.join("link.md");
+            std::os::unix::fs::symlink(&path, &link).unwrap();
+            atomic_write(&link, b"third").unwrap();
+            assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+            assert_eq!(fs::read(&path).unwrap(), b"third");
+        }
+    }
+}
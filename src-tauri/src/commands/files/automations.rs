@@ -0,0 +1,543 @@
+// src-tauri/src/commands/files/automations.rs
+// Watch-and-run automation hooks: when a file matching a rule's glob is
+// created or modified under the rule's folder, run the rule's shell command
+// (generate a PDF, validate markdown, reindex...). Rules live in
+// ~/.tv-client/automations/file_rules.json, runs are appended to
+// file_runs.jsonl next to it.
+
+use crate::commands::error::{CmdResult, CommandError};
+use notify::RecommendedWatcher;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{command, Emitter};
+
+/// Quiet period so an editor's save burst triggers one run
+const DEBOUNCE: Duration = Duration::from_millis(1000);
+/// Events for a file right after its rule ran are ignored, so commands that
+/// touch their own input don't loop
+const COOLDOWN: Duration = Duration::from_secs(5);
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+/// Output kept per run in the log
+const MAX_OUTPUT_CHARS: usize = 4000;
+/// Log is trimmed back to this many runs once it grows past twice that
+const MAX_LOG_RUNS: usize = 500;
+
+/// One watcher per folder that has at least one enabled rule
+static AUTOMATION_WATCHERS: std::sync::LazyLock<Mutex<HashMap<String, RecommendedWatcher>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// (rule id, path) → when its last run finished
+static LAST_RUN: std::sync::LazyLock<Mutex<HashMap<(String, PathBuf), Instant>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// (rule id, path) pairs with a run in progress
+static RUNNING: std::sync::LazyLock<Mutex<HashSet<(String, PathBuf)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashSet::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileAutomationRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// Folder to watch (recursively)
+    pub folder: String,
+    /// Glob relative to the folder: `contracts/**/*.md`. Without a `/` it
+    /// matches file names anywhere below the folder (`*.md`).
+    pub pattern: String,
+    #[serde(default = "default_true")]
+    pub on_create: bool,
+    #[serde(default = "default_true")]
+    pub on_modify: bool,
+    /// Shell command. Placeholders: {path} {name} {stem} {dir} {folder}
+    pub command: String,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileAutomationRun {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub path: String,
+    /// created | modified | manual
+    pub trigger: String,
+    pub command: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    pub timed_out: bool,
+    /// Tail of combined stdout + stderr
+    pub output: String,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn automations_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("automations")
+}
+
+fn load_rules() -> Vec<FileAutomationRule> {
+    fs::read_to_string(automations_dir().join("file_rules.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_rules(rules: &[FileAutomationRule]) -> CmdResult<()> {
+    let dir = automations_dir();
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("file_rules.json"), serde_json::to_string_pretty(rules)?)?;
+    Ok(())
+}
+
+fn append_run(run: &FileAutomationRun) -> CmdResult<()> {
+    let dir = automations_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("file_runs.jsonl");
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    drop(file);
+
+    let content = fs::read_to_string(&path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() > MAX_LOG_RUNS * 2 {
+        let kept = lines[lines.len() - MAX_LOG_RUNS..].join("\n");
+        fs::write(&path, format!("{}\n", kept))?;
+    }
+    Ok(())
+}
+
+fn load_runs() -> Vec<FileAutomationRun> {
+    fs::read_to_string(automations_dir().join("file_runs.jsonl"))
+        .map(|content| content.lines().filter_map(|l| serde_json::from_str(l).ok()).collect())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Glob match with `*` (within a segment), `**` (any number of segments) and
+/// `?`. Patterns without a `/` are matched against the file name only.
+pub fn glob_match(pattern: &str, rel_path: &str) -> bool {
+    let rel_path = rel_path.replace('\\', "/");
+    if !pattern.contains('/') {
+        let name = rel_path.rsplit('/').next().unwrap_or(&rel_path);
+        return segment_match(pattern.as_bytes(), name.as_bytes());
+    }
+    let pattern: Vec<&str> = pattern.trim_start_matches("./").split('/').collect();
+    let path: Vec<&str> = rel_path.split('/').collect();
+    path_match(&pattern, &path)
+}
+
+fn path_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| path_match(rest, &path[skip..])),
+        Some((seg, rest)) => match path.split_first() {
+            Some((p, path_rest)) => segment_match(seg.as_bytes(), p.as_bytes()) && path_match(rest, path_rest),
+            None => false,
+        },
+    }
+}
+
+fn segment_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| segment_match(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && segment_match(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && segment_match(rest, &text[1..]),
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Substitute quoted placeholders for the triggering file
+pub fn render_command(template: &str, path: &Path, folder: &Path) -> String {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let stem = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+    template
+        .replace("{path}", &shell_quote(&path.to_string_lossy()))
+        .replace("{name}", &shell_quote(&name))
+        .replace("{stem}", &shell_quote(&stem))
+        .replace("{dir}", &shell_quote(&dir))
+        .replace("{folder}", &shell_quote(&folder.to_string_lossy()))
+}
+
+fn rule_matches(rule: &FileAutomationRule, path: &Path, trigger: &str) -> bool {
+    if !rule.enabled || (trigger == "created" && !rule.on_create) || (trigger == "modified" && !rule.on_modify) {
+        return false;
+    }
+    let Ok(rel) = path.strip_prefix(&rule.folder) else {
+        return false;
+    };
+    let hidden = rel.components().any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    !hidden && glob_match(&rule.pattern, &rel.to_string_lossy())
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+fn execute(rule: &FileAutomationRule, path: &Path, trigger: &str) -> FileAutomationRun {
+    use std::process::{Command, Stdio};
+
+    let command = render_command(&rule.command, path, Path::new(&rule.folder));
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let start = Instant::now();
+    let timeout = Duration::from_secs(rule.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+
+    // Output goes to a scratch file so a chatty command can't fill a pipe and stall
+    let out_path = std::env::temp_dir().join(format!(
+        "tv-automation-{}-{}.log",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let mut exit_code = None;
+    let mut timed_out = false;
+    let mut output = String::new();
+
+    let spawned = fs::File::create(&out_path).and_then(|out| {
+        let err = out.try_clone()?;
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut c = Command::new("cmd");
+            c.args(["/C", &command]);
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.args(["-c", &command]);
+            c
+        };
+        cmd.current_dir(&rule.folder)
+            .env("TV_FILE_PATH", path)
+            .env("TV_FILE_TRIGGER", trigger)
+            .env("TV_AUTOMATION_RULE", &rule.name)
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(out))
+            .stderr(Stdio::from(err))
+            .spawn()
+    });
+
+    match spawned {
+        Ok(mut child) => {
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => {
+                        exit_code = status.code();
+                        break;
+                    }
+                    Ok(None) if start.elapsed() >= timeout => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        timed_out = true;
+                        break;
+                    }
+                    Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+                    Err(e) => {
+                        output = format!("Failed to wait for command: {}", e);
+                        break;
+                    }
+                }
+            }
+            if output.is_empty() {
+                let raw = fs::read_to_string(&out_path).unwrap_or_default();
+                let skip = raw.chars().count().saturating_sub(MAX_OUTPUT_CHARS);
+                output = raw.chars().skip(skip).collect();
+            }
+        }
+        Err(e) => output = format!("Failed to start command: {}", e),
+    }
+    let _ = fs::remove_file(&out_path);
+
+    FileAutomationRun {
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        path: path.to_string_lossy().to_string(),
+        trigger: trigger.to_string(),
+        command,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        exit_code,
+        success: exit_code == Some(0) && !timed_out,
+        timed_out,
+        output,
+    }
+}
+
+fn run_and_log(app: &tauri::AppHandle, rule: &FileAutomationRule, path: &Path, trigger: &str) -> FileAutomationRun {
+    let key = (rule.id.clone(), path.to_path_buf());
+    if let Ok(mut running) = RUNNING.lock() {
+        running.insert(key.clone());
+    }
+    let run = execute(rule, path, trigger);
+    if let Ok(mut last) = LAST_RUN.lock() {
+        last.insert(key.clone(), Instant::now());
+    }
+    if let Ok(mut running) = RUNNING.lock() {
+        running.remove(&key);
+    }
+    if let Err(e) = append_run(&run) {
+        log::warn!("Failed to log automation run for {}: {}", rule.name, e);
+    }
+    let _ = app.emit("file-automation:run", &run);
+    run
+}
+
+/// Still running, or finished within COOLDOWN
+fn in_cooldown(rule_id: &str, path: &Path) -> bool {
+    let key = (rule_id.to_string(), path.to_path_buf());
+    if RUNNING.lock().map(|running| running.contains(&key)).unwrap_or(false) {
+        return true;
+    }
+    LAST_RUN
+        .lock()
+        .ok()
+        .and_then(|last| last.get(&key).copied())
+        .is_some_and(|at| at.elapsed() < COOLDOWN)
+}
+
+// ============================================================================
+// Watchers
+// ============================================================================
+
+fn trigger_for(kind: &notify::EventKind, path: &Path) -> Option<&'static str> {
+    use notify::event::{EventKind, ModifyKind};
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        // Moved/renamed into place — what most "drop a file in" flows look like
+        EventKind::Modify(ModifyKind::Name(_)) if path.exists() => Some("created"),
+        EventKind::Modify(ModifyKind::Name(_)) | EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) => Some("modified"),
+        _ => None,
+    }
+}
+
+fn start_watch(app: tauri::AppHandle, folder: String) -> CmdResult<()> {
+    use notify::{Config, RecursiveMode, Watcher};
+    use std::sync::mpsc::{channel, RecvTimeoutError};
+
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(tx, Config::default())
+        .map_err(|e| CommandError::Internal(format!("Failed to create watcher: {}", e)))?;
+    watcher
+        .watch(Path::new(&folder), RecursiveMode::Recursive)
+        .map_err(|e| CommandError::Internal(format!("Failed to watch directory: {}", e)))?;
+    {
+        let mut watchers = AUTOMATION_WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        watchers.insert(folder.clone(), watcher);
+    }
+
+    std::thread::spawn(move || {
+        // path → trigger; "created" wins over a following "modified"
+        let mut pending: HashMap<PathBuf, &'static str> = HashMap::new();
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    for path in event.paths {
+                        if path.is_dir() {
+                            continue;
+                        }
+                        if let Some(trigger) = trigger_for(&event.kind, &path) {
+                            let entry = pending.entry(path).or_insert(trigger);
+                            if trigger == "created" {
+                                *entry = trigger;
+                            }
+                        }
+                    }
+                }
+                Ok(Err(e)) => log::error!("Automation watch error for {}: {:?}", folder, e),
+                Err(RecvTimeoutError::Timeout) => {
+                    if pending.is_empty() {
+                        continue;
+                    }
+                    // Rules are re-read so edits apply without restarting the watcher
+                    let rules: Vec<FileAutomationRule> =
+                        load_rules().into_iter().filter(|r| r.folder == folder).collect();
+                    for (path, trigger) in pending.drain() {
+                        if !path.exists() {
+                            continue;
+                        }
+                        for rule in rules.iter().filter(|r| rule_matches(r, &path, trigger)) {
+                            if in_cooldown(&rule.id, &path) {
+                                continue;
+                            }
+                            let app = app.clone();
+                            let rule = rule.clone();
+                            let path = path.clone();
+                            std::thread::spawn(move || {
+                                run_and_log(&app, &rule, &path, trigger);
+                            });
+                        }
+                    }
+                }
+                // Watcher dropped by sync_watchers
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Watch exactly the folders that have an enabled rule
+fn sync_watchers(app: &tauri::AppHandle) -> CmdResult<()> {
+    let mut folders: Vec<String> = load_rules()
+        .into_iter()
+        .filter(|r| r.enabled && Path::new(&r.folder).is_dir())
+        .map(|r| r.folder)
+        .collect();
+    folders.sort();
+    folders.dedup();
+
+    let missing: Vec<String> = {
+        let mut watchers = AUTOMATION_WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        watchers.retain(|folder, _| folders.contains(folder));
+        folders.iter().filter(|f| !watchers.contains_key(*f)).cloned().collect()
+    };
+    for folder in missing {
+        start_watch(app.clone(), folder)?;
+    }
+    Ok(())
+}
+
+/// Called from app setup — starts watchers for saved rules
+pub fn start_file_automations(app: tauri::AppHandle) {
+    if let Err(e) = sync_watchers(&app) {
+        log::warn!("File automations not started: {}", e);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn files_automation_list_rules() -> CmdResult<Vec<FileAutomationRule>> {
+    Ok(load_rules())
+}
+
+/// Create (empty `id`) or update a rule
+#[command]
+pub async fn files_automation_save_rule(
+    app: tauri::AppHandle,
+    mut rule: FileAutomationRule,
+) -> CmdResult<FileAutomationRule> {
+    rule.folder = rule.folder.trim_end_matches(|c: char| c == '/' || c == '\\').to_string();
+    if !Path::new(&rule.folder).is_dir() {
+        return Err(CommandError::NotFound(format!("Folder not found: {}", rule.folder)));
+    }
+    if rule.pattern.trim().is_empty() || rule.command.trim().is_empty() {
+        return Err(CommandError::Config("Rule needs a pattern and a command".into()));
+    }
+
+    let mut rules = load_rules();
+    if rule.id.is_empty() {
+        rule.id = format!("rule_{}", chrono::Utc::now().timestamp_millis());
+        rule.created_at = Some(chrono::Utc::now().to_rfc3339());
+        rules.push(rule.clone());
+    } else {
+        let existing = rules
+            .iter_mut()
+            .find(|r| r.id == rule.id)
+            .ok_or_else(|| CommandError::NotFound(format!("Rule not found: {}", rule.id)))?;
+        rule.created_at = existing.created_at.clone();
+        *existing = rule.clone();
+    }
+    save_rules(&rules)?;
+    sync_watchers(&app)?;
+    Ok(rule)
+}
+
+#[command]
+pub async fn files_automation_delete_rule(app: tauri::AppHandle, id: String) -> CmdResult<bool> {
+    let mut rules = load_rules();
+    let before = rules.len();
+    rules.retain(|r| r.id != id);
+    let removed = rules.len() != before;
+    if removed {
+        save_rules(&rules)?;
+        sync_watchers(&app)?;
+    }
+    Ok(removed)
+}
+
+/// Run a rule against a file now, regardless of its pattern (for testing a rule)
+#[command]
+pub async fn files_automation_run_rule(app: tauri::AppHandle, id: String, path: String) -> CmdResult<FileAutomationRun> {
+    let rule = load_rules()
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| CommandError::NotFound(format!("Rule not found: {}", id)))?;
+    tauri::async_runtime::spawn_blocking(move || run_and_log(&app, &rule, Path::new(&path), "manual"))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Task error: {}", e)))
+}
+
+/// Run log, newest first
+#[command]
+pub async fn files_automation_list_runs(rule_id: Option<String>, limit: Option<usize>) -> CmdResult<Vec<FileAutomationRun>> {
+    Ok(load_runs()
+        .into_iter()
+        .rev()
+        .filter(|r| rule_id.as_ref().map(|id| id == &r.rule_id).unwrap_or(true))
+        .take(limit.unwrap_or(100))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_globs_against_relative_paths() {
+        assert!(glob_match("*.md", "contracts/acme/order-form.md"));
+        assert!(glob_match("contracts/*.md", "contracts/order.md"));
+        assert!(!glob_match("contracts/*.md", "contracts/acme/order.md"));
+        assert!(glob_match("contracts/**/*.md", "contracts/order.md"));
+        assert!(glob_match("contracts/**/*.md", "contracts/acme/2026/order.md"));
+        assert!(!glob_match("contracts/**/*.md", "notes/order.md"));
+        assert!(glob_match("order-??.md", "order-01.md"));
+        assert!(!glob_match("*.md", "order.pdf"));
+    }
+
+    #[test]
+    fn renders_quoted_placeholders() {
+        let cmd = render_command(
+            "pandoc {path} -o {dir}/{stem}.pdf",
+            Path::new("/k/contracts/it's.md"),
+            Path::new("/k"),
+        );
+        if !cfg!(target_os = "windows") {
+            assert_eq!(cmd, "pandoc '/k/contracts/it'\\''s.md' -o '/k/contracts'/'it'\\''s'.pdf");
+        }
+    }
+}
@@ -0,0 +1,479 @@
+// src-tauri/src/commands/files/backlinks.rs
+// Backlinks + link graph for knowledge docs. Markdown links and [[wiki-links]]
+// are parsed per file and cached in memory by mtime/size, so repeat lookups
+// only re-read documents that changed. Links are resolved at query time
+// because a wiki-link's target depends on which files exist.
+
+use super::index::{doc_meta, INDEX_FILE};
+use super::lint::{prose_lines, split_frontmatter, LINK_RE};
+use super::related::{knowledge_root, relative_to};
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::command;
+
+/// [[target]], [[target#heading]], [[target|alias]] — captures the target
+static WIKI_RE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"\[\[([^\]|#]+)(?:#[^\]|]*)?(?:\|[^\]]*)?\]\]").unwrap());
+
+/// Surrounding text kept per link for the backlinks panel
+const CONTEXT_CHARS: usize = 160;
+
+// One index per knowledge root
+static LINK_INDEXES: std::sync::LazyLock<Mutex<HashMap<PathBuf, LinkIndex>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct RawLink {
+    target: String,
+    kind: &'static str, // markdown | wiki
+    line: usize,
+    context: String,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedFile {
+    modified: Option<SystemTime>,
+    size: u64,
+    title: String,
+    links: Vec<RawLink>,
+}
+
+#[derive(Debug, Default)]
+struct LinkIndex {
+    /// Keyed by path relative to the root
+    files: HashMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backlink {
+    pub source: String,
+    pub title: String,
+    pub line: usize,
+    pub kind: String,
+    /// The line the link appears on, trimmed
+    pub context: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutgoingLink {
+    /// Absolute path of the linked doc, None when it doesn't resolve
+    pub target: Option<String>,
+    pub raw: String,
+    pub kind: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backlinks {
+    pub path: String,
+    pub title: Option<String>,
+    pub backlinks: Vec<Backlink>,
+    pub outgoing: Vec<OutgoingLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkGraphNode {
+    /// Relative to the graph root
+    pub path: String,
+    pub title: String,
+    pub inbound: usize,
+    pub outbound: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkGraphEdge {
+    pub source: String,
+    pub target: String,
+    /// Links from source to target (a doc can link the same page repeatedly)
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkGraph {
+    pub root: String,
+    pub nodes: Vec<LinkGraphNode>,
+    pub edges: Vec<LinkGraphEdge>,
+    /// Links to docs that don't exist
+    pub broken_links: usize,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+fn context(line: &str) -> String {
+    let trimmed = line.trim();
+    if trimmed.chars().count() > CONTEXT_CHARS {
+        format!("{}…", trimmed.chars().take(CONTEXT_CHARS).collect::<String>())
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Only links that could point at another document: relative paths to
+/// .md/.markdown files or extensionless paths. URLs, anchors and assets are skipped.
+fn is_doc_link(target: &str) -> bool {
+    if target.starts_with('#') || target.contains("://") || target.starts_with("mailto:") || target.starts_with("tel:") {
+        return false;
+    }
+    match Path::new(target).extension().and_then(|e| e.to_str()) {
+        None => true,
+        Some(ext) => ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"),
+    }
+}
+
+/// Document links in prose (fenced code is ignored)
+fn extract_links(content: &str) -> Vec<RawLink> {
+    let (_, body, body_start) = split_frontmatter(content);
+    let mut links = Vec::new();
+    for (line_no, line) in prose_lines(body, body_start) {
+        for cap in LINK_RE.captures_iter(line) {
+            let target = cap[1].split(['#', '?']).next().unwrap_or_default();
+            if target.is_empty() || !is_doc_link(target) {
+                continue;
+            }
+            let target = urlencoding::decode(target)
+                .map(|s| s.into_owned())
+                .unwrap_or_else(|_| target.to_string());
+            links.push(RawLink { target, kind: "markdown", line: line_no, context: context(line) });
+        }
+        for cap in WIKI_RE.captures_iter(line) {
+            let target = cap[1].trim();
+            if !target.is_empty() {
+                links.push(RawLink { target: target.to_string(), kind: "wiki", line: line_no, context: context(line) });
+            }
+        }
+    }
+    links
+}
+
+/// Collapse `.` and `..` segments; None if the path climbs above the root
+fn normalize(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            s => parts.push(s),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn parent_dir(rel: &str) -> &str {
+    rel.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// Resolves link targets against the set of indexed docs
+pub(super) struct Resolver<'a> {
+    files: HashSet<&'a str>,
+    /// Lowercase file stem → docs with that stem, for wiki-links
+    by_stem: HashMap<String, Vec<&'a str>>,
+}
+
+impl<'a> Resolver<'a> {
+    pub(super) fn new(paths: impl Iterator<Item = &'a str>) -> Self {
+        let mut files = HashSet::new();
+        let mut by_stem: HashMap<String, Vec<&str>> = HashMap::new();
+        for path in paths {
+            files.insert(path);
+            if let Some(stem) = Path::new(path).file_stem().and_then(|s| s.to_str()) {
+                by_stem.entry(stem.to_lowercase()).or_default().push(path);
+            }
+        }
+        Self { files, by_stem }
+    }
+
+    /// An existing doc at `path`, trying `.md` when the link omits the extension
+    fn existing(&self, path: &str) -> Option<String> {
+        if self.files.contains(path) {
+            return Some(path.to_string());
+        }
+        let with_ext = format!("{}.md", path);
+        if self.files.contains(with_ext.as_str()) {
+            Some(with_ext)
+        } else {
+            None
+        }
+    }
+
+    /// The doc a `[[target]]` in `source` points at
+    pub(super) fn resolve_wiki(&self, source: &str, target: &str) -> Option<String> {
+        if target.contains('/') {
+            return self.existing(&normalize(target)?);
+        }
+        let stem = target.trim_end_matches(".md").to_lowercase();
+        let candidates = self.by_stem.get(&stem)?;
+        // Prefer a doc next to the source, then the shallowest match
+        let dir = parent_dir(source);
+        candidates
+            .iter()
+            .min_by_key(|c| (parent_dir(c) != dir, c.matches('/').count(), **c))
+            .map(|c| c.to_string())
+    }
+
+    fn resolve(&self, source: &str, link: &RawLink) -> Option<String> {
+        if link.kind == "wiki" {
+            return self.resolve_wiki(source, &link.target);
+        }
+        let joined = match link.target.strip_prefix('/') {
+            Some(root_relative) => root_relative.to_string(),
+            None => format!("{}/{}", parent_dir(source), link.target),
+        };
+        self.existing(&normalize(&joined)?)
+    }
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// Re-read docs whose mtime or size changed and drop deleted ones
+fn refresh(root: &Path, index: &mut LinkIndex) {
+    let mut seen = HashSet::new();
+    for entry in WalkBuilder::new(root).hidden(true).git_ignore(true).build().flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !(name.ends_with(".md") || name.ends_with(".markdown")) || name == INDEX_FILE {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let rel = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        let modified = meta.modified().ok();
+        let fresh = index
+            .files
+            .get(&rel)
+            .map(|f| f.modified == modified && f.size == meta.len())
+            .unwrap_or(false);
+        if !fresh {
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            index.files.insert(
+                rel.clone(),
+                IndexedFile {
+                    modified,
+                    size: meta.len(),
+                    title: doc_meta(&name, &content).title,
+                    links: extract_links(&content),
+                },
+            );
+        }
+        seen.insert(rel);
+    }
+    index.files.retain(|rel, _| seen.contains(rel));
+}
+
+async fn with_index<T: Send + 'static>(
+    root: PathBuf,
+    f: impl FnOnce(&LinkIndex) -> T + Send + 'static,
+) -> CmdResult<T> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut indexes = LINK_INDEXES
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Link index lock poisoned: {}", e)))?;
+        let index = indexes.entry(root.clone()).or_default();
+        refresh(&root, index);
+        Ok(f(index))
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Link index task failed: {}", e)))?
+}
+
+/// Every indexed doc under `root`, relative to it
+pub(super) async fn indexed_docs(root: PathBuf) -> CmdResult<Vec<String>> {
+    with_index(root, |index| index.files.keys().cloned().collect()).await
+}
+
+fn backlinks_for(target: &str, index: &LinkIndex) -> (Vec<Backlink>, Vec<OutgoingLink>) {
+    let resolver = Resolver::new(index.files.keys().map(|k| k.as_str()));
+    let mut backlinks = Vec::new();
+    for (source, file) in &index.files {
+        if source == target {
+            continue;
+        }
+        for link in &file.links {
+            if resolver.resolve(source, link).as_deref() == Some(target) {
+                backlinks.push(Backlink {
+                    source: source.clone(),
+                    title: file.title.clone(),
+                    line: link.line,
+                    kind: link.kind.to_string(),
+                    context: link.context.clone(),
+                });
+            }
+        }
+    }
+    backlinks.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+
+    let outgoing = index
+        .files
+        .get(target)
+        .map(|file| {
+            file.links
+                .iter()
+                .map(|link| OutgoingLink {
+                    target: resolver.resolve(target, link),
+                    raw: link.target.clone(),
+                    kind: link.kind.to_string(),
+                    line: link.line,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (backlinks, outgoing)
+}
+
+fn build_graph(index: &LinkIndex, prefix: &str) -> LinkGraph {
+    let folder = format!("{}/", prefix);
+    let in_scope = |rel: &str| prefix.is_empty() || rel.starts_with(&folder);
+    let resolver = Resolver::new(index.files.keys().map(|k| k.as_str()));
+    let mut edges: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut broken_links = 0;
+    for (source, file) in index.files.iter().filter(|(rel, _)| in_scope(rel)) {
+        for link in &file.links {
+            match resolver.resolve(source, link) {
+                Some(target) if in_scope(&target) && target != *source => {
+                    *edges.entry((source.clone(), target)).or_default() += 1;
+                }
+                Some(_) => {}
+                None => broken_links += 1,
+            }
+        }
+    }
+
+    let mut degree: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (source, target) in edges.keys() {
+        degree.entry(source.as_str()).or_default().1 += 1;
+        degree.entry(target.as_str()).or_default().0 += 1;
+    }
+    let mut nodes: Vec<LinkGraphNode> = index
+        .files
+        .iter()
+        .filter(|(rel, _)| in_scope(rel))
+        .map(|(rel, file)| {
+            let (inbound, outbound) = degree.get(rel.as_str()).copied().unwrap_or_default();
+            LinkGraphNode {
+                path: rel[prefix.len()..].trim_start_matches('/').to_string(),
+                title: file.title.clone(),
+                inbound,
+                outbound,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let strip = |rel: &str| rel[prefix.len()..].trim_start_matches('/').to_string();
+    LinkGraph {
+        root: String::new(),
+        nodes,
+        edges: edges
+            .into_iter()
+            .map(|((source, target), count)| LinkGraphEdge { source: strip(&source), target: strip(&target), count })
+            .collect(),
+        broken_links,
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Documents that link to `path`, plus the links `path` itself makes.
+/// `root` defaults to the knowledge path; links are resolved within it.
+#[command]
+pub async fn get_backlinks(path: String, root: Option<String>) -> CmdResult<Backlinks> {
+    let root = knowledge_root(root)?;
+    let target = relative_to(&root, &path);
+    let (title, backlinks, outgoing) = with_index(root.clone(), move |index| {
+        let title = index.files.get(&target).map(|f| f.title.clone());
+        let (backlinks, outgoing) = backlinks_for(&target, index);
+        (title, backlinks, outgoing)
+    })
+    .await?;
+
+    let absolute = |rel: &str| root.join(rel).to_string_lossy().to_string();
+    Ok(Backlinks {
+        path,
+        title,
+        backlinks: backlinks
+            .into_iter()
+            .map(|b| Backlink { source: absolute(&b.source), ..b })
+            .collect(),
+        outgoing: outgoing
+            .into_iter()
+            .map(|o| OutgoingLink { target: o.target.as_deref().map(absolute), ..o })
+            .collect(),
+    })
+}
+
+/// Link graph of the docs under `root` (a folder inside the knowledge path,
+/// or the whole knowledge path when omitted). Node paths are relative to `root`.
+#[command]
+pub async fn get_link_graph(root: Option<String>) -> CmdResult<LinkGraph> {
+    let knowledge = knowledge_root(None).ok();
+    let requested = root.filter(|r| !r.is_empty());
+    // Index the whole knowledge base so links leaving the folder still
+    // resolve, then keep the subtree; folders outside it are indexed alone
+    let (index_root, prefix) = match (&knowledge, &requested) {
+        (Some(k), Some(r)) if Path::new(r).starts_with(k) => (k.clone(), relative_to(k, r)),
+        (_, Some(r)) => (PathBuf::from(r), String::new()),
+        (Some(k), None) => (k.clone(), String::new()),
+        (None, None) => return Err(CommandError::Config("Knowledge path not configured".into())),
+    };
+    if !index_root.is_dir() {
+        return Err(CommandError::NotFound(format!("Folder not found: {}", index_root.display())));
+    }
+    let prefix = prefix.trim_matches('/').to_string();
+    let mut graph = with_index(index_root.clone(), move |index| build_graph(index, &prefix)).await?;
+    graph.root = requested.unwrap_or_else(|| index_root.to_string_lossy().to_string());
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_and_resolves_markdown_and_wiki_links() {
+        let doc = "---\ntitle: Orders\n---\nSee [overview](../tables/overview.md#cols) and [[Glossary|terms]].\n\
+                   ![chart](chart.png) [site](https://example.com)\n```\n[[not-a-link]]\n```\n[[tables/overview]]\n";
+        let links = extract_links(doc);
+        let targets: Vec<(&str, &str, usize)> = links.iter().map(|l| (l.target.as_str(), l.kind, l.line)).collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("../tables/overview.md", "markdown", 4),
+                ("Glossary", "wiki", 4),
+                ("tables/overview", "wiki", 9),
+            ]
+        );
+
+        let files = ["reports/orders.md", "tables/overview.md", "glossary.md", "reports/glossary.md"];
+        let resolver = Resolver::new(files.into_iter());
+        let source = "reports/orders.md";
+        assert_eq!(resolver.resolve(source, &links[0]).as_deref(), Some("tables/overview.md"));
+        // Same-folder doc wins over a shallower one
+        assert_eq!(resolver.resolve(source, &links[1]).as_deref(), Some("reports/glossary.md"));
+        assert_eq!(resolver.resolve(source, &links[2]).as_deref(), Some("tables/overview.md"));
+        assert_eq!(normalize("a/../../b"), None);
+    }
+}
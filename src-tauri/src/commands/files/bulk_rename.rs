@@ -0,0 +1,304 @@
+// src-tauri/src/commands/files/bulk_rename.rs
+// Rename many files at once from a pattern — preview first, then apply as
+// one unit that rolls back if any rename fails
+
+use crate::commands::error::{CmdResult, CommandError};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Steps applied in order: find/replace, then prefix/suffix, then sequence.
+/// By default only the file stem changes; the extension is kept.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenamePattern {
+    /// Regex to find; `replace` may use $1 / ${name} capture references
+    pub find: Option<String>,
+    pub replace: String,
+    pub case_insensitive: bool,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub sequence: Option<SequencePattern>,
+    /// Apply the pattern to the extension too
+    pub include_extension: bool,
+}
+
+/// Template tokens: {name} = stem after earlier steps, {n} = sequence number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SequencePattern {
+    pub template: String,
+    pub start: i64,
+    pub step: i64,
+    /// Zero-pad {n} to this many digits
+    pub padding: usize,
+}
+
+impl Default for SequencePattern {
+    fn default() -> Self {
+        Self { template: "{name}-{n}".to_string(), start: 1, step: 1, padding: 0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenameStatus {
+    Rename,
+    Unchanged,
+    Conflict,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamePreview {
+    pub from: String,
+    pub to: String,
+    pub status: RenameStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkRenameResult {
+    pub dry_run: bool,
+    pub renamed: usize,
+    pub items: Vec<RenamePreview>,
+}
+
+// ============================================================================
+// Planning
+// ============================================================================
+
+fn split_name(name: &str, include_extension: bool) -> (&str, &str) {
+    if include_extension {
+        return (name, "");
+    }
+    match name.rfind('.') {
+        // Dotfiles like ".env" are all stem
+        Some(i) if i > 0 => (&name[..i], &name[i..]),
+        _ => (name, ""),
+    }
+}
+
+fn new_name(name: &str, index: usize, pattern: &RenamePattern, find: Option<&regex::Regex>) -> String {
+    let (stem, ext) = split_name(name, pattern.include_extension);
+    let mut stem = match find {
+        Some(re) => re.replace_all(stem, pattern.replace.as_str()).into_owned(),
+        None => stem.to_string(),
+    };
+    if let Some(prefix) = &pattern.prefix {
+        stem = format!("{}{}", prefix, stem);
+    }
+    if let Some(suffix) = &pattern.suffix {
+        stem.push_str(suffix);
+    }
+    if let Some(seq) = &pattern.sequence {
+        let n = seq.start + seq.step * index as i64;
+        let n = format!("{:0width$}", n, width = seq.padding);
+        stem = seq.template.replace("{name}", &stem).replace("{n}", &n);
+    }
+    format!("{}{}", stem, ext)
+}
+
+fn invalid_name(name: &str) -> Option<&'static str> {
+    if name.trim().is_empty() {
+        Some("Name would be empty")
+    } else if name.contains('/') || name.contains('\\') {
+        Some("Name can't contain path separators")
+    } else if name == "." || name == ".." {
+        Some("Invalid name")
+    } else {
+        None
+    }
+}
+
+/// Work out every target and flag anything that can't be applied cleanly
+fn plan_renames(paths: &[String], pattern: &RenamePattern) -> CmdResult<Vec<RenamePreview>> {
+    let find = match pattern.find.as_deref().filter(|f| !f.is_empty()) {
+        Some(f) => Some(
+            RegexBuilder::new(f)
+                .case_insensitive(pattern.case_insensitive)
+                .build()
+                .map_err(|e| CommandError::Config(format!("Invalid regex: {}", e)))?,
+        ),
+        None => None,
+    };
+
+    let mut items: Vec<RenamePreview> = paths
+        .iter()
+        .enumerate()
+        .map(|(i, from)| {
+            let path = Path::new(from);
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let target = new_name(&name, i, pattern, find.as_ref());
+            let to = path.with_file_name(&target).to_string_lossy().to_string();
+            let (status, message) = if !path.exists() {
+                (RenameStatus::Invalid, Some("Source not found".to_string()))
+            } else if let Some(reason) = invalid_name(&target) {
+                (RenameStatus::Invalid, Some(reason.to_string()))
+            } else if target == name {
+                (RenameStatus::Unchanged, None)
+            } else {
+                (RenameStatus::Rename, None)
+            };
+            RenamePreview { from: from.clone(), to, status, message }
+        })
+        .collect();
+
+    // Two sources landing on one target, or a target that exists and isn't
+    // itself being renamed away in this batch
+    let moving_away: HashSet<PathBuf> = items
+        .iter()
+        .filter(|i| i.status == RenameStatus::Rename)
+        .map(|i| PathBuf::from(&i.from))
+        .collect();
+    let mut target_counts: HashMap<String, usize> = HashMap::new();
+    for item in items.iter().filter(|i| i.status != RenameStatus::Invalid) {
+        *target_counts.entry(item.to.clone()).or_default() += 1;
+    }
+    for item in items.iter_mut().filter(|i| i.status == RenameStatus::Rename) {
+        let to = PathBuf::from(&item.to);
+        if target_counts.get(&item.to).copied().unwrap_or(0) > 1 {
+            item.status = RenameStatus::Conflict;
+            item.message = Some("Another file in this batch gets the same name".into());
+        } else if to.exists() && !moving_away.contains(&to) && !same_file(&to, Path::new(&item.from)) {
+            item.status = RenameStatus::Conflict;
+            item.message = Some("A file with this name already exists".into());
+        }
+    }
+    Ok(items)
+}
+
+/// Case-only renames on case-insensitive volumes resolve to the same file
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Applying
+// ============================================================================
+
+/// Rename via temporary names so swaps and chains (a→b, b→a) work, undoing
+/// every completed step if one fails
+fn apply_renames(items: &[RenamePreview]) -> CmdResult<usize> {
+    let renames: Vec<(&RenamePreview, PathBuf)> = items
+        .iter()
+        .filter(|i| i.status == RenameStatus::Rename)
+        .enumerate()
+        .map(|(n, i)| {
+            let from = Path::new(&i.from);
+            let name = from.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+            let temp = from.with_file_name(format!(".{}.tv-rename-{}-{}", name, std::process::id(), n));
+            (i, temp)
+        })
+        .collect();
+
+    // Completed (from, to) steps, for rollback
+    let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let steps = renames
+        .iter()
+        .map(|(item, temp)| (PathBuf::from(&item.from), temp.clone()))
+        .chain(renames.iter().map(|(item, temp)| (temp.clone(), PathBuf::from(&item.to))));
+    for (from, to) in steps {
+        if let Err(e) = fs::rename(&from, &to) {
+            for (undo_from, undo_to) in done.iter().rev() {
+                if let Err(undo) = fs::rename(undo_to, undo_from) {
+                    log::error!("bulk_rename rollback failed for {}: {}", undo_to.display(), undo);
+                }
+            }
+            return Err(CommandError::Io(format!(
+                "Failed to rename {} (all changes rolled back): {}",
+                from.display(),
+                e
+            )));
+        }
+        done.push((from, to));
+    }
+    Ok(renames.len())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Rename `paths` by `pattern`. Defaults to a dry run that only returns the
+/// preview; with `dry_run: false` every rename is applied or none are.
+#[command]
+pub async fn bulk_rename(paths: Vec<String>, pattern: RenamePattern, dry_run: Option<bool>) -> CmdResult<BulkRenameResult> {
+    let dry_run = dry_run.unwrap_or(true);
+    let items = plan_renames(&paths, &pattern)?;
+    if dry_run {
+        return Ok(BulkRenameResult { dry_run, renamed: 0, items });
+    }
+
+    let blocked: Vec<&RenamePreview> = items
+        .iter()
+        .filter(|i| matches!(i.status, RenameStatus::Conflict | RenameStatus::Invalid))
+        .collect();
+    if let Some(first) = blocked.first() {
+        return Err(CommandError::Config(format!(
+            "{} rename(s) can't be applied, e.g. {}: {}",
+            blocked.len(),
+            first.from,
+            first.message.as_deref().unwrap_or("conflict")
+        )));
+    }
+
+    let renamed = tauri::async_runtime::spawn_blocking({
+        let items = items.clone();
+        move || apply_renames(&items)
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Rename task failed: {}", e)))??;
+    Ok(BulkRenameResult { dry_run, renamed, items })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_patterns_and_swaps_names_atomically() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        let a = root.join("a.md");
+        let b = root.join("b.md");
+        fs::write(&a, "A").unwrap();
+        fs::write(&b, "B").unwrap();
+        let paths = vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+
+        let seq = RenamePattern {
+            prefix: Some("note-".into()),
+            sequence: Some(SequencePattern { template: "{n}_{name}".into(), padding: 2, ..Default::default() }),
+            ..Default::default()
+        };
+        let preview = plan_renames(&paths, &seq).unwrap();
+        assert!(preview[0].to.ends_with("01_note-a.md"));
+        assert!(preview[1].to.ends_with("02_note-b.md"));
+
+        // Everything onto one name conflicts
+        let clash = RenamePattern { find: Some("^.*$".into()), replace: "same".into(), ..Default::default() };
+        assert!(plan_renames(&paths, &clash).unwrap().iter().all(|i| i.status == RenameStatus::Conflict));
+
+        // a ↔ b swap goes through temp names
+        let item = |from: &PathBuf, to: &PathBuf| RenamePreview {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+            status: RenameStatus::Rename,
+            message: None,
+        };
+        let items = vec![item(&a, &b), item(&b, &a)];
+        assert_eq!(apply_renames(&items).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "B");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "A");
+    }
+}
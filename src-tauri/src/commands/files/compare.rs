@@ -0,0 +1,344 @@
+// src-tauri/src/commands/files/compare.rs
+// Folder compare — reconcile two knowledge roots (e.g. a consultant's copy vs canonical)
+
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+/// Text diffs are only computed when both sides are at most this size
+const DEFAULT_MAX_DIFF_BYTES: u64 = 256 * 1024;
+/// `diff_files` refuses inputs larger than this
+const MAX_FILE_DIFF_BYTES: u64 = 8 * 1024 * 1024;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFingerprint {
+    pub hash: String,
+    pub size: u64,
+    pub modified: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedFile {
+    pub path: String, // relative to both roots
+    pub size_a: u64,
+    pub size_b: u64,
+    pub modified_a: Option<String>,
+    pub modified_b: Option<String>,
+    pub newer: Option<String>, // "a" | "b" | None when mtimes are equal or unknown
+    /// Unified diff (A → B), only for small UTF-8 files when requested
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OneSidedFile {
+    pub path: String,
+    pub size: u64,
+    pub modified: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderComparison {
+    pub path_a: String,
+    pub path_b: String,
+    pub matched: Vec<String>,
+    pub changed: Vec<ChangedFile>,
+    pub only_in_a: Vec<OneSidedFile>,
+    pub only_in_b: Vec<OneSidedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: String, // context | add | remove
+    /// 1-based line number in A (None for added lines)
+    pub old_line: Option<usize>,
+    /// 1-based line number in B (None for removed lines)
+    pub new_line: Option<usize>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    /// `@@ -a,b +c,d @@` header as in a unified diff
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    pub path_a: String,
+    pub path_b: String,
+    pub identical: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    pub hunks: Vec<DiffHunk>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Hash every file under `root` (respects .gitignore, skips hidden files), keyed by relative path
+fn fingerprint_folder(root: &Path) -> BTreeMap<String, FileFingerprint> {
+    let mut out = BTreeMap::new();
+    for entry in WalkBuilder::new(root).hidden(true).git_ignore(true).build().flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Ok(content) = fs::read(path) else {
+            continue;
+        };
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let modified = fs::metadata(path).ok().and_then(|m| m.modified().ok()).map(|t| {
+            chrono::DateTime::<chrono::Utc>::from(t)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        });
+        out.insert(
+            rel,
+            FileFingerprint {
+                hash: format!("{:x}", Sha256::digest(&content)),
+                size: content.len() as u64,
+                modified,
+            },
+        );
+    }
+    out
+}
+
+/// Split two fingerprint maps into matched / changed / only-in-A / only-in-B
+pub fn classify(
+    a: &BTreeMap<String, FileFingerprint>,
+    b: &BTreeMap<String, FileFingerprint>,
+) -> (Vec<String>, Vec<ChangedFile>, Vec<OneSidedFile>, Vec<OneSidedFile>) {
+    let mut matched = Vec::new();
+    let mut changed = Vec::new();
+    let mut only_in_a = Vec::new();
+
+    for (path, fa) in a {
+        match b.get(path) {
+            Some(fb) if fb.hash == fa.hash => matched.push(path.clone()),
+            Some(fb) => {
+                // ISO timestamps compare correctly as strings
+                let newer = match (&fa.modified, &fb.modified) {
+                    (Some(ma), Some(mb)) if ma > mb => Some("a".to_string()),
+                    (Some(ma), Some(mb)) if mb > ma => Some("b".to_string()),
+                    _ => None,
+                };
+                changed.push(ChangedFile {
+                    path: path.clone(),
+                    size_a: fa.size,
+                    size_b: fb.size,
+                    modified_a: fa.modified.clone(),
+                    modified_b: fb.modified.clone(),
+                    newer,
+                    diff: None,
+                });
+            }
+            None => only_in_a.push(OneSidedFile {
+                path: path.clone(),
+                size: fa.size,
+                modified: fa.modified.clone(),
+            }),
+        }
+    }
+
+    let only_in_b = b
+        .iter()
+        .filter(|(path, _)| !a.contains_key(*path))
+        .map(|(path, fb)| OneSidedFile {
+            path: path.clone(),
+            size: fb.size,
+            modified: fb.modified.clone(),
+        })
+        .collect();
+
+    (matched, changed, only_in_a, only_in_b)
+}
+
+/// Unified diff of two files, if both are UTF-8 text
+fn text_diff(path_a: &Path, path_b: &Path, rel: &str) -> Option<String> {
+    let a = fs::read_to_string(path_a).ok()?;
+    let b = fs::read_to_string(path_b).ok()?;
+    let diff = similar::TextDiff::from_lines(&a, &b)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", rel), &format!("b/{}", rel))
+        .to_string();
+    Some(diff)
+}
+
+/// Line-numbered hunks between two texts, with `context` unchanged lines around each change
+fn diff_hunks(a: &str, b: &str, context: usize) -> Vec<DiffHunk> {
+    use similar::{ChangeTag, TextDiff};
+
+    let diff = TextDiff::from_lines(a, b);
+    let mut hunks = Vec::new();
+    for group in diff.grouped_ops(context) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else { continue };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Insert => "add",
+                    ChangeTag::Delete => "remove",
+                    ChangeTag::Equal => "context",
+                };
+                lines.push(DiffLine {
+                    kind: kind.to_string(),
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    content: change.value().trim_end_matches(|c: char| c == '\n' || c == '\r').to_string(),
+                });
+            }
+        }
+
+        // Unified diff convention: an empty range starts at the line before it
+        let start = |range: &std::ops::Range<usize>| if range.is_empty() { range.start } else { range.start + 1 };
+        let (old_start, new_start) = (start(&old_range), start(&new_range));
+        hunks.push(DiffHunk {
+            old_start,
+            old_lines: old_range.len(),
+            new_start,
+            new_lines: new_range.len(),
+            header: format!("@@ -{},{} +{},{} @@", old_start, old_range.len(), new_start, new_range.len()),
+            lines,
+        });
+    }
+    hunks
+}
+
+fn read_for_diff(path: &str) -> CmdResult<String> {
+    let meta = fs::metadata(path).map_err(|_| CommandError::NotFound(format!("File not found: {}", path)))?;
+    if meta.len() > MAX_FILE_DIFF_BYTES {
+        return Err(CommandError::Config(format!(
+            "{} is too large to diff ({} MB max)",
+            path,
+            MAX_FILE_DIFF_BYTES / (1024 * 1024)
+        )));
+    }
+    let bytes = fs::read(path)?;
+    String::from_utf8(bytes).map_err(|_| CommandError::Parse(format!("{} is not a text file", path)))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Structured unified diff of two text files (A → B), `context` lines around
+/// each change (default 3)
+#[command]
+pub async fn diff_files(path_a: String, path_b: String, context: Option<usize>) -> CmdResult<FileDiff> {
+    let a = read_for_diff(&path_a)?;
+    let b = read_for_diff(&path_b)?;
+    let hunks = if a == b { Vec::new() } else { diff_hunks(&a, &b, context.unwrap_or(3)) };
+    let count = |kind: &str| hunks.iter().flat_map(|h| &h.lines).filter(|l| l.kind == kind).count();
+    Ok(FileDiff {
+        identical: hunks.is_empty(),
+        additions: count("add"),
+        deletions: count("remove"),
+        path_a,
+        path_b,
+        hunks,
+    })
+}
+
+/// Compare two folders by content hash. With `include_diffs`, changed text
+/// files up to `max_diff_bytes` (default 256 KB) get a unified diff.
+#[command]
+pub async fn files_compare_folders(
+    path_a: String,
+    path_b: String,
+    include_diffs: Option<bool>,
+    max_diff_bytes: Option<u64>,
+) -> CmdResult<FolderComparison> {
+    let root_a = Path::new(&path_a);
+    let root_b = Path::new(&path_b);
+    for root in [root_a, root_b] {
+        if !root.is_dir() {
+            return Err(CommandError::NotFound(format!("Folder not found: {}", root.display())));
+        }
+    }
+
+    let (a, b) = (fingerprint_folder(root_a), fingerprint_folder(root_b));
+    let (matched, mut changed, only_in_a, only_in_b) = classify(&a, &b);
+
+    if include_diffs.unwrap_or(false) {
+        let limit = max_diff_bytes.unwrap_or(DEFAULT_MAX_DIFF_BYTES);
+        for file in changed.iter_mut().filter(|f| f.size_a <= limit && f.size_b <= limit) {
+            file.diff = text_diff(&root_a.join(&file.path), &root_b.join(&file.path), &file.path);
+        }
+    }
+
+    Ok(FolderComparison {
+        path_a,
+        path_b,
+        matched,
+        changed,
+        only_in_a,
+        only_in_b,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(hash: &str, modified: &str) -> FileFingerprint {
+        FileFingerprint { hash: hash.to_string(), size: 1, modified: Some(modified.to_string()) }
+    }
+
+    #[test]
+    fn classifies_by_hash_and_reports_newer_side() {
+        let a = BTreeMap::from([
+            ("same.md".to_string(), fp("h1", "2026-01-01T00:00:00Z")),
+            ("edited.md".to_string(), fp("h2", "2026-03-01T00:00:00Z")),
+            ("a-only.md".to_string(), fp("h3", "2026-01-01T00:00:00Z")),
+        ]);
+        let b = BTreeMap::from([
+            ("same.md".to_string(), fp("h1", "2026-02-01T00:00:00Z")),
+            ("edited.md".to_string(), fp("h9", "2026-02-01T00:00:00Z")),
+            ("b-only.md".to_string(), fp("h4", "2026-01-01T00:00:00Z")),
+        ]);
+        let (matched, changed, only_a, only_b) = classify(&a, &b);
+        assert_eq!(matched, vec!["same.md"]);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].newer.as_deref(), Some("a"));
+        assert_eq!(only_a[0].path, "a-only.md");
+        assert_eq!(only_b[0].path, "b-only.md");
+    }
+
+    #[test]
+    fn hunks_carry_line_numbers_on_both_sides() {
+        let a = "one\ntwo\nthree\nfour\n";
+        let b = "one\ntwo\nTHREE\nfour\nfive\n";
+        let hunks = diff_hunks(a, b, 1);
+        assert_eq!(hunks.len(), 1);
+        let h = &hunks[0];
+        assert_eq!(h.header, "@@ -2,3 +2,4 @@");
+        let removed = h.lines.iter().find(|l| l.kind == "remove").unwrap();
+        assert_eq!((removed.old_line, removed.new_line), (Some(3), None));
+        let added: Vec<_> = h.lines.iter().filter(|l| l.kind == "add").map(|l| l.new_line).collect();
+        assert_eq!(added, vec![Some(3), Some(5)]);
+    }
+}
@@ -0,0 +1,106 @@
+// src-tauri/src/commands/files/dir_size.rs
+// Recursive disk usage of a folder (synced VAL domains, the Outlook cache, ...)
+// with throttled progress events, since big trees take a while to walk
+
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+use walkdir::WalkDir;
+
+/// Minimum gap between progress events so big folders don't flood the frontend
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectorySize {
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u64,
+    /// Subfolders, not counting `path` itself
+    pub dir_count: u64,
+    /// Entries that couldn't be read (permissions, removed mid-walk)
+    pub skipped: u64,
+    /// False while walking (progress events), true on the final result
+    pub done: bool,
+}
+
+/// Walk `root` without following symlinks (a link counts as neither a file
+/// nor its target), calling `on_progress` with the running totals
+fn measure(root: &Path, mut on_progress: impl FnMut(&DirectorySize)) -> CmdResult<DirectorySize> {
+    if !root.is_dir() {
+        return Err(CommandError::NotFound(format!("Not a directory: {}", root.display())));
+    }
+    let mut size = DirectorySize {
+        path: root.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let mut last_emit = Instant::now();
+    for entry in WalkDir::new(root).follow_links(false).min_depth(1) {
+        let Ok(entry) = entry else {
+            size.skipped += 1;
+            continue;
+        };
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            size.dir_count += 1;
+        } else if file_type.is_file() {
+            match entry.metadata() {
+                Ok(meta) => {
+                    size.file_count += 1;
+                    size.total_bytes += meta.len();
+                }
+                Err(_) => size.skipped += 1,
+            }
+        }
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            on_progress(&size);
+            last_emit = Instant::now();
+        }
+    }
+
+    size.done = true;
+    on_progress(&size);
+    Ok(size)
+}
+
+/// Total bytes and file / folder counts under `path`.
+/// Emits `directory-size:progress` with running totals while walking.
+#[command]
+pub async fn get_directory_size(app: AppHandle, path: String) -> CmdResult<DirectorySize> {
+    tauri::async_runtime::spawn_blocking(move || {
+        measure(Path::new(&path), |size| {
+            let _ = app.emit("directory-size:progress", size);
+        })
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Directory size task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn sums_files_and_folders() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("top.txt"), vec![0u8; 10]).unwrap();
+        fs::write(root.join("a/one.txt"), vec![0u8; 100]).unwrap();
+        fs::write(root.join("a/b/two.txt"), vec![0u8; 1000]).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root.join("a"), root.join("link")).unwrap();
+
+        let mut events = Vec::new();
+        let size = measure(&root, |s| events.push(s.clone())).unwrap();
+        assert_eq!((size.total_bytes, size.file_count, size.dir_count, size.skipped), (1110, 3, 2, 0));
+        assert!(size.done);
+        assert!(events.last().is_some_and(|e| e.done && e.total_bytes == 1110));
+
+        assert!(measure(&root.join("top.txt"), |_| {}).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// src-tauri/src/commands/files/duplicates.rs
+// Duplicate detection — files are grouped by size first, and only same-size
+// candidates are hashed (blake3), so a large knowledge root stays cheap to scan
+
+use super::integrity::{hash_path, HashAlgorithm};
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateSet {
+    pub hash: String,
+    /// Size of each copy in bytes
+    pub size: u64,
+    /// Sorted; the UI treats the first as the one to keep by default
+    pub paths: Vec<String>,
+    /// Bytes freed by keeping a single copy
+    pub wasted_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    pub root: String,
+    pub files_scanned: usize,
+    /// Largest savings first
+    pub sets: Vec<DuplicateSet>,
+    pub total_wasted_bytes: u64,
+}
+
+// ============================================================================
+// Scanning
+// ============================================================================
+
+/// Walk `root` (respects .gitignore, skips hidden files) and group identical files.
+/// Empty files are ignored — they're all "identical" and free to keep.
+fn scan_duplicates(root: &Path) -> DuplicateReport {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    let mut files_scanned = 0;
+    for entry in WalkBuilder::new(root).hidden(true).git_ignore(true).build().flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        files_scanned += 1;
+        if meta.len() > 0 {
+            by_size.entry(meta.len()).or_default().push(entry.into_path());
+        }
+    }
+
+    let mut sets = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, p)| p.len() > 1) {
+        let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+        for path in paths {
+            match hash_path(&path, HashAlgorithm::Blake3) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(path.to_string_lossy().to_string()),
+                Err(e) => log::warn!("find_duplicates: skipping {}: {}", path.display(), e),
+            }
+        }
+        for (hash, mut paths) in by_hash.into_iter().filter(|(_, p)| p.len() > 1) {
+            paths.sort();
+            let wasted_bytes = size * (paths.len() as u64 - 1);
+            sets.push(DuplicateSet { hash, size, paths, wasted_bytes });
+        }
+    }
+    sets.sort_by(|a, b| b.wasted_bytes.cmp(&a.wasted_bytes).then_with(|| a.paths.cmp(&b.paths)));
+
+    DuplicateReport {
+        root: root.to_string_lossy().to_string(),
+        files_scanned,
+        total_wasted_bytes: sets.iter().map(|s| s.wasted_bytes).sum(),
+        sets,
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Find sets of identical files under `root`, for cleanup of duplicated exports
+#[command]
+pub async fn find_duplicates(root: String) -> CmdResult<DuplicateReport> {
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+        return Err(CommandError::NotFound(format!("Folder not found: {}", root.display())));
+    }
+    tauri::async_runtime::spawn_blocking(move || scan_duplicates(&root))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Duplicate scan failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn groups_identical_files_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("exports")).unwrap();
+        fs::write(root.join("plan.md"), "same content").unwrap();
+        fs::write(root.join("exports/plan (1).md"), "same content").unwrap();
+        fs::write(root.join("other.md"), "diff content").unwrap(); // same size, different bytes
+        fs::write(root.join("empty-a.md"), "").unwrap();
+        fs::write(root.join("empty-b.md"), "").unwrap();
+
+        let report = scan_duplicates(&root);
+        assert_eq!(report.files_scanned, 5);
+        assert_eq!(report.sets.len(), 1);
+        assert_eq!(report.sets[0].paths.len(), 2);
+        assert!(report.sets[0].paths.iter().all(|p| p.contains("plan")));
+        assert_eq!(report.total_wasted_bytes, "same content".len() as u64);
+    }
+}
@@ -0,0 +1,137 @@
+// src-tauri/src/commands/files/encoding.rs
+// Binary/text sniffing and charset detection for read_file. Handles BOMs,
+// BOM-less UTF-16 (VAL CSV exports), and falls back to Windows-1252 (a
+// superset of Latin-1) for legacy 8-bit text that isn't valid UTF-8.
+
+use crate::commands::error::{CmdResult, CommandError};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::command;
+
+/// Bytes inspected when sniffing UTF-16 and binary content
+const SNIFF_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedText {
+    /// UTF-8 text (empty when `is_binary`)
+    pub content: String,
+    /// WHATWG encoding name, e.g. "UTF-8", "UTF-16LE", "windows-1252"
+    pub encoding: String,
+    pub had_bom: bool,
+    pub is_binary: bool,
+    /// Some bytes couldn't be decoded and were replaced with U+FFFD
+    pub lossy: bool,
+    /// Set by read_file_text; pass back to write_file for conflict detection
+    pub stamp: Option<super::atomic::FileStamp>,
+}
+
+/// BOM-less UTF-16 shows up as a NUL in every other byte for ASCII-range text
+fn sniff_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    let pairs = sample.len() / 2;
+    if pairs < 2 {
+        return None;
+    }
+    let even_zeros = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_zeros = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    if odd_zeros * 10 >= pairs * 4 && even_zeros * 20 < pairs {
+        Some(UTF_16LE)
+    } else if even_zeros * 10 >= pairs * 4 && odd_zeros * 20 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// NUL bytes, or mostly control characters, mean this isn't text
+fn looks_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    let control = sample
+        .iter()
+        .filter(|b| **b < 0x20 && !matches!(**b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b))
+        .count();
+    control * 10 > sample.len() * 3
+}
+
+fn decoded(content: std::borrow::Cow<'_, str>, encoding: &'static Encoding, had_bom: bool, lossy: bool) -> DecodedText {
+    DecodedText {
+        content: content.into_owned(),
+        encoding: encoding.name().to_string(),
+        had_bom,
+        is_binary: false,
+        lossy,
+        stamp: None,
+    }
+}
+
+/// Detect the encoding of `bytes` and transcode to UTF-8
+pub fn decode_bytes(bytes: &[u8]) -> DecodedText {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let (text, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return decoded(text, encoding, true, lossy);
+    }
+
+    let sample = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if let Some(encoding) = sniff_utf16(sample) {
+        let (text, lossy) = encoding.decode_without_bom_handling(bytes);
+        return decoded(text, encoding, false, lossy);
+    }
+    if looks_binary(sample) {
+        return DecodedText {
+            content: String::new(),
+            encoding: "binary".to_string(),
+            had_bom: false,
+            is_binary: true,
+            lossy: false,
+            stamp: None,
+        };
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => decoded(text.into(), UTF_8, false, false),
+        Err(_) => {
+            let (text, lossy) = WINDOWS_1252.decode_without_bom_handling(bytes);
+            decoded(text, WINDOWS_1252, false, lossy)
+        }
+    }
+}
+
+/// Read a file as text with encoding detection. Binary files come back with
+/// `is_binary` set and no content (use read_file_binary for those).
+#[command]
+pub async fn read_file_text(path: String) -> CmdResult<DecodedText> {
+    let bytes = fs::read(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    let mut decoded = decode_bytes(&bytes);
+    decoded.stamp = Some(super::atomic::stamp_for(std::path::Path::new(&path), &bytes));
+    if !decoded.is_binary {
+        super::recent::record_open(&path);
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_bom_utf16_latin1_and_binary() {
+        let plain = decode_bytes("naïve,1\n".as_bytes());
+        assert_eq!((plain.encoding.as_str(), plain.lossy), ("UTF-8", false));
+
+        let mut bom_le = vec![0xFF, 0xFE];
+        bom_le.extend("id,name\n".encode_utf16().flat_map(|u| u.to_le_bytes()));
+        let csv = decode_bytes(&bom_le);
+        assert_eq!((csv.encoding.as_str(), csv.had_bom, csv.content.as_str()), ("UTF-16LE", true, "id,name\n"));
+
+        let no_bom_be: Vec<u8> = "id,name\n".encode_utf16().flat_map(|u| u.to_be_bytes()).collect();
+        assert_eq!(decode_bytes(&no_bom_be).encoding, "UTF-16BE");
+
+        let latin1 = decode_bytes(b"caf\xe9");
+        assert_eq!((latin1.encoding.as_str(), latin1.content.as_str()), ("windows-1252", "café"));
+
+        let png = decode_bytes(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00");
+        assert!(png.is_binary && png.content.is_empty());
+    }
+}
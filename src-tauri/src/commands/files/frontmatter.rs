@@ -0,0 +1,149 @@
+// src-tauri/src/commands/files/frontmatter.rs
+// Read and patch YAML frontmatter in markdown files, keeping the body and key order
+
+use crate::commands::error::{CmdResult, CommandError};
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::command;
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Split a document into (frontmatter YAML, body). None when the file
+/// doesn't open with a `---` … `---` block.
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix("---")?;
+    let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Parse the frontmatter into a JSON object. Ok(None) when there is none.
+pub fn parse_frontmatter(content: &str) -> CmdResult<Option<Map<String, Value>>> {
+    let Some((block, _)) = split_frontmatter(content) else {
+        return Ok(None);
+    };
+    if block.trim().is_empty() {
+        return Ok(Some(Map::new()));
+    }
+    let value: Value = serde_yaml::from_str(block)
+        .map_err(|e| CommandError::Parse(format!("Invalid frontmatter: {}", e)))?;
+    match value {
+        Value::Object(map) => Ok(Some(map)),
+        Value::Null => Ok(Some(Map::new())),
+        _ => Err(CommandError::Parse("Frontmatter is not a key/value mapping".into())),
+    }
+}
+
+/// One scalar field as a string. Falls back to a line scan when the block
+/// isn't valid YAML (e.g. an unquoted colon in a title), so listings still
+/// show something for hand-written files.
+pub fn frontmatter_field(content: &str, key: &str) -> Option<String> {
+    let value = match parse_frontmatter(content) {
+        Ok(map) => map?.get(key).and_then(|v| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }),
+        Err(_) => {
+            let prefix = format!("{}:", key);
+            split_frontmatter(content)?
+                .0
+                .lines()
+                .find_map(|l| l.strip_prefix(prefix.as_str()))
+                .map(|v| v.trim().trim_matches('"').trim_matches('\'').to_string())
+        }
+    };
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// JSON merge patch: null removes a key, objects merge recursively, anything
+/// else replaces. Existing keys keep their position; new keys are appended.
+fn merge_patch(target: &mut Map<String, Value>, patch: &Map<String, Value>) {
+    for (key, value) in patch {
+        if value.is_null() {
+            target.shift_remove(key);
+            continue;
+        }
+        if let (Value::Object(inner), Some(Value::Object(existing))) = (value, target.get_mut(key)) {
+            merge_patch(existing, inner);
+            continue;
+        }
+        target.insert(key.clone(), value.clone());
+    }
+}
+
+/// Reassemble a document. An empty map drops the frontmatter block.
+fn render(frontmatter: &Map<String, Value>, body: &str) -> CmdResult<String> {
+    if frontmatter.is_empty() {
+        return Ok(body.to_string());
+    }
+    let yaml = serde_yaml::to_string(frontmatter)
+        .map_err(|e| CommandError::Internal(format!("Failed to write frontmatter: {}", e)))?;
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
+
+fn apply_patch(content: &str, patch: &Map<String, Value>) -> CmdResult<(String, Map<String, Value>)> {
+    let mut frontmatter = parse_frontmatter(content)?.unwrap_or_default();
+    let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+    merge_patch(&mut frontmatter, patch);
+    let updated = render(&frontmatter, body)?;
+    Ok((updated, frontmatter))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Frontmatter of a markdown file as a JSON object (empty when it has none)
+#[command]
+pub async fn get_frontmatter(path: String) -> CmdResult<Value> {
+    let content = fs::read_to_string(&path)?;
+    Ok(Value::Object(parse_frontmatter(&content)?.unwrap_or_default()))
+}
+
+/// Apply a partial update (JSON merge patch — null removes a key) to a file's
+/// frontmatter, creating the block if needed. Returns the new frontmatter.
+#[command]
+pub async fn update_frontmatter(path: String, patch: Value) -> CmdResult<Value> {
+    let Value::Object(patch) = patch else {
+        return Err(CommandError::Config("Frontmatter patch must be an object".into()));
+    };
+    let content = fs::read_to_string(&path)?;
+    let (updated, frontmatter) = apply_patch(&content, &patch)?;
+    if updated != content {
+        fs::write(&path, updated)?;
+    }
+    Ok(Value::Object(frontmatter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn patches_keep_key_order_and_body() {
+        let doc = "---\ntitle: Plan\nstatus: draft\ntags:\n  - a\n---\n# Plan\n\n---\nbody rule\n";
+        let patch = json!({ "status": null, "tags": ["b"], "owner": "ops" });
+        let (updated, fm) = apply_patch(doc, patch.as_object().unwrap()).unwrap();
+
+        let keys: Vec<&String> = fm.keys().collect();
+        assert_eq!(keys, vec!["title", "tags", "owner"]);
+        assert!(updated.starts_with("---\ntitle: Plan\ntags:\n- b\nowner: ops\n---\n"));
+        assert!(updated.ends_with("# Plan\n\n---\nbody rule\n"));
+
+        let (fresh, _) = apply_patch("no frontmatter", patch.as_object().unwrap()).unwrap();
+        assert_eq!(fresh, "---\ntags:\n- b\nowner: ops\n---\nno frontmatter");
+
+        assert_eq!(frontmatter_field("---\ntitle: Q3: review\n---\n", "title").as_deref(), Some("Q3: review"));
+    }
+}
@@ -0,0 +1,408 @@
+// src-tauri/src/commands/files/index.rs
+// Auto-generated _index.md navigation pages for knowledge folders
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::tools::docgen::markdown_table_cell;
+use notify::RecommendedWatcher;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{command, Emitter};
+
+pub const INDEX_FILE: &str = "_index.md";
+/// The generated listing lives between these markers; anything outside them
+/// (intro text, hand-written notes) is preserved on regeneration
+const START_MARKER: &str = "<!-- index:start -->";
+const END_MARKER: &str = "<!-- index:end -->";
+/// Quiet period before a burst of file events triggers regeneration
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+static INDEX_WATCHERS: std::sync::LazyLock<Mutex<HashMap<String, RecommendedWatcher>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexDoc {
+    /// File name relative to the folder
+    pub file: String,
+    pub title: String,
+    pub summary: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexSubfolder {
+    pub name: String,
+    /// Documents in the subfolder and below
+    pub documents: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexResult {
+    pub folder: String,
+    /// _index.md files created or changed
+    pub written: Vec<String>,
+    pub unchanged: usize,
+    pub documents: usize,
+    pub watching: bool,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn is_skipped(name: &str) -> bool {
+    name.starts_with('.') || name == "node_modules" || name == "__pycache__" || name == "target"
+}
+
+fn is_doc(name: &str) -> bool {
+    (name.ends_with(".md") || name.ends_with(".markdown")) && name != INDEX_FILE
+}
+
+/// Title, summary and status from frontmatter; title falls back to the first
+/// H1, then the file name
+pub fn doc_meta(file: &str, content: &str) -> IndexDoc {
+    let mut fields: HashMap<&str, String> = HashMap::new();
+    let mut body = content;
+    if let Some(rest) = content.strip_prefix("---") {
+        if let Some(end) = rest.find("\n---") {
+            for line in rest[..end].lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    let key = key.trim();
+                    if matches!(key, "title" | "summary" | "status") {
+                        let value = value.trim().trim_matches('"').trim_matches('\'');
+                        if !value.is_empty() {
+                            fields.insert(key, value.to_string());
+                        }
+                    }
+                }
+            }
+            body = &rest[end + 4..];
+        }
+    }
+    let heading = body
+        .lines()
+        .find_map(|l| l.strip_prefix("# "))
+        .map(|h| h.trim().to_string());
+    let stem = file.rsplit_once('.').map(|(s, _)| s).unwrap_or(file);
+
+    IndexDoc {
+        file: file.to_string(),
+        title: fields
+            .remove("title")
+            .or(heading)
+            .unwrap_or_else(|| stem.replace(|c: char| c == '-' || c == '_', " ")),
+        summary: fields.remove("summary"),
+        status: fields.remove("status"),
+    }
+}
+
+fn link_target(path: &str) -> String {
+    path.replace(' ', "%20")
+}
+
+/// The managed listing block (markers included)
+pub fn render_listing(subfolders: &[IndexSubfolder], docs: &[IndexDoc]) -> String {
+    let mut out = format!("{}\n", START_MARKER);
+    if !subfolders.is_empty() {
+        out.push_str("\n## Folders\n\n");
+        for sub in subfolders {
+            out.push_str(&format!(
+                "- [{}]({}/{}) — {} doc{}\n",
+                sub.name,
+                link_target(&sub.name),
+                INDEX_FILE,
+                sub.documents,
+                if sub.documents == 1 { "" } else { "s" }
+            ));
+        }
+    }
+    if !docs.is_empty() {
+        out.push_str("\n## Documents\n\n| Document | Summary | Status |\n|----------|---------|--------|\n");
+        for doc in docs {
+            out.push_str(&format!(
+                "| [{}]({}) | {} | {} |\n",
+                markdown_table_cell(&doc.title),
+                link_target(&doc.file),
+                markdown_table_cell(doc.summary.as_deref().unwrap_or("")),
+                doc.status.as_deref().map(|s| format!("`{}`", s)).unwrap_or_default()
+            ));
+        }
+    }
+    out.push('\n');
+    out.push_str(END_MARKER);
+    out
+}
+
+/// Replace the managed block in an existing _index.md, append it to a
+/// hand-written one, or build a fresh page
+pub fn merge_index(existing: Option<&str>, folder_name: &str, listing: &str) -> String {
+    match existing {
+        Some(content) => match (content.find(START_MARKER), content.find(END_MARKER)) {
+            (Some(start), Some(end)) if end > start => format!(
+                "{}{}{}",
+                &content[..start],
+                listing,
+                &content[end + END_MARKER.len()..]
+            ),
+            _ => format!("{}\n\n{}\n", content.trim_end(), listing),
+        },
+        None => format!(
+            "---\ntitle: \"{}\"\nsummary: \"Index of {}\"\nstatus: generated\n---\n\n# {}\n\n{}\n",
+            folder_name.replace('"', "\\\""),
+            folder_name.replace('"', "\\\""),
+            folder_name,
+            listing
+        ),
+    }
+}
+
+/// Write _index.md for `dir` (and subfolders when `recursive`). Returns the
+/// number of documents at or below `dir`; folders with none get no index.
+fn build(dir: &Path, recursive: bool, result: &mut IndexResult) -> CmdResult<usize> {
+    let entries = fs::read_dir(dir).map_err(|e| CommandError::Io(format!("Failed to read directory: {}", e)))?;
+    let mut docs: Vec<IndexDoc> = Vec::new();
+    let mut subfolders: Vec<IndexSubfolder> = Vec::new();
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_skipped(&name) {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            let documents = if recursive {
+                build(&path, true, result)?
+            } else {
+                count_docs(&path)
+            };
+            if documents > 0 {
+                subfolders.push(IndexSubfolder { name, documents });
+            }
+        } else if is_doc(&name) {
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            docs.push(doc_meta(&name, &content));
+        }
+    }
+
+    let total = docs.len() + subfolders.iter().map(|s| s.documents).sum::<usize>();
+    if total == 0 {
+        return Ok(0);
+    }
+    subfolders.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    docs.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+
+    let index_path = dir.join(INDEX_FILE);
+    let existing = fs::read_to_string(&index_path).ok();
+    let folder_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Index".to_string());
+    let content = merge_index(existing.as_deref(), &folder_name, &render_listing(&subfolders, &docs));
+
+    // Only touch the file when the listing changed — keeps mtimes (and watchers) quiet
+    if existing.as_deref() == Some(content.as_str()) {
+        result.unchanged += 1;
+    } else {
+        fs::write(&index_path, content).map_err(|e| CommandError::Io(format!("Failed to write index: {}", e)))?;
+        result.written.push(index_path.to_string_lossy().to_string());
+    }
+    Ok(total)
+}
+
+fn count_docs(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_skipped(&name) {
+                0
+            } else if entry.path().is_dir() {
+                count_docs(&entry.path())
+            } else {
+                usize::from(is_doc(&name))
+            }
+        })
+        .sum()
+}
+
+fn generate(root: &Path, recursive: bool) -> CmdResult<IndexResult> {
+    if !root.is_dir() {
+        return Err(CommandError::NotFound(format!("Folder not found: {}", root.display())));
+    }
+    let mut result = IndexResult {
+        folder: root.to_string_lossy().to_string(),
+        written: Vec::new(),
+        unchanged: 0,
+        documents: 0,
+        watching: false,
+    };
+    result.documents = build(root, recursive, &mut result)?;
+    Ok(result)
+}
+
+/// Regenerate the folders touched by a batch of events, plus their parents up
+/// to the root (parent listings carry subfolder document counts)
+fn regenerate_for(root: &Path, changed: &HashSet<PathBuf>) {
+    let mut dirs: HashSet<PathBuf> = HashSet::new();
+    for path in changed {
+        let mut dir = path.parent().map(Path::to_path_buf);
+        while let Some(d) = dir {
+            if !d.starts_with(root) {
+                break;
+            }
+            let at_root = d == root;
+            dirs.insert(d.clone());
+            if at_root {
+                break;
+            }
+            dir = d.parent().map(Path::to_path_buf);
+        }
+    }
+    // Deepest first so parents see fresh subfolder counts
+    let mut dirs: Vec<PathBuf> = dirs.into_iter().collect();
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.components().count()));
+    for dir in dirs {
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut result = IndexResult {
+            folder: String::new(),
+            written: Vec::new(),
+            unchanged: 0,
+            documents: 0,
+            watching: true,
+        };
+        if let Err(e) = build(&dir, false, &mut result) {
+            log::warn!("Index regeneration failed for {}: {}", dir.display(), e);
+        }
+    }
+}
+
+fn start_watch(app: tauri::AppHandle, root: PathBuf) -> CmdResult<()> {
+    use notify::{Config, RecursiveMode, Watcher};
+    use std::sync::mpsc::{channel, RecvTimeoutError};
+
+    let key = root.to_string_lossy().to_string();
+    {
+        let watchers = INDEX_WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        if watchers.contains_key(&key) {
+            return Ok(());
+        }
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(tx, Config::default())
+        .map_err(|e| CommandError::Internal(format!("Failed to create watcher: {}", e)))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| CommandError::Internal(format!("Failed to watch directory: {}", e)))?;
+    {
+        let mut watchers = INDEX_WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        watchers.insert(key, watcher);
+    }
+
+    std::thread::spawn(move || {
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+        loop {
+            match rx.recv_timeout(WATCH_DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    for path in event.paths {
+                        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        // Our own writes, and non-markdown churn, don't trigger a rebuild
+                        let hidden = path
+                            .strip_prefix(&root)
+                            .map(|rel| rel.components().any(|c| is_skipped(&c.as_os_str().to_string_lossy())))
+                            .unwrap_or(true);
+                        if hidden || name == INDEX_FILE {
+                            continue;
+                        }
+                        if is_doc(&name) || path.is_dir() || path.extension().is_none() {
+                            pending.insert(path);
+                        }
+                    }
+                }
+                Ok(Err(e)) => log::error!("Index watch error for {}: {:?}", root.display(), e),
+                Err(RecvTimeoutError::Timeout) => {
+                    if !pending.is_empty() {
+                        regenerate_for(&root, &pending);
+                        let _ = app.emit("knowledge-index:updated", root.to_string_lossy().to_string());
+                        pending.clear();
+                    }
+                }
+                // Watcher dropped by knowledge_stop_index_watch
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Build or refresh `_index.md` navigation pages for a knowledge folder.
+/// `recursive` (default true) indexes every subfolder; `watch` keeps the
+/// indexes current as markdown files are added, renamed or edited.
+#[command]
+pub async fn knowledge_generate_index(
+    app: tauri::AppHandle,
+    folder: String,
+    recursive: Option<bool>,
+    watch: Option<bool>,
+) -> CmdResult<IndexResult> {
+    let root = PathBuf::from(&folder);
+    let mut result = generate(&root, recursive.unwrap_or(true))?;
+    if watch.unwrap_or(false) {
+        start_watch(app, root)?;
+        result.watching = true;
+    }
+    Ok(result)
+}
+
+/// Stop auto-updating indexes for a folder
+#[command]
+pub async fn knowledge_stop_index_watch(folder: String) -> CmdResult<bool> {
+    let mut watchers = INDEX_WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    Ok(watchers.remove(&folder).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_and_merges_the_managed_listing() {
+        let doc = doc_meta(
+            "scope.md",
+            "---\ntitle: \"Pilot | Scope\"\nsummary: What we agreed\nstatus: draft\n---\n# Ignored\n",
+        );
+        assert_eq!(doc.title, "Pilot | Scope");
+        assert_eq!(doc.status.as_deref(), Some("draft"));
+        assert_eq!(doc_meta("kick-off_notes.md", "no frontmatter").title, "kick off notes");
+        assert_eq!(doc_meta("a.md", "intro\n# From Heading\n").title, "From Heading");
+
+        let subs = vec![IndexSubfolder { name: "Meeting Notes".to_string(), documents: 1 }];
+        let listing = render_listing(&subs, &[doc]);
+        assert!(listing.contains("- [Meeting Notes](Meeting%20Notes/_index.md) — 1 doc\n"));
+        assert!(listing.contains("| [Pilot \\| Scope](scope.md) | What we agreed | `draft` |"));
+
+        let page = merge_index(None, "Acme", &listing);
+        assert!(page.starts_with("---\ntitle: \"Acme\""));
+
+        let edited = page.replace("# Acme\n", "# Acme\n\nHand-written intro.\n");
+        let refreshed = merge_index(Some(&edited), "Acme", &render_listing(&[], &[]));
+        assert!(refreshed.contains("Hand-written intro."));
+        assert!(!refreshed.contains("Meeting Notes"));
+    }
+}
@@ -0,0 +1,217 @@
+// src-tauri/src/commands/files/integrity.rs
+// Checksums and tree manifests — hash a file, snapshot a folder as a manifest,
+// and later verify the folder (e.g. synced VAL artifacts) against it
+
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileHash {
+    pub path: String,
+    pub algorithm: HashAlgorithm,
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeManifest {
+    pub algorithm: HashAlgorithm,
+    pub generated_at: String,
+    /// Keyed by path relative to the root, with forward slashes
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeVerification {
+    pub root: String,
+    pub ok: bool,
+    pub matched: usize,
+    /// Content differs from the manifest
+    pub modified: Vec<String>,
+    /// In the manifest but not on disk
+    pub missing: Vec<String>,
+    /// On disk but not in the manifest
+    pub added: Vec<String>,
+}
+
+// ============================================================================
+// Hashing
+// ============================================================================
+
+/// Stream a file through the hasher (lowercase hex digest)
+pub fn hash_path(path: &Path, algorithm: HashAlgorithm) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    match algorithm {
+        HashAlgorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+    }
+}
+
+/// Hash every file under `root` (respects .gitignore, skips hidden files).
+/// `skip` leaves out the manifest itself when it's stored inside the root.
+fn build_manifest(root: &Path, algorithm: HashAlgorithm, skip: Option<&Path>) -> CmdResult<TreeManifest> {
+    let mut files = BTreeMap::new();
+    for entry in WalkBuilder::new(root).hidden(true).git_ignore(true).build().flatten() {
+        let path = entry.path();
+        if !path.is_file() || skip.map(|s| s == path).unwrap_or(false) {
+            continue;
+        }
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let hash = hash_path(path, algorithm)
+            .map_err(|e| CommandError::Io(format!("Failed to hash {}: {}", rel, e)))?;
+        files.insert(rel, ManifestEntry { hash, size });
+    }
+    Ok(TreeManifest {
+        algorithm,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        files,
+    })
+}
+
+/// Compare a fresh snapshot against the expected manifest
+fn compare_manifests(root: &str, expected: &TreeManifest, actual: &TreeManifest) -> TreeVerification {
+    let mut matched = 0;
+    let mut modified = Vec::new();
+    let mut missing = Vec::new();
+    for (path, entry) in &expected.files {
+        match actual.files.get(path) {
+            Some(found) if found == entry => matched += 1,
+            Some(_) => modified.push(path.clone()),
+            None => missing.push(path.clone()),
+        }
+    }
+    let added: Vec<String> = actual
+        .files
+        .keys()
+        .filter(|p| !expected.files.contains_key(*p))
+        .cloned()
+        .collect();
+    TreeVerification {
+        root: root.to_string(),
+        ok: modified.is_empty() && missing.is_empty() && added.is_empty(),
+        matched,
+        modified,
+        missing,
+        added,
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Checksum one file (sha256 by default)
+#[command]
+pub async fn hash_file(path: String, algorithm: Option<HashAlgorithm>) -> CmdResult<FileHash> {
+    let algorithm = algorithm.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let p = Path::new(&path);
+        let size = fs::metadata(p).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?.len();
+        let hash = hash_path(p, algorithm).map_err(|e| CommandError::Io(format!("Failed to hash file: {}", e)))?;
+        Ok(FileHash { path, algorithm, hash, size })
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Hash task failed: {}", e)))?
+}
+
+/// Snapshot every file under `root`. With `output` the manifest is also
+/// written there as JSON, ready for verify_tree.
+#[command]
+pub async fn generate_manifest(root: String, algorithm: Option<HashAlgorithm>, output: Option<String>) -> CmdResult<TreeManifest> {
+    let algorithm = algorithm.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = output.map(PathBuf::from);
+        let manifest = build_manifest(Path::new(&root), algorithm, output.as_deref())?;
+        if let Some(output) = output {
+            let json = serde_json::to_string_pretty(&manifest)?;
+            fs::write(&output, json).map_err(|e| CommandError::Io(format!("Failed to write manifest: {}", e)))?;
+        }
+        Ok(manifest)
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Manifest task failed: {}", e)))?
+}
+
+/// Check `root` against a manifest file from generate_manifest, reporting
+/// modified, missing and unexpected files
+#[command]
+pub async fn verify_tree(root: String, manifest: String) -> CmdResult<TreeVerification> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let manifest_path = PathBuf::from(&manifest);
+        let content = fs::read_to_string(&manifest_path)
+            .map_err(|e| CommandError::Io(format!("Failed to read manifest: {}", e)))?;
+        let expected: TreeManifest = serde_json::from_str(&content)
+            .map_err(|e| CommandError::Parse(format!("Invalid manifest: {}", e)))?;
+        let actual = build_manifest(Path::new(&root), expected.algorithm, Some(&manifest_path))?;
+        Ok(compare_manifests(&root, &expected, &actual))
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Verify task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_tree_against_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("tables")).unwrap();
+        fs::write(root.join("tables/a.json"), "{}").unwrap();
+        fs::write(root.join("b.json"), "[]").unwrap();
+
+        let sha = hash_path(&root.join("b.json"), HashAlgorithm::Sha256).unwrap();
+        assert_eq!(sha, "4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945");
+
+        let expected = build_manifest(&root, HashAlgorithm::Blake3, None).unwrap();
+        assert_eq!(expected.files.keys().collect::<Vec<_>>(), vec!["b.json", "tables/a.json"]);
+
+        fs::write(root.join("tables/a.json"), "{\"x\":1}").unwrap();
+        fs::remove_file(root.join("b.json")).unwrap();
+        fs::write(root.join("c.json"), "").unwrap();
+        let actual = build_manifest(&root, HashAlgorithm::Blake3, None).unwrap();
+        let result = compare_manifests("root", &expected, &actual);
+        assert!(!result.ok);
+        assert_eq!(result.modified, vec!["tables/a.json"]);
+        assert_eq!(result.missing, vec!["b.json"]);
+        assert_eq!(result.added, vec!["c.json"]);
+    }
+}
@@ -0,0 +1,134 @@
+// src-tauri/src/commands/files/line_index.rs
+// Line counts and checkpoint offsets for large files, so `read_file_lines`
+// can seek near a deep start line instead of rescanning from the top
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::models::FileLineCount;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::command;
+
+/// A byte offset is recorded at the start of every Nth line
+const CHECKPOINT_LINES: usize = 10_000;
+/// Indexes kept in memory (one per recently paged file)
+const MAX_INDEXES: usize = 16;
+
+static INDEXES: std::sync::LazyLock<Mutex<HashMap<String, LineIndex>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone)]
+struct LineIndex {
+    size: u64,
+    modified: Option<SystemTime>,
+    total_lines: usize,
+    /// checkpoints[i] = byte offset where line i * CHECKPOINT_LINES starts
+    checkpoints: Vec<u64>,
+}
+
+fn file_stamp(path: &str) -> CmdResult<(u64, Option<SystemTime>)> {
+    let meta = fs::metadata(path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    Ok((meta.len(), meta.modified().ok()))
+}
+
+/// Count lines the way read_file_lines pages them: a final line without a
+/// trailing newline still counts, an empty file has none
+fn build_index<R: Read>(mut reader: R) -> std::io::Result<(usize, Vec<u64>)> {
+    let mut buf = vec![0u8; 256 * 1024];
+    let mut checkpoints = vec![0u64];
+    let mut newlines = 0usize;
+    let mut offset = 0u64;
+    let mut last_byte = None;
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for (i, b) in buf[..n].iter().enumerate() {
+            if *b == b'\n' {
+                newlines += 1;
+                if newlines % CHECKPOINT_LINES == 0 {
+                    checkpoints.push(offset + i as u64 + 1);
+                }
+            }
+        }
+        last_byte = Some(buf[n - 1]);
+        offset += n as u64;
+    }
+    let total_lines = match last_byte {
+        None => 0,
+        Some(b'\n') => newlines,
+        Some(_) => newlines + 1,
+    };
+    // A checkpoint at EOF would point past the last line
+    if checkpoints.len() > 1 && checkpoints.last() == Some(&offset) {
+        checkpoints.pop();
+    }
+    Ok((total_lines, checkpoints))
+}
+
+fn cached(path: &str, stamp: (u64, Option<SystemTime>)) -> Option<LineIndex> {
+    let indexes = INDEXES.lock().ok()?;
+    indexes
+        .get(path)
+        .filter(|i| (i.size, i.modified) == stamp)
+        .cloned()
+}
+
+fn index_file(path: &str) -> CmdResult<LineIndex> {
+    let stamp = file_stamp(path)?;
+    if let Some(index) = cached(path, stamp) {
+        return Ok(index);
+    }
+    let file = fs::File::open(path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    let (total_lines, checkpoints) = build_index(file)?;
+    let index = LineIndex { size: stamp.0, modified: stamp.1, total_lines, checkpoints };
+
+    let mut indexes = INDEXES.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    if indexes.len() >= MAX_INDEXES && !indexes.contains_key(path) {
+        // Evicting any entry is fine — it's only a cache
+        if let Some(key) = indexes.keys().next().cloned() {
+            indexes.remove(&key);
+        }
+    }
+    indexes.insert(path.to_string(), index.clone());
+    Ok(index)
+}
+
+/// Nearest checkpoint at or before `start_line`, as (line, byte offset).
+/// Only uses an index that's already built and still matches the file.
+pub(super) fn seek_hint(path: &str, start_line: usize) -> Option<(usize, u64)> {
+    let index = cached(path, file_stamp(path).ok()?)?;
+    let slot = (start_line / CHECKPOINT_LINES).min(index.checkpoints.len().saturating_sub(1));
+    let offset = *index.checkpoints.get(slot)?;
+    Some((slot * CHECKPOINT_LINES, offset))
+}
+
+/// Total lines in a file. Builds (and caches) a checkpoint index that later
+/// read_file_lines calls use to jump straight to deep pages.
+#[command]
+pub async fn files_get_line_count(path: String) -> CmdResult<FileLineCount> {
+    let index = tauri::async_runtime::spawn_blocking(move || index_file(&path))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Line count task failed: {}", e)))??;
+    Ok(FileLineCount { total_lines: index.total_lines, total_size: index.size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_lines_and_records_checkpoints() {
+        assert_eq!(build_index(&b""[..]).unwrap(), (0, vec![0]));
+        assert_eq!(build_index(&b"a\nb"[..]).unwrap().0, 2);
+        assert_eq!(build_index(&b"a\nb\n"[..]).unwrap().0, 2);
+
+        let content = "x\n".repeat(CHECKPOINT_LINES * 2 + 5);
+        let (total, checkpoints) = build_index(content.as_bytes()).unwrap();
+        assert_eq!(total, CHECKPOINT_LINES * 2 + 5);
+        assert_eq!(checkpoints, vec![0, (CHECKPOINT_LINES * 2) as u64, (CHECKPOINT_LINES * 4) as u64]);
+    }
+}
@@ -0,0 +1,365 @@
+// src-tauri/src/commands/files/lint.rs
+// Markdown lint + frontmatter schema validation for knowledge docs
+
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+/// Inline markdown links and images: captures the link target
+pub(super) static LINK_RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap()
+});
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Which checks to run. Every field is optional on the wire so the frontend
+/// can send a partial ruleset and inherit the defaults for the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownRuleset {
+    /// Frontmatter keys that must be present and non-empty
+    pub required_frontmatter: Vec<String>,
+    /// Flag relative links whose target doesn't exist on disk
+    pub check_links: bool,
+    /// Flag skipped heading levels and multiple H1s
+    pub check_headings: bool,
+    /// Flag lines ending in spaces or tabs
+    pub check_trailing_whitespace: bool,
+}
+
+impl Default for MarkdownRuleset {
+    fn default() -> Self {
+        Self {
+            required_frontmatter: ["title", "summary", "tags", "status"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            check_links: true,
+            check_headings: true,
+            check_trailing_whitespace: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintViolation {
+    pub path: String,
+    pub line: Option<usize>,
+    pub rule: String,     // "frontmatter" | "broken-link" | "heading" | "trailing-whitespace"
+    pub severity: String, // "error" | "warning"
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkdownLintReport {
+    pub files_checked: usize,
+    pub files_with_errors: usize,
+    pub violations: Vec<LintViolation>,
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// Split a document into (frontmatter block, body, body start line).
+/// Returns None for the block when the file has no `---` frontmatter.
+pub(super) fn split_frontmatter(content: &str) -> (Option<&str>, &str, usize) {
+    if !content.starts_with("---") {
+        return (None, content, 1);
+    }
+    let rest = &content[3..];
+    match rest.find("\n---") {
+        Some(end_idx) => {
+            let block = &rest[..end_idx];
+            let after = &rest[end_idx + 4..];
+            let body = after.strip_prefix('\n').unwrap_or(after);
+            // Opening delimiter + block lines + closing delimiter
+            let body_start = block.lines().count() + 2;
+            (Some(block), body, body_start)
+        }
+        None => (None, content, 1),
+    }
+}
+
+/// Top-level frontmatter keys with whether each has a value.
+/// A key with no inline value counts as filled if followed by list items.
+fn frontmatter_keys(block: &str) -> Vec<(String, bool)> {
+    let lines: Vec<&str> = block.lines().collect();
+    let mut keys = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with(' ') || line.starts_with('\t') || line.starts_with('-') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        let filled = if value.is_empty() {
+            lines
+                .get(i + 1)
+                .map(|next| next.trim_start().starts_with('-'))
+                .unwrap_or(false)
+        } else {
+            value != "[]"
+        };
+        keys.push((key.trim().to_string(), filled));
+    }
+    keys
+}
+
+fn check_frontmatter(block: Option<&str>, ruleset: &MarkdownRuleset, path: &str, out: &mut Vec<LintViolation>) {
+    if ruleset.required_frontmatter.is_empty() {
+        return;
+    }
+    let Some(block) = block else {
+        out.push(LintViolation {
+            path: path.to_string(),
+            line: Some(1),
+            rule: "frontmatter".to_string(),
+            severity: "error".to_string(),
+            message: "Missing frontmatter block".to_string(),
+        });
+        return;
+    };
+
+    let keys = frontmatter_keys(block);
+    for required in &ruleset.required_frontmatter {
+        match keys.iter().find(|(k, _)| k == required) {
+            None => out.push(LintViolation {
+                path: path.to_string(),
+                line: Some(1),
+                rule: "frontmatter".to_string(),
+                severity: "error".to_string(),
+                message: format!("Missing required frontmatter key '{}'", required),
+            }),
+            Some((_, false)) => out.push(LintViolation {
+                path: path.to_string(),
+                line: Some(1),
+                rule: "frontmatter".to_string(),
+                severity: "error".to_string(),
+                message: format!("Frontmatter key '{}' is empty", required),
+            }),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Walk body lines outside fenced code blocks, yielding (1-based line number, line)
+pub(super) fn prose_lines(body: &str, body_start: usize) -> Vec<(usize, &str)> {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for (i, line) in body.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            lines.push((body_start + i, line));
+        }
+    }
+    lines
+}
+
+fn check_links(body: &str, body_start: usize, file_path: &Path, out: &mut Vec<LintViolation>) {
+    let base = file_path.parent().unwrap_or(Path::new("."));
+    let path = file_path.to_string_lossy().to_string();
+
+    for (line_no, line) in prose_lines(body, body_start) {
+        for cap in LINK_RE.captures_iter(line) {
+            let target = &cap[1];
+            // Only relative file links are checked — skip URLs, anchors, absolute paths
+            if target.starts_with('#')
+                || target.starts_with('/')
+                || target.contains("://")
+                || target.starts_with("mailto:")
+                || target.starts_with("tel:")
+            {
+                continue;
+            }
+            let without_anchor = target.split(['#', '?']).next().unwrap_or(target);
+            if without_anchor.is_empty() {
+                continue;
+            }
+            let decoded = urlencoding::decode(without_anchor)
+                .map(|s| s.into_owned())
+                .unwrap_or_else(|_| without_anchor.to_string());
+            if !base.join(&decoded).exists() {
+                out.push(LintViolation {
+                    path: path.clone(),
+                    line: Some(line_no),
+                    rule: "broken-link".to_string(),
+                    severity: "error".to_string(),
+                    message: format!("Broken relative link: {}", target),
+                });
+            }
+        }
+    }
+}
+
+fn check_headings(body: &str, body_start: usize, path: &str, out: &mut Vec<LintViolation>) {
+    let mut prev_level: Option<usize> = None;
+    let mut h1_count = 0;
+
+    for (line_no, line) in prose_lines(body, body_start) {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if level == 0 || level > 6 || !line[level..].starts_with(' ') {
+            continue;
+        }
+        if level == 1 {
+            h1_count += 1;
+            if h1_count == 2 {
+                out.push(LintViolation {
+                    path: path.to_string(),
+                    line: Some(line_no),
+                    rule: "heading".to_string(),
+                    severity: "warning".to_string(),
+                    message: "Multiple H1 headings".to_string(),
+                });
+            }
+        }
+        if let Some(prev) = prev_level {
+            if level > prev + 1 {
+                out.push(LintViolation {
+                    path: path.to_string(),
+                    line: Some(line_no),
+                    rule: "heading".to_string(),
+                    severity: "warning".to_string(),
+                    message: format!("Heading level skips from H{} to H{}", prev, level),
+                });
+            }
+        }
+        prev_level = Some(level);
+    }
+}
+
+fn check_trailing_whitespace(content: &str, path: &str, out: &mut Vec<LintViolation>) {
+    for (i, line) in content.lines().enumerate() {
+        if line.ends_with(' ') || line.ends_with('\t') {
+            out.push(LintViolation {
+                path: path.to_string(),
+                line: Some(i + 1),
+                rule: "trailing-whitespace".to_string(),
+                severity: "warning".to_string(),
+                message: "Trailing whitespace".to_string(),
+            });
+        }
+    }
+}
+
+/// Lint a single markdown document's content. `file_path` is used to resolve relative links.
+pub fn lint_markdown(content: &str, file_path: &Path, ruleset: &MarkdownRuleset) -> Vec<LintViolation> {
+    let path = file_path.to_string_lossy().to_string();
+    let mut violations = Vec::new();
+    let (block, body, body_start) = split_frontmatter(content);
+
+    check_frontmatter(block, ruleset, &path, &mut violations);
+    if ruleset.check_links {
+        check_links(body, body_start, file_path, &mut violations);
+    }
+    if ruleset.check_headings {
+        check_headings(body, body_start, &path, &mut violations);
+    }
+    if ruleset.check_trailing_whitespace {
+        check_trailing_whitespace(content, &path, &mut violations);
+    }
+    violations
+}
+
+fn is_markdown(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("md") | Some("markdown")
+    )
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Validate markdown docs under a folder (recursive, respects .gitignore) or a single file
+#[command]
+pub async fn files_validate_markdown(
+    path: String,
+    ruleset: Option<MarkdownRuleset>,
+) -> CmdResult<MarkdownLintReport> {
+    let ruleset = ruleset.unwrap_or_default();
+    let root = Path::new(&path);
+    if !root.exists() {
+        return Err(CommandError::NotFound(format!("Path not found: {}", path)));
+    }
+
+    let targets: Vec<std::path::PathBuf> = if root.is_file() {
+        vec![root.to_path_buf()]
+    } else {
+        WalkBuilder::new(root)
+            .hidden(true)
+            .git_ignore(true)
+            .build()
+            .flatten()
+            .map(|e| e.into_path())
+            .filter(|p| p.is_file() && is_markdown(p))
+            .collect()
+    };
+
+    let mut violations = Vec::new();
+    let mut files_with_errors = 0;
+    for target in &targets {
+        let content = match fs::read_to_string(target) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let file_violations = lint_markdown(&content, target, &ruleset);
+        if file_violations.iter().any(|v| v.severity == "error") {
+            files_with_errors += 1;
+        }
+        violations.extend(file_violations);
+    }
+
+    Ok(MarkdownLintReport {
+        files_checked: targets.len(),
+        files_with_errors,
+        violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_for(v: &[LintViolation], rule: &str) -> usize {
+        v.iter().filter(|x| x.rule == rule).count()
+    }
+
+    #[test]
+    fn reports_missing_and_empty_frontmatter_keys() {
+        let doc = "---\ntitle: Hello\nsummary:\ntags:\n  - a\n---\n# Hello\n";
+        let v = lint_markdown(doc, Path::new("/tmp/doc.md"), &MarkdownRuleset::default());
+        let messages: Vec<&str> = v.iter().filter(|x| x.rule == "frontmatter").map(|x| x.message.as_str()).collect();
+        assert_eq!(messages, vec!["Frontmatter key 'summary' is empty", "Missing required frontmatter key 'status'"]);
+    }
+
+    #[test]
+    fn flags_heading_skips_and_ignores_code_fences() {
+        let doc = "# A\n### C\n```\n# not a heading\n```\n# B\n";
+        let ruleset = MarkdownRuleset { required_frontmatter: vec![], ..Default::default() };
+        let v = lint_markdown(doc, Path::new("/tmp/doc.md"), &ruleset);
+        assert_eq!(rules_for(&v, "heading"), 2);
+        assert_eq!(v[0].line, Some(2));
+    }
+
+    #[test]
+    fn flags_only_relative_broken_links() {
+        let doc = "[ok](https://example.com) [anchor](#x) [bad](./missing-file.md) line  \n";
+        let ruleset = MarkdownRuleset { required_frontmatter: vec![], ..Default::default() };
+        let v = lint_markdown(doc, Path::new("/nonexistent/doc.md"), &ruleset);
+        assert_eq!(rules_for(&v, "broken-link"), 1);
+        assert_eq!(rules_for(&v, "trailing-whitespace"), 1);
+    }
+}
@@ -0,0 +1,874 @@
+// src-tauri/src/commands/files/mod.rs
+// File system operations for the Library module
+
+pub mod atomic;
+pub mod automations;
+pub mod backlinks;
+pub mod bulk_rename;
+pub mod compare;
+pub mod dir_size;
+pub mod duplicates;
+pub mod encoding;
+pub mod frontmatter;
+pub mod index;
+pub mod integrity;
+pub mod line_index;
+pub mod lint;
+pub mod permissions;
+pub mod recent;
+pub mod related;
+pub mod symlinks;
+pub mod transclusion;
+pub mod transfer;
+pub mod trash_ledger;
+pub mod versions;
+pub mod watch;
+
+pub use atomic::*;
+pub use automations::*;
+pub use backlinks::*;
+pub use bulk_rename::*;
+pub use compare::*;
+pub use dir_size::*;
+pub use duplicates::*;
+pub use encoding::*;
+pub use frontmatter::*;
+pub use index::*;
+pub use integrity::*;
+pub use line_index::*;
+pub use lint::*;
+pub use permissions::*;
+pub use recent::*;
+pub use related::*;
+pub use transclusion::*;
+pub use transfer::*;
+pub use versions::*;
+pub use watch::*;
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::models::{DirectoryPage, FileChunk, FileEntry, FileInfo, FileLines, TreeNode};
+use crate::AppState;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{command, State};
+
+/// Read a file as UTF-8 text. Latin-1 / UTF-16 files are transcoded (see
+/// read_file_text for the detected encoding); binary files are rejected.
+#[command]
+pub async fn read_file(path: String) -> CmdResult<String> {
+    let bytes = fs::read(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    let decoded = encoding::decode_bytes(&bytes);
+    if decoded.is_binary {
+        return Err(CommandError::Validation {
+            message: format!("{} appears to be a binary file", path),
+            fields: vec!["path".to_string()],
+        });
+    }
+    recent::record_open(&path);
+    Ok(decoded.content)
+}
+
+/// Largest byte range `read_file_range` returns in one call
+const MAX_RANGE_BYTES: u64 = 8 * 1024 * 1024;
+/// Most lines `read_file_lines` returns in one call
+const MAX_PAGE_LINES: usize = 10_000;
+/// Longer lines are cut so one minified blob can't blow up a page
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Length of the longest prefix of `bytes` that doesn't end mid-character
+fn utf8_boundary(bytes: &[u8]) -> usize {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => bytes.len(),
+        // error_len() == None means the input ended inside a character
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => bytes.len(),
+    }
+}
+
+/// Read up to `len` bytes starting at `offset`. Invalid UTF-8 is replaced;
+/// continue from `offset + bytes_read`.
+#[command]
+pub async fn read_file_range(path: String, offset: u64, len: u64) -> CmdResult<FileChunk> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    let total_size = file.metadata()?.len();
+    let len = len.min(MAX_RANGE_BYTES);
+    file.seek(SeekFrom::Start(offset.min(total_size)))?;
+
+    let mut buf = Vec::with_capacity(len.min(total_size.saturating_sub(offset)) as usize);
+    file.take(len).read_to_end(&mut buf)?;
+    let end_of_file = offset + buf.len() as u64 >= total_size;
+    // Hold back a trailing partial character unless there's nothing after it
+    let keep = if end_of_file { buf.len() } else { utf8_boundary(&buf) };
+    buf.truncate(keep);
+
+    Ok(FileChunk {
+        content: String::from_utf8_lossy(&buf).into_owned(),
+        offset,
+        bytes_read: keep as u64,
+        total_size,
+        eof: offset + keep as u64 >= total_size,
+    })
+}
+
+/// Read one line (without its terminator), keeping at most `max` bytes of it.
+/// Returns None at end of file.
+fn read_line_capped<R: std::io::BufRead>(reader: &mut R, max: usize) -> std::io::Result<Option<(Vec<u8>, bool)>> {
+    let mut line = Vec::new();
+    let mut truncated = false;
+    let mut saw_any = false;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        saw_any = true;
+        let (chunk, found_newline) = match buf.iter().position(|b| *b == b'\n') {
+            Some(i) => (&buf[..i], Some(i)),
+            None => (buf, None),
+        };
+        let room = max.saturating_sub(line.len());
+        if chunk.len() > room {
+            truncated = true;
+        }
+        line.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        let consumed = found_newline.map(|i| i + 1).unwrap_or(chunk.len());
+        reader.consume(consumed);
+        if found_newline.is_some() {
+            break;
+        }
+    }
+    if !saw_any {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(Some((line, truncated)))
+}
+
+/// Read `count` lines starting at the zero-based `start_line`. Deep pages are
+/// fast once files_get_line_count has indexed the file.
+#[command]
+pub async fn read_file_lines(path: String, start_line: usize, count: usize) -> CmdResult<FileLines> {
+    use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+    let mut file = fs::File::open(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    let total_size = file.metadata()?.len();
+    let (skipped, offset) = line_index::seek_hint(&path, start_line).unwrap_or((0, 0));
+    file.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::with_capacity(256 * 1024, file);
+
+    // Skip ahead without keeping any of the skipped content
+    for _ in skipped..start_line {
+        if read_line_capped(&mut reader, 0)?.is_none() {
+            return Ok(FileLines { lines: Vec::new(), start_line, truncated_lines: Vec::new(), total_size, eof: true });
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut truncated_lines = Vec::new();
+    let mut eof = false;
+    while lines.len() < count.min(MAX_PAGE_LINES) {
+        match read_line_capped(&mut reader, MAX_LINE_BYTES)? {
+            Some((bytes, truncated)) => {
+                if truncated {
+                    truncated_lines.push(start_line + lines.len());
+                }
+                lines.push(String::from_utf8_lossy(&bytes[..utf8_boundary(&bytes)]).into_owned());
+            }
+            None => {
+                eof = true;
+                break;
+            }
+        }
+    }
+    if !eof {
+        eof = reader.fill_buf()?.is_empty();
+    }
+
+    Ok(FileLines { lines, start_line, truncated_lines, total_size, eof })
+}
+
+/// Write a text file atomically (temp file + rename). Pass the stamp the
+/// content was read with (`expected_hash` and/or `expected_mtime`, from
+/// read_file_text or get_file_stamp) to get a Conflict error instead of
+/// overwriting changes made elsewhere. Returns the new stamp for the next save.
+#[command]
+pub async fn write_file(
+    path: String,
+    content: String,
+    expected_mtime: Option<i64>,
+    expected_hash: Option<String>,
+) -> CmdResult<FileStamp> {
+    let p = Path::new(&path);
+    // Ensure parent directory exists
+    if let Some(parent) = p.parent() {
+        fs::create_dir_all(parent).map_err(|e| CommandError::Io(format!("Failed to create directory: {}", e)))?;
+    }
+    atomic::check_expected(p, expected_mtime, expected_hash.as_deref())?;
+    versions::snapshot_before_write(&path, content.as_bytes());
+    atomic::atomic_write(p, content.as_bytes()).map_err(|e| CommandError::Io(format!("Failed to write file: {}", e)))?;
+    recent::record_modified(&path);
+    Ok(atomic::stamp_for(p, content.as_bytes()))
+}
+
+#[command]
+pub async fn write_file_base64(path: String, data: String) -> CmdResult<()> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(&data)
+        .map_err(|e| CommandError::Config(format!("Invalid base64: {}", e)))?;
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent).map_err(|e| CommandError::Io(format!("Failed to create directory: {}", e)))?;
+    }
+    versions::snapshot_before_write(&path, &bytes);
+    atomic::atomic_write(Path::new(&path), &bytes).map_err(|e| CommandError::Io(format!("Failed to write file: {}", e)))?;
+    recent::record_modified(&path);
+    Ok(())
+}
+
+/// Delete a file or directory. Moves it to the OS trash unless `permanent`.
+#[command]
+pub async fn delete_file(path: String, permanent: Option<bool>) -> CmdResult<()> {
+    let p = Path::new(&path);
+    if !permanent.unwrap_or(false) {
+        return trash_ledger::move_to_trash(p);
+    }
+    if p.is_dir() {
+        fs::remove_dir_all(&path).map_err(|e| CommandError::Io(format!("Failed to delete directory: {}", e)))
+    } else {
+        fs::remove_file(&path).map_err(|e| CommandError::Io(format!("Failed to delete file: {}", e)))
+    }
+}
+
+/// Put a trashed file or directory back at its original path
+#[command]
+pub async fn restore_from_trash(path: String) -> CmdResult<String> {
+    let target = Path::new(&path);
+    if target.exists() {
+        return Err(CommandError::Config(format!("Something already exists at {}", path)));
+    }
+    trash_ledger::restore_trashed(target)?;
+    Ok(path)
+}
+
+/// Visible entries of one directory (hidden files and build folders skipped), unsorted
+fn read_dir_entries(path: &str) -> CmdResult<Vec<FileEntry>> {
+    let entries = fs::read_dir(path).map_err(|e| CommandError::Io(format!("Failed to read directory: {}", e)))?;
+
+    let mut files: Vec<FileEntry> = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip hidden files and common ignore patterns
+        if name.starts_with('.') || name == "node_modules" || name == "__pycache__" || name == "target" {
+            continue;
+        }
+
+        // Symlinks report their target's type and size (the link's own when broken)
+        let path = entry.path();
+        let (metadata, is_symlink) = match symlinks::resolved_metadata(&path) {
+            Ok((m, is_symlink)) => (Some(m), is_symlink),
+            Err(_) => (None, false),
+        };
+        files.push(FileEntry {
+            name,
+            path: path.to_string_lossy().to_string(),
+            is_directory: metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false),
+            size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+            modified: metadata
+                .and_then(|m| m.modified().ok())
+                .map(|t| {
+                    chrono::DateTime::<chrono::Utc>::from(t)
+                        .format("%Y-%m-%dT%H:%M:%SZ")
+                        .to_string()
+                }),
+            is_symlink,
+            target: if is_symlink { symlinks::symlink_target(&path) } else { None },
+            title: None,
+            summary: None,
+        });
+    }
+    Ok(files)
+}
+
+/// Sort by name | size | modified, keeping directories first unless
+/// `dirs_first` is false. Ties fall back to name so pages are stable.
+fn sort_entries(files: &mut [FileEntry], sort_by: &str, desc: bool, dirs_first: bool) {
+    use std::cmp::Ordering;
+    files.sort_by(|a, b| {
+        if dirs_first && a.is_directory != b.is_directory {
+            return if a.is_directory { Ordering::Less } else { Ordering::Greater };
+        }
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ord = match sort_by {
+            "size" => a.size.cmp(&b.size).then_with(by_name),
+            // ISO timestamps compare correctly as strings; unknown sorts first
+            "modified" => a.modified.cmp(&b.modified).then_with(by_name),
+            _ => by_name(),
+        };
+        if desc {
+            ord.reverse()
+        } else {
+            ord
+        }
+    });
+}
+
+#[command]
+pub async fn list_directory(path: String) -> CmdResult<Vec<FileEntry>> {
+    let mut files = read_dir_entries(&path)?;
+    // Sort: directories first, then alphabetically
+    sort_entries(&mut files, "name", false, true);
+    Ok(files)
+}
+
+/// Largest page `list_directory_page` returns
+const MAX_PAGE_ENTRIES: usize = 1000;
+
+/// One sorted page of a directory listing, with the total count so the UI
+/// can virtualize folders holding tens of thousands of files.
+/// `sort_by` is name (default) | size | modified.
+#[command]
+pub async fn list_directory_page(
+    path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort_by: Option<String>,
+    sort_desc: Option<bool>,
+    dirs_first: Option<bool>,
+) -> CmdResult<DirectoryPage> {
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(200).clamp(1, MAX_PAGE_ENTRIES);
+    let sort_by = sort_by.unwrap_or_else(|| "name".to_string());
+    if !matches!(sort_by.as_str(), "name" | "size" | "modified") {
+        return Err(CommandError::Config(format!("Invalid sort_by '{}' (name | size | modified)", sort_by)));
+    }
+    let desc = sort_desc.unwrap_or(false);
+    let dirs_first = dirs_first.unwrap_or(true);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = read_dir_entries(&path)?;
+        sort_entries(&mut files, &sort_by, desc, dirs_first);
+        let total = files.len();
+        let entries: Vec<FileEntry> = files.into_iter().skip(offset).take(limit).collect();
+        Ok(DirectoryPage {
+            has_more: offset + entries.len() < total,
+            entries,
+            offset,
+            total,
+        })
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Directory listing task failed: {}", e)))?
+}
+
+/// Always skipped, even without a .gitignore
+const TREE_SKIPPED: [&str; 3] = ["node_modules", "__pycache__", "target"];
+
+fn sort_tree_children(children: &mut [TreeNode]) {
+    // Directories first, then alphabetically
+    children.sort_by(|a, b| match (a.is_directory, b.is_directory) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+}
+
+/// Options shared by every level of `build_file_tree`
+struct TreeOptions<'a> {
+    respect_gitignore: bool,
+    globs: &'a [String],
+    follow_symlinks: bool,
+}
+
+/// Walk `root` with the `ignore` crate (hidden files, .gitignore and `ignore`
+/// globs filtered out) and assemble the nested tree. Directories at
+/// `max_depth` get `children: None` so the UI knows to lazy-load them.
+fn build_file_tree(root: &Path, max_depth: usize, opts: &TreeOptions) -> CmdResult<TreeNode> {
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| CommandError::NotFound("Failed to build file tree".to_string()))?;
+    let target = symlinks::symlink_target(root);
+    if !root.is_dir() {
+        return Ok(TreeNode {
+            name,
+            path: root.to_string_lossy().to_string(),
+            is_directory: false,
+            children: None,
+            is_symlink: target.is_some(),
+            target,
+        });
+    }
+
+    let ancestors: Vec<PathBuf> = fs::canonicalize(root).into_iter().collect();
+    let children = tree_children(root, max_depth, opts, &ancestors)?;
+    Ok(TreeNode {
+        name,
+        path: root.to_string_lossy().to_string(),
+        is_directory: true,
+        children,
+        is_symlink: target.is_some(),
+        target,
+    })
+}
+
+/// Children of `root` down to `max_depth`. The walker itself never follows
+/// links; with `follow_symlinks`, each linked folder is walked separately with
+/// `ancestors` (canonical folders above it) so a link back up the tree stops
+/// there instead of recursing.
+fn tree_children(root: &Path, max_depth: usize, opts: &TreeOptions, ancestors: &[PathBuf]) -> CmdResult<Option<Vec<TreeNode>>> {
+    if max_depth == 0 {
+        return Ok(None);
+    }
+
+    let mut overrides = ignore::overrides::OverrideBuilder::new(root);
+    for glob in opts.globs {
+        overrides
+            .add(&format!("!{}", glob))
+            .map_err(|e| CommandError::Config(format!("Invalid ignore glob '{}': {}", glob, e)))?;
+    }
+    let overrides = overrides
+        .build()
+        .map_err(|e| CommandError::Config(format!("Invalid ignore globs: {}", e)))?;
+
+    let walker = ignore::WalkBuilder::new(root)
+        .max_depth(Some(max_depth))
+        .hidden(true)
+        .follow_links(false)
+        .git_ignore(opts.respect_gitignore)
+        .git_exclude(opts.respect_gitignore)
+        .git_global(false)
+        .ignore(opts.respect_gitignore)
+        .parents(opts.respect_gitignore)
+        .require_git(false)
+        .overrides(overrides)
+        .filter_entry(|e| !e.file_name().to_str().map(|n| TREE_SKIPPED.contains(&n)).unwrap_or(false))
+        .build();
+
+    // Deepest entries first, so every directory's children are complete
+    // before the directory itself is attached to its parent
+    let mut entries: Vec<(usize, PathBuf, bool)> = walker
+        .flatten()
+        .filter(|e| e.depth() > 0)
+        .map(|e| (e.depth(), e.path().to_path_buf(), e.path_is_symlink()))
+        .collect();
+    entries.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut children_of: HashMap<PathBuf, Vec<TreeNode>> = HashMap::new();
+    for (depth, path, is_symlink) in entries {
+        // Symlinks take their target's type; a broken link is a plain leaf
+        let is_directory = path.is_dir();
+        let children = if !is_directory || depth >= max_depth {
+            None
+        } else if is_symlink {
+            let followable = if opts.follow_symlinks { symlinks::followable_dir(&path, ancestors) } else { None };
+            match followable {
+                Some(target) => {
+                    let mut chain = ancestors.to_vec();
+                    chain.push(target);
+                    tree_children(&path, max_depth - depth, opts, &chain)?
+                }
+                None => None,
+            }
+        } else {
+            let mut children = children_of.remove(&path).unwrap_or_default();
+            sort_tree_children(&mut children);
+            Some(children)
+        };
+        let Some(parent) = path.parent().map(Path::to_path_buf) else {
+            continue;
+        };
+        children_of.entry(parent).or_default().push(TreeNode {
+            name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            path: path.to_string_lossy().to_string(),
+            is_directory,
+            children,
+            is_symlink,
+            target: if is_symlink { symlinks::symlink_target(&path) } else { None },
+        });
+    }
+
+    let mut children = children_of.remove(root).unwrap_or_default();
+    sort_tree_children(&mut children);
+    Ok(Some(children))
+}
+
+/// Nested tree under `path` (default: the knowledge root), `max_depth` levels
+/// deep (default 3). Hidden files, node_modules and friends are always
+/// skipped; `.gitignore` rules apply unless `respect_gitignore` is false, and
+/// `ignore` adds extra globs (e.g. "dist/", "*.log"). Symlinked folders are
+/// expanded unless `follow_symlinks` is false; links looping back into their
+/// own ancestry are listed but not expanded.
+#[command]
+pub async fn get_file_tree(
+    state: State<'_, AppState>,
+    path: Option<String>,
+    max_depth: Option<usize>,
+    respect_gitignore: Option<bool>,
+    ignore: Option<Vec<String>>,
+    follow_symlinks: Option<bool>,
+) -> CmdResult<TreeNode> {
+    let root_path = PathBuf::from(path.unwrap_or_else(|| state.knowledge_path.clone()));
+    let depth = max_depth.unwrap_or(3);
+    let ignore = ignore.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let opts = TreeOptions {
+            respect_gitignore: respect_gitignore.unwrap_or(true),
+            globs: &ignore,
+            follow_symlinks: follow_symlinks.unwrap_or(true),
+        };
+        build_file_tree(&root_path, depth, &opts)
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("File tree task failed: {}", e)))?
+}
+
+#[command]
+pub async fn create_directory(path: String) -> CmdResult<()> {
+    fs::create_dir_all(&path).map_err(|e| CommandError::Io(format!("Failed to create directory: {}", e)))
+}
+
+#[command]
+pub async fn rename_path(old_path: String, new_path: String) -> CmdResult<()> {
+    fs::rename(&old_path, &new_path).map_err(|e| CommandError::Io(format!("Failed to rename: {}", e)))
+}
+
+#[command]
+pub async fn get_file_info(path: String) -> CmdResult<FileInfo> {
+    let p = Path::new(&path);
+    let (metadata, is_symlink) =
+        symlinks::resolved_metadata(p).map_err(|e| CommandError::Io(format!("Failed to get file info: {}", e)))?;
+    // resolved_metadata falls back to the link itself when the target is gone
+    let is_broken_link = is_symlink && metadata.file_type().is_symlink();
+
+    Ok(FileInfo {
+        name: p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        path: path.clone(),
+        is_directory: metadata.is_dir(),
+        size: metadata.len(),
+        created: metadata.created().ok().map(|t| {
+            chrono::DateTime::<chrono::Utc>::from(t)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        }),
+        modified: metadata.modified().ok().map(|t| {
+            chrono::DateTime::<chrono::Utc>::from(t)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        }),
+        extension: p.extension().map(|e| e.to_string_lossy().to_string()),
+        is_symlink,
+        target: symlinks::symlink_target(p),
+        is_broken_link,
+    })
+}
+
+/// Open a file or folder in Finder (macOS)
+#[command]
+pub async fn open_in_finder(path: String) -> CmdResult<()> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R") // Reveal in Finder
+            .arg(&path)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open in Finder: {}", e)))?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg("/select,")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open in Explorer: {}", e)))?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // Try xdg-open on the parent directory
+        let parent = Path::new(&path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        std::process::Command::new("xdg-open")
+            .arg(&parent)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open file manager: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Read a file as base64 encoded string (for binary files like images, PDFs)
+#[command]
+pub async fn read_file_binary(path: String) -> CmdResult<String> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let bytes = fs::read(&path).map_err(|e| CommandError::Io(format!("Failed to read file: {}", e)))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+/// Concurrent frontmatter reads in `get_folder_files`
+const FRONTMATTER_CONCURRENCY: usize = 16;
+/// Cached files before the frontmatter cache is dropped and rebuilt
+const MAX_FRONTMATTER_CACHE: usize = 20_000;
+
+/// (modified, size) a cached frontmatter entry was read at
+type FrontmatterKey = (Option<String>, u64);
+
+/// Title/summary per markdown file, reused while modified time and size match
+static FRONTMATTER_CACHE: std::sync::LazyLock<std::sync::Mutex<HashMap<PathBuf, (FrontmatterKey, Option<String>, Option<String>)>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+fn is_markdown(name: &str) -> bool {
+    name.ends_with(".md") || name.ends_with(".markdown")
+}
+
+/// `extract_frontmatter` through the cache; `force_refresh` always re-reads
+fn cached_frontmatter(path: &Path, key: FrontmatterKey, force_refresh: bool) -> (Option<String>, Option<String>) {
+    if !force_refresh {
+        if let Ok(cache) = FRONTMATTER_CACHE.lock() {
+            if let Some((cached_key, title, summary)) = cache.get(path) {
+                if *cached_key == key {
+                    return (title.clone(), summary.clone());
+                }
+            }
+        }
+    }
+    let (title, summary) = extract_frontmatter(path);
+    if let Ok(mut cache) = FRONTMATTER_CACHE.lock() {
+        if cache.len() >= MAX_FRONTMATTER_CACHE {
+            cache.clear();
+        }
+        cache.insert(path.to_path_buf(), (key, title.clone(), summary.clone()));
+    }
+    (title, summary)
+}
+
+/// Get files in a folder, sorted by modified time (most recent first)
+/// For markdown files, extracts title and summary from frontmatter
+/// Always searches recursively to find nested files (e.g., sessions/2026-01-01/notes.md)
+/// Frontmatter is only read for the returned page, in parallel, and cached by
+/// modified time; `force_refresh` bypasses the cache.
+#[command]
+pub async fn get_folder_files(path: String, limit: Option<u32>, force_refresh: Option<bool>) -> CmdResult<Vec<FileEntry>> {
+    use futures::StreamExt;
+
+    let limit = limit.unwrap_or(20) as usize;
+    let force_refresh = force_refresh.unwrap_or(false);
+
+    // Always search recursively - depth 4 to handle nested structures like sessions/_archive/date/notes.md
+    let mut files = tauri::async_runtime::spawn_blocking(move || collect_files_recursive(&path, 4))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Folder listing task failed: {}", e)))??;
+
+    // Sort by modified time (most recent first)
+    files.sort_by(|a, b| {
+        match (&b.modified, &a.modified) {
+            (Some(b_mod), Some(a_mod)) => b_mod.cmp(a_mod),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    });
+
+    // Limit results
+    files.truncate(limit);
+
+    // Extract title and summary from markdown frontmatter
+    let frontmatter: Vec<(Option<String>, Option<String>)> = futures::stream::iter(files.iter().map(|f| {
+        let path = PathBuf::from(&f.path);
+        let key = (f.modified.clone(), f.size);
+        let markdown = is_markdown(&f.name);
+        async move {
+            if !markdown {
+                return (None, None);
+            }
+            tauri::async_runtime::spawn_blocking(move || cached_frontmatter(&path, key, force_refresh))
+                .await
+                .unwrap_or((None, None))
+        }
+    }))
+    .buffered(FRONTMATTER_CONCURRENCY)
+    .collect()
+    .await;
+    for (file, (title, summary)) in files.iter_mut().zip(frontmatter) {
+        file.title = title;
+        file.summary = summary;
+    }
+
+    Ok(files)
+}
+
+/// Collect files from a single directory (non-recursive)
+#[allow(dead_code)]
+fn collect_files_in_dir(path: &str) -> CmdResult<Vec<FileEntry>> {
+    let entries = fs::read_dir(path).map_err(|e| CommandError::Io(format!("Failed to read directory: {}", e)))?;
+    let mut files: Vec<FileEntry> = Vec::new();
+
+    for entry in entries.flatten() {
+        let (metadata, is_symlink) = match symlinks::resolved_metadata(&entry.path()) {
+            Ok((m, is_symlink)) => (Some(m), is_symlink),
+            Err(_) => (None, false),
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip hidden files and directories
+        if name.starts_with('.') || name == "node_modules" || name == "__pycache__" || name == "target" {
+            continue;
+        }
+
+        // Skip directories - we only want files
+        let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+        if is_dir {
+            continue;
+        }
+
+        let file_path = entry.path();
+        let modified = metadata
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .map(|t| {
+                chrono::DateTime::<chrono::Utc>::from(t)
+                    .format("%Y-%m-%dT%H:%M:%SZ")
+                    .to_string()
+            });
+
+        // Extract title and summary from markdown frontmatter
+        let (title, summary) = if is_markdown(&name) {
+            extract_frontmatter(&file_path)
+        } else {
+            (None, None)
+        };
+
+        files.push(FileEntry {
+            name,
+            path: file_path.to_string_lossy().to_string(),
+            is_directory: false,
+            size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+            modified,
+            is_symlink,
+            target: if is_symlink { symlinks::symlink_target(&file_path) } else { None },
+            title,
+            summary,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Collect files recursively from subdirectories
+fn collect_files_recursive(path: &str, max_depth: usize) -> CmdResult<Vec<FileEntry>> {
+    let mut files: Vec<FileEntry> = Vec::new();
+    collect_files_recursive_impl(Path::new(path), 0, max_depth, &mut files);
+    Ok(files)
+}
+
+fn collect_files_recursive_impl(path: &Path, current_depth: usize, max_depth: usize, files: &mut Vec<FileEntry>) {
+    if current_depth > max_depth {
+        return;
+    }
+
+    let entries = match fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let (metadata, is_symlink) = match symlinks::resolved_metadata(&entry.path()) {
+            Ok((m, is_symlink)) => (Some(m), is_symlink),
+            Err(_) => (None, false),
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip hidden files and directories
+        if name.starts_with('.') || name == "node_modules" || name == "__pycache__" || name == "target" {
+            continue;
+        }
+
+        let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+        let file_path = entry.path();
+
+        if is_dir {
+            // Linked folders that point back up the tree would only repeat it
+            if is_symlink && symlinks::followable_dir(&file_path, &[]).is_none() {
+                continue;
+            }
+            // Recurse into subdirectories
+            collect_files_recursive_impl(&file_path, current_depth + 1, max_depth, files);
+        } else {
+            let modified = metadata
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .map(|t| {
+                    chrono::DateTime::<chrono::Utc>::from(t)
+                        .format("%Y-%m-%dT%H:%M:%SZ")
+                        .to_string()
+                });
+
+            // Frontmatter is filled in by get_folder_files once the list is trimmed
+            files.push(FileEntry {
+                name,
+                path: file_path.to_string_lossy().to_string(),
+                is_directory: false,
+                size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+                modified,
+                is_symlink,
+                target: if is_symlink { symlinks::symlink_target(&file_path) } else { None },
+                title: None,
+                summary: None,
+            });
+        }
+    }
+}
+
+/// Extract title and summary from markdown frontmatter
+fn extract_frontmatter(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(content) = fs::read_to_string(path) else {
+        return (None, None);
+    };
+    (
+        frontmatter::frontmatter_field(&content, "title"),
+        frontmatter::frontmatter_field(&content, "summary"),
+    )
+}
+
+/// Open a file with its default application
+#[command]
+pub async fn open_with_default_app(path: String) -> CmdResult<()> {
+    recent::record_open(&path);
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open file: {}", e)))?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", &path])
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open file: {}", e)))?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open file: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+// src-tauri/src/commands/files/permissions.rs
+// File permissions: read and change the mode (chmod-style, octal or symbolic)
+// so scripts exported from the terminal can be made executable from the app.
+// Windows has no mode bits; only the readonly attribute is settable there.
+
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+/// Extensions Windows runs directly (it has no executable bit)
+#[cfg(not(unix))]
+const WINDOWS_EXECUTABLE: [&str; 5] = ["exe", "bat", "cmd", "com", "ps1"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePermissions {
+    pub path: String,
+    /// Permission bits, e.g. 493 (0o755); None on Windows
+    pub mode: Option<u32>,
+    /// Octal form of `mode`, e.g. "755"
+    pub octal: Option<String>,
+    /// ls-style form of `mode`, e.g. "rwxr-xr-x"
+    pub symbolic: Option<String>,
+    pub readonly: bool,
+    /// Owner can execute (Unix) or the extension is runnable (Windows)
+    pub is_executable: bool,
+}
+
+fn symbolic(mode: u32) -> String {
+    let flags = ['r', 'w', 'x'];
+    (0..9)
+        .map(|i| if mode & (0o400 >> i) != 0 { flags[i % 3] } else { '-' })
+        .collect()
+}
+
+/// Apply a chmod-style spec to `current`: octal ("755", "0644") or comma-separated
+/// symbolic clauses ("+x", "u+x,go-w", "a=r"). Special bits are kept unless
+/// an octal mode sets them.
+pub fn apply_mode(current: u32, spec: &str) -> Result<u32, String> {
+    let spec = spec.trim();
+    let invalid = || format!("Invalid mode '{}' (use octal like 755 or symbolic like u+x,go-w)", spec);
+    if spec.is_empty() {
+        return Err(invalid());
+    }
+    if spec.chars().all(|c| c.is_ascii_digit()) {
+        let mode = u32::from_str_radix(spec, 8).map_err(|_| invalid())?;
+        return if mode <= 0o7777 { Ok(mode) } else { Err(invalid()) };
+    }
+
+    let mut mode = current;
+    for clause in spec.split(',') {
+        let op_at = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+        let (who, rest) = clause.split_at(op_at);
+        let (op, perms) = rest.split_at(1);
+
+        let mut who_mask = 0;
+        for c in who.chars() {
+            who_mask |= match c {
+                'u' => 0o700,
+                'g' => 0o070,
+                'o' => 0o007,
+                'a' => 0o777,
+                _ => return Err(invalid()),
+            };
+        }
+        if who_mask == 0 {
+            who_mask = 0o777;
+        }
+
+        let mut bits = 0;
+        for c in perms.chars() {
+            bits |= match c {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                _ => return Err(invalid()),
+            };
+        }
+        let bits = bits & who_mask;
+        match op {
+            "+" => mode |= bits,
+            "-" => mode &= !bits,
+            _ => mode = (mode & !who_mask) | bits,
+        }
+    }
+    Ok(mode)
+}
+
+fn read_permissions(path: &Path) -> CmdResult<FilePermissions> {
+    let meta = fs::metadata(path).map_err(|e| CommandError::Io(format!("Failed to read permissions: {}", e)))?;
+    let perms = meta.permissions();
+
+    #[cfg(unix)]
+    let (mode, is_executable) = {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = perms.mode() & 0o7777;
+        (Some(mode), mode & 0o100 != 0)
+    };
+    #[cfg(not(unix))]
+    let (mode, is_executable) = {
+        let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+        (None::<u32>, WINDOWS_EXECUTABLE.contains(&ext.as_str()))
+    };
+
+    Ok(FilePermissions {
+        path: path.to_string_lossy().to_string(),
+        mode,
+        octal: mode.map(|m| format!("{:o}", m)),
+        symbolic: mode.map(|m| symbolic(m & 0o777)),
+        readonly: perms.readonly(),
+        is_executable,
+    })
+}
+
+/// Current permissions of a file or directory
+#[command]
+pub async fn get_permissions(path: String) -> CmdResult<FilePermissions> {
+    read_permissions(Path::new(&path))
+}
+
+/// Change permissions. `mode` is chmod-style (Unix only; e.g. "755" or "+x");
+/// `readonly` toggles write access on any platform (all write bits on Unix).
+#[command]
+pub async fn set_permissions(path: String, mode: Option<String>, readonly: Option<bool>) -> CmdResult<FilePermissions> {
+    if mode.is_none() && readonly.is_none() {
+        return Err(CommandError::Validation {
+            message: "Nothing to change: pass a mode or readonly".to_string(),
+            fields: vec!["mode".to_string(), "readonly".to_string()],
+        });
+    }
+    let target = Path::new(&path);
+    let meta = fs::metadata(target).map_err(|e| CommandError::Io(format!("Failed to read permissions: {}", e)))?;
+    let mut perms = meta.permissions();
+
+    if let Some(spec) = mode.as_deref() {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let updated = apply_mode(perms.mode() & 0o7777, spec).map_err(|message| CommandError::Validation {
+                message,
+                fields: vec!["mode".to_string()],
+            })?;
+            perms.set_mode(updated);
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = spec;
+            return Err(CommandError::Validation {
+                message: "Mode bits aren't supported on this platform; use readonly".to_string(),
+                fields: vec!["mode".to_string()],
+            });
+        }
+    }
+
+    if let Some(readonly) = readonly {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // Clearing readonly restores the owner's write bit only, not world-writable
+            let mode = perms.mode();
+            perms.set_mode(if readonly { mode & !0o222 } else { mode | 0o200 });
+        }
+        #[cfg(not(unix))]
+        perms.set_readonly(readonly);
+    }
+
+    fs::set_permissions(target, perms).map_err(|e| CommandError::Io(format!("Failed to set permissions: {}", e)))?;
+    read_permissions(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_modes() {
+        assert_eq!(apply_mode(0o644, "755"), Ok(0o755));
+        assert_eq!(apply_mode(0o644, "0600"), Ok(0o600));
+        assert_eq!(apply_mode(0o644, "+x"), Ok(0o755));
+        assert_eq!(apply_mode(0o644, "u+x"), Ok(0o744));
+        assert_eq!(apply_mode(0o775, "go-w,o-x"), Ok(0o754));
+        assert_eq!(apply_mode(0o4755, "a=r"), Ok(0o4444));
+        assert!(apply_mode(0o644, "u+z").is_err());
+        assert!(apply_mode(0o644, "99").is_err());
+        assert!(apply_mode(0o644, "").is_err());
+        assert_eq!(symbolic(0o754), "rwxr-xr--");
+    }
+}
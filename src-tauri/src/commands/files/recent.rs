@@ -0,0 +1,206 @@
+// src-tauri/src/commands/files/recent.rs
+// Recently opened / modified files. read_file and open_with_default_app
+// record opens, write_file records saves; the list lives in
+// ~/.tv-client/recent_files.json so every window (and the next launch)
+// sees the same history.
+
+use crate::commands::error::CmdResult;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Entries kept on disk; the oldest touched fall off first
+const MAX_ENTRIES: usize = 200;
+const DEFAULT_LIMIT: usize = 20;
+/// Re-opening the same file within this window updates memory only, so
+/// editors polling read_file don't rewrite the store on every call
+const PERSIST_THROTTLE_SECS: i64 = 30;
+
+static RECENT: std::sync::LazyLock<Mutex<Option<Vec<RecentFile>>>> = std::sync::LazyLock::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: String,
+    pub name: String,
+    pub opened_at: Option<String>,
+    pub modified_at: Option<String>,
+    pub open_count: u32,
+}
+
+impl RecentFile {
+    fn last_touched(&self) -> &str {
+        self.opened_at.as_deref().max(self.modified_at.as_deref()).unwrap_or("")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Touch {
+    Opened,
+    Modified,
+}
+
+fn store_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("recent_files.json")
+}
+
+fn load() -> Vec<RecentFile> {
+    fs::read_to_string(store_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &[RecentFile]) -> CmdResult<()> {
+    let path = store_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(entries)?)?;
+    Ok(())
+}
+
+/// App-internal files (.tv-client, .git, dotfiles) aren't user documents
+fn is_tracked(path: &Path) -> bool {
+    !path.components().any(|c| match c {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    })
+}
+
+/// Move `path` to the front with the new timestamp. Returns false when the
+/// change is too small to be worth persisting (a repeat open inside the throttle).
+fn upsert(entries: &mut Vec<RecentFile>, path: &str, touch: Touch, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let stamp = now.to_rfc3339();
+    let index = entries.iter().position(|e| e.path == path);
+    let mut entry = match index {
+        Some(i) => entries.remove(i),
+        None => RecentFile {
+            path: path.to_string(),
+            name: Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string()),
+            opened_at: None,
+            modified_at: None,
+            open_count: 0,
+        },
+    };
+
+    let persist = match touch {
+        Touch::Opened => {
+            let recent = entry
+                .opened_at
+                .as_deref()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| (now - t.with_timezone(&chrono::Utc)).num_seconds() < PERSIST_THROTTLE_SECS)
+                .unwrap_or(false);
+            entry.opened_at = Some(stamp);
+            entry.open_count += 1;
+            !recent
+        }
+        Touch::Modified => {
+            entry.modified_at = Some(stamp);
+            true
+        }
+    };
+    entries.insert(0, entry);
+    entries.truncate(MAX_ENTRIES);
+    persist || index.is_none()
+}
+
+fn record(path: &str, touch: Touch) {
+    if !is_tracked(Path::new(path)) {
+        return;
+    }
+    let Ok(mut cache) = RECENT.lock() else {
+        return;
+    };
+    let entries = cache.get_or_insert_with(load);
+    if upsert(entries, path, touch, chrono::Utc::now()) {
+        if let Err(e) = save(entries) {
+            eprintln!("[files:recent] Failed to save recent files: {}", e);
+        }
+    }
+}
+
+/// Record that the user opened `path` (read_file, open_with_default_app)
+pub fn record_open(path: &str) {
+    record(path, Touch::Opened);
+}
+
+/// Record that `path` was saved from the app (write_file)
+pub fn record_modified(path: &str) {
+    record(path, Touch::Modified);
+}
+
+/// Most recent first by the requested timestamp ("opened", "modified", or
+/// whichever is later when omitted)
+fn select(entries: &[RecentFile], kind: Option<&str>, limit: usize) -> Vec<RecentFile> {
+    let key = |e: &RecentFile| -> String {
+        match kind {
+            Some("opened") => e.opened_at.clone().unwrap_or_default(),
+            Some("modified") => e.modified_at.clone().unwrap_or_default(),
+            _ => e.last_touched().to_string(),
+        }
+    };
+    let mut selected: Vec<RecentFile> = entries.iter().filter(|e| !key(e).is_empty()).cloned().collect();
+    selected.sort_by_key(|e| std::cmp::Reverse(key(e)));
+    selected.truncate(limit);
+    selected
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Recently opened / modified files that still exist, newest first.
+/// `kind` is "opened" or "modified"; omit it for either.
+#[command]
+pub fn list_recent_files(limit: Option<usize>, kind: Option<String>) -> CmdResult<Vec<RecentFile>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_ENTRIES);
+    let entries = match RECENT.lock() {
+        Ok(mut cache) => cache.get_or_insert_with(load).clone(),
+        Err(_) => load(),
+    };
+    let existing: Vec<RecentFile> = entries.into_iter().filter(|e| Path::new(&e.path).exists()).collect();
+    Ok(select(&existing, kind.as_deref(), limit))
+}
+
+#[command]
+pub fn clear_recent_files() -> CmdResult<()> {
+    if let Ok(mut cache) = RECENT.lock() {
+        *cache = Some(Vec::new());
+    }
+    save(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn orders_by_latest_touch_and_throttles_repeat_opens() {
+        let t = |secs: i64| chrono::Utc.timestamp_opt(1_790_000_000 + secs, 0).unwrap();
+        let mut entries = Vec::new();
+
+        assert!(upsert(&mut entries, "/k/a.md", Touch::Opened, t(0)));
+        assert!(upsert(&mut entries, "/k/b.md", Touch::Modified, t(10)));
+        // Re-open of a.md 5s later: moves to the front but isn't persisted
+        assert!(!upsert(&mut entries, "/k/a.md", Touch::Opened, t(5)));
+        assert_eq!(entries[0].open_count, 2);
+
+        let all: Vec<String> = select(&entries, None, 10).into_iter().map(|e| e.name).collect();
+        assert_eq!(all, vec!["b.md", "a.md"]);
+        let opened: Vec<String> = select(&entries, Some("opened"), 10).into_iter().map(|e| e.name).collect();
+        assert_eq!(opened, vec!["a.md"]);
+
+        assert!(!is_tracked(Path::new("/home/me/.tv-client/settings.json")));
+        assert!(is_tracked(Path::new("/home/me/knowledge/table overview.md")));
+    }
+}
@@ -0,0 +1,167 @@
+// src-tauri/src/commands/files/related.rs
+// Related documents — ranks knowledge docs by cosine similarity using the
+// semantic search index (search::semantic), where a document's vector is the
+// mean of its chunk vectors. Pairs above NEAR_DUPLICATE are flagged so copies
+// of the same SOP can be merged.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::search::semantic::{document_vectors, DocumentVector};
+use crate::commands::settings;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Similarity above which two documents are treated as the same content
+pub const NEAR_DUPLICATE: f32 = 0.95;
+const DEFAULT_TOP_K: usize = 5;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedDocument {
+    pub path: String,
+    pub title: String,
+    pub score: f32,
+    pub near_duplicate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearDuplicatePair {
+    pub path_a: String,
+    pub title_a: String,
+    pub path_b: String,
+    pub title_b: String,
+    pub score: f32,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+pub(crate) fn knowledge_root(root: Option<String>) -> CmdResult<PathBuf> {
+    root.or_else(|| {
+        settings::load_settings()
+            .ok()
+            .and_then(|s| s.keys.get(settings::KEY_KNOWLEDGE_PATH).cloned())
+    })
+    .filter(|p| !p.is_empty())
+    .map(PathBuf::from)
+    .ok_or_else(|| CommandError::Config("Knowledge path not configured".into()))
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut na, mut nb) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    if na == 0.0 || nb == 0.0 {
+        0.0
+    } else {
+        dot / (na.sqrt() * nb.sqrt())
+    }
+}
+
+fn rank_related(target: &str, docs: &[DocumentVector], top_k: usize) -> CmdResult<Vec<RelatedDocument>> {
+    let doc = docs
+        .iter()
+        .find(|d| d.path == target)
+        .ok_or_else(|| CommandError::NotFound(format!("Not an indexed knowledge document: {}", target)))?;
+    let mut related: Vec<RelatedDocument> = docs
+        .iter()
+        .filter(|d| d.path != target)
+        .map(|d| {
+            let score = cosine(&doc.vector, &d.vector);
+            RelatedDocument { path: d.path.clone(), title: d.title.clone(), score, near_duplicate: score >= NEAR_DUPLICATE }
+        })
+        .collect();
+    related.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    related.truncate(top_k);
+    Ok(related)
+}
+
+/// `docs` must be sorted by path (document_vectors returns them that way)
+fn near_duplicate_pairs(docs: &[DocumentVector], threshold: f32) -> Vec<NearDuplicatePair> {
+    let mut pairs = Vec::new();
+    for (i, a) in docs.iter().enumerate() {
+        for b in &docs[i + 1..] {
+            let score = cosine(&a.vector, &b.vector);
+            if score >= threshold {
+                pairs.push(NearDuplicatePair {
+                    path_a: a.path.clone(),
+                    title_a: a.title.clone(),
+                    path_b: b.path.clone(),
+                    title_b: b.title.clone(),
+                    score,
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    pairs
+}
+
+pub(super) fn relative_to(root: &Path, path: &str) -> String {
+    Path::new(path)
+        .strip_prefix(root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| path.replace('\\', "/"))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Documents most similar to `path` (absolute, or relative to the knowledge
+/// root), best first. Scores ≥ 0.95 are flagged as near duplicates. Refreshes
+/// the root's semantic index first.
+#[command]
+pub async fn knowledge_find_related(path: String, top_k: Option<usize>, root: Option<String>) -> CmdResult<Vec<RelatedDocument>> {
+    let root = knowledge_root(root)?;
+    let target = std::fs::canonicalize(root.join(&path))
+        .map_err(|e| CommandError::NotFound(format!("{}: {}", path, e)))?;
+    let docs = document_vectors(&root).await?;
+    rank_related(&target.to_string_lossy(), &docs, top_k.unwrap_or(DEFAULT_TOP_K))
+}
+
+/// Every pair of documents at or above `threshold` similarity (default 0.95)
+#[command]
+pub async fn knowledge_find_near_duplicates(root: Option<String>, threshold: Option<f32>) -> CmdResult<Vec<NearDuplicatePair>> {
+    let root = knowledge_root(root)?;
+    let docs = document_vectors(&root).await?;
+    Ok(near_duplicate_pairs(&docs, threshold.unwrap_or(NEAR_DUPLICATE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_by_cosine_and_flags_near_duplicates() {
+        let doc = |path: &str, title: &str, v: &[f32]| DocumentVector { path: path.into(), title: title.into(), vector: v.to_vec() };
+        let docs = vec![
+            doc("/kb/misc/lunch.md", "Lunch", &[0.0, 0.0, 1.0]),
+            doc("/kb/sop/billing.md", "Billing", &[0.5, 0.8, 0.0]),
+            doc("/kb/sop/onboarding-v2.md", "Onboarding v2", &[0.99, 0.05, 0.0]),
+            doc("/kb/sop/onboarding.md", "Onboarding", &[1.0, 0.0, 0.0]),
+        ];
+
+        let related = rank_related("/kb/sop/onboarding.md", &docs, 2).unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].path, "/kb/sop/onboarding-v2.md");
+        assert!(related[0].near_duplicate);
+        assert_eq!(related[1].path, "/kb/sop/billing.md");
+        assert!(!related[1].near_duplicate);
+        assert!(rank_related("/kb/missing.md", &docs, 2).is_err());
+
+        let pairs = near_duplicate_pairs(&docs, NEAR_DUPLICATE);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].path_a, "/kb/sop/onboarding-v2.md");
+    }
+}
@@ -0,0 +1,120 @@
+// src-tauri/src/commands/files/symlinks.rs
+// Symlink detection for listings and the file tree. The knowledge folder links
+// to shared folders, some of which link back into it — following those blindly
+// recurses until the depth limit (or forever, for callers without one).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Target of `path` if it is a symlink, as written in the link
+pub(crate) fn symlink_target(path: &Path) -> Option<String> {
+    let meta = fs::symlink_metadata(path).ok()?;
+    if !meta.file_type().is_symlink() {
+        return None;
+    }
+    fs::read_link(path).ok().map(|t| t.to_string_lossy().to_string())
+}
+
+/// Metadata of what `path` points to, falling back to the link itself when
+/// the target is missing. The flag is true for symlinks.
+pub(crate) fn resolved_metadata(path: &Path) -> std::io::Result<(fs::Metadata, bool)> {
+    let link_meta = fs::symlink_metadata(path)?;
+    if !link_meta.file_type().is_symlink() {
+        return Ok((link_meta, false));
+    }
+    Ok((fs::metadata(path).unwrap_or(link_meta), true))
+}
+
+/// Canonical target of a symlinked directory, or None if following it would
+/// loop: it resolves to one of `ancestors` (canonical dirs already being
+/// walked) or to a folder containing the link itself.
+pub(crate) fn followable_dir(link: &Path, ancestors: &[PathBuf]) -> Option<PathBuf> {
+    let target = fs::canonicalize(link).ok()?;
+    if !target.is_dir() {
+        return None;
+    }
+    let parent = link.parent().and_then(|p| fs::canonicalize(p).ok())?;
+    if parent.starts_with(&target) || ancestors.iter().any(|a| a.starts_with(&target)) {
+        return None;
+    }
+    Some(target)
+}
+
+/// Create a link at `to` pointing where the link at `from` does, target
+/// written as-is (relative links stay relative)
+pub(crate) fn copy_symlink(from: &Path, to: &Path) -> std::io::Result<()> {
+    let target = fs::read_link(from)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, to)
+    }
+    #[cfg(windows)]
+    {
+        // Windows needs to know the kind; a dangling link becomes a file link
+        if fs::metadata(from).is_ok_and(|m| m.is_dir()) {
+            std::os::windows::fs::symlink_dir(&target, to)
+        } else {
+            std::os::windows::fs::symlink_file(&target, to)
+        }
+    }
+}
+
+/// True if `path` (under `root`) is reached through a symlinked folder. The
+/// path itself being a link doesn't count; that's an ordinary entry.
+pub(crate) fn passes_through_link(root: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(root) else {
+        return false;
+    };
+    let mut current = root.to_path_buf();
+    let mut parts = rel.components().peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            break;
+        }
+        current.push(part);
+        if fs::symlink_metadata(&current).is_ok_and(|m| m.file_type().is_symlink()) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn detects_links_and_loops() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("kb/notes")).unwrap();
+        fs::create_dir_all(root.join("shared/docs")).unwrap();
+        fs::write(root.join("shared/docs/a.md"), "hello").unwrap();
+        symlink(root.join("shared"), root.join("kb/shared")).unwrap();
+        symlink("..", root.join("kb/notes/up")).unwrap();
+        symlink(root.join("missing"), root.join("kb/broken")).unwrap();
+
+        let kb = fs::canonicalize(root.join("kb")).unwrap();
+        assert_eq!(symlink_target(&root.join("kb/notes/up")).as_deref(), Some(".."));
+        assert_eq!(symlink_target(&root.join("kb/notes")), None);
+
+        let (meta, is_link) = resolved_metadata(&root.join("kb/shared/docs/a.md")).unwrap();
+        assert_eq!((meta.len(), is_link), (5, false));
+        let (meta, is_link) = resolved_metadata(&root.join("kb/broken")).unwrap();
+        assert!(is_link && meta.file_type().is_symlink());
+
+        assert!(followable_dir(&root.join("kb/shared"), &[kb.clone()]).is_some());
+        // notes/up -> kb, which contains the link
+        assert!(followable_dir(&root.join("kb/notes/up"), &[kb]).is_none());
+        // shared/back -> kb while walking kb through kb/shared
+        symlink(root.join("kb"), root.join("shared/back")).unwrap();
+        let chain = vec![fs::canonicalize(root.join("kb")).unwrap(), fs::canonicalize(root.join("shared")).unwrap()];
+        assert!(followable_dir(&root.join("kb/shared/back"), &chain).is_none());
+        assert!(followable_dir(&root.join("kb/broken"), &chain).is_none());
+
+        assert!(passes_through_link(&root.join("kb"), &root.join("kb/shared/docs/a.md")));
+        assert!(!passes_through_link(&root.join("kb"), &root.join("kb/shared")));
+        assert!(!passes_through_link(&root.join("kb"), &root.join("kb/notes/new.md")));
+    }
+}
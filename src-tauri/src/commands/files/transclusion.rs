@@ -0,0 +1,330 @@
+// src-tauri/src/commands/files/transclusion.rs
+// Transclusion for knowledge docs: `![[other-doc]]` and `![[other-doc#Section]]`
+// are replaced by the embedded doc (or one heading's section of it), so
+// composite documents like proposals can be assembled from standard sections.
+// Embeds resolve like wiki-links and are expanded recursively, with depth,
+// count and cycle limits; anything that can't be embedded is left as a
+// visible note and reported as a warning.
+
+use super::backlinks::{indexed_docs, Resolver};
+use super::lint::split_frontmatter;
+use super::related::{knowledge_root, relative_to};
+use crate::commands::error::{CmdResult, CommandError};
+use pulldown_cmark::{html, Options, Parser};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use tauri::command;
+
+/// ![[target]], ![[target#heading]], ![[target#heading|alias]]
+static EMBED_RE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"!\[\[([^\]|#]+)(?:#([^\]|]*))?(?:\|[^\]]*)?\]\]").unwrap());
+
+/// Embeds nested deeper than this are left unexpanded
+const MAX_DEPTH: usize = 5;
+
+/// Total embeds expanded per render, so a doc embedding the same large
+/// sections many times over can't blow up
+const MAX_EMBEDS: usize = 200;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransclusionWarning {
+    /// Doc containing the embed, relative to the knowledge root
+    pub source: String,
+    pub line: usize,
+    /// The embed as written, e.g. `![[pricing#Standard terms]]`
+    pub embed: String,
+    /// not_found | section_not_found | cycle | depth_limit | embed_limit
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedDocument {
+    pub path: String,
+    /// Body with every embed expanded (frontmatter dropped)
+    pub markdown: String,
+    pub html: String,
+    /// Docs pulled in, relative to the knowledge root, in first-use order
+    pub embedded: Vec<String>,
+    pub warnings: Vec<TransclusionWarning>,
+}
+
+// ============================================================================
+// Sections
+// ============================================================================
+
+/// ATX heading level and text, e.g. `## Scope ##` → (2, "Scope")
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !(rest.is_empty() || rest.starts_with(' ') || rest.starts_with('\t')) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// Headings match by text, case-insensitively, or by their anchor slug
+fn same_heading(text: &str, wanted: &str) -> bool {
+    let slug = |s: &str| {
+        s.trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-')
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    text.eq_ignore_ascii_case(wanted.trim()) || slug(text) == slug(wanted)
+}
+
+/// The heading line for `section` and everything up to the next heading of
+/// the same or a higher level, with its 1-based starting line in `body`.
+/// Headings inside fenced code don't count.
+fn extract_section(body: &str, section: &str) -> Option<(String, usize)> {
+    let mut in_fence = false;
+    let mut start: Option<(usize, usize)> = None; // (line index, level)
+    let lines: Vec<&str> = body.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((level, text)) = heading(line) else {
+            continue;
+        };
+        match start {
+            Some((from, open)) if level <= open => {
+                return Some((lines[from..i].join("\n"), from + 1));
+            }
+            None if same_heading(text, section) => start = Some((i, level)),
+            _ => {}
+        }
+    }
+    start.map(|(from, _)| (lines[from..].join("\n"), from + 1))
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Expands embeds against a set of docs; `load` reads a doc by relative path
+struct Renderer<'a, F: Fn(&str) -> Option<String>> {
+    resolver: Resolver<'a>,
+    load: F,
+    /// (doc, section) pairs currently being expanded
+    stack: Vec<(String, String)>,
+    expanded: usize,
+    embedded: Vec<String>,
+    warnings: Vec<TransclusionWarning>,
+}
+
+impl<'a, F: Fn(&str) -> Option<String>> Renderer<'a, F> {
+    fn new(docs: &'a [String], load: F) -> Self {
+        Self {
+            resolver: Resolver::new(docs.iter().map(|d| d.as_str())),
+            load,
+            stack: Vec::new(),
+            expanded: 0,
+            embedded: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn warn(&mut self, source: &str, line: usize, embed: &str, reason: &str) -> String {
+        self.warnings.push(TransclusionWarning {
+            source: source.to_string(),
+            line,
+            embed: embed.to_string(),
+            reason: reason.to_string(),
+        });
+        let note = match reason {
+            "not_found" => "document not found",
+            "section_not_found" => "section not found",
+            "cycle" => "embeds itself",
+            "depth_limit" => "nested too deeply",
+            _ => "too many embeds",
+        };
+        format!("> **Embed skipped ({}):** `{}`", note, embed)
+    }
+
+    /// Markdown for `rel` (or one section of it) with embeds expanded.
+    /// `body_start` is the line the text starts at in `rel`, for warnings.
+    fn expand(&mut self, rel: &str, text: &str, body_start: usize, depth: usize) -> String {
+        let mut out = Vec::new();
+        let mut in_fence = false;
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            if in_fence || !line.contains("![[") {
+                out.push(line.to_string());
+                continue;
+            }
+            let line_no = body_start + i;
+            let mut rendered = String::new();
+            let mut last = 0;
+            for cap in EMBED_RE.captures_iter(line) {
+                let whole = cap.get(0).unwrap();
+                rendered.push_str(&line[last..whole.start()]);
+                last = whole.end();
+                let target = cap[1].trim();
+                let section = cap.get(2).map(|s| s.as_str().trim()).unwrap_or_default();
+                let embedded = self.embed(rel, line_no, whole.as_str(), target, section, depth);
+                rendered.push_str(&embedded);
+            }
+            rendered.push_str(&line[last..]);
+            out.push(rendered);
+        }
+        out.join("\n")
+    }
+
+    fn embed(&mut self, source: &str, line: usize, raw: &str, target: &str, section: &str, depth: usize) -> String {
+        let Some(rel) = self.resolver.resolve_wiki(source, target) else {
+            return self.warn(source, line, raw, "not_found");
+        };
+        let key = (rel.clone(), section.to_lowercase());
+        if self.stack.iter().any(|(doc, s)| *doc == key.0 && (s.is_empty() || *s == key.1)) {
+            return self.warn(source, line, raw, "cycle");
+        }
+        if depth >= MAX_DEPTH {
+            return self.warn(source, line, raw, "depth_limit");
+        }
+        if self.expanded >= MAX_EMBEDS {
+            return self.warn(source, line, raw, "embed_limit");
+        }
+        let Some(content) = (self.load)(&rel) else {
+            return self.warn(source, line, raw, "not_found");
+        };
+        let (_, body, body_start) = split_frontmatter(&content);
+        let (text, start) = if section.is_empty() {
+            (body.trim_end().to_string(), body_start)
+        } else {
+            match extract_section(body, section) {
+                Some((text, offset)) => (text.trim_end().to_string(), body_start + offset - 1),
+                None => return self.warn(source, line, raw, "section_not_found"),
+            }
+        };
+
+        self.expanded += 1;
+        if !self.embedded.contains(&rel) {
+            self.embedded.push(rel.clone());
+        }
+        self.stack.push(key);
+        let expanded = self.expand(&rel, &text, start, depth + 1);
+        self.stack.pop();
+        expanded
+    }
+
+    /// Expand a whole document
+    fn render(&mut self, rel: &str, content: &str) -> String {
+        let (_, body, body_start) = split_frontmatter(content);
+        self.stack.push((rel.to_string(), String::new()));
+        let markdown = self.expand(rel, body, body_start, 0);
+        self.stack.pop();
+        markdown
+    }
+}
+
+fn to_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new_ext(markdown, options));
+    out
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Render a knowledge doc with `![[doc]]` / `![[doc#Section]]` embeds expanded
+/// into final markdown and HTML. `root` defaults to the knowledge path;
+/// embeds resolve within it the same way wiki-links do.
+#[command]
+pub async fn knowledge_render_document(path: String, root: Option<String>) -> CmdResult<RenderedDocument> {
+    let root = knowledge_root(root)?;
+    let rel = relative_to(&root, &path);
+    let content = fs::read_to_string(root.join(&rel))
+        .map_err(|e| CommandError::Io(format!("Failed to read {}: {}", path, e)))?;
+    let docs = indexed_docs(root.clone()).await?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut renderer = Renderer::new(&docs, |doc: &str| fs::read_to_string(root.join(doc)).ok());
+        let markdown = renderer.render(&rel, &content);
+        // Deduplicate warnings for an embed repeated through several paths
+        let mut seen = BTreeSet::new();
+        let warnings = std::mem::take(&mut renderer.warnings)
+            .into_iter()
+            .filter(|w| seen.insert((w.source.clone(), w.line, w.embed.clone())))
+            .collect();
+        RenderedDocument {
+            path,
+            html: to_html(&markdown),
+            markdown,
+            embedded: renderer.embedded,
+            warnings,
+        }
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Render task failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn expands_embeds_with_sections_and_limits() {
+        let files: HashMap<&str, &str> = HashMap::from([
+            ("proposals/acme.md", "---\ntitle: Acme\n---\n# Acme\n\n![[terms#Payment]]\n\n![[scope]]\n\n![[missing]]"),
+            (
+                "standard/terms.md",
+                "# Terms\n\n## Payment\nNet 30.\n### Late fees\n2%.\n## Liability\nCapped.",
+            ),
+            ("standard/scope.md", "---\nowner: ops\n---\nScope text. ![[scope]] ![[terms#Nope]]"),
+        ]);
+        let docs: Vec<String> = files.keys().map(|k| k.to_string()).collect();
+        let mut renderer = Renderer::new(&docs, |rel: &str| files.get(rel).map(|c| c.to_string()));
+        let markdown = renderer.render("proposals/acme.md", files["proposals/acme.md"]);
+
+        assert!(markdown.starts_with("# Acme"));
+        assert!(markdown.contains("## Payment\nNet 30.\n### Late fees\n2%."));
+        assert!(!markdown.contains("Liability"));
+        assert!(markdown.contains("Scope text."));
+        assert!(!markdown.contains("owner: ops"));
+        assert_eq!(renderer.embedded, vec!["standard/terms.md", "standard/scope.md"]);
+
+        let reasons: Vec<(&str, usize, &str)> = renderer
+            .warnings
+            .iter()
+            .map(|w| (w.source.as_str(), w.line, w.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("standard/scope.md", 4, "cycle"),
+                ("standard/scope.md", 4, "section_not_found"),
+                ("proposals/acme.md", 10, "not_found"),
+            ]
+        );
+        assert!(to_html(&markdown).contains("<h2>Payment</h2>"));
+    }
+}
@@ -0,0 +1,464 @@
+// src-tauri/src/commands/files/transfer.rs
+// Copy / move files and folders across directories with conflict handling
+
+use super::symlinks::copy_symlink;
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+use walkdir::WalkDir;
+
+/// Minimum gap between progress events so big folders don't flood the frontend
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Disambiguates staging siblings from concurrent transfers in one process
+static STAGING_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// What to do when the destination already exists. Without a strategy the
+/// command refuses to touch an existing destination.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// Replace the destination once the transfer has succeeded
+    Overwrite,
+    /// Leave the destination alone and report the source as skipped
+    Skip,
+    /// Fold folders together; same-named files are replaced by the incoming ones
+    Merge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+    pub source: String,
+    pub destination: String,
+    pub files_done: u32,
+    pub files_total: u32,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Relative path of the file just written
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferResult {
+    pub files_transferred: u32,
+    pub files_skipped: u32,
+    pub bytes_transferred: u64,
+}
+
+/// One file in a planned transfer, relative to the source root
+struct PlannedFile {
+    rel: PathBuf,
+    size: u64,
+}
+
+struct Plan {
+    dirs: Vec<PathBuf>,
+    files: Vec<PlannedFile>,
+    /// Symlinks inside the source, recreated as links rather than followed
+    links: Vec<PathBuf>,
+    bytes: u64,
+}
+
+/// How the transfer lands at the destination
+#[derive(Debug, PartialEq)]
+enum Landing {
+    Skip,
+    /// Write straight to the destination (it's free, or being merged into)
+    Direct,
+    /// Write to a staging sibling, then swap it in, so the existing
+    /// destination survives a failed transfer
+    Replace,
+}
+
+// ============================================================================
+// Planning
+// ============================================================================
+
+fn plan(source: &Path) -> CmdResult<Plan> {
+    let mut plan = Plan { dirs: Vec::new(), files: Vec::new(), links: Vec::new(), bytes: 0 };
+    if source.is_file() {
+        let size = fs::metadata(source)?.len();
+        plan.files.push(PlannedFile { rel: PathBuf::new(), size });
+        plan.bytes = size;
+        return Ok(plan);
+    }
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry.map_err(|e| CommandError::Io(format!("Failed to read {}: {}", source.display(), e)))?;
+        let rel = entry.path().strip_prefix(source).unwrap_or(entry.path()).to_path_buf();
+        if entry.file_type().is_symlink() && entry.depth() > 0 {
+            plan.links.push(rel);
+        } else if entry.file_type().is_dir() {
+            plan.dirs.push(rel);
+        } else {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            plan.bytes += size;
+            plan.files.push(PlannedFile { rel, size });
+        }
+    }
+    Ok(plan)
+}
+
+/// Reject transfers onto themselves, into their own subtree, or onto a
+/// folder that contains the source (replacing it would destroy the source)
+fn check_paths(source: &Path, destination: &Path) -> CmdResult<()> {
+    if !source.exists() {
+        return Err(CommandError::NotFound(format!("Source not found: {}", source.display())));
+    }
+    let src = fs::canonicalize(source)?;
+    let dest = canonical_target(destination);
+    if dest == src {
+        return Err(CommandError::Config("Source and destination are the same".into()));
+    }
+    if src.is_dir() && dest.starts_with(&src) {
+        return Err(CommandError::Config("Cannot copy or move a folder into itself".into()));
+    }
+    if src.starts_with(&dest) {
+        return Err(CommandError::Config(format!(
+            "Cannot copy or move onto {}, which contains the source",
+            destination.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Canonical form of a path that may not exist yet (resolve the parent instead)
+fn canonical_target(path: &Path) -> PathBuf {
+    if let Ok(p) = fs::canonicalize(path) {
+        return p;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent)
+            .map(|p| p.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Remove a file, folder or link (a link is removed, not its target)
+fn remove_path(path: &Path) -> CmdResult<()> {
+    if fs::symlink_metadata(path).is_ok_and(|m| m.is_dir()) {
+        fs::remove_dir_all(path).map_err(|e| CommandError::Io(format!("Failed to remove {}: {}", path.display(), e)))
+    } else {
+        fs::remove_file(path).map_err(|e| CommandError::Io(format!("Failed to remove {}: {}", path.display(), e)))
+    }
+}
+
+/// Hidden sibling of `path` used to stage a replacement or park the old copy
+fn staging_path(path: &Path, tag: &str) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}-{}-{}",
+        name,
+        tag,
+        std::process::id(),
+        STAGING_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Swap a fully written `staged` copy in for `destination`. The old
+/// destination is parked beside it and only removed after the swap; if the
+/// swap fails it's put back.
+fn replace_with(staged: &Path, destination: &Path) -> CmdResult<()> {
+    let parked = staging_path(destination, "tv-replaced");
+    fs::rename(destination, &parked)
+        .map_err(|e| CommandError::Io(format!("Failed to replace {}: {}", destination.display(), e)))?;
+    if let Err(e) = fs::rename(staged, destination) {
+        let _ = fs::rename(&parked, destination);
+        return Err(CommandError::Io(format!("Failed to replace {}: {}", destination.display(), e)));
+    }
+    if let Err(e) = remove_path(&parked) {
+        log::warn!("Replaced {} but couldn't remove the old copy: {}", destination.display(), e);
+    }
+    Ok(())
+}
+
+/// Decide how an existing destination is handled per the strategy. Nothing
+/// is removed here; an overwrite replaces the destination only once the
+/// transfer has succeeded.
+fn prepare_destination(source: &Path, destination: &Path, conflict: Option<ConflictStrategy>) -> CmdResult<Landing> {
+    if fs::symlink_metadata(destination).is_err() {
+        return Ok(Landing::Direct);
+    }
+    match conflict {
+        None => Err(CommandError::Config(format!("{} already exists", destination.display()))),
+        Some(ConflictStrategy::Skip) => Ok(Landing::Skip),
+        Some(ConflictStrategy::Overwrite) => Ok(Landing::Replace),
+        Some(ConflictStrategy::Merge) => {
+            if source.is_dir() != destination.is_dir() {
+                return Err(CommandError::Config(format!(
+                    "Cannot merge a file and a folder at {}",
+                    destination.display()
+                )));
+            }
+            Ok(Landing::Direct)
+        }
+    }
+}
+
+// ============================================================================
+// Copy
+// ============================================================================
+
+/// Copy a planned tree into `target` (the destination or its staging
+/// sibling), emitting throttled progress against `destination`. Destination
+/// conflicts are resolved by the caller, so existing files here are always
+/// replaced.
+fn copy_planned(
+    app: &AppHandle,
+    source: &Path,
+    destination: &Path,
+    target: &Path,
+    plan: &Plan,
+) -> CmdResult<TransferResult> {
+    let mut progress = TransferProgress {
+        source: source.to_string_lossy().to_string(),
+        destination: destination.to_string_lossy().to_string(),
+        files_done: 0,
+        files_total: plan.files.len() as u32,
+        bytes_done: 0,
+        bytes_total: plan.bytes,
+        current: None,
+    };
+    let _ = app.emit("file-transfer:progress", &progress);
+
+    for dir in &plan.dirs {
+        fs::create_dir_all(target.join(dir))
+            .map_err(|e| CommandError::Io(format!("Failed to create directory: {}", e)))?;
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    for link in &plan.links {
+        let to = target.join(link);
+        match fs::symlink_metadata(&to) {
+            Ok(meta) if meta.is_dir() => {
+                return Err(CommandError::Config(format!("A folder already exists at {}", to.display())));
+            }
+            Ok(_) => remove_path(&to)?,
+            Err(_) => {}
+        }
+        copy_symlink(&source.join(link), &to)
+            .map_err(|e| CommandError::Io(format!("Failed to copy link {}: {}", source.join(link).display(), e)))?;
+    }
+
+    let mut last_emit = Instant::now();
+    for file in &plan.files {
+        let (from, to) = if file.rel.as_os_str().is_empty() {
+            (source.to_path_buf(), target.to_path_buf())
+        } else {
+            (source.join(&file.rel), target.join(&file.rel))
+        };
+        if to.is_dir() {
+            return Err(CommandError::Config(format!("A folder already exists at {}", to.display())));
+        }
+        fs::copy(&from, &to).map_err(|e| CommandError::Io(format!("Failed to copy {}: {}", from.display(), e)))?;
+
+        progress.files_done += 1;
+        progress.bytes_done += file.size;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            progress.current = Some(file.rel.to_string_lossy().to_string());
+            let _ = app.emit("file-transfer:progress", &progress);
+            last_emit = Instant::now();
+        }
+    }
+
+    progress.current = None;
+    let _ = app.emit("file-transfer:progress", &progress);
+
+    Ok(TransferResult {
+        files_transferred: progress.files_done,
+        files_skipped: 0,
+        bytes_transferred: progress.bytes_done,
+    })
+}
+
+fn skipped(plan: &Plan) -> TransferResult {
+    TransferResult { files_transferred: 0, files_skipped: plan.files.len() as u32, bytes_transferred: 0 }
+}
+
+/// Copy into a staging sibling and swap it in, cleaning up on failure
+fn copy_replacing(app: &AppHandle, source: &Path, destination: &Path, plan: &Plan) -> CmdResult<TransferResult> {
+    let staged = staging_path(destination, "tv-transfer");
+    let result = copy_planned(app, source, destination, &staged, plan).and_then(|result| {
+        replace_with(&staged, destination)?;
+        Ok(result)
+    });
+    if result.is_err() && fs::symlink_metadata(&staged).is_ok() {
+        let _ = remove_path(&staged);
+    }
+    result
+}
+
+fn copy_blocking(app: &AppHandle, source: &Path, destination: &Path, conflict: Option<ConflictStrategy>) -> CmdResult<TransferResult> {
+    check_paths(source, destination)?;
+    let plan = plan(source)?;
+    match prepare_destination(source, destination, conflict)? {
+        Landing::Skip => Ok(skipped(&plan)),
+        Landing::Direct => copy_planned(app, source, destination, destination, &plan),
+        Landing::Replace => copy_replacing(app, source, destination, &plan),
+    }
+}
+
+/// Rename `source` to `destination` (swapping it in when replacing). False
+/// when a rename isn't possible, e.g. across volumes.
+fn rename_into_place(source: &Path, destination: &Path, landing: &Landing) -> CmdResult<bool> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if *landing == Landing::Direct {
+        return Ok(fs::rename(source, destination).is_ok());
+    }
+    let staged = staging_path(destination, "tv-transfer");
+    if fs::rename(source, &staged).is_err() {
+        return Ok(false);
+    }
+    if let Err(e) = replace_with(&staged, destination) {
+        // Put the source back where it was
+        let _ = fs::rename(&staged, source);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+fn move_blocking(app: &AppHandle, source: &Path, destination: &Path, conflict: Option<ConflictStrategy>) -> CmdResult<TransferResult> {
+    check_paths(source, destination)?;
+    let plan = plan(source)?;
+    let landing = prepare_destination(source, destination, conflict)?;
+    if landing == Landing::Skip {
+        return Ok(skipped(&plan));
+    }
+
+    // A plain rename is instant on the same volume, unless merging into an
+    // existing folder
+    let merging = landing == Landing::Direct && fs::symlink_metadata(destination).is_ok();
+    if !merging && rename_into_place(source, destination, &landing)? {
+        let result = TransferResult {
+            files_transferred: plan.files.len() as u32,
+            files_skipped: 0,
+            bytes_transferred: plan.bytes,
+        };
+        let _ = app.emit(
+            "file-transfer:progress",
+            TransferProgress {
+                source: source.to_string_lossy().to_string(),
+                destination: destination.to_string_lossy().to_string(),
+                files_done: result.files_transferred,
+                files_total: result.files_transferred,
+                bytes_done: plan.bytes,
+                bytes_total: plan.bytes,
+                current: None,
+            },
+        );
+        return Ok(result);
+    }
+
+    // Cross-volume or merging: copy everything, then drop the source
+    let result = match landing {
+        Landing::Replace => copy_replacing(app, source, destination, &plan)?,
+        _ => copy_planned(app, source, destination, destination, &plan)?,
+    };
+    remove_path(source)?;
+    Ok(result)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Copy a file or folder (recursively) to `destination`, the full target path.
+/// Emits `file-transfer:progress` while copying.
+#[command]
+pub async fn copy_path(
+    app: AppHandle,
+    source: String,
+    destination: String,
+    conflict: Option<ConflictStrategy>,
+) -> CmdResult<TransferResult> {
+    tauri::async_runtime::spawn_blocking(move || copy_blocking(&app, Path::new(&source), Path::new(&destination), conflict))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Copy task failed: {}", e)))?
+}
+
+/// Move a file or folder to `destination`, the full target path. Renames in
+/// place when possible, otherwise copies then removes the source.
+#[command]
+pub async fn move_path(
+    app: AppHandle,
+    source: String,
+    destination: String,
+    conflict: Option<ConflictStrategy>,
+) -> CmdResult<TransferResult> {
+    tauri::async_runtime::spawn_blocking(move || move_blocking(&app, Path::new(&source), Path::new(&destination), conflict))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Move task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_self_nesting_and_respects_conflict_strategies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dest = tmp.path().join("dest");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("nested/a.md"), "a").unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("old.md"), "old").unwrap();
+
+        assert!(check_paths(&src, &src.join("nested/copy")).is_err());
+        assert!(check_paths(&src, &dest).is_ok());
+        // Replacing a folder that holds the source would delete the source
+        assert!(check_paths(&src.join("nested/a.md"), &src).is_err());
+        assert!(check_paths(&src.join("nested/a.md"), tmp.path()).is_err());
+
+        let p = plan(&src).unwrap();
+        assert_eq!(p.files.len(), 1);
+        assert_eq!(p.bytes, 1);
+
+        assert!(prepare_destination(&src, &dest, None).is_err());
+        assert_eq!(prepare_destination(&src, &dest, Some(ConflictStrategy::Skip)).unwrap(), Landing::Skip);
+        assert_eq!(prepare_destination(&src, &dest, Some(ConflictStrategy::Merge)).unwrap(), Landing::Direct);
+        assert!(prepare_destination(&src.join("nested/a.md"), &dest, Some(ConflictStrategy::Merge)).is_err());
+        assert_eq!(prepare_destination(&src, &dest, Some(ConflictStrategy::Overwrite)).unwrap(), Landing::Replace);
+        // Deciding to overwrite doesn't touch the destination
+        assert!(dest.join("old.md").exists());
+
+        let staged = staging_path(&dest, "tv-transfer");
+        fs::create_dir_all(&staged).unwrap();
+        fs::write(staged.join("new.md"), "new").unwrap();
+        replace_with(&staged, &dest).unwrap();
+        assert!(dest.join("new.md").exists() && !dest.join("old.md").exists());
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recreates_symlinks_instead_of_following_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(tmp.path().join("shared/docs")).unwrap();
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.md"), "a").unwrap();
+        std::os::unix::fs::symlink("../shared", src.join("shared")).unwrap();
+
+        let p = plan(&src).unwrap();
+        assert_eq!(p.links, vec![PathBuf::from("shared")]);
+        assert_eq!(p.files.len(), 1);
+
+        let copy = tmp.path().join("copy");
+        fs::create_dir_all(&copy).unwrap();
+        copy_symlink(&src.join("shared"), &copy.join("shared")).unwrap();
+        assert_eq!(fs::read_link(copy.join("shared")).unwrap(), PathBuf::from("../shared"));
+        assert!(copy.join("shared/docs").is_dir());
+    }
+}
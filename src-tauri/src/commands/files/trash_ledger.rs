@@ -0,0 +1,129 @@
+// src-tauri/src/commands/files/trash_ledger.rs
+// Move to trash and put back. Windows and Linux list the trash with each
+// item's original path. The macOS trash can't be listed through the API, and
+// Finder renames clashes ("overview 2.md"), so on macOS we record where each
+// of our own deletions landed in ~/.Trash (~/.tv-client/trash_ledger.json)
+// and only restore items we can tie back to their original path.
+
+use crate::commands::error::{CmdResult, CommandError};
+use std::path::Path;
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn move_to_trash(path: &Path) -> CmdResult<()> {
+    trash::delete(path).map_err(|e| CommandError::Io(format!("Failed to move to trash: {}", e)))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn restore_trashed(target: &Path) -> CmdResult<()> {
+    let items = trash::os_limited::list().map_err(|e| CommandError::Io(format!("Failed to read trash: {}", e)))?;
+    // Most recent deletion wins if the same path was trashed more than once
+    let item = items
+        .into_iter()
+        .filter(|item| item.original_path() == target)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| CommandError::NotFound(format!("Not in trash: {}", target.display())))?;
+    trash::os_limited::restore_all([item]).map_err(|e| CommandError::Io(format!("Failed to restore: {}", e)))
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) use macos::{move_to_trash, restore_trashed};
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashSet;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct TrashedItem {
+        original: String,
+        trashed: String,
+        deleted_at: String,
+    }
+
+    fn ledger_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".tv-client")
+            .join("trash_ledger.json")
+    }
+
+    fn load() -> Vec<TrashedItem> {
+        fs::read_to_string(ledger_path())
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(items: &[TrashedItem]) -> CmdResult<()> {
+        let path = ledger_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(items)?)?;
+        Ok(())
+    }
+
+    fn trash_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(".Trash"))
+    }
+
+    fn names_in(dir: &Path) -> HashSet<OsString> {
+        fs::read_dir(dir)
+            .map(|entries| entries.flatten().map(|e| e.file_name()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Trash `path` and note where it landed. If that can't be told apart from
+    /// other items arriving at the same moment, nothing is recorded and the
+    /// item can only be put back from Finder.
+    pub(crate) fn move_to_trash(path: &Path) -> CmdResult<()> {
+        let dir = trash_dir();
+        let before = dir.as_deref().map(names_in).unwrap_or_default();
+        trash::delete(path).map_err(|e| CommandError::Io(format!("Failed to move to trash: {}", e)))?;
+
+        let (Some(dir), Some(stem)) = (dir, path.file_stem().map(|s| s.to_string_lossy().to_string())) else {
+            return Ok(());
+        };
+        let added: Vec<OsString> = names_in(&dir)
+            .difference(&before)
+            .filter(|n| n.to_string_lossy().starts_with(&stem))
+            .cloned()
+            .collect();
+        if let [name] = added.as_slice() {
+            let mut items = load();
+            // Items emptied from the Trash or put back by hand are gone
+            items.retain(|i| Path::new(&i.trashed).exists());
+            items.push(TrashedItem {
+                original: path.to_string_lossy().to_string(),
+                trashed: dir.join(name).to_string_lossy().to_string(),
+                deleted_at: chrono::Utc::now().to_rfc3339(),
+            });
+            save(&items)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn restore_trashed(target: &Path) -> CmdResult<()> {
+        let mut items = load();
+        // Most recent deletion wins if the same path was trashed more than once
+        let pos = items
+            .iter()
+            .rposition(|i| Path::new(&i.original) == target && Path::new(&i.trashed).exists())
+            .ok_or_else(|| {
+                CommandError::NotFound(format!(
+                    "{} can't be matched to an item in the Trash — restore it from Finder",
+                    target.display()
+                ))
+            })?;
+        let item = items.remove(pos);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&item.trashed, target).map_err(|e| CommandError::Io(format!("Failed to restore: {}", e)))?;
+        save(&items)
+    }
+}
@@ -0,0 +1,212 @@
+// src-tauri/src/commands/files/versions.rs
+// Opt-in snapshots before write. When the `file_versioning` setting is
+// "true", write_file copies the previous content to
+// `<dir>/.versions/<file name>/<version id>` and prunes to the newest
+// `file_versioning_max` (default 20). Version ids are UTC timestamps, so
+// they sort chronologically as plain strings.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings::{load_settings, KEY_FILE_VERSIONING, KEY_FILE_VERSIONING_MAX};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+const VERSIONS_DIR: &str = ".versions";
+const DEFAULT_MAX_VERSIONS: usize = 20;
+/// Larger files aren't snapshotted; the store is for documents, not media
+const MAX_SNAPSHOT_BYTES: u64 = 20 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileVersion {
+    pub id: String,
+    /// Location of the snapshot inside the .versions store
+    pub snapshot_path: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+fn versions_dir(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    Some(path.parent()?.join(VERSIONS_DIR).join(name))
+}
+
+/// Versioning is off unless explicitly enabled; returns the prune limit when on
+fn versioning_limit() -> Option<usize> {
+    let settings = load_settings().ok()?;
+    if settings.keys.get(KEY_FILE_VERSIONING).map(|v| v.as_str()) != Some("true") {
+        return None;
+    }
+    let max = settings
+        .keys
+        .get(KEY_FILE_VERSIONING_MAX)
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_MAX_VERSIONS);
+    Some(max)
+}
+
+/// Ids are generated here, so anything else (separators, "..") is rejected
+fn valid_version_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Snapshot ids in `dir`, oldest first
+fn version_ids(dir: &Path) -> Vec<String> {
+    let mut ids: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|id| valid_version_id(id))
+                .collect()
+        })
+        .unwrap_or_default();
+    ids.sort();
+    ids
+}
+
+/// Copy the current content of `path` into its version store unless it is
+/// missing, too large, or identical to `new_content`. Returns the new id.
+fn snapshot(path: &Path, new_content: Option<&[u8]>, max_versions: usize) -> std::io::Result<Option<String>> {
+    let Ok(meta) = fs::metadata(path) else {
+        return Ok(None);
+    };
+    if !meta.is_file() || meta.len() > MAX_SNAPSHOT_BYTES {
+        return Ok(None);
+    }
+    let current = fs::read(path)?;
+    if new_content == Some(current.as_slice()) {
+        return Ok(None);
+    }
+    let Some(dir) = versions_dir(path) else {
+        return Ok(None);
+    };
+    fs::create_dir_all(&dir)?;
+
+    let base = chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ").to_string();
+    let mut id = base.clone();
+    let mut n = 1;
+    while dir.join(&id).exists() {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    fs::write(dir.join(&id), current)?;
+
+    let ids = version_ids(&dir);
+    if ids.len() > max_versions {
+        for old in &ids[..ids.len() - max_versions] {
+            let _ = fs::remove_file(dir.join(old));
+        }
+    }
+    Ok(Some(id))
+}
+
+/// Called by write_file before it overwrites `path`. A failed snapshot is
+/// logged rather than blocking the write.
+pub fn snapshot_before_write(path: &str, new_content: &[u8]) {
+    let Some(max) = versioning_limit() else {
+        return;
+    };
+    if let Err(e) = snapshot(Path::new(path), Some(new_content), max) {
+        eprintln!("[files:versions] Failed to snapshot {}: {}", path, e);
+    }
+}
+
+/// Snapshot `path` before a bulk edit (search-and-replace) rewrites it with
+/// `new_content`. Like restore_version this runs even with versioning off,
+/// so the edit can always be undone. Returns the new version id.
+pub fn snapshot_before_edit(path: &Path, new_content: &[u8]) -> std::io::Result<Option<String>> {
+    snapshot(path, Some(new_content), versioning_limit().unwrap_or(DEFAULT_MAX_VERSIONS))
+}
+
+fn parse_version_time(id: &str) -> String {
+    let stamp = id.split('-').next().unwrap_or(id);
+    chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%3fZ")
+        .map(|t| t.and_utc().to_rfc3339())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Snapshots of `path`, newest first
+#[command]
+pub fn list_versions(path: String) -> CmdResult<Vec<FileVersion>> {
+    let Some(dir) = versions_dir(Path::new(&path)) else {
+        return Ok(Vec::new());
+    };
+    let versions = version_ids(&dir)
+        .into_iter()
+        .rev()
+        .map(|id| {
+            let snapshot_path = dir.join(&id);
+            FileVersion {
+                size: fs::metadata(&snapshot_path).map(|m| m.len()).unwrap_or(0),
+                created_at: parse_version_time(&id),
+                snapshot_path: snapshot_path.to_string_lossy().to_string(),
+                id,
+            }
+        })
+        .collect();
+    Ok(versions)
+}
+
+/// Restore `path` to a snapshot. The content being replaced is snapshotted
+/// first (even with versioning off) so a restore can itself be undone.
+#[command]
+pub fn restore_version(path: String, version_id: String) -> CmdResult<()> {
+    if !valid_version_id(&version_id) {
+        return Err(CommandError::Validation {
+            message: format!("Invalid version id: {}", version_id),
+            fields: vec!["version_id".to_string()],
+        });
+    }
+    let target = Path::new(&path);
+    let dir = versions_dir(target).ok_or_else(|| CommandError::Validation {
+        message: format!("Invalid path: {}", path),
+        fields: vec!["path".to_string()],
+    })?;
+    let snapshot_path = dir.join(&version_id);
+    if !snapshot_path.is_file() {
+        return Err(CommandError::NotFound(format!("Version {} of {}", version_id, path)));
+    }
+
+    let content = fs::read(&snapshot_path)?;
+    let max = versioning_limit().unwrap_or(DEFAULT_MAX_VERSIONS);
+    snapshot(target, Some(&content), max)
+        .map_err(|e| CommandError::Io(format!("Failed to snapshot current content: {}", e)))?;
+    fs::write(target, content).map_err(|e| CommandError::Io(format!("Failed to restore version: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_prunes_and_restores() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        let file = root.join("note.md");
+        fs::write(&file, "v1").unwrap();
+
+        // Identical content isn't snapshotted
+        assert_eq!(snapshot(&file, Some(b"v1"), 2).unwrap(), None);
+        for next in ["v2", "v3", "v4"] {
+            assert!(snapshot(&file, Some(next.as_bytes()), 2).unwrap().is_some());
+            fs::write(&file, next).unwrap();
+        }
+
+        let path = file.to_string_lossy().to_string();
+        let versions = list_versions(path.clone()).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(fs::read_to_string(&versions[0].snapshot_path).unwrap(), "v3");
+        assert!(!versions[0].created_at.is_empty());
+
+        restore_version(path.clone(), versions[1].id.clone()).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v2");
+        assert!(restore_version(path, "../escape".into()).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+// src-tauri/src/commands/files/watch.rs
+// Debounced directory watching — notify events are coalesced per path and
+// emitted as file-created / file-modified / file-deleted / file-renamed,
+// plus the batched `file-change` path list older listeners use.
+
+use super::symlinks;
+use crate::commands::error::{CmdResult, CommandError};
+use ignore::overrides::{Override, OverrideBuilder};
+use notify::event::{EventKind, ModifyKind, RenameMode};
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+
+const DEFAULT_DEBOUNCE_MS: u64 = 200;
+/// A steady stream of writes still flushes at least this often
+const MAX_BATCH_DELAY: Duration = Duration::from_secs(2);
+/// Cap on extra watches for symlinked folders outside the root
+const MAX_LINKED_DIRS: usize = 64;
+
+// Global watcher registry — prevents duplicate watchers and supports cleanup
+static WATCHERS: std::sync::LazyLock<Mutex<HashMap<String, WatchEntry>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+struct WatchEntry {
+    options: WatchOptions,
+    _watcher: RecommendedWatcher,
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchOptions {
+    /// Watch subfolders too (default true)
+    pub recursive: bool,
+    /// Only report paths matching one of these globs (e.g. "*.md")
+    pub include: Vec<String>,
+    /// Never report paths matching these globs (e.g. ".git/**")
+    pub exclude: Vec<String>,
+    /// Quiet period before a batch of changes is emitted
+    pub debounce_ms: u64,
+    /// Report changes inside symlinked folders, under the link's path (default
+    /// true). Links are resolved when watching starts.
+    pub follow_symlinks: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
+            follow_symlinks: true,
+        }
+    }
+}
+
+/// Payload of file-created / file-modified / file-deleted / file-renamed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileWatchEvent {
+    /// The watched directory that produced the event
+    pub root: String,
+    pub path: String,
+    /// Previous path, for renames
+    pub from: Option<String>,
+}
+
+/// Symlinked folder reachable from the watch root; changes under `target`
+/// are reported under `link`
+#[derive(Debug, Clone, PartialEq)]
+struct LinkedDir {
+    link: PathBuf,
+    target: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Created,
+    Modified,
+    Deleted,
+    Renamed { from: PathBuf },
+}
+
+// ============================================================================
+// Coalescing
+// ============================================================================
+
+/// Fold a new change into what's already pending for the same path.
+/// None means the two cancel out (created then deleted within one batch).
+fn merge_change(prev: Option<Change>, next: Change) -> Option<Change> {
+    match (prev, next) {
+        (Some(Change::Created), Change::Modified) => Some(Change::Created),
+        (Some(Change::Created), Change::Deleted) => None,
+        (Some(Change::Deleted), Change::Created) => Some(Change::Modified),
+        (Some(Change::Renamed { from }), Change::Modified) => Some(Change::Renamed { from }),
+        (_, next) => Some(next),
+    }
+}
+
+/// Pending changes in arrival order
+#[derive(Default)]
+struct Batch {
+    order: Vec<PathBuf>,
+    changes: HashMap<PathBuf, Change>,
+}
+
+impl Batch {
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn push(&mut self, path: PathBuf, change: Change) {
+        let mut change = change;
+        // The old path drops out; a file created and renamed in one batch is just created
+        if let Change::Renamed { from } = &change {
+            if self.changes.remove(from) == Some(Change::Created) {
+                change = Change::Created;
+            }
+        }
+        let prev = self.changes.remove(&path);
+        if let Some(merged) = merge_change(prev, change) {
+            if !self.order.contains(&path) {
+                self.order.push(path.clone());
+            }
+            self.changes.insert(path, merged);
+        }
+    }
+
+    fn drain(&mut self) -> Vec<(PathBuf, Change)> {
+        let mut changes = std::mem::take(&mut self.changes);
+        std::mem::take(&mut self.order)
+            .into_iter()
+            .filter_map(|p| changes.remove(&p).map(|c| (p, c)))
+            .collect()
+    }
+}
+
+/// Translate a notify event into per-path changes
+fn changes_from(event: notify::Event) -> Vec<(PathBuf, Change)> {
+    let mut paths = event.paths;
+    match event.kind {
+        EventKind::Create(_) => paths.into_iter().map(|p| (p, Change::Created)).collect(),
+        EventKind::Remove(_) => paths.into_iter().map(|p| (p, Change::Deleted)).collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => {
+            let to = paths.pop().unwrap_or_default();
+            let from = paths.pop().unwrap_or_default();
+            vec![(to, Change::Renamed { from })]
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            paths.into_iter().map(|p| (p, Change::Deleted)).collect()
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            paths.into_iter().map(|p| (p, Change::Created)).collect()
+        }
+        // Backends that can't pair renames report each side; existence tells which
+        EventKind::Modify(ModifyKind::Name(_)) => paths
+            .into_iter()
+            .map(|p| {
+                let change = if p.exists() { Change::Created } else { Change::Deleted };
+                (p, change)
+            })
+            .collect(),
+        EventKind::Modify(_) | EventKind::Any | EventKind::Other => {
+            paths.into_iter().map(|p| (p, Change::Modified)).collect()
+        }
+        EventKind::Access(_) => Vec::new(),
+    }
+}
+
+fn build_filter(root: &Path, options: &WatchOptions) -> CmdResult<Override> {
+    if options.include.is_empty() && options.exclude.is_empty() {
+        return Ok(Override::empty());
+    }
+    let mut builder = OverrideBuilder::new(root);
+    // Exclude-only filters shouldn't hide everything else (later globs win)
+    if options.include.is_empty() {
+        builder
+            .add("**")
+            .map_err(|e| CommandError::Internal(format!("Failed to build filter: {}", e)))?;
+    }
+    let globs = options
+        .include
+        .iter()
+        .cloned()
+        .chain(options.exclude.iter().map(|g| format!("!{}", g)));
+    for glob in globs {
+        builder
+            .add(&glob)
+            .map_err(|e| CommandError::Config(format!("Invalid glob '{}': {}", glob, e)))?;
+    }
+    builder
+        .build()
+        .map_err(|e| CommandError::Config(format!("Invalid watch filter: {}", e)))
+}
+
+// ============================================================================
+// Symlinks
+// ============================================================================
+
+/// Symlinked folders under `root` whose targets live outside it, including
+/// links inside those targets. Links that loop back (see followable_dir) are
+/// skipped. Targets inside the root are already covered by the root watch.
+fn linked_dirs(root: &Path, recursive: bool) -> Vec<LinkedDir> {
+    let Ok(canonical_root) = std::fs::canonicalize(root) else {
+        return Vec::new();
+    };
+    let mut found: Vec<LinkedDir> = Vec::new();
+    // (real folder to scan, path it's shown under, canonical folders walked to get here)
+    let mut stack = vec![(canonical_root.clone(), root.to_path_buf(), vec![canonical_root.clone()])];
+
+    while let Some((dir, shown_as, chain)) = stack.pop() {
+        let walker = walkdir::WalkDir::new(&dir)
+            .follow_links(false)
+            .min_depth(1)
+            .max_depth(if recursive { usize::MAX } else { 1 });
+        for entry in walker.into_iter().flatten().filter(|e| e.path_is_symlink()) {
+            let Some(target) = symlinks::followable_dir(entry.path(), &chain) else {
+                continue;
+            };
+            if target.starts_with(&canonical_root) || found.iter().any(|l| target.starts_with(&l.target)) {
+                continue;
+            }
+            if found.len() >= MAX_LINKED_DIRS {
+                log::warn!("Watching only the first {} symlinked folders under {}", MAX_LINKED_DIRS, root.display());
+                return found;
+            }
+            let rel = entry.path().strip_prefix(&dir).unwrap_or(entry.path());
+            let link = shown_as.join(rel);
+            found.push(LinkedDir { link: link.clone(), target: target.clone() });
+            if recursive {
+                let mut next_chain = chain.clone();
+                next_chain.push(target.clone());
+                stack.push((target, link, next_chain));
+            }
+        }
+    }
+    found
+}
+
+/// Path as seen from the watch root: events from a linked folder's own
+/// watch come in under the target and are moved back under the link
+fn shown_path(path: PathBuf, links: &[LinkedDir]) -> PathBuf {
+    let best = links
+        .iter()
+        .filter(|l| path.starts_with(&l.target))
+        .max_by_key(|l| l.target.components().count());
+    match best {
+        Some(l) => l.link.join(path.strip_prefix(&l.target).unwrap_or(Path::new(""))),
+        None => path,
+    }
+}
+
+fn is_filtered_out(filter: &Override, path: &Path) -> bool {
+    !filter.is_empty() && filter.matched(path, path.is_dir()).is_ignore()
+}
+
+// ============================================================================
+// Emitting
+// ============================================================================
+
+fn emit_batch(app: &AppHandle, root: &str, batch: Vec<(PathBuf, Change)>) {
+    let mut all_paths = Vec::new();
+    for (path, change) in batch {
+        let path_str = path.to_string_lossy().to_string();
+        let (event, from) = match change {
+            Change::Created => ("file-created", None),
+            Change::Modified => ("file-modified", None),
+            Change::Deleted => ("file-deleted", None),
+            Change::Renamed { from } => ("file-renamed", Some(from.to_string_lossy().to_string())),
+        };
+        if let Some(ref from) = from {
+            all_paths.push(from.clone());
+        }
+        all_paths.push(path_str.clone());
+        let _ = app.emit(event, FileWatchEvent { root: root.to_string(), path: path_str, from });
+    }
+    if !all_paths.is_empty() {
+        let _ = app.emit("file-change", all_paths);
+    }
+}
+
+/// Emit a batch, then hand its paths to the full-text search index
+fn flush(app: &AppHandle, root: &str, batch: Vec<(PathBuf, Change)>) {
+    let mut paths = Vec::new();
+    for (path, change) in &batch {
+        if let Change::Renamed { from } = change {
+            paths.push(from.clone());
+        }
+        paths.push(path.clone());
+    }
+    emit_batch(app, root, batch);
+    if !paths.is_empty() {
+        crate::commands::search::fulltext::apply_file_changes(&paths);
+    }
+}
+
+/// Collect events until the debounce window goes quiet, then emit the batch
+fn run_debouncer(
+    app: AppHandle,
+    root: String,
+    rx: std::sync::mpsc::Receiver<notify::Result<notify::Event>>,
+    filter: Override,
+    debounce: Duration,
+    links: Vec<LinkedDir>,
+    follow_symlinks: bool,
+) {
+    let root_path = PathBuf::from(&root);
+    let mut batch = Batch::default();
+    let mut first_at = Instant::now();
+    let mut last_at = Instant::now();
+    loop {
+        let received = if batch.is_empty() {
+            rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            let quiet = (last_at + debounce).min(first_at + MAX_BATCH_DELAY);
+            rx.recv_timeout(quiet.saturating_duration_since(Instant::now()))
+        };
+        match received {
+            Ok(Ok(event)) => {
+                for (path, change) in changes_from(event) {
+                    let path = shown_path(path, &links);
+                    let change = match change {
+                        Change::Renamed { from } => Change::Renamed { from: shown_path(from, &links) },
+                        other => other,
+                    };
+                    // Some backends (inotify) follow links on their own
+                    if !follow_symlinks && symlinks::passes_through_link(&root_path, &path) {
+                        continue;
+                    }
+                    if is_filtered_out(&filter, &path) {
+                        continue;
+                    }
+                    if batch.is_empty() {
+                        first_at = Instant::now();
+                    }
+                    batch.push(path, change);
+                }
+                last_at = Instant::now();
+            }
+            Ok(Err(e)) => log::error!("Watch error for {}: {:?}", root, e),
+            Err(RecvTimeoutError::Timeout) => flush(&app, &root, batch.drain()),
+            Err(RecvTimeoutError::Disconnected) => {
+                // Watcher dropped (unwatch_directory) — flush and exit
+                flush(&app, &root, batch.drain());
+                break;
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Watch a directory and emit debounced file-created / file-modified /
+/// file-deleted / file-renamed events (plus a batched `file-change`).
+/// Calling again for the same path is a no-op unless the options changed.
+#[command]
+pub async fn watch_directory(app: AppHandle, path: String, options: Option<WatchOptions>) -> CmdResult<()> {
+    let options = options.unwrap_or_default();
+    {
+        let watchers = WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        if watchers.get(&path).map(|w| w.options == options).unwrap_or(false) {
+            return Ok(()); // Already watching
+        }
+    }
+
+    let root = Path::new(&path);
+    let filter = build_filter(root, &options)?;
+    let (tx, rx) = channel();
+
+    let mut watcher = RecommendedWatcher::new(tx, Config::default())
+        .map_err(|e| CommandError::Internal(format!("Failed to create watcher: {}", e)))?;
+    let mode = if options.recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    watcher
+        .watch(root, mode)
+        .map_err(|e| CommandError::Internal(format!("Failed to watch directory: {}", e)))?;
+
+    let links = if options.follow_symlinks { linked_dirs(root, options.recursive) } else { Vec::new() };
+    for linked in &links {
+        if let Err(e) = watcher.watch(&linked.target, mode) {
+            log::warn!("Failed to watch {} (linked from {}): {}", linked.target.display(), linked.link.display(), e);
+        }
+    }
+
+    let debounce = Duration::from_millis(options.debounce_ms);
+    let watch_root = path.clone();
+    let follow_symlinks = options.follow_symlinks;
+    std::thread::spawn(move || run_debouncer(app, watch_root, rx, filter, debounce, links, follow_symlinks));
+
+    // Store watcher in registry (keeps it alive); replacing drops the old one
+    let mut watchers = WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    watchers.insert(path, WatchEntry { options, _watcher: watcher });
+    Ok(())
+}
+
+/// Stop watching a directory
+#[command]
+pub async fn unwatch_directory(path: String) -> CmdResult<()> {
+    let mut watchers = WATCHERS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    // Dropping the watcher closes the channel, so its debounce thread exits
+    watchers.remove(&path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_coalesce_per_path() {
+        let mut batch = Batch::default();
+        batch.push(PathBuf::from("/k/a.md"), Change::Created);
+        batch.push(PathBuf::from("/k/a.md"), Change::Modified);
+        batch.push(PathBuf::from("/k/tmp.md"), Change::Created);
+        batch.push(PathBuf::from("/k/tmp.md"), Change::Deleted);
+        batch.push(PathBuf::from("/k/b.md"), Change::Deleted);
+        batch.push(PathBuf::from("/k/b.md"), Change::Created);
+
+        let drained = batch.drain();
+        assert_eq!(
+            drained,
+            vec![(PathBuf::from("/k/a.md"), Change::Created), (PathBuf::from("/k/b.md"), Change::Modified)]
+        );
+        assert!(batch.is_empty());
+
+        let filter = build_filter(
+            Path::new("/k"),
+            &WatchOptions { exclude: vec![".git/**".into()], ..Default::default() },
+        )
+        .unwrap();
+        assert!(is_filtered_out(&filter, Path::new("/k/.git/HEAD")));
+        assert!(!is_filtered_out(&filter, Path::new("/k/notes/a.md")));
+
+        let links = vec![
+            LinkedDir { link: PathBuf::from("/k/shared"), target: PathBuf::from("/repo/docs") },
+            LinkedDir { link: PathBuf::from("/k/shared/api/v2"), target: PathBuf::from("/repo/docs/api/v2") },
+        ];
+        assert_eq!(shown_path(PathBuf::from("/repo/docs/a.md"), &links), PathBuf::from("/k/shared/a.md"));
+        assert_eq!(shown_path(PathBuf::from("/k/notes/a.md"), &links), PathBuf::from("/k/notes/a.md"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_linked_dirs_outside_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().to_path_buf();
+        std::fs::create_dir_all(base.join("kb/notes")).unwrap();
+        std::fs::create_dir_all(base.join("repo/docs/deeper")).unwrap();
+        std::fs::create_dir_all(base.join("other")).unwrap();
+        std::os::unix::fs::symlink(base.join("repo/docs"), base.join("kb/shared")).unwrap();
+        std::os::unix::fs::symlink(base.join("kb/notes"), base.join("kb/alias")).unwrap();
+        std::os::unix::fs::symlink(base.join("kb"), base.join("repo/docs/back")).unwrap();
+        std::os::unix::fs::symlink(base.join("other"), base.join("repo/docs/deeper/other")).unwrap();
+
+        let kb = base.join("kb");
+        let canonical = |p: &str| std::fs::canonicalize(base.join(p)).unwrap();
+        let mut links = linked_dirs(&kb, true);
+        links.sort_by(|a, b| a.link.cmp(&b.link));
+        // alias points inside the root and back loops, so neither gets a watch
+        assert_eq!(
+            links,
+            vec![
+                LinkedDir { link: kb.join("shared"), target: canonical("repo/docs") },
+                LinkedDir { link: kb.join("shared/deeper/other"), target: canonical("other") },
+            ]
+        );
+        assert_eq!(linked_dirs(&kb, false).len(), 1);
+    }
+}
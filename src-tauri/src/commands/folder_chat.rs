@@ -7,6 +7,7 @@ use std::path::Path;
 use tauri::command;
 
 use super::error::{CmdResult, CommandError};
+use super::search::semantic;
 use super::settings;
 
 // ============================================================================
@@ -254,6 +255,55 @@ const TOOLS_SCHEMA: &str = r#"[
     }
 ]"#;
 
+/// Offered only when the folder sits inside a semantic index
+const SEMANTIC_TOOL_SCHEMA: &str = r#"{
+    "name": "semantic_search",
+    "description": "Find passages whose meaning matches a question or concept, even when they use different words. Returns the closest passages with file paths and line numbers.",
+    "input_schema": {
+        "type": "object",
+        "properties": {
+            "query": {
+                "type": "string",
+                "description": "What to look for, in natural language"
+            },
+            "top_k": {
+                "type": "integer",
+                "description": "How many passages to return (default 6)"
+            }
+        },
+        "required": ["query"]
+    }
+}"#;
+
+/// Run semantic_search for the model: formatted passages plus the files they
+/// came from (relative to the folder), so they can be cited as sources
+async fn semantic_tool(input: &serde_json::Value, folder_path: &str) -> (String, Vec<String>) {
+    let query = input.get("query").and_then(|v| v.as_str()).unwrap_or("");
+    let top_k = input.get("top_k").and_then(|v| v.as_u64()).unwrap_or(6).clamp(1, 20) as usize;
+    match semantic::semantic_search(folder_path, query, top_k).await {
+        Ok(hits) if hits.is_empty() => (format!("No passages found for '{}'", query), Vec::new()),
+        Ok(hits) => {
+            let text = hits
+                .iter()
+                .map(|h| {
+                    format!(
+                        "## {} (lines {}-{}, score {:.2}){}\n{}",
+                        h.relative_path,
+                        h.start_line,
+                        h.end_line,
+                        h.score,
+                        h.heading.as_ref().map(|s| format!(" — {}", s)).unwrap_or_default(),
+                        h.text.chars().take(1200).collect::<String>()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            (text, hits.into_iter().map(|h| h.relative_path).collect())
+        }
+        Err(e) => (format!("Semantic search failed: {}", e), Vec::new()),
+    }
+}
+
 // ============================================================================
 // Main command
 // ============================================================================
@@ -296,6 +346,7 @@ pub async fn folder_chat_ask(
         }
     }
 
+    let semantic_enabled = semantic::is_indexed(&folder_path);
     let system_prompt = format!(
         r#"You are a helpful assistant exploring a folder called "{}" to answer questions.
 
@@ -304,7 +355,7 @@ You have access to tools to explore the folder:
 - **list_files**: See what files are in a directory
 - **read_file**: Read a file's content to find information
 - **search_files**: Search for keywords across all files in the folder
-
+{}
 ## Your Approach
 1. First, consider using search_files to find relevant files quickly
 2. Read the most promising files (usually 1-3 files)
@@ -321,6 +372,11 @@ You have access to tools to explore the folder:
 - If you can't find the answer, say so and suggest what might help
 - Always mention which files you found the information in"#,
         folder_name,
+        if semantic_enabled {
+            "- **semantic_search**: Find passages by meaning when keywords don't match (use it for conceptual questions)\n"
+        } else {
+            ""
+        },
         file_list,
         if context_content.is_empty() {
             String::new()
@@ -329,7 +385,12 @@ You have access to tools to explore the folder:
         }
     );
 
-    let tools: serde_json::Value = serde_json::from_str(TOOLS_SCHEMA)?;
+    let mut tools: serde_json::Value = serde_json::from_str(TOOLS_SCHEMA)?;
+    if semantic_enabled {
+        if let Some(list) = tools.as_array_mut() {
+            list.push(serde_json::from_str(SEMANTIC_TOOL_SCHEMA)?);
+        }
+    }
 
     // Build messages array
     let mut messages = Vec::new();
@@ -455,8 +516,19 @@ You have access to tools to explore the folder:
                         "input": input,
                     }));
 
-                    // Execute the tool
-                    let result = execute_tool(name, input, &folder_path);
+                    // Execute the tool (semantic search embeds the query, so it's async)
+                    let result = if name == "semantic_search" && semantic_enabled {
+                        let (result, files) = semantic_tool(input, &folder_path).await;
+                        for file in files {
+                            let abs_path = format!("{}/{}", folder_path, file);
+                            if !read_files.contains(&abs_path) {
+                                read_files.push(abs_path);
+                            }
+                        }
+                        result
+                    } else {
+                        execute_tool(name, input, &folder_path)
+                    };
 
                     // Track read files
                     if name == "read_file" {
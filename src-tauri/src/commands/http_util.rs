@@ -0,0 +1,158 @@
+// Minimal HTTP/1.1 plumbing shared by the app's local listeners (MCP bridge,
+// bot API, Outlook push receiver): one request per connection, read whole,
+// answered with `Connection: close`.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+pub(crate) fn respond(stream: &mut TcpStream, status: &str, body: &serde_json::Value) {
+    respond_text(stream, status, "application/json", &body.to_string());
+}
+
+/// Like `respond`, for bodies that aren't JSON (e.g. Graph's validation echo)
+pub(crate) fn respond_text(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// A parsed request off a local listener socket
+pub(crate) struct HttpRequest {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Raw query string (after `?`), if any
+    pub query: Option<String>,
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Value of an `Authorization: Bearer ...` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.head.lines().find_map(|l| {
+            let (k, v) = l.split_once(':')?;
+            if !k.eq_ignore_ascii_case("authorization") {
+                return None;
+            }
+            v.trim().strip_prefix("Bearer ").map(|t| t.trim())
+        })
+    }
+
+    /// Decoded value of a query-string parameter
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query.as_deref()?.split('&').find_map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            if k != name {
+                return None;
+            }
+            // Form encoding: '+' is a space (Graph's validationToken has them)
+            urlencoding::decode(&v.replace('+', " ")).ok().map(|s| s.into_owned())
+        })
+    }
+}
+
+/// Read headers + body (up to MAX_BODY_BYTES). Oversized bodies get a 413
+/// and `None`, as do dropped connections.
+pub(crate) fn read_request(stream: &mut TcpStream) -> Option<HttpRequest> {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    let (header_end, content_length) = loop {
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => return None,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let headers = String::from_utf8_lossy(&buf[..pos]).to_lowercase();
+            let len = headers
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length:"))
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            break (pos + 4, len);
+        }
+        if buf.len() > 64 * 1024 {
+            return None;
+        }
+    };
+    if content_length > MAX_BODY_BYTES {
+        respond(stream, "413 Payload Too Large", &serde_json::json!({ "error": "Body too large" }));
+        return None;
+    }
+    while buf.len() < header_end + content_length {
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or("/");
+    let (path, query) = match target.split_once('?') {
+        Some((p, q)) => (p.to_string(), Some(q.to_string())),
+        None => (target.to_string(), None),
+    };
+    let body = buf[header_end..].to_vec();
+
+    Some(HttpRequest { method, path, query, head, body })
+}
+
+/// Serve connections on `workers` threads. The accept loop feeds the returned
+/// sender; once `queue` connections are waiting, `try_send` hands the stream
+/// back so the caller can turn it away. Workers exit when the sender drops.
+pub(crate) fn worker_pool(workers: usize, queue: usize, handler: fn(TcpStream)) -> SyncSender<TcpStream> {
+    let (tx, rx) = mpsc::sync_channel::<TcpStream>(queue);
+    let rx = Arc::new(Mutex::new(rx));
+    for _ in 0..workers {
+        let rx = Arc::clone(&rx);
+        std::thread::spawn(move || loop {
+            let next = match rx.lock() {
+                Ok(rx) => rx.recv(),
+                Err(_) => return,
+            };
+            match next {
+                Ok(stream) => handler(stream),
+                Err(_) => return,
+            }
+        });
+    }
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(query: &str) -> HttpRequest {
+        HttpRequest {
+            method: "GET".into(),
+            path: "/".into(),
+            query: Some(query.into()),
+            head: "GET / HTTP/1.1\r\nAuthorization: Bearer abc123\r\n\r\n".into(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reads_bearer_token_and_form_decoded_params() {
+        let req = request("validationToken=Validation%3a+Testing+client&context=work");
+        assert_eq!(req.bearer_token(), Some("abc123"));
+        assert_eq!(req.query_param("validationToken").as_deref(), Some("Validation: Testing client"));
+        assert_eq!(req.query_param("context").as_deref(), Some("work"));
+        assert_eq!(req.query_param("missing"), None);
+    }
+}
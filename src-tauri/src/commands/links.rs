@@ -0,0 +1,421 @@
+// Global entity resolver + tvclient:// deep links
+//
+// A reference can be a task key (TASK-123), a UUID (task, project, deal,
+// company, contact), a VAL domain or table name, or a full deep link:
+//   tvclient://open/<ref>          resolve anything
+//   tvclient://<kind>/<ref>        resolve with a type hint (task, deal, table...)
+// Resolved links are emitted as "deep-link:open" to the window showing that
+// module (or the main window), which navigates via the notification nav target.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use crate::commands::val_sync::config::load_config_internal;
+
+pub const SCHEME: &str = "tvclient";
+
+/// Last link opened, for a window whose listener wasn't mounted yet (cold start)
+static PENDING: Lazy<Mutex<Option<ResolvedEntity>>> = Lazy::new(|| Mutex::new(None));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedEntity {
+    /// What the reference points at: task | project | deal | company | contact | domain | table
+    pub kind: String,
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Frontend module to open (ModuleId)
+    pub module: String,
+    /// Entity type / id the module's nav handler expects (e.g. a deal opens its company)
+    pub nav_entity_type: String,
+    pub nav_entity_id: String,
+    /// Canonical deep link for sharing
+    pub link: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityRef {
+    TaskKey { prefix: String, number: i32 },
+    Uuid(String),
+    Name(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskRow {
+    id: String,
+    title: String,
+    project_id: String,
+    task_number: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectRow {
+    id: String,
+    name: String,
+    identifier_prefix: Option<String>,
+    project_type: Option<String>,
+    company_id: Option<String>,
+    deal_stage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedRow {
+    id: String,
+    name: String,
+    company_id: Option<String>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+fn is_uuid(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    parts.len() == 5
+        && parts.iter().map(|p| p.len()).eq([8, 4, 4, 4, 12])
+        && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Classify a bare reference (no scheme)
+pub fn parse_ref(input: &str) -> EntityRef {
+    let s = input.trim().trim_start_matches('#');
+    if is_uuid(s) {
+        return EntityRef::Uuid(s.to_lowercase());
+    }
+    if let Some((prefix, number)) = s.rsplit_once('-') {
+        let prefix_ok = !prefix.is_empty()
+            && prefix.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && prefix.chars().all(|c| c.is_ascii_alphanumeric());
+        if let (true, Ok(n)) = (prefix_ok, number.parse::<i32>()) {
+            return EntityRef::TaskKey { prefix: prefix.to_uppercase(), number: n };
+        }
+    }
+    EntityRef::Name(s.to_string())
+}
+
+/// Split a tvclient:// URL into (kind hint, reference). Plain references pass through.
+pub fn parse_link(input: &str) -> (Option<String>, String) {
+    let Some(rest) = input.trim().strip_prefix(&format!("{}://", SCHEME)) else {
+        return (None, input.trim().to_string());
+    };
+    let rest = rest.split(|c: char| c == '?' || c == '#').next().unwrap_or_default().trim_end_matches('/');
+    let decode = |s: &str| urlencoding::decode(s).map(|d| d.into_owned()).unwrap_or_else(|_| s.to_string());
+    match rest.split_once('/') {
+        Some(("open", reference)) => (None, decode(reference)),
+        Some((kind, reference)) => (Some(kind.to_lowercase()), decode(reference)),
+        None => (None, decode(rest)),
+    }
+}
+
+/// No hint allows every kind
+fn hint_allows(hint: Option<&str>, kinds: &[&str]) -> bool {
+    match hint {
+        Some(h) => kinds.contains(&h),
+        None => true,
+    }
+}
+
+fn link_for(kind: &str, id: &str) -> String {
+    format!("{}://{}/{}", SCHEME, kind, urlencoding::encode(id))
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+fn from_project(p: ProjectRow) -> ResolvedEntity {
+    if p.project_type.as_deref() == Some("deal") {
+        ResolvedEntity {
+            kind: "deal".to_string(),
+            link: link_for("deal", &p.id),
+            title: p.name,
+            subtitle: p.deal_stage,
+            module: "crm".to_string(),
+            nav_entity_type: "crm_deal".to_string(),
+            // The CRM view opens deals through their company
+            nav_entity_id: p.company_id.unwrap_or_else(|| p.id.clone()),
+            id: p.id,
+        }
+    } else {
+        ResolvedEntity {
+            kind: "project".to_string(),
+            link: link_for("project", &p.id),
+            title: p.name,
+            subtitle: p.identifier_prefix,
+            module: "projects".to_string(),
+            nav_entity_type: "project".to_string(),
+            nav_entity_id: p.id.clone(),
+            id: p.id,
+        }
+    }
+}
+
+fn from_task(t: TaskRow, project: Option<&ProjectRow>) -> ResolvedEntity {
+    let key = match (project.and_then(|p| p.identifier_prefix.as_deref()), t.task_number) {
+        (Some(prefix), Some(n)) => Some(format!("{}-{}", prefix, n)),
+        _ => None,
+    };
+    ResolvedEntity {
+        kind: "task".to_string(),
+        link: link_for("task", key.as_deref().unwrap_or(&t.id)),
+        title: t.title,
+        subtitle: match (key, project) {
+            (Some(k), Some(p)) => Some(format!("{} · {}", k, p.name)),
+            (None, Some(p)) => Some(p.name.clone()),
+            (k, None) => k,
+        },
+        module: "projects".to_string(),
+        nav_entity_type: "task".to_string(),
+        nav_entity_id: t.id.clone(),
+        id: t.id,
+    }
+}
+
+async fn project_by_id(client: &SupabaseClient, id: &str) -> CmdResult<Option<ProjectRow>> {
+    client
+        .select_single(
+            "projects",
+            &format!("select=id,name,identifier_prefix,project_type,company_id,deal_stage&id=eq.{}", id),
+        )
+        .await
+}
+
+async fn resolve_task_key(client: &SupabaseClient, prefix: &str, number: i32) -> CmdResult<Option<ResolvedEntity>> {
+    let projects: Vec<ProjectRow> = client
+        .select(
+            "projects",
+            &format!(
+                "select=id,name,identifier_prefix,project_type,company_id,deal_stage&identifier_prefix=ilike.{}",
+                urlencoding::encode(prefix)
+            ),
+        )
+        .await?;
+    if projects.is_empty() {
+        return Ok(None);
+    }
+    let ids: Vec<&str> = projects.iter().map(|p| p.id.as_str()).collect();
+    let task: Option<TaskRow> = client
+        .select_single(
+            "tasks",
+            &format!(
+                "select=id,title,project_id,task_number&project_id=in.({})&task_number=eq.{}",
+                ids.join(","),
+                number
+            ),
+        )
+        .await?;
+    Ok(task.map(|t| {
+        let project = projects.iter().find(|p| p.id == t.project_id);
+        from_task(t, project)
+    }))
+}
+
+async fn resolve_uuid(client: &SupabaseClient, id: &str, hint: Option<&str>) -> CmdResult<Option<ResolvedEntity>> {
+    let wants = |kinds: &[&str]| hint_allows(hint, kinds);
+
+    if wants(&["task"]) {
+        let task: Option<TaskRow> = client
+            .select_single("tasks", &format!("select=id,title,project_id,task_number&id=eq.{}", id))
+            .await?;
+        if let Some(t) = task {
+            let project = project_by_id(client, &t.project_id).await?;
+            return Ok(Some(from_task(t, project.as_ref())));
+        }
+    }
+    if wants(&["project", "deal"]) {
+        if let Some(p) = project_by_id(client, id).await? {
+            return Ok(Some(from_project(p)));
+        }
+    }
+    if wants(&["company"]) {
+        let company: Option<NamedRow> = client
+            .select_single("crm_companies", &format!("select=id,name&id=eq.{}", id))
+            .await?;
+        if let Some(c) = company {
+            return Ok(Some(ResolvedEntity {
+                kind: "company".to_string(),
+                link: link_for("company", &c.id),
+                title: c.name,
+                subtitle: None,
+                module: "crm".to_string(),
+                nav_entity_type: "crm_company".to_string(),
+                nav_entity_id: c.id.clone(),
+                id: c.id,
+            }));
+        }
+    }
+    if wants(&["contact"]) {
+        let contact: Option<NamedRow> = client
+            .select_single("crm_contacts", &format!("select=id,name,company_id&id=eq.{}", id))
+            .await?;
+        if let Some(c) = contact {
+            return Ok(Some(ResolvedEntity {
+                kind: "contact".to_string(),
+                link: link_for("contact", &c.id),
+                title: c.name,
+                subtitle: None,
+                module: "crm".to_string(),
+                nav_entity_type: "crm_company".to_string(),
+                nav_entity_id: c.company_id.unwrap_or_else(|| c.id.clone()),
+                id: c.id,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// VAL domain by name, or a table id found under any domain's data_models
+fn resolve_val_name(name: &str, hint: Option<&str>) -> Option<ResolvedEntity> {
+    let config = load_config_internal().ok()?;
+    let lower = name.to_lowercase();
+
+    if hint_allows(hint, &["domain"]) {
+        if let Some(d) = config.domains.iter().find(|d| d.domain.to_lowercase() == lower) {
+            return Some(ResolvedEntity {
+                kind: "domain".to_string(),
+                id: d.domain.clone(),
+                title: d.domain.clone(),
+                subtitle: d.domain_type.clone(),
+                module: "domains".to_string(),
+                nav_entity_type: "domain".to_string(),
+                nav_entity_id: d.domain.clone(),
+                link: link_for("domain", &d.domain),
+            });
+        }
+    }
+
+    if hint_allows(hint, &["table"]) {
+        // "domain/table" narrows the search to one domain
+        let (domain_filter, table) = match lower.split_once('/') {
+            Some((d, t)) => (Some(d.to_string()), t.to_string()),
+            None => (None, lower.clone()),
+        };
+        let table = table.strip_prefix("table_").unwrap_or(&table).to_string();
+        for d in &config.domains {
+            if domain_filter.as_deref().is_some_and(|f| f != d.domain.to_lowercase()) {
+                continue;
+            }
+            let dir = Path::new(&d.global_path).join("data_models").join(format!("table_{}", table));
+            if dir.is_dir() {
+                return Some(ResolvedEntity {
+                    kind: "table".to_string(),
+                    id: format!("{}/{}", d.domain, table),
+                    title: table.clone(),
+                    subtitle: Some(d.domain.clone()),
+                    module: "domains".to_string(),
+                    nav_entity_type: "domain_artifact".to_string(),
+                    nav_entity_id: d.domain.clone(),
+                    link: link_for("table", &format!("{}/{}", d.domain, table)),
+                });
+            }
+        }
+    }
+    None
+}
+
+async fn resolve(reference: &str) -> CmdResult<ResolvedEntity> {
+    let (hint, raw) = parse_link(reference);
+    if raw.is_empty() {
+        return Err(CommandError::Config("Empty reference".to_string()));
+    }
+    let hint = hint.as_deref().filter(|h| *h != "open");
+
+    let found = match parse_ref(&raw) {
+        EntityRef::TaskKey { prefix, number } if hint_allows(hint, &["task"]) => {
+            let client = get_client().await?;
+            resolve_task_key(&client, &prefix, number).await?
+        }
+        EntityRef::Uuid(id) => {
+            let client = get_client().await?;
+            resolve_uuid(&client, &id, hint).await?
+        }
+        _ => resolve_val_name(&raw, hint),
+    };
+    found.ok_or_else(|| CommandError::NotFound(format!("Nothing matches '{}'", reference)))
+}
+
+// ============================================================================
+// Deep link dispatch
+// ============================================================================
+
+/// Resolve a tvclient:// URL and hand it to the window showing its module.
+/// Called from the deep-link plugin's open-url handler in main.rs.
+pub fn open_deep_link(app_handle: tauri::AppHandle, url: String) {
+    tauri::async_runtime::spawn(async move {
+        match resolve(&url).await {
+            Ok(entity) => {
+                if let Ok(mut pending) = PENDING.lock() {
+                    *pending = Some(entity.clone());
+                }
+                let windows = app_handle.webview_windows();
+                let prefix = format!("module-{}-", entity.module);
+                let window = windows
+                    .iter()
+                    .find(|(label, _)| label.starts_with(&prefix))
+                    .or_else(|| windows.iter().find(|(label, _)| label.as_str() == "main"))
+                    .or_else(|| windows.iter().next());
+                if let Some((label, window)) = window {
+                    let _ = window.unminimize();
+                    let _ = window.set_focus();
+                    let _ = app_handle.emit_to(label.as_str(), "deep-link:open", &entity);
+                }
+            }
+            Err(e) => {
+                eprintln!("[links] Failed to resolve {}: {}", url, e);
+                let _ = app_handle.emit(
+                    "deep-link:error",
+                    serde_json::json!({ "url": url, "error": e.to_string() }),
+                );
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Resolve a task key, UUID, domain/table name or tvclient:// link to its module and route
+#[tauri::command]
+pub async fn resolve_entity(reference: String) -> CmdResult<ResolvedEntity> {
+    resolve(&reference).await
+}
+
+/// Take the link that arrived before the frontend was listening (cold start)
+#[tauri::command]
+pub fn deep_link_take_pending() -> Option<ResolvedEntity> {
+    PENDING.lock().ok().and_then(|mut p| p.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_refs_and_links() {
+        assert_eq!(parse_ref("task-123"), EntityRef::TaskKey { prefix: "TASK".to_string(), number: 123 });
+        assert_eq!(
+            parse_ref("3F2504E0-4F89-11D3-9A0C-0305E82C3301"),
+            EntityRef::Uuid("3f2504e0-4f89-11d3-9a0c-0305e82c3301".to_string())
+        );
+        assert_eq!(parse_ref("custom_tbl_1_2"), EntityRef::Name("custom_tbl_1_2".to_string()));
+        assert_eq!(parse_ref("2026-10"), EntityRef::Name("2026-10".to_string()));
+
+        assert_eq!(parse_link("tvclient://open/TASK-7"), (None, "TASK-7".to_string()));
+        assert_eq!(
+            parse_link("tvclient://table/koi%2Fcustom_tbl_1_2/"),
+            (Some("table".to_string()), "koi/custom_tbl_1_2".to_string())
+        );
+        assert_eq!(parse_link("tvclient://TASK-7"), (None, "TASK-7".to_string()));
+        assert_eq!(parse_link(" TASK-7 "), (None, "TASK-7".to_string()));
+    }
+}
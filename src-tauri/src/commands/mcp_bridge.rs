@@ -0,0 +1,579 @@
+// MCP REST bridge — exposes a configurable subset of tv-mcp tools as plain
+// HTTP endpoints for automations that can't speak MCP.
+//
+// The bridge keeps one `tv-mcp` child process on stdio and forwards calls as
+// JSON-RPC `tools/call`. Callers authenticate with a bot API token and count
+// against that bot's rate limit and project scopes (see work::bot_api), so
+// every local entry point shares one set of tokens, limits and scopes. The bridge config (port, exposed
+// tools) lives in ~/.tv-mcp/bridge.json so tv-mcp sees the same values.
+//
+//   GET  /openapi.json        OpenAPI 3 schema built from tool input schemas
+//   GET  /tools               Exposed tools (?context=<module>|active to rank by module)
+//   POST /tools/{name}        Call a tool; JSON body = tool arguments
+
+use crate::commands::claude_setup::resolve_binary_path;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::http_util::{read_request, respond, worker_pool, HttpRequest};
+use crate::commands::mcp_dry_run::is_work_tool;
+use crate::commands::work::bot_api::{self, BotAccess};
+use crate::commands::work::tasks::work_get_task;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TrySendError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::command;
+
+/// Connections handled at once; tool calls share one tv-mcp child anyway
+const WORKERS: usize = 4;
+/// Accepted connections waiting for a worker before new ones get a 503
+const MAX_QUEUED: usize = 32;
+/// Longest a single tv-mcp call may take before the child is killed and
+/// respawned, so one hung tool can't hold the lock for every later caller
+const MCP_CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct McpBridgeConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// Tool names reachable over HTTP. Empty = nothing exposed.
+    pub exposed_tools: Vec<String>,
+}
+
+impl Default for McpBridgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 3849,
+            exposed_tools: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpBridgeStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub base_url: Option<String>,
+    pub requests_served: u64,
+    pub requests_rejected: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpToolSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "inputSchema", default)]
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Default)]
+struct BridgeRuntime {
+    running_port: Option<u16>,
+    requests_served: u64,
+    requests_rejected: u64,
+    last_error: Option<String>,
+}
+
+static RUNTIME: std::sync::LazyLock<Mutex<BridgeRuntime>> =
+    std::sync::LazyLock::new(|| Mutex::new(BridgeRuntime::default()));
+
+/// One long-lived tv-mcp child; calls are serialized through this lock.
+/// Dropping it kills the child.
+struct McpProcess {
+    child: Child,
+    stdin: ChildStdin,
+    /// Stdout lines, read on their own thread so waits can time out
+    lines: Receiver<String>,
+    next_id: u64,
+    /// A call timed out; the child may still be working on it, so it's
+    /// replaced rather than reused
+    timed_out: bool,
+}
+
+static MCP: std::sync::LazyLock<Mutex<Option<McpProcess>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+// ============================================================================
+// Config
+// ============================================================================
+
+fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-mcp")
+        .join("bridge.json")
+}
+
+fn load_config() -> McpBridgeConfig {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn set_error(message: Option<String>) {
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.last_error = message;
+    }
+}
+
+// ============================================================================
+// tv-mcp stdio client
+// ============================================================================
+
+impl McpProcess {
+    fn spawn() -> CmdResult<Self> {
+        let binary = resolve_binary_path()?;
+        let mut child = Command::new(&binary)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| CommandError::Internal(format!("failed to spawn tv-mcp: {}", e)))?;
+        let stdin = child.stdin.take().ok_or_else(|| CommandError::Internal("tv-mcp stdin unavailable".into()))?;
+        let stdout = child.stdout.take().ok_or_else(|| CommandError::Internal("tv-mcp stdout unavailable".into()))?;
+
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut process = Self { child, stdin, lines, next_id: 1, timed_out: false };
+        process.request(
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "tv-client-bridge", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        )?;
+        process.send(&serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))?;
+        Ok(process)
+    }
+
+    fn send(&mut self, message: &serde_json::Value) -> CmdResult<()> {
+        writeln!(self.stdin, "{}", message)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Send a JSON-RPC request and wait up to MCP_CALL_TIMEOUT for the
+    /// response with the same id, skipping any server notifications in between
+    fn request(&mut self, method: &str, params: serde_json::Value) -> CmdResult<serde_json::Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+
+        let deadline = Instant::now() + MCP_CALL_TIMEOUT;
+        loop {
+            let line = match self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.timed_out = true;
+                    return Err(CommandError::Internal(format!(
+                        "tv-mcp {} timed out after {}s",
+                        method,
+                        MCP_CALL_TIMEOUT.as_secs()
+                    )));
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err(CommandError::Internal("tv-mcp exited".into())),
+            };
+            let Ok(msg) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+                continue;
+            };
+            if msg.get("id").and_then(|v| v.as_u64()) != Some(id) {
+                continue;
+            }
+            if let Some(err) = msg.get("error") {
+                return Err(CommandError::Internal(format!(
+                    "tv-mcp {} failed: {}",
+                    method,
+                    err.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error")
+                )));
+            }
+            return Ok(msg.get("result").cloned().unwrap_or(serde_json::Value::Null));
+        }
+    }
+}
+
+impl Drop for McpProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Run a request against the shared child, respawning it once if it died.
+/// A call that times out kills the child (the next call gets a fresh one)
+/// and isn't retried, since the tool may already have had side effects.
+fn mcp_request(method: &str, params: serde_json::Value) -> CmdResult<serde_json::Value> {
+    let mut guard = MCP.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    for attempt in 0..2 {
+        if guard.is_none() {
+            *guard = Some(McpProcess::spawn()?);
+        }
+        let process = guard.as_mut().expect("process just spawned");
+        match process.request(method, params.clone()) {
+            Ok(result) => return Ok(result),
+            Err(e) if process.timed_out => {
+                *guard = None;
+                return Err(e);
+            }
+            Err(CommandError::Io(_)) | Err(CommandError::Internal(_)) if attempt == 0 && process.child.try_wait().ok().flatten().is_some() => {
+                *guard = None;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(CommandError::Internal("tv-mcp unavailable".into()))
+}
+
+/// One tool's spec from tv-mcp's tools/list
+pub(crate) fn tool_spec(name: &str) -> CmdResult<Option<McpToolSpec>> {
+    let result = mcp_request("tools/list", serde_json::json!({}))?;
+    let tools: Vec<McpToolSpec> = serde_json::from_value(result.get("tools").cloned().unwrap_or_default())?;
+    Ok(tools.into_iter().find(|t| t.name == name))
+}
+
+fn list_exposed_tools(config: &McpBridgeConfig) -> CmdResult<Vec<McpToolSpec>> {
+    let result = mcp_request("tools/list", serde_json::json!({}))?;
+    let tools: Vec<McpToolSpec> = serde_json::from_value(result.get("tools").cloned().unwrap_or_default())?;
+    Ok(tools
+        .into_iter()
+        .filter(|t| config.exposed_tools.iter().any(|name| name == &t.name))
+        .collect())
+}
+
+// ============================================================================
+// OpenAPI
+// ============================================================================
+
+/// Build an OpenAPI 3.0 document: one POST operation per exposed tool, request
+/// body = the tool's MCP input schema
+pub fn build_openapi(tools: &[McpToolSpec], base_url: &str) -> serde_json::Value {
+    let mut paths = serde_json::Map::new();
+    for tool in tools {
+        let schema = if tool.input_schema.is_object() {
+            tool.input_schema.clone()
+        } else {
+            serde_json::json!({ "type": "object" })
+        };
+        paths.insert(
+            format!("/tools/{}", tool.name),
+            serde_json::json!({
+                "post": {
+                    "operationId": tool.name,
+                    "summary": tool.description.clone().unwrap_or_default(),
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": schema } }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Tool result",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ToolResult" } } }
+                        },
+                        "401": { "description": "Missing or invalid bot token" },
+                        "404": { "description": "Tool not exposed" },
+                        "429": { "description": "Rate limit exceeded" }
+                    }
+                }
+            }),
+        );
+    }
+
+    serde_json::json!({
+        "openapi": "3.0.3",
+        "info": { "title": "tv-mcp REST bridge", "version": env!("CARGO_PKG_VERSION") },
+        "servers": [{ "url": base_url }],
+        "security": [{ "bearerAuth": [] }],
+        "paths": paths,
+        "components": {
+            "securitySchemes": { "bearerAuth": { "type": "http", "scheme": "bearer" } },
+            "schemas": {
+                "ToolResult": {
+                    "type": "object",
+                    "properties": {
+                        "content": { "type": "array", "items": { "type": "object" } },
+                        "isError": { "type": "boolean" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+// ============================================================================
+// HTTP server
+// ============================================================================
+
+/// Project and task ids named at the top level of a tool call's arguments
+fn scope_targets(arguments: &serde_json::Value) -> (Vec<String>, Vec<String>) {
+    let ids = |keys: [&str; 2]| -> Vec<String> {
+        keys.iter()
+            .filter_map(|k| arguments.get(*k)?.as_str())
+            .map(str::to_string)
+            .collect()
+    };
+    (ids(["project_id", "projectId"]), ids(["task_id", "taskId"]))
+}
+
+/// Hold a tool call to the bot's project scope: every project it names,
+/// directly or through a task, must be allowed, and Work tools must name one
+/// (a bare `list-tasks` would read every project).
+fn check_scope(bot: &BotAccess, name: &str, arguments: &serde_json::Value) -> Result<(), String> {
+    let (mut projects, tasks) = scope_targets(arguments);
+    for task_id in tasks {
+        match tauri::async_runtime::block_on(work_get_task(task_id.clone())) {
+            Ok(task) => projects.push(task.project_id),
+            Err(e) => return Err(format!("Can't check task {} against the bot's projects: {}", task_id, e)),
+        }
+    }
+    if let Some(project_id) = projects.iter().find(|p| !bot.allows_project(p)) {
+        return Err(format!("Bot is not allowed to access project {}", project_id));
+    }
+    if projects.is_empty() && is_work_tool(name) {
+        return Err("Work tools need a project_id or task_id in one of the bot's projects".into());
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: std::net::TcpStream) {
+    let Some(request) = read_request(&mut stream) else {
+        return;
+    };
+    let HttpRequest { method, path, .. } = &request;
+
+    // Config and grants are re-read per request so UI changes apply without a restart
+    let config = load_config();
+
+    let bot = match bot_api::authenticate(&request) {
+        None => Err(("401 Unauthorized", "Missing or invalid bot token")),
+        Some(bot) if !bot_api::allow_request(&bot) => Err(("429 Too Many Requests", "Rate limit exceeded")),
+        Some(bot) => Ok(bot),
+    };
+    let bot = match bot {
+        Ok(bot) => bot,
+        Err((status, error)) => {
+            if let Ok(mut rt) = RUNTIME.lock() {
+                rt.requests_rejected += 1;
+            }
+            respond(&mut stream, status, &serde_json::json!({ "error": error }));
+            return;
+        }
+    };
+
+    let result = match (method.as_str(), path.as_str()) {
+        ("GET", "/openapi.json") => list_exposed_tools(&config)
+            .map(|tools| build_openapi(&tools, &format!("http://127.0.0.1:{}", config.port))),
+        // ?context=<module> ranks (and per profile, trims) for that module; ?context=active uses the app's
+        ("GET", "/tools") => list_exposed_tools(&config).and_then(|tools| match request.query_param("context") {
+            Some(ctx) => {
+                let module = if ctx == "active" { None } else { Some(ctx.as_str()) };
+                Ok(serde_json::to_value(crate::commands::mcp_context::rank_for_context(tools, module))?)
+            }
+            None => Ok(serde_json::json!({ "tools": tools })),
+        }),
+        ("POST", p) if p.starts_with("/tools/") => {
+            let name = urlencoding::decode(&p["/tools/".len()..]).map(|s| s.into_owned()).unwrap_or_default();
+            if !config.exposed_tools.contains(&name) {
+                respond(&mut stream, "404 Not Found", &serde_json::json!({ "error": format!("Tool not exposed: {}", name) }));
+                return;
+            }
+            let body = &request.body;
+            let arguments: serde_json::Value = if body.is_empty() {
+                serde_json::json!({})
+            } else {
+                match serde_json::from_slice(body) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        respond(&mut stream, "400 Bad Request", &serde_json::json!({ "error": format!("Invalid JSON body: {}", e) }));
+                        return;
+                    }
+                }
+            };
+            if let Err(error) = check_scope(&bot, &name, &arguments) {
+                if let Ok(mut rt) = RUNTIME.lock() {
+                    rt.requests_rejected += 1;
+                }
+                respond(&mut stream, "403 Forbidden", &serde_json::json!({ "error": error }));
+                return;
+            }
+            // Dry-run mode answers Work/CRM/VAL mutations with a preview instead
+            crate::commands::mcp_dry_run::intercept(&name, &arguments).and_then(|preview| match preview {
+                Some(preview) => Ok(preview),
+                None => mcp_request("tools/call", serde_json::json!({ "name": name, "arguments": arguments })),
+            })
+        }
+        _ => {
+            respond(&mut stream, "404 Not Found", &serde_json::json!({ "error": "Not found" }));
+            return;
+        }
+    };
+
+    match result {
+        Ok(body) => {
+            if let Ok(mut rt) = RUNTIME.lock() {
+                rt.requests_served += 1;
+            }
+            respond(&mut stream, "200 OK", &body);
+        }
+        Err(e) => {
+            set_error(Some(e.to_string()));
+            respond(&mut stream, "502 Bad Gateway", &serde_json::json!({ "error": e.to_string() }));
+        }
+    }
+}
+
+/// Start the HTTP listener if enabled and not already running. Port changes
+/// take effect on next app start.
+fn start_server(config: &McpBridgeConfig) -> CmdResult<()> {
+    {
+        let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        if rt.running_port.is_some() {
+            return Ok(());
+        }
+    }
+
+    let listener = std::net::TcpListener::bind(format!("127.0.0.1:{}", config.port))
+        .map_err(|e| CommandError::Io(format!("Failed to bind MCP bridge on port {}: {}", config.port, e)))?;
+
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.running_port = Some(config.port);
+    }
+    eprintln!("[mcp-bridge] Listening on 127.0.0.1:{}", config.port);
+
+    std::thread::spawn(move || {
+        let pool = worker_pool(WORKERS, MAX_QUEUED, handle_connection);
+        for stream in listener.incoming().flatten() {
+            // Disabled at runtime: refuse without tearing the listener down
+            if !load_config().enabled {
+                let mut stream = stream;
+                respond(&mut stream, "503 Service Unavailable", &serde_json::json!({ "error": "Bridge disabled" }));
+                continue;
+            }
+            if let Err(TrySendError::Full(mut stream) | TrySendError::Disconnected(mut stream)) = pool.try_send(stream) {
+                respond(&mut stream, "503 Service Unavailable", &serde_json::json!({ "error": "Bridge busy" }));
+            }
+        }
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.running_port = None;
+        }
+    });
+
+    Ok(())
+}
+
+/// Called from app setup — starts the bridge only if the user enabled it
+pub fn start_bridge() {
+    let config = load_config();
+    if !config.enabled {
+        return;
+    }
+    if let Err(e) = start_server(&config) {
+        eprintln!("[mcp-bridge] {}", e);
+        set_error(Some(e.to_string()));
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn mcp_bridge_get_config() -> CmdResult<McpBridgeConfig> {
+    Ok(load_config())
+}
+
+/// Save bridge config. Callers need a bot API token (work_bot_api_grant).
+#[command]
+pub async fn mcp_bridge_save_config(config: McpBridgeConfig) -> CmdResult<McpBridgeStatus> {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&config)?)?;
+
+    if config.enabled {
+        start_server(&config)?;
+    }
+    mcp_bridge_status().await
+}
+
+#[command]
+pub async fn mcp_bridge_status() -> CmdResult<McpBridgeStatus> {
+    let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    Ok(McpBridgeStatus {
+        running: rt.running_port.is_some(),
+        port: rt.running_port,
+        base_url: rt.running_port.map(|p| format!("http://127.0.0.1:{}", p)),
+        requests_served: rt.requests_served,
+        requests_rejected: rt.requests_rejected,
+        last_error: rt.last_error.clone(),
+    })
+}
+
+/// All tools tv-mcp offers, for picking which to expose
+#[command]
+pub async fn mcp_bridge_list_tools() -> CmdResult<Vec<McpToolSpec>> {
+    tauri::async_runtime::spawn_blocking(|| -> CmdResult<Vec<McpToolSpec>> {
+        let result = mcp_request("tools/list", serde_json::json!({}))?;
+        Ok(serde_json::from_value(result.get("tools").cloned().unwrap_or_default())?)
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Task error: {}", e)))?
+}
+
+/// OpenAPI document for the currently exposed tools
+#[command]
+pub async fn mcp_bridge_openapi() -> CmdResult<serde_json::Value> {
+    let config = load_config();
+    tauri::async_runtime::spawn_blocking(move || -> CmdResult<serde_json::Value> {
+        let tools = list_exposed_tools(&config)?;
+        Ok(build_openapi(&tools, &format!("http://127.0.0.1:{}", config.port)))
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Task error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bot(projects: &[&str]) -> BotAccess {
+        BotAccess {
+            bot_id: "bot".into(),
+            bot_name: "Bot".into(),
+            enabled: true,
+            allowed_project_ids: projects.iter().map(|p| p.to_string()).collect(),
+            rate_limit_per_minute: 30,
+            token_hash: None,
+        }
+    }
+
+    #[test]
+    fn tool_calls_stay_inside_the_bot_projects() {
+        let bot = bot(&["p1"]);
+        assert!(check_scope(&bot, "list-tasks", &serde_json::json!({ "project_id": "p1" })).is_ok());
+        assert!(check_scope(&bot, "create-task", &serde_json::json!({ "projectId": "p2" })).is_err());
+        // Work tools must name a project; other modules aren't project-scoped
+        assert!(check_scope(&bot, "list-tasks", &serde_json::json!({})).is_err());
+        assert!(check_scope(&bot, "list-companies", &serde_json::json!({})).is_ok());
+    }
+}
@@ -0,0 +1,272 @@
+// MCP tool context — tells tv-mcp (and the REST bridge) which module the
+// user is working in, so tool lists can lead with the tools that matter
+// there instead of the full catalog.
+//
+// The frontend reports the active module/window; it's written to
+// ~/.tv-mcp/context.json next to bridge.json so tv-mcp can read it on
+// tools/list. Module → tool keyword profiles live in
+// ~/.tv-mcp/context_profiles.json and fall back to built-in defaults.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::mcp_bridge::McpToolSpec;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// A context older than this is treated as unset (the app may have closed)
+const CONTEXT_TTL_HOURS: i64 = 12;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpActiveContext {
+    /// Frontend module id, e.g. "crm", "work", "domains"
+    pub module: String,
+    /// Tauri window label, for multi-window setups
+    pub window: Option<String>,
+    /// Entity the user has open (company, project, domain…), if any
+    pub entity_id: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct McpContextProfile {
+    pub module: String,
+    /// Tools whose name (or description) mentions one of these rank first
+    pub keywords: Vec<String>,
+    /// Tool names always kept and ranked first, e.g. cross-module search
+    pub always: Vec<String>,
+    /// Drop unrelated tools instead of only reordering them
+    pub trim: bool,
+    /// Cap on the trimmed list (None = no cap)
+    pub max_tools: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpContextTools {
+    pub module: Option<String>,
+    /// Names of the tools ranked as relevant to the module
+    pub prioritized: Vec<String>,
+    /// Tools left out because the profile trims
+    pub trimmed: usize,
+    pub tools: Vec<McpToolSpec>,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn mcp_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")).join(".tv-mcp")
+}
+
+fn context_path() -> PathBuf {
+    mcp_dir().join("context.json")
+}
+
+fn profiles_path() -> PathBuf {
+    mcp_dir().join("context_profiles.json")
+}
+
+fn profile(module: &str, keywords: &[&str]) -> McpContextProfile {
+    McpContextProfile {
+        module: module.to_string(),
+        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+fn default_profiles() -> Vec<McpContextProfile> {
+    vec![
+        profile("crm", &["crm", "company", "companies", "contact", "activity", "activities", "deal"]),
+        profile("work", &["task", "project", "milestone", "initiative", "label", "session"]),
+        profile("projects", &["project", "milestone", "initiative", "task"]),
+        profile("inbox", &["email", "outlook", "draft", "inbox"]),
+        profile("email", &["email", "draft", "campaign", "outreach"]),
+        profile("shared-inbox", &["email", "inbox", "draft"]),
+        profile("calendar", &["calendar", "event", "meeting"]),
+        profile("library", &["file", "folder", "knowledge", "document", "doc", "search"]),
+        profile("domains", &["val", "domain", "table", "workflow", "query", "dashboard", "sql", "sync"]),
+        profile("scheduler", &["automation", "job", "schedule", "run"]),
+        profile("skills", &["skill"]),
+        profile("mcp-tools", &["mcp", "tool"]),
+    ]
+}
+
+fn load_profiles() -> Vec<McpContextProfile> {
+    std::fs::read_to_string(profiles_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_else(default_profiles)
+}
+
+/// The active context, if one was reported within CONTEXT_TTL_HOURS
+pub fn load_active_context() -> Option<McpActiveContext> {
+    let ctx: McpActiveContext = std::fs::read_to_string(context_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())?;
+    let updated = chrono::DateTime::parse_from_rfc3339(&ctx.updated_at).ok()?;
+    let fresh = chrono::Utc::now().signed_duration_since(updated) < chrono::Duration::hours(CONTEXT_TTL_HOURS);
+    if fresh {
+        Some(ctx)
+    } else {
+        None
+    }
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> CmdResult<()> {
+    std::fs::create_dir_all(mcp_dir())?;
+    std::fs::write(path, serde_json::to_string_pretty(value)?)
+        .map_err(|e| CommandError::Io(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+// ============================================================================
+// Ranking
+// ============================================================================
+
+/// 3 = pinned by `always`, 2 = keyword in the name, 1 = keyword in the description
+fn relevance(tool: &McpToolSpec, profile: &McpContextProfile) -> u8 {
+    if profile.always.iter().any(|n| n == &tool.name) {
+        return 3;
+    }
+    let name = tool.name.to_lowercase().replace('-', "_");
+    let description = tool.description.as_deref().unwrap_or_default().to_lowercase();
+    let keywords = || profile.keywords.iter().map(|k| k.to_lowercase());
+    if keywords().any(|k| name.contains(&k)) {
+        2
+    } else if keywords().any(|k| description.contains(&k)) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Order `tools` for `module`: relevant first (stable within a tier), and
+/// drop the rest when the profile trims. Unknown modules keep the full list.
+pub fn rank_tools(tools: Vec<McpToolSpec>, module: Option<&str>, profiles: &[McpContextProfile]) -> McpContextTools {
+    let Some(profile) = module.and_then(|m| profiles.iter().find(|p| p.module == m)) else {
+        return McpContextTools { module: module.map(String::from), prioritized: Vec::new(), trimmed: 0, tools };
+    };
+
+    let mut scored: Vec<(u8, McpToolSpec)> = tools.into_iter().map(|t| (relevance(&t, profile), t)).collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    let prioritized: Vec<String> = scored.iter().filter(|(s, _)| *s > 0).map(|(_, t)| t.name.clone()).collect();
+
+    let total = scored.len();
+    let mut tools: Vec<McpToolSpec> = if profile.trim && !prioritized.is_empty() {
+        scored.into_iter().filter(|(s, _)| *s > 0).map(|(_, t)| t).collect()
+    } else {
+        scored.into_iter().map(|(_, t)| t).collect()
+    };
+    if profile.trim {
+        if let Some(max) = profile.max_tools {
+            tools.truncate(max.max(1));
+        }
+    }
+
+    McpContextTools {
+        module: Some(profile.module.clone()),
+        prioritized,
+        trimmed: total - tools.len(),
+        tools,
+    }
+}
+
+/// Rank for the active context, or for an explicit module
+pub fn rank_for_context(tools: Vec<McpToolSpec>, module: Option<&str>) -> McpContextTools {
+    let active = load_active_context();
+    let module = module.map(String::from).or_else(|| active.map(|c| c.module));
+    rank_tools(tools, module.as_deref(), &load_profiles())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Record the module/window the user is working in (called on navigation and focus)
+#[command]
+pub async fn mcp_set_active_context(
+    module: String,
+    window: Option<String>,
+    entity_id: Option<String>,
+) -> CmdResult<McpActiveContext> {
+    let ctx = McpActiveContext {
+        module,
+        window,
+        entity_id,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    write_json(&context_path(), &ctx)?;
+    Ok(ctx)
+}
+
+#[command]
+pub async fn mcp_get_active_context() -> CmdResult<Option<McpActiveContext>> {
+    Ok(load_active_context())
+}
+
+#[command]
+pub async fn mcp_clear_active_context() -> CmdResult<()> {
+    match std::fs::remove_file(context_path()) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[command]
+pub async fn mcp_context_get_profiles() -> CmdResult<Vec<McpContextProfile>> {
+    Ok(load_profiles())
+}
+
+#[command]
+pub async fn mcp_context_save_profiles(profiles: Vec<McpContextProfile>) -> CmdResult<Vec<McpContextProfile>> {
+    write_json(&profiles_path(), &profiles)?;
+    Ok(profiles)
+}
+
+/// Preview the tool list an agent would see for `module` (default: active context)
+#[command]
+pub async fn mcp_list_tools_for_context(module: Option<String>) -> CmdResult<McpContextTools> {
+    let tools = crate::commands::mcp_bridge::mcp_bridge_list_tools().await?;
+    Ok(rank_for_context(tools, module.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, description: &str) -> McpToolSpec {
+        McpToolSpec { name: name.to_string(), description: Some(description.to_string()), input_schema: serde_json::json!({}) }
+    }
+
+    #[test]
+    fn ranks_module_tools_first_and_trims_when_asked() {
+        let tools = vec![
+            tool("list-files", "List a folder"),
+            tool("list-crm-companies", "Companies"),
+            tool("log-activity", "Log a touchpoint on a company"),
+            tool("search", "Search everything"),
+        ];
+        let mut crm = profile("crm", &["crm", "company"]);
+        crm.always = vec!["search".to_string()];
+
+        let ranked = rank_tools(tools.clone(), Some("crm"), &[crm.clone()]);
+        let names: Vec<&str> = ranked.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["search", "list-crm-companies", "log-activity", "list-files"]);
+        assert_eq!(ranked.trimmed, 0);
+
+        crm.trim = true;
+        let trimmed = rank_tools(tools.clone(), Some("crm"), &[crm]);
+        assert_eq!(trimmed.tools.len(), 3);
+        assert_eq!(trimmed.trimmed, 1);
+
+        let unknown = rank_tools(tools, Some("gallery"), &[]);
+        assert_eq!(unknown.tools.len(), 4);
+        assert!(unknown.prioritized.is_empty());
+    }
+}
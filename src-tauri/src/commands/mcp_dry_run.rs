@@ -0,0 +1,347 @@
+// MCP dry-run mode — a global switch that turns Work/CRM/VAL mutation tools
+// (create/update/delete and friends) into previews: the arguments are checked
+// against the tool's input schema and the call is described instead of run,
+// so automation prompts can be tried against production data safely.
+//
+// Only the REST bridge (mcp_bridge) enforces it, before forwarding a call.
+// Agent sessions talk to tv-mcp directly and are not covered. The switch
+// lives in ~/.tv-mcp/dry_run.json next to bridge.json.
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::mcp_bridge::tool_spec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tauri::command;
+
+/// Tool-name words that mark a call as a mutation
+const MUTATION_VERBS: [&str; 8] = ["create", "add", "update", "set", "delete", "remove", "archive", "upsert"];
+
+/// Module of a mutation tool whose name matches none of MODULE_KEYWORDS. These
+/// are previewed whenever dry-run is on, since nothing says they're safe.
+const UNKNOWN_MODULE: &str = "unknown";
+
+/// Tool-name words that place a tool in a module (checked in this order)
+const MODULE_KEYWORDS: [(&str, &[&str]); 3] = [
+    ("crm", &["crm", "company", "companies", "contact", "contacts", "activity", "activities", "deal", "deals"]),
+    ("val", &["val", "domain", "domains", "table", "workflow", "dashboard", "query", "sql"]),
+    ("work", &["task", "tasks", "project", "projects", "milestone", "initiative", "session", "label", "update"]),
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct McpDryRunConfig {
+    pub enabled: bool,
+    /// Modules whose mutations are previewed: any of work, crm, val
+    pub modules: Vec<String>,
+    pub updated_at: Option<String>,
+}
+
+impl Default for McpDryRunConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            modules: vec!["work".to_string(), "crm".to_string(), "val".to_string()],
+            updated_at: None,
+        }
+    }
+}
+
+/// How a tool name reads: `update-company` → crm / update / company
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpMutation {
+    pub module: String,
+    pub action: String,
+    pub entity: String,
+}
+
+/// What a mutation tool would have done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpDryRunPreview {
+    pub tool: String,
+    pub module: String,
+    pub action: String,
+    pub entity: String,
+    /// Arguments pass the tool's input schema
+    pub valid: bool,
+    pub errors: Vec<String>,
+    /// The arguments as they would have been sent
+    pub payload: Value,
+    /// Ids named in the arguments ("company_id=…"), i.e. the rows touched
+    pub affected_ids: Vec<String>,
+    pub summary: String,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-mcp")
+        .join("dry_run.json")
+}
+
+pub fn load_config() -> McpDryRunConfig {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Classification + validation
+// ============================================================================
+
+/// First module in MODULE_KEYWORDS with a word among `tokens`
+fn module_for(tokens: &[&str]) -> Option<&'static str> {
+    MODULE_KEYWORDS
+        .iter()
+        .find(|(_, words)| tokens.iter().any(|t| words.contains(t)))
+        .map(|(m, _)| *m)
+}
+
+/// Whether a tool, mutation or read, works on Work data (tasks, projects, ...)
+pub fn is_work_tool(name: &str) -> bool {
+    match classify_tool(name) {
+        Some(mutation) => mutation.module == "work",
+        None => {
+            let lower = name.to_lowercase();
+            let tokens: Vec<&str> = lower.split(['-', '_']).filter(|t| !t.is_empty()).collect();
+            module_for(&tokens) == Some("work")
+        }
+    }
+}
+
+/// Read a tool name as a mutation, placed in work/crm/val or UNKNOWN_MODULE;
+/// None when it has no mutation verb
+pub fn classify_tool(name: &str) -> Option<McpMutation> {
+    let lower = name.to_lowercase();
+    let tokens: Vec<&str> = lower.split(['-', '_']).filter(|t| !t.is_empty()).collect();
+    let verb_at = tokens.iter().position(|t| MUTATION_VERBS.contains(t))?;
+
+    // The verb itself isn't evidence of a module ("update" is also a work entity)
+    let others: Vec<&str> = tokens.iter().enumerate().filter(|(i, _)| *i != verb_at).map(|(_, t)| *t).collect();
+    let module = module_for(&others).unwrap_or(UNKNOWN_MODULE).to_string();
+
+    Some(McpMutation {
+        module,
+        action: tokens[verb_at].to_string(),
+        entity: tokens[verb_at + 1..].join("-"),
+    })
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Check arguments against the top level of a JSON Schema: required fields,
+/// property types, enums and (when closed) unknown fields
+pub fn validate_arguments(schema: &Value, args: &Value) -> Vec<String> {
+    let Some(args) = args.as_object() else {
+        return vec!["Arguments must be a JSON object".to_string()];
+    };
+    let mut errors = Vec::new();
+
+    for field in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+        if args.get(field).map(Value::is_null).unwrap_or(true) {
+            errors.push(format!("Missing required field '{}'", field));
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+    for (key, value) in args {
+        let Some(prop) = properties.and_then(|p| p.get(key)) else {
+            if closed {
+                errors.push(format!("Unknown field '{}'", key));
+            }
+            continue;
+        };
+        let types: Vec<&str> = match prop.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| type_matches(t, value)) {
+            errors.push(format!("Field '{}' should be {}", key, types.join(" or ")));
+            continue;
+        }
+        if let Some(options) = prop.get("enum").and_then(Value::as_array) {
+            if !options.contains(value) {
+                errors.push(format!("Field '{}' must be one of {}", key, Value::Array(options.clone())));
+            }
+        }
+    }
+    errors
+}
+
+/// `key=value` for every id-like argument (id, company_id, taskIds, …)
+fn affected_ids(args: &Value) -> Vec<String> {
+    let Some(args) = args.as_object() else {
+        return Vec::new();
+    };
+    let mut ids = Vec::new();
+    for (key, value) in args {
+        let k = key.to_lowercase();
+        if !(k == "id" || k == "ids" || k.ends_with("_id") || k.ends_with("_ids") || key.ends_with("Id") || key.ends_with("Ids")) {
+            continue;
+        }
+        let values: Vec<&Value> = match value {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        for v in values {
+            match v {
+                Value::String(s) if !s.is_empty() => ids.push(format!("{}={}", key, s)),
+                Value::Number(n) => ids.push(format!("{}={}", key, n)),
+                _ => {}
+            }
+        }
+    }
+    ids
+}
+
+/// Describe a mutation call without running it
+pub fn build_preview(tool: &str, mutation: &McpMutation, schema: &Value, args: &Value) -> McpDryRunPreview {
+    let errors = validate_arguments(schema, args);
+    let affected = affected_ids(args);
+    let target = if affected.is_empty() { String::new() } else { format!(" ({})", affected.join(", ")) };
+    let summary = if errors.is_empty() {
+        format!("Dry run: would {} {}{}. Nothing was saved.", mutation.action, mutation.entity, target)
+    } else {
+        format!("Dry run: {} {} would be rejected: {}", mutation.action, mutation.entity, errors.join("; "))
+    };
+
+    McpDryRunPreview {
+        tool: tool.to_string(),
+        module: mutation.module.clone(),
+        action: mutation.action.clone(),
+        entity: mutation.entity.clone(),
+        valid: errors.is_empty(),
+        errors,
+        payload: args.clone(),
+        affected_ids: affected,
+        summary,
+    }
+}
+
+/// Bridge hook: when dry-run covers `tool`, an MCP-shaped tool result that
+/// describes the call instead of making it; None means forward as usual.
+/// Blocking (fetches the tool's schema from tv-mcp).
+pub fn intercept(tool: &str, args: &Value) -> CmdResult<Option<Value>> {
+    let config = load_config();
+    if !config.enabled {
+        return Ok(None);
+    }
+    let Some(mutation) = classify_tool(tool).filter(|m| m.module == UNKNOWN_MODULE || config.modules.contains(&m.module))
+    else {
+        return Ok(None);
+    };
+    let schema = tool_spec(tool)?.map(|t| t.input_schema).unwrap_or_default();
+    let preview = build_preview(tool, &mutation, &schema, args);
+    Ok(Some(serde_json::json!({
+        "content": [{ "type": "text", "text": preview.summary }],
+        "isError": !preview.valid,
+        "dryRun": preview,
+    })))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[command]
+pub async fn mcp_dry_run_get_config() -> CmdResult<McpDryRunConfig> {
+    Ok(load_config())
+}
+
+#[command]
+pub async fn mcp_dry_run_save_config(config: McpDryRunConfig) -> CmdResult<McpDryRunConfig> {
+    if let Some(unknown) = config.modules.iter().find(|m| !MODULE_KEYWORDS.iter().any(|(k, _)| k == m)) {
+        return Err(CommandError::Validation {
+            message: format!("Unknown module '{}' (expected work, crm or val)", unknown),
+            fields: vec!["modules".to_string()],
+        });
+    }
+    let config = McpDryRunConfig { updated_at: Some(chrono::Utc::now().to_rfc3339()), ..config };
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&config)?)?;
+    Ok(config)
+}
+
+/// Preview a tool call as dry-run would, whether or not the switch is on
+#[command]
+pub async fn mcp_dry_run_preview(tool: String, arguments: Value) -> CmdResult<McpDryRunPreview> {
+    let Some(mutation) = classify_tool(&tool) else {
+        return Err(CommandError::Validation {
+            message: format!("{} isn't a mutation; it runs the same with dry-run on", tool),
+            fields: vec!["tool".to_string()],
+        });
+    };
+    tauri::async_runtime::spawn_blocking(move || -> CmdResult<McpDryRunPreview> {
+        let spec = tool_spec(&tool)?.ok_or_else(|| CommandError::NotFound(format!("tv-mcp has no tool named {}", tool)))?;
+        Ok(build_preview(&tool, &mutation, &spec.input_schema, &arguments))
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Task error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_and_previews_mutations() {
+        let m = |module: &str, action: &str, entity: &str| {
+            Some(McpMutation { module: module.into(), action: action.into(), entity: entity.into() })
+        };
+        assert_eq!(classify_tool("update-company"), m("crm", "update", "company"));
+        assert_eq!(classify_tool("add-activity"), m("crm", "add", "activity"));
+        assert_eq!(classify_tool("create-project-update"), m("work", "create", "project-update"));
+        assert_eq!(classify_tool("delete_val_dashboard"), m("val", "delete", "val-dashboard"));
+        assert_eq!(classify_tool("list-tasks"), None);
+        // No module keyword: still previewed rather than run
+        assert_eq!(classify_tool("create-draft"), m("unknown", "create", "draft"));
+
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "stage"],
+            "properties": {
+                "id": { "type": "string" },
+                "stage": { "type": "string", "enum": ["lead", "won"] },
+                "value": { "type": ["number", "null"] }
+            },
+            "additionalProperties": false
+        });
+        let mutation = classify_tool("update-company").unwrap();
+
+        let ok = build_preview("update-company", &mutation, &schema, &json!({ "id": "c1", "stage": "won", "value": 5 }));
+        assert!(ok.valid);
+        assert_eq!(ok.affected_ids, vec!["id=c1"]);
+        assert!(ok.summary.contains("would update company (id=c1)"));
+
+        let bad = build_preview("update-company", &mutation, &schema, &json!({ "stage": "maybe", "value": "x", "extra": 1 }));
+        assert!(!bad.valid);
+        assert_eq!(bad.errors.len(), 4, "{:?}", bad.errors);
+    }
+}
@@ -2,21 +2,32 @@
 
 pub mod error;
 pub mod agent_runner;
+pub mod ai;
 pub mod analytics;
 pub mod auth;
+pub mod backup;
 pub mod claude_setup;
+pub mod connection_test;
+pub mod diagnostics;
 pub mod crm;
 pub mod files;
 pub mod folder_chat;
 pub mod github_sync;
 pub mod help_chat;
+pub mod links;
 pub mod outlook;
+pub mod palette;
 pub mod scheduler;
 pub mod search;
+pub mod secret_scan;
 pub mod settings;
 pub mod supabase;
 pub mod terminal;
+pub mod http_util;
 pub mod mcp_tools;
+pub mod mcp_bridge;
+pub mod mcp_context;
+pub mod mcp_dry_run;
 pub mod tools;
 pub mod val_sync;
 pub mod skill_registry;
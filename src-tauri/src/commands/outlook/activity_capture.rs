@@ -0,0 +1,602 @@
+// CRM activity auto-capture — logs meetings and email exchanges from the
+// local Outlook cache as crm_activities, so company history reflects what
+// actually happened without anyone having to log it. Participants are matched
+// to companies via CRM contacts, then company website domain. Each captured
+// item carries a source_ref and is skipped when it (or a manual log of the
+// same meeting/thread) already exists. Runs hourly when enabled.
+
+use super::db::EmailDb;
+use super::types::*;
+use crate::commands::crm::{extract_domain, Activity, Company, Contact, FREE_EMAIL_DOMAINS};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, like_literal, SupabaseClient};
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::Emitter;
+
+const STATE_CONFIG: &str = "activity_capture_config";
+const STATE_LAST_RUN: &str = "activity_capture_last_run";
+const SOURCE: &str = "auto_capture";
+const RUN_INTERVAL_SECS: u64 = 3600;
+/// Upper bound on cached emails/events scanned per run
+const SCAN_LIMIT: i64 = 5000;
+
+/// Something worth logging, before company matching
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    /// "meeting" or "email" (also the crm_activities type)
+    pub kind: &'static str,
+    pub source_ref: String,
+    pub subject: String,
+    pub content: String,
+    pub activity_date: String,
+    /// Latest message of an exchange
+    pub email_id: Option<String>,
+    /// All message IDs of an exchange (to spot manually linked emails)
+    pub message_ids: Vec<String>,
+    /// External participant addresses, lowercase
+    pub participants: Vec<String>,
+}
+
+struct CompanyMatch {
+    company_id: String,
+    company_name: String,
+    contact_id: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn load_config(db: &EmailDb) -> ActivityCaptureConfig {
+    db.get_sync_state(STATE_CONFIG)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Graph event times are naive UTC ("2026-10-16T09:00:00.0000000")
+fn parse_event_time(ts: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(ts.get(..19)?, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+/// "Re: Fwd: Kick-off" → "kick-off"
+fn normalize_subject(subject: &str) -> String {
+    let mut s = subject.trim();
+    loop {
+        let lower = s.to_lowercase();
+        let Some(prefix) = ["re:", "fw:", "fwd:", "aw:"].iter().find(|p| lower.starts_with(*p)) else {
+            break;
+        };
+        s = s[prefix.len()..].trim_start();
+    }
+    s.to_lowercase()
+}
+
+/// Host of a company website: "https://www.Acme.com/about" → "acme.com"
+fn website_host(website: &str) -> String {
+    let w = website.trim().to_lowercase();
+    let w = w.split_once("://").map_or(w.as_str(), |(_, rest)| rest);
+    let host = w.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.split(':').next().unwrap_or("");
+    host.trim_start_matches("www.").to_string()
+}
+
+fn is_excluded(address: &str, excluded: &HashSet<String>) -> bool {
+    match extract_domain(address) {
+        Some(domain) => excluded.contains(&domain),
+        None => true,
+    }
+}
+
+/// Meetings that ended in the window, long enough and with an outside attendee
+pub fn meeting_candidates(
+    events: &[CalendarEvent],
+    excluded: &HashSet<String>,
+    config: &ActivityCaptureConfig,
+    now: NaiveDateTime,
+) -> Vec<Candidate> {
+    events
+        .iter()
+        .filter(|e| !e.is_cancelled && !e.is_all_day)
+        .filter_map(|e| {
+            let start = parse_event_time(&e.start_at)?;
+            let end = parse_event_time(&e.end_at)?;
+            let minutes = (end - start).num_minutes();
+            if end > now || minutes < config.min_meeting_minutes {
+                return None;
+            }
+            let mut participants: Vec<String> = e
+                .attendees
+                .iter()
+                .map(|a| a.email.to_lowercase())
+                .chain(std::iter::once(e.organizer_email.to_lowercase()))
+                .filter(|a| !is_excluded(a, excluded))
+                .collect();
+            participants.sort();
+            participants.dedup();
+            if participants.is_empty() {
+                return None;
+            }
+            let names: Vec<&str> = e
+                .attendees
+                .iter()
+                .map(|a| if a.name.is_empty() { a.email.as_str() } else { a.name.as_str() })
+                .collect();
+            Some(Candidate {
+                kind: "meeting",
+                source_ref: format!("meeting:{}", e.id),
+                subject: if e.subject.is_empty() { "Meeting".to_string() } else { e.subject.clone() },
+                content: format!("{} min · Attendees: {}", minutes, names.join(", ")),
+                activity_date: format!("{}Z", start.format("%Y-%m-%dT%H:%M:%S")),
+                email_id: None,
+                message_ids: Vec::new(),
+                participants,
+            })
+        })
+        .collect()
+}
+
+/// Conversations with enough back-and-forth: at least `min_exchange_messages`
+/// messages, including one sent and one received
+pub fn exchange_candidates(
+    emails: &[EmailEntry],
+    excluded: &HashSet<String>,
+    config: &ActivityCaptureConfig,
+) -> Vec<Candidate> {
+    let mut threads: BTreeMap<&str, Vec<&EmailEntry>> = BTreeMap::new();
+    for email in emails {
+        if let Some(cid) = email.conversation_id.as_deref() {
+            threads.entry(cid).or_default().push(email);
+        }
+    }
+
+    threads
+        .into_iter()
+        .filter_map(|(cid, mut messages)| {
+            messages.sort_by(|a, b| a.received_at.cmp(&b.received_at));
+            let sent = messages.iter().filter(|m| m.folder_name == "Sent Items").count();
+            let received = messages.len() - sent;
+            if messages.len() < config.min_exchange_messages || sent == 0 || received == 0 {
+                return None;
+            }
+            let mut participants: Vec<String> = messages
+                .iter()
+                .flat_map(|m| {
+                    std::iter::once(m.from_email.clone())
+                        .chain(m.to_addresses.iter().map(|a| a.email.clone()))
+                        .chain(m.cc_addresses.iter().map(|a| a.email.clone()))
+                })
+                .map(|a| a.to_lowercase())
+                .filter(|a| !is_excluded(a, excluded))
+                .collect();
+            participants.sort();
+            participants.dedup();
+            if participants.is_empty() {
+                return None;
+            }
+            let first = messages.first()?;
+            let last = messages.last()?;
+            Some(Candidate {
+                kind: "email",
+                source_ref: format!("email:{}", cid),
+                subject: first.subject.clone(),
+                content: format!(
+                    "{} messages ({} sent, {} received), {} – {}",
+                    messages.len(),
+                    sent,
+                    received,
+                    first.received_at.get(..10).unwrap_or(&first.received_at),
+                    last.received_at.get(..10).unwrap_or(&last.received_at),
+                ),
+                activity_date: last.received_at.clone(),
+                email_id: Some(last.id.clone()),
+                message_ids: messages.iter().map(|m| m.id.clone()).collect(),
+                participants,
+            })
+        })
+        .collect()
+}
+
+/// Already captured, or already logged by hand: an activity of the same type
+/// with the same subject that day, a meeting starting the same minute, or an
+/// email activity for one of the thread's messages
+pub fn is_duplicate(candidate: &Candidate, existing: &[Activity]) -> bool {
+    let day = candidate.activity_date.get(..10).unwrap_or("");
+    let start = candidate.activity_date.get(..16).unwrap_or("");
+    let subject = normalize_subject(&candidate.subject);
+    existing.iter().any(|a| {
+        if a.source_ref.as_deref() == Some(candidate.source_ref.as_str()) {
+            return true;
+        }
+        if a.activity_type != candidate.kind {
+            return false;
+        }
+        let same_day = a.activity_date.as_deref().and_then(|d| d.get(..10)) == Some(day);
+        if same_day && a.subject.as_deref().map(normalize_subject).as_deref() == Some(subject.as_str()) {
+            return true;
+        }
+        match candidate.kind {
+            "meeting" => a.activity_date.as_deref().and_then(|d| d.get(..16)) == Some(start),
+            _ => a.email_id.as_ref().is_some_and(|id| candidate.message_ids.contains(id)),
+        }
+    })
+}
+
+/// Resolves participant addresses to CRM companies, caching lookups across candidates
+struct CompanyResolver<'a> {
+    client: &'a SupabaseClient,
+    contacts: HashMap<String, Option<Contact>>,
+    domains: HashMap<String, Option<Company>>,
+    names: HashMap<String, String>,
+}
+
+impl<'a> CompanyResolver<'a> {
+    fn new(client: &'a SupabaseClient) -> Self {
+        Self {
+            client,
+            contacts: HashMap::new(),
+            domains: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    async fn company_name(&mut self, company_id: &str) -> CmdResult<String> {
+        if let Some(name) = self.names.get(company_id) {
+            return Ok(name.clone());
+        }
+        let company: Option<Company> = self
+            .client
+            .select_single("crm_companies", &format!("id=eq.{}", company_id))
+            .await?;
+        let name = company
+            .map(|c| c.display_name.unwrap_or(c.name))
+            .unwrap_or_else(|| company_id.to_string());
+        self.names.insert(company_id.to_string(), name.clone());
+        Ok(name)
+    }
+
+    /// Contact email match first, then company website domain
+    async fn resolve(&mut self, participants: &[String]) -> CmdResult<Option<CompanyMatch>> {
+        let unknown: Vec<String> = participants
+            .iter()
+            .filter(|p| !self.contacts.contains_key(*p))
+            .map(|p| format!("\"{}\"", p))
+            .collect();
+        if !unknown.is_empty() {
+            let found: Vec<Contact> = self
+                .client
+                .select(
+                    "crm_contacts",
+                    &format!("email=in.({})", urlencoding::encode(&unknown.join(","))),
+                )
+                .await?;
+            for p in participants {
+                self.contacts.entry(p.clone()).or_insert(None);
+            }
+            for c in found {
+                self.contacts.insert(c.email.to_lowercase(), Some(c));
+            }
+        }
+        for p in participants {
+            if let Some(Some(contact)) = self.contacts.get(p) {
+                if let Some(company_id) = contact.company_id.clone() {
+                    let contact_id = Some(contact.id.clone());
+                    let company_name = self.company_name(&company_id).await?;
+                    return Ok(Some(CompanyMatch { company_id, company_name, contact_id }));
+                }
+            }
+        }
+
+        for p in participants {
+            let Some(domain) = extract_domain(p) else {
+                continue;
+            };
+            if FREE_EMAIL_DOMAINS.contains(&domain.as_str()) {
+                continue;
+            }
+            if !self.domains.contains_key(&domain) {
+                // ilike narrows the candidates; the host must then match exactly,
+                // so "acme.com" doesn't pick up "notacme.com" or "acme.com.sg"
+                let companies: Vec<Company> = self
+                    .client
+                    .select("crm_companies", &format!("website=ilike.*{}*&limit=20", like_literal(&domain)))
+                    .await?;
+                let company = companies
+                    .into_iter()
+                    .find(|c| c.website.as_deref().is_some_and(|w| website_host(w) == domain.trim_start_matches("www.")));
+                self.domains.insert(domain.clone(), company);
+            }
+            if let Some(Some(company)) = self.domains.get(&domain) {
+                return Ok(Some(CompanyMatch {
+                    company_id: company.id.clone(),
+                    company_name: company.display_name.clone().unwrap_or_else(|| company.name.clone()),
+                    contact_id: None,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// ============================================================================
+// Run
+// ============================================================================
+
+async fn run_capture(config: &ActivityCaptureConfig, dry_run: bool) -> CmdResult<ActivityCaptureResult> {
+    let db = EmailDb::open()?;
+    let now = Utc::now();
+    let since = now - Duration::days(config.lookback_days.max(1));
+    let since_str = since.format("%Y-%m-%dT%H:%M:%S").to_string();
+    let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let emails = db.list_between(&since_str, &format!("{}Z", now_str), SCAN_LIMIT)?;
+
+    // Own domains (from sent mail) plus configured exclusions
+    let mut excluded: HashSet<String> = config
+        .excluded_domains
+        .iter()
+        .map(|d| d.trim().trim_start_matches('@').to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect();
+    excluded.extend(
+        emails
+            .iter()
+            .filter(|e| e.folder_name == "Sent Items")
+            .filter_map(|e| extract_domain(&e.from_email)),
+    );
+
+    let mut candidates = Vec::new();
+    if config.capture_meetings {
+        let events = db.list_events(&since_str, &now_str, SCAN_LIMIT)?;
+        candidates.extend(meeting_candidates(&events, &excluded, config, now.naive_utc()));
+    }
+    if config.capture_emails {
+        candidates.extend(exchange_candidates(&emails, &excluded, config));
+    }
+
+    let mut result = ActivityCaptureResult {
+        dry_run,
+        ..Default::default()
+    };
+    if candidates.is_empty() {
+        return Ok(result);
+    }
+
+    let client = get_client().await?;
+    let mut resolver = CompanyResolver::new(&client);
+    let mut matched: Vec<(Candidate, CompanyMatch)> = Vec::new();
+    for candidate in candidates {
+        match resolver.resolve(&candidate.participants).await? {
+            Some(company) => matched.push((candidate, company)),
+            None => result.unmatched += 1,
+        }
+    }
+    if matched.is_empty() {
+        return Ok(result);
+    }
+
+    // Everything already logged for these companies in the window, manual or captured
+    let company_ids: HashSet<&str> = matched.iter().map(|(_, m)| m.company_id.as_str()).collect();
+    let existing: Vec<Activity> = client
+        .select(
+            "crm_activities",
+            &format!(
+                "company_id=in.({})&activity_date=gte.{}",
+                company_ids.into_iter().collect::<Vec<_>>().join(","),
+                (since - Duration::days(1)).format("%Y-%m-%d")
+            ),
+        )
+        .await?;
+    let mut by_company: HashMap<String, Vec<Activity>> = HashMap::new();
+    for a in existing {
+        if let Some(cid) = a.company_id.clone() {
+            by_company.entry(cid).or_default().push(a);
+        }
+    }
+
+    for (candidate, company) in matched {
+        let existing = by_company.entry(company.company_id.clone()).or_default();
+        let status = if is_duplicate(&candidate, existing) {
+            result.duplicates += 1;
+            "duplicate"
+        } else if dry_run {
+            "preview"
+        } else {
+            let logged: Activity = client
+                .insert(
+                    "crm_activities",
+                    &serde_json::json!({
+                        "company_id": company.company_id,
+                        "contact_id": company.contact_id,
+                        "type": candidate.kind,
+                        "subject": candidate.subject,
+                        "content": candidate.content,
+                        "activity_date": candidate.activity_date,
+                        "email_id": candidate.email_id,
+                        "source": SOURCE,
+                        "source_ref": candidate.source_ref,
+                    }),
+                )
+                .await?;
+            existing.push(logged);
+            if candidate.kind == "meeting" {
+                result.meetings_logged += 1;
+            } else {
+                result.emails_logged += 1;
+            }
+            "logged"
+        };
+        result.activities.push(CapturedActivity {
+            kind: candidate.kind.to_string(),
+            company_id: company.company_id,
+            company_name: company.company_name,
+            subject: candidate.subject,
+            activity_date: candidate.activity_date,
+            source_ref: candidate.source_ref,
+            status: status.to_string(),
+        });
+    }
+
+    if !dry_run {
+        let _ = db.set_sync_state(STATE_LAST_RUN, &now.to_rfc3339());
+    }
+    Ok(result)
+}
+
+/// Start the hourly capture loop. Call from main.rs setup hook (no-op unless enabled).
+pub fn start_activity_capture(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+        loop {
+            let config = EmailDb::open().ok().map(|db| load_config(&db));
+            if let Some(config) = config.filter(|c| c.enabled) {
+                let job_id = format!("activity-capture-{}", Utc::now().timestamp_millis());
+                let started_at = Utc::now().to_rfc3339();
+                let name = "CRM activity capture";
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &job_id, "name": name, "status": "running",
+                    "message": "Capturing meetings and email exchanges", "startedAt": &started_at,
+                }));
+                let (status, message) = match run_capture(&config, false).await {
+                    Ok(r) => (
+                        "completed",
+                        format!(
+                            "{} meetings, {} email exchanges logged ({} already logged)",
+                            r.meetings_logged, r.emails_logged, r.duplicates
+                        ),
+                    ),
+                    Err(e) => {
+                        eprintln!("[outlook:capture] Failed: {}", e);
+                        ("failed", format!("Activity capture failed: {}", e))
+                    }
+                };
+                let _ = app_handle.emit("jobs:update", serde_json::json!({
+                    "id": &job_id, "name": name, "status": status,
+                    "message": message, "startedAt": &started_at,
+                }));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(RUN_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Run the capture now. `dry_run` previews what would be logged without writing.
+#[tauri::command]
+pub async fn outlook_activity_capture_run(dry_run: Option<bool>) -> CmdResult<ActivityCaptureResult> {
+    let db = EmailDb::open()?;
+    let config = load_config(&db);
+    run_capture(&config, dry_run.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn outlook_activity_capture_get_config() -> CmdResult<ActivityCaptureConfig> {
+    let db = EmailDb::open()?;
+    Ok(load_config(&db))
+}
+
+#[tauri::command]
+pub async fn outlook_activity_capture_save_config(config: ActivityCaptureConfig) -> CmdResult<ActivityCaptureConfig> {
+    if config.lookback_days < 1 || config.lookback_days > 90 {
+        return Err(CommandError::Config("lookbackDays must be between 1 and 90".to_string()));
+    }
+    let db = EmailDb::open()?;
+    db.set_sync_state(STATE_CONFIG, &serde_json::to_string(&config)?)?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(id: &str, folder: &str, from: &str, to: &str, at: &str, subject: &str) -> EmailEntry {
+        EmailEntry {
+            id: id.to_string(),
+            conversation_id: Some("conv-1".to_string()),
+            subject: subject.to_string(),
+            from_name: String::new(),
+            from_email: from.to_string(),
+            to_addresses: vec![EmailAddress { name: String::new(), email: to.to_string() }],
+            cc_addresses: Vec::new(),
+            received_at: at.to_string(),
+            folder_name: folder.to_string(),
+            importance: "normal".to_string(),
+            is_read: true,
+            has_attachments: false,
+            body_preview: String::new(),
+            body_path: None,
+            category: "client".to_string(),
+            priority_score: 0,
+            priority_level: "normal".to_string(),
+            ai_summary: None,
+            action_required: false,
+            status: "read".to_string(),
+            linked_company_id: None,
+            linked_company_name: None,
+        }
+    }
+
+    #[test]
+    fn captures_two_way_exchanges_and_skips_manual_logs() {
+        let excluded: HashSet<String> = ["us.com".to_string()].into_iter().collect();
+        let config = ActivityCaptureConfig::default();
+        let emails = vec![
+            email("m1", "Inbox", "ann@acme.com", "me@us.com", "2026-10-12T09:00:00Z", "Pilot scope"),
+            email("m2", "Sent Items", "me@us.com", "ann@acme.com", "2026-10-12T11:00:00Z", "RE: Pilot scope"),
+            email("m3", "Inbox", "ann@acme.com", "me@us.com", "2026-10-13T08:00:00Z", "RE: Pilot scope"),
+        ];
+
+        let found = exchange_candidates(&emails, &excluded, &config);
+        assert_eq!(found.len(), 1);
+        let c = &found[0];
+        assert_eq!(c.source_ref, "email:conv-1");
+        assert_eq!(c.participants, vec!["ann@acme.com"]);
+        assert_eq!(c.email_id.as_deref(), Some("m3"));
+
+        // One-directional threads don't count
+        assert!(exchange_candidates(&emails[..1], &excluded, &config).is_empty());
+
+        let manual: Activity = serde_json::from_value(serde_json::json!({
+            "id": "a1", "company_id": "c1", "type": "email",
+            "subject": "Re: pilot scope", "activity_date": "2026-10-13T10:00:00Z"
+        }))
+        .unwrap();
+        assert!(is_duplicate(c, &[manual.clone()]));
+        let other_day = Activity { activity_date: Some("2026-10-01T10:00:00Z".to_string()), ..manual };
+        assert!(!is_duplicate(c, &[other_day]));
+    }
+
+    #[test]
+    fn meetings_match_on_subject_or_start_time_and_websites_on_host() {
+        let meeting = Candidate {
+            kind: "meeting",
+            source_ref: "event:e1".to_string(),
+            subject: "Pilot kick-off".to_string(),
+            content: String::new(),
+            activity_date: "2026-10-13T09:00:00".to_string(),
+            email_id: None,
+            message_ids: Vec::new(),
+            participants: vec!["ann@acme.com".to_string()],
+        };
+        let logged: Activity = serde_json::from_value(serde_json::json!({
+            "id": "a1", "company_id": "c1", "type": "meeting",
+            "subject": "Weekly sync", "activity_date": "2026-10-13T15:00:00Z"
+        }))
+        .unwrap();
+        // Another meeting that day isn't this one
+        assert!(!is_duplicate(&meeting, &[logged.clone()]));
+        let same_start = Activity { activity_date: Some("2026-10-13T09:00:00Z".to_string()), ..logged.clone() };
+        assert!(is_duplicate(&meeting, &[same_start]));
+        let same_subject = Activity { subject: Some("pilot kick-off".to_string()), ..logged };
+        assert!(is_duplicate(&meeting, &[same_subject]));
+
+        assert_eq!(website_host("https://www.Acme.com/about"), "acme.com");
+        assert_eq!(website_host("acme.com:8080?x=1"), "acme.com");
+        assert_ne!(website_host("http://notacme.com"), "acme.com");
+    }
+}
@@ -1,6 +1,8 @@
 // Outlook OAuth2 authentication via Azure AD
 // Uses local callback server on port 3847 (matching Azure AD app registration)
 
+use super::db::EmailDb;
+use super::diagnostics::RunTimer;
 use super::types::{GraphTokenResponse, GraphUserProfile, OutlookAuthStatus, OutlookTokens};
 use crate::commands::error::{CmdResult, CommandError};
 use crate::commands::settings;
@@ -67,7 +69,12 @@ pub async fn get_valid_token() -> CmdResult<String> {
         let refresh_token = tokens.refresh_token
             .ok_or_else(|| CommandError::Internal("No refresh token available. Please re-authenticate.".to_string()))?;
 
-        let new_tokens = refresh_access_token(&refresh_token).await?;
+        let timer = RunTimer::start("token_refresh", "auth");
+        let result = refresh_access_token(&refresh_token).await;
+        if let Ok(db) = EmailDb::open() {
+            timer.record(&db, 0, result.as_ref().err());
+        }
+        let new_tokens = result?;
         save_tokens(&new_tokens)?;
         return Ok(new_tokens.access_token);
     }
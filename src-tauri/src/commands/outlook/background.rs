@@ -2,6 +2,7 @@
 // Starts 10s after app launch, runs incremental delta sync every 5 minutes
 
 use super::db::EmailDb;
+use super::diagnostics::RunTimer;
 use super::sync;
 use tauri::Emitter;
 
@@ -51,7 +52,10 @@ pub fn start_background_sync(app_handle: tauri::AppHandle) {
                     let started_at = chrono::Utc::now().to_rfc3339();
                     emit_job(&app_handle, &job_id, "Outlook Email Sync", "running", "Syncing emails...", &started_at);
 
-                    match sync::run_incremental_sync(&db, &app_handle).await {
+                    let timer = RunTimer::start("email_incremental", "background");
+                    let result = sync::run_incremental_sync(&db, &app_handle).await;
+                    timer.finish(&db, &result);
+                    match result {
                         Ok(count) => {
                             let msg = format!("{} new emails", count);
                             emit_job(&app_handle, &job_id, "Outlook Email Sync", "completed", &msg, &started_at);
@@ -78,7 +82,10 @@ pub fn start_background_sync(app_handle: tauri::AppHandle) {
                         let cal_started = chrono::Utc::now().to_rfc3339();
                         emit_job(&app_handle, &cal_job_id, "Outlook Calendar Sync", "running", "Syncing events...", &cal_started);
 
-                        match sync::run_calendar_sync(&db, &app_handle, 1).await {
+                        let timer = RunTimer::start("calendar", "background");
+                        let result = sync::run_calendar_sync(&db, &app_handle, 1).await;
+                        timer.finish(&db, &result);
+                        match result {
                             Ok(count) => {
                                 emit_job(&app_handle, &cal_job_id, "Outlook Calendar Sync", "completed", &format!("{} events", count), &cal_started);
                                 eprintln!("[outlook:bg] Calendar sync done: {} events", count);
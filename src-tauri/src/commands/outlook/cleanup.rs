@@ -0,0 +1,262 @@
+// Local cache cleanup — retention for cached bodies and inline attachments.
+// Headers in emails.db are kept forever; dropped bodies re-fetch lazily when
+// opened. Runs on its own cron (UTC) and reports through the same
+// "jobs:update" events as scheduled automations.
+
+use super::db::EmailDb;
+use super::sync::get_bodies_dir;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::scheduler::background::is_due;
+use chrono::Local;
+use cron::Schedule;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tauri::Emitter;
+
+const STATE_POLICY: &str = "cleanup_policy";
+const STATE_LAST_RUN: &str = "cleanup_last_run";
+/// Unreferenced body files younger than this may still be mid-write
+const ORPHAN_GRACE_SECS: u64 = 60 * 60;
+/// 1x1 transparent GIF swapped in for stripped inline images
+const PLACEHOLDER_IMAGE: &str = "data:image/gif;base64,R0lGODlhAQABAIAAAAAAAP///yH5BAEAAAAALAAAAAABAAEAAAIBRAA7";
+
+static DATA_URI_RE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"data:[A-Za-z0-9.+/-]+;base64,[A-Za-z0-9+/=]+").unwrap());
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn load_policy(db: &EmailDb) -> CleanupPolicy {
+    db.get_sync_state(STATE_POLICY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Replace inline attachments whose encoded size exceeds `max_bytes`.
+/// Returns the new HTML, how many were stripped, and the bytes saved.
+fn strip_large_attachments(html: &str, max_bytes: u64) -> (String, usize, u64) {
+    let mut count = 0;
+    let mut saved = 0u64;
+    let out = DATA_URI_RE.replace_all(html, |caps: &regex::Captures| {
+        let uri = &caps[0];
+        // base64 inflates by 4/3; compare against the decoded size
+        if uri != PLACEHOLDER_IMAGE && (uri.len() as u64) * 3 / 4 > max_bytes {
+            count += 1;
+            saved += (uri.len() - PLACEHOLDER_IMAGE.len()) as u64;
+            PLACEHOLDER_IMAGE.to_string()
+        } else {
+            uri.to_string()
+        }
+    });
+    (out.into_owned(), count, saved)
+}
+
+/// Apply (or with `dry_run`, measure) the policy against the cache
+fn run_cleanup(db: &EmailDb, policy: &CleanupPolicy, dry_run: bool) -> CmdResult<CleanupReport> {
+    let mut report = CleanupReport { dry_run, ..Default::default() };
+    let cutoff = if policy.keep_body_months > 0 {
+        chrono::Utc::now()
+            .checked_sub_months(chrono::Months::new(policy.keep_body_months))
+            .map(|d| d.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+    } else {
+        None
+    };
+
+    let cached = db.list_cached_bodies()?;
+    let referenced: HashSet<PathBuf> = cached.iter().map(|(_, path, _)| PathBuf::from(path)).collect();
+
+    // Bodies past retention
+    let mut expired = Vec::new();
+    for (id, path, received_at) in &cached {
+        let path = Path::new(path);
+        let is_expired = cutoff.as_deref().map(|c| received_at.as_str() < c).unwrap_or(false);
+        if is_expired {
+            report.bodies_removed += 1;
+            report.body_bytes += file_size(path);
+            expired.push(id.clone());
+            if !dry_run {
+                let _ = fs::remove_file(path);
+            }
+            continue;
+        }
+
+        // Large inline attachments in bodies we keep
+        if let Some(max_kb) = policy.max_attachment_kb {
+            let Ok(html) = fs::read_to_string(path) else { continue };
+            let (stripped, count, saved) = strip_large_attachments(&html, max_kb * 1024);
+            if count > 0 {
+                report.attachments_purged += count;
+                report.attachment_bytes += saved;
+                if !dry_run {
+                    fs::write(path, stripped)?;
+                }
+            }
+        }
+    }
+    if !dry_run && !expired.is_empty() {
+        db.clear_body_paths(&expired)?;
+    }
+
+    // Body files nothing points at any more (e.g. mail deleted upstream)
+    if let Ok(entries) = fs::read_dir(get_bodies_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if referenced.contains(&path) || path.extension().and_then(|e| e.to_str()) != Some("html") {
+                continue;
+            }
+            let old_enough = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .map(|age| age.as_secs() > ORPHAN_GRACE_SECS)
+                .unwrap_or(false);
+            if !old_enough {
+                continue;
+            }
+            report.orphans_removed += 1;
+            report.orphan_bytes += file_size(&path);
+            if !dry_run {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+
+    if policy.vacuum {
+        report.database_bytes = db.free_page_bytes()?;
+        if !dry_run {
+            db.vacuum()?;
+        }
+    }
+
+    report.total_bytes = report.body_bytes + report.orphan_bytes + report.attachment_bytes + report.database_bytes;
+    Ok(report)
+}
+
+fn cleanup_blocking(policy: Option<CleanupPolicy>, dry_run: bool) -> CmdResult<CleanupReport> {
+    let db = EmailDb::open()?;
+    let policy = policy.unwrap_or_else(|| load_policy(&db));
+    run_cleanup(&db, &policy, dry_run)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1u64 << 20) as f64),
+        b => format!("{} KB", b / 1024),
+    }
+}
+
+// ============================================================================
+// Background schedule
+// ============================================================================
+
+/// Start the cleanup schedule check (every 60s). Call from main.rs setup hook.
+pub fn start_cleanup_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(45)).await;
+        loop {
+            let due = EmailDb::open().ok().and_then(|db| {
+                let policy = load_policy(&db);
+                let last_run = db
+                    .get_sync_state(STATE_LAST_RUN)
+                    .ok()
+                    .flatten()
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok());
+                if !policy.enabled || !is_due(&policy.cron, last_run) {
+                    return None;
+                }
+                let _ = db.set_sync_state(STATE_LAST_RUN, &Local::now().to_rfc3339());
+                Some(policy)
+            });
+            if let Some(policy) = due {
+                run_scheduled(&app_handle, policy).await;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        }
+    });
+}
+
+async fn run_scheduled(app_handle: &tauri::AppHandle, policy: CleanupPolicy) {
+    let tracking_id = format!("outlook-cleanup-{}", chrono::Utc::now().timestamp_millis());
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let name = "Scheduled: Email cache cleanup";
+    let _ = app_handle.emit("jobs:update", serde_json::json!({
+        "id": &tracking_id, "name": name, "status": "running",
+        "message": "Cleaning up email cache", "startedAt": &started_at,
+    }));
+    let result = tauri::async_runtime::spawn_blocking(move || cleanup_blocking(Some(policy), false))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Cleanup task failed: {}", e)))
+        .and_then(|r| r);
+    let (status, message) = match result {
+        Ok(r) => ("completed", format!("Reclaimed {}", format_bytes(r.total_bytes))),
+        Err(e) => {
+            eprintln!("[outlook:cleanup] Failed: {}", e);
+            ("failed", format!("Email cache cleanup failed: {}", e))
+        }
+    };
+    let _ = app_handle.emit("jobs:update", serde_json::json!({
+        "id": &tracking_id, "name": name, "status": status,
+        "message": message, "startedAt": &started_at,
+    }));
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Run cleanup now. `dry_run` reports what would be reclaimed without
+/// touching anything; `policy` overrides the saved one for this run.
+#[tauri::command]
+pub async fn outlook_run_cleanup(dry_run: Option<bool>, policy: Option<CleanupPolicy>) -> CmdResult<CleanupReport> {
+    let dry_run = dry_run.unwrap_or(true);
+    tauri::async_runtime::spawn_blocking(move || cleanup_blocking(policy, dry_run))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Cleanup task failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn outlook_cleanup_get_policy() -> CmdResult<CleanupPolicy> {
+    let db = EmailDb::open()?;
+    Ok(load_policy(&db))
+}
+
+#[tauri::command]
+pub async fn outlook_cleanup_save_policy(policy: CleanupPolicy) -> CmdResult<CleanupPolicy> {
+    let expr = crate::commands::scheduler::background::normalize_cron(&policy.cron);
+    Schedule::from_str(&expr)
+        .map_err(|e| CommandError::Config(format!("Invalid cron '{}': {}", policy.cron, e)))?;
+    let db = EmailDb::open()?;
+    db.set_sync_state(STATE_POLICY, &serde_json::to_string(&policy)?)?;
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_only_attachments_over_the_limit() {
+        let small = format!("data:image/png;base64,{}", "A".repeat(100));
+        let large = format!("data:image/png;base64,{}", "B".repeat(4000));
+        let html = format!(r#"<img src="{}"><p>hi</p><img src="{}">"#, small, large);
+        let (out, count, saved) = strip_large_attachments(&html, 1024);
+        assert_eq!(count, 1);
+        assert!(out.contains(&small));
+        assert!(out.contains(PLACEHOLDER_IMAGE));
+        assert!(!out.contains(&large));
+        assert_eq!(saved, (large.len() - PLACEHOLDER_IMAGE.len()) as u64);
+    }
+}
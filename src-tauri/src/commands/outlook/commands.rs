@@ -3,6 +3,7 @@
 
 use super::contacts;
 use super::db::EmailDb;
+use super::diagnostics::RunTimer;
 use super::sync;
 use super::types::*;
 use crate::commands::error::CmdResult;
@@ -159,7 +160,10 @@ pub async fn outlook_initial_setup(app_handle: tauri::AppHandle, months: Option<
 
     let _ = db.set_sync_state("sync_months", &m.to_string());
 
-    let email_count = sync::run_initial_sync(&db, &app_handle, m).await.map_err(|e| {
+    let timer = RunTimer::start("email_initial", "setup");
+    let result = sync::run_initial_sync(&db, &app_handle, m).await;
+    timer.finish(&db, &result);
+    let email_count = result.map_err(|e| {
         let _ = app_handle.emit("jobs:update", serde_json::json!({
             "id": &job_id, "name": "Outlook Initial Setup", "status": "failed",
             "message": format!("Email sync failed: {}", e), "startedAt": &started_at,
@@ -167,7 +171,10 @@ pub async fn outlook_initial_setup(app_handle: tauri::AppHandle, months: Option<
         e
     })?;
 
-    let event_count = sync::run_calendar_sync(&db, &app_handle, m).await.map_err(|e| {
+    let timer = RunTimer::start("calendar", "setup");
+    let result = sync::run_calendar_sync(&db, &app_handle, m).await;
+    timer.finish(&db, &result);
+    let event_count = result.map_err(|e| {
         let _ = app_handle.emit("jobs:update", serde_json::json!({
             "id": &job_id, "name": "Outlook Initial Setup", "status": "failed",
             "message": format!("Calendar sync failed: {}", e), "startedAt": &started_at,
@@ -221,7 +228,9 @@ pub async fn outlook_sync_start(app_handle: tauri::AppHandle) -> CmdResult<i64>
         return Err("Initial sync not completed. Go to Settings > Outlook to set up.".into());
     }
 
+    let timer = RunTimer::start("email_incremental", "manual");
     let result = sync::run_incremental_sync(&db, &app_handle).await;
+    timer.finish(&db, &result);
     match &result {
         Ok(count) => {
             let _ = app_handle.emit("jobs:update", serde_json::json!({
@@ -442,7 +451,9 @@ pub async fn outlook_calendar_sync_start(app_handle: tauri::AppHandle) -> CmdRes
         return Err("Calendar initial sync not completed. Go to Settings > Outlook to set up.".into());
     }
 
+    let timer = RunTimer::start("calendar", "manual");
     let result = sync::run_calendar_sync(&db, &app_handle, 1).await;
+    timer.finish(&db, &result);
     match &result {
         Ok(count) => {
             let _ = app_handle.emit("jobs:update", serde_json::json!({
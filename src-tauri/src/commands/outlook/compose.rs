@@ -0,0 +1,309 @@
+// Compose assist — AI-drafted replies with CRM context.
+// Builds a prompt from the conversation history, the linked CRM company, its
+// open deals and recent activities, asks the model for a reply and saves it
+// as an Outlook draft (createReply). Nothing is ever sent from here — the
+// draft is returned for a person to edit and send.
+
+use super::db::EmailDb;
+use super::graph::GraphClient;
+use super::sync;
+use super::types::*;
+use crate::commands::crm::{Activity, Company, Contact, EmailCompanyLink};
+use crate::commands::ai::anthropic_messages;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings;
+use crate::commands::supabase::get_client;
+use crate::commands::work::Project;
+use serde::{Deserialize, Serialize};
+
+const MODEL: &str = "claude-haiku-4-5-20251001";
+/// Earlier messages in the conversation included as context
+const MAX_THREAD_MESSAGES: i64 = 10;
+/// The email being replied to is cut to this many characters
+const MAX_BODY_CHARS: usize = 8_000;
+const MAX_ACTIVITIES: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftReply {
+    pub email_id: String,
+    /// Graph ID of the saved draft (in Drafts, not sent)
+    pub draft_id: String,
+    pub web_link: Option<String>,
+    /// Plain-text draft, as generated
+    pub body: String,
+    pub company_name: Option<String>,
+    pub thread_messages: usize,
+    pub deals: usize,
+    pub activities: usize,
+}
+
+struct CrmContext {
+    company: Company,
+    deals: Vec<Project>,
+    activities: Vec<Activity>,
+}
+
+// ============================================================================
+// Text helpers
+// ============================================================================
+
+/// Readable plain text from an email HTML body
+pub fn html_to_text(html: &str) -> String {
+    let blocks = regex::Regex::new(r"(?is)<(style|script|head)\b[^>]*>.*?</(style|script|head)>").unwrap();
+    let breaks = regex::Regex::new(r"(?i)<br\s*/?>|</(p|div|li|tr|h[1-6])>").unwrap();
+    let tags = regex::Regex::new(r"<[^>]+>").unwrap();
+    let blank_lines = regex::Regex::new(r"\n\s*\n\s*\n+").unwrap();
+
+    let text = blocks.replace_all(html, "");
+    let text = breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let text: String = text.lines().map(|l| l.trim_end()).collect::<Vec<_>>().join("\n");
+    blank_lines.replace_all(&text, "\n\n").trim().to_string()
+}
+
+/// Plain-text draft → HTML for the Graph comment (paragraphs + line breaks)
+pub fn text_to_html(text: &str) -> String {
+    text.trim()
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| {
+            let escaped = p
+                .trim()
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('\n', "<br>");
+            format!("<p>{}</p>", escaped)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// Context
+// ============================================================================
+
+/// Company for the email: an explicit CRM link first, then the sender's contact record
+async fn load_crm_context(email: &EmailEntry) -> CmdResult<Option<CrmContext>> {
+    let client = get_client().await?;
+
+    let link: Option<EmailCompanyLink> = client
+        .select_single(
+            "crm_email_company_links",
+            &format!("select=*,company:crm_companies(*)&email_id=eq.{}", email.id),
+        )
+        .await?;
+    let company = match link.and_then(|l| l.company) {
+        Some(company) => Some(*company),
+        None => {
+            let contact: Option<Contact> = client
+                .select_single(
+                    "crm_contacts",
+                    &format!("email=eq.{}", urlencoding::encode(&email.from_email.to_lowercase())),
+                )
+                .await?;
+            match contact.and_then(|c| c.company_id) {
+                Some(company_id) => {
+                    client
+                        .select_single("crm_companies", &format!("id=eq.{}", company_id))
+                        .await?
+                }
+                None => None,
+            }
+        }
+    };
+    let Some(company) = company else {
+        return Ok(None);
+    };
+
+    let deals: Vec<Project> = client
+        .select(
+            "projects",
+            &format!(
+                "company_id=eq.{}&project_type=eq.deal&archived_at=is.null&order=updated_at.desc&limit=5",
+                company.id
+            ),
+        )
+        .await?;
+    let activities: Vec<Activity> = client
+        .select(
+            "crm_activities",
+            &format!(
+                "company_id=eq.{}&order=activity_date.desc&limit={}",
+                company.id, MAX_ACTIVITIES
+            ),
+        )
+        .await?;
+
+    Ok(Some(CrmContext { company, deals, activities }))
+}
+
+fn crm_section(crm: &CrmContext) -> String {
+    let c = &crm.company;
+    let mut out = format!(
+        "## CRM: {}\nStage: {}",
+        c.display_name.as_deref().unwrap_or(&c.name),
+        c.stage.as_deref().unwrap_or("unknown")
+    );
+    if let Some(industry) = &c.industry {
+        out.push_str(&format!(" · Industry: {}", industry));
+    }
+    if let Some(notes) = c.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        out.push_str(&format!("\nNotes: {}", notes.chars().take(500).collect::<String>()));
+    }
+
+    if !crm.deals.is_empty() {
+        out.push_str("\n\nOpen deals:");
+        for d in &crm.deals {
+            out.push_str(&format!("\n- {} — stage {}", d.name, d.deal_stage.as_deref().unwrap_or("?")));
+            if let Some(value) = d.deal_value {
+                out.push_str(&format!(", {} {:.0}", d.deal_currency.as_deref().unwrap_or(""), value));
+            }
+            if let Some(close) = &d.deal_expected_close {
+                out.push_str(&format!(", expected close {}", close));
+            }
+        }
+    }
+
+    if !crm.activities.is_empty() {
+        out.push_str("\n\nRecent activity (newest first):");
+        for a in &crm.activities {
+            let date = a.activity_date.as_deref().and_then(|d| d.get(..10)).unwrap_or("?");
+            let summary = a
+                .subject
+                .as_deref()
+                .or(a.content.as_deref())
+                .unwrap_or("")
+                .chars()
+                .take(160)
+                .collect::<String>();
+            out.push_str(&format!("\n- [{}] {}: {}", date, a.activity_type, summary));
+        }
+    }
+    out
+}
+
+fn thread_section(earlier: &[EmailEntry]) -> String {
+    let mut out = String::from("## Earlier in this thread (oldest first)");
+    for m in earlier {
+        out.push_str(&format!(
+            "\n\n[{}] {} <{}>:\n{}",
+            m.received_at.get(..16).unwrap_or(&m.received_at),
+            m.from_name,
+            m.from_email,
+            m.body_preview
+        ));
+    }
+    out
+}
+
+async fn generate(api_key: &str, system: &str, user: &str) -> CmdResult<String> {
+    anthropic_messages(api_key, MODEL, system, user, 1024, 0.4).await
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Draft a reply to an email with thread + CRM context and save it to Outlook
+/// Drafts. `instruction` steers the reply ("decline politely", "propose
+/// Tuesday"). Never sends — the draft is for human editing.
+#[tauri::command]
+pub async fn outlook_draft_reply(email_id: String, instruction: Option<String>) -> CmdResult<DraftReply> {
+    let api_key = settings::settings_get_anthropic_key()?
+        .ok_or_else(|| CommandError::Config("Anthropic API key not configured. Go to Settings (⌘,) to add it.".into()))?;
+
+    let db = EmailDb::open()?;
+    let email = db
+        .get_email(&email_id)?
+        .ok_or_else(|| CommandError::NotFound(format!("Email {} not found", email_id)))?;
+
+    let body: String = html_to_text(&sync::ensure_body_cached(&db, &email_id).await?)
+        .chars()
+        .take(MAX_BODY_CHARS)
+        .collect();
+    let earlier: Vec<EmailEntry> = match &email.conversation_id {
+        Some(cid) => db
+            .list_conversation(cid, MAX_THREAD_MESSAGES + 1)?
+            .into_iter()
+            .filter(|m| m.id != email.id && m.received_at <= email.received_at)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    // CRM context is a bonus — draft without it if Supabase isn't reachable
+    let crm = match load_crm_context(&email).await {
+        Ok(crm) => crm,
+        Err(e) => {
+            log::warn!("Compose assist: CRM context unavailable: {}", e);
+            None
+        }
+    };
+
+    let mut prompt = String::new();
+    if let Some(crm) = &crm {
+        prompt.push_str(&crm_section(crm));
+        prompt.push_str("\n\n");
+    }
+    if !earlier.is_empty() {
+        prompt.push_str(&thread_section(&earlier));
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str(&format!(
+        "## Email to reply to\nFrom: {} <{}>\nDate: {}\nSubject: {}\n\n{}",
+        email.from_name, email.from_email, email.received_at, email.subject, body
+    ));
+    let instruction = instruction.map(|i| i.trim().to_string()).filter(|i| !i.is_empty());
+    if let Some(instruction) = &instruction {
+        prompt.push_str(&format!("\n\n## How to reply\n{}", instruction));
+    }
+
+    let system = "You draft email replies for a consultant at a small data/software consultancy. \
+        Write only the reply body in plain text: a greeting, a concise answer to what the sender asked, and a \
+        short sign-off without a name or signature block. Match the sender's tone and language. Use the CRM \
+        context to stay consistent with deal status and recent conversations, but never reveal internal notes, \
+        deal values or stages. Do not invent commitments, prices or dates that aren't in the context; leave \
+        [placeholders] for anything the sender must fill in.";
+    let draft = generate(&api_key, system, &prompt).await?;
+
+    let graph = GraphClient::new();
+    let (draft_id, web_link) = graph.create_reply_draft(&email_id, &text_to_html(&draft)).await?;
+
+    Ok(DraftReply {
+        email_id,
+        draft_id,
+        web_link,
+        body: draft,
+        company_name: crm
+            .as_ref()
+            .map(|c| c.company.display_name.clone().unwrap_or_else(|| c.company.name.clone())),
+        thread_messages: earlier.len(),
+        deals: crm.as_ref().map(|c| c.deals.len()).unwrap_or(0),
+        activities: crm.as_ref().map(|c| c.activities.len()).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_html_and_plain_text() {
+        let html = "<html><head><style>p{color:red}</style></head><body><p>Hi Sam,</p>\
+            <div>Can we meet&nbsp;Tuesday?<br>Thanks &amp; regards</div></body></html>";
+        assert_eq!(html_to_text(html), "Hi Sam,\nCan we meet Tuesday?\nThanks & regards");
+
+        assert_eq!(
+            text_to_html("Hi Sam,\n\nTuesday works <10am>.\nBest"),
+            "<p>Hi Sam,</p>\n<p>Tuesday works &lt;10am&gt;.<br>Best</p>"
+        );
+    }
+}
@@ -5,7 +5,7 @@ use rusqlite::{params, Connection};
 use std::path::PathBuf;
 use std::sync::Mutex;
 
-use super::types::{CalendarEvent, ContactRule, EmailEntry, EmailStats};
+use super::types::{CalendarEvent, ContactRule, EmailEntry, EmailStats, SenderStats, SyncRun};
 use crate::commands::error::{CmdResult, CommandError};
 
 // ============================================================================
@@ -125,6 +125,18 @@ impl EmailDb {
                 synced_at TEXT NOT NULL DEFAULT (datetime('now'))
             );
 
+            CREATE TABLE IF NOT EXISTS sync_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                trigger TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                items INTEGER NOT NULL DEFAULT 0,
+                ok INTEGER NOT NULL DEFAULT 1,
+                error TEXT,
+                error_code TEXT
+            );
+
             CREATE INDEX IF NOT EXISTS idx_events_start ON events(start_at);
             CREATE INDEX IF NOT EXISTS idx_events_end ON events(end_at);
             ",
@@ -135,6 +147,7 @@ impl EmailDb {
         let migrations = [
             "ALTER TABLE emails ADD COLUMN to_addresses TEXT NOT NULL DEFAULT '[]'",
             "ALTER TABLE emails ADD COLUMN cc_addresses TEXT NOT NULL DEFAULT '[]'",
+            "ALTER TABLE emails ADD COLUMN archived_at TEXT",
         ];
         for sql in &migrations {
             // Ignore "duplicate column" errors — means column already exists
@@ -202,7 +215,6 @@ impl EmailDb {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn delete_email(&self, id: &str) -> CmdResult<()> {
         let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
         conn.execute("DELETE FROM emails WHERE id = ?1", params![id])
@@ -298,6 +310,83 @@ impl EmailDb {
         Ok(emails)
     }
 
+    /// Inbox-side emails received in [start, end), newest first
+    pub fn list_received_between(&self, start: &str, end: &str, limit: i64) -> CmdResult<Vec<EmailEntry>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT * FROM emails
+                 WHERE received_at >= ?1 AND received_at < ?2
+                   AND folder_name NOT IN ('Sent Items', 'Drafts', 'Deleted Items', 'Junk Email')
+                 ORDER BY received_at DESC LIMIT ?3",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![start, end, limit], |row| Ok(row_to_email(row)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        rows.map(|r| r.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?)
+            .collect()
+    }
+
+    /// Sent and received emails in [start, end), oldest first (drafts, deleted and junk excluded)
+    pub fn list_between(&self, start: &str, end: &str, limit: i64) -> CmdResult<Vec<EmailEntry>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT * FROM emails
+                 WHERE received_at >= ?1 AND received_at < ?2
+                   AND folder_name NOT IN ('Drafts', 'Deleted Items', 'Junk Email')
+                 ORDER BY received_at ASC LIMIT ?3",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![start, end, limit], |row| Ok(row_to_email(row)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        rows.map(|r| r.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?)
+            .collect()
+    }
+
+    /// Latest sent email per conversation since `since` that nobody has replied to yet
+    pub fn list_awaiting_reply(&self, since: &str, before: &str, limit: i64) -> CmdResult<Vec<EmailEntry>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT * FROM emails s
+                 WHERE s.folder_name = 'Sent Items'
+                   AND s.received_at >= ?1 AND s.received_at < ?2
+                   AND s.conversation_id IS NOT NULL
+                   AND s.received_at = (
+                     SELECT MAX(x.received_at) FROM emails x
+                     WHERE x.conversation_id = s.conversation_id
+                   )
+                 ORDER BY s.received_at ASC LIMIT ?3",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![since, before, limit], |row| Ok(row_to_email(row)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        rows.map(|r| r.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?)
+            .collect()
+    }
+
+    /// All cached messages in a conversation, oldest first
+    pub fn list_conversation(&self, conversation_id: &str, limit: i64) -> CmdResult<Vec<EmailEntry>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT * FROM (
+                   SELECT * FROM emails WHERE conversation_id = ?1
+                   ORDER BY received_at DESC LIMIT ?2
+                 ) ORDER BY received_at ASC",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![conversation_id, limit], |row| Ok(row_to_email(row)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        rows.map(|r| r.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?)
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub fn email_exists(&self, id: &str) -> CmdResult<bool> {
         let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
@@ -324,7 +413,7 @@ impl EmailDb {
     pub fn archive_email(&self, id: &str) -> CmdResult<()> {
         let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
         conn.execute(
-            "UPDATE emails SET status = 'archived', is_read = 1, updated_at = datetime('now') WHERE id = ?1",
+            "UPDATE emails SET status = 'archived', is_read = 1, archived_at = datetime('now'), updated_at = datetime('now') WHERE id = ?1",
             params![id],
         )
         .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
@@ -424,6 +513,73 @@ impl EmailDb {
         })
     }
 
+    /// Per-sender volume, read and archive figures for inbox-side mail since `since`
+    pub fn get_sender_stats(&self, since: &str, limit: i64) -> CmdResult<Vec<SenderStats>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT lower(e.from_email) AS sender, MAX(e.from_name), COUNT(*),
+                        SUM(e.is_read), SUM(e.status = 'archived'),
+                        AVG(CASE WHEN e.archived_at IS NOT NULL
+                            THEN (julianday(e.archived_at) - julianday(e.received_at)) * 24 END),
+                        SUM(e.category = 'noise'),
+                        MAX(e.category IN ('client', 'internal', 'lead')),
+                        MAX(e.received_at),
+                        EXISTS (SELECT 1 FROM contacts c
+                                WHERE c.match_type = 'archive_email' AND c.match_value = lower(e.from_email))
+                 FROM emails e
+                 WHERE e.received_at >= ?1 AND e.from_email != ''
+                   AND e.folder_name NOT IN ('Sent Items', 'Drafts', 'Deleted Items', 'Junk Email')
+                 GROUP BY sender
+                 ORDER BY COUNT(*) DESC LIMIT ?2",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![since, limit], |row| {
+                let volume: i64 = row.get(2)?;
+                let read_count: i64 = row.get(3)?;
+                Ok(SenderStats {
+                    email: row.get(0)?,
+                    name: row.get(1)?,
+                    volume,
+                    read_count,
+                    read_rate: if volume > 0 { read_count as f64 / volume as f64 } else { 0.0 },
+                    archived_count: row.get(4)?,
+                    avg_hours_to_archive: row.get(5)?,
+                    noise_count: row.get(6)?,
+                    known_contact: row.get::<_, i64>(7)? != 0,
+                    last_received_at: row.get(8)?,
+                    auto_archived: row.get::<_, i64>(9)? != 0,
+                })
+            })
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        rows.map(|r| r.map_err(|e| CommandError::Internal(format!("DB: {}", e))))
+            .collect()
+    }
+
+    pub fn has_archive_rule(&self, email: &str) -> CmdResult<bool> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM contacts WHERE match_type = 'archive_email' AND match_value = ?1",
+                params![email.to_lowercase()],
+                |row| row.get(0),
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(count > 0)
+    }
+
+    /// Archive everything still in the inbox from a sender. Returns rows changed.
+    pub fn archive_from_sender(&self, email: &str) -> CmdResult<usize> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        conn.execute(
+            "UPDATE emails SET status = 'archived', is_read = 1, archived_at = datetime('now'), updated_at = datetime('now')
+             WHERE lower(from_email) = ?1 AND status IN ('inbox', 'read')",
+            params![email.to_lowercase()],
+        )
+        .map_err(|e| CommandError::Internal(format!("DB: {}", e)))
+    }
+
     // ========================================================================
     // Sync state
     // ========================================================================
@@ -547,6 +703,16 @@ impl EmailDb {
         Ok(result)
     }
 
+    /// Drop the email-match rule for `email`; returns how many rows went
+    pub fn delete_contact_by_email(&self, email: &str) -> CmdResult<usize> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        conn.execute(
+            "DELETE FROM contacts WHERE match_type = 'email' AND lower(match_value) = lower(?1)",
+            params![email],
+        )
+        .map_err(|e| CommandError::Internal(format!("DB: {}", e)))
+    }
+
     pub fn is_noise_domain(&self, domain: &str) -> CmdResult<bool> {
         let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
         let count: i64 = conn
@@ -593,6 +759,152 @@ impl EmailDb {
             .map_err(|e| CommandError::Internal(format!("DB: {}", e)))
     }
 
+    // ========================================================================
+    // Sync diagnostics
+    // ========================================================================
+
+    /// Log a sync run, keeping the newest `keep` rows
+    pub fn record_sync_run(&self, run: &SyncRun, keep: i64) -> CmdResult<()> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        conn.execute(
+            "INSERT INTO sync_runs (kind, trigger, started_at, duration_ms, items, ok, error, error_code)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                run.kind,
+                run.trigger,
+                run.started_at,
+                run.duration_ms,
+                run.items,
+                run.ok as i32,
+                run.error,
+                run.error_code,
+            ],
+        )
+        .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        conn.execute(
+            "DELETE FROM sync_runs WHERE id <= (SELECT MAX(id) FROM sync_runs) - ?1",
+            params![keep],
+        )
+        .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(())
+    }
+
+    /// Newest first
+    pub fn list_sync_runs(&self, limit: i64) -> CmdResult<Vec<SyncRun>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, kind, trigger, started_at, duration_ms, items, ok, error, error_code
+                 FROM sync_runs ORDER BY id DESC LIMIT ?1",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok(SyncRun {
+                    id: row.get(0)?,
+                    kind: row.get(1)?,
+                    trigger: row.get(2)?,
+                    started_at: row.get(3)?,
+                    duration_ms: row.get(4)?,
+                    items: row.get(5)?,
+                    ok: row.get::<_, i32>(6)? != 0,
+                    error: row.get(7)?,
+                    error_code: row.get(8)?,
+                })
+            })
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let mut runs = Vec::new();
+        for row in rows {
+            runs.push(row.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?);
+        }
+        Ok(runs)
+    }
+
+    /// (folder id, display name, cached messages, newest received_at) per known folder
+    pub fn folder_cache_stats(&self) -> CmdResult<Vec<(String, String, i64, Option<String>)>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT f.folder_id, f.display_name, COUNT(e.id), MAX(e.received_at)
+                 FROM folder_map f LEFT JOIN emails e ON e.folder_name = f.display_name
+                 GROUP BY f.folder_id, f.display_name
+                 ORDER BY f.display_name",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let mut folders = Vec::new();
+        for row in rows {
+            folders.push(row.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?);
+        }
+        Ok(folders)
+    }
+
+    /// Ids of cached emails in a folder received at or after `since`
+    pub fn list_folder_email_ids(&self, folder_name: &str, since: &str) -> CmdResult<Vec<String>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare("SELECT id FROM emails WHERE folder_name = ?1 AND received_at >= ?2")
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![folder_name, since], |row| row.get(0))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row.map_err(|e| CommandError::Internal(format!("DB: {}", e)))?);
+        }
+        Ok(ids)
+    }
+
+    // ========================================================================
+    // Cache cleanup
+    // ========================================================================
+
+    /// (id, body_path, received_at) for every email with a cached body on disk
+    pub fn list_cached_bodies(&self) -> CmdResult<Vec<(String, String, String)>> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut stmt = conn
+            .prepare("SELECT id, body_path, received_at FROM emails WHERE body_path IS NOT NULL")
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Forget cached bodies; the headers stay and bodies re-fetch lazily on open
+    pub fn clear_body_paths(&self, ids: &[String]) -> CmdResult<()> {
+        let mut conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let tx = conn.transaction().map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        for id in ids {
+            tx.execute(
+                "UPDATE emails SET body_path = NULL, updated_at = datetime('now') WHERE id = ?1",
+                params![id],
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        }
+        tx.commit().map_err(|e| CommandError::Internal(format!("DB: {}", e)))
+    }
+
+    /// Bytes held by free pages, i.e. what VACUUM would give back
+    pub fn free_page_bytes(&self) -> CmdResult<u64> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let free: i64 = conn
+            .query_row("PRAGMA freelist_count", [], |row| row.get(0))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let page_size: i64 = conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok((free * page_size).max(0) as u64)
+    }
+
+    pub fn vacuum(&self) -> CmdResult<()> {
+        let conn = self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))
+    }
+
     // ========================================================================
     // Calendar events
     // ========================================================================
@@ -0,0 +1,329 @@
+// Sync diagnostics — run log, health rollups and per-folder repair
+// Every sync run and token refresh is logged to emails.db so failures in the
+// background loop are visible after the fact. Repair re-fetches one folder's
+// recent mail from Graph instead of wiping the whole cache.
+
+use super::auth;
+use super::db::EmailDb;
+use super::graph::GraphClient;
+use super::sync;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use std::collections::HashSet;
+use std::time::Instant;
+use tauri::Emitter;
+
+/// Rows kept in sync_runs
+const KEEP_RUNS: i64 = 500;
+const DEFAULT_LIMIT: i64 = 50;
+const RUN_KINDS: &[&str] = &["email_initial", "email_incremental", "calendar", "token_refresh", "folder_repair"];
+/// sync_state keys holding a sync position
+const CURSOR_KEYS: &[&str] = &["last_sync", "calendar_last_sync"];
+const STATE_REPAIRED_PREFIX: &str = "folder_repaired:";
+const DEFAULT_REPAIR_DAYS: i64 = 30;
+const MAX_REPAIR_MESSAGES: usize = 10000;
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Times one run; call `finish` or `record` when it ends
+pub struct RunTimer {
+    kind: &'static str,
+    trigger: &'static str,
+    started_at: String,
+    started: Instant,
+}
+
+impl RunTimer {
+    pub fn start(kind: &'static str, trigger: &'static str) -> Self {
+        Self {
+            kind,
+            trigger,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Log a sync that returns its item count
+    pub fn finish(self, db: &EmailDb, result: &CmdResult<i64>) {
+        let items = result.as_ref().copied().unwrap_or(0);
+        self.record(db, items, result.as_ref().err());
+    }
+
+    /// Log the run. Best-effort — diagnostics never fail the sync itself.
+    pub fn record(self, db: &EmailDb, items: i64, error: Option<&CommandError>) {
+        let run = SyncRun {
+            id: 0,
+            kind: self.kind.to_string(),
+            trigger: self.trigger.to_string(),
+            started_at: self.started_at,
+            duration_ms: self.started.elapsed().as_millis() as i64,
+            items,
+            ok: error.is_none(),
+            error: error.map(|e| e.to_string()),
+            error_code: error.and_then(error_code),
+        };
+        if let Err(e) = db.record_sync_run(&run, KEEP_RUNS) {
+            eprintln!("[outlook:diagnostics] Failed to record {} run: {}", run.kind, e);
+        }
+    }
+}
+
+/// Graph error code from the response body, the OAuth error from a failed
+/// refresh, or the bare HTTP status
+fn error_code(e: &CommandError) -> Option<String> {
+    match e {
+        CommandError::Http { status, body } => serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v["error"]["code"].as_str().map(|s| s.to_string()))
+            .or_else(|| Some(format!("HTTP {}", status))),
+        CommandError::Network(msg) => msg
+            .strip_prefix("Token refresh failed: ")
+            .and_then(|rest| rest.split(" - ").next())
+            .map(|code| code.trim().to_string()),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Rollups
+// ============================================================================
+
+/// Per-kind health from runs ordered newest first
+fn summarize(runs: &[SyncRun]) -> Vec<SyncKindHealth> {
+    RUN_KINDS
+        .iter()
+        .filter_map(|kind| {
+            let of_kind: Vec<&SyncRun> = runs.iter().filter(|r| r.kind == *kind).collect();
+            let latest = of_kind.first()?;
+            let consecutive_failures = of_kind.iter().take_while(|r| !r.ok).count();
+            let total: i64 = of_kind.iter().map(|r| r.duration_ms).sum();
+            Some(SyncKindHealth {
+                kind: kind.to_string(),
+                last_run_at: Some(latest.started_at.clone()),
+                last_success_at: of_kind.iter().find(|r| r.ok).map(|r| r.started_at.clone()),
+                consecutive_failures,
+                last_error: of_kind.iter().find(|r| !r.ok).and_then(|r| r.error.clone()),
+                avg_duration_ms: total / of_kind.len() as i64,
+            })
+        })
+        .collect()
+}
+
+fn age_secs(timestamp: &str, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| (now - t.with_timezone(&chrono::Utc)).num_seconds())
+}
+
+fn collect_diagnostics(db: &EmailDb, limit: i64) -> CmdResult<SyncDiagnostics> {
+    let now = chrono::Utc::now();
+    let mut runs = db.list_sync_runs(KEEP_RUNS)?;
+    let health = summarize(&runs);
+    runs.truncate(limit.max(0) as usize);
+
+    let cursors = CURSOR_KEYS
+        .iter()
+        .map(|key| -> CmdResult<SyncCursor> {
+            let value = db.get_sync_state(key)?;
+            let age = value.as_deref().and_then(|v| age_secs(v, now));
+            Ok(SyncCursor { key: key.to_string(), value, age_secs: age })
+        })
+        .collect::<CmdResult<Vec<_>>>()?;
+
+    let folders = db
+        .folder_cache_stats()?
+        .into_iter()
+        .map(|(folder_id, display_name, cached_messages, newest_message_at)| -> CmdResult<FolderSyncState> {
+            let last_repaired_at = db.get_sync_state(&format!("{}{}", STATE_REPAIRED_PREFIX, folder_id))?;
+            Ok(FolderSyncState {
+                newest_age_secs: newest_message_at.as_deref().and_then(|v| age_secs(v, now)),
+                folder_id,
+                display_name,
+                cached_messages,
+                newest_message_at,
+                last_repaired_at,
+            })
+        })
+        .collect::<CmdResult<Vec<_>>>()?;
+
+    let tokens = auth::load_tokens();
+    Ok(SyncDiagnostics {
+        runs,
+        health,
+        cursors,
+        folders,
+        token_expires_at: tokens
+            .as_ref()
+            .and_then(|t| chrono::DateTime::from_timestamp(t.expires_at, 0))
+            .map(|t| t.to_rfc3339()),
+        has_refresh_token: tokens.is_some_and(|t| t.refresh_token.is_some()),
+    })
+}
+
+// ============================================================================
+// Folder repair
+// ============================================================================
+
+async fn repair_folder(db: &EmailDb, folder_id: &str, days: i64) -> CmdResult<FolderRepairReport> {
+    let graph = GraphClient::new();
+
+    // Refresh the whole folder map — a stale id → name mapping is the usual corruption
+    let folders = graph.list_folders().await?;
+    for folder in &folders {
+        db.upsert_folder(&folder.id, &folder.display_name)?;
+    }
+    let folder = folders
+        .iter()
+        .find(|f| f.id == folder_id)
+        .ok_or_else(|| CommandError::NotFound(format!("Folder not found in mailbox: {}", folder_id)))?;
+
+    let since = (chrono::Utc::now() - chrono::Duration::days(days)).format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let filter = format!("receivedDateTime ge {}", since);
+    let messages = graph
+        .fetch_folder_messages(folder_id, MAX_REPAIR_MESSAGES, Some(&filter))
+        .await?;
+
+    let mut fetched = HashSet::new();
+    for msg in &messages {
+        let email = sync::graph_message_to_entry(msg, db)?;
+        db.upsert_email(&email)?;
+        fetched.insert(msg.id.clone());
+    }
+
+    // Cached rows Graph no longer has here were deleted or moved upstream
+    let mut removed = 0;
+    for id in db.list_folder_email_ids(&folder.display_name, &since)? {
+        if !fetched.contains(&id) {
+            db.delete_email(&id)?;
+            removed += 1;
+        }
+    }
+
+    db.set_sync_state(
+        &format!("{}{}", STATE_REPAIRED_PREFIX, folder_id),
+        &chrono::Utc::now().to_rfc3339(),
+    )?;
+
+    Ok(FolderRepairReport {
+        folder_id: folder_id.to_string(),
+        display_name: folder.display_name.clone(),
+        refetched: fetched.len() as i64,
+        removed,
+        since,
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Recent sync runs (newest first, default 50) with per-kind health, sync
+/// cursor ages, per-folder cache state and token status
+#[tauri::command]
+pub async fn outlook_get_sync_diagnostics(limit: Option<i64>) -> CmdResult<SyncDiagnostics> {
+    let db = EmailDb::open()?;
+    collect_diagnostics(&db, limit.unwrap_or(DEFAULT_LIMIT))
+}
+
+/// Re-fetch the last `days` (default 30) of one folder from Graph, rewrite
+/// those rows and drop cached ones Graph no longer has. Other folders and
+/// cached bodies are left alone.
+#[tauri::command]
+pub async fn outlook_repair_folder(
+    app_handle: tauri::AppHandle,
+    folder_id: String,
+    days: Option<i64>,
+) -> CmdResult<FolderRepairReport> {
+    let days = days.unwrap_or(DEFAULT_REPAIR_DAYS);
+    if days < 1 {
+        return Err(CommandError::Validation {
+            message: "days must be at least 1".to_string(),
+            fields: vec!["days".to_string()],
+        });
+    }
+
+    let job_id = format!("outlook-repair-{}", chrono::Utc::now().timestamp_millis());
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let name = "Outlook Folder Repair";
+    let _ = app_handle.emit("jobs:update", serde_json::json!({
+        "id": &job_id, "name": name, "status": "running",
+        "message": "Re-fetching folder...", "startedAt": &started_at,
+    }));
+
+    let db = EmailDb::open()?;
+    let timer = RunTimer::start("folder_repair", "manual");
+    let result = repair_folder(&db, &folder_id, days).await;
+    timer.record(&db, result.as_ref().map(|r| r.refetched).unwrap_or(0), result.as_ref().err());
+
+    let (status, message) = match &result {
+        Ok(r) => ("completed", format!("{}: {} re-fetched, {} removed", r.display_name, r.refetched, r.removed)),
+        Err(e) => ("failed", format!("Folder repair failed: {}", e)),
+    };
+    let _ = app_handle.emit("jobs:update", serde_json::json!({
+        "id": &job_id, "name": name, "status": status,
+        "message": message, "startedAt": &started_at,
+    }));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(kind: &str, ok: bool, duration_ms: i64, started_at: &str) -> SyncRun {
+        SyncRun {
+            id: 0,
+            kind: kind.to_string(),
+            trigger: "background".to_string(),
+            started_at: started_at.to_string(),
+            duration_ms,
+            items: 0,
+            ok,
+            error: if ok { None } else { Some(format!("failed at {}", started_at)) },
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn summarizes_streaks_per_kind() {
+        // Newest first
+        let runs = vec![
+            run("email_incremental", false, 300, "t4"),
+            run("calendar", true, 1000, "t3"),
+            run("email_incremental", false, 100, "t2"),
+            run("email_incremental", true, 200, "t1"),
+        ];
+        let health = summarize(&runs);
+        assert_eq!(health.len(), 2);
+
+        let email = &health[0];
+        assert_eq!(email.kind, "email_incremental");
+        assert_eq!(email.last_run_at.as_deref(), Some("t4"));
+        assert_eq!(email.last_success_at.as_deref(), Some("t1"));
+        assert_eq!(email.consecutive_failures, 2);
+        assert_eq!(email.last_error.as_deref(), Some("failed at t4"));
+        assert_eq!(email.avg_duration_ms, 200);
+
+        assert_eq!(health[1].kind, "calendar");
+        assert_eq!(health[1].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn extracts_graph_and_oauth_error_codes() {
+        let graph = CommandError::Http {
+            status: 401,
+            body: r#"{"error":{"code":"InvalidAuthenticationToken","message":"expired"}}"#.to_string(),
+        };
+        assert_eq!(error_code(&graph).as_deref(), Some("InvalidAuthenticationToken"));
+
+        let throttled = CommandError::Http { status: 429, body: "Too Many Requests".to_string() };
+        assert_eq!(error_code(&throttled).as_deref(), Some("HTTP 429"));
+
+        let oauth = CommandError::Network("Token refresh failed: invalid_grant - AADSTS70008".to_string());
+        assert_eq!(error_code(&oauth).as_deref(), Some("invalid_grant"));
+
+        assert_eq!(error_code(&CommandError::Network("connection reset".to_string())), None);
+    }
+}
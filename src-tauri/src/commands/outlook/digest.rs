@@ -0,0 +1,372 @@
+// Daily email digest — a morning markdown summary of what needs attention:
+// important new mail, threads awaiting a reply, follow-up tasks due, and
+// deals with fresh CRM activity. Runs as a built-in scheduler job on its own
+// cron (UTC, like automations).
+
+use super::db::EmailDb;
+use super::types::*;
+use crate::commands::crm::Activity;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::scheduler::builtin::{BuiltinJob, JobFuture};
+use crate::commands::supabase::get_client;
+use crate::commands::work::{Project, Task};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use cron::Schedule;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const STATE_CONFIG: &str = "digest_config";
+const STATE_LAST_RUN: &str = "digest_last_run";
+/// How far back to look for unanswered sent mail
+const AWAITING_REPLY_DAYS: i64 = 14;
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn load_config(db: &EmailDb) -> DigestConfig {
+    db.get_sync_state(STATE_CONFIG)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn digest_dir(config: &DigestConfig) -> PathBuf {
+    config
+        .output_folder
+        .as_ref()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".tv-client")
+                .join("outlook")
+                .join("digests")
+        })
+}
+
+/// Local midnight of `date` as a UTC timestamp string comparable with received_at
+fn local_midnight_utc(date: NaiveDate) -> String {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+fn is_important(email: &EmailEntry) -> bool {
+    email.status != "archived"
+        && (email.action_required
+            || email.importance == "high"
+            || matches!(email.priority_level.as_str(), "high" | "urgent"))
+}
+
+fn local_time(ts: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.with_timezone(&Local).format("%a %H:%M").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+fn one_line(s: &str, max: usize) -> String {
+    let flat = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > max {
+        format!("{}…", flat.chars().take(max).collect::<String>())
+    } else {
+        flat
+    }
+}
+
+async fn follow_ups_due(date: NaiveDate) -> CmdResult<Vec<Task>> {
+    let client = get_client().await?;
+    client
+        .select(
+            "tasks",
+            &format!(
+                "select=*,project:projects(*),status:task_statuses!inner(*)&task_type=eq.follow_up&due_date=lte.{}&status.type=not.in.(completed,canceled)&order=due_date.asc",
+                date.format("%Y-%m-%d")
+            ),
+        )
+        .await
+}
+
+async fn deals_with_activity(since: &str) -> CmdResult<Vec<(Project, Vec<Activity>)>> {
+    let client = get_client().await?;
+    let activities: Vec<Activity> = client
+        .select(
+            "crm_activities",
+            &format!("project_id=not.is.null&activity_date=gte.{}&order=activity_date.desc", since),
+        )
+        .await?;
+    if activities.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut by_project: HashMap<String, Vec<Activity>> = HashMap::new();
+    for a in activities {
+        if let Some(pid) = a.project_id.clone() {
+            by_project.entry(pid).or_default().push(a);
+        }
+    }
+    let ids: Vec<&str> = by_project.keys().map(|k| k.as_str()).collect();
+    let deals: Vec<Project> = client
+        .select(
+            "projects",
+            &format!("id=in.({})&project_type=eq.deal&order=updated_at.desc", ids.join(",")),
+        )
+        .await?;
+
+    Ok(deals
+        .into_iter()
+        .map(|d| {
+            let acts = by_project.remove(&d.id).unwrap_or_default();
+            (d, acts)
+        })
+        .collect())
+}
+
+/// Build the digest for `date` (local). Supabase-backed sections degrade to a
+/// note when the workspace isn't reachable so the email part still renders.
+async fn build_digest(date: NaiveDate) -> CmdResult<(String, [usize; 4])> {
+    let start = local_midnight_utc(date - chrono::Duration::days(1));
+    let today = local_midnight_utc(date);
+    let end = local_midnight_utc(date + chrono::Duration::days(1));
+    let awaiting_since = local_midnight_utc(date - chrono::Duration::days(AWAITING_REPLY_DAYS));
+
+    // Local reads first — the connection isn't held across the Supabase awaits
+    let (important, awaiting) = {
+        let db = EmailDb::open()?;
+        let important: Vec<EmailEntry> = db
+            .list_received_between(&start, &end, 500)?
+            .into_iter()
+            .filter(is_important)
+            .collect();
+        (important, db.list_awaiting_reply(&awaiting_since, &today, 50)?)
+    };
+    let follow_ups = follow_ups_due(date).await;
+    let deals = deals_with_activity(&start).await;
+
+    let mut md = format!(
+        "---\ntitle: Email digest {}\ngenerated: {}\n---\n\n# Email digest — {}\n",
+        date.format("%Y-%m-%d"),
+        chrono::Utc::now().to_rfc3339(),
+        date.format("%A, %-d %B %Y"),
+    );
+
+    md.push_str(&format!("\n## Important new email ({})\n\n", important.len()));
+    if important.is_empty() {
+        md.push_str("_Nothing flagged._\n");
+    }
+    for e in &important {
+        let flag = if e.action_required { " · **action required**" } else { "" };
+        md.push_str(&format!(
+            "- **{}** — {} <{}> · {}{}\n",
+            one_line(&e.subject, 120),
+            e.from_name,
+            e.from_email,
+            local_time(&e.received_at),
+            flag
+        ));
+        let summary = e.ai_summary.as_deref().unwrap_or(&e.body_preview);
+        if !summary.trim().is_empty() {
+            md.push_str(&format!("  > {}\n", one_line(summary, 200)));
+        }
+    }
+
+    md.push_str(&format!("\n## Awaiting reply ({})\n\n", awaiting.len()));
+    if awaiting.is_empty() {
+        md.push_str("_No open threads._\n");
+    }
+    for e in &awaiting {
+        let to = e
+            .to_addresses
+            .iter()
+            .map(|a| if a.name.is_empty() { a.email.clone() } else { a.name.clone() })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let days = chrono::DateTime::parse_from_rfc3339(&e.received_at)
+            .map(|dt| (chrono::Utc::now() - dt.with_timezone(&chrono::Utc)).num_days())
+            .unwrap_or(0);
+        md.push_str(&format!(
+            "- **{}** — to {} · sent {} day{} ago\n",
+            one_line(&e.subject, 120),
+            to,
+            days,
+            if days == 1 { "" } else { "s" }
+        ));
+    }
+
+    let follow_up_count = match &follow_ups {
+        Ok(tasks) => {
+            md.push_str(&format!("\n## Follow-ups due ({})\n\n", tasks.len()));
+            if tasks.is_empty() {
+                md.push_str("_None due._\n");
+            }
+            let today_str = date.format("%Y-%m-%d").to_string();
+            for t in tasks {
+                let key = match (t.project.as_ref().and_then(|p| p.identifier_prefix.as_deref()), t.task_number) {
+                    (Some(prefix), Some(n)) => format!("[{}-{}] ", prefix, n),
+                    _ => String::new(),
+                };
+                let due = t.due_date.as_deref().unwrap_or_default();
+                let overdue = if !due.is_empty() && &due[..due.len().min(10)] < today_str.as_str() { " (overdue)" } else { "" };
+                md.push_str(&format!("- {}{} — due {}{}\n", key, t.title, &due[..due.len().min(10)], overdue));
+            }
+            tasks.len()
+        }
+        Err(e) => {
+            md.push_str(&format!("\n## Follow-ups due\n\n_Unavailable: {}_\n", e));
+            0
+        }
+    };
+
+    let deal_count = match &deals {
+        Ok(deals) => {
+            md.push_str(&format!("\n## Deals with activity ({})\n\n", deals.len()));
+            if deals.is_empty() {
+                md.push_str("_No deal activity._\n");
+            }
+            for (deal, acts) in deals {
+                let stage = deal.deal_stage.as_deref().unwrap_or("—");
+                let value = deal
+                    .deal_value
+                    .map(|v| format!(", {} {:.0}", deal.deal_currency.as_deref().unwrap_or(""), v))
+                    .unwrap_or_default();
+                let latest = acts
+                    .first()
+                    .and_then(|a| a.subject.as_deref())
+                    .map(|s| format!(": latest \"{}\"", one_line(s, 80)))
+                    .unwrap_or_default();
+                md.push_str(&format!(
+                    "- **{}** ({}{}) — {} activit{}{}\n",
+                    deal.name,
+                    stage,
+                    value,
+                    acts.len(),
+                    if acts.len() == 1 { "y" } else { "ies" },
+                    latest
+                ));
+            }
+            deals.len()
+        }
+        Err(e) => {
+            md.push_str(&format!("\n## Deals with activity\n\n_Unavailable: {}_\n", e));
+            0
+        }
+    };
+
+    Ok((md, [important.len(), awaiting.len(), follow_up_count, deal_count]))
+}
+
+async fn generate(app_handle: &tauri::AppHandle, date: NaiveDate, notify: bool) -> CmdResult<EmailDigest> {
+    let config = load_config(&EmailDb::open()?);
+    let (markdown, [important, awaiting, follow_ups, deals]) = build_digest(date).await?;
+
+    let dir = digest_dir(&config);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.md", date.format("%Y-%m-%d")));
+    std::fs::write(&path, &markdown)?;
+
+    if notify {
+        use tauri_plugin_notification::NotificationExt;
+        let _ = app_handle
+            .notification()
+            .builder()
+            .title("Email digest")
+            .body(format!(
+                "{} important · {} awaiting reply · {} follow-ups · {} deals",
+                important, awaiting, follow_ups, deals
+            ))
+            .show();
+    }
+
+    Ok(EmailDigest {
+        date: date.format("%Y-%m-%d").to_string(),
+        path: path.to_string_lossy().to_string(),
+        markdown,
+        important_count: important,
+        awaiting_reply_count: awaiting,
+        follow_up_count: follow_ups,
+        deal_count: deals,
+    })
+}
+
+// ============================================================================
+// Background schedule
+// ============================================================================
+
+pub(crate) const DIGEST_JOB: BuiltinJob = BuiltinJob {
+    name: "Email digest",
+    schedule: scheduled_cron,
+    record_run,
+    run: run_scheduled,
+};
+
+fn scheduled_cron() -> Option<(String, Option<DateTime<Utc>>)> {
+    let db = EmailDb::open().ok()?;
+    let config = load_config(&db);
+    if !config.enabled {
+        return None;
+    }
+    let last_run = db
+        .get_sync_state(STATE_LAST_RUN)
+        .ok()
+        .flatten()
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    Some((config.cron, last_run))
+}
+
+fn record_run(at: DateTime<Utc>) {
+    if let Ok(db) = EmailDb::open() {
+        let _ = db.set_sync_state(STATE_LAST_RUN, &at.to_rfc3339());
+    }
+}
+
+fn run_scheduled(app_handle: tauri::AppHandle) -> JobFuture {
+    Box::pin(async move {
+        let notify = EmailDb::open().map(|db| load_config(&db).notify).unwrap_or(true);
+        let digest = generate(&app_handle, Local::now().date_naive(), notify).await?;
+        Ok(format!("Digest written to {}", digest.path))
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Generate the digest for `date` (YYYY-MM-DD, local; default today)
+#[tauri::command]
+pub async fn outlook_generate_digest(
+    app_handle: tauri::AppHandle,
+    date: Option<String>,
+    notify: Option<bool>,
+) -> CmdResult<EmailDigest> {
+    let date = match date {
+        Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+            .map_err(|e| CommandError::Parse(format!("Invalid date '{}': {}", d, e)))?,
+        None => Local::now().date_naive(),
+    };
+    generate(&app_handle, date, notify.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn outlook_digest_get_config() -> CmdResult<DigestConfig> {
+    let db = EmailDb::open()?;
+    Ok(load_config(&db))
+}
+
+#[tauri::command]
+pub async fn outlook_digest_save_config(config: DigestConfig) -> CmdResult<DigestConfig> {
+    let expr = crate::commands::scheduler::background::normalize_cron(&config.cron);
+    Schedule::from_str(&expr)
+        .map_err(|e| CommandError::Config(format!("Invalid cron '{}': {}", config.cron, e)))?;
+    let db = EmailDb::open()?;
+    db.set_sync_state(STATE_CONFIG, &serde_json::to_string(&config)?)?;
+    Ok(config)
+}
@@ -73,6 +73,40 @@ impl GraphClient {
             url.push_str(&format!("&$filter={}", urlencoding::encode(f)));
         }
 
+        self.fetch_message_pages(&token, url, max_count).await
+    }
+
+    /// Fetch messages in one folder, newest first
+    pub async fn fetch_folder_messages(
+        &self,
+        folder_id: &str,
+        max_count: usize,
+        filter: Option<&str>,
+    ) -> CmdResult<Vec<GraphMessage>> {
+        let token = self.get_token().await?;
+
+        let select = "id,conversationId,subject,from,toRecipients,ccRecipients,receivedDateTime,importance,isRead,hasAttachments,bodyPreview,parentFolderId,categories";
+        let mut url = format!(
+            "{}/me/mailFolders/{}/messages?$top=100&$orderby=receivedDateTime%20desc&$select={}",
+            GRAPH_BASE,
+            urlencoding::encode(folder_id),
+            select,
+        );
+
+        if let Some(f) = filter {
+            url.push_str(&format!("&$filter={}", urlencoding::encode(f)));
+        }
+
+        self.fetch_message_pages(&token, url, max_count).await
+    }
+
+    /// Follow @odata.nextLink until `max_count` messages are collected
+    async fn fetch_message_pages(
+        &self,
+        token: &str,
+        mut url: String,
+        max_count: usize,
+    ) -> CmdResult<Vec<GraphMessage>> {
         let mut all_messages = Vec::new();
 
         loop {
@@ -383,6 +417,98 @@ impl GraphClient {
         Ok(())
     }
 
+    // ========================================================================
+    // Change notification subscriptions
+    // ========================================================================
+
+    /// Subscribe to new mail in the inbox. Graph validates `notification_url`
+    /// synchronously, so the receiver/relay must already be reachable.
+    pub async fn create_subscription(
+        &self,
+        notification_url: &str,
+        client_state: &str,
+        expiration: &str,
+    ) -> CmdResult<GraphSubscription> {
+        let token = self.get_token().await?;
+        let url = format!("{}/subscriptions", GRAPH_BASE);
+        let body = serde_json::json!({
+            "changeType": "created",
+            "notificationUrl": notification_url,
+            "resource": "me/mailFolders('Inbox')/messages",
+            "expirationDateTime": expiration,
+            "clientState": client_state,
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status: status.as_u16(), body });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| CommandError::Parse(format!("Failed to parse subscription: {}", e)))
+    }
+
+    /// Extend a subscription's expiration
+    pub async fn renew_subscription(
+        &self,
+        subscription_id: &str,
+        expiration: &str,
+    ) -> CmdResult<GraphSubscription> {
+        let token = self.get_token().await?;
+        let url = format!("{}/subscriptions/{}", GRAPH_BASE, subscription_id);
+
+        let response = self
+            .client
+            .patch(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&serde_json::json!({ "expirationDateTime": expiration }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status: status.as_u16(), body });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| CommandError::Parse(format!("Failed to parse subscription: {}", e)))
+    }
+
+    /// Delete a subscription (404 is treated as already gone)
+    pub async fn delete_subscription(&self, subscription_id: &str) -> CmdResult<()> {
+        let token = self.get_token().await?;
+        let url = format!("{}/subscriptions/{}", GRAPH_BASE, subscription_id);
+
+        let response = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() && status.as_u16() != 404 {
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status: status.as_u16(), body });
+        }
+
+        Ok(())
+    }
+
     // ========================================================================
     // Calendar
     // ========================================================================
@@ -526,6 +652,46 @@ impl GraphClient {
         Ok((id, display_name, mail))
     }
 
+    /// Create a reply draft in the Drafts folder (does not send).
+    /// Returns the draft's message ID and Outlook web link.
+    pub async fn create_reply_draft(
+        &self,
+        message_id: &str,
+        comment_html: &str,
+    ) -> CmdResult<(String, Option<String>)> {
+        let token = self.get_token().await?;
+        let url = format!("{}/me/messages/{}/createReply", GRAPH_BASE, message_id);
+
+        let payload = serde_json::json!({
+            "comment": comment_html
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(&payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status: status.as_u16(), body });
+        }
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| CommandError::Parse(format!("Failed to parse draft response: {}", e)))?;
+
+        let draft_id = data["id"].as_str().unwrap_or("").to_string();
+        let web_link = data["webLink"].as_str().map(String::from);
+
+        Ok((draft_id, web_link))
+    }
+
     /// Reply to email
     pub async fn reply_to_email(
         &self,
@@ -0,0 +1,461 @@
+// Mail merge — personalised bulk send through Graph
+// A template with {{field}} / {{field|fallback}} placeholders is rendered per
+// recipient (CRM contact + company fields, plus any explicit fields), previewed
+// in a dry run, then sent in the background at a capped rate per minute.
+// Progress goes out on "outlook:bulk-send"; a job can be aborted mid-run.
+
+use super::compose::text_to_html;
+use super::graph::GraphClient;
+use super::types::{BulkSendJob, BulkSendResult, EmailAddress, MergePreview, MergeRecipient, MergeTemplate, RecipientStatus};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+use crate::commands::tools::docgen_theme::escape_html;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+/// Graph allows ~30 messages/minute per mailbox; stay under it by default
+const DEFAULT_PER_MINUTE: u32 = 20;
+const MAX_PER_MINUTE: u32 = 30;
+const MAX_RECIPIENTS: usize = 500;
+/// Back-off after Graph answers 429, before the single retry
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+/// How long a finished job stays queryable through outlook_bulk_send_status
+const FINISHED_JOB_TTL_SECS: i64 = 3600;
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.]+)\s*(?:\|([^}]*))?\}\}").unwrap());
+
+static JOBS: std::sync::LazyLock<Mutex<HashMap<String, BulkSendJob>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn field_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) if s.trim().is_empty() => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Replace placeholders from `fields`. Returns the text and the names of
+/// placeholders that had neither a value nor a fallback.
+pub fn render_template(template: &str, fields: &Map<String, Value>, escape: bool) -> (String, Vec<String>) {
+    let mut missing = Vec::new();
+    let rendered = PLACEHOLDER.replace_all(template, |caps: &regex::Captures| {
+        let key = &caps[1];
+        match fields.get(key).and_then(field_text) {
+            Some(v) if escape => escape_html(&v),
+            Some(v) => v,
+            None => match caps.get(2) {
+                Some(fallback) => fallback.as_str().trim().to_string(),
+                None => {
+                    if !missing.iter().any(|m| m == key) {
+                        missing.push(key.to_string());
+                    }
+                    String::new()
+                }
+            },
+        }
+    });
+    (rendered.into_owned(), missing)
+}
+
+/// Merge fields for a contact: its own columns, first_name, and the
+/// company's columns as company.<column>
+fn contact_fields(contact: &Value) -> Map<String, Value> {
+    let mut fields = Map::new();
+    if let Some(obj) = contact.as_object() {
+        for (k, v) in obj {
+            if k == "company" {
+                if let Some(company) = v.as_object() {
+                    for (ck, cv) in company {
+                        fields.insert(format!("company.{}", ck), cv.clone());
+                    }
+                    let display = company.get("display_name").and_then(field_text).or_else(|| company.get("name").and_then(field_text));
+                    if let Some(name) = display {
+                        fields.insert("company".into(), Value::String(name));
+                    }
+                }
+            } else {
+                fields.insert(k.clone(), v.clone());
+            }
+        }
+    }
+    fields
+}
+
+fn recipient_fields(recipient: &MergeRecipient, contact: Option<&Value>) -> Map<String, Value> {
+    let mut fields = contact.map(contact_fields).unwrap_or_default();
+    if let Some(email) = &recipient.email {
+        fields.insert("email".into(), Value::String(email.clone()));
+    }
+    if let Some(name) = &recipient.name {
+        fields.insert("name".into(), Value::String(name.clone()));
+    }
+    if !fields.contains_key("first_name") {
+        let first = fields
+            .get("name")
+            .and_then(field_text)
+            .and_then(|n| n.split_whitespace().next().map(String::from));
+        if let Some(first) = first {
+            fields.insert("first_name".into(), Value::String(first));
+        }
+    }
+    for (k, v) in &recipient.fields {
+        fields.insert(k.clone(), v.clone());
+    }
+    fields
+}
+
+fn build_previews(template: &MergeTemplate, recipients: &[MergeRecipient], contacts: &HashMap<String, Value>) -> Vec<MergePreview> {
+    let mut seen = HashSet::new();
+    recipients
+        .iter()
+        .map(|r| {
+            let contact = r.contact_id.as_ref().and_then(|id| contacts.get(id));
+            let fields = recipient_fields(r, contact);
+            let email = fields.get("email").and_then(field_text).unwrap_or_default().trim().to_lowercase();
+            let name = fields.get("name").and_then(field_text).unwrap_or_default();
+            let (subject, mut missing) = render_template(&template.subject, &fields, false);
+            let (body, body_missing) = render_template(&template.body, &fields, !template.plain_text);
+            for m in body_missing {
+                if !missing.contains(&m) {
+                    missing.push(m);
+                }
+            }
+            let body = if template.plain_text { text_to_html(&body) } else { body };
+            let error = if r.contact_id.is_some() && contact.is_none() {
+                Some("CRM contact not found".to_string())
+            } else if email.is_empty() || !email.contains('@') {
+                Some("No valid email address".to_string())
+            } else if !seen.insert(email.clone()) {
+                Some("Duplicate recipient".to_string())
+            } else if !missing.is_empty() {
+                Some(format!("Missing fields: {}", missing.join(", ")))
+            } else {
+                None
+            };
+            MergePreview { email, name, subject: subject.trim().to_string(), body, missing_fields: missing, error }
+        })
+        .collect()
+}
+
+async fn load_contacts(recipients: &[MergeRecipient]) -> CmdResult<HashMap<String, Value>> {
+    let ids: Vec<&str> = recipients.iter().filter_map(|r| r.contact_id.as_deref()).collect();
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let client = get_client().await?;
+    let mut contacts = HashMap::new();
+    for chunk in ids.chunks(100) {
+        let rows: Vec<Value> = client
+            .select(
+                "crm_contacts",
+                &format!("select=*,company:crm_companies(*)&id=in.({})", chunk.join(",")),
+            )
+            .await?;
+        for row in rows {
+            if let Some(id) = row.get("id").and_then(|v| v.as_str()) {
+                contacts.insert(id.to_string(), row.clone());
+            }
+        }
+    }
+    Ok(contacts)
+}
+
+// ============================================================================
+// Sending
+// ============================================================================
+
+fn update_job<T>(job_id: &str, f: impl FnOnce(&mut BulkSendJob) -> T) -> Option<T> {
+    let mut jobs = JOBS.lock().ok()?;
+    jobs.get_mut(job_id).map(f)
+}
+
+/// Drop jobs that finished more than FINISHED_JOB_TTL_SECS ago
+fn prune_finished_jobs(jobs: &mut HashMap<String, BulkSendJob>, now: chrono::DateTime<chrono::Utc>) {
+    jobs.retain(|_, j| {
+        j.finished_at
+            .as_deref()
+            .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+            .is_none_or(|at| (now - at.with_timezone(&chrono::Utc)).num_seconds() < FINISHED_JOB_TTL_SECS)
+    });
+}
+
+fn abort_requested(job_id: &str) -> bool {
+    update_job(job_id, |j| j.abort_requested).unwrap_or(true)
+}
+
+/// Sleep in one-second steps so an abort takes effect promptly.
+/// Returns false if the job was aborted while waiting.
+async fn wait(job_id: &str, duration: Duration) -> bool {
+    let mut left = duration;
+    while !left.is_zero() {
+        if abort_requested(job_id) {
+            return false;
+        }
+        let step = left.min(Duration::from_secs(1));
+        tokio::time::sleep(step).await;
+        left -= step;
+    }
+    !abort_requested(job_id)
+}
+
+fn emit_job(app: &tauri::AppHandle, job_id: &str) {
+    if let Some(job) = update_job(job_id, |j| j.clone()) {
+        let _ = app.emit("outlook:bulk-send", job);
+    }
+}
+
+fn emit_jobs_update(app: &tauri::AppHandle, job_id: &str, status: &str, message: String, started_at: &str) {
+    let _ = app.emit(
+        "jobs:update",
+        serde_json::json!({
+            "id": job_id, "name": "Mail Merge", "status": status,
+            "message": message, "startedAt": started_at,
+        }),
+    );
+}
+
+async fn send_one(graph: &GraphClient, preview: &MergePreview) -> CmdResult<()> {
+    let to = [EmailAddress { name: preview.name.clone(), email: preview.email.clone() }];
+    graph.send_email(&to, &[], &preview.subject, &preview.body).await
+}
+
+async fn run_job(app: tauri::AppHandle, job_id: String, previews: Vec<MergePreview>, per_minute: u32) {
+    let started_at = update_job(&job_id, |j| j.started_at.clone()).unwrap_or_default();
+    emit_jobs_update(&app, &job_id, "running", format!("Sending to {} recipients…", previews.len()), &started_at);
+
+    let graph = GraphClient::new();
+    let interval = Duration::from_millis(60_000 / per_minute as u64);
+    let mut first = true;
+    for (i, preview) in previews.iter().enumerate() {
+        if preview.error.is_some() {
+            continue;
+        }
+        if !first && !wait(&job_id, interval).await {
+            break;
+        }
+        if abort_requested(&job_id) {
+            break;
+        }
+        first = false;
+
+        let mut result = send_one(&graph, preview).await;
+        if matches!(result, Err(CommandError::Http { status: 429, .. })) {
+            if !wait(&job_id, RATE_LIMIT_BACKOFF).await {
+                break;
+            }
+            result = send_one(&graph, preview).await;
+        }
+        update_job(&job_id, |j| {
+            let r = &mut j.recipients[i];
+            match &result {
+                Ok(()) => {
+                    r.status = "sent".into();
+                    r.sent_at = Some(chrono::Utc::now().to_rfc3339());
+                    j.sent += 1;
+                }
+                Err(e) => {
+                    r.status = "failed".into();
+                    r.error = Some(e.to_string());
+                    j.failed += 1;
+                }
+            }
+        });
+        emit_job(&app, &job_id);
+    }
+
+    let summary = update_job(&job_id, |j| {
+        let aborted = j.abort_requested;
+        for r in j.recipients.iter_mut().filter(|r| r.status == "pending") {
+            r.status = "aborted".into();
+        }
+        j.status = if aborted { "aborted" } else { "completed" }.into();
+        j.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        (aborted, j.sent, j.failed, j.skipped)
+    });
+    emit_job(&app, &job_id);
+    if let Some((aborted, sent, failed, skipped)) = summary {
+        let message = format!("{} sent, {} failed, {} skipped{}", sent, failed, skipped, if aborted { " (aborted)" } else { "" });
+        let status = if failed > 0 && sent == 0 { "failed" } else { "completed" };
+        emit_jobs_update(&app, &job_id, status, message, &started_at);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Render `template` for every recipient. Dry run (the default) only returns
+/// the previews; with `dry_run: false` sending starts in the background at
+/// `throttle` messages per minute (default 20, max 30). Recipients with a
+/// preview error (missing fields, bad address, duplicate) are skipped.
+/// `segment_id` adds a CRM segment's members to `recipient_list`.
+#[tauri::command]
+pub async fn outlook_bulk_send(
+    app: tauri::AppHandle,
+    template: MergeTemplate,
+    recipient_list: Option<Vec<MergeRecipient>>,
+    segment_id: Option<String>,
+    throttle: Option<u32>,
+    dry_run: Option<bool>,
+) -> CmdResult<BulkSendResult> {
+    let dry_run = dry_run.unwrap_or(true);
+    let mut recipient_list = recipient_list.unwrap_or_default();
+    if let Some(segment_id) = segment_id.filter(|s| !s.is_empty()) {
+        recipient_list.extend(crate::commands::crm::segments::segment_recipients(&segment_id).await?);
+    }
+    if recipient_list.is_empty() {
+        return Err(CommandError::Config("No recipients".into()));
+    }
+    if recipient_list.len() > MAX_RECIPIENTS {
+        return Err(CommandError::Config(format!("At most {} recipients per send", MAX_RECIPIENTS)));
+    }
+    if template.subject.trim().is_empty() {
+        return Err(CommandError::Config("Subject is required".into()));
+    }
+
+    let contacts = load_contacts(&recipient_list).await?;
+    let previews = build_previews(&template, &recipient_list, &contacts);
+    if dry_run {
+        return Ok(BulkSendResult { dry_run, job_id: None, previews });
+    }
+
+    let sendable = previews.iter().filter(|p| p.error.is_none()).count();
+    if sendable == 0 {
+        return Err(CommandError::Config("No recipient can be sent to — check the preview errors".into()));
+    }
+    let per_minute = throttle.unwrap_or(DEFAULT_PER_MINUTE).clamp(1, MAX_PER_MINUTE);
+    let job_id = format!("mail-merge-{}", chrono::Utc::now().timestamp_millis());
+    let job = BulkSendJob {
+        id: job_id.clone(),
+        status: "running".into(),
+        per_minute,
+        total: previews.len(),
+        sent: 0,
+        failed: 0,
+        skipped: previews.len() - sendable,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        finished_at: None,
+        recipients: previews
+            .iter()
+            .map(|p| RecipientStatus {
+                email: p.email.clone(),
+                name: p.name.clone(),
+                status: if p.error.is_some() { "skipped" } else { "pending" }.into(),
+                error: p.error.clone(),
+                sent_at: None,
+            })
+            .collect(),
+        abort_requested: false,
+    };
+    {
+        let mut jobs = JOBS.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        prune_finished_jobs(&mut jobs, chrono::Utc::now());
+        jobs.insert(job_id.clone(), job);
+    }
+
+    tokio::spawn(run_job(app, job_id.clone(), previews.clone(), per_minute));
+    Ok(BulkSendResult { dry_run, job_id: Some(job_id), previews })
+}
+
+/// Current state of a bulk send, with per-recipient status
+#[tauri::command]
+pub async fn outlook_bulk_send_status(job_id: String) -> CmdResult<BulkSendJob> {
+    update_job(&job_id, |j| j.clone()).ok_or_else(|| CommandError::NotFound(format!("Bulk send not found: {}", job_id)))
+}
+
+/// Stop a running bulk send after the message in flight. Returns false if the
+/// job had already finished.
+#[tauri::command]
+pub async fn outlook_bulk_send_abort(job_id: String) -> CmdResult<bool> {
+    update_job(&job_id, |j| {
+        if j.status != "running" {
+            return false;
+        }
+        j.abort_requested = true;
+        true
+    })
+    .ok_or_else(|| CommandError::NotFound(format!("Bulk send not found: {}", job_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_fields_fallbacks_and_flags_missing() {
+        let contact = json!({
+            "id": "c1", "name": "Mei Ling Tan", "email": "ML@acme.sg",
+            "company": { "name": "Acme Pte Ltd", "display_name": "Acme" }
+        });
+        let recipient = MergeRecipient {
+            contact_id: Some("c1".into()),
+            fields: json!({ "renewal_date": "1 Dec 2026" }).as_object().unwrap().clone(),
+            ..Default::default()
+        };
+        let fields = recipient_fields(&recipient, Some(&contact));
+
+        let (text, missing) = render_template(
+            "Hi {{first_name}}, {{company}}'s plan renews {{ renewal_date }}. {{discount|No discount}}. {{seats}}",
+            &fields,
+            false,
+        );
+        assert_eq!(text, "Hi Mei, Acme's plan renews 1 Dec 2026. No discount. ");
+        assert_eq!(missing, vec!["seats"]);
+
+        let (html, _) = render_template("<b>{{company.name}}</b>", &json!({ "company.name": "A&B" }).as_object().unwrap().clone(), true);
+        assert_eq!(html, "<b>A&amp;B</b>");
+
+        let template = MergeTemplate { subject: "Renewal for {{company}}".into(), body: "Hi {{first_name}}".into(), plain_text: true };
+        let contacts: HashMap<String, Value> = [("c1".to_string(), contact)].into_iter().collect();
+        let previews = build_previews(&template, &[recipient.clone(), recipient], &contacts);
+        assert_eq!(previews[0].email, "ml@acme.sg");
+        assert!(previews[0].error.is_none());
+        assert_eq!(previews[1].error.as_deref(), Some("Duplicate recipient"));
+    }
+
+    #[test]
+    fn prunes_jobs_finished_over_an_hour_ago() {
+        let now = chrono::Utc::now();
+        let job = |id: &str, finished_at: Option<chrono::DateTime<chrono::Utc>>| BulkSendJob {
+            id: id.into(),
+            status: if finished_at.is_some() { "completed" } else { "running" }.into(),
+            per_minute: DEFAULT_PER_MINUTE,
+            total: 0,
+            sent: 0,
+            failed: 0,
+            skipped: 0,
+            started_at: now.to_rfc3339(),
+            finished_at: finished_at.map(|at| at.to_rfc3339()),
+            recipients: Vec::new(),
+            abort_requested: false,
+        };
+        let mut jobs: HashMap<String, BulkSendJob> = [
+            job("running", None),
+            job("recent", Some(now - chrono::Duration::minutes(5))),
+            job("stale", Some(now - chrono::Duration::hours(2))),
+        ]
+        .into_iter()
+        .map(|j| (j.id.clone(), j))
+        .collect();
+
+        prune_finished_jobs(&mut jobs, now);
+        let mut left: Vec<_> = jobs.keys().cloned().collect();
+        left.sort();
+        assert_eq!(left, vec!["recent", "running"]);
+    }
+}
@@ -1,12 +1,20 @@
 // Outlook email module - Native MS Graph integration
 // OAuth + SQLite + HTML rendering
 
+pub mod activity_capture;
 pub mod auth;
 pub mod background;
 pub mod classify;
+pub mod cleanup;
 pub mod commands;
+pub mod compose;
 pub mod contacts;
 pub mod db;
+pub mod diagnostics;
+pub mod digest;
 pub mod graph;
+pub mod mail_merge;
+pub mod push;
+pub mod senders;
 pub mod sync;
 pub mod types;
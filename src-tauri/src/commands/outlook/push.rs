@@ -0,0 +1,442 @@
+// Graph change-notification push for near-real-time mail
+// A Graph subscription posts to a public URL — either a tunnel to our local
+// receiver or a hosted relay we poll. Either way a notification just wakes
+// the incremental sync early; the 5-minute background poll stays as fallback.
+
+use super::auth;
+use super::db::EmailDb;
+use super::diagnostics::RunTimer;
+use super::graph::GraphClient;
+use super::sync;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::http_util::{read_request, respond, respond_text};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+/// Graph caps mail subscriptions at 4230 minutes; stay a bit under
+const SUBSCRIPTION_LIFETIME_MINS: i64 = 4200;
+/// Renew once fewer than this many hours remain
+const RENEW_WITHIN_HOURS: i64 = 12;
+/// How often the renewal loop checks the subscription
+const RENEW_CHECK_SECS: u64 = 30 * 60;
+/// Relay poll interval
+const RELAY_POLL_SECS: u64 = 10;
+/// Coalesce bursts of notifications into one sync
+const DEBOUNCE_MS: u64 = 2000;
+
+const STATE_CONFIG: &str = "push_config";
+const STATE_SUBSCRIPTION_ID: &str = "push_subscription_id";
+const STATE_SUBSCRIPTION_EXPIRES: &str = "push_subscription_expires";
+const STATE_CLIENT_STATE: &str = "push_client_state";
+
+/// The local receiver thread and how to stop it
+struct Receiver {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct PushRuntime {
+    receiver: Option<Receiver>,
+    notifications_received: u64,
+    last_notification_at: Option<String>,
+    last_error: Option<String>,
+}
+
+static RUNTIME: std::sync::LazyLock<Mutex<PushRuntime>> =
+    std::sync::LazyLock::new(|| Mutex::new(PushRuntime::default()));
+
+/// Woken by the receiver/relay when a valid notification arrives
+static SYNC_TRIGGER: std::sync::LazyLock<tokio::sync::Notify> =
+    std::sync::LazyLock::new(tokio::sync::Notify::new);
+
+/// Woken when config changes so the subscription is (re)created right away
+static CONFIG_CHANGED: std::sync::LazyLock<tokio::sync::Notify> =
+    std::sync::LazyLock::new(tokio::sync::Notify::new);
+
+// ============================================================================
+// Config + state helpers
+// ============================================================================
+
+fn load_config(db: &EmailDb) -> PushConfig {
+    db.get_sync_state(STATE_CONFIG)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn set_error(message: Option<String>) {
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.last_error = message;
+    }
+}
+
+/// Shared secret Graph echoes back in every notification, so we can drop spoofed posts
+fn client_state(db: &EmailDb) -> CmdResult<String> {
+    if let Some(existing) = db.get_sync_state(STATE_CLIENT_STATE)? {
+        return Ok(existing);
+    }
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| CommandError::Internal(format!("Failed to generate client state: {}", e)))?;
+    let secret: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    db.set_sync_state(STATE_CLIENT_STATE, &secret)?;
+    Ok(secret)
+}
+
+fn clear_subscription(db: &EmailDb) {
+    let _ = db.set_sync_state(STATE_SUBSCRIPTION_ID, "");
+    let _ = db.set_sync_state(STATE_SUBSCRIPTION_EXPIRES, "");
+}
+
+fn stored(db: &EmailDb, key: &str) -> Option<String> {
+    db.get_sync_state(key).ok().flatten().filter(|v| !v.is_empty())
+}
+
+/// Count valid notifications in a Graph payload (`{ "value": [...] }`) and wake the sync
+fn accept_notifications(payload: &serde_json::Value, expected_state: &str) -> usize {
+    let accepted = payload
+        .get("value")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter(|n| n.get("clientState").and_then(|s| s.as_str()) == Some(expected_state))
+                .count()
+        })
+        .unwrap_or(0);
+
+    if accepted > 0 {
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.notifications_received += accepted as u64;
+            rt.last_notification_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        SYNC_TRIGGER.notify_one();
+    }
+    accepted
+}
+
+// ============================================================================
+// Subscription lifecycle
+// ============================================================================
+
+/// Create, renew, or tear down the subscription to match current config
+async fn ensure_subscription(db: &EmailDb) -> CmdResult<()> {
+    let config = load_config(db);
+    let graph = GraphClient::new();
+    let existing_id = stored(db, STATE_SUBSCRIPTION_ID);
+
+    if !config.enabled || config.notification_url.is_empty() || auth::load_tokens().is_none() {
+        if let Some(id) = existing_id {
+            let _ = graph.delete_subscription(&id).await;
+            clear_subscription(db);
+        }
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    let expiration = (now + chrono::Duration::minutes(SUBSCRIPTION_LIFETIME_MINS)).to_rfc3339();
+
+    if let Some(id) = existing_id {
+        let expires_soon = stored(db, STATE_SUBSCRIPTION_EXPIRES)
+            .and_then(|e| chrono::DateTime::parse_from_rfc3339(&e).ok())
+            .map(|e| e.with_timezone(&chrono::Utc) - now < chrono::Duration::hours(RENEW_WITHIN_HOURS))
+            .unwrap_or(true);
+        if !expires_soon {
+            return Ok(());
+        }
+        match graph.renew_subscription(&id, &expiration).await {
+            Ok(sub) => {
+                db.set_sync_state(STATE_SUBSCRIPTION_EXPIRES, &sub.expiration_date_time)?;
+                eprintln!("[outlook:push] Renewed subscription until {}", sub.expiration_date_time);
+                return Ok(());
+            }
+            Err(e) => {
+                // Expired or deleted server-side — fall through and recreate
+                eprintln!("[outlook:push] Renew failed, recreating: {}", e);
+                clear_subscription(db);
+            }
+        }
+    }
+
+    let secret = client_state(db)?;
+    let sub = graph
+        .create_subscription(&config.notification_url, &secret, &expiration)
+        .await?;
+    db.set_sync_state(STATE_SUBSCRIPTION_ID, &sub.id)?;
+    db.set_sync_state(STATE_SUBSCRIPTION_EXPIRES, &sub.expiration_date_time)?;
+    eprintln!("[outlook:push] Created subscription {} until {}", sub.id, sub.expiration_date_time);
+    Ok(())
+}
+
+// ============================================================================
+// Receiver (local HTTP listener behind a tunnel)
+// ============================================================================
+
+fn handle_receiver_connection(mut stream: std::net::TcpStream, expected_state: &str) {
+    let Some(request) = read_request(&mut stream) else {
+        return;
+    };
+
+    // Subscription validation handshake: echo validationToken as text/plain
+    if let Some(token) = request.query_param("validationToken") {
+        respond_text(&mut stream, "200 OK", "text/plain", &token);
+        return;
+    }
+
+    // Graph retries anything that isn't 2xx, so acknowledge before doing work
+    respond(&mut stream, "202 Accepted", &serde_json::json!({}));
+
+    if let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&request.body) {
+        accept_notifications(&payload, expected_state);
+    }
+}
+
+/// Start the local receiver on `port`, replacing one running on another port
+fn start_receiver(port: u16) -> CmdResult<()> {
+    {
+        let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        if rt.receiver.as_ref().is_some_and(|r| r.port == port && !r.thread.is_finished()) {
+            return Ok(());
+        }
+    }
+    stop_receiver();
+
+    let listener = std::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+        .map_err(|e| CommandError::Io(format!("Failed to bind push receiver on port {}: {}", port, e)))?;
+    eprintln!("[outlook:push] Receiver listening on 127.0.0.1:{}", port);
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let thread = std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if thread_stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else { continue };
+            // Re-read the secret per request so a reset is picked up without a restart
+            let expected = EmailDb::open()
+                .ok()
+                .and_then(|db| stored(&db, STATE_CLIENT_STATE))
+                .unwrap_or_default();
+            if expected.is_empty() {
+                continue;
+            }
+            handle_receiver_connection(stream, &expected);
+        }
+        // Dropping the listener here releases the port
+    });
+
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.receiver = Some(Receiver { port, stop, thread });
+    }
+    Ok(())
+}
+
+/// Stop the local receiver (if running) and wait until its port is free
+fn stop_receiver() {
+    let Some(receiver) = RUNTIME.lock().ok().and_then(|mut rt| rt.receiver.take()) else {
+        return;
+    };
+    receiver.stop.store(true, Ordering::SeqCst);
+    // The thread is parked in accept(); a throwaway connection wakes it
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], receiver.port));
+    let _ = std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_secs(1));
+    let _ = receiver.thread.join();
+    eprintln!("[outlook:push] Receiver on port {} stopped", receiver.port);
+}
+
+// ============================================================================
+// Relay polling
+// ============================================================================
+
+async fn poll_relay(url: &str, expected_state: &str) -> CmdResult<usize> {
+    let response = crate::HTTP_CLIENT
+        .get(url)
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(CommandError::Http { status: status.as_u16(), body });
+    }
+
+    // Relays may return either the raw Graph envelope or a bare array of notifications
+    let payload: serde_json::Value = response.json().await?;
+    let envelope = if payload.is_array() {
+        serde_json::json!({ "value": payload })
+    } else {
+        payload
+    };
+    Ok(accept_notifications(&envelope, expected_state))
+}
+
+// ============================================================================
+// Background loops
+// ============================================================================
+
+/// Start push loops (subscription renewal, relay polling, triggered sync).
+/// Call from main.rs setup hook. Everything is a no-op until push is enabled.
+pub fn start_push(app_handle: tauri::AppHandle) {
+    // Subscription lifecycle
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(20)).await;
+        loop {
+            if let Ok(db) = EmailDb::open() {
+                let config = load_config(&db);
+                if config.enabled && config.mode == "receiver" {
+                    if let Err(e) = start_receiver(config.local_port) {
+                        set_error(Some(e.to_string()));
+                    }
+                } else {
+                    stop_receiver();
+                }
+                match ensure_subscription(&db).await {
+                    Ok(()) => set_error(None),
+                    Err(e) => {
+                        eprintln!("[outlook:push] Subscription error (falling back to polling): {}", e);
+                        set_error(Some(e.to_string()));
+                    }
+                }
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_secs(RENEW_CHECK_SECS)) => {}
+                _ = CONFIG_CHANGED.notified() => {}
+            }
+        }
+    });
+
+    // Relay polling
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(RELAY_POLL_SECS)).await;
+            let Ok(db) = EmailDb::open() else { continue };
+            let config = load_config(&db);
+            if !config.enabled || config.mode != "relay" {
+                continue;
+            }
+            let (Some(url), Some(secret)) = (config.relay_poll_url, stored(&db, STATE_CLIENT_STATE)) else {
+                continue;
+            };
+            if let Err(e) = poll_relay(&url, &secret).await {
+                eprintln!("[outlook:push] Relay poll failed: {}", e);
+            }
+        }
+    });
+
+    // Triggered incremental sync
+    tauri::async_runtime::spawn(async move {
+        loop {
+            SYNC_TRIGGER.notified().await;
+            tokio::time::sleep(std::time::Duration::from_millis(DEBOUNCE_MS)).await;
+
+            let Ok(db) = EmailDb::open() else { continue };
+            let initial_done = db
+                .get_sync_state("initial_sync_done")
+                .ok()
+                .flatten()
+                .map(|v| v == "true")
+                .unwrap_or(false);
+            if !initial_done {
+                continue;
+            }
+
+            let timer = RunTimer::start("email_incremental", "push");
+            let result = sync::run_incremental_sync(&db, &app_handle).await;
+            timer.finish(&db, &result);
+            match result {
+                Ok(count) => {
+                    if count > 0 {
+                        let _ = app_handle.emit("outlook:new-mail", serde_json::json!({ "count": count, "source": "push" }));
+                    }
+                }
+                Err(e) => eprintln!("[outlook:push] Triggered sync failed: {}", e),
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn outlook_push_get_config() -> CmdResult<PushConfig> {
+    let db = EmailDb::open()?;
+    Ok(load_config(&db))
+}
+
+#[tauri::command]
+pub async fn outlook_push_save_config(config: PushConfig) -> CmdResult<PushStatus> {
+    if config.enabled {
+        if !config.notification_url.starts_with("https://") {
+            return Err(CommandError::Config("Notification URL must be a public https:// URL".to_string()));
+        }
+        if config.mode == "relay" && config.relay_poll_url.as_deref().unwrap_or_default().is_empty() {
+            return Err(CommandError::Config("Relay mode requires a relay poll URL".to_string()));
+        }
+    }
+
+    let db = EmailDb::open()?;
+    let previous = load_config(&db);
+    db.set_sync_state(STATE_CONFIG, &serde_json::to_string(&config)?)?;
+
+    // A new URL needs a fresh subscription — Graph can't repoint an existing one
+    if previous.notification_url != config.notification_url {
+        if let Some(id) = stored(&db, STATE_SUBSCRIPTION_ID) {
+            let _ = GraphClient::new().delete_subscription(&id).await;
+        }
+        clear_subscription(&db);
+    }
+
+    if config.enabled && config.mode == "receiver" {
+        start_receiver(config.local_port)?;
+    } else {
+        stop_receiver();
+    }
+    CONFIG_CHANGED.notify_one();
+
+    outlook_push_status().await
+}
+
+#[tauri::command]
+pub async fn outlook_push_status() -> CmdResult<PushStatus> {
+    let db = EmailDb::open()?;
+    let config = load_config(&db);
+    let subscription_id = stored(&db, STATE_SUBSCRIPTION_ID);
+    let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+
+    Ok(PushStatus {
+        enabled: config.enabled,
+        mode: config.mode,
+        polling_fallback: !config.enabled || subscription_id.is_none(),
+        subscription_expires_at: stored(&db, STATE_SUBSCRIPTION_EXPIRES),
+        subscription_id,
+        receiver_running: rt.receiver.as_ref().is_some_and(|r| !r.thread.is_finished()),
+        notifications_received: rt.notifications_received,
+        last_notification_at: rt.last_notification_at.clone(),
+        last_error: rt.last_error.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopping_the_receiver_releases_its_port() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        start_receiver(port).unwrap();
+        assert!(std::net::TcpListener::bind(("127.0.0.1", port)).is_err());
+
+        stop_receiver();
+        assert!(RUNTIME.lock().unwrap().receiver.is_none());
+        assert!(std::net::TcpListener::bind(("127.0.0.1", port)).is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+// Per-sender statistics + noisy sender report
+// Ranks senders by volume with read rate and time-to-archive, and suggests
+// contact rules (auto-archive / mark as noise) that can be accepted in one call.
+
+use super::db::EmailDb;
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+
+/// Senders below this volume in the period never get a suggestion
+const MIN_VOLUME: i64 = 5;
+/// Read rate at or below which a sender counts as ignored
+const IGNORED_READ_RATE: f64 = 0.2;
+/// Archived this fast on average (hours) counts as "archived on sight"
+const QUICK_ARCHIVE_HOURS: f64 = 2.0;
+const MAX_SENDERS: i64 = 200;
+
+/// Days in a reporting period: week, month, quarter or year (or 7d/30d/90d/365d)
+pub(crate) fn period_days(period: &str) -> CmdResult<i64> {
+    match period {
+        "week" | "7d" => Ok(7),
+        "month" | "30d" => Ok(30),
+        "quarter" | "90d" => Ok(90),
+        "year" | "365d" => Ok(365),
+        other => Err(CommandError::Config(format!(
+            "Unknown period: {} (expected week, month, quarter or year)",
+            other
+        ))),
+    }
+}
+
+/// Rule suggestions for senders that are mostly ignored or archived on sight.
+/// Known contacts (client / internal / lead) and senders that already have an
+/// auto-archive rule are never suggested.
+pub fn suggest_rules(senders: &[SenderStats]) -> Vec<SenderRuleSuggestion> {
+    senders
+        .iter()
+        .filter(|s| s.volume >= MIN_VOLUME && !s.known_contact && !s.auto_archived)
+        .filter_map(|s| {
+            let archive_share = s.archived_count as f64 / s.volume as f64;
+            let quick_archive = archive_share >= 0.8
+                && s.avg_hours_to_archive.is_some_and(|h| h <= QUICK_ARCHIVE_HOURS);
+            let ignored = s.read_rate <= IGNORED_READ_RATE;
+            let mostly_noise = s.noise_count * 2 >= s.volume;
+
+            let (action, reason) = if quick_archive || (ignored && mostly_noise) {
+                let reason = if quick_archive {
+                    format!(
+                        "{} emails, {:.0}% archived within {:.1}h on average",
+                        s.volume,
+                        archive_share * 100.0,
+                        s.avg_hours_to_archive.unwrap_or_default()
+                    )
+                } else {
+                    format!("{} emails, {:.0}% read, classified as noise", s.volume, s.read_rate * 100.0)
+                };
+                ("auto_archive", reason)
+            } else if ignored && s.noise_count == 0 {
+                ("mark_noise", format!("{} emails, {:.0}% read", s.volume, s.read_rate * 100.0))
+            } else {
+                return None;
+            };
+
+            Some(SenderRuleSuggestion {
+                action: action.to_string(),
+                match_type: "email".to_string(),
+                match_value: s.email.clone(),
+                sender_name: s.name.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Sender ranking for the period ("week", "month" (default), "quarter", "year")
+#[tauri::command]
+pub async fn outlook_get_sender_stats(period: Option<String>) -> CmdResult<SenderReport> {
+    let period = period.unwrap_or_else(|| "month".to_string());
+    let since = (chrono::Utc::now() - chrono::Duration::days(period_days(&period)?))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+
+    let db = EmailDb::open()?;
+    let senders = db.get_sender_stats(&since, MAX_SENDERS)?;
+    let suggestions = suggest_rules(&senders);
+
+    Ok(SenderReport {
+        period,
+        since,
+        senders,
+        suggestions,
+    })
+}
+
+/// Accept a suggestion into the contact rules. "auto_archive" also archives the
+/// sender's mail still in the inbox; returns how many emails were archived.
+#[tauri::command]
+pub async fn outlook_accept_sender_rule(suggestion: SenderRuleSuggestion) -> CmdResult<usize> {
+    let email = suggestion.match_value.trim().to_lowercase();
+    if suggestion.match_type != "email" || email.is_empty() {
+        return Err(CommandError::Config(format!(
+            "Unsupported rule match: {} = {}",
+            suggestion.match_type, suggestion.match_value
+        )));
+    }
+
+    let db = EmailDb::open()?;
+    let match_type = match suggestion.action.as_str() {
+        "auto_archive" => "archive_email",
+        "mark_noise" => "email",
+        other => return Err(CommandError::Config(format!("Unknown rule action: {}", other))),
+    };
+    db.upsert_contact(&ContactRule {
+        match_type: match_type.to_string(),
+        match_value: email.clone(),
+        entity_type: "noise".to_string(),
+        entity_name: if suggestion.sender_name.is_empty() { email.clone() } else { suggestion.sender_name },
+        entity_path: None,
+    })?;
+
+    if match_type == "archive_email" {
+        db.archive_from_sender(&email)
+    } else {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sender(email: &str, volume: i64, read: i64, archived: i64, hours: Option<f64>, noise: i64) -> SenderStats {
+        SenderStats {
+            email: email.to_string(),
+            name: String::new(),
+            volume,
+            read_count: read,
+            read_rate: read as f64 / volume as f64,
+            archived_count: archived,
+            avg_hours_to_archive: hours,
+            noise_count: noise,
+            known_contact: false,
+            last_received_at: String::new(),
+            auto_archived: false,
+        }
+    }
+
+    #[test]
+    fn suggests_archive_for_ignored_noise_and_quick_archives() {
+        let mut client = sender("ceo@client.com", 20, 0, 0, None, 0);
+        client.known_contact = true;
+        let senders = vec![
+            sender("news@letter.io", 12, 1, 0, None, 12),
+            sender("alerts@tool.com", 10, 10, 9, Some(0.5), 0),
+            sender("cold@vendor.com", 8, 0, 0, None, 0),
+            sender("colleague@corp.com", 15, 14, 2, Some(30.0), 0),
+            sender("rare@x.com", 2, 0, 0, None, 2),
+            client,
+        ];
+        let s = suggest_rules(&senders);
+        let picked: Vec<(&str, &str)> = s.iter().map(|r| (r.match_value.as_str(), r.action.as_str())).collect();
+        assert_eq!(
+            picked,
+            vec![
+                ("news@letter.io", "auto_archive"),
+                ("alerts@tool.com", "auto_archive"),
+                ("cold@vendor.com", "mark_noise"),
+            ]
+        );
+    }
+}
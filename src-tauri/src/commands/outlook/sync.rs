@@ -15,7 +15,7 @@ use crate::commands::error::CmdResult;
 // Body storage
 // ============================================================================
 
-fn get_bodies_dir() -> PathBuf {
+pub(super) fn get_bodies_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".tv-client")
@@ -308,7 +308,7 @@ pub async fn ensure_body_cached(
 // Helpers
 // ============================================================================
 
-fn graph_message_to_entry(
+pub(super) fn graph_message_to_entry(
     msg: &GraphMessage,
     db: &EmailDb,
 ) -> CmdResult<EmailEntry> {
@@ -373,6 +373,7 @@ fn graph_message_to_entry(
     let (priority_score, priority_level) =
         calculate_priority(&classification.category, &received_at, is_read, &importance);
     let action_required = is_action_required(&classification.category, priority_score);
+    let auto_archive = db.has_archive_rule(&from_email).unwrap_or(false);
 
     // Check if body already cached
     let body_path = if read_body_file(&msg.id).is_some() {
@@ -401,7 +402,14 @@ fn graph_message_to_entry(
         priority_level,
         ai_summary: None,
         action_required,
-        status: if is_read { "read" } else { "inbox" }.to_string(),
+        status: if auto_archive {
+            "archived"
+        } else if is_read {
+            "read"
+        } else {
+            "inbox"
+        }
+        .to_string(),
         linked_company_id: classification.entity_path.clone(),
         linked_company_name: classification.entity_name,
     })
@@ -108,13 +108,330 @@ pub struct SyncProgress {
     pub message: String,
 }
 
+// ============================================================================
+// Sync diagnostics types
+// ============================================================================
+
+/// One recorded sync run or token refresh
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRun {
+    pub id: i64,
+    /// "email_initial", "email_incremental", "calendar", "token_refresh" or "folder_repair"
+    pub kind: String,
+    /// "setup", "manual", "background", "push" or "auth"
+    pub trigger: String,
+    pub started_at: String,
+    pub duration_ms: i64,
+    /// Messages or events written
+    pub items: i64,
+    pub ok: bool,
+    pub error: Option<String>,
+    /// Graph / OAuth error code, e.g. "InvalidAuthenticationToken", "invalid_grant", "HTTP 429"
+    pub error_code: Option<String>,
+}
+
+/// Rolled-up state of one kind of run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncKindHealth {
+    pub kind: String,
+    pub last_run_at: Option<String>,
+    pub last_success_at: Option<String>,
+    /// Failures since the last success (newest first)
+    pub consecutive_failures: usize,
+    pub last_error: Option<String>,
+    pub avg_duration_ms: i64,
+}
+
+/// A sync position and how old it is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCursor {
+    pub key: String,
+    pub value: Option<String>,
+    pub age_secs: Option<i64>,
+}
+
+/// Cache state of one mail folder. Mail sync advances a single received-time
+/// cursor for every folder, so a folder's freshness is its newest cached message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSyncState {
+    pub folder_id: String,
+    pub display_name: String,
+    pub cached_messages: i64,
+    pub newest_message_at: Option<String>,
+    pub newest_age_secs: Option<i64>,
+    pub last_repaired_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDiagnostics {
+    pub runs: Vec<SyncRun>,
+    pub health: Vec<SyncKindHealth>,
+    pub cursors: Vec<SyncCursor>,
+    pub folders: Vec<FolderSyncState>,
+    /// Access token expiry (None when not signed in)
+    pub token_expires_at: Option<String>,
+    pub has_refresh_token: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderRepairReport {
+    pub folder_id: String,
+    pub display_name: String,
+    /// Messages re-fetched from Graph and rewritten
+    pub refetched: i64,
+    /// Cached rows in the window that Graph no longer has in this folder
+    pub removed: usize,
+    pub since: String,
+}
+
+// ============================================================================
+// Push notification types (Graph change notifications)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushConfig {
+    pub enabled: bool,
+    /// "receiver" = local HTTP listener exposed via a tunnel,
+    /// "relay" = a hosted relay receives notifications and we poll it
+    pub mode: String,
+    /// Public HTTPS URL Graph posts notifications to
+    pub notification_url: String,
+    /// Relay mode: URL returning queued notifications as JSON
+    pub relay_poll_url: Option<String>,
+    /// Receiver mode: local port the tunnel forwards to
+    pub local_port: u16,
+}
+
+impl Default for PushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: "receiver".to_string(),
+            notification_url: String::new(),
+            relay_poll_url: None,
+            local_port: 3848,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushStatus {
+    pub enabled: bool,
+    pub mode: String,
+    pub subscription_id: Option<String>,
+    pub subscription_expires_at: Option<String>,
+    pub receiver_running: bool,
+    pub notifications_received: u64,
+    pub last_notification_at: Option<String>,
+    pub last_error: Option<String>,
+    /// True when push is unavailable and the 5-minute poll is the only source
+    pub polling_fallback: bool,
+}
+
+// ============================================================================
+// Digest types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    /// 5-field cron in UTC like scheduler automations, e.g. "0 8 * * MON-FRI"
+    pub cron: String,
+    pub notify: bool,
+    /// Folder for digest markdown; defaults to ~/.tv-client/outlook/digests
+    pub output_folder: Option<String>,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: "0 8 * * MON-FRI".to_string(),
+            notify: true,
+            output_folder: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailDigest {
+    pub date: String,
+    pub path: String,
+    pub markdown: String,
+    pub important_count: usize,
+    pub awaiting_reply_count: usize,
+    pub follow_up_count: usize,
+    pub deal_count: usize,
+}
+
+// ============================================================================
+// Cache cleanup types
+// ============================================================================
+
+/// Retention for the local cache. Headers are always kept; bodies re-fetch on open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CleanupPolicy {
+    /// Run on `cron` in the background
+    pub enabled: bool,
+    /// 5-field cron in UTC
+    pub cron: String,
+    /// Drop cached bodies for mail older than this many months (0 = keep all)
+    pub keep_body_months: u32,
+    /// Strip inline attachments larger than this from kept bodies (None = keep all)
+    pub max_attachment_kb: Option<u64>,
+    /// Compact the database after cleaning
+    pub vacuum: bool,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: "0 3 * * SUN".to_string(),
+            keep_body_months: 6,
+            max_attachment_kb: Some(1024),
+            vacuum: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    /// Bodies past the retention window
+    pub bodies_removed: usize,
+    pub body_bytes: u64,
+    /// Body files no email points at any more
+    pub orphans_removed: usize,
+    pub orphan_bytes: u64,
+    /// Inline attachments stripped from kept bodies
+    pub attachments_purged: usize,
+    pub attachment_bytes: u64,
+    /// Free database pages reclaimable by VACUUM
+    pub database_bytes: u64,
+    pub total_bytes: u64,
+}
+
+// ============================================================================
+// Activity capture types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActivityCaptureConfig {
+    pub enabled: bool,
+    pub capture_meetings: bool,
+    pub capture_emails: bool,
+    /// Shorter meetings are skipped
+    pub min_meeting_minutes: i64,
+    /// Messages a conversation needs (in both directions) to count as an exchange
+    pub min_exchange_messages: usize,
+    /// Participants on these domains never trigger a capture (own domains are always excluded)
+    pub excluded_domains: Vec<String>,
+    pub lookback_days: i64,
+}
+
+impl Default for ActivityCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capture_meetings: true,
+            capture_emails: true,
+            min_meeting_minutes: 15,
+            min_exchange_messages: 3,
+            excluded_domains: Vec::new(),
+            lookback_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedActivity {
+    /// "meeting" or "email"
+    pub kind: String,
+    pub company_id: String,
+    pub company_name: String,
+    pub subject: String,
+    pub activity_date: String,
+    pub source_ref: String,
+    /// "logged", "duplicate" or "preview" (dry run)
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCaptureResult {
+    pub dry_run: bool,
+    pub meetings_logged: usize,
+    pub emails_logged: usize,
+    pub duplicates: usize,
+    /// Candidates with no matching CRM company
+    pub unmatched: usize,
+    pub activities: Vec<CapturedActivity>,
+}
+
+// ============================================================================
+// Sender stats types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderStats {
+    pub email: String,
+    pub name: String,
+    pub volume: i64,
+    pub read_count: i64,
+    pub read_rate: f64,
+    pub archived_count: i64,
+    /// Average hours from receipt to archive, over emails archived in-app
+    pub avg_hours_to_archive: Option<f64>,
+    pub noise_count: i64,
+    /// Any email from this sender classified as client / internal / lead
+    pub known_contact: bool,
+    pub last_received_at: String,
+    /// Sender already has an auto-archive rule
+    pub auto_archived: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderRuleSuggestion {
+    pub action: String, // "auto_archive" | "mark_noise"
+    pub match_type: String, // "email"
+    pub match_value: String,
+    pub sender_name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderReport {
+    pub period: String,
+    pub since: String,
+    pub senders: Vec<SenderStats>,
+    pub suggestions: Vec<SenderRuleSuggestion>,
+}
+
 // ============================================================================
 // Contact types
 // ============================================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactRule {
-    pub match_type: String,  // "email", "domain", "noise_domain"
+    pub match_type: String,  // "email", "domain", "noise_domain", "archive_email"
     pub match_value: String,
     pub entity_type: String, // "client", "internal", "vendor", "noise"
     pub entity_name: String,
@@ -154,6 +471,17 @@ pub struct GraphTokenResponse {
     pub error_description: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphSubscription {
+    pub id: String,
+    pub resource: String,
+    pub change_type: String,
+    pub notification_url: String,
+    pub expiration_date_time: String,
+    pub client_state: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GraphMessageList {
     pub value: Vec<GraphMessage>,
@@ -423,3 +751,78 @@ pub struct GraphOnlineMeeting {
     #[serde(rename = "joinUrl")]
     pub join_url: Option<String>,
 }
+
+// ============================================================================
+// Mail merge
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTemplate {
+    pub subject: String,
+    pub body: String,
+    /// Body is plain text (converted to HTML paragraphs) rather than HTML
+    #[serde(default)]
+    pub plain_text: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRecipient {
+    /// CRM contact to pull name, email and company fields from
+    pub contact_id: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    /// Extra merge fields; override CRM values with the same key
+    #[serde(default)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreview {
+    pub email: String,
+    pub name: String,
+    pub subject: String,
+    pub body: String,
+    /// Placeholders with no value and no fallback; these recipients are skipped
+    pub missing_fields: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipientStatus {
+    pub email: String,
+    pub name: String,
+    pub status: String, // pending | sent | failed | skipped | aborted
+    pub error: Option<String>,
+    pub sent_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSendJob {
+    pub id: String,
+    pub status: String, // running | completed | aborted
+    pub per_minute: u32,
+    pub total: usize,
+    pub sent: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub recipients: Vec<RecipientStatus>,
+    #[serde(skip)]
+    pub abort_requested: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSendResult {
+    pub dry_run: bool,
+    /// Set when sending started; poll outlook_bulk_send_status or listen for
+    /// "outlook:bulk-send"
+    pub job_id: Option<String>,
+    pub previews: Vec<MergePreview>,
+}
@@ -0,0 +1,447 @@
+// Command palette backend — registry of invokable app actions + fuzzy search
+//
+// Each action names the Tauri command it runs and the args that command
+// takes (camelCase, as passed to `invoke`). New backend capabilities become
+// palette entries by adding a row to ACTIONS; a test checks every row still
+// points at a command registered in main.rs.
+
+use serde::Serialize;
+use tauri::command;
+
+use crate::commands::error::CmdResult;
+
+const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteArg {
+    pub name: &'static str,
+    /// string | boolean | number | path | domain | date
+    pub kind: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteAction {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// Frontend ModuleId the action belongs to
+    pub module: &'static str,
+    /// Tauri command to invoke
+    pub command: &'static str,
+    pub args: &'static [PaletteArg],
+    pub keywords: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteMatch {
+    pub action: PaletteAction,
+    pub score: i64,
+    /// title | keyword | module | command
+    pub matched_field: &'static str,
+    /// Char positions in the title to highlight (empty unless the title matched)
+    pub title_matches: Vec<usize>,
+}
+
+const fn arg(name: &'static str, kind: &'static str, required: bool, description: &'static str) -> PaletteArg {
+    PaletteArg { name, kind, required, description }
+}
+
+const DOMAIN: PaletteArg = arg("domain", "domain", true, "VAL domain");
+const DRY_RUN: PaletteArg = arg("dryRun", "boolean", false, "Preview without making changes");
+
+pub static ACTIONS: &[PaletteAction] = &[
+    // Inbox / calendar
+    PaletteAction {
+        id: "outlook.sync",
+        title: "Sync Outlook mail",
+        description: "Fetch new and changed messages from Microsoft 365",
+        module: "inbox",
+        command: "outlook_sync_start",
+        args: &[],
+        keywords: &["email", "refresh", "inbox", "fetch"],
+    },
+    PaletteAction {
+        id: "outlook.calendar-sync",
+        title: "Sync Outlook calendar",
+        description: "Fetch calendar events from Microsoft 365",
+        module: "calendar",
+        command: "outlook_calendar_sync_start",
+        args: &[],
+        keywords: &["events", "meetings", "refresh"],
+    },
+    PaletteAction {
+        id: "outlook.digest",
+        title: "Generate daily email digest",
+        description: "Summarize a day's mail into a digest",
+        module: "inbox",
+        command: "outlook_generate_digest",
+        args: &[
+            arg("date", "date", false, "Day to summarize (default today)"),
+            arg("notify", "boolean", false, "Send a notification when ready"),
+        ],
+        keywords: &["summary", "email"],
+    },
+    PaletteAction {
+        id: "outlook.cleanup",
+        title: "Run mailbox cache cleanup",
+        description: "Apply the retention policy to the local mail cache",
+        module: "inbox",
+        command: "outlook_run_cleanup",
+        args: &[DRY_RUN],
+        keywords: &["retention", "purge", "email"],
+    },
+    PaletteAction {
+        id: "outlook.capture-activities",
+        title: "Capture CRM activities from email",
+        description: "Log emails and meetings with CRM contacts as activities",
+        module: "crm",
+        command: "outlook_activity_capture_run",
+        args: &[DRY_RUN],
+        keywords: &["log", "meetings", "contacts"],
+    },
+    // CRM / work
+    PaletteAction {
+        id: "crm.hubspot-import",
+        title: "Import from HubSpot",
+        description: "Pull companies, contacts, deals and notes from HubSpot",
+        module: "crm",
+        command: "crm_hubspot_import",
+        args: &[arg("full", "boolean", false, "Ignore the last pull time and import everything"), DRY_RUN],
+        keywords: &["sync", "deals", "contacts"],
+    },
+    PaletteAction {
+        id: "crm.recompute-segment",
+        title: "Recompute CRM segment",
+        description: "Re-evaluate a segment's filter and refresh its members",
+        module: "crm",
+        command: "crm_recompute_segment",
+        args: &[arg("segmentId", "string", true, "Segment to recompute")],
+        keywords: &["icp", "list", "audience", "refresh"],
+    },
+    PaletteAction {
+        id: "work.project-digests",
+        title: "Send weekly project digests",
+        description: "Email each opted-in user a summary of their projects",
+        module: "work",
+        command: "work_send_project_digests",
+        args: &[arg("force", "boolean", false, "Send even if already sent this week")],
+        keywords: &["email", "summary", "weekly"],
+    },
+    PaletteAction {
+        id: "notion.sync",
+        title: "Sync Notion",
+        description: "Run the configured Notion database syncs",
+        module: "work",
+        command: "notion_sync_start",
+        args: &[],
+        keywords: &["import", "refresh"],
+    },
+    // VAL domains
+    PaletteAction {
+        id: "val.sync-all",
+        title: "Sync VAL domain",
+        description: "Fetch fields, queries, workflows, dashboards and tables",
+        module: "domains",
+        command: "val_sync_all",
+        args: &[DOMAIN],
+        keywords: &["refresh", "pull", "definitions"],
+    },
+    PaletteAction {
+        id: "val.extract-workflows",
+        title: "Extract workflow definitions",
+        description: "Write workflow definitions to disk and record versions",
+        module: "domains",
+        command: "val_extract_workflows",
+        args: &[DOMAIN],
+        keywords: &["plugins", "versions"],
+    },
+    PaletteAction {
+        id: "val.compute-dependencies",
+        title: "Compute domain dependencies",
+        description: "Rebuild the table/query/workflow dependency graph",
+        module: "domains",
+        command: "val_compute_dependencies",
+        args: &[DOMAIN],
+        keywords: &["lineage", "graph", "impact"],
+    },
+    PaletteAction {
+        id: "val.recent-changes",
+        title: "Show recent VAL changes",
+        description: "Which artifacts changed and who changed them",
+        module: "domains",
+        command: "val_get_recent_changes",
+        args: &[DOMAIN, arg("since", "date", false, "Cut-off date (default: latest extract runs)")],
+        keywords: &["audit", "who", "history"],
+    },
+    PaletteAction {
+        id: "val.context-pack",
+        title: "Generate AI context pack",
+        description: "Bundle a domain's schema and SQL for an AI assistant",
+        module: "domains",
+        command: "val_generate_context_pack",
+        args: &[
+            DOMAIN,
+            arg("scope", "string", false, "Limit to part of the domain"),
+            arg("tablePattern", "string", false, "Only tables matching this pattern"),
+            arg("maxTokens", "number", false, "Token budget"),
+            arg("outputDir", "path", false, "Where to write the pack"),
+        ],
+        keywords: &["llm", "prompt", "schema"],
+    },
+    PaletteAction {
+        id: "val.data-dictionary",
+        title: "Export data dictionary",
+        description: "Export table and column descriptions for a domain",
+        module: "domains",
+        command: "val_export_data_dictionary",
+        args: &[
+            DOMAIN,
+            arg("format", "string", false, "Output format"),
+            arg("outputPath", "path", false, "File to write"),
+        ],
+        keywords: &["schema", "columns", "docs"],
+    },
+    // Library / knowledge
+    PaletteAction {
+        id: "knowledge.generate-index",
+        title: "Generate folder index pages",
+        description: "Write _index.md navigation pages for a knowledge folder",
+        module: "library",
+        command: "knowledge_generate_index",
+        args: &[
+            arg("folder", "path", true, "Knowledge folder"),
+            arg("recursive", "boolean", false, "Include subfolders"),
+            arg("watch", "boolean", false, "Regenerate as files change"),
+        ],
+        keywords: &["toc", "navigation", "contents"],
+    },
+    PaletteAction {
+        id: "knowledge.validate-markdown",
+        title: "Lint markdown docs",
+        description: "Check frontmatter, headings and relative links",
+        module: "library",
+        command: "files_validate_markdown",
+        args: &[arg("path", "path", true, "Folder or file to check")],
+        keywords: &["validate", "frontmatter", "broken links"],
+    },
+    PaletteAction {
+        id: "knowledge.near-duplicates",
+        title: "Find near-duplicate documents",
+        description: "Pairs of knowledge docs with near-identical content",
+        module: "library",
+        command: "knowledge_find_near_duplicates",
+        args: &[arg("threshold", "number", false, "Similarity cut-off (default 0.95)")],
+        keywords: &["similar", "copies", "dedupe"],
+    },
+    PaletteAction {
+        id: "knowledge.link-graph",
+        title: "Show knowledge link graph",
+        description: "Documents and the links between them",
+        module: "library",
+        command: "get_link_graph",
+        args: &[arg("root", "path", false, "Folder (default: whole knowledge base)")],
+        keywords: &["backlinks", "wiki", "references"],
+    },
+    PaletteAction {
+        id: "files.find-duplicates",
+        title: "Find duplicate files",
+        description: "Byte-identical files under a folder and the space they waste",
+        module: "library",
+        command: "find_duplicates",
+        args: &[arg("root", "path", true, "Folder to scan")],
+        keywords: &["dedupe", "disk space", "copies"],
+    },
+    // App
+    PaletteAction {
+        id: "backup.run",
+        title: "Back up knowledge now",
+        description: "Run the knowledge backup immediately",
+        module: "settings",
+        command: "backup_run_now",
+        args: &[],
+        keywords: &["snapshot", "archive"],
+    },
+    PaletteAction {
+        id: "diagnostics.collect",
+        title: "Collect support bundle",
+        description: "Zip logs and settings (secrets redacted) for support",
+        module: "settings",
+        command: "diagnostics_collect",
+        args: &[arg("outputPath", "path", true, "Where to save the bundle")],
+        keywords: &["logs", "debug", "help"],
+    },
+    PaletteAction {
+        id: "mcp.sync-tools",
+        title: "Sync MCP tool registry",
+        description: "Refresh the MCP tools list from tv-mcp",
+        module: "mcp-tools",
+        command: "sync_mcp_tools_command",
+        args: &[],
+        keywords: &["tools", "refresh"],
+    },
+];
+
+// ============================================================================
+// Fuzzy matching
+// ============================================================================
+
+fn is_boundary(text: &[char], i: usize) -> bool {
+    match i.checked_sub(1).map(|p| text[p]) {
+        None => true,
+        Some(prev) => !prev.is_alphanumeric() || (prev.is_lowercase() && text[i].is_uppercase()),
+    }
+}
+
+/// Score `query` as an in-order subsequence of `text` (case-insensitive,
+/// whitespace in the query ignored). Rewards word starts and consecutive
+/// runs, penalizes gaps. Returns the score and matched char positions.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let q: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    if q.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    let (m, n) = (q.len(), chars.len());
+    if m > n {
+        return None;
+    }
+
+    let bonus = |j: usize| if is_boundary(&chars, j) { 9 } else { 1 };
+    const NONE: i64 = i64::MIN / 2;
+    // best[i][j]: best score with q[i] matched at text position j
+    let mut best = vec![vec![NONE; n]; m];
+    let mut from = vec![vec![0usize; n]; m];
+    for (j, c) in lower.iter().enumerate() {
+        if *c == q[0] {
+            best[0][j] = bonus(j) - j.min(5) as i64;
+        }
+    }
+    for i in 1..m {
+        for j in i..n {
+            if lower[j] != q[i] {
+                continue;
+            }
+            for k in (i - 1)..j {
+                if best[i - 1][k] == NONE {
+                    continue;
+                }
+                let step = if k + 1 == j { 6 } else { -((j - k - 1).min(3) as i64) };
+                let score = best[i - 1][k] + bonus(j) + step;
+                if score > best[i][j] {
+                    best[i][j] = score;
+                    from[i][j] = k;
+                }
+            }
+        }
+    }
+
+    let (mut j, score) = best[m - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| **s != NONE)
+        .max_by_key(|(j, s)| (**s, std::cmp::Reverse(*j)))
+        .map(|(j, s)| (j, *s))?;
+    let mut positions = vec![j];
+    for i in (1..m).rev() {
+        j = from[i][j];
+        positions.push(j);
+    }
+    positions.reverse();
+    let prefix = lower.iter().take(m).eq(q.iter());
+    Some((score + 10 * m as i64 + if prefix { 20 } else { 0 }, positions))
+}
+
+fn match_action(query: &str, action: &PaletteAction) -> Option<PaletteMatch> {
+    let mut best: Option<(i64, &'static str, Vec<usize>)> = None;
+    let mut consider = |field: &'static str, text: &str, weight: (i64, i64)| {
+        if let Some((score, positions)) = fuzzy_match(query, text) {
+            let weighted = score * weight.0 / weight.1;
+            if best.as_ref().map_or(true, |(b, _, _)| weighted > *b) {
+                let positions = if field == "title" { positions } else { Vec::new() };
+                best = Some((weighted, field, positions));
+            }
+        }
+    };
+    consider("title", action.title, (1, 1));
+    for keyword in action.keywords {
+        consider("keyword", keyword, (2, 3));
+    }
+    consider("module", action.module, (1, 2));
+    consider("command", action.command, (1, 2));
+
+    best.map(|(score, matched_field, title_matches)| PaletteMatch {
+        action: action.clone(),
+        score,
+        matched_field,
+        title_matches,
+    })
+}
+
+/// Rank actions against `query`, best first (ties by title)
+pub fn search_actions(query: &str, module: Option<&str>, limit: usize) -> Vec<PaletteMatch> {
+    let mut matches: Vec<PaletteMatch> = ACTIONS
+        .iter()
+        .filter(|a| module.map_or(true, |m| a.module == m))
+        .filter_map(|a| match_action(query, a))
+        .collect();
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.action.title.cmp(b.action.title)));
+    matches.truncate(limit);
+    matches
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Every registered palette action
+#[command]
+pub fn palette_list_actions() -> CmdResult<Vec<PaletteAction>> {
+    Ok(ACTIONS.to_vec())
+}
+
+/// Fuzzy-ranked actions for the palette's query. An empty query returns
+/// everything (optionally limited to one module) in title order.
+#[command]
+pub fn palette_search(query: String, module: Option<String>, limit: Option<usize>) -> CmdResult<Vec<PaletteMatch>> {
+    Ok(search_actions(
+        query.trim(),
+        module.as_deref().filter(|m| !m.is_empty()),
+        limit.unwrap_or(DEFAULT_LIMIT),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn ranks_word_starts_and_prefixes() {
+        let (_, positions) = fuzzy_match("sol", "Sync Outlook mail").unwrap();
+        assert_eq!(positions, vec![0, 5, 8]);
+        assert!(fuzzy_match("xyz", "Sync Outlook mail").is_none());
+
+        assert_eq!(search_actions("backup", None, 5)[0].action.id, "backup.run");
+        assert_eq!(search_actions("cal sync", None, 5)[0].action.id, "outlook.calendar-sync");
+        let crm = search_actions("", Some("crm"), 50);
+        assert!(!crm.is_empty() && crm.iter().all(|m| m.action.module == "crm"));
+    }
+
+    #[test]
+    fn registry_points_at_registered_commands() {
+        let main_rs = include_str!("../main.rs");
+        let mut ids = HashSet::new();
+        for action in ACTIONS {
+            assert!(ids.insert(action.id), "duplicate palette id {}", action.id);
+            assert!(
+                main_rs.contains(&format!("::{},", action.command)),
+                "{} runs {}, which isn't in generate_handler!",
+                action.id,
+                action.command
+            );
+        }
+    }
+}
@@ -4,6 +4,7 @@ use chrono::Utc;
 use cron::Schedule;
 use std::str::FromStr;
 
+use super::builtin;
 use super::runner;
 use super::storage;
 use super::types::RunTrigger;
@@ -18,6 +19,7 @@ pub fn start_scheduler(app_handle: tauri::AppHandle, default_reports_folder: Str
 
         loop {
             check_and_run_automations(&app_handle, &default_reports_folder).await;
+            builtin::check_builtin_jobs(&app_handle, &Utc::now());
             // Poll every 60s
             tokio::time::sleep(std::time::Duration::from_secs(60)).await;
         }
@@ -107,7 +109,7 @@ async fn check_and_run_automations(app_handle: &tauri::AppHandle, default_report
 }
 
 /// Check if the cron schedule matches the current minute and automation hasn't run this minute
-fn should_run_now(
+pub(super) fn should_run_now(
     schedule: &Schedule,
     last_run_at: &Option<chrono::DateTime<Utc>>,
     now: &chrono::DateTime<Utc>,
@@ -138,7 +140,7 @@ fn should_run_now(
 }
 
 /// Normalize a 5-field cron expression to 6-field (add seconds) for the cron crate
-fn normalize_cron(expr: &str) -> String {
+pub(crate) fn normalize_cron(expr: &str) -> String {
     let fields: Vec<&str> = expr.trim().split_whitespace().collect();
     match fields.len() {
         5 => format!("0 {}", expr.trim()), // Add "0" seconds prefix
@@ -146,3 +148,13 @@ fn normalize_cron(expr: &str) -> String {
         _ => expr.to_string(),             // Let cron crate handle the error
     }
 }
+
+/// `should_run_now` for the single-job schedules that keep their own
+/// last-run stamp (backup, email cleanup): the cron is read in UTC, like
+/// automations.
+pub(crate) fn is_due(cron: &str, last_run: Option<chrono::DateTime<chrono::FixedOffset>>) -> bool {
+    let Ok(schedule) = Schedule::from_str(&normalize_cron(cron)) else {
+        return false;
+    };
+    should_run_now(&schedule, &last_run.map(|lr| lr.with_timezone(&Utc)), &Utc::now())
+}
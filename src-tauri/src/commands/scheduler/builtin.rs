@@ -0,0 +1,73 @@
+// Built-in jobs — app features that run on a cron (e.g. the Outlook email
+// digest) rather than as claude -p automations. They're checked on the
+// scheduler's 60s tick with the same UTC cron matching as automations, and
+// report through the same "jobs:update" events. Each job keeps its own config
+// and last-run stamp.
+
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use tauri::Emitter;
+
+use super::background::{normalize_cron, should_run_now};
+use crate::commands::error::CmdResult;
+
+pub type JobFuture = Pin<Box<dyn Future<Output = CmdResult<String>> + Send>>;
+
+pub struct BuiltinJob {
+    /// Shown as "Scheduled: <name>" in the jobs panel
+    pub name: &'static str,
+    /// Cron expression and last run, or None while the job is turned off
+    pub schedule: fn() -> Option<(String, Option<DateTime<Utc>>)>,
+    /// Stamp the run before it starts, so the next tick doesn't start it again
+    pub record_run: fn(DateTime<Utc>),
+    /// Do the work; Ok carries the completion message
+    pub run: fn(tauri::AppHandle) -> JobFuture,
+}
+
+const BUILTIN_JOBS: &[BuiltinJob] = &[crate::commands::outlook::digest::DIGEST_JOB];
+
+/// Start every enabled built-in job whose cron fired this minute
+pub(super) fn check_builtin_jobs(app_handle: &tauri::AppHandle, now: &DateTime<Utc>) {
+    for job in BUILTIN_JOBS {
+        let Some((cron_expr, last_run_at)) = (job.schedule)() else {
+            continue;
+        };
+        let schedule = match Schedule::from_str(&normalize_cron(&cron_expr)) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("[scheduler] Invalid cron '{}' for built-in job '{}': {}", cron_expr, job.name, e);
+                continue;
+            }
+        };
+        if !should_run_now(&schedule, &last_run_at, now) {
+            continue;
+        }
+
+        eprintln!("[scheduler] Triggering built-in job: {}", job.name);
+        (job.record_run)(*now);
+        let handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let tracking_id = format!("builtin-{}-{}", job.name, Utc::now().timestamp_millis());
+            let started_at = Utc::now().to_rfc3339();
+            let display_name = format!("Scheduled: {}", job.name);
+            let _ = handle.emit("jobs:update", serde_json::json!({
+                "id": &tracking_id, "name": &display_name, "status": "running",
+                "message": format!("Running scheduled: {}", job.name), "startedAt": &started_at,
+            }));
+            let (status, message) = match (job.run)(handle.clone()).await {
+                Ok(message) => ("completed", message),
+                Err(e) => {
+                    eprintln!("[scheduler] Built-in job '{}' failed: {}", job.name, e);
+                    ("failed", format!("{} failed: {}", job.name, e))
+                }
+            };
+            let _ = handle.emit("jobs:update", serde_json::json!({
+                "id": &tracking_id, "name": &display_name, "status": status,
+                "message": message, "startedAt": &started_at,
+            }));
+        });
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod action;
 pub mod background;
+pub mod builtin;
 pub mod commands;
 pub mod runner;
 pub mod storage;
@@ -0,0 +1,245 @@
+// src-tauri/src/commands/search/filters.rs
+// Structured filters shared by filename and content search: extensions,
+// size bounds, modified-date ranges and path prefixes, e.g. "markdown files
+// edited this week under 0_Platform/"
+
+use crate::commands::error::{CmdResult, CommandError};
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Every field is optional; unset fields don't filter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFilters {
+    /// Case-insensitive, with or without the dot ("md", ".MD")
+    pub extensions: Option<Vec<String>>,
+    /// Bytes, inclusive
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    /// RFC 3339 timestamp or YYYY-MM-DD (local midnight)
+    pub modified_after: Option<String>,
+    pub modified_before: Option<String>,
+    /// Relative window ending now: "30m", "12h", "7d", "2w", or "today",
+    /// "this week" (since Monday), "this month"
+    pub modified_within: Option<String>,
+    /// Only paths under one of these; relative prefixes are taken from the search root
+    pub path_prefixes: Option<Vec<String>>,
+}
+
+/// `SearchFilters` checked and ready to apply
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedFilters {
+    extensions: Vec<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<SystemTime>,
+    modified_before: Option<SystemTime>,
+    prefixes: Vec<PathBuf>,
+}
+
+fn invalid(field: &str, message: String) -> CommandError {
+    CommandError::Validation { message, fields: vec![format!("filters.{}", field)] }
+}
+
+fn local_midnight(date: NaiveDate) -> Option<SystemTime> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+fn parse_instant(field: &str, value: &str) -> CmdResult<SystemTime> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(SystemTime::from(dt));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(local_midnight)
+        .ok_or_else(|| invalid(field, format!("Invalid date '{}' (use YYYY-MM-DD or an RFC 3339 timestamp)", value)))
+}
+
+/// Start of a `modified_within` window
+fn window_start(value: &str, now: DateTime<Local>) -> CmdResult<SystemTime> {
+    let spec = value.trim().to_lowercase();
+    let today = now.date_naive();
+    let calendar_start = match spec.replace('_', " ").as_str() {
+        "today" => Some(today),
+        "this week" => Some(today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64)),
+        "this month" => today.with_day(1),
+        _ => None,
+    };
+    if let Some(start) = calendar_start {
+        return local_midnight(start).ok_or_else(|| invalid("modified_within", format!("Invalid window '{}'", value)));
+    }
+
+    let unit_at = spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len());
+    let (count, unit) = spec.split_at(unit_at);
+    let count: u64 = count.parse().map_err(|_| {
+        invalid("modified_within", format!("Invalid window '{}' (use 30m, 12h, 7d, 2w, today, this week or this month)", value))
+    })?;
+    let secs = match unit.trim() {
+        "m" | "min" | "mins" => 60,
+        "h" | "hr" | "hrs" => 3_600,
+        "d" | "day" | "days" => 86_400,
+        "w" | "wk" | "week" | "weeks" => 7 * 86_400,
+        _ => return Err(invalid("modified_within", format!("Unknown unit in '{}' (m, h, d or w)", value))),
+    };
+    Ok(SystemTime::from(now) - Duration::from_secs(count * secs))
+}
+
+impl SearchFilters {
+    /// Validate against `root`: bad dates, windows and reversed ranges are validation errors
+    pub fn resolve(&self, root: &Path) -> CmdResult<ResolvedFilters> {
+        self.resolve_at(root, Local::now())
+    }
+
+    fn resolve_at(&self, root: &Path, now: DateTime<Local>) -> CmdResult<ResolvedFilters> {
+        if let (Some(min), Some(max)) = (self.min_size, self.max_size) {
+            if min > max {
+                return Err(invalid("min_size", format!("min_size ({}) is larger than max_size ({})", min, max)));
+            }
+        }
+
+        let mut modified_after = self.modified_after.as_deref().map(|v| parse_instant("modified_after", v)).transpose()?;
+        if let Some(within) = self.modified_within.as_deref() {
+            let start = window_start(within, now)?;
+            // Both given: the later start wins
+            modified_after = Some(modified_after.map_or(start, |after| after.max(start)));
+        }
+        let modified_before = self.modified_before.as_deref().map(|v| parse_instant("modified_before", v)).transpose()?;
+        if let (Some(after), Some(before)) = (modified_after, modified_before) {
+            if after > before {
+                return Err(invalid("modified_before", "modified_before is earlier than the modified-after bound".to_string()));
+            }
+        }
+
+        Ok(ResolvedFilters {
+            extensions: self
+                .extensions
+                .iter()
+                .flatten()
+                .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+                .filter(|e| !e.is_empty())
+                .collect(),
+            min_size: self.min_size,
+            max_size: self.max_size,
+            modified_after,
+            modified_before,
+            prefixes: self
+                .path_prefixes
+                .iter()
+                .flatten()
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .map(|p| {
+                    let p = Path::new(p);
+                    if p.is_absolute() { p.to_path_buf() } else { root.join(p) }
+                })
+                .collect(),
+        })
+    }
+}
+
+impl ResolvedFilters {
+    pub fn extensions(&self) -> Option<&[String]> {
+        if self.extensions.is_empty() {
+            None
+        } else {
+            Some(&self.extensions)
+        }
+    }
+
+    /// Whether the walk should enter / keep `path`: inside a prefix, or a
+    /// folder on the way to one
+    pub fn allows_path(&self, path: &Path) -> bool {
+        self.prefixes.is_empty() || self.prefixes.iter().any(|p| path.starts_with(p) || p.starts_with(path))
+    }
+
+    /// Checks that need no metadata (extension, prefix)
+    pub fn matches_path(&self, path: &Path) -> bool {
+        let ext_ok = self.extensions.is_empty()
+            || path
+                .extension()
+                .is_some_and(|e| self.extensions.contains(&e.to_string_lossy().to_lowercase()));
+        ext_ok && (self.prefixes.is_empty() || self.prefixes.iter().any(|p| path.starts_with(p)))
+    }
+
+    pub fn needs_metadata(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some() || self.modified_after.is_some() || self.modified_before.is_some()
+    }
+
+    /// Size and modified-date checks; a file whose metadata can't be read only
+    /// passes when no such filter is set
+    pub fn matches_metadata(&self, metadata: Option<&Metadata>) -> bool {
+        if !self.needs_metadata() {
+            return true;
+        }
+        let Some(meta) = metadata else {
+            return false;
+        };
+        self.matches_values(meta.len(), meta.modified().ok())
+    }
+
+    fn matches_values(&self, size: u64, modified: Option<SystemTime>) -> bool {
+        if self.min_size.is_some_and(|min| size < min) || self.max_size.is_some_and(|max| size > max) {
+            return false;
+        }
+        if self.modified_after.is_none() && self.modified_before.is_none() {
+            return true;
+        }
+        let Some(modified) = modified else {
+            return false;
+        };
+        let too_early = self.modified_after.is_some_and(|after| modified < after);
+        let too_late = self.modified_before.is_some_and(|before| modified > before);
+        !too_early && !too_late
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_and_applies_filters() {
+        let root = Path::new("/kb");
+        // Friday 16 Oct 2026, 15:00 local
+        let now = Local.with_ymd_and_hms(2026, 10, 16, 15, 0, 0).unwrap();
+        let filters = SearchFilters {
+            extensions: Some(vec![".MD".into()]),
+            min_size: Some(10),
+            modified_within: Some("this week".into()),
+            path_prefixes: Some(vec!["0_Platform/".into()]),
+            ..Default::default()
+        };
+        let f = filters.resolve_at(root, now).unwrap();
+
+        assert!(f.matches_path(Path::new("/kb/0_Platform/domains/acme.md")));
+        assert!(!f.matches_path(Path::new("/kb/0_Platform/domains/acme.json")));
+        assert!(!f.matches_path(Path::new("/kb/notes/acme.md")));
+        assert!(f.allows_path(Path::new("/kb")));
+        assert!(!f.allows_path(Path::new("/kb/notes")));
+
+        let monday = local_midnight(NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()).unwrap();
+        assert!(f.matches_values(100, Some(monday + Duration::from_secs(60))));
+        assert!(!f.matches_values(100, Some(monday - Duration::from_secs(60))));
+        assert!(!f.matches_values(5, Some(monday + Duration::from_secs(60))));
+        assert!(!f.matches_values(100, None));
+
+        let week = window_start("7d", now).unwrap();
+        assert_eq!(SystemTime::from(now).duration_since(week).unwrap().as_secs(), 7 * 86_400);
+
+        let bad = |f: SearchFilters| matches!(f.resolve_at(root, now), Err(CommandError::Validation { .. }));
+        assert!(bad(SearchFilters { modified_within: Some("soon".into()), ..Default::default() }));
+        assert!(bad(SearchFilters { min_size: Some(10), max_size: Some(1), ..Default::default() }));
+        assert!(bad(SearchFilters {
+            modified_after: Some("2026-10-10".into()),
+            modified_before: Some("2026-10-01".into()),
+            ..Default::default()
+        }));
+    }
+}
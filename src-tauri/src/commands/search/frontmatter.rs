@@ -0,0 +1,271 @@
+// src-tauri/src/commands/search/frontmatter.rs
+// Frontmatter field search: find documents by their parsed YAML fields
+// ("status: published", "tags contains data-model", "owner missing") and
+// return the fields that were tested, for governance checks across the
+// knowledge path.
+
+use super::{first_visit, SearchFilters};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::parse_frontmatter;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+/// Same cap as content search
+const MAX_FILE_BYTES: u64 = 1_000_000;
+
+/// A file whose frontmatter satisfied the conditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrontmatterMatch {
+    pub name: String,
+    pub path: String,
+    /// Each field named in a condition, by its dotted key (absent fields are left out)
+    pub fields: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Equals(String),
+    NotEquals(String),
+    Contains(String),
+    Exists,
+    Missing,
+}
+
+/// One parsed condition, e.g. `tags contains data-model`
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    /// Dotted path into nested mappings ("owner.team")
+    key: String,
+    test: Test,
+}
+
+impl Condition {
+    /// Accepts `key: value`, `key = value`, `key != value`,
+    /// `key contains value`, `key exists` and `key missing`
+    fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let unquote = |v: &str| v.trim().trim_matches('"').trim_matches('\'').to_string();
+
+        if let Some((key, value)) = raw.split_once("!=") {
+            return Self::new(key, Test::NotEquals(unquote(value)));
+        }
+        if let Some((key, value)) = raw.split_once(" contains ") {
+            return Self::new(key, Test::Contains(unquote(value)));
+        }
+        if let Some(key) = raw.strip_suffix(" exists") {
+            return Self::new(key, Test::Exists);
+        }
+        if let Some(key) = raw.strip_suffix(" missing") {
+            return Self::new(key, Test::Missing);
+        }
+        let split = raw.find([':', '='])?;
+        let (key, value) = (&raw[..split], &raw[split + 1..]);
+        Self::new(key, Test::Equals(unquote(value)))
+    }
+
+    fn new(key: &str, test: Test) -> Option<Self> {
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        if let Test::Equals(v) | Test::NotEquals(v) | Test::Contains(v) = &test {
+            if v.is_empty() {
+                return None;
+            }
+        }
+        Some(Self { key: key.to_string(), test })
+    }
+
+    fn matches(&self, frontmatter: &Map<String, Value>) -> bool {
+        let value = lookup(frontmatter, &self.key).filter(|v| !v.is_null());
+        match (&self.test, value) {
+            (Test::Exists, v) => v.is_some(),
+            (Test::Missing, v) => v.is_none(),
+            (Test::Equals(expected), Some(v)) => equals(v, expected),
+            (Test::NotEquals(expected), v) => !v.is_some_and(|v| equals(v, expected)),
+            (Test::Contains(needle), Some(Value::Array(items))) => items.iter().any(|i| equals(i, needle)),
+            (Test::Contains(needle), Some(v)) => {
+                scalar(v).is_some_and(|s| s.to_lowercase().contains(&needle.to_lowercase()))
+            }
+            (_, None) => false,
+        }
+    }
+}
+
+fn lookup<'a>(frontmatter: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let mut value = frontmatter.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_object()?.get(part)?;
+    }
+    Some(value)
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Case-insensitive scalar comparison; a list equals a value it contains
+fn equals(value: &Value, expected: &str) -> bool {
+    match value {
+        Value::Array(items) => items.iter().any(|i| equals(i, expected)),
+        v => scalar(v).is_some_and(|s| s.eq_ignore_ascii_case(expected)),
+    }
+}
+
+/// Walk `root` for markdown (or `filters.extensions`) files and keep those whose
+/// frontmatter satisfies every condition (any one with `match_any`).
+/// Files without frontmatter or with invalid YAML never match. Unparseable
+/// conditions are a validation error on `conditions`.
+#[command]
+pub async fn search_frontmatter(
+    root: String,
+    conditions: Vec<String>,
+    match_any: Option<bool>,
+    max_results: Option<usize>,
+    follow_symlinks: Option<bool>,
+    filters: Option<SearchFilters>,
+) -> CmdResult<Vec<FrontmatterMatch>> {
+    let parsed = conditions
+        .iter()
+        .map(|raw| {
+            Condition::parse(raw).ok_or_else(|| CommandError::Validation {
+                message: format!(
+                    "Can't read condition '{}' (expected 'key: value', 'key != value', 'key contains value', 'key exists' or 'key missing')",
+                    raw
+                ),
+                fields: vec!["conditions".to_string()],
+            })
+        })
+        .collect::<CmdResult<Vec<_>>>()?;
+    if parsed.is_empty() {
+        return Err(CommandError::Validation {
+            message: "At least one condition is required".to_string(),
+            fields: vec!["conditions".to_string()],
+        });
+    }
+
+    let any = match_any.unwrap_or(false);
+    let max = max_results.unwrap_or(200);
+    let follow = follow_symlinks.unwrap_or(true);
+    let filters = filters.unwrap_or_default().resolve(Path::new(&root))?;
+    let prefix_filter = filters.clone();
+    let extensions = filters
+        .extensions()
+        .map(|e| e.to_vec())
+        .unwrap_or_else(|| vec!["md".to_string()]);
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+
+    let walker = WalkBuilder::new(&root)
+        .hidden(true)
+        .git_ignore(true)
+        .follow_links(follow)
+        .filter_entry(move |e| prefix_filter.allows_path(e.path()))
+        .build();
+
+    for entry in walker.flatten() {
+        if results.len() >= max {
+            break;
+        }
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let ext = path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !extensions.contains(&ext) || !filters.matches_path(path) {
+            continue;
+        }
+        if follow && !first_visit(&mut seen, path) {
+            continue;
+        }
+        let metadata = path.metadata().ok();
+        if metadata.as_ref().map(|m| m.len() > MAX_FILE_BYTES).unwrap_or(false) {
+            continue;
+        }
+        if !filters.matches_metadata(metadata.as_ref()) {
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(Some(frontmatter)) = parse_frontmatter(&content) else {
+            continue;
+        };
+        let matched = if any {
+            parsed.iter().any(|c| c.matches(&frontmatter))
+        } else {
+            parsed.iter().all(|c| c.matches(&frontmatter))
+        };
+        if !matched {
+            continue;
+        }
+
+        let fields = parsed
+            .iter()
+            .filter_map(|c| lookup(&frontmatter, &c.key).map(|v| (c.key.clone(), v.clone())))
+            .collect();
+        results.push(FrontmatterMatch {
+            name: path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: path.to_string_lossy().to_string(),
+            fields,
+        });
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_and_evaluates_conditions() {
+        let fm = json!({
+            "status": "Published",
+            "tags": ["data-model", "finance"],
+            "ai_generated": true,
+            "owner": { "team": "ops" },
+            "summary": "Revenue data model",
+            "reviewed": null,
+        });
+        let fm = fm.as_object().unwrap();
+        let check = |raw: &str| Condition::parse(raw).unwrap().matches(fm);
+
+        assert!(check("status: published"));
+        assert!(check("status = \"Published\""));
+        assert!(!check("status != published"));
+        assert!(check("tags contains data-model"));
+        assert!(check("tags: finance"));
+        assert!(!check("tags contains data"));
+        assert!(check("summary contains data model"));
+        assert!(check("ai_generated: true"));
+        assert!(check("owner.team: ops"));
+        assert!(check("owner exists"));
+        assert!(check("reviewed missing"));
+        assert!(check("deprecated != true"));
+        assert!(!check("deprecated: true"));
+
+        assert_eq!(Condition::parse("url: https://x.io").map(|c| c.test), Some(Test::Equals("https://x.io".to_string())));
+        assert_eq!(Condition::parse("ref = a:b").map(|c| c.test), Some(Test::Equals("a:b".to_string())));
+        assert!(Condition::parse("status").is_none());
+        assert!(Condition::parse("status:").is_none());
+        assert!(Condition::parse("two words: x").is_none());
+    }
+}
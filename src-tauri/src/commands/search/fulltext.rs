@@ -0,0 +1,457 @@
+// src-tauri/src/commands/search/fulltext.rs
+// Persistent full-text index (tantivy) for a knowledge root, so content
+// search doesn't cold-scan every file per query. One index per root under
+// ~/.tv-client/search_index/<hash>/, refreshed incrementally: a build only
+// re-reads files whose mtime changed, and watched folders push their change
+// batches here (see files::watch).
+
+use super::DEFAULT_CONTENT_EXTENSIONS;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::index::doc_meta;
+use crate::models::SearchResult;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+use tauri::command;
+
+/// Same cutoff as the cold scan in search_content
+const MAX_FILE_BYTES: u64 = 1_000_000;
+const WRITER_HEAP_BYTES: usize = 50_000_000;
+const PREVIEW_CHARS: usize = 200;
+
+// Open indexes, keyed by canonical root
+static INDEXES: std::sync::LazyLock<Mutex<HashMap<PathBuf, Arc<RootIndex>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexStats {
+    pub root: String,
+    pub documents: u64,
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexStatus {
+    pub root: String,
+    pub documents: u64,
+    pub built_at: Option<String>,
+    pub size_bytes: u64,
+}
+
+/// Indexed files and their mtimes, so refreshes only re-read what changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Manifest {
+    built_at: Option<String>,
+    /// Absolute path -> modified (ms since epoch)
+    files: HashMap<String, i64>,
+}
+
+struct Fields {
+    path: Field,
+    title: Field,
+    body: Field,
+}
+
+struct RootIndex {
+    root: PathBuf,
+    dir: PathBuf,
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    manifest: Mutex<Manifest>,
+    fields: Fields,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn index_err(e: impl std::fmt::Display) -> CommandError {
+    CommandError::Internal(format!("Search index error: {}", e))
+}
+
+fn indexes_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("search_index")
+}
+
+pub(super) fn dir_for(root: &Path) -> PathBuf {
+    let hash = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+    indexes_dir().join(&hash[..16])
+}
+
+fn schema() -> (Schema, Fields) {
+    let mut builder = Schema::builder();
+    let fields = Fields {
+        path: builder.add_text_field("path", STRING | STORED),
+        title: builder.add_text_field("title", TEXT | STORED),
+        // Stored for result previews
+        body: builder.add_text_field("body", TEXT | STORED),
+    };
+    (builder.build(), fields)
+}
+
+fn modified_ms(meta: &fs::Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis())
+        .unwrap_or_default()
+}
+
+fn is_indexable(path: &Path) -> bool {
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    DEFAULT_CONTENT_EXTENSIONS.contains(&ext.as_str())
+}
+
+/// Indexable files under `root` with their mtimes (same walk rules as search_content)
+fn scan(root: &Path) -> HashMap<String, i64> {
+    let walker = WalkBuilder::new(root).hidden(true).git_ignore(true).build();
+    walker
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()) && is_indexable(e.path()))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            (meta.len() <= MAX_FILE_BYTES).then(|| (e.path().to_string_lossy().to_string(), modified_ms(&meta)))
+        })
+        .collect()
+}
+
+/// 1-based line of the first line containing any query term
+fn first_matching_line(body: &str, query: &str) -> Option<usize> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.len() > 1)
+        .map(str::to_lowercase)
+        .collect();
+    body.lines()
+        .position(|line| {
+            let line = line.to_lowercase();
+            terms.iter().any(|t| line.contains(t.as_str()))
+        })
+        .map(|i| i + 1)
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+impl RootIndex {
+    fn open(root: &Path) -> CmdResult<Self> {
+        Self::open_at(root, dir_for(root))
+    }
+
+    fn open_at(root: &Path, dir: PathBuf) -> CmdResult<Self> {
+        fs::create_dir_all(&dir)?;
+        let (schema, fields) = schema();
+        let directory = MmapDirectory::open(&dir).map_err(index_err)?;
+        let index = Index::open_or_create(directory, schema).map_err(index_err)?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()
+            .map_err(index_err)?;
+        let writer = index.writer(WRITER_HEAP_BYTES).map_err(index_err)?;
+        let manifest = fs::read_to_string(dir.join("manifest.json"))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Ok(Self {
+            root: root.to_path_buf(),
+            dir,
+            index,
+            reader,
+            writer: Mutex::new(writer),
+            manifest: Mutex::new(manifest),
+            fields,
+        })
+    }
+
+    /// Replace (or drop) each path's document, then commit once
+    fn apply(&self, upserts: &[String], removals: &[String]) -> CmdResult<(usize, usize)> {
+        let mut writer = self.writer.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut manifest = self.manifest.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let mut written = 0;
+        let mut removed = 0;
+
+        for path in removals {
+            writer.delete_term(Term::from_field_text(self.fields.path, path));
+            if manifest.files.remove(path).is_some() {
+                removed += 1;
+            }
+        }
+        for path in upserts {
+            writer.delete_term(Term::from_field_text(self.fields.path, path));
+            let p = Path::new(path);
+            let (Ok(meta), Ok(content)) = (fs::metadata(p), fs::read_to_string(p)) else {
+                manifest.files.remove(path);
+                continue;
+            };
+            let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            writer
+                .add_document(doc!(
+                    self.fields.path => path.as_str(),
+                    self.fields.title => doc_meta(&name, &content).title,
+                    self.fields.body => content,
+                ))
+                .map_err(index_err)?;
+            manifest.files.insert(path.clone(), modified_ms(&meta));
+            written += 1;
+        }
+
+        writer.commit().map_err(index_err)?;
+        self.reader.reload().map_err(index_err)?;
+        manifest.built_at = Some(chrono::Utc::now().to_rfc3339());
+        fs::write(self.dir.join("manifest.json"), serde_json::to_string(&*manifest)?)?;
+        Ok((written, removed))
+    }
+
+    /// Bring the index in line with the files on disk. `rebuild` re-reads everything.
+    fn refresh(&self, rebuild: bool) -> CmdResult<SearchIndexStats> {
+        let started = Instant::now();
+        let on_disk = scan(&self.root);
+        let known = if rebuild {
+            let mut writer = self.writer.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+            writer.delete_all_documents().map_err(index_err)?;
+            writer.commit().map_err(index_err)?;
+            let mut manifest = self.manifest.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+            manifest.files.clear();
+            HashMap::new()
+        } else {
+            self.manifest.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?.files.clone()
+        };
+
+        let mut added = 0;
+        let mut upserts = Vec::new();
+        for (path, modified) in &on_disk {
+            match known.get(path) {
+                Some(m) if m == modified => {}
+                Some(_) => upserts.push(path.clone()),
+                None => {
+                    added += 1;
+                    upserts.push(path.clone());
+                }
+            }
+        }
+        let removals: Vec<String> = known.keys().filter(|p| !on_disk.contains_key(*p)).cloned().collect();
+        let (written, removed) = self.apply(&upserts, &removals)?;
+
+        Ok(SearchIndexStats {
+            root: self.root.to_string_lossy().to_string(),
+            documents: self.reader.searcher().num_docs(),
+            added,
+            updated: written.saturating_sub(added),
+            removed,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn query(&self, query: &str, max_results: usize) -> CmdResult<Vec<SearchResult>> {
+        let searcher = self.reader.searcher();
+        let mut parser = QueryParser::for_index(&self.index, vec![self.fields.title, self.fields.body]);
+        parser.set_field_boost(self.fields.title, 3.0);
+        // Lenient: stray quotes or colons in user input still search
+        let (parsed, _) = parser.parse_query_lenient(query);
+        let top = searcher.search(&parsed, &TopDocs::with_limit(max_results)).map_err(index_err)?;
+
+        let mut snippets = SnippetGenerator::create(&searcher, &*parsed, self.fields.body).map_err(index_err)?;
+        snippets.set_max_num_chars(PREVIEW_CHARS);
+
+        let mut results = Vec::new();
+        for (score, address) in top {
+            let doc: TantivyDocument = searcher.doc(address).map_err(index_err)?;
+            let Some(path) = doc.get_first(self.fields.path).and_then(|v| v.as_str()).map(String::from) else {
+                continue;
+            };
+            let body = doc.get_first(self.fields.body).and_then(|v| v.as_str()).unwrap_or_default();
+            let fragment = snippets.snippet_from_doc(&doc).fragment().trim().to_string();
+            results.push(SearchResult {
+                name: Path::new(&path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                size: fs::metadata(&path).ok().map(|m| m.len()),
+                path,
+                is_directory: false,
+                match_type: "content".to_string(),
+                preview: Some(fragment).filter(|f| !f.is_empty()),
+                line_number: first_matching_line(body, query),
+                score: Some(score as f64),
+                span: None,
+            });
+        }
+        Ok(results)
+    }
+
+    fn status(&self) -> SearchIndexStatus {
+        let size_bytes = fs::read_dir(&self.dir)
+            .map(|entries| entries.flatten().filter_map(|e| e.metadata().ok()).map(|m| m.len()).sum())
+            .unwrap_or(0);
+        SearchIndexStatus {
+            root: self.root.to_string_lossy().to_string(),
+            documents: self.reader.searcher().num_docs(),
+            built_at: self.manifest.lock().ok().and_then(|m| m.built_at.clone()),
+            size_bytes,
+        }
+    }
+}
+
+pub(super) fn canonical_root(root: &str) -> CmdResult<PathBuf> {
+    let path = fs::canonicalize(root).map_err(|e| CommandError::NotFound(format!("{}: {}", root, e)))?;
+    if !path.is_dir() {
+        return Err(CommandError::NotFound(format!("Not a directory: {}", root)));
+    }
+    Ok(path)
+}
+
+/// The open index for `root`, opening it from disk if it was built before.
+/// With `create`, a missing index is created (empty until refreshed).
+fn get_index(root: &Path, create: bool) -> CmdResult<Option<Arc<RootIndex>>> {
+    let mut indexes = INDEXES.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    if let Some(index) = indexes.get(root) {
+        return Ok(Some(index.clone()));
+    }
+    if !create && !dir_for(root).join("manifest.json").exists() {
+        return Ok(None);
+    }
+    let index = Arc::new(RootIndex::open(root)?);
+    indexes.insert(root.to_path_buf(), index.clone());
+    Ok(Some(index))
+}
+
+/// Feed a batch of changed paths (from the file watcher) to any open index
+/// that covers them. Cheap no-op when nothing is indexed.
+pub fn apply_file_changes(paths: &[PathBuf]) {
+    let open: Vec<Arc<RootIndex>> = match INDEXES.lock() {
+        Ok(indexes) => indexes.values().cloned().collect(),
+        Err(_) => return,
+    };
+    for index in open {
+        let mut upserts = Vec::new();
+        let mut removals = Vec::new();
+        for path in paths {
+            // Watch paths may not be canonical (e.g. through a symlink); match on both
+            let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            if !(canonical.starts_with(&index.root) || path.starts_with(&index.root)) || !is_indexable(path) {
+                continue;
+            }
+            let key = canonical.to_string_lossy().to_string();
+            match fs::metadata(&canonical) {
+                Ok(meta) if meta.is_file() && meta.len() <= MAX_FILE_BYTES => upserts.push(key),
+                _ => removals.push(key),
+            }
+        }
+        if upserts.is_empty() && removals.is_empty() {
+            continue;
+        }
+        if let Err(e) = index.apply(&upserts, &removals) {
+            log::warn!("Search index update failed for {}: {}", index.root.display(), e);
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Build the full-text index for `root`, or refresh it (only changed files
+/// are re-read). `rebuild` starts from scratch.
+#[command]
+pub async fn search_index_build(root: String, rebuild: Option<bool>) -> CmdResult<SearchIndexStats> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = canonical_root(&root)?;
+        let index = get_index(&root, true)?.ok_or_else(|| CommandError::Internal("Index unavailable".into()))?;
+        index.refresh(rebuild.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Index task failed: {}", e)))?
+}
+
+/// Index status for `root`; None if it has never been indexed
+#[command]
+pub async fn search_index_status(root: String) -> CmdResult<Option<SearchIndexStatus>> {
+    let root = canonical_root(&root)?;
+    Ok(get_index(&root, false)?.map(|index| index.status()))
+}
+
+/// Ranked content search against the index (build it first with search_index_build)
+#[command]
+pub async fn search_index_query(root: String, query: String, max_results: Option<usize>) -> CmdResult<Vec<SearchResult>> {
+    let root = canonical_root(&root)?;
+    let index = get_index(&root, false)?
+        .ok_or_else(|| CommandError::NotFound(format!("No search index for {}; build it first", root.display())))?;
+    index.query(&query, max_results.unwrap_or(50))
+}
+
+/// Drop the index for `root` (close it and delete it from disk)
+#[command]
+pub async fn search_index_delete(root: String) -> CmdResult<bool> {
+    let root = canonical_root(&root)?;
+    let removed = INDEXES
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?
+        .remove(&root);
+    // The writer's lock file is released when the last handle drops
+    drop(removed);
+    let dir = dir_for(&root);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    fs::remove_dir_all(&dir).map_err(|e| CommandError::Io(format!("Failed to delete search index: {}", e)))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_incrementally_and_ranks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().to_path_buf();
+        let root = base.join("kb");
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::write(root.join("notes/pricing.md"), "# Pricing\n\nAnnual plans renew in March.").unwrap();
+        fs::write(root.join("notes/onboarding.md"), "# Onboarding\n\nSee the pricing page.\nKickoff call first.").unwrap();
+        fs::write(root.join("image.png"), "not text").unwrap();
+        let root = fs::canonicalize(&root).unwrap();
+
+        let index = RootIndex::open_at(&root, base.join("index")).unwrap();
+        let stats = index.refresh(false).unwrap();
+        assert_eq!((stats.documents, stats.added, stats.updated), (2, 2, 0));
+
+        // Title hit ranks first; body-only hit follows with its line
+        let results = index.query("pricing", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "pricing.md");
+        assert_eq!(results[1].line_number, Some(3));
+        assert!(results[1].preview.as_deref().unwrap_or_default().contains("pricing"));
+
+        // Unchanged files aren't re-read; removed files drop out
+        assert_eq!(index.refresh(false).unwrap().updated, 0);
+        fs::remove_file(root.join("notes/pricing.md")).unwrap();
+        fs::write(root.join("notes/kickoff.md"), "Kickoff agenda").unwrap();
+        let stats = index.refresh(false).unwrap();
+        assert_eq!((stats.documents, stats.added, stats.removed), (2, 1, 1));
+        assert_eq!(index.query("kickoff", 10).unwrap().len(), 2);
+        assert_eq!(index.query("\"unbalanced", 10).unwrap().len(), 0);
+
+        drop(index);
+    }
+}
@@ -0,0 +1,75 @@
+// src-tauri/src/commands/search/fuzzy.rs
+// fzf-style fuzzy matching for quick open: the query's characters must appear
+// in order in the file's path (relative to the search root), scored by how
+// tightly they land and on which boundaries, with a small boost for recent files
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+use std::time::SystemTime;
+
+/// Share of the match score a file modified just now gains
+const RECENCY_BOOST: f64 = 0.25;
+/// Age at which the recency boost has halved
+const RECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+pub struct FuzzyMatcher {
+    matcher: Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl FuzzyMatcher {
+    /// Smart case: case-insensitive unless the query has capitals. Spaces split
+    /// the query into terms that must all match; fzf operators (^, $, ', !) work.
+    pub fn new(query: &str) -> Self {
+        Self {
+            matcher: Matcher::new(Config::DEFAULT.match_paths()),
+            pattern: Pattern::parse(query, CaseMatching::Smart, Normalization::Smart),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Match quality for `relative_path`, None when it doesn't match. Hits
+    /// within the file name count again, so `mod` prefers `mod.rs` over
+    /// `modules/index.ts`. Higher is better.
+    pub fn score(&mut self, relative_path: &str) -> Option<f64> {
+        let path_score = self.pattern.score(Utf32Str::new(relative_path, &mut self.buf), &mut self.matcher)?;
+        let name = relative_path.rsplit(['/', '\\']).next().unwrap_or(relative_path);
+        let name_score = self
+            .pattern
+            .score(Utf32Str::new(name, &mut self.buf), &mut self.matcher)
+            .unwrap_or(0);
+        Some((path_score + name_score) as f64)
+    }
+}
+
+/// Nudge a match score towards recently modified files (a tie-breaker, not
+/// enough to lift a poor match over a good one)
+pub fn with_recency(score: f64, modified: Option<SystemTime>) -> f64 {
+    let age_days = modified
+        .and_then(|m| SystemTime::now().duration_since(m).ok())
+        .map(|d| d.as_secs_f64() / 86_400.0);
+    let recency = age_days.map_or(0.0, |age| RECENCY_BOOST * 0.5_f64.powf(age / RECENCY_HALF_LIFE_DAYS));
+    score * (1.0 + recency)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_tight_filename_matches_first() {
+        let mut m = FuzzyMatcher::new("srchmod");
+        assert!(m.score("src-tauri/src/commands/search/mod.rs").is_some());
+        assert!(m.score("src/hooks/useTasks.ts").is_none());
+
+        let mut m = FuzzyMatcher::new("mod");
+        let name_hit = m.score("src/commands/search/mod.rs").unwrap();
+        let dir_hit = m.score("src/modules/library/index.ts").unwrap();
+        assert!(name_hit > dir_hit, "{} vs {}", name_hit, dir_hit);
+
+        let fresh = with_recency(name_hit, Some(SystemTime::now()));
+        assert!(fresh > name_hit && fresh <= name_hit * (1.0 + RECENCY_BOOST) + 1e-9);
+        assert_eq!(with_recency(name_hit, None), name_hit);
+    }
+}
@@ -0,0 +1,241 @@
+// src-tauri/src/commands/search/grouped.rs
+// Grouped content search: one entry per matching file with its relevance
+// score, total match count and the first few matching lines, each clipped
+// around its match with highlight ranges — enough for a VS Code-style
+// results panel without a second round trip per file.
+
+use super::{compile_pattern, ContentScan, MatchOptions, Matcher, SearchFilters, SearchRanking};
+use crate::commands::error::CmdResult;
+use crate::models::{GroupedSearchResults, LineMatch, MatchRange, SearchResultGroup};
+use regex::Regex;
+use std::sync::atomic::AtomicBool;
+use tauri::command;
+
+/// Matching lines previewed per file when the caller doesn't say
+const DEFAULT_LINES_PER_FILE: usize = 5;
+const MAX_LINES_PER_FILE: usize = 100;
+/// Characters kept of a previewed line (same as the flat preview)
+const MAX_PREVIEW_CHARS: usize = 200;
+/// Characters kept before the first match when it's far into a line
+const PREVIEW_CONTEXT_CHARS: usize = 40;
+
+/// Matches found in one file
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct FileMatches {
+    pub(super) matches: usize,
+    pub(super) lines: usize,
+    pub(super) previews: Vec<LineMatch>,
+}
+
+/// A regex finding every match the scan's matcher would. Plain queries
+/// match case-insensitively, as they do in the scan.
+fn highlighter(matcher: &Matcher) -> CmdResult<(Regex, bool)> {
+    match matcher {
+        Matcher::Plain(query_lower) => Ok((compile_pattern(query_lower, false, false, true)?, false)),
+        Matcher::Regex { re, multiline } => Ok((re.clone(), *multiline)),
+    }
+}
+
+/// `line` trimmed and clipped to MAX_PREVIEW_CHARS, starting shortly before
+/// the first match if that's far in, with byte `ranges` turned into
+/// character ranges of the returned text
+fn preview(line: &str, line_number: usize, ranges: &[(usize, usize)]) -> LineMatch {
+    let line = line.trim_end();
+    let indent = line.len() - line.trim_start().len();
+    let first = ranges.first().map(|r| r.0).unwrap_or(indent).clamp(indent, line.len());
+    let start = if line[indent..first].chars().count() > PREVIEW_CONTEXT_CHARS {
+        line[..first]
+            .char_indices()
+            .rev()
+            .nth(PREVIEW_CONTEXT_CHARS - 1)
+            .map(|(i, _)| i)
+            .unwrap_or(indent)
+    } else {
+        indent
+    };
+    let end = line[start..]
+        .char_indices()
+        .nth(MAX_PREVIEW_CHARS)
+        .map(|(i, _)| start + i)
+        .unwrap_or(line.len());
+
+    let prefix = if start > indent { "…" } else { "" };
+    let suffix = if end < line.len() { "…" } else { "" };
+    let offset = prefix.chars().count();
+    let to_char = |byte: usize| offset + line[start..byte].chars().count();
+    LineMatch {
+        line_number,
+        text: format!("{}{}{}", prefix, &line[start..end], suffix),
+        ranges: ranges
+            .iter()
+            .filter(|(s, e)| *s < end && *e > start)
+            .map(|(s, e)| MatchRange {
+                start: to_char((*s).max(start)),
+                end: to_char((*e).min(end)),
+            })
+            .collect(),
+    }
+}
+
+/// Count every non-empty match in `content` and preview the first
+/// `max_previews` matching lines. Multiline matches are counted once and
+/// highlighted on the line they start on.
+pub(super) fn line_matches(re: &Regex, content: &str, multiline: bool, max_previews: usize) -> FileMatches {
+    let mut found = FileMatches::default();
+    let record = |found: &mut FileMatches, index: usize, line: &str, ranges: Vec<(usize, usize)>| {
+        found.matches += ranges.len();
+        found.lines += 1;
+        if found.previews.len() < max_previews {
+            found.previews.push(preview(line, index + 1, &ranges));
+        }
+    };
+
+    if !multiline {
+        for (index, line) in content.lines().enumerate() {
+            let ranges: Vec<(usize, usize)> = re
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            if !ranges.is_empty() {
+                record(&mut found, index, line, ranges);
+            }
+        }
+        return found;
+    }
+
+    let starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let line_at = |index: usize| {
+        let rest = &content[starts[index]..];
+        rest.split('\n').next().unwrap_or_default().trim_end_matches('\r')
+    };
+    let mut current: Option<(usize, Vec<(usize, usize)>)> = None;
+    for m in re.find_iter(content).filter(|m| !m.is_empty()) {
+        let index = starts.partition_point(|s| *s <= m.start()) - 1;
+        let line_start = starts[index];
+        let span = (m.start() - line_start, (m.end() - line_start).min(line_at(index).len()));
+        match current.as_mut() {
+            Some((i, ranges)) if *i == index => ranges.push(span),
+            _ => {
+                if let Some((i, ranges)) = current.take() {
+                    record(&mut found, i, line_at(i), ranges);
+                }
+                current = Some((index, vec![span]));
+            }
+        }
+    }
+    if let Some((i, ranges)) = current {
+        record(&mut found, i, line_at(i), ranges);
+    }
+    found
+}
+
+/// Content search grouped by file: each group has the file's relevance score,
+/// its total match and line counts, and up to `max_lines_per_file` (default 5)
+/// highlighted line previews. Same matching, options and errors as
+/// `search_content`; the top `max_results` files (default 50) come back best-first.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_content_grouped(
+    root: String,
+    query: String,
+    extensions: Option<Vec<String>>,
+    max_results: Option<usize>,
+    max_lines_per_file: Option<usize>,
+    ranking: Option<SearchRanking>,
+    follow_symlinks: Option<bool>,
+    regex: Option<bool>,
+    multiline: Option<bool>,
+    filters: Option<SearchFilters>,
+    match_options: Option<MatchOptions>,
+) -> CmdResult<GroupedSearchResults> {
+    let scan = ContentScan::new(
+        root,
+        &query,
+        extensions,
+        ranking,
+        follow_symlinks,
+        regex,
+        multiline,
+        filters,
+        match_options,
+    )?;
+    let (re, multiline) = highlighter(&scan.matcher)?;
+    let max = max_results.unwrap_or(50);
+    let per_file = max_lines_per_file.unwrap_or(DEFAULT_LINES_PER_FILE).clamp(1, MAX_LINES_PER_FILE);
+
+    let mut groups = Vec::new();
+    let mut total_matches = 0;
+    scan.run_with_content(&AtomicBool::new(false), |result, content| {
+        let found = line_matches(&re, content, multiline, per_file);
+        total_matches += found.matches;
+        groups.push(SearchResultGroup {
+            name: result.name,
+            path: result.path,
+            score: result.score.unwrap_or(0.0),
+            match_count: found.matches,
+            line_count: found.lines,
+            lines: found.previews,
+        });
+        true
+    });
+
+    let total_files = groups.len();
+    groups.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    groups.truncate(max);
+
+    Ok(GroupedSearchResults {
+        groups,
+        total_files,
+        total_matches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(line: &LineMatch) -> Vec<(usize, usize)> {
+        line.ranges.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn groups_every_match_with_highlighted_previews() {
+        let content = "Orders\n  usr_id and USR_ID\nnothing\nlast usr_id\n";
+        let plain = Matcher::new("usr_id", false, false, MatchOptions::default()).unwrap();
+        let (re, multiline) = highlighter(&plain).unwrap();
+        let found = line_matches(&re, content, multiline, 1);
+        assert_eq!((found.matches, found.lines), (3, 2));
+        // Preview cap applies to lines, not the counts; indent is trimmed
+        assert_eq!(found.previews.len(), 1);
+        assert_eq!(found.previews[0].line_number, 2);
+        assert_eq!(found.previews[0].text, "usr_id and USR_ID");
+        assert_eq!(ranges(&found.previews[0]), vec![(0, 6), (11, 17)]);
+
+        // Multiline: counted once, highlighted on its first line up to the line end
+        let spanning = Matcher::new(r"and USR_ID\nnothing", true, true, MatchOptions::default()).unwrap();
+        let (re, multiline) = highlighter(&spanning).unwrap();
+        let found = line_matches(&re, content, multiline, 5);
+        assert_eq!((found.matches, found.lines), (1, 1));
+        assert_eq!(found.previews[0].line_number, 2);
+        assert_eq!(ranges(&found.previews[0]), vec![(7, 17)]);
+    }
+
+    #[test]
+    fn clips_long_lines_around_the_first_match() {
+        let line = format!("{}émoji needle{}", "x".repeat(100), "y".repeat(300));
+        let start = line.find("needle").unwrap();
+        let clipped = preview(&line, 7, &[(start, start + 6)]);
+        assert!(clipped.text.starts_with('…') && clipped.text.ends_with('…'));
+        assert_eq!(clipped.text.chars().count(), MAX_PREVIEW_CHARS + 2);
+        let (s, e) = ranges(&clipped)[0];
+        let highlighted: String = clipped.text.chars().skip(s).take(e - s).collect();
+        assert_eq!(highlighted, "needle");
+
+        // Short lines are kept whole
+        let short = preview("a needle", 1, &[(2, 8)]);
+        assert_eq!((short.text.as_str(), ranges(&short)), ("a needle", vec![(2, 8)]));
+    }
+}
@@ -0,0 +1,107 @@
+// src-tauri/src/commands/search/match_options.rs
+// Match options for content search: how letter case is treated (always,
+// never, or ripgrep-style smart case) and whole-word matching. Literal vs
+// regex stays the `regex` flag; these apply to both.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseMode {
+    Insensitive,
+    Sensitive,
+    /// Insensitive unless the query has an uppercase letter
+    Smart,
+}
+
+/// Every field is optional on the wire
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchOptions {
+    /// Unset: plain queries are case-insensitive, regexes case-sensitive
+    /// (unless they start with `(?i)`)
+    pub case: Option<CaseMode>,
+    /// Only match where the query isn't part of a longer word
+    pub whole_word: bool,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Uppercase letters the query would match literally. In a regex, escapes
+/// (`\W`, `\S`, `\p{Lu}`) don't count.
+fn has_uppercase(query: &str, regex: bool) -> bool {
+    if !regex {
+        return query.chars().any(char::is_uppercase);
+    }
+    let mut chars = query.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some('p' | 'P') = chars.next() {
+                    if chars.clone().next() == Some('{') {
+                        chars.by_ref().find(|c| *c == '}');
+                    } else {
+                        chars.next();
+                    }
+                }
+            }
+            c if c.is_uppercase() => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+impl MatchOptions {
+    pub(super) fn case_insensitive(&self, query: &str, regex: bool) -> bool {
+        match self.case {
+            Some(CaseMode::Insensitive) => true,
+            Some(CaseMode::Sensitive) => false,
+            Some(CaseMode::Smart) => !has_uppercase(query, regex),
+            None => !regex,
+        }
+    }
+
+    /// `query` as a regex pattern: escaped unless `regex`, and with
+    /// `whole_word` bounded by `\b` (for a literal, only on ends that are
+    /// word characters, so "-v" still matches " -v ")
+    pub(super) fn pattern(&self, query: &str, regex: bool) -> String {
+        let pattern = if regex { query.to_string() } else { regex::escape(query) };
+        if !self.whole_word {
+            return pattern;
+        }
+        if regex {
+            return format!(r"\b(?:{})\b", pattern);
+        }
+        let start = if query.chars().next().is_some_and(is_word_char) { r"\b" } else { "" };
+        let end = if query.chars().last().is_some_and(is_word_char) { r"\b" } else { "" };
+        format!("{}{}{}", start, pattern, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_case_and_word_bounds() {
+        let smart = MatchOptions { case: Some(CaseMode::Smart), whole_word: false };
+        assert!(smart.case_insensitive("orders", false));
+        assert!(!smart.case_insensitive("Orders", false));
+        // Escapes aren't letters the user typed
+        assert!(smart.case_insensitive(r"usr_\w+\S\p{Lu}", true));
+        assert!(!smart.case_insensitive(r"\bUSR_\w+", true));
+
+        let default = MatchOptions::default();
+        assert!(default.case_insensitive("Orders", false));
+        assert!(!default.case_insensitive("orders", true));
+
+        let word = MatchOptions { case: None, whole_word: true };
+        assert_eq!(word.pattern("usr.id", false), r"\busr\.id\b");
+        assert_eq!(word.pattern("-v", false), r"-v\b");
+        assert_eq!(word.pattern("a|b", true), r"\b(?:a|b)\b");
+        assert_eq!(default.pattern("a.b", false), r"a\.b");
+    }
+}
@@ -0,0 +1,554 @@
+// src-tauri/src/commands/search/mod.rs
+// Search operations for the Library module
+
+pub mod filters;
+pub mod frontmatter;
+pub mod fulltext;
+pub mod fuzzy;
+pub mod grouped;
+pub mod match_options;
+pub mod ranking;
+pub mod replace;
+pub mod semantic;
+pub mod stream;
+pub mod val_artifacts;
+
+pub use filters::*;
+pub use frontmatter::*;
+pub use fulltext::*;
+pub use fuzzy::*;
+pub use grouped::*;
+pub use match_options::*;
+pub use ranking::*;
+pub use replace::*;
+pub use semantic::*;
+pub use stream::*;
+pub use val_artifacts::*;
+
+use crate::commands::error::{CmdResult, CommandError};
+use crate::models::{MatchSpan, SearchResult};
+use ignore::WalkBuilder;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::command;
+
+/// Extensions content search reads when the caller doesn't pass any
+pub(crate) const DEFAULT_CONTENT_EXTENSIONS: [&str; 16] = [
+    "md", "txt", "js", "ts", "tsx", "jsx", "json", "sql", "py", "rs",
+    "yaml", "yml", "toml", "html", "css", "scss",
+];
+
+/// Files fuzzy mode will look at before ranking (it can't stop at the first
+/// `max_results` hits like substring mode)
+const MAX_FUZZY_CANDIDATES: usize = 100_000;
+
+/// Longest pattern accepted in regex mode
+const MAX_PATTERN_LEN: usize = 1000;
+/// Caps on the compiled regex and its lazy DFA. The regex crate never
+/// backtracks, so matching stays linear in the file size; these stop
+/// pathological patterns (e.g. nested counted repetition) from eating memory.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+const REGEX_DFA_SIZE_LIMIT: usize = 4 << 20;
+
+/// How search_content matches a file
+enum Matcher {
+    /// Case-insensitive substring (query lowercased)
+    Plain(String),
+    /// A regex, or a literal query with case or whole-word options. Multiline
+    /// patterns run over the whole file (`^`/`$` per line, `.` crosses newlines).
+    Regex { re: Regex, multiline: bool },
+}
+
+/// The first match in a file
+struct FirstMatch<'a> {
+    /// 0-based line the match starts on, and that line
+    line_index: usize,
+    line: &'a str,
+    /// The matched text lowercased (what ranking scores against)
+    matched: String,
+    /// Byte offsets into the file, end exclusive
+    start: usize,
+    end: usize,
+}
+
+/// Compile a search pattern with the size caps above. Plain queries are
+/// escaped first; invalid or oversized patterns are a validation error on `query`.
+fn compile_pattern(query: &str, regex: bool, multiline: bool, case_insensitive: bool) -> CmdResult<Regex> {
+    let invalid = |message: String| CommandError::Validation { message, fields: vec!["query".to_string()] };
+    if query.len() > MAX_PATTERN_LEN {
+        return Err(invalid(format!("Pattern is too long (max {} characters)", MAX_PATTERN_LEN)));
+    }
+    let pattern = if regex { query.to_string() } else { regex::escape(query) };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .multi_line(multiline)
+        .dot_matches_new_line(multiline)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_DFA_SIZE_LIMIT)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::CompiledTooBig(_) => invalid("Pattern is too complex; simplify repetitions".to_string()),
+            other => invalid(format!("Invalid regex: {}", other)),
+        })
+}
+
+/// Each line of `content` with the byte offset it starts at (line endings
+/// stripped, as `str::lines` does)
+fn lines_with_offsets(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content.split_inclusive('\n').scan(0, |offset, raw| {
+        let start = *offset;
+        *offset += raw.len();
+        let line = raw.strip_suffix('\n').unwrap_or(raw);
+        Some((start, line.strip_suffix('\r').unwrap_or(line)))
+    })
+}
+
+/// Byte range in `line` of the first case-insensitive occurrence of
+/// `query_lower`, mapped back through lowercasing (which can change lengths)
+fn find_lowercase(line: &str, query_lower: &str) -> Option<(usize, usize)> {
+    let mut lowered = String::with_capacity(line.len());
+    // Original byte offset of the character each lowered byte came from
+    let mut origin = Vec::with_capacity(line.len());
+    for (i, c) in line.char_indices() {
+        lowered.extend(c.to_lowercase());
+        origin.resize(lowered.len(), i);
+    }
+    if query_lower.is_empty() {
+        return Some((0, 0));
+    }
+    let start = lowered.find(query_lower)?;
+    let end = start + query_lower.len();
+    let last = origin[end - 1];
+    let last_len = line[last..].chars().next().map(char::len_utf8).unwrap_or(0);
+    Some((origin[start], last + last_len))
+}
+
+/// Line/column form of the byte range `start..end` in `content`
+fn match_span(content: &str, start: usize, end: usize) -> MatchSpan {
+    let position = |offset: usize| {
+        let line_start = content[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        (content[..offset].matches('\n').count() + 1, content[line_start..offset].chars().count() + 1)
+    };
+    let (_, column) = position(start);
+    let (end_line, end_column) = position(end);
+    MatchSpan { start, end, column, end_line, end_column }
+}
+
+impl Matcher {
+    fn new(query: &str, regex: bool, multiline: bool, options: MatchOptions) -> CmdResult<Self> {
+        let case_insensitive = options.case_insensitive(query, regex);
+        if !regex && case_insensitive && !options.whole_word {
+            return Ok(Self::Plain(query.to_lowercase()));
+        }
+        let multiline = regex && multiline;
+        let re = compile_pattern(&options.pattern(query, regex), true, multiline, case_insensitive)?;
+        Ok(Self::Regex { re, multiline })
+    }
+
+    /// First non-empty match in `content`
+    fn first_match<'a>(&self, content: &'a str) -> Option<FirstMatch<'a>> {
+        match self {
+            Self::Plain(query_lower) => lines_with_offsets(content)
+                .enumerate()
+                .find(|(_, (_, line))| line.to_lowercase().contains(query_lower.as_str()))
+                .map(|(i, (offset, line))| {
+                    // Final-sigma lowercasing can defeat the per-character
+                    // mapping; the line start is close enough then
+                    let (start, end) = find_lowercase(line, query_lower).unwrap_or((0, 0));
+                    FirstMatch {
+                        line_index: i,
+                        line,
+                        matched: query_lower.clone(),
+                        start: offset + start,
+                        end: offset + end,
+                    }
+                }),
+            Self::Regex { re, multiline: false } => {
+                lines_with_offsets(content).enumerate().find_map(|(i, (offset, line))| {
+                    re.find_iter(line).find(|m| !m.is_empty()).map(|m| FirstMatch {
+                        line_index: i,
+                        line,
+                        matched: m.as_str().to_lowercase(),
+                        start: offset + m.start(),
+                        end: offset + m.end(),
+                    })
+                })
+            }
+            Self::Regex { re, multiline: true } => {
+                let m = re.find_iter(content).find(|m| !m.is_empty())?;
+                let line_index = content[..m.start()].matches('\n').count();
+                let line = content.lines().nth(line_index).unwrap_or_default();
+                Some(FirstMatch {
+                    line_index,
+                    line,
+                    matched: m.as_str().to_lowercase(),
+                    start: m.start(),
+                    end: m.end(),
+                })
+            }
+        }
+    }
+}
+
+/// Whether a file has been seen already. Following symlinks, the same file can
+/// be reached through several links (or a link and its real folder); only the
+/// first path found is reported. Loops are cut by the walker itself.
+fn first_visit(seen: &mut HashSet<PathBuf>, path: &Path) -> bool {
+    seen.insert(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+/// Search files by filename pattern. Symlinked folders are searched unless
+/// `follow_symlinks` is false.
+/// With `fuzzy`, the query is matched fzf-style against the path under `root`
+/// and results come back best-first with a `score` (match quality, nudged by recency).
+/// `filters` narrows by extension, size, modified date and path prefix.
+#[command]
+pub async fn search_files(
+    root: String,
+    query: String,
+    extensions: Option<Vec<String>>,
+    max_results: Option<usize>,
+    follow_symlinks: Option<bool>,
+    fuzzy: Option<bool>,
+    filters: Option<SearchFilters>,
+) -> CmdResult<Vec<SearchResult>> {
+    let query_lower = query.to_lowercase();
+    let max = max_results.unwrap_or(100);
+    let follow = follow_symlinks.unwrap_or(true);
+    let filters = filters.unwrap_or_default().resolve(Path::new(&root))?;
+    let prefix_filter = filters.clone();
+    let mut fuzzy_matcher = fuzzy.unwrap_or(false).then(|| FuzzyMatcher::new(&query));
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+    let mut candidates = 0;
+
+    let walker = WalkBuilder::new(&root)
+        .hidden(true)           // Respect hidden files
+        .git_ignore(true)       // Respect .gitignore
+        .git_global(true)
+        .git_exclude(true)
+        .follow_links(follow)
+        .filter_entry(move |e| prefix_filter.allows_path(e.path()))
+        .build();
+
+    for entry in walker.flatten() {
+        if fuzzy_matcher.is_none() && results.len() >= max {
+            break;
+        }
+
+        let path = entry.path();
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Skip directories for filename search
+        if path.is_dir() {
+            continue;
+        }
+
+        // Check extension filter
+        if let Some(ref exts) = extensions {
+            if let Some(ext) = path.extension() {
+                if !exts.iter().any(|e| e == &ext.to_string_lossy().to_string()) {
+                    continue;
+                }
+            } else {
+                continue;
+            }
+        }
+        if !filters.matches_path(path) {
+            continue;
+        }
+
+        // Match filename (fuzzy: the path under root)
+        let (score, metadata) = match fuzzy_matcher.as_mut() {
+            Some(matcher) => {
+                candidates += 1;
+                if candidates > MAX_FUZZY_CANDIDATES {
+                    break;
+                }
+                let relative = path.strip_prefix(&root).unwrap_or(path).to_string_lossy();
+                let Some(score) = matcher.score(&relative) else {
+                    continue;
+                };
+                let metadata = entry.metadata().ok();
+                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+                (Some(with_recency(score, modified)), metadata)
+            }
+            None if name.to_lowercase().contains(&query_lower) => (None, entry.metadata().ok()),
+            None => continue,
+        };
+        if !filters.matches_metadata(metadata.as_ref()) {
+            continue;
+        }
+        if follow && !first_visit(&mut seen, path) {
+            continue;
+        }
+
+        results.push(SearchResult {
+            name,
+            path: path.to_string_lossy().to_string(),
+            is_directory: false,
+            size: metadata.as_ref().map(|m| m.len()),
+            match_type: "filename".to_string(),
+            preview: None,
+            line_number: None,
+            score,
+            span: None,
+        });
+    }
+
+    if fuzzy_matcher.is_some() {
+        results.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+        results.truncate(max);
+    }
+    Ok(results)
+}
+
+/// A content search ready to walk: compiled matcher plus resolved options
+struct ContentScan {
+    root: String,
+    matcher: Matcher,
+    extensions: Vec<String>,
+    filters: ResolvedFilters,
+    ranking: SearchRanking,
+    follow: bool,
+}
+
+impl ContentScan {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        root: String,
+        query: &str,
+        extensions: Option<Vec<String>>,
+        ranking: Option<SearchRanking>,
+        follow_symlinks: Option<bool>,
+        regex: Option<bool>,
+        multiline: Option<bool>,
+        filters: Option<SearchFilters>,
+        match_options: Option<MatchOptions>,
+    ) -> CmdResult<Self> {
+        let matcher = Matcher::new(
+            query,
+            regex.unwrap_or(false),
+            multiline.unwrap_or(false),
+            match_options.unwrap_or_default(),
+        )?;
+        let filters = filters.unwrap_or_default().resolve(Path::new(&root))?;
+
+        // Default to common text extensions
+        let extensions = extensions
+            .or_else(|| filters.extensions().map(|e| e.to_vec()))
+            .unwrap_or_else(|| DEFAULT_CONTENT_EXTENSIONS.into_iter().map(String::from).collect());
+
+        Ok(Self {
+            root,
+            matcher,
+            extensions,
+            filters,
+            ranking: ranking.unwrap_or_default(),
+            follow: follow_symlinks.unwrap_or(true),
+        })
+    }
+
+    /// Walk the tree, handing each matching file to `on_hit` in walk order.
+    /// Stops early when `on_hit` returns false or `cancelled` is set.
+    fn run(&self, cancelled: &AtomicBool, mut on_hit: impl FnMut(SearchResult) -> bool) {
+        self.run_with_content(cancelled, |result, _| on_hit(result));
+    }
+
+    /// `run`, also handing over each matching file's content
+    fn run_with_content(&self, cancelled: &AtomicBool, mut on_hit: impl FnMut(SearchResult, &str) -> bool) {
+        let mut seen = HashSet::new();
+        let prefix_filter = self.filters.clone();
+
+        let walker = WalkBuilder::new(&self.root)
+            .hidden(true)
+            .git_ignore(true)
+            .follow_links(self.follow)
+            .filter_entry(move |e| prefix_filter.allows_path(e.path()))
+            .build();
+
+        for entry in walker.flatten() {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            // Check extension
+            let ext = path.extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !self.extensions.contains(&ext) || !self.filters.matches_path(path) {
+                continue;
+            }
+            if self.follow && !first_visit(&mut seen, path) {
+                continue;
+            }
+
+            // Skip large files (> 1MB)
+            let metadata = path.metadata().ok();
+            if metadata.as_ref().map(|m| m.len() > 1_000_000).unwrap_or(false) {
+                continue;
+            }
+            if !self.filters.matches_metadata(metadata.as_ref()) {
+                continue;
+            }
+
+            // Read and search file — first matching line becomes the preview
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            let Some(found) = self.matcher.first_match(&content) else {
+                continue;
+            };
+
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            let keep_going = on_hit(SearchResult {
+                name: path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path: path.to_string_lossy().to_string(),
+                is_directory: false,
+                size: None,
+                match_type: "content".to_string(),
+                preview: Some(
+                    found.line.trim().chars().take(200).collect::<String>()
+                ),
+                line_number: Some(found.line_index + 1),
+                score: Some(score_document(&content, path, &found.matched, modified, &self.ranking)),
+                span: Some(match_span(&content, found.start, found.end)),
+            }, &content);
+            if !keep_going {
+                return;
+            }
+        }
+    }
+}
+
+/// Search file content for a query string, or with `regex` a pattern (case-sensitive
+/// unless it starts with `(?i)`; `multiline` lets it span lines). Invalid or oversized
+/// patterns are a validation error on `query`.
+/// `match_options` sets case handling (sensitive, insensitive or smart case) and
+/// whole-word matching for either kind of query.
+/// Every matching file is scored (see `ranking`) and the top `max_results` are returned
+/// best-first, each with the `span` (byte offsets and line/columns) of its first match.
+/// Symlinked folders are searched unless `follow_symlinks` is false.
+/// `filters` narrows by extension, size, modified date and path prefix; its
+/// extensions replace the default text list when `extensions` isn't given.
+/// For results as they're found, see `search_content_stream`.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_content(
+    root: String,
+    query: String,
+    extensions: Option<Vec<String>>,
+    max_results: Option<usize>,
+    ranking: Option<SearchRanking>,
+    follow_symlinks: Option<bool>,
+    regex: Option<bool>,
+    multiline: Option<bool>,
+    filters: Option<SearchFilters>,
+    match_options: Option<MatchOptions>,
+) -> CmdResult<Vec<SearchResult>> {
+    let scan = ContentScan::new(
+        root,
+        &query,
+        extensions,
+        ranking,
+        follow_symlinks,
+        regex,
+        multiline,
+        filters,
+        match_options,
+    )?;
+    let max = max_results.unwrap_or(50);
+    let mut results = Vec::new();
+    scan.run(&AtomicBool::new(false), |result| {
+        results.push(result);
+        true
+    });
+
+    results.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results.truncate(max);
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(query: &str, regex: bool, multiline: bool) -> CmdResult<Matcher> {
+        Matcher::new(query, regex, multiline, MatchOptions::default())
+    }
+
+    #[test]
+    fn matches_plain_and_regex_queries() {
+        let content = "name: Orders\ncolumns:\n  - usr_created_by_id\n  - usr_owner_id\n";
+
+        let plain = matcher("ORDERS", false, false).unwrap();
+        assert_eq!(plain.first_match(content).map(|m| m.line_index), Some(0));
+
+        let re = matcher(r"usr_[a-z_]+_id", true, false).unwrap();
+        let found = re.first_match(content).unwrap();
+        assert_eq!(
+            (found.line_index, found.line.trim(), found.matched.as_str()),
+            (2, "- usr_created_by_id", "usr_created_by_id")
+        );
+        // Case-sensitive unless asked
+        assert!(matcher("orders", true, false).unwrap().first_match(content).is_none());
+        assert!(matcher("(?i)orders", true, false).unwrap().first_match(content).is_some());
+        // Empty matches don't count
+        assert!(matcher("x*", true, false).unwrap().first_match(content).is_none());
+
+        // Spanning lines needs multiline
+        assert!(matcher(r"columns:\s+- usr", true, false).unwrap().first_match(content).is_none());
+        let spanning = matcher(r"^columns:\s+- usr", true, true).unwrap();
+        assert_eq!(spanning.first_match(content).map(|m| m.line_index), Some(1));
+
+        let invalid = |r: CmdResult<Matcher>| matches!(r, Err(CommandError::Validation { .. }));
+        assert!(invalid(matcher("usr_(", true, false)));
+        assert!(invalid(matcher(r"(\w{1000}){1000}", true, false)));
+        assert!(invalid(matcher(&"a".repeat(MAX_PATTERN_LEN + 1), true, false)));
+    }
+
+    #[test]
+    fn applies_match_options_and_reports_spans() {
+        let content = "usr_orders_id\r\nSee Orders and orders_v2\nÉtat Orders\n";
+        let span = |query: &str, regex: bool, case: Option<CaseMode>, whole_word: bool| {
+            let options = MatchOptions { case, whole_word };
+            let found = Matcher::new(query, regex, regex, options).unwrap().first_match(content)?;
+            let span = match_span(content, found.start, found.end);
+            Some((&content[span.start..span.end], span.end_line, span.column, span.end_column))
+        };
+
+        // Plain: case-insensitive substring; offsets survive the \r\n
+        assert_eq!(span("orders", false, None, false), Some(("orders", 1, 5, 11)));
+        assert_eq!(span("ORDERS", false, None, false), Some(("orders", 1, 5, 11)));
+        // Whole word skips usr_orders_id; case-sensitive skips "orders_v2" too
+        assert_eq!(span("orders", false, None, true), Some(("Orders", 2, 5, 11)));
+        assert_eq!(span("orders", false, Some(CaseMode::Sensitive), true), None);
+        // Smart case: an uppercase letter makes it sensitive
+        assert_eq!(span("Orders", false, Some(CaseMode::Smart), false), Some(("Orders", 2, 5, 11)));
+        assert_eq!(span("orders", false, Some(CaseMode::Smart), false), Some(("orders", 1, 5, 11)));
+        // Regexes can opt into insensitivity; columns count characters, not bytes
+        assert_eq!(span(r"état \w+", true, Some(CaseMode::Insensitive), false), Some(("État Orders", 3, 1, 12)));
+        // Multiline spans report where they end
+        assert_eq!(span(r"v2\nÉtat", true, None, false), Some(("v2\nÉtat", 3, 23, 5)));
+
+        // Lowercasing that changes lengths still maps back to the original bytes
+        assert_eq!(find_lowercase("xİstanbul", "stan"), Some((3, 7)));
+    }
+}
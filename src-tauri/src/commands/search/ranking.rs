@@ -0,0 +1,168 @@
+// src-tauri/src/commands/search/ranking.rs
+// Relevance scoring for content search results
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Ranking weights. Every field is optional on the wire; missing fields use the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchRanking {
+    /// Weight on log-scaled occurrence count of the query in the document
+    pub term_frequency_weight: f64,
+    /// Bonus when the filename, frontmatter title, or first H1 contains the query
+    pub title_weight: f64,
+    /// Bonus when any other frontmatter value contains the query
+    pub frontmatter_weight: f64,
+    /// Maximum bonus for a file modified just now, decaying with age
+    pub recency_weight: f64,
+    /// Age at which the recency bonus has halved
+    pub recency_half_life_days: f64,
+    /// Folder name (case-insensitive) -> score multiplier, applied for every
+    /// matching path component. Use < 1.0 to demote, > 1.0 to promote.
+    pub folder_weights: HashMap<String, f64>,
+}
+
+impl Default for SearchRanking {
+    fn default() -> Self {
+        let folder_weights = [("archive", 0.3), ("_archive", 0.3), ("old", 0.5)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        Self {
+            term_frequency_weight: 1.0,
+            title_weight: 5.0,
+            frontmatter_weight: 3.0,
+            recency_weight: 2.0,
+            recency_half_life_days: 30.0,
+            folder_weights,
+        }
+    }
+}
+
+/// Frontmatter block (between leading `---` lines), if any
+fn frontmatter(content: &str) -> Option<&str> {
+    let rest = content.strip_prefix("---")?;
+    rest.find("\n---").map(|end| &rest[..end])
+}
+
+fn title_hit(content_lower: &str, path: &Path, query_lower: &str) -> bool {
+    let stem_hit = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase().contains(query_lower))
+        .unwrap_or(false);
+    if stem_hit {
+        return true;
+    }
+    let fm_title_hit = frontmatter(content_lower)
+        .map(|fm| {
+            fm.lines()
+                .filter_map(|l| l.strip_prefix("title:"))
+                .any(|v| v.contains(query_lower))
+        })
+        .unwrap_or(false);
+    if fm_title_hit {
+        return true;
+    }
+    content_lower
+        .lines()
+        .find(|l| l.starts_with("# "))
+        .map(|h1| h1.contains(query_lower))
+        .unwrap_or(false)
+}
+
+fn frontmatter_hit(content_lower: &str, query_lower: &str) -> bool {
+    frontmatter(content_lower)
+        .map(|fm| {
+            fm.lines()
+                .filter(|l| !l.starts_with("title:"))
+                .filter_map(|l| l.split_once(':').map(|(_, v)| v).or(Some(l)))
+                .any(|v| v.contains(query_lower))
+        })
+        .unwrap_or(false)
+}
+
+fn recency_score(modified: Option<SystemTime>, ranking: &SearchRanking) -> f64 {
+    let Some(modified) = modified else {
+        return 0.0;
+    };
+    if ranking.recency_half_life_days <= 0.0 {
+        return 0.0;
+    }
+    let age_days = SystemTime::now()
+        .duration_since(modified)
+        .map(|d| d.as_secs_f64() / 86_400.0)
+        .unwrap_or(0.0);
+    ranking.recency_weight * 0.5_f64.powf(age_days / ranking.recency_half_life_days)
+}
+
+fn folder_multiplier(path: &Path, ranking: &SearchRanking) -> f64 {
+    if ranking.folder_weights.is_empty() {
+        return 1.0;
+    }
+    let weights: HashMap<String, f64> = ranking
+        .folder_weights
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), *v))
+        .collect();
+    path.parent()
+        .map(|p| {
+            p.components()
+                .filter_map(|c| weights.get(&c.as_os_str().to_string_lossy().to_lowercase()))
+                .product()
+        })
+        .unwrap_or(1.0)
+}
+
+/// Score a document that matched `query_lower`. Higher is better.
+pub fn score_document(
+    content: &str,
+    path: &Path,
+    query_lower: &str,
+    modified: Option<SystemTime>,
+    ranking: &SearchRanking,
+) -> f64 {
+    let content_lower = content.to_lowercase();
+    let occurrences = content_lower.matches(query_lower).count() as f64;
+
+    let mut score = ranking.term_frequency_weight * (1.0 + occurrences).ln();
+    if title_hit(&content_lower, path, query_lower) {
+        score += ranking.title_weight;
+    }
+    if frontmatter_hit(&content_lower, query_lower) {
+        score += ranking.frontmatter_weight;
+    }
+    score += recency_score(modified, ranking);
+
+    score * folder_multiplier(path, ranking)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_and_frontmatter_hits_outrank_body_mentions() {
+        let ranking = SearchRanking::default();
+        let titled = "---\ntitle: Pricing model\ntags: [pricing]\n---\nBody text.\n";
+        let body_only = "Notes\n\npricing pricing pricing came up again.\n";
+        let a = score_document(titled, Path::new("/kb/a.md"), "pricing", None, &ranking);
+        let b = score_document(body_only, Path::new("/kb/b.md"), "pricing", None, &ranking);
+        assert!(a > b, "{} should beat {}", a, b);
+    }
+
+    #[test]
+    fn archive_folders_are_demoted_and_recent_files_boosted() {
+        let ranking = SearchRanking::default();
+        let doc = "# Roadmap\nroadmap details\n";
+        let live = score_document(doc, Path::new("/kb/plans/roadmap.md"), "roadmap", None, &ranking);
+        let archived = score_document(doc, Path::new("/kb/Archive/roadmap.md"), "roadmap", None, &ranking);
+        assert!(live > archived);
+
+        let fresh = score_document(doc, Path::new("/kb/x.md"), "details", Some(SystemTime::now()), &ranking);
+        let stale = score_document(doc, Path::new("/kb/x.md"), "details", None, &ranking);
+        assert!((fresh - stale - ranking.recency_weight).abs() < 0.01);
+    }
+}
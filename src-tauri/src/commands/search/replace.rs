@@ -0,0 +1,358 @@
+// src-tauri/src/commands/search/replace.rs
+// Search-and-replace across files: a dry run returns per-file previews of the
+// changed lines, then applying rewrites each file atomically after
+// snapshotting the original into its version store, so every replaced file
+// shows up in list_versions and can be undone with restore_version. Regex
+// mode supports $1 / ${name} capture references in the replacement.
+
+use super::{compile_pattern, first_visit, SearchFilters, DEFAULT_CONTENT_EXTENSIONS};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::{atomic_write, snapshot_before_edit};
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Files listed in a dry run; applying still rewrites every match
+const DEFAULT_MAX_PREVIEW_FILES: usize = 500;
+/// Changed lines shown per file
+const MAX_PREVIEW_CHANGES: usize = 20;
+/// Characters kept of a previewed line
+const MAX_PREVIEW_CHARS: usize = 400;
+/// Same cap as content search
+const MAX_FILE_BYTES: u64 = 1_000_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplaceOptions {
+    pub root: String,
+    /// Treat the query as a regex (case-sensitive unless it starts with `(?i)`)
+    pub regex: bool,
+    /// Let a regex span lines (`^`/`$` match per line, `.` crosses newlines)
+    pub multiline: bool,
+    /// Plain queries match case-insensitively unless this is set
+    pub case_sensitive: bool,
+    pub extensions: Option<Vec<String>>,
+    pub filters: Option<SearchFilters>,
+    /// Search inside symlinked folders (default true)
+    pub follow_symlinks: Option<bool>,
+    /// Only touch these files, e.g. the ones kept from the preview
+    pub paths: Option<Vec<String>>,
+    /// Default true: preview only
+    pub dry_run: Option<bool>,
+    /// Default true: snapshot originals (see list_versions) before rewriting them
+    pub backup: Option<bool>,
+    /// Files listed in a dry run (default 500)
+    pub max_files: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaceLineChange {
+    /// 1-based line the change starts on
+    pub line: usize,
+    /// The affected line(s) before and after
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceFileResult {
+    pub path: String,
+    pub replacements: usize,
+    /// First few changed lines
+    pub changes: Vec<ReplaceLineChange>,
+    /// Snapshot of the original, for restore_version
+    pub version_id: Option<String>,
+    /// Set when applying to this file failed; it was left untouched
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceResult {
+    pub dry_run: bool,
+    pub files_matched: usize,
+    pub files_changed: usize,
+    pub replacements: usize,
+    pub files: Vec<ReplaceFileResult>,
+    /// More files matched than a dry run lists
+    pub truncated: bool,
+}
+
+// ============================================================================
+// Replacing
+// ============================================================================
+
+/// Cut a previewed line to MAX_PREVIEW_CHARS
+fn clip(text: &str) -> String {
+    if text.chars().count() > MAX_PREVIEW_CHARS {
+        format!("{}…", text.chars().take(MAX_PREVIEW_CHARS).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+/// `content` with every non-empty match replaced (literally, or expanding
+/// capture references), the replacement count, and the changed lines
+fn replace_all(re: &Regex, content: &str, replacement: &str, literal: bool) -> (String, usize, Vec<ReplaceLineChange>) {
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    // (original start, original end, new start, new end) per match
+    let mut spans: Vec<(usize, usize, usize, usize)> = Vec::new();
+    for caps in re.captures_iter(content) {
+        let m = caps.get(0).unwrap();
+        if m.is_empty() {
+            continue;
+        }
+        out.push_str(&content[last..m.start()]);
+        let new_start = out.len();
+        if literal {
+            out.push_str(replacement);
+        } else {
+            caps.expand(replacement, &mut out);
+        }
+        spans.push((m.start(), m.end(), new_start, out.len()));
+        last = m.end();
+    }
+    out.push_str(&content[last..]);
+
+    // Widen each match to whole lines; matches on the same lines share a change
+    let mut groups: Vec<(usize, usize, usize, usize)> = Vec::new();
+    for (start, end, new_start, new_end) in spans.iter().copied() {
+        let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = content[end..].find('\n').map(|i| end + i).unwrap_or(content.len());
+        let new_line_start = new_start - (start - line_start);
+        let new_line_end = new_end + (line_end - end);
+        match groups.last_mut() {
+            Some(group) if line_start <= group.1 => {
+                group.1 = line_end;
+                group.3 = new_line_end;
+            }
+            _ => groups.push((line_start, line_end, new_line_start, new_line_end)),
+        }
+    }
+    let changes = groups
+        .into_iter()
+        .take(MAX_PREVIEW_CHANGES)
+        .map(|(start, end, new_start, new_end)| ReplaceLineChange {
+            line: content[..start].matches('\n').count() + 1,
+            before: clip(&content[start..end]),
+            after: clip(&out[new_start..new_end]),
+        })
+        .collect();
+    (out, spans.len(), changes)
+}
+
+/// Candidate files under `root`, the same set search_content reads
+fn candidate_files(root: &str, options: &ReplaceOptions) -> CmdResult<Vec<PathBuf>> {
+    let follow = options.follow_symlinks.unwrap_or(true);
+    let filters = options.filters.clone().unwrap_or_default().resolve(Path::new(root))?;
+    let prefix_filter = filters.clone();
+    let exts = options
+        .extensions
+        .clone()
+        .or_else(|| filters.extensions().map(|e| e.to_vec()))
+        .unwrap_or_else(|| DEFAULT_CONTENT_EXTENSIONS.into_iter().map(String::from).collect());
+    let only: Option<HashSet<PathBuf>> = options.paths.as_ref().map(|paths| paths.iter().map(PathBuf::from).collect());
+
+    let walker = WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .follow_links(follow)
+        .filter_entry(move |e| prefix_filter.allows_path(e.path()))
+        .build();
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let ext = path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        if !exts.contains(&ext) || !filters.matches_path(path) {
+            continue;
+        }
+        if only.as_ref().is_some_and(|only| !only.contains(path)) {
+            continue;
+        }
+        if follow && !first_visit(&mut seen, path) {
+            continue;
+        }
+        let metadata = path.metadata().ok();
+        if metadata.as_ref().map(|m| m.len() > MAX_FILE_BYTES).unwrap_or(false) {
+            continue;
+        }
+        if !filters.matches_metadata(metadata.as_ref()) {
+            continue;
+        }
+        files.push(path.to_path_buf());
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Snapshot the original (when `snapshot`), then write the new content over
+/// it. A failed snapshot leaves the file untouched.
+fn apply_file(path: &Path, updated: &str, snapshot: bool) -> CmdResult<Option<String>> {
+    let version_id = if snapshot {
+        snapshot_before_edit(path, updated.as_bytes())
+            .map_err(|e| CommandError::Io(format!("Failed to snapshot {}: {}", path.display(), e)))?
+    } else {
+        None
+    };
+    atomic_write(path, updated.as_bytes())?;
+    Ok(version_id)
+}
+
+fn run_replace(query: &str, replacement: &str, options: &ReplaceOptions) -> CmdResult<ReplaceResult> {
+    let root = options.root.trim();
+    if root.is_empty() {
+        return Err(CommandError::Validation {
+            message: "A search root is required".to_string(),
+            fields: vec!["options.root".to_string()],
+        });
+    }
+    if query.is_empty() {
+        return Err(CommandError::Validation { message: "Nothing to search for".to_string(), fields: vec!["query".to_string()] });
+    }
+    let re = compile_pattern(query, options.regex, options.multiline, !options.regex && !options.case_sensitive)?;
+    let dry_run = options.dry_run.unwrap_or(true);
+    let max_files = options.max_files.unwrap_or(DEFAULT_MAX_PREVIEW_FILES);
+    let snapshot = options.backup.unwrap_or(true);
+
+    let mut result = ReplaceResult {
+        dry_run,
+        files_matched: 0,
+        files_changed: 0,
+        replacements: 0,
+        files: Vec::new(),
+        truncated: false,
+    };
+    for path in candidate_files(root, options)? {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let (updated, count, changes) = replace_all(&re, &content, replacement, !options.regex);
+        if count == 0 {
+            continue;
+        }
+        result.files_matched += 1;
+        result.replacements += count;
+        if dry_run && result.files.len() >= max_files {
+            result.truncated = true;
+            continue;
+        }
+
+        let mut file = ReplaceFileResult {
+            path: path.to_string_lossy().to_string(),
+            replacements: count,
+            changes,
+            version_id: None,
+            error: None,
+        };
+        if !dry_run && updated != content {
+            match apply_file(&path, &updated, snapshot) {
+                Ok(version_id) => {
+                    file.version_id = version_id;
+                    result.files_changed += 1;
+                }
+                Err(e) => file.error = Some(e.to_string()),
+            }
+        }
+        result.files.push(file);
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Replace `query` with `replacement` in every text file under `options.root`.
+/// Matches like search_content (plain queries case-insensitive by default;
+/// regex replacements may use $1 / ${name}). Defaults to a dry run returning
+/// per-file previews; with `dry_run: false` each file is rewritten atomically
+/// and, unless `backup` is false, its original snapshotted as `version_id`.
+/// A file that fails to write is reported with `error` and the rest still apply.
+#[command]
+pub async fn replace_content(query: String, replacement: String, options: ReplaceOptions) -> CmdResult<ReplaceResult> {
+    tauri::async_runtime::spawn_blocking(move || run_replace(&query, &replacement, &options))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Replace task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::files::{list_versions, restore_version};
+
+    #[test]
+    fn replaces_with_captures_and_previews_lines() {
+        let content = "# Orders\nSee usr_orders and USR_ORDERS.\n\n| usr_orders_v2 | x |\nend";
+
+        let plain = compile_pattern("usr_orders", false, false, true).unwrap();
+        let (out, count, changes) = replace_all(&plain, content, "$1 crm_orders", true);
+        assert_eq!(count, 3);
+        assert!(out.contains("See $1 crm_orders and $1 crm_orders."));
+        assert_eq!(changes.len(), 2, "matches on one line share a change");
+        assert_eq!(changes[0].line, 2);
+        assert_eq!(changes[0].before, "See usr_orders and USR_ORDERS.");
+        assert_eq!(changes[1], ReplaceLineChange {
+            line: 4,
+            before: "| usr_orders_v2 | x |".into(),
+            after: "| $1 crm_orders_v2 | x |".into(),
+        });
+
+        let re = compile_pattern(r"usr_(?P<table>\w+?)(_v\d+)?\b", true, false, false).unwrap();
+        let (out, count, _) = replace_all(&re, content, "crm_${table}$2", false);
+        assert_eq!(count, 2);
+        assert!(out.contains("See crm_orders and USR_ORDERS."));
+        assert!(out.contains("| crm_orders_v2 |"));
+
+        // A change spanning lines previews every line it touches
+        let spanning = compile_pattern(r"Orders\nSee", true, true, false).unwrap();
+        let (_, _, changes) = replace_all(&spanning, content, "Orders - see", false);
+        assert_eq!(changes[0].before, "# Orders\nSee usr_orders and USR_ORDERS.");
+        assert_eq!(changes[0].after, "# Orders - see usr_orders and USR_ORDERS.");
+
+        // Empty matches are skipped rather than inserting everywhere
+        let empty = compile_pattern("x*", true, false, false).unwrap();
+        assert_eq!(replace_all(&empty, "abc", "-", false).1, 0);
+    }
+
+    #[test]
+    fn applies_with_version_snapshots() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("a")).unwrap();
+        let doc = root.join("a/overview.md");
+        fs::write(&doc, "table: usr_orders\n").unwrap();
+        fs::write(root.join("notes.txt"), "nothing here\n").unwrap();
+        let options = |dry_run: bool| ReplaceOptions {
+            root: root.to_string_lossy().to_string(),
+            dry_run: Some(dry_run),
+            ..Default::default()
+        };
+
+        let preview = run_replace("usr_orders", "crm_orders", &options(true)).unwrap();
+        assert_eq!((preview.files_matched, preview.files_changed), (1, 0));
+        assert_eq!(fs::read_to_string(&doc).unwrap(), "table: usr_orders\n");
+
+        let applied = run_replace("usr_orders", "crm_orders", &options(false)).unwrap();
+        assert_eq!(applied.files_changed, 1);
+        assert_eq!(fs::read_to_string(&doc).unwrap(), "table: crm_orders\n");
+        let version_id = applied.files[0].version_id.clone().unwrap();
+        let path = doc.to_string_lossy().to_string();
+        let versions = list_versions(path.clone()).unwrap();
+        assert_eq!(versions[0].id, version_id);
+        assert_eq!(fs::read_to_string(&versions[0].snapshot_path).unwrap(), "table: usr_orders\n");
+        restore_version(path, version_id).unwrap();
+        assert_eq!(fs::read_to_string(&doc).unwrap(), "table: usr_orders\n");
+    }
+}
@@ -0,0 +1,813 @@
+// src-tauri/src/commands/search/semantic.rs
+// Semantic search over a knowledge root: markdown docs are split into
+// heading-scoped chunks, embedded with the configured provider and stored in
+// SQLite next to the full-text index (~/.tv-client/search_index/<hash>/).
+// A query is embedded the same way and chunks are ranked by cosine
+// similarity, so the folder chat can find passages that share meaning but
+// not keywords. Builds are incremental: only files whose mtime changed are
+// re-embedded, and switching provider or model rebuilds from scratch.
+
+use super::fulltext::{canonical_root, dir_for};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::index::{doc_meta, INDEX_FILE};
+use crate::commands::files::related::{cosine, knowledge_root};
+use crate::commands::settings;
+use ignore::WalkBuilder;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::time::Instant;
+use tauri::command;
+
+const DB_FILE: &str = "semantic.db";
+const MAX_FILE_BYTES: u64 = 1_000_000;
+/// Sections longer than this are split at paragraph breaks
+const MAX_CHUNK_CHARS: usize = 1_500;
+/// Chunks with less text than this (a bare heading, a lone link) aren't embedded
+const MIN_CHUNK_CHARS: usize = 40;
+/// One oversized paragraph is cut to this before embedding
+const MAX_EMBED_CHARS: usize = 6_000;
+/// Texts per embedding request
+const EMBED_BATCH: usize = 64;
+const DEFAULT_TOP_K: usize = 8;
+const DEFAULT_LOCAL_URL: &str = "http://localhost:11434";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIndexStats {
+    pub root: String,
+    /// "provider:model" the vectors came from
+    pub embedder: String,
+    pub files: u64,
+    pub chunks: u64,
+    /// Files (re-)embedded by this build
+    pub embedded: usize,
+    pub removed: usize,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIndexStatus {
+    pub root: String,
+    pub embedder: Option<String>,
+    pub files: u64,
+    pub chunks: u64,
+    pub built_at: Option<String>,
+}
+
+/// One matching passage, with enough to cite and open it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticChunk {
+    pub path: String,
+    /// Relative to the folder that was searched
+    pub relative_path: String,
+    pub title: String,
+    pub heading: Option<String>,
+    /// 1-based, inclusive
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    pub score: f32,
+}
+
+/// A whole document's vector: the normalized mean of its chunk vectors
+#[derive(Debug, Clone)]
+pub struct DocumentVector {
+    pub path: String,
+    pub title: String,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Chunk {
+    heading: Option<String>,
+    /// 0-based line indexes, inclusive
+    first: usize,
+    last: usize,
+    text: String,
+}
+
+// ============================================================================
+// Embedding providers
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingProvider {
+    OpenAi,
+    Gemini,
+    /// Any Ollama-compatible server (`POST /api/embed`)
+    Local,
+}
+
+impl EmbeddingProvider {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "openai" => Some(Self::OpenAi),
+            "gemini" => Some(Self::Gemini),
+            "local" | "ollama" => Some(Self::Local),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Gemini => "gemini",
+            Self::Local => "local",
+        }
+    }
+
+    fn default_model(self) -> &'static str {
+        match self {
+            Self::OpenAi => "text-embedding-3-small",
+            Self::Gemini => "text-embedding-004",
+            Self::Local => "nomic-embed-text",
+        }
+    }
+}
+
+pub struct Embedder {
+    provider: EmbeddingProvider,
+    model: String,
+    api_key: Option<String>,
+    local_url: String,
+}
+
+fn floats(value: &serde_json::Value) -> Vec<f32> {
+    value
+        .as_array()
+        .map(|a| a.iter().filter_map(|x| x.as_f64()).map(|x| x as f32).collect())
+        .unwrap_or_default()
+}
+
+fn normalize(mut v: Vec<f32>) -> Vec<f32> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    v
+}
+
+impl Embedder {
+    /// Provider from `embedding_provider` (openai | gemini | local) and model
+    /// from `embedding_model`. Unset, the first of OpenAI and Gemini with a
+    /// key is used, else the local model. Anthropic has no embeddings
+    /// endpoint, so an Anthropic key alone means the local model.
+    pub fn from_settings() -> CmdResult<Self> {
+        let stored = settings::load_settings()?;
+        let key = |name: &str| stored.keys.get(name).filter(|v| !v.trim().is_empty()).cloned();
+        let provider = match key(settings::KEY_EMBEDDING_PROVIDER) {
+            Some(p) => EmbeddingProvider::parse(&p).ok_or_else(|| {
+                CommandError::Config(format!("Unknown embedding provider '{}' (use openai, gemini or local)", p))
+            })?,
+            None if key(settings::KEY_OPENAI_API).is_some() => EmbeddingProvider::OpenAi,
+            None if key(settings::KEY_GEMINI_API).is_some() => EmbeddingProvider::Gemini,
+            None => EmbeddingProvider::Local,
+        };
+        let api_key = match provider {
+            EmbeddingProvider::OpenAi => Some(key(settings::KEY_OPENAI_API).ok_or_else(|| {
+                CommandError::Config("OpenAI API key not configured. Go to Settings (⌘,) to add it.".into())
+            })?),
+            EmbeddingProvider::Gemini => Some(key(settings::KEY_GEMINI_API).ok_or_else(|| {
+                CommandError::Config("Gemini API key not configured. Go to Settings (⌘,) to add it.".into())
+            })?),
+            EmbeddingProvider::Local => None,
+        };
+        Ok(Self {
+            provider,
+            model: key(settings::KEY_EMBEDDING_MODEL).unwrap_or_else(|| provider.default_model().to_string()),
+            api_key,
+            local_url: key(settings::KEY_LOCAL_EMBEDDING_URL)
+                .unwrap_or_else(|| DEFAULT_LOCAL_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+        })
+    }
+
+    /// Vectors from different models aren't comparable, so the index records this
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.provider.as_str(), self.model)
+    }
+
+    /// Unit-length vectors, one per text, in order
+    pub async fn embed(&self, texts: &[String]) -> CmdResult<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH) {
+            let embedded = self.embed_batch(batch).await?;
+            if embedded.len() != batch.len() || embedded.iter().any(|v| v.is_empty()) {
+                return Err(CommandError::Parse(format!(
+                    "Embedding provider returned {} vectors for {} texts",
+                    embedded.len(),
+                    batch.len()
+                )));
+            }
+            vectors.extend(embedded.into_iter().map(normalize));
+        }
+        Ok(vectors)
+    }
+
+    async fn embed_batch(&self, batch: &[String]) -> CmdResult<Vec<Vec<f32>>> {
+        let key = self.api_key.as_deref().unwrap_or_default();
+        let request = match self.provider {
+            EmbeddingProvider::OpenAi => crate::HTTP_CLIENT
+                .post("https://api.openai.com/v1/embeddings")
+                .bearer_auth(key)
+                .json(&json!({ "model": self.model, "input": batch })),
+            EmbeddingProvider::Gemini => {
+                let requests: Vec<serde_json::Value> = batch
+                    .iter()
+                    .map(|text| json!({ "model": format!("models/{}", self.model), "content": { "parts": [{ "text": text }] } }))
+                    .collect();
+                crate::HTTP_CLIENT
+                    .post(format!(
+                        "https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents",
+                        self.model
+                    ))
+                    .header("x-goog-api-key", key)
+                    .json(&json!({ "requests": requests }))
+            }
+            EmbeddingProvider::Local => crate::HTTP_CLIENT
+                .post(format!("{}/api/embed", self.local_url))
+                .json(&json!({ "model": self.model, "input": batch })),
+        };
+
+        let response = request.send().await.map_err(|e| match self.provider {
+            EmbeddingProvider::Local => CommandError::Network(format!(
+                "Local embedding server not reachable at {} ({}). Start Ollama or set an embedding provider in Settings.",
+                self.local_url, e
+            )),
+            _ => e.into(),
+        })?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CommandError::Http { status, body: body.chars().take(500).collect() });
+        }
+        let value: serde_json::Value = response.json().await?;
+        let vectors = match self.provider {
+            EmbeddingProvider::OpenAi => value["data"].as_array().map(|data| {
+                let mut data: Vec<&serde_json::Value> = data.iter().collect();
+                data.sort_by_key(|d| d["index"].as_u64().unwrap_or_default());
+                data.into_iter().map(|d| floats(&d["embedding"])).collect()
+            }),
+            EmbeddingProvider::Gemini => value["embeddings"]
+                .as_array()
+                .map(|e| e.iter().map(|v| floats(&v["values"])).collect()),
+            EmbeddingProvider::Local => value["embeddings"].as_array().map(|e| e.iter().map(floats).collect()),
+        };
+        vectors.ok_or_else(|| CommandError::Parse("Unexpected embedding response".into()))
+    }
+}
+
+// ============================================================================
+// Chunking
+// ============================================================================
+
+/// `## Heading` → "Heading"
+fn heading_text(line: &str) -> Option<String> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) || !line[hashes..].starts_with(' ') {
+        return None;
+    }
+    Some(line[hashes..].trim().trim_end_matches('#').trim().to_string()).filter(|h| !h.is_empty())
+}
+
+/// Split a markdown doc into chunks: one per heading section, with long
+/// sections split at paragraph breaks. Frontmatter is skipped and `#` lines
+/// inside code fences aren't headings.
+fn chunk_markdown(content: &str) -> Vec<Chunk> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if lines.first().map(|l| l.trim()) == Some("---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim() == "---") {
+            start = end + 2;
+        }
+    }
+
+    // (heading, first line, end line exclusive)
+    let mut sections = Vec::new();
+    let mut heading = None;
+    let mut from = start;
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(h) = heading_text(trimmed) {
+            if i > from {
+                sections.push((heading.take(), from, i));
+            }
+            heading = Some(h);
+            from = i;
+        }
+    }
+    sections.push((heading, from, lines.len()));
+
+    let mut chunks = Vec::new();
+    for (heading, from, to) in sections {
+        let mut current: Option<(usize, usize)> = None;
+        let mut len = 0;
+        let push = |span: Option<(usize, usize)>, chunks: &mut Vec<Chunk>| {
+            if let Some((first, last)) = span {
+                let text = lines[first..=last].join("\n").trim().to_string();
+                if text.chars().filter(|c| !c.is_whitespace()).count() >= MIN_CHUNK_CHARS {
+                    chunks.push(Chunk { heading: heading.clone(), first, last, text });
+                }
+            }
+        };
+
+        let mut i = from;
+        while i < to {
+            while i < to && lines[i].trim().is_empty() {
+                i += 1;
+            }
+            let para_start = i;
+            while i < to && !lines[i].trim().is_empty() {
+                i += 1;
+            }
+            if i == para_start {
+                break;
+            }
+            let para_len: usize = lines[para_start..i].iter().map(|l| l.len() + 1).sum();
+            if len > 0 && len + para_len > MAX_CHUNK_CHARS {
+                push(current.take(), &mut chunks);
+                len = 0;
+            }
+            current = Some((current.map(|(first, _)| first).unwrap_or(para_start), i - 1));
+            len += para_len;
+        }
+        push(current, &mut chunks);
+    }
+    chunks
+}
+
+/// What gets embedded: title and heading give a bare paragraph its context
+fn embed_text(title: &str, chunk: &Chunk) -> String {
+    let context = match &chunk.heading {
+        Some(h) if h != title => format!("{} > {}", title, h),
+        _ => title.to_string(),
+    };
+    format!("{}\n\n{}", context, chunk.text).chars().take(MAX_EMBED_CHARS).collect()
+}
+
+fn modified_ms(meta: &fs::Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis())
+        .unwrap_or_default()
+}
+
+/// Markdown docs under `root` with their mtimes
+fn scan_markdown(root: &Path) -> HashMap<String, i64> {
+    WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            (name.ends_with(".md") || name.ends_with(".markdown")) && name != INDEX_FILE
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            (meta.len() <= MAX_FILE_BYTES).then(|| (e.path().to_string_lossy().to_string(), modified_ms(&meta)))
+        })
+        .collect()
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn db_err(e: rusqlite::Error) -> CommandError {
+    CommandError::Internal(format!("Semantic index error: {}", e))
+}
+
+fn to_blob(v: &[f32]) -> Vec<u8> {
+    v.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn from_blob(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+struct SemanticDb {
+    conn: Connection,
+}
+
+impl SemanticDb {
+    fn open(root: &Path) -> CmdResult<Self> {
+        Self::open_at(&dir_for(root).join(DB_FILE))
+    }
+
+    fn exists(root: &Path) -> bool {
+        dir_for(root).join(DB_FILE).exists()
+    }
+
+    fn open_at(path: &Path) -> CmdResult<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let conn = Connection::open(path).map_err(db_err)?;
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode=WAL;
+            PRAGMA foreign_keys=ON;
+            CREATE TABLE IF NOT EXISTS meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS files (
+                path TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                modified INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS chunks (
+                id INTEGER PRIMARY KEY,
+                path TEXT NOT NULL REFERENCES files(path) ON DELETE CASCADE,
+                heading TEXT,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                embedding BLOB NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_chunks_path ON chunks(path);
+            ",
+        )
+        .map_err(db_err)?;
+        Ok(Self { conn })
+    }
+
+    fn meta(&self, key: &str) -> CmdResult<Option<String>> {
+        self.conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", params![key], |r| r.get(0))
+            .optional()
+            .map_err(db_err)
+    }
+
+    fn set_meta(&self, key: &str, value: &str) -> CmdResult<()> {
+        self.conn
+            .execute("INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)", params![key, value])
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    /// Drop every vector; the next build re-embeds with `embedder`
+    fn reset(&self, embedder: &str) -> CmdResult<()> {
+        self.conn.execute_batch("DELETE FROM chunks; DELETE FROM files;").map_err(db_err)?;
+        self.set_meta("embedder", embedder)
+    }
+
+    fn files(&self) -> CmdResult<HashMap<String, i64>> {
+        let mut stmt = self.conn.prepare("SELECT path, modified FROM files").map_err(db_err)?;
+        let rows = stmt
+            .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))
+            .map_err(db_err)?;
+        let files = rows.collect::<Result<_, _>>().map_err(db_err)?;
+        Ok(files)
+    }
+
+    fn remove_file(&self, path: &str) -> CmdResult<()> {
+        self.conn.execute("DELETE FROM files WHERE path = ?1", params![path]).map_err(db_err)?;
+        Ok(())
+    }
+
+    fn replace_file(&mut self, path: &str, title: &str, modified: i64, chunks: &[Chunk], vectors: &[Vec<f32>]) -> CmdResult<()> {
+        let tx = self.conn.transaction().map_err(db_err)?;
+        tx.execute("DELETE FROM files WHERE path = ?1", params![path]).map_err(db_err)?;
+        tx.execute(
+            "INSERT INTO files (path, title, modified) VALUES (?1, ?2, ?3)",
+            params![path, title, modified],
+        )
+        .map_err(db_err)?;
+        for (chunk, vector) in chunks.iter().zip(vectors) {
+            tx.execute(
+                "INSERT INTO chunks (path, heading, start_line, end_line, text, embedding) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![path, chunk.heading, chunk.first as i64 + 1, chunk.last as i64 + 1, chunk.text, to_blob(vector)],
+            )
+            .map_err(db_err)?;
+        }
+        tx.commit().map_err(db_err)
+    }
+
+    fn counts(&self) -> CmdResult<(u64, u64)> {
+        self.conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM files), (SELECT COUNT(*) FROM chunks)",
+                [],
+                |r| Ok((r.get::<_, i64>(0)? as u64, r.get::<_, i64>(1)? as u64)),
+            )
+            .map_err(db_err)
+    }
+
+    /// One vector per indexed file, ordered by path
+    fn document_vectors(&self) -> CmdResult<Vec<DocumentVector>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT c.path, f.title, c.embedding FROM chunks c JOIN files f ON f.path = c.path ORDER BY c.path")
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, from_blob(&r.get::<_, Vec<u8>>(2)?))))
+            .map_err(db_err)?;
+        let mut docs: Vec<DocumentVector> = Vec::new();
+        for row in rows {
+            let (path, title, vector) = row.map_err(db_err)?;
+            match docs.last_mut() {
+                Some(doc) if doc.path == path && doc.vector.len() == vector.len() => {
+                    doc.vector.iter_mut().zip(&vector).for_each(|(sum, x)| *sum += x);
+                }
+                _ => docs.push(DocumentVector { path, title, vector }),
+            }
+        }
+        Ok(docs
+            .into_iter()
+            .map(|d| DocumentVector { vector: normalize(d.vector), ..d })
+            .collect())
+    }
+
+    /// The `k` chunks under `folder` closest to `query`. Scores every vector
+    /// first and only loads text for the winners.
+    fn top_k(&self, query: &[f32], folder: &str, k: usize) -> CmdResult<Vec<SemanticChunk>> {
+        let prefix = format!("{}{}", folder.trim_end_matches(MAIN_SEPARATOR), MAIN_SEPARATOR);
+        let mut scan = self
+            .conn
+            .prepare("SELECT id, embedding FROM chunks WHERE substr(path, 1, length(?1)) = ?1")
+            .map_err(db_err)?;
+        let rows = scan
+            .query_map(params![prefix], |r| {
+                Ok((r.get::<_, i64>(0)?, cosine(query, &from_blob(&r.get::<_, Vec<u8>>(1)?))))
+            })
+            .map_err(db_err)?;
+        let mut scored: Vec<(i64, f32)> = rows.collect::<Result<_, _>>().map_err(db_err)?;
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(k);
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT c.path, f.title, c.heading, c.start_line, c.end_line, c.text
+                 FROM chunks c JOIN files f ON f.path = c.path WHERE c.id = ?1",
+            )
+            .map_err(db_err)?;
+        let hits = scored
+            .into_iter()
+            .map(|(id, score)| {
+                stmt.query_row(params![id], |r| {
+                    let path: String = r.get(0)?;
+                    Ok(SemanticChunk {
+                        relative_path: path.strip_prefix(&prefix).unwrap_or(&path).to_string(),
+                        path,
+                        title: r.get(1)?,
+                        heading: r.get(2)?,
+                        start_line: r.get::<_, i64>(3)? as usize,
+                        end_line: r.get::<_, i64>(4)? as usize,
+                        text: r.get(5)?,
+                        score,
+                    })
+                })
+                .map_err(db_err)
+            })
+            .collect::<CmdResult<Vec<_>>>()?;
+        Ok(hits)
+    }
+}
+
+// ============================================================================
+// Build and query
+// ============================================================================
+
+/// The nearest ancestor of `folder` (itself included) with a semantic index
+fn covering_root(folder: &Path) -> Option<PathBuf> {
+    folder.ancestors().find(|a| SemanticDb::exists(a)).map(Path::to_path_buf)
+}
+
+/// Whether `folder` sits inside an indexed knowledge root (for the folder chat)
+pub fn is_indexed(folder: &str) -> bool {
+    fs::canonicalize(folder).ok().and_then(|f| covering_root(&f)).is_some()
+}
+
+/// Files waiting to be embedded: (path, title, modified, chunks)
+type Pending = Vec<(String, String, i64, Vec<Chunk>)>;
+
+async fn flush(db: &mut SemanticDb, embedder: &Embedder, pending: &mut Pending) -> CmdResult<()> {
+    let inputs: Vec<String> = pending
+        .iter()
+        .flat_map(|(_, title, _, chunks)| chunks.iter().map(move |c| embed_text(title, c)))
+        .collect();
+    let vectors = embedder.embed(&inputs).await?;
+    let mut offset = 0;
+    for (path, title, modified, chunks) in pending.drain(..) {
+        db.replace_file(&path, &title, modified, &chunks, &vectors[offset..offset + chunks.len()])?;
+        offset += chunks.len();
+    }
+    Ok(())
+}
+
+async fn build(root: PathBuf, rebuild: bool) -> CmdResult<SemanticIndexStats> {
+    let started = Instant::now();
+    let embedder = Embedder::from_settings()?;
+    let mut db = SemanticDb::open(&root)?;
+    if rebuild || db.meta("embedder")?.as_deref() != Some(embedder.id().as_str()) {
+        db.reset(&embedder.id())?;
+    }
+
+    let scan_root = root.clone();
+    let on_disk = tauri::async_runtime::spawn_blocking(move || scan_markdown(&scan_root))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Knowledge scan failed: {}", e)))?;
+    let known = db.files()?;
+
+    let mut removed = 0;
+    for path in known.keys().filter(|p| !on_disk.contains_key(*p)) {
+        db.remove_file(path)?;
+        removed += 1;
+    }
+
+    let mut embedded = 0;
+    let mut pending: Pending = Vec::new();
+    for (path, modified) in &on_disk {
+        if known.get(path) == Some(modified) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let name = Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        pending.push((path.clone(), doc_meta(&name, &content).title, *modified, chunk_markdown(&content)));
+        embedded += 1;
+        if pending.iter().map(|(_, _, _, c)| c.len()).sum::<usize>() >= EMBED_BATCH {
+            flush(&mut db, &embedder, &mut pending).await?;
+        }
+    }
+    flush(&mut db, &embedder, &mut pending).await?;
+    db.set_meta("built_at", &chrono::Utc::now().to_rfc3339())?;
+
+    let (files, chunks) = db.counts()?;
+    Ok(SemanticIndexStats {
+        root: root.to_string_lossy().to_string(),
+        embedder: embedder.id(),
+        files,
+        chunks,
+        embedded,
+        removed,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Passages under `folder` closest in meaning to `query`, best first. Uses
+/// the index of the nearest indexed ancestor (build it first with
+/// search_semantic_index_build).
+pub async fn semantic_search(folder: &str, query: &str, top_k: usize) -> CmdResult<Vec<SemanticChunk>> {
+    let folder = fs::canonicalize(folder).map_err(|e| CommandError::NotFound(format!("{}: {}", folder, e)))?;
+    let root = covering_root(&folder).ok_or_else(|| {
+        CommandError::NotFound(format!("No semantic index covers {}; build it first", folder.display()))
+    })?;
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let embedder = Embedder::from_settings()?;
+    let vector = embedder
+        .embed(&[query.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| CommandError::Parse("Empty embedding for query".into()))?;
+
+    let db = SemanticDb::open(&root)?;
+    let built_with = db.meta("embedder")?;
+    if built_with.as_deref() != Some(embedder.id().as_str()) {
+        return Err(CommandError::Config(format!(
+            "Semantic index was built with {}; rebuild it to search with {}",
+            built_with.unwrap_or_else(|| "nothing".to_string()),
+            embedder.id()
+        )));
+    }
+    db.top_k(&vector, &folder.to_string_lossy(), top_k)
+}
+
+/// Bring the index for `root` up to date (only changed files are embedded)
+/// and return one vector per document, for document-level similarity
+pub async fn document_vectors(root: &Path) -> CmdResult<Vec<DocumentVector>> {
+    let root = canonical_root(&root.to_string_lossy())?;
+    build(root.clone(), false).await?;
+    SemanticDb::open(&root)?.document_vectors()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Build or refresh the semantic index for `root` (default: the knowledge
+/// path). Only changed files are re-embedded; `rebuild` starts over.
+#[command]
+pub async fn search_semantic_index_build(root: Option<String>, rebuild: Option<bool>) -> CmdResult<SemanticIndexStats> {
+    let root = canonical_root(&knowledge_root(root)?.to_string_lossy())?;
+    build(root, rebuild.unwrap_or(false)).await
+}
+
+/// Semantic index status for `root` (default: the knowledge path); None if never built
+#[command]
+pub async fn search_semantic_index_status(root: Option<String>) -> CmdResult<Option<SemanticIndexStatus>> {
+    let root = canonical_root(&knowledge_root(root)?.to_string_lossy())?;
+    if !SemanticDb::exists(&root) {
+        return Ok(None);
+    }
+    let db = SemanticDb::open(&root)?;
+    let (files, chunks) = db.counts()?;
+    Ok(Some(SemanticIndexStatus {
+        root: root.to_string_lossy().to_string(),
+        embedder: db.meta("embedder")?,
+        files,
+        chunks,
+        built_at: db.meta("built_at")?,
+    }))
+}
+
+/// Passages closest in meaning to `query` under `folder` (default: the
+/// knowledge path), best first (default 8)
+#[command]
+pub async fn search_semantic(query: String, top_k: Option<usize>, folder: Option<String>) -> CmdResult<Vec<SemanticChunk>> {
+    let folder = knowledge_root(folder)?;
+    semantic_search(&folder.to_string_lossy(), &query, top_k.unwrap_or(DEFAULT_TOP_K)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_by_heading_and_paragraph() {
+        let long_para = "Invoices are matched against purchase orders nightly. ".repeat(20);
+        let doc = format!(
+            "---\ntitle: AP guide\n---\n# AP guide\n\nIntro paragraph that explains the whole process end to end.\n\n\
+             ## Matching\n\n{}\n\n{}\n\n```sh\n# not a heading\necho run the matcher job here please\n```\n\n## Tiny\n\nok\n",
+            long_para, long_para
+        );
+        let chunks = chunk_markdown(&doc);
+        let headings: Vec<Option<&str>> = chunks.iter().map(|c| c.heading.as_deref()).collect();
+        assert_eq!(headings, vec![Some("AP guide"), Some("Matching"), Some("Matching")]);
+
+        // Frontmatter skipped; lines are 0-based indexes into the file
+        assert_eq!(chunks[0].first, 3);
+        assert!(chunks[0].text.starts_with("# AP guide"));
+        // The second long paragraph (and the fenced block) spill into a new chunk
+        assert!(chunks[2].text.contains("# not a heading"));
+        assert!(chunks[1].text.len() <= MAX_CHUNK_CHARS + 100);
+
+        assert_eq!(heading_text("### Setup ###").as_deref(), Some("Setup"));
+        assert_eq!(heading_text("#hashtag"), None);
+        assert_eq!(embed_text("AP guide", &chunks[1]).lines().next(), Some("AP guide > Matching"));
+    }
+
+    #[test]
+    fn stores_vectors_and_ranks_within_folder() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().to_path_buf();
+        let mut db = SemanticDb::open_at(&base.join(DB_FILE)).unwrap();
+        db.reset("test:model").unwrap();
+
+        let chunk = |text: &str| Chunk { heading: None, first: 0, last: 1, text: text.to_string() };
+        let sep = MAIN_SEPARATOR;
+        let billing = format!("{sep}kb{sep}finance{sep}billing.md");
+        let lunch = format!("{sep}kb{sep}misc{sep}lunch.md");
+        db.replace_file(&billing, "Billing", 1, &[chunk("invoices"), chunk("refunds")], &[normalize(vec![1.0, 0.0]), normalize(vec![0.6, 0.8])])
+            .unwrap();
+        db.replace_file(&lunch, "Lunch", 1, &[chunk("menu")], &[normalize(vec![0.9, 0.1])]).unwrap();
+        assert_eq!(db.counts().unwrap(), (2, 3));
+
+        // A document's vector is the normalized mean of its chunks
+        let docs = db.document_vectors().unwrap();
+        assert_eq!(docs.iter().map(|d| d.title.as_str()).collect::<Vec<_>>(), vec!["Billing", "Lunch"]);
+        assert!((docs[0].vector[0] - 0.894).abs() < 0.001 && (docs[0].vector[1] - 0.447).abs() < 0.001);
+
+        let hits = db.top_k(&[1.0, 0.0], &format!("{sep}kb"), 2).unwrap();
+        assert_eq!(hits.iter().map(|h| h.text.as_str()).collect::<Vec<_>>(), vec!["invoices", "menu"]);
+        assert_eq!(hits[0].relative_path, format!("finance{sep}billing.md"));
+        assert_eq!((hits[0].start_line, hits[0].end_line), (1, 2));
+
+        // Scoped to a subfolder; re-indexing a file replaces its chunks
+        let hits = db.top_k(&[1.0, 0.0], &format!("{sep}kb{sep}finance"), 5).unwrap();
+        assert_eq!(hits.len(), 2);
+        db.replace_file(&billing, "Billing", 2, &[chunk("statements")], &[normalize(vec![1.0, 0.0])]).unwrap();
+        db.remove_file(&lunch).unwrap();
+        assert_eq!(db.counts().unwrap(), (1, 1));
+        assert_eq!(db.files().unwrap().get(&billing), Some(&2));
+
+        drop(db);
+    }
+}
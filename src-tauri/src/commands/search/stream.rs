@@ -0,0 +1,179 @@
+// src-tauri/src/commands/search/stream.rs
+// Streaming content search: matches are emitted as `search-result` events as
+// the walk finds them, then one `search-complete`, all tagged with the
+// caller's search id. cancel_search stops a scan between files so the next
+// keystroke doesn't wait for the previous one.
+
+use super::{ContentScan, MatchOptions, SearchFilters, SearchRanking};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::models::SearchResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tauri::{command, AppHandle, Emitter};
+
+/// One match from a streaming search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResultEvent {
+    pub search_id: String,
+    pub result: SearchResult,
+}
+
+/// Emitted once when a streaming search ends (also the command's return value)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchComplete {
+    pub search_id: String,
+    /// Results emitted
+    pub matched: usize,
+    /// Stopped by cancel_search (or a newer search reusing the id)
+    pub cancelled: bool,
+    /// Stopped at max_results
+    pub truncated: bool,
+    pub elapsed_ms: u64,
+}
+
+// Cancel flags of running streaming searches, by search id
+fn active_searches() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static INSTANCE: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    INSTANCE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Track a new search, cancelling any still running under the same id
+fn register(search_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut searches = active_searches().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(previous) = searches.insert(search_id.to_string(), flag.clone()) {
+        previous.store(true, Ordering::Relaxed);
+    }
+    flag
+}
+
+/// Stop tracking `flag`, unless a newer search has taken over the id
+fn unregister(search_id: &str, flag: &Arc<AtomicBool>) {
+    let mut searches = active_searches().lock().unwrap_or_else(|e| e.into_inner());
+    if searches.get(search_id).is_some_and(|current| Arc::ptr_eq(current, flag)) {
+        searches.remove(search_id);
+    }
+}
+
+/// Same search as `search_content`, but each matching file is emitted as a
+/// `search-result` event as soon as it's found (walk order, each with its
+/// score), followed by `search-complete`. Stops after `max_results` matches
+/// (default 50) or when cancelled. Invalid patterns fail before any event.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_content_stream(
+    app: AppHandle,
+    search_id: String,
+    root: String,
+    query: String,
+    extensions: Option<Vec<String>>,
+    max_results: Option<usize>,
+    ranking: Option<SearchRanking>,
+    follow_symlinks: Option<bool>,
+    regex: Option<bool>,
+    multiline: Option<bool>,
+    filters: Option<SearchFilters>,
+    match_options: Option<MatchOptions>,
+) -> CmdResult<SearchComplete> {
+    let scan = ContentScan::new(
+        root,
+        &query,
+        extensions,
+        ranking,
+        follow_symlinks,
+        regex,
+        multiline,
+        filters,
+        match_options,
+    )?;
+    let max = max_results.unwrap_or(50);
+    let cancelled = register(&search_id);
+
+    let flag = cancelled.clone();
+    let id = search_id.clone();
+    let task = tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let mut matched = 0;
+        scan.run(&flag, |result| {
+            let _ = app.emit("search-result", SearchResultEvent { search_id: id.clone(), result });
+            matched += 1;
+            matched < max
+        });
+        let complete = SearchComplete {
+            search_id: id,
+            matched,
+            cancelled: flag.load(Ordering::Relaxed),
+            truncated: matched >= max,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        let _ = app.emit("search-complete", &complete);
+        complete
+    })
+    .await;
+
+    unregister(&search_id, &cancelled);
+    task.map_err(|e| CommandError::Internal(format!("Search task failed: {}", e)))
+}
+
+/// Stop a streaming search. Returns false if it already finished or never existed.
+#[command]
+pub async fn cancel_search(search_id: String) -> CmdResult<bool> {
+    let searches = active_searches()
+        .lock()
+        .map_err(|e| CommandError::Internal(e.to_string()))?;
+    match searches.get(&search_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_search_cancels_and_replaces_older() {
+        let first = register("stream-test");
+        let second = register("stream-test");
+        assert!(first.load(Ordering::Relaxed));
+        assert!(!second.load(Ordering::Relaxed));
+
+        // The older search finishing must not drop the newer one's flag
+        unregister("stream-test", &first);
+        assert!(active_searches().lock().unwrap().contains_key("stream-test"));
+
+        unregister("stream-test", &second);
+        assert!(!active_searches().lock().unwrap().contains_key("stream-test"));
+    }
+
+    #[test]
+    fn scan_stops_when_cancelled_or_told_to() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        for i in 0..5 {
+            std::fs::write(root.join(format!("note{}.md", i)), "needle in here").unwrap();
+        }
+        let root_str = root.to_string_lossy().to_string();
+        let scan = ContentScan::new(root_str, "needle", None, None, None, None, None, None, None).unwrap();
+
+        let mut hits = 0;
+        scan.run(&AtomicBool::new(false), |_| {
+            hits += 1;
+            hits < 2
+        });
+        assert_eq!(hits, 2);
+
+        let mut hits = 0;
+        scan.run(&AtomicBool::new(true), |_| {
+            hits += 1;
+            true
+        });
+        assert_eq!(hits, 0);
+    }
+}
@@ -0,0 +1,328 @@
+// src-tauri/src/commands/search/val_artifacts.rs
+// Search across a VAL domain's synced artifacts. Knows the extract layout
+// under the domain's global path — data_models/table_<name>/,
+// workflows/workflow_<id>/ (with sql/), queries/query_<id>/,
+// dashboards/dashboard_<id>/ — so a hit comes back as a typed artifact with
+// its ID and display name instead of a file path, plus per-kind counts for
+// faceting ("which workflow mentions usr_outlet_code" is one call).
+
+use super::compile_pattern;
+use super::grouped::line_matches;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::val_sync::config::get_domain_config;
+use crate::commands::val_sync::table_pipeline::build_table_display_names;
+use crate::models::LineMatch;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+use walkdir::WalkDir;
+
+/// Extensions read inside an artifact folder
+const ARTIFACT_EXTENSIONS: [&str; 6] = ["json", "md", "sql", "yaml", "yml", "txt"];
+/// Same cap as content search
+const MAX_FILE_BYTES: u64 = 1_000_000;
+/// Matching lines previewed per artifact, across its files
+const MAX_LINES_PER_ARTIFACT: usize = 5;
+const DEFAULT_MAX_RESULTS: usize = 100;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValArtifactKind {
+    Table,
+    Workflow,
+    Query,
+    Dashboard,
+}
+
+impl ValArtifactKind {
+    const ALL: [Self; 4] = [Self::Table, Self::Workflow, Self::Query, Self::Dashboard];
+
+    /// Folder under the global path, and the prefix of each artifact folder in it
+    fn layout(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Table => ("data_models", "table_"),
+            Self::Workflow => ("workflows", "workflow_"),
+            Self::Query => ("queries", "query_"),
+            Self::Dashboard => ("dashboards", "dashboard_"),
+        }
+    }
+}
+
+/// Matches within one file of an artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValArtifactFile {
+    /// Relative to the artifact folder, e.g. "sql/workflow_42_definition.sql"
+    pub relative_path: String,
+    pub match_count: usize,
+    pub lines: Vec<LineMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValArtifactHit {
+    pub kind: ValArtifactKind,
+    /// Table name or workflow / query / dashboard ID, as in the folder name
+    pub id: String,
+    pub name: Option<String>,
+    /// The artifact folder
+    pub path: String,
+    /// The query appears in the ID or name
+    pub name_match: bool,
+    /// Matches across the artifact's files
+    pub match_count: usize,
+    pub files: Vec<ValArtifactFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValArtifactFacet {
+    pub kind: ValArtifactKind,
+    /// Matching artifacts of this kind, whatever kind was asked for
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValArtifactSearch {
+    pub domain: String,
+    pub query: String,
+    /// Name matches first, then by match count
+    pub hits: Vec<ValArtifactHit>,
+    pub facets: Vec<ValArtifactFacet>,
+    /// Hits of the requested kind(s) before max_results
+    pub total: usize,
+    /// Artifact folders looked at
+    pub scanned: usize,
+}
+
+// ============================================================================
+// Searching
+// ============================================================================
+
+/// `name` from an extracted definition (top level or under `data`)
+fn definition_name(folder: &Path) -> Option<String> {
+    let content = fs::read_to_string(folder.join("definition.json")).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    value
+        .get("name")
+        .or_else(|| value.get("data").and_then(|d| d.get("name")))
+        .and_then(|n| n.as_str())
+        .map(String::from)
+}
+
+fn is_searchable(path: &Path) -> bool {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    ARTIFACT_EXTENSIONS.contains(&ext.as_str())
+        && path.metadata().map(|m| m.len() <= MAX_FILE_BYTES).unwrap_or(false)
+}
+
+/// Match one artifact folder by ID, name and file content
+fn match_artifact(
+    kind: ValArtifactKind,
+    id: &str,
+    folder: &Path,
+    re: &Regex,
+    query_lower: &str,
+    table_names: &HashMap<String, String>,
+) -> Option<ValArtifactHit> {
+    let name = match kind {
+        ValArtifactKind::Table => table_names.get(id).cloned().or_else(|| definition_name(folder)),
+        _ => definition_name(folder),
+    };
+    let name_match = id.to_lowercase().contains(query_lower)
+        || name.as_ref().is_some_and(|n| n.to_lowercase().contains(query_lower));
+
+    let mut paths: Vec<PathBuf> = WalkDir::new(folder)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| is_searchable(p))
+        .collect();
+    paths.sort();
+
+    let mut files = Vec::new();
+    let mut match_count = 0;
+    let mut previews_left = MAX_LINES_PER_ARTIFACT;
+    for path in paths {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let found = line_matches(re, &content, false, previews_left);
+        if found.matches == 0 {
+            continue;
+        }
+        previews_left -= found.previews.len();
+        match_count += found.matches;
+        files.push(ValArtifactFile {
+            relative_path: path.strip_prefix(folder).unwrap_or(&path).to_string_lossy().replace('\\', "/"),
+            match_count: found.matches,
+            lines: found.previews,
+        });
+    }
+
+    (name_match || match_count > 0).then(|| ValArtifactHit {
+        kind,
+        id: id.to_string(),
+        name,
+        path: folder.to_string_lossy().to_string(),
+        name_match,
+        match_count,
+        files,
+    })
+}
+
+/// Search every artifact kind under `global_path` (facets count them all),
+/// keeping hits of `kind` (all when None). Matching is case-insensitive.
+fn search_artifacts(
+    global_path: &Path,
+    kind: Option<ValArtifactKind>,
+    query: &str,
+    max_results: usize,
+) -> CmdResult<ValArtifactSearch> {
+    let re = compile_pattern(query, false, false, true)?;
+    let query_lower = query.to_lowercase();
+    let table_names = build_table_display_names(&global_path.to_string_lossy());
+
+    let mut hits = Vec::new();
+    let mut facets = Vec::new();
+    let mut scanned = 0;
+    for artifact_kind in ValArtifactKind::ALL {
+        let (dir, prefix) = artifact_kind.layout();
+        let mut folders: Vec<PathBuf> = fs::read_dir(global_path.join(dir))
+            .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+            .unwrap_or_default();
+        folders.sort();
+
+        let mut count = 0;
+        for folder in folders {
+            let Some(id) = folder
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(prefix))
+                .map(String::from)
+            else {
+                continue;
+            };
+            scanned += 1;
+            if let Some(hit) = match_artifact(artifact_kind, &id, &folder, &re, &query_lower, &table_names) {
+                count += 1;
+                if kind.is_none() || kind == Some(artifact_kind) {
+                    hits.push(hit);
+                }
+            }
+        }
+        facets.push(ValArtifactFacet { kind: artifact_kind, count });
+    }
+
+    hits.sort_by(|a, b| {
+        b.name_match
+            .cmp(&a.name_match)
+            .then_with(|| b.match_count.cmp(&a.match_count))
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let total = hits.len();
+    hits.truncate(max_results);
+
+    Ok(ValArtifactSearch {
+        domain: String::new(),
+        query: query.to_string(),
+        hits,
+        facets,
+        total,
+        scanned,
+    })
+}
+
+/// Search a VAL domain's synced tables, workflows, queries and dashboards for
+/// `query` (case-insensitive, in IDs, names and definition / SQL / doc files).
+/// `kind` narrows the hits; facets always count every kind.
+#[command]
+pub async fn search_val_artifacts(
+    domain: String,
+    kind: Option<ValArtifactKind>,
+    query: String,
+    max_results: Option<usize>,
+) -> CmdResult<ValArtifactSearch> {
+    if query.trim().is_empty() {
+        return Err(CommandError::Validation {
+            message: "Query is required".to_string(),
+            fields: vec!["query".to_string()],
+        });
+    }
+    let config = get_domain_config(&domain)?;
+    let global_path = PathBuf::from(&config.global_path);
+    if !global_path.is_dir() {
+        return Err(CommandError::NotFound(format!(
+            "Synced folder for '{}' not found: {}",
+            domain, config.global_path
+        )));
+    }
+
+    let max = max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+    let mut result = tauri::async_runtime::spawn_blocking(move || search_artifacts(&global_path, kind, query.trim(), max))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Artifact search failed: {}", e)))??;
+    result.domain = domain;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_typed_artifacts_with_facets() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(
+            "schema/all_tables.json",
+            r#"[{"type":"repoTable","table_name":"custom_tbl_1_2","name":"Outlets"}]"#,
+        );
+        write("data_models/table_custom_tbl_1_2/definition.json", "{\n  \"columns\": [\"usr_outlet_code\"]\n}");
+        write("workflows/workflow_42/definition.json", r#"{"name": "Nightly sales load"}"#);
+        write(
+            "workflows/workflow_42/sql/workflow_42_definition.sql",
+            "SELECT usr_outlet_code\nFROM sales\nGROUP BY USR_OUTLET_CODE\n",
+        );
+        write("workflows/workflow_7/definition.json", r#"{"data": {"name": "Outlet cleanup"}}"#);
+        write("queries/query_9/definition.json", r#"{"name": "Revenue"}"#);
+        write("workflows/notes.md", "usr_outlet_code outside any artifact folder");
+
+        let all = search_artifacts(&root, None, "usr_outlet_code", 10).unwrap();
+        assert_eq!(all.scanned, 4);
+        let counts: Vec<(ValArtifactKind, usize)> = all.facets.iter().map(|f| (f.kind, f.count)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (ValArtifactKind::Table, 1),
+                (ValArtifactKind::Workflow, 1),
+                (ValArtifactKind::Query, 0),
+                (ValArtifactKind::Dashboard, 0)
+            ]
+        );
+        // Most matches first
+        assert_eq!((all.hits[0].kind, all.hits[0].id.as_str()), (ValArtifactKind::Workflow, "42"));
+        assert_eq!(all.hits[0].name.as_deref(), Some("Nightly sales load"));
+        assert_eq!(all.hits[0].match_count, 2);
+        assert_eq!(all.hits[0].files[0].relative_path, "sql/workflow_42_definition.sql");
+        assert_eq!(all.hits[1].name.as_deref(), Some("Outlets"));
+
+        // Kind narrows hits, not facets; names match too
+        let workflows = search_artifacts(&root, Some(ValArtifactKind::Workflow), "outlet", 10).unwrap();
+        let ids: Vec<&str> = workflows.hits.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, vec!["7", "42"]);
+        assert!(workflows.hits[0].name_match && !workflows.hits[1].name_match);
+        assert_eq!(workflows.facets[0].count, 1);
+    }
+}
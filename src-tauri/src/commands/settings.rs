@@ -36,6 +36,12 @@ pub const KEY_APOLLO_API: &str = "apollo_api_key";
 pub const KEY_LINKEDIN_CLIENT_ID: &str = "linkedin_client_id";
 pub const KEY_LINKEDIN_CLIENT_SECRET: &str = "linkedin_client_secret";
 pub const KEY_OPENROUTER_API: &str = "openrouter_api_key";
+pub const KEY_HUBSPOT_API: &str = "hubspot_api_key";
+/// Semantic search embeddings: "openai" | "gemini" | "local" (unset = first hosted key found)
+pub const KEY_EMBEDDING_PROVIDER: &str = "embedding_provider";
+pub const KEY_EMBEDDING_MODEL: &str = "embedding_model";
+/// Ollama-compatible server for the "local" provider (default http://localhost:11434)
+pub const KEY_LOCAL_EMBEDDING_URL: &str = "local_embedding_url";
 
 // Background sync toggle keys (default: not set = disabled)
 pub const KEY_BG_SYNC_OUTLOOK_EMAIL: &str = "bg_sync_outlook_email";
@@ -43,6 +49,11 @@ pub const KEY_BG_SYNC_OUTLOOK_CALENDAR: &str = "bg_sync_outlook_calendar";
 pub const KEY_BG_SYNC_NOTION: &str = "bg_sync_notion";
 pub const KEY_BG_SYNC_PUBLIC_DATA: &str = "bg_sync_public_data";
 pub const KEY_BG_SYNC_GA4: &str = "bg_sync_ga4";
+pub const KEY_BG_SYNC_HUBSPOT: &str = "bg_sync_hubspot";
+
+// File versioning (opt-in): snapshot previous content before write_file
+pub const KEY_FILE_VERSIONING: &str = "file_versioning";
+pub const KEY_FILE_VERSIONING_MAX: &str = "file_versioning_max";
 
 /// Key where the list of registered workspace IDs is stored (JSON array of
 /// strings). Populated by `settings_register_workspace` — Rust background
@@ -277,6 +288,7 @@ pub fn settings_list_keys() -> CmdResult<Vec<ApiKeyInfo>> {
         (KEY_APOLLO_API, "Apollo API Key", "For prospect search and enrichment"),
         (KEY_LINKEDIN_CLIENT_ID, "LinkedIn Client ID", "For LinkedIn social media integration"),
         (KEY_LINKEDIN_CLIENT_SECRET, "LinkedIn Client Secret", "For LinkedIn social media integration"),
+        (KEY_HUBSPOT_API, "HubSpot Private App Token", "For importing legacy CRM data from HubSpot"),
     ];
 
     let mut result = Vec::new();
@@ -5,6 +5,9 @@ use crate::commands::error::{CmdResult, CommandError};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde::{de::DeserializeOwned, Serialize};
 
+/// PostgREST page size for select_all (Supabase caps responses at 1000 rows by default)
+const SELECT_PAGE_SIZE: usize = 1000;
+
 /// Supabase client for making REST API requests
 pub struct SupabaseClient {
     base_url: String,
@@ -103,6 +106,30 @@ impl SupabaseClient {
         Ok(response.json().await?)
     }
 
+    /// GET every matching row, a page at a time. `query` should include an
+    /// `order` so rows don't shift between pages.
+    pub async fn select_all<T: DeserializeOwned>(
+        &self,
+        table: &str,
+        query: &str,
+    ) -> CmdResult<Vec<T>> {
+        let separator = if query.is_empty() { "" } else { "&" };
+        let mut rows = Vec::new();
+        loop {
+            let page: Vec<T> = self
+                .select(
+                    table,
+                    &format!("{}{}limit={}&offset={}", query, separator, SELECT_PAGE_SIZE, rows.len()),
+                )
+                .await?;
+            let n = page.len();
+            rows.extend(page);
+            if n < SELECT_PAGE_SIZE {
+                return Ok(rows);
+            }
+        }
+    }
+
     /// GET single row
     pub async fn select_single<T: DeserializeOwned>(
         &self,
@@ -260,6 +287,24 @@ pub fn client_from(url: &str, anon_key: &str) -> SupabaseClient {
     SupabaseClient::new(url, anon_key)
 }
 
+/// `value` as a literal `like`/`ilike` pattern, with SQL wildcards escaped.
+/// PostgREST turns `*` into `%` before Postgres sees it, so a literal `*`
+/// can't be expressed; it becomes a single-character wildcard instead.
+pub fn like_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '%' | '_' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '*' => out.push('_'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 // ── Bot JWT Authentication ──────────────────────────────────────────────────
 // When TV_BOT_API_KEY is set, tv-mcp authenticates with the gateway on startup
 // and uses a scoped JWT for all Supabase queries instead of the anon key.
@@ -748,6 +793,13 @@ mod tests {
         assert_eq!(client.anon_key, "key123");
     }
 
+    #[test]
+    fn like_literal_escapes_wildcards() {
+        assert_eq!(like_literal("Acme"), "Acme");
+        assert_eq!(like_literal("100%_Co\\"), "100\\%\\_Co\\\\");
+        assert_eq!(like_literal("A*B"), "A_B");
+    }
+
     #[test]
     fn client_strips_trailing_slash() {
         let client = SupabaseClient::new("https://example.supabase.co/", "key");
@@ -1,13 +1,37 @@
 // src-tauri/src/commands/terminal.rs
 // Terminal/PTY commands for the Console module
+// Shell PIDs are also recorded in ~/.tv-client/terminal-sessions.json so shells
+// left behind by a crashed app or window can be found and killed later.
+// Output is also kept in a capped scrollback so terminal_copy_output can hand
+// back clean, secret-redacted text for the clipboard.
 
 use crate::commands::error::{CmdResult, CommandError};
-use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use crate::commands::secret_scan::{configured_secrets, redact_secrets};
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, PtySize};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use tauri::State;
+use tauri::{Manager, State};
+
+/// Raw output kept per session for copying; older output is dropped
+const MAX_SCROLLBACK_BYTES: usize = 2_000_000;
+
+/// CSI, OSC (ended by BEL or ST) and two-character escape sequences
+static ANSI_RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[()][0-9A-Za-z]|\x1b[@-Z\\-_=>]").unwrap()
+});
+/// bash `user@host:dir$`, zsh `user@host dir %`, optional `(venv)` prefix,
+/// PowerShell `PS C:\dir>`, oh-my-zsh `➜  dir git:(main) ✗`, bare `$` / `%` / `❯`.
+/// Group 1 is the command typed after it.
+static PROMPT_RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(
+        r"^(?:\([^)\s]+\)\s+)?(?:[\w.-]+@[\w.-]+(?::[^\s$%#]*)?(?:\s+[^\s$%#]+)?\s?[$%#]|PS [^>]*>|➜\s+\S+(?:\s+git:\([^)]*\))?(?:\s+✗)?|[$%❯])(?:\s+(.*))?$",
+    )
+    .unwrap()
+});
 
 // Terminal session state
 pub struct TerminalSessions {
@@ -17,7 +41,13 @@ pub struct TerminalSessions {
 struct TerminalSession {
     writer: Box<dyn Write + Send>,
     output_buffer: Arc<Mutex<Vec<u8>>>,
-    _child: Box<dyn portable_pty::Child + Send + Sync>,
+    /// Recent output, not drained by reads
+    scrollback: Arc<Mutex<Vec<u8>>>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    /// Shell PID (None if the platform doesn't report one)
+    pid: Option<u32>,
+    /// Label of the window that opened the session
+    window: String,
     // Reader thread handle — dropped (detached) when session is removed
     _reader_handle: std::thread::JoinHandle<()>,
 }
@@ -37,6 +67,75 @@ pub struct TerminalInfo {
     pub cols: u16,
 }
 
+/// On-disk record of a live shell, keyed by session id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SessionRecord {
+    pid: u32,
+    /// PID of the app instance that spawned it — a different value means a previous run
+    app_pid: u32,
+    window: String,
+    /// Shell executable name, checked before killing in case the PID was reused
+    shell: String,
+    created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub ppid: u32,
+    pub command: String,
+    pub cpu_percent: f64,
+    pub memory_kb: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalResourceUsage {
+    pub session_id: String,
+    pub shell_pid: u32,
+    /// The shell and every process started under it
+    pub processes: Vec<ProcessUsage>,
+    pub total_cpu_percent: f64,
+    pub total_memory_kb: u64,
+}
+
+/// Which part of the scrollback to copy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CopyRange {
+    #[default]
+    All,
+    /// The last `count` lines
+    LastLines { count: usize },
+    /// From the prompt of the `count`th-last command to the end
+    LastCommands { count: usize },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyMode {
+    /// Escapes stripped and prompts collapsed
+    Plain,
+    /// Plain, with secrets replaced by `[REDACTED:<rule>]`
+    #[default]
+    Redacted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalCopyOutput {
+    pub text: String,
+    pub line_count: usize,
+    pub redactions: usize,
+    /// Rules that redacted something, first-seen order
+    pub redacted_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrphanCleanupResult {
+    /// Sessions whose shells were killed
+    pub sessions: Vec<String>,
+    pub killed_pids: Vec<u32>,
+}
+
 /// Create a new terminal session
 #[tauri::command]
 pub fn terminal_create(
@@ -44,6 +143,7 @@ pub fn terminal_create(
     rows: u16,
     cols: u16,
     cwd: Option<String>,
+    window: tauri::WebviewWindow,
     sessions: State<'_, TerminalSessions>,
 ) -> CmdResult<TerminalInfo> {
     let pty_system = native_pty_system();
@@ -58,7 +158,8 @@ pub fn terminal_create(
         .map_err(|e| CommandError::Io(format!("Failed to open PTY: {}", e)))?;
 
     // Build shell command
-    let mut cmd = CommandBuilder::new(get_default_shell());
+    let shell = get_default_shell();
+    let mut cmd = CommandBuilder::new(&shell);
     cmd.arg("-l"); // Login shell
 
     // Set working directory
@@ -92,6 +193,8 @@ pub fn terminal_create(
     // Spawn background thread to read PTY output into buffer
     let output_buffer = Arc::new(Mutex::new(Vec::<u8>::new()));
     let buffer_clone = output_buffer.clone();
+    let scrollback = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let scrollback_clone = scrollback.clone();
     let reader_handle = std::thread::spawn(move || {
         let mut buf = [0u8; 4096];
         loop {
@@ -101,24 +204,54 @@ pub fn terminal_create(
                     if let Ok(mut buffer) = buffer_clone.lock() {
                         buffer.extend_from_slice(&buf[..n]);
                     }
+                    if let Ok(mut history) = scrollback_clone.lock() {
+                        history.extend_from_slice(&buf[..n]);
+                        if history.len() > MAX_SCROLLBACK_BYTES {
+                            let excess = history.len() - MAX_SCROLLBACK_BYTES;
+                            history.drain(..excess);
+                        }
+                    }
                 }
                 Err(_) => break, // PTY closed
             }
         }
     });
 
+    let pid = child.process_id();
+    if let Some(pid) = pid {
+        update_registry(|records| {
+            records.insert(
+                id.clone(),
+                SessionRecord {
+                    pid,
+                    app_pid: std::process::id(),
+                    window: window.label().to_string(),
+                    shell: shell_name(&shell),
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        });
+    }
+
     let session = TerminalSession {
         writer,
         output_buffer,
-        _child: child,
+        scrollback,
+        child,
+        pid,
+        window: window.label().to_string(),
         _reader_handle: reader_handle,
     };
 
-    sessions
+    // Re-using an id replaces (and ends) the previous shell
+    let previous = sessions
         .sessions
         .lock()
         .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?
         .insert(id.clone(), session);
+    if let Some(previous) = previous {
+        end_session(previous);
+    }
 
     Ok(TerminalInfo { id, rows, cols })
 }
@@ -178,6 +311,54 @@ pub fn terminal_read(id: String, sessions: State<'_, TerminalSessions>) -> CmdRe
     Ok(data)
 }
 
+/// Copy a session's output for the clipboard: escapes stripped, prompts
+/// collapsed to `$ <command>`, and (by default) secrets redacted
+#[tauri::command]
+pub fn terminal_copy_output(
+    session_id: String,
+    range: Option<CopyRange>,
+    mode: Option<CopyMode>,
+    sessions: State<'_, TerminalSessions>,
+) -> CmdResult<TerminalCopyOutput> {
+    let scrollback = sessions
+        .sessions
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?
+        .get(&session_id)
+        .ok_or_else(|| CommandError::NotFound("Session not found".to_string()))?
+        .scrollback
+        .clone();
+    let raw = {
+        let history = scrollback
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Buffer lock error: {}", e)))?;
+        String::from_utf8_lossy(&history).to_string()
+    };
+
+    let lines = select_range(collapse_prompts(&strip_ansi(&raw)), range.unwrap_or_default());
+    let text = lines.join("\n");
+    let (text, rules) = match mode.unwrap_or_default() {
+        CopyMode::Plain => (text, Vec::new()),
+        CopyMode::Redacted => {
+            let redacted = redact_secrets(&text, &configured_secrets());
+            (redacted.text, redacted.rules)
+        }
+    };
+
+    let mut redacted_rules: Vec<String> = Vec::new();
+    for rule in &rules {
+        if !redacted_rules.iter().any(|r| r == rule) {
+            redacted_rules.push(rule.to_string());
+        }
+    }
+    Ok(TerminalCopyOutput {
+        line_count: lines.len(),
+        text,
+        redactions: rules.len(),
+        redacted_rules,
+    })
+}
+
 /// Resize terminal
 #[tauri::command]
 pub fn terminal_resize(
@@ -210,7 +391,12 @@ pub fn terminal_close(id: String, sessions: State<'_, TerminalSessions>) -> CmdR
         .lock()
         .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
 
-    sessions_guard.remove(&id);
+    if let Some(session) = sessions_guard.remove(&id) {
+        end_session(session);
+    }
+    update_registry(|records| {
+        records.remove(&id);
+    });
     log::info!("Terminal {} closed", id);
 
     Ok(())
@@ -227,6 +413,324 @@ pub fn terminal_list(sessions: State<'_, TerminalSessions>) -> CmdResult<Vec<Str
     Ok(sessions_guard.keys().cloned().collect())
 }
 
+/// CPU and memory of a session's shell and everything running under it
+#[tauri::command]
+pub fn terminal_get_resource_usage(
+    session_id: String,
+    sessions: State<'_, TerminalSessions>,
+) -> CmdResult<TerminalResourceUsage> {
+    let shell_pid = sessions
+        .sessions
+        .lock()
+        .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?
+        .get(&session_id)
+        .ok_or_else(|| CommandError::NotFound("Session not found".to_string()))?
+        .pid
+        .ok_or_else(|| CommandError::NotFound("Shell PID not available on this platform".to_string()))?;
+
+    let table = process_table()?;
+    let tree = process_tree(&table, shell_pid);
+    let processes: Vec<ProcessUsage> = table
+        .into_iter()
+        .filter(|p| tree.contains(&p.pid))
+        .collect();
+
+    Ok(TerminalResourceUsage {
+        session_id,
+        shell_pid,
+        total_cpu_percent: processes.iter().map(|p| p.cpu_percent).sum(),
+        total_memory_kb: processes.iter().map(|p| p.memory_kb).sum(),
+        processes,
+    })
+}
+
+/// Kill shells left behind by a previous app run, and sessions whose window is gone
+#[tauri::command]
+pub fn terminal_kill_orphans(
+    app: tauri::AppHandle,
+    sessions: State<'_, TerminalSessions>,
+) -> CmdResult<OrphanCleanupResult> {
+    let mut result = cleanup_stale_sessions();
+
+    let windows = app.webview_windows();
+    let orphaned: Vec<(String, TerminalSession)> = {
+        let mut guard = sessions
+            .sessions
+            .lock()
+            .map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        let ids: Vec<String> = guard
+            .iter()
+            .filter(|(_, s)| !windows.contains_key(&s.window))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| guard.remove(&id).map(|s| (id, s)))
+            .collect()
+    };
+    for (id, session) in orphaned {
+        if let Some(pid) = session.pid {
+            result.killed_pids.extend(kill_tree(pid));
+        }
+        end_session(session);
+        update_registry(|records| {
+            records.remove(&id);
+        });
+        result.sessions.push(id);
+    }
+
+    if !result.sessions.is_empty() {
+        log::info!("Terminal orphans cleaned up: {:?}", result.sessions);
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// Clipboard output
+// ============================================================================
+
+/// Output as the screen showed it: escape sequences removed, carriage-return
+/// overwrites and backspaces applied, other control characters dropped
+pub fn strip_ansi(raw: &str) -> String {
+    let text = ANSI_RE.replace_all(raw, "");
+    text.split('\n')
+        .map(|line| {
+            let line = line.trim_end_matches('\r');
+            // Progress bars redraw with \r; the last redraw is what stayed on screen
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            let mut out = String::with_capacity(line.len());
+            for c in line.chars() {
+                match c {
+                    '\x08' => {
+                        out.pop();
+                    }
+                    '\t' => out.push(c),
+                    c if c.is_control() => {}
+                    c => out.push(c),
+                }
+            }
+            out.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One line of copied output
+#[derive(Debug, PartialEq)]
+struct OutputLine {
+    text: String,
+    /// A prompt with a command, rewritten as `$ <command>`
+    is_command: bool,
+}
+
+/// Rewrite prompt lines as `$ <command>`, drop prompts with nothing typed
+/// after them, squeeze runs of blank lines and trim blank ends
+fn collapse_prompts(text: &str) -> Vec<OutputLine> {
+    let mut lines: Vec<OutputLine> = Vec::new();
+    for line in text.lines() {
+        let output = match PROMPT_RE.captures(line) {
+            Some(caps) => match caps.get(1).map(|m| m.as_str().trim()).filter(|c| !c.is_empty()) {
+                Some(command) => OutputLine {
+                    text: format!("$ {}", command),
+                    is_command: true,
+                },
+                None => continue,
+            },
+            None => OutputLine {
+                text: line.to_string(),
+                is_command: false,
+            },
+        };
+        let blank_run = output.text.is_empty() && !lines.last().is_some_and(|l| !l.text.is_empty());
+        if !blank_run {
+            lines.push(output);
+        }
+    }
+    while lines.last().is_some_and(|l| l.text.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn select_range(lines: Vec<OutputLine>, range: CopyRange) -> Vec<String> {
+    let start = match range {
+        CopyRange::All => 0,
+        CopyRange::LastLines { count } => lines.len().saturating_sub(count),
+        CopyRange::LastCommands { count } => {
+            let commands: Vec<usize> = lines
+                .iter()
+                .enumerate()
+                .filter(|(_, l)| l.is_command)
+                .map(|(i, _)| i)
+                .collect();
+            // Fewer commands than asked for (or none recognised): everything
+            commands
+                .len()
+                .checked_sub(count.max(1))
+                .map(|i| commands[i])
+                .unwrap_or(0)
+        }
+    };
+    lines.into_iter().skip(start).map(|l| l.text).collect()
+}
+
+// ============================================================================
+// Process tracking
+// ============================================================================
+
+fn registry_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".tv-client").join("terminal-sessions.json"))
+}
+
+fn load_registry() -> HashMap<String, SessionRecord> {
+    registry_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn update_registry(f: impl FnOnce(&mut HashMap<String, SessionRecord>)) {
+    static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(path) = registry_path() else { return };
+    let mut records = load_registry();
+    f(&mut records);
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&records) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
+/// Kill shells recorded by app runs that have exited. Records from another
+/// instance that is still running are left alone — those shells are live.
+pub fn cleanup_stale_sessions() -> OrphanCleanupResult {
+    let mut result = OrphanCleanupResult::default();
+    let records = load_registry();
+    if records.values().all(|r| r.app_pid == std::process::id()) {
+        return result;
+    }
+
+    let table = process_table().unwrap_or_default();
+    let stale: Vec<(String, SessionRecord)> = records
+        .into_iter()
+        .filter(|(_, r)| is_stale(r, std::process::id(), &table))
+        .collect();
+    for (id, record) in &stale {
+        // Only kill if the PID still belongs to a shell of the same name
+        let alive = table
+            .iter()
+            .any(|p| p.pid == record.pid && shell_name(&p.command) == record.shell);
+        if alive {
+            result.killed_pids.extend(kill_tree(record.pid));
+            result.sessions.push(id.clone());
+        }
+    }
+    update_registry(|records| {
+        for (id, _) in &stale {
+            records.remove(id);
+        }
+    });
+    result
+}
+
+/// True if `record` belongs to an app process that is no longer running
+fn is_stale(record: &SessionRecord, current: u32, table: &[ProcessUsage]) -> bool {
+    record.app_pid != current && !table.iter().any(|p| p.pid == record.app_pid)
+}
+
+/// Kill the shell and reap it; dropping the session closes the PTY
+fn end_session(mut session: TerminalSession) {
+    let _ = session.child.kill();
+    let _ = session.child.try_wait();
+}
+
+fn shell_name(path: &str) -> String {
+    let name = path.rsplit(|c| c == '/' || c == '\\').next().unwrap_or(path);
+    name.trim_start_matches('-').to_string()
+}
+
+/// `root` and all of its descendants, parents before children
+pub fn process_tree(table: &[ProcessUsage], root: u32) -> Vec<u32> {
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(table.iter().filter(|p| p.ppid == parent && p.pid != root).map(|p| p.pid));
+        i += 1;
+    }
+    tree
+}
+
+/// Parse `ps -A -o pid=,ppid=,pcpu=,rss=,comm=` output
+pub fn parse_ps(output: &str) -> Vec<ProcessUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let ppid = parts.next()?.parse().ok()?;
+            let cpu_percent = parts.next()?.parse().ok()?;
+            let memory_kb = parts.next()?.parse().ok()?;
+            let command = parts.collect::<Vec<_>>().join(" ");
+            Some(ProcessUsage {
+                pid,
+                ppid,
+                command,
+                cpu_percent,
+                memory_kb,
+            })
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn process_table() -> CmdResult<Vec<ProcessUsage>> {
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,pcpu=,rss=,comm="])
+        .output()
+        .map_err(|e| CommandError::Io(format!("Failed to run ps: {}", e)))?;
+    Ok(parse_ps(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(windows)]
+fn process_table() -> CmdResult<Vec<ProcessUsage>> {
+    Err(CommandError::Config("Process usage is not supported on Windows".to_string()))
+}
+
+/// SIGHUP the tree (what closing a terminal sends; interactive shells ignore
+/// SIGTERM), then SIGKILL whatever is left. Returns the PIDs signalled.
+#[cfg(unix)]
+fn kill_tree(root: u32) -> Vec<u32> {
+    let tree = match process_table() {
+        Ok(table) => process_tree(&table, root),
+        Err(_) => vec![root],
+    };
+    // Children first so the shell can't respawn anything
+    for pid in tree.iter().rev() {
+        unsafe {
+            libc::kill(*pid as i32, libc::SIGHUP);
+        }
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    for pid in tree.iter().rev() {
+        unsafe {
+            if libc::kill(*pid as i32, 0) == 0 {
+                libc::kill(*pid as i32, libc::SIGKILL);
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(windows)]
+fn kill_tree(root: u32) -> Vec<u32> {
+    let _ = std::process::Command::new("taskkill")
+        .args(["/PID", &root.to_string(), "/T", "/F"])
+        .output();
+    vec![root]
+}
+
 fn get_default_shell() -> String {
     std::env::var("SHELL").unwrap_or_else(|_| {
         if cfg!(target_os = "windows") {
@@ -236,3 +740,81 @@ fn get_default_shell() -> String {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_the_process_tree_from_ps_output() {
+        let table = parse_ps(
+            "    1     0   0.0   1024 launchd\n\
+             500     1   0.1   4096 -zsh\n\
+             501   500  12.5  20480 node\n\
+             502   501   3.0   8192 esbuild\n\
+             600     1   0.0   2048 /bin/zsh\n\
+             garbage line\n",
+        );
+        assert_eq!(table.len(), 5);
+        assert_eq!(process_tree(&table, 500), vec![500, 501, 502]);
+        assert_eq!(shell_name("-zsh"), "zsh");
+        assert_eq!(shell_name("/bin/zsh"), "zsh");
+    }
+
+    #[test]
+    fn only_records_from_exited_app_runs_are_stale() {
+        let table = parse_ps(" 700     1   0.0   1024 tv-client\n 701   700   0.0   1024 -zsh\n");
+        let record = |app_pid| SessionRecord {
+            pid: 701,
+            app_pid,
+            window: "main".to_string(),
+            shell: "zsh".to_string(),
+            created_at: String::new(),
+        };
+        // Another running instance owns its shells
+        assert!(!is_stale(&record(700), 900, &table));
+        assert!(!is_stale(&record(900), 900, &table));
+        assert!(is_stale(&record(650), 900, &table));
+    }
+
+    #[test]
+    fn cleans_output_for_the_clipboard() {
+        let raw = "\x1b]0;melvin@mbp: ~/tv\x07melvin@mbp tv % \x1b[1mcargo build\x1b[0m\r\n\
+                   Compiling tv 0.1.0\r\n\
+                   \x1b[32m 10%\r 55%\r100%\x1b[0m done\r\n\
+                   \r\n\r\n\r\n\
+                   (venv) melvin@mbp:~/tv$ echo oxx\x08\x08ps\r\n\
+                   ops\r\n\
+                   melvin@mbp:~/tv$ \r\n\
+                   $5 total\r\n\
+                   ❯ ls\r\n\
+                   a.txt\r\n\
+                   melvin@mbp tv % ";
+        let clean = strip_ansi(raw);
+        assert!(clean.starts_with("melvin@mbp tv % cargo build\nCompiling tv 0.1.0\n100% done\n"));
+
+        let lines = collapse_prompts(&clean);
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "$ cargo build",
+                "Compiling tv 0.1.0",
+                "100% done",
+                "",
+                "$ echo ops",
+                "ops",
+                "$5 total",
+                "$ ls",
+                "a.txt"
+            ]
+        );
+
+        let last_two = select_range(collapse_prompts(&clean), CopyRange::LastCommands { count: 2 });
+        assert_eq!(last_two, vec!["$ echo ops", "ops", "$5 total", "$ ls", "a.txt"]);
+        let tail = select_range(collapse_prompts(&clean), CopyRange::LastLines { count: 1 });
+        assert_eq!(tail, vec!["a.txt"]);
+        let none_found = select_range(collapse_prompts("just output"), CopyRange::LastCommands { count: 1 });
+        assert_eq!(none_found, vec!["just output"]);
+    }
+}
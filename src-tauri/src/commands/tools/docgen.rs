@@ -1,6 +1,8 @@
 // Document Generator - PDF generation for order forms and proposals
 // Both use HTML template + Chrome headless for professional formatting
+// Branding (logo, colors, fonts, footer) comes from the theme layer in docgen_theme.rs
 
+use super::docgen_theme::{resolve_theme, DocTheme};
 use crate::commands::error::{CmdResult, CommandError};
 use pulldown_cmark::{html, Options, Parser};
 use regex::Regex;
@@ -465,9 +467,9 @@ fn extract_implementation_payments(markdown: &str) -> Vec<ImplementationPaymentR
 // ============================================================================
 
 /// Generate order form PDF from data using HTML template + Chrome headless
-pub fn generate_order_form_pdf(data: &OrderFormData, output_path: &str) -> CmdResult<String> {
+pub fn generate_order_form_pdf(data: &OrderFormData, output_path: &str, theme: &DocTheme) -> CmdResult<String> {
     // Generate HTML from data
-    let full_html = wrap_in_order_form_template(data);
+    let full_html = wrap_in_order_form_template(data, theme);
 
     // Write HTML to temp file
     let temp_dir = std::env::temp_dir();
@@ -500,7 +502,7 @@ pub fn generate_order_form_pdf(data: &OrderFormData, output_path: &str) -> CmdRe
 }
 
 /// Wrap order form data in HTML template
-fn wrap_in_order_form_template(data: &OrderFormData) -> String {
+fn wrap_in_order_form_template(data: &OrderFormData, theme: &DocTheme) -> String {
     // Generate scope items HTML with sub-headings support
     let mut scope_items_html = String::new();
     let mut in_list = false;
@@ -653,7 +655,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     }}
 
     body {{
-      font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
+      font-family: var(--font-body, 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif);
       font-size: 11pt;
       line-height: 1.4;
       color: #333;
@@ -674,7 +676,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
 
     .header {{
       margin-bottom: 10px;
-      border-bottom: 3px solid #00A0E3;
+      border-bottom: 3px solid var(--brand-primary, #00A0E3);
       padding-bottom: 10px;
     }}
 
@@ -692,9 +694,9 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     h2 {{
       font-size: 14pt;
       font-weight: bold;
-      color: #00A0E3;
+      color: var(--brand-primary, #00A0E3);
       margin: 25px 0 10px 0;
-      border-bottom: 2px solid #00A0E3;
+      border-bottom: 2px solid var(--brand-primary, #00A0E3);
       padding-bottom: 5px;
     }}
 
@@ -742,7 +744,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     }}
 
     .payment-table th {{
-      background-color: #00A0E3;
+      background-color: var(--brand-primary, #00A0E3);
       color: white;
       padding: 10px;
       text-align: left;
@@ -787,7 +789,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     .entitlement-section {{
       margin: 15px 0;
       padding: 10px 0 10px 15px;
-      border-left: 3px solid #00A0E3;
+      border-left: 3px solid var(--brand-primary, #00A0E3);
     }}
 
     .entitlement-section p {{
@@ -850,7 +852,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     }}
 
     .notice-box h3 {{
-      color: #00A0E3;
+      color: var(--brand-primary, #00A0E3);
       margin-bottom: 10px;
     }}
 
@@ -903,7 +905,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
     }}
 
     .terms-box a {{
-      color: #00A0E3;
+      color: var(--brand-primary, #00A0E3);
       text-decoration: underline;
     }}
 
@@ -942,13 +944,14 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
       }}
     }}
   </style>
+{theme_head}
 </head>
 <body>
   <div class="document">
     <!-- Header with Logo -->
     <header class="header">
       <div class="logo">
-        <img src="data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAYUAAABOCAYAAADPewqLAAAACXBIWXMAAAsSAAALEgHS3X78AAAJ2UlEQVR4nO2dT04kNxTGvzdinyEXyIhcAInZD5GaIzRHYI7QLLOEVdawyhqOAFKYfVqaEyBygZCcwFm0u1SQ8rOr2+V/9f2kXjDNtE1Vlz8/v39ijAEhpDxE5Cn3HMjs+H6QewaEECdfck+AzI8PuSdACCGkHGgpEFIHv+SeAGmWYwC/bX+gKBBSAcaYp9xzIG0iIm9+5vERIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQjg8iYgp+LbTJi8iD6/+muoBTzSPhZ/4tIie7fmbEeRR3vUaOf6N8j5NcY+0aiMjV1OOHICKrXZ/3keNo9yP5tcj9/RwDLQVyCOBORA5zT6RWROQGwIXj7VcAZ8aYdYKp3CvvXRRyj1eOf382xjzGGMD+na77AZRzLYqEokAA4AjAXe5J1IhHEIB0ggBjzC2AZ8fbhwCWKebhQkQu7DyGuI44lHY/YOfg+53ZQlEgWxalHDHUQoAgfE0lCD1ulfdcu/RUuMZ/hW7lBGMtgJC/k6LggKJA+qzsbo54EJEV/IKgLdBTcYvNIjvEkYhksRasv+DI8fa1McY157Es4bZG+hzxuz4MRYG85yq147k27GKiWVW5BAF2cVV9C6nmMmLcKFaCZYw1RFEY4IMxRvZ5AXA6h/b97FiOJzIKOp4VrCDcKL+STRB6aOfzCxFx7dgnwY7nslBujTEuP8jYcZZwWyNDnMSMeGoFWgpkCDqeBwgQhNsCBAF2kdU2VKl9C9p4Ma0E187/Hm4HfG4/S3FQFIgLOp57BArC11TzCUCzFpapLEE7jstKWEcMQ10AcO36b+G+Hsktp9KhKBANOp5RpSDALrZaeGqq+6qFoca0qlThsRacy5lNa6EHRYH4mLXjuUZB6KFZCylFYYjXWEdtdqfvGqc/hut6MJmtB0WB+DgEcDPHh8YeSWiCsC5YEIDNWboWnjqpMNjPd4ahRhwqVHhKjMoqDooC2aIlWZ1AXxybw1pHmrN9DeAs0XR2woanarvxqXMWtM+PZSVoR2FvhMc64F3jrua48RmCokC2nMG9qwQ2zslZnL1aQXiA+yx8jU35ilgJV1OiLb6LqY4G7ec6Hb8Rr53msxiyDFzXI3sZkFKgKBAA3a7St/O9ypURm4rGBMG3OwamOzbRPjemg9npSxjKf7BlR1wRT7PY9PigKJAO+8D4zshvWnU8BwjCMyoShB5Jq6d6HL+PsepBeXwWmvC4rke2MiAlQVEgb7COOe2BatLxHCAIrwDOKxSEbXiqthDHtha0hTVFspoqPJ5qsrN3OFMUyP+wETWzcTwHCkKyEtgTkbJ6qtYzIZaDeYHN93CIEOFxzWMx99IXFAXiYhaOZ2vxtC4I3l4LscJTPT0TYvoS9hUeLZlt1kdIFAUyyBwcz3MRhB4pHM5az4SYyWpaSQsvnmqyF3MufUFRIE5adjz3BEGb+2VDggDoC+beFUM9PRPuI/pjYglPCRnfxdGsKChNu6O/4N61VE+LjucRgpC94mlMEiSzaQtplAxmT7LaKOHxVJOdbemLZkWBxKMlx3OgIADtZriqR0i7Hpt4eiY8xuqZgPjCoyWzzdJaoCiQUM5RueN5hCAAm0XhYdoZpceTvAXsvhBq9z5mnSPXODsJjzFG67VAUSDEhX3gzj2/VqzjeaQgbDkRkSosoJH4rIVRFpKnZ8JzxJ4JWmTTPsLj+r+z7ONMUSDB2If70vNrN4VGbtxhnCBsuSjdAhqLZ3e8Sw2gqRbr92hhqPsIj1ZNtql7HwJFgYzCGHMNv+O5qB7PdrevBQP4nJNXDSY0xUxmc5auRqQMZk//5b2Ex+OAP2rw3qs0KwrGGEn1gn5G2yKX8DueS2rlqR0BrAH8DH8o412NobcKWvJW8ELoqz8UuRrqELGEJ2XGd9E0KwpkOuyD7nM813Ds0lU8tRFWmrhXF3qrERCeGnrvUvRMmLwMt/WZucRlVn2cKQpkJ0Y4nks1vYdKYJ+jkdDbQHy9FtSFMGCxThGGGjOXhNYCgIPcEyD1Yox5FJFL6EdFdyLyOeICEYPBngjGmFcROQfwJ9yO06WIXBljfA734jHGPIvIPdy7/RX0jHZtsY7lS9DKcD9jcz9iDLXlFcP3/kJErgv7Hk8CRYHshTHm2vPgbh3PpfQhUJvk2IXyDHpNpJWIRKv4mZlbuEVhKSKXQ9fKc8/XscJQlTGAjS8jpe9qibjRVEXC4yMSg1ocz0FNcmyCl88SuGrB8WwXby081bUo5+6/nINWs9zfQFEge1OJ43lUkxxrBWjCcAjgoZFFYpfCcM6idBEtKC3/IQez6ONMUSBRKNzxvFMJ7MCcjOpLYdhFXAtPfbMQTphZ/J6SrIQtzTucKQokGoEZz3dwx7VPxc49EQJCVVsphTHGWtAWxlhHR1r+Q06aL31BUSBRCdxdJ33YI/RE8IWq5j4ai4EWLbTY+k88PRNSJKsBwI8JElI/K+M3fYREUSBT4HM8V0Wgz6TYYoAh2OO/kM5sk+cMePovxxQeJ55qsk33caYokOjYh/Yr/DWFqsEumr72pFV2oevhq556Ar1nQqyNwOTNegJJ0b60OCgKZBICW3lWRcDfVHUpjIBeC5pTPWayWopmPV481WSXrZa+oCiQybAPVfWZv30CQlVPsHGm14q2uLvELmYi3+RO7JHMrvQFRYFMinU8R9lFlkKAM30hIiUk643GLu5jd+MpktWe7SYjNVq47rJWq1CDokBS4OvxXB0BfatXFYcujlnkfdVWx5Cq8F0w1j/mEqMoGdciYhK8gvNpKApkclp0PFvOoAtDrY5nbXf8nvuI0UDOLGlkEgWL5txu7giJokCS0KjjOUTsqiuF4dkdvydKNJAnSzpJGKoLT6+Fw4otwkEoCiQZjTqe19BDVWutkRSy2MeMBirNwfye2TicKQokKY06nn1WUClVYoOxi72v/HUsB7PWf/m+hB4GnmqyTfVxpiiQHLToePaFqtZYCkOzFmJGAxXnYHYwC9+CGGNyz4EQMoCIdA+nrcdDSHRE5BTAH/bHb7QUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdByIyBOALxOP8xeAl4nHCOUp9wQs3wH8k3sSAF6MMS+5J0EIKYODROP8ZF8lMLUAVodIEXlR/2IjlCUwtWC/GGN+H/MfROTXaaZCCD71fxBsds5cKAlJxzdjzKnvl/oZzYQk4ttByJdzX0TkGMDHqccJ4COA49yTsJzmnoDlGMAPuSdBCCkD1j4iRSAin/DOjM3I6cSfH3R8xCMjkoEXigIhhJAOhqQSQgjpoCgQQgjpoCgQQgjpoCgQQgjp+A+fvWvPHsV1gAAAAABJRU5ErkJggg==" alt="ThinkVAL" height="50">
+        {logo}
       </div>
     </header>
 
@@ -1086,6 +1089,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
         </div>
       </div>
     </div>
+{footer}
   </div>
 </body>
 </html>"##,
@@ -1111,6 +1115,12 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
         implementation_fee = data.implementation_fee,
         customer_officer_name = data.customer_officer_name,
         customer_officer_title = data.customer_officer_title,
+        theme_head = theme.head_html(),
+        logo = theme.logo_html(50),
+        footer = theme
+            .footer_html()
+            .map(|lines| format!("    <footer class=\"theme-footer\">\n{}\n    </footer>", lines))
+            .unwrap_or_default(),
     )
 }
 
@@ -1119,7 +1129,7 @@ fn wrap_in_order_form_template(data: &OrderFormData) -> String {
 // ============================================================================
 
 /// Generate proposal PDF from markdown using HTML template + Chrome headless
-pub fn generate_proposal_pdf(markdown: &str, output_path: &str) -> CmdResult<String> {
+pub fn generate_proposal_pdf(markdown: &str, output_path: &str, theme: &DocTheme) -> CmdResult<String> {
     // Step 1: Convert markdown to HTML
     let html_body = markdown_to_html(markdown);
 
@@ -1127,7 +1137,7 @@ pub fn generate_proposal_pdf(markdown: &str, output_path: &str) -> CmdResult<Str
     let title = extract_title_from_markdown(markdown);
 
     // Step 3: Wrap in HTML template
-    let full_html = wrap_in_proposal_template(&html_body, &title);
+    let full_html = wrap_in_proposal_template(&html_body, &title, theme);
 
     // Step 4: Write HTML to temp file
     let temp_dir = std::env::temp_dir();
@@ -1207,8 +1217,12 @@ fn extract_title_from_markdown(markdown: &str) -> String {
     "Proposal".to_string()
 }
 
+/// Proposal footer when the theme doesn't set one
+const PROPOSAL_DEFAULT_FOOTER: &str = r#"      <p><strong>ThinkVAL Pte. Ltd.</strong></p>
+      <p>This document is confidential and intended solely for the addressee. Unauthorized distribution is prohibited.</p>"#;
+
 /// Wrap HTML content in the proposal template
-fn wrap_in_proposal_template(body: &str, title: &str) -> String {
+fn wrap_in_proposal_template(body: &str, title: &str, theme: &DocTheme) -> String {
     format!(r##"<!DOCTYPE html>
 <html lang="en">
 <head>
@@ -1223,7 +1237,7 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
     }}
 
     body {{
-      font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, 'Helvetica Neue', Arial, sans-serif;
+      font-family: var(--font-body, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, 'Helvetica Neue', Arial, sans-serif);
       font-size: 10.5pt;
       line-height: 1.6;
       color: #1e293b;
@@ -1241,7 +1255,7 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
     .header {{
       margin-bottom: 24px;
       padding-bottom: 16px;
-      border-bottom: 3px solid #00A0E3;
+      border-bottom: 3px solid var(--brand-primary, #00A0E3);
     }}
 
     .logo svg {{
@@ -1379,7 +1393,7 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
 
     /* Bold text in table body - blue to match accent color */
     td strong {{
-      color: #0284c7;
+      color: var(--brand-accent, #0284c7);
     }}
 
     /* Horizontal rules */
@@ -1399,7 +1413,7 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
       margin: 14px 0;
       padding: 12px 16px;
       background-color: #f0f9ff;
-      border-left: 4px solid #00A0E3;
+      border-left: 4px solid var(--brand-primary, #00A0E3);
       color: #0369a1;
       font-style: normal;
     }}
@@ -1469,13 +1483,14 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
       font-weight: 600;
     }}
   </style>
+{theme_head}
 </head>
 <body>
   <div class="document">
     <!-- Header -->
     <header class="header">
       <div class="logo">
-        <img src="data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAYUAAABOCAYAAADPewqLAAAACXBIWXMAAAsSAAALEgHS3X78AAAJ2UlEQVR4nO2dT04kNxTGvzdinyEXyIhcAInZD5GaIzRHYI7QLLOEVdawyhqOAFKYfVqaEyBygZCcwFm0u1SQ8rOr2+V/9f2kXjDNtE1Vlz8/v39ijAEhpDxE5Cn3HMjs+H6QewaEECdfck+AzI8PuSdACCGkHGgpEFIHv+SeAGmWYwC/bX+gKBBSAcaYp9xzIG0iIm9+5vERIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQjg8iYgp+LbTJi8iD6/+muoBTzSPhZ/4tIie7fmbEeRR3vUaOf6N8j5NcY+0aiMjV1OOHICKrXZ/3keNo9yP5tcj9/RwDLQVyCOBORA5zT6RWROQGwIXj7VcAZ8aYdYKp3CvvXRRyj1eOf382xjzGGMD+na77AZRzLYqEokAA4AjAXe5J1IhHEIB0ggBjzC2AZ8fbhwCWKebhQkQu7DyGuI44lHY/YOfg+53ZQlEgWxalHDHUQoAgfE0lCD1ulfdcu/RUuMZ/hW7lBGMtgJC/k6LggKJA+qzsbo54EJEV/IKgLdBTcYvNIjvEkYhksRasv+DI8fa1McY157Es4bZG+hzxuz4MRYG85yq147k27GKiWVW5BAF2cVV9C6nmMmLcKFaCZYw1RFEY4IMxRvZ5AXA6h/b97FiOJzIKOp4VrCDcKL+STRB6aOfzCxFx7dgnwY7nslBujTEuP8jYcZZwWyNDnMSMeGoFWgpkCDqeBwgQhNsCBAF2kdU2VKl9C9p4Ma0E187/Hm4HfG4/S3FQFIgLOp57BArC11TzCUCzFpapLEE7jstKWEcMQ10AcO36b+G+Hsktp9KhKBANOp5RpSDALrZaeGqq+6qFoca0qlThsRacy5lNa6EHRYH4mLXjuUZB6KFZCylFYYjXWEdtdqfvGqc/hut6MJmtB0WB+DgEcDPHh8YeSWiCsC5YEIDNWboWnjqpMNjPd4ahRhwqVHhKjMoqDooC2aIlWZ1AXxybw1pHmrN9DeAs0XR2woanarvxqXMWtM+PZSVoR2FvhMc64F3jrua48RmCokC2nMG9qwQ2zslZnL1aQXiA+yx8jU35ilgJV1OiLb6LqY4G7ec6Hb8Rr53msxiyDFzXI3sZkFKgKBAA3a7St/O9ypURm4rGBMG3OwamOzbRPjemg9npSxjKf7BlR1wRT7PY9PigKJAO+8D4zshvWnU8BwjCMyoShB5Jq6d6HL+PsepBeXwWmvC4rke2MiAlQVEgb7COOe2BatLxHCAIrwDOKxSEbXiqthDHtha0hTVFspoqPJ5qsrN3OFMUyP+wETWzcTwHCkKyEtgTkbJ6qtYzIZaDeYHN93CIEOFxzWMx99IXFAXiYhaOZ2vxtC4I3l4LscJTPT0TYvoS9hUeLZlt1kdIFAUyyBwcz3MRhB4pHM5az4SYyWpaSQsvnmqyF3MufUFRIE5adjz3BEGb+2VDggDoC+beFUM9PRPuI/pjYglPCRnfxdGsKChNu6O/4N61VE+LjucRgpC94mlMEiSzaQtplAxmT7LaKOHxVJOdbemLZkWBxKMlx3OgIADtZriqR0i7Hpt4eiY8xuqZgPjCoyWzzdJaoCiQUM5RueN5hCAAm0XhYdoZpceTvAXsvhBq9z5mnSPXODsJjzFG67VAUSDEhX3gzj2/VqzjeaQgbDkRkSosoJH4rIVRFpKnZ8JzxJ4JWmTTPsLj+r+z7ONMUSDB2If70vNrN4VGbtxhnCBsuSjdAhqLZ3e8Sw2gqRbr92hhqPsIj1ZNtql7HwJFgYzCGHMNv+O5qB7PdrevBQP4nJNXDSY0xUxmc5auRqQMZk//5b2Ex+OAP2rw3qs0KwrGGEn1gn5G2yKX8DueS2rlqR0BrAH8DH8o412NobcKWvJW8ELoqz8UuRrqELGEJ2XGd9E0KwpkOuyD7nM813Ds0lU8tRFWmrhXF3qrERCeGnrvUvRMmLwMt/WZucRlVn2cKQpkJ0Y4nks1vYdKYJ+jkdDbQHy9FtSFMGCxThGGGjOXhNYCgIPcEyD1Yox5FJFL6EdFdyLyOeICEYPBngjGmFcROQfwJ9yO06WIXBljfA734jHGPIvIPdy7/RX0jHZtsY7lS9DKcD9jcz9iDLXlFcP3/kJErgv7Hk8CRYHshTHm2vPgbh3PpfQhUJvk2IXyDHpNpJWIRKv4mZlbuEVhKSKXQ9fKc8/XscJQlTGAjS8jpe9qibjRVEXC4yMSg1ocz0FNcmyCl88SuGrB8WwXby081bUo5+6/nINWs9zfQFEge1OJ43lUkxxrBWjCcAjgoZFFYpfCcM6idBEtKC3/IQez6ONMUSBRKNzxvFMJ7MCcjOpLYdhFXAtPfbMQTphZ/J6SrIQtzTucKQokGoEZz3dwx7VPxc49EQJCVVsphTHGWtAWxlhHR1r+Q06aL31BUSBRCdxdJ33YI/RE8IWq5j4ai4EWLbTY+k88PRNSJKsBwI8JElI/K+M3fYREUSBT4HM8V0Wgz6TYYoAh2OO/kM5sk+cMePovxxQeJ55qsk33caYokOjYh/Yr/DWFqsEumr72pFV2oevhq556Ar1nQqyNwOTNegJJ0b60OCgKZBICW3lWRcDfVHUpjIBeC5pTPWayWopmPV481WSXrZa+oCiQybAPVfWZv30CQlVPsHGm14q2uLvELmYi3+RO7JHMrvQFRYFMinU8R9lFlkKAM30hIiUk643GLu5jd+MpktWe7SYjNVq47rJWq1CDokBS4OvxXB0BfatXFYcujlnkfdVWx5Cq8F0w1j/mEqMoGdciYhK8gvNpKApkclp0PFvOoAtDrY5nbXf8nvuI0UDOLGlkEgWL5txu7giJokCS0KjjOUTsqiuF4dkdvydKNJAnSzpJGKoLT6+Fw4otwkEoCiQZjTqe19BDVWutkRSy2MeMBirNwfye2TicKQokKY06nn1WUClVYoOxi72v/HUsB7PWf/m+hB4GnmqyTfVxpiiQHLToePaFqtZYCkOzFmJGAxXnYHYwC9+CGGNyz4EQMoCIdA+nrcdDSHRE5BTAH/bHb7QUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdByIyBOALxOP8xeAl4nHCOUp9wQs3wH8k3sSAF6MMS+5J0EIKYODROP8ZF8lMLUAVodIEXlR/2IjlCUwtWC/GGN+H/MfROTXaaZCCD71fxBsds5cKAlJxzdjzKnvl/oZzYQk4ttByJdzX0TkGMDHqccJ4COA49yTsJzmnoDlGMAPuSdBCCkD1j4iRSAin/DOjM3I6cSfH3R8xCMjkoEXigIhhJAOhqQSQgjpoCgQQgjpoCgQQgjpoCgQQgjp+A+fvWvPHsV1gAAAAABJRU5ErkJggg==" alt="ThinkVAL" height="45">
+        {logo}
       </div>
     </header>
 
@@ -1486,12 +1501,17 @@ fn wrap_in_proposal_template(body: &str, title: &str) -> String {
 
     <!-- Footer -->
     <footer class="footer">
-      <p><strong>ThinkVAL Pte. Ltd.</strong></p>
-      <p>This document is confidential and intended solely for the addressee. Unauthorized distribution is prohibited.</p>
+{footer}
     </footer>
   </div>
 </body>
-</html>"##, title = title, body = body)
+</html>"##,
+        title = title,
+        body = body,
+        theme_head = theme.head_html(),
+        logo = theme.logo_html(45),
+        footer = theme.footer_html().unwrap_or_else(|| PROPOSAL_DEFAULT_FOOTER.to_string()),
+    )
 }
 
 /// Find Chrome executable path
@@ -1543,8 +1563,9 @@ pub fn generate_proposal_from_file(
             parent.join(format!("{}.pdf", stem)).to_string_lossy().to_string()
         });
 
-    // Generate PDF
-    generate_proposal_pdf(&markdown, &output)
+    // Generate PDF with the theme that applies to this file's folder
+    let theme = resolve_theme(Some(Path::new(file_path)));
+    generate_proposal_pdf(&markdown, &output, &theme)
 }
 
 // ============================================================================
@@ -1572,8 +1593,9 @@ pub fn generate_order_form_from_file(
             parent.join(format!("{}.pdf", stem)).to_string_lossy().to_string()
         });
 
-    // Generate PDF
-    generate_order_form_pdf(&data, &output)
+    // Generate PDF with the theme that applies to this file's folder
+    let theme = resolve_theme(Some(Path::new(file_path)));
+    generate_order_form_pdf(&data, &output, &theme)
 }
 
 /// Check if a file is an order form data file
@@ -1592,6 +1614,12 @@ pub fn is_proposal_file(file_path: &str) -> bool {
     filename == "proposal-data.md" || filename.ends_with("-proposal.md")
 }
 
+/// Text safe to drop into a markdown table cell: pipes are escaped and
+/// newlines collapse to spaces, since either would break the row
+pub fn markdown_table_cell(text: &str) -> String {
+    text.replace('|', "\\|").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
@@ -1647,6 +1675,12 @@ pub async fn html_to_pdf_cmd(file_path: String) -> CmdResult<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_markdown_table_cell() {
+        assert_eq!(markdown_table_cell("a | b\n  c"), "a \\| b c");
+        assert_eq!(markdown_table_cell("  "), "");
+    }
+
     #[test]
     fn test_extract_yaml_values() {
         let markdown = r#"
@@ -0,0 +1,387 @@
+// Document Generator - Branded theme layer
+// A theme supplies logo, brand colors, fonts and footer text to the docgen HTML
+// templates (order forms, proposals) through CSS variables and template slots.
+// Unset fields keep the built-in ThinkVAL look.
+//
+// Lookup: the nearest `docgen-theme.json` walking up from the source document
+// (so a client folder in the knowledge base can carry its own branding), then
+// ~/.tv-client/docgen/theme.json.
+
+use super::docgen::{generate_order_form_pdf, generate_proposal_pdf, OrderFormData, PaymentRow};
+use crate::commands::error::{CmdResult, CommandError};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of a folder-level theme in the knowledge base
+pub const THEME_FILE_NAME: &str = "docgen-theme.json";
+
+/// Built-in ThinkVAL logo, used when the theme has no logo_path
+const DEFAULT_LOGO_DATA_URI: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAYUAAABOCAYAAADPewqLAAAACXBIWXMAAAsSAAALEgHS3X78AAAJ2UlEQVR4nO2dT04kNxTGvzdinyEXyIhcAInZD5GaIzRHYI7QLLOEVdawyhqOAFKYfVqaEyBygZCcwFm0u1SQ8rOr2+V/9f2kXjDNtE1Vlz8/v39ijAEhpDxE5Cn3HMjs+H6QewaEECdfck+AzI8PuSdACCGkHGgpEFIHv+SeAGmWYwC/bX+gKBBSAcaYp9xzIG0iIm9+5vERIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQDooCIYSQjg8iYgp+LbTJi8iD6/+muoBTzSPhZ/4tIie7fmbEeRR3vUaOf6N8j5NcY+0aiMjV1OOHICKrXZ/3keNo9yP5tcj9/RwDLQVyCOBORA5zT6RWROQGwIXj7VcAZ8aYdYKp3CvvXRRyj1eOf382xjzGGMD+na77AZRzLYqEokAA4AjAXe5J1IhHEIB0ggBjzC2AZ8fbhwCWKebhQkQu7DyGuI44lHY/YOfg+53ZQlEgWxalHDHUQoAgfE0lCD1ulfdcu/RUuMZ/hW7lBGMtgJC/k6LggKJA+qzsbo54EJEV/IKgLdBTcYvNIjvEkYhksRasv+DI8fa1McY157Es4bZG+hzxuz4MRYG85yq147k27GKiWVW5BAF2cVV9C6nmMmLcKFaCZYw1RFEY4IMxRvZ5AXA6h/b97FiOJzIKOp4VrCDcKL+STRB6aOfzCxFx7dgnwY7nslBujTEuP8jYcZZwWyNDnMSMeGoFWgpkCDqeBwgQhNsCBAF2kdU2VKl9C9p4Ma0E187/Hm4HfG4/S3FQFIgLOp57BArC11TzCUCzFpapLEE7jstKWEcMQ10AcO36b+G+Hsktp9KhKBANOp5RpSDALrZaeGqq+6qFoca0qlThsRacy5lNa6EHRYH4mLXjuUZB6KFZCylFYYjXWEdtdqfvGqc/hut6MJmtB0WB+DgEcDPHh8YeSWiCsC5YEIDNWboWnjqpMNjPd4ahRhwqVHhKjMoqDooC2aIlWZ1AXxybw1pHmrN9DeAs0XR2woanarvxqXMWtM+PZSVoR2FvhMc64F3jrua48RmCokC2nMG9qwQ2zslZnL1aQXiA+yx8jU35ilgJV1OiLb6LqY4G7ec6Hb8Rr53msxiyDFzXI3sZkFKgKBAA3a7St/O9ypURm4rGBMG3OwamOzbRPjemg9npSxjKf7BlR1wRT7PY9PigKJAO+8D4zshvWnU8BwjCMyoShB5Jq6d6HL+PsepBeXwWmvC4rke2MiAlQVEgb7COOe2BatLxHCAIrwDOKxSEbXiqthDHtha0hTVFspoqPJ5qsrN3OFMUyP+wETWzcTwHCkKyEtgTkbJ6qtYzIZaDeYHN93CIEOFxzWMx99IXFAXiYhaOZ2vxtC4I3l4LscJTPT0TYvoS9hUeLZlt1kdIFAUyyBwcz3MRhB4pHM5az4SYyWpaSQsvnmqyF3MufUFRIE5adjz3BEGb+2VDggDoC+beFUM9PRPuI/pjYglPCRnfxdGsKChNu6O/4N61VE+LjucRgpC94mlMEiSzaQtplAxmT7LaKOHxVJOdbemLZkWBxKMlx3OgIADtZriqR0i7Hpt4eiY8xuqZgPjCoyWzzdJaoCiQUM5RueN5hCAAm0XhYdoZpceTvAXsvhBq9z5mnSPXODsJjzFG67VAUSDEhX3gzj2/VqzjeaQgbDkRkSosoJH4rIVRFpKnZ8JzxJ4JWmTTPsLj+r+z7ONMUSDB2If70vNrN4VGbtxhnCBsuSjdAhqLZ3e8Sw2gqRbr92hhqPsIj1ZNtql7HwJFgYzCGHMNv+O5qB7PdrevBQP4nJNXDSY0xUxmc5auRqQMZk//5b2Ex+OAP2rw3qs0KwrGGEn1gn5G2yKX8DueS2rlqR0BrAH8DH8o412NobcKWvJW8ELoqz8UuRrqELGEJ2XGd9E0KwpkOuyD7nM813Ds0lU8tRFWmrhXF3qrERCeGnrvUvRMmLwMt/WZucRlVn2cKQpkJ0Y4nks1vYdKYJ+jkdDbQHy9FtSFMGCxThGGGjOXhNYCgIPcEyD1Yox5FJFL6EdFdyLyOeICEYPBngjGmFcROQfwJ9yO06WIXBljfA734jHGPIvIPdy7/RX0jHZtsY7lS9DKcD9jcz9iDLXlFcP3/kJErgv7Hk8CRYHshTHm2vPgbh3PpfQhUJvk2IXyDHpNpJWIRKv4mZlbuEVhKSKXQ9fKc8/XscJQlTGAjS8jpe9qibjRVEXC4yMSg1ocz0FNcmyCl88SuGrB8WwXby081bUo5+6/nINWs9zfQFEge1OJ43lUkxxrBWjCcAjgoZFFYpfCcM6idBEtKC3/IQez6ONMUSBRKNzxvFMJ7MCcjOpLYdhFXAtPfbMQTphZ/J6SrIQtzTucKQokGoEZz3dwx7VPxc49EQJCVVsphTHGWtAWxlhHR1r+Q06aL31BUSBRCdxdJ33YI/RE8IWq5j4ai4EWLbTY+k88PRNSJKsBwI8JElI/K+M3fYREUSBT4HM8V0Wgz6TYYoAh2OO/kM5sk+cMePovxxQeJ55qsk33caYokOjYh/Yr/DWFqsEumr72pFV2oevhq556Ar1nQqyNwOTNegJJ0b60OCgKZBICW3lWRcDfVHUpjIBeC5pTPWayWopmPV481WSXrZa+oCiQybAPVfWZv30CQlVPsHGm14q2uLvELmYi3+RO7JHMrvQFRYFMinU8R9lFlkKAM30hIiUk643GLu5jd+MpktWe7SYjNVq47rJWq1CDokBS4OvxXB0BfatXFYcujlnkfdVWx5Cq8F0w1j/mEqMoGdciYhK8gvNpKApkclp0PFvOoAtDrY5nbXf8nvuI0UDOLGlkEgWL5txu7giJokCS0KjjOUTsqiuF4dkdvydKNJAnSzpJGKoLT6+Fw4otwkEoCiQZjTqe19BDVWutkRSy2MeMBirNwfye2TicKQokKY06nn1WUClVYoOxi72v/HUsB7PWf/m+hB4GnmqyTfVxpiiQHLToePaFqtZYCkOzFmJGAxXnYHYwC9+CGGNyz4EQMoCIdA+nrcdDSHRE5BTAH/bHb7QUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdFAUCCGEdByIyBOALxOP8xeAl4nHCOUp9wQs3wH8k3sSAF6MMS+5J0EIKYODROP8ZF8lMLUAVodIEXlR/2IjlCUwtWC/GGN+H/MfROTXaaZCCD71fxBsds5cKAlJxzdjzKnvl/oZzYQk4ttByJdzX0TkGMDHqccJ4COA49yTsJzmnoDlGMAPuSdBCCkD1j4iRSAin/DOjM3I6cSfH3R8xCMjkoEXigIhhJAOhqQSQgjpoCgQQgjpoCgQQgjpoCgQQgjp+A+fvWvPHsV1gAAAAABJRU5ErkJggg==";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocTheme {
+    pub name: Option<String>,
+    /// PNG / JPG / SVG; relative paths resolve against the theme file's folder
+    pub logo_path: Option<String>,
+    /// Headings, rules and table headers (default #00A0E3)
+    pub primary_color: Option<String>,
+    /// Secondary highlights such as bold table text (default #0284c7)
+    pub accent_color: Option<String>,
+    /// CSS font-family list for body text
+    pub body_font: Option<String>,
+    /// CSS font-family list for headings (defaults to body font)
+    pub heading_font: Option<String>,
+    /// Optional stylesheet URL for web fonts (e.g. Google Fonts)
+    pub font_css_url: Option<String>,
+    /// Footer text, one paragraph per line
+    pub footer_text: Option<String>,
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Escape text for HTML element content and double-quoted attribute values
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Hex (#rgb … #rrggbbaa), rgb()/hsl() or a plain named color
+fn is_valid_color(value: &str) -> bool {
+    let v = value.trim();
+    if let Some(hex) = v.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    if v.starts_with("rgb(") || v.starts_with("rgba(") || v.starts_with("hsl(") || v.starts_with("hsla(") {
+        return v.ends_with(')')
+            && v.chars().all(|c| c.is_ascii_alphanumeric() || " ,.%()/".contains(c));
+    }
+    !v.is_empty() && v.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Font stacks are written straight into CSS, so block anything that could
+/// close the declaration or the style element
+fn is_valid_font(value: &str) -> bool {
+    !value.trim().is_empty() && !value.contains(|c: char| matches!(c, ';' | '{' | '}' | '<' | '>' | '\\'))
+}
+
+impl DocTheme {
+    pub fn validate(&self) -> CmdResult<()> {
+        for (field, value) in [("primary_color", &self.primary_color), ("accent_color", &self.accent_color)] {
+            if let Some(v) = non_empty(value) {
+                if !is_valid_color(v) {
+                    return Err(CommandError::Config(format!("Invalid {}: {}", field, v)));
+                }
+            }
+        }
+        for (field, value) in [("body_font", &self.body_font), ("heading_font", &self.heading_font)] {
+            if let Some(v) = non_empty(value) {
+                if !is_valid_font(v) {
+                    return Err(CommandError::Config(format!("Invalid {}: {}", field, v)));
+                }
+            }
+        }
+        if let Some(url) = non_empty(&self.font_css_url) {
+            if !url.starts_with("https://") || url.contains(|c: char| matches!(c, '"' | '<' | '>')) {
+                return Err(CommandError::Config(format!("font_css_url must be an https URL: {}", url)));
+            }
+        }
+        if let Some(logo) = non_empty(&self.logo_path) {
+            if !Path::new(logo).is_file() {
+                return Err(CommandError::NotFound(format!("Logo not found: {}", logo)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extra `<head>` markup: web font link plus the CSS variables the templates read.
+    /// Invalid values are skipped rather than breaking the document.
+    pub fn head_html(&self) -> String {
+        let mut vars = Vec::new();
+        let mut rules = Vec::new();
+        if let Some(c) = non_empty(&self.primary_color).filter(|c| is_valid_color(c)) {
+            vars.push(format!("--brand-primary: {};", c));
+        }
+        if let Some(c) = non_empty(&self.accent_color).filter(|c| is_valid_color(c)) {
+            vars.push(format!("--brand-accent: {};", c));
+        }
+        if let Some(f) = non_empty(&self.body_font).filter(|f| is_valid_font(f)) {
+            vars.push(format!("--font-body: {};", f));
+        }
+        if let Some(f) = non_empty(&self.heading_font).filter(|f| is_valid_font(f)) {
+            vars.push(format!("--font-heading: {};", f));
+            rules.push("    h1, h2, h3, h4, .title { font-family: var(--font-heading); }".to_string());
+        }
+        if non_empty(&self.footer_text).is_some() {
+            rules.push(
+                "    .theme-footer { margin-top: 30px; padding-top: 10px; border-top: 1px solid #ddd; font-size: 8.5pt; color: #666; text-align: center; }"
+                    .to_string(),
+            );
+        }
+
+        let mut out = String::new();
+        if let Some(url) = non_empty(&self.font_css_url).filter(|u| u.starts_with("https://")) {
+            out.push_str(&format!("  <link rel=\"stylesheet\" href=\"{}\">\n", escape_html(url)));
+        }
+        if !vars.is_empty() || !rules.is_empty() {
+            out.push_str("  <style>\n");
+            if !vars.is_empty() {
+                out.push_str(&format!("    :root {{ {} }}\n", vars.join(" ")));
+            }
+            for rule in rules {
+                out.push_str(&rule);
+                out.push('\n');
+            }
+            out.push_str("  </style>");
+        }
+        out
+    }
+
+    /// Logo `<img>` with the theme logo inlined as a data URI (falls back to the
+    /// built-in logo if the file can't be read)
+    pub fn logo_html(&self, height: u32) -> String {
+        let custom = non_empty(&self.logo_path).and_then(|p| {
+            let bytes = fs::read(p).ok()?;
+            let mime = match Path::new(p).extension()?.to_str()?.to_lowercase().as_str() {
+                "png" => "image/png",
+                "jpg" | "jpeg" => "image/jpeg",
+                "svg" => "image/svg+xml",
+                "webp" => "image/webp",
+                _ => return None,
+            };
+            Some(format!(
+                "data:{};base64,{}",
+                mime,
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            ))
+        });
+        let alt = match (&custom, non_empty(&self.name)) {
+            (None, _) => "ThinkVAL".to_string(),
+            (Some(_), Some(name)) => escape_html(name),
+            (Some(_), None) => "Logo".to_string(),
+        };
+        format!(
+            r#"<img src="{}" alt="{}" height="{}">"#,
+            custom.as_deref().unwrap_or(DEFAULT_LOGO_DATA_URI),
+            alt,
+            height
+        )
+    }
+
+    /// Footer paragraphs, or None when the template's own footer should be used
+    pub fn footer_html(&self) -> Option<String> {
+        let text = non_empty(&self.footer_text)?;
+        Some(
+            text.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(|l| format!("      <p>{}</p>", escape_html(l)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn global_theme_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("docgen")
+        .join("theme.json")
+}
+
+fn load_theme_file(path: &Path) -> Option<DocTheme> {
+    let mut theme: DocTheme = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    // Relative logo paths are relative to the theme file
+    if let (Some(logo), Some(dir)) = (non_empty(&theme.logo_path), path.parent()) {
+        if Path::new(logo).is_relative() {
+            theme.logo_path = Some(dir.join(logo).to_string_lossy().to_string());
+        }
+    }
+    Some(theme)
+}
+
+/// Nearest folder theme above `doc_path`, else the global theme, else defaults
+pub fn resolve_theme(doc_path: Option<&Path>) -> DocTheme {
+    let start = doc_path.and_then(|p| if p.is_dir() { Some(p) } else { p.parent() });
+    if let Some(found) = start
+        .into_iter()
+        .flat_map(Path::ancestors)
+        .map(|dir| dir.join(THEME_FILE_NAME))
+        .find(|p| p.is_file())
+        .and_then(|p| load_theme_file(&p))
+    {
+        return found;
+    }
+    load_theme_file(&global_theme_path()).unwrap_or_default()
+}
+
+// ============================================================================
+// Preview samples
+// ============================================================================
+
+const SAMPLE_PROPOSAL: &str = r#"# Sample Customer Pte. Ltd.
+
+## Proposal for Data Platform Subscription
+
+This preview shows how proposals render with the current theme: headings,
+tables, callouts and the footer all pick up the brand settings.
+
+> **Note:** Figures below are placeholders.
+
+| Item | Annual | Monthly |
+|------|--------|---------|
+| Platform subscription | **SGD 24,000** | SGD 2,000 |
+| Implementation | **SGD 8,000** | - |
+| **Total** | **SGD 32,000** | |
+
+### Scope
+
+- Data integration for up to 3 source systems
+- Standard dashboards and weekly reporting
+- Onboarding and training sessions
+"#;
+
+fn sample_order_form() -> OrderFormData {
+    OrderFormData {
+        order_form_reference: "OF-PREVIEW-001".to_string(),
+        customer_name: "Sample Customer Pte. Ltd.".to_string(),
+        customer_uen: "202600000X".to_string(),
+        customer_address: "1 Example Road, Singapore 000000".to_string(),
+        contact_name: "Jane Doe".to_string(),
+        contact_email: "jane@example.com".to_string(),
+        subscription_start_date: "1 January 2027".to_string(),
+        subscription_end_date: "31 December 2027".to_string(),
+        subscription_fee: "24,000".to_string(),
+        service_term: "12 months".to_string(),
+        solutions: "VAL Data Platform".to_string(),
+        scope_items: vec![
+            "Data integration for up to 3 source systems".to_string(),
+            "Standard dashboards and weekly reporting".to_string(),
+        ],
+        subscription_payments: vec![PaymentRow {
+            period: "Year 1".to_string(),
+            date: "1 January 2027".to_string(),
+            amount: "24,000".to_string(),
+        }],
+        customer_officer_name: "Jane Doe".to_string(),
+        customer_officer_title: "Director".to_string(),
+        ..Default::default()
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Get the theme that applies to `file_path` (or the global theme)
+#[tauri::command]
+pub async fn docgen_get_theme(file_path: Option<String>) -> CmdResult<DocTheme> {
+    Ok(resolve_theme(file_path.as_deref().map(Path::new)))
+}
+
+/// Save a theme globally, or as `docgen-theme.json` in `folder` so documents
+/// under it use that branding. Returns the file written.
+#[tauri::command]
+pub async fn docgen_save_theme(theme: DocTheme, folder: Option<String>) -> CmdResult<String> {
+    theme.validate()?;
+    let path = match folder.filter(|f| !f.is_empty()) {
+        Some(dir) => {
+            let dir = PathBuf::from(dir);
+            if !dir.is_dir() {
+                return Err(CommandError::NotFound(format!("Folder not found: {}", dir.display())));
+            }
+            dir.join(THEME_FILE_NAME)
+        }
+        None => global_theme_path(),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&theme)?)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Render a sample "proposal" (default) or "order_form" PDF with `theme`, or
+/// with the saved theme when none is given. Returns the PDF path.
+#[tauri::command]
+pub async fn docgen_preview_theme(
+    theme: Option<DocTheme>,
+    kind: Option<String>,
+    output_path: Option<String>,
+) -> CmdResult<String> {
+    let theme = match theme {
+        Some(t) => {
+            t.validate()?;
+            t
+        }
+        None => resolve_theme(None),
+    };
+    let kind = kind.unwrap_or_else(|| "proposal".to_string());
+    let output = output_path.unwrap_or_else(|| {
+        global_theme_path()
+            .with_file_name(format!("theme-preview-{}.pdf", kind))
+            .to_string_lossy()
+            .to_string()
+    });
+    if let Some(dir) = Path::new(&output).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    tokio::task::spawn_blocking(move || match kind.as_str() {
+        "proposal" => generate_proposal_pdf(SAMPLE_PROPOSAL, &output, &theme),
+        "order_form" => generate_order_form_pdf(&sample_order_form(), &output, &theme),
+        other => Err(CommandError::Config(format!(
+            "Unknown preview kind: {} (expected proposal or order_form)",
+            other
+        ))),
+    })
+    .await
+    .map_err(|e| CommandError::Internal(format!("Preview task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_theme_keeps_builtin_look() {
+        let theme = DocTheme::default();
+        assert_eq!(theme.head_html(), "");
+        assert!(theme.footer_html().is_none());
+        assert!(theme.logo_html(50).contains(r#"alt="ThinkVAL" height="50""#));
+    }
+
+    #[test]
+    fn head_html_skips_unsafe_values() {
+        let theme = DocTheme {
+            primary_color: Some("#123abc".to_string()),
+            accent_color: Some("red;} body { display:none".to_string()),
+            heading_font: Some("'Inter', sans-serif".to_string()),
+            ..Default::default()
+        };
+        let head = theme.head_html();
+        assert!(head.contains("--brand-primary: #123abc;"));
+        assert!(!head.contains("--brand-accent"));
+        assert!(head.contains("--font-heading: 'Inter', sans-serif;"));
+        assert!(theme.validate().is_err());
+    }
+}
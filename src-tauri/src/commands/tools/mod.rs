@@ -2,6 +2,8 @@
 
 pub mod gamma;
 pub mod nanobanana;
+pub mod nanobanana_prompts;
 pub mod docgen;
+pub mod docgen_theme;
 pub mod intercom;
 pub mod seedance;
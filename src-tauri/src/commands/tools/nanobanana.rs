@@ -23,6 +23,9 @@ pub struct NanobananOptions {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reference_images: Option<Vec<ReferenceImage>>,
+    /// e.g. "16:9", "1:1", "9:16" — omitted means the model default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<String>,
 }
 
 impl Default for NanobananOptions {
@@ -30,6 +33,7 @@ impl Default for NanobananOptions {
         Self {
             model: Some(DEFAULT_MODEL.to_string()),
             reference_images: None,
+            aspect_ratio: None,
         }
     }
 }
@@ -76,6 +80,14 @@ struct GeminiInlineData {
 struct GeminiGenerationConfig {
     #[serde(rename = "responseModalities")]
     response_modalities: Vec<String>,
+    #[serde(rename = "imageConfig", skip_serializing_if = "Option::is_none")]
+    image_config: Option<GeminiImageConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiImageConfig {
+    #[serde(rename = "aspectRatio")]
+    aspect_ratio: String,
 }
 
 // Gemini API response types
@@ -171,6 +183,9 @@ pub async fn nanobanana_generate(
         contents: vec![GeminiContent { parts }],
         generation_config: GeminiGenerationConfig {
             response_modalities: vec!["TEXT".to_string(), "IMAGE".to_string()],
+            image_config: opts
+                .aspect_ratio
+                .map(|aspect_ratio| GeminiImageConfig { aspect_ratio }),
         },
     };
 
@@ -0,0 +1,402 @@
+// Nanobanana Prompt Library
+// Reusable image prompt templates with {{variables}}, style presets (brand
+// colors, aspect ratio) and a generation history for reproducibility.
+//
+// Everything lives in the knowledge folder under `_image_prompts/`:
+//   <name>.md          template — frontmatter (description, style, aspect_ratio)
+//                      + prompt body with {{var}} or {{var|default}} placeholders
+//   _styles.json       { "<preset>": StylePreset, ... }  ("default" applies when
+//                      a template names no style)
+//   _history.jsonl     one GenerationRecord per generated image
+
+use super::nanobanana::{nanobanana_generate_to_file, NanobananOptions};
+use crate::commands::error::{CmdResult, CommandError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use tauri::command;
+
+const PROMPTS_DIR: &str = "_image_prompts";
+const STYLES_FILE: &str = "_styles.json";
+const HISTORY_FILE: &str = "_history.jsonl";
+
+static VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_\-]+)\s*(?:\|([^}]*))?\}\}").unwrap());
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub style: Option<String>,
+    pub aspect_ratio: Option<String>,
+    /// Placeholder names in order of first appearance
+    pub variables: Vec<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StylePreset {
+    pub description: Option<String>,
+    /// Hex colors, e.g. ["#0B1F3A", "#F5A623"]
+    pub colors: Vec<String>,
+    pub aspect_ratio: Option<String>,
+    /// Free-text style direction appended to every prompt using this preset
+    pub prompt_suffix: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub prompt: String,
+    pub template: Option<String>,
+    pub style: Option<String>,
+    pub aspect_ratio: Option<String>,
+    pub model: Option<String>,
+    /// Placeholders with no value and no default — left as-is in the prompt
+    pub missing_vars: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationRecord {
+    pub id: String,
+    pub generated_at: String,
+    pub template: Option<String>,
+    pub vars: BTreeMap<String, String>,
+    pub style: Option<String>,
+    pub prompt: String,
+    pub model: Option<String>,
+    pub aspect_ratio: Option<String>,
+    pub output_path: String,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn prompts_dir() -> CmdResult<PathBuf> {
+    let knowledge = crate::commands::settings::load_settings()
+        .ok()
+        .and_then(|s| s.keys.get(crate::commands::settings::KEY_KNOWLEDGE_PATH).cloned())
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| CommandError::Config("Knowledge path is not set in settings".to_string()))?;
+    Ok(PathBuf::from(knowledge).join(PROMPTS_DIR))
+}
+
+fn template_variables(body: &str) -> Vec<String> {
+    let mut vars: Vec<String> = Vec::new();
+    for cap in VAR_RE.captures_iter(body) {
+        let name = cap[1].to_string();
+        if !vars.contains(&name) {
+            vars.push(name);
+        }
+    }
+    vars
+}
+
+fn parse_template(name: &str, path: &std::path::Path, content: &str) -> PromptTemplate {
+    let mut description = None;
+    let mut style = None;
+    let mut aspect_ratio = None;
+    let mut body = content;
+
+    if let Some(rest) = content.strip_prefix("---") {
+        if let Some(end) = rest.find("\n---") {
+            for line in rest[..end].lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"').trim_matches('\'').to_string();
+                if value.is_empty() {
+                    continue;
+                }
+                match key.trim() {
+                    "description" => description = Some(value),
+                    "style" => style = Some(value),
+                    "aspect_ratio" => aspect_ratio = Some(value),
+                    _ => {}
+                }
+            }
+            body = rest[end + 4..].trim_start_matches(['\r', '\n']);
+        }
+    }
+
+    let body = body.trim().to_string();
+    PromptTemplate {
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        description,
+        style,
+        aspect_ratio,
+        variables: template_variables(&body),
+        body,
+    }
+}
+
+/// A template name is a bare file stem inside the prompts folder
+fn is_valid_template_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+fn load_template(name: &str) -> CmdResult<Option<PromptTemplate>> {
+    if !is_valid_template_name(name) {
+        return Err(CommandError::Validation {
+            message: format!("Invalid template name: {}", name),
+            fields: vec!["template".to_string()],
+        });
+    }
+    let path = prompts_dir()?.join(format!("{}.md", name));
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(Some(parse_template(name, &path, &content)))
+}
+
+fn load_styles() -> CmdResult<HashMap<String, StylePreset>> {
+    let path = prompts_dir()?.join(STYLES_FILE);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+}
+
+/// Substitute {{var}} / {{var|default}}; returns the text and unresolved names
+pub fn substitute_vars(body: &str, vars: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut missing = Vec::new();
+    let rendered = VAR_RE
+        .replace_all(body, |cap: &regex::Captures| {
+            let name = &cap[1];
+            match (vars.get(name), cap.get(2)) {
+                (Some(v), _) => v.clone(),
+                (None, Some(default)) => default.as_str().trim().to_string(),
+                (None, None) => {
+                    if !missing.iter().any(|m: &String| m == name) {
+                        missing.push(name.to_string());
+                    }
+                    cap[0].to_string()
+                }
+            }
+        })
+        .into_owned();
+    (rendered, missing)
+}
+
+fn apply_style(prompt: &str, preset: &StylePreset) -> String {
+    let mut out = prompt.trim_end().to_string();
+    if let Some(suffix) = preset.prompt_suffix.as_deref().filter(|s| !s.trim().is_empty()) {
+        out.push_str("\n\nStyle: ");
+        out.push_str(suffix.trim());
+    }
+    if !preset.colors.is_empty() {
+        out.push_str("\nUse this color palette: ");
+        out.push_str(&preset.colors.join(", "));
+    }
+    out
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List prompt templates in `_image_prompts/`
+#[command]
+pub fn nanobanana_list_prompt_templates() -> CmdResult<Vec<PromptTemplate>> {
+    let dir = prompts_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut templates = Vec::new();
+    for entry in fs::read_dir(&dir)?.flatten() {
+        let path = entry.path();
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if stem.starts_with('_') || path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(&path) {
+            templates.push(parse_template(&stem, &path, &content));
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Style presets from `_image_prompts/_styles.json`
+#[command]
+pub fn nanobanana_list_style_presets() -> CmdResult<HashMap<String, StylePreset>> {
+    load_styles()
+}
+
+/// Replace all style presets
+#[command]
+pub fn nanobanana_save_style_presets(presets: HashMap<String, StylePreset>) -> CmdResult<()> {
+    let dir = prompts_dir()?;
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(STYLES_FILE), serde_json::to_string_pretty(&presets)?)?;
+    Ok(())
+}
+
+/// Render a prompt. `template` is a template name from `_image_prompts/`, or
+/// literal prompt text with placeholders if no such template exists. The style
+/// preset is `style`, else the template's `style:`, else the "default" preset.
+#[command]
+pub fn nanobanana_render_prompt(
+    template: String,
+    vars: Option<HashMap<String, String>>,
+    style: Option<String>,
+) -> CmdResult<RenderedPrompt> {
+    let vars = vars.unwrap_or_default();
+    let stored = load_template(template.trim())?;
+    let (body, template_name, template_style, template_aspect) = match &stored {
+        Some(t) => (t.body.as_str(), Some(t.name.clone()), t.style.clone(), t.aspect_ratio.clone()),
+        None => (template.as_str(), None, None, None),
+    };
+
+    let styles = load_styles()?;
+    let style_name = style.or(template_style).or_else(|| {
+        styles.contains_key("default").then(|| "default".to_string())
+    });
+    let preset = match &style_name {
+        Some(name) => Some(
+            styles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| CommandError::NotFound(format!("Style preset not found: {}", name)))?,
+        ),
+        None => None,
+    };
+
+    let (mut prompt, missing_vars) = substitute_vars(body, &vars);
+    if let Some(p) = &preset {
+        prompt = apply_style(&prompt, p);
+    }
+
+    Ok(RenderedPrompt {
+        prompt,
+        template: template_name,
+        aspect_ratio: template_aspect.or_else(|| preset.as_ref().and_then(|p| p.aspect_ratio.clone())),
+        model: preset.as_ref().and_then(|p| p.model.clone()),
+        style: style_name,
+        missing_vars,
+    })
+}
+
+/// Render a template, generate the image, and record it in the history
+#[command]
+pub async fn nanobanana_generate_from_template(
+    api_key: String,
+    template: String,
+    vars: Option<HashMap<String, String>>,
+    style: Option<String>,
+    output_path: String,
+    options: Option<NanobananOptions>,
+) -> CmdResult<GenerationRecord> {
+    let rendered = nanobanana_render_prompt(template, vars.clone(), style)?;
+    if !rendered.missing_vars.is_empty() {
+        return Err(CommandError::Config(format!(
+            "Missing template variables: {}",
+            rendered.missing_vars.join(", ")
+        )));
+    }
+
+    // Explicit options win over the preset/template
+    let mut opts = options.unwrap_or_default();
+    if opts.aspect_ratio.is_none() {
+        opts.aspect_ratio = rendered.aspect_ratio.clone();
+    }
+    if rendered.model.is_some() && opts.model.as_deref() == NanobananOptions::default().model.as_deref() {
+        opts.model = rendered.model.clone();
+    }
+    let model = opts.model.clone();
+    let aspect_ratio = opts.aspect_ratio.clone();
+
+    let saved_path = nanobanana_generate_to_file(api_key, rendered.prompt.clone(), output_path, Some(opts)).await?;
+
+    let now = chrono::Utc::now();
+    let record = GenerationRecord {
+        id: format!("img_{}", now.format("%Y%m%d%H%M%S%3f")),
+        generated_at: now.to_rfc3339(),
+        template: rendered.template,
+        vars: vars.unwrap_or_default().into_iter().collect(),
+        style: rendered.style,
+        prompt: rendered.prompt,
+        model,
+        aspect_ratio,
+        output_path: saved_path,
+    };
+
+    let dir = prompts_dir()?;
+    fs::create_dir_all(&dir)?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(HISTORY_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+
+    Ok(record)
+}
+
+/// Generation history, newest first
+#[command]
+pub fn nanobanana_list_history(limit: Option<usize>) -> CmdResult<Vec<GenerationRecord>> {
+    let path = prompts_dir()?.join(HISTORY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut records: Vec<GenerationRecord> = fs::read_to_string(&path)?
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    records.reverse();
+    records.truncate(limit.unwrap_or(100));
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_names_stay_inside_the_prompts_folder() {
+        assert!(is_valid_template_name("hero-shot"));
+        assert!(!is_valid_template_name("../secrets"));
+        assert!(!is_valid_template_name("sub/hero"));
+        assert!(!is_valid_template_name("..\\hero"));
+        assert!(!is_valid_template_name(""));
+    }
+
+    #[test]
+    fn substitutes_values_defaults_and_reports_missing() {
+        let vars: HashMap<String, String> = [("product".to_string(), "VAL".to_string())].into();
+        let (out, missing) = substitute_vars(
+            "Hero shot of {{ product }} on a {{surface|marble desk}} for {{audience}}",
+            &vars,
+        );
+        assert_eq!(out, "Hero shot of VAL on a marble desk for {{audience}}");
+        assert_eq!(missing, vec!["audience"]);
+    }
+
+    #[test]
+    fn parses_frontmatter_and_variables() {
+        let t = parse_template(
+            "hero",
+            std::path::Path::new("/kb/_image_prompts/hero.md"),
+            "---\ndescription: Landing hero\nstyle: brand\naspect_ratio: \"16:9\"\n---\n\n{{a}} and {{b}} then {{a}}\n",
+        );
+        assert_eq!(t.style.as_deref(), Some("brand"));
+        assert_eq!(t.aspect_ratio.as_deref(), Some("16:9"));
+        assert_eq!(t.variables, vec!["a", "b"]);
+        assert_eq!(t.body, "{{a}} and {{b}} then {{a}}");
+    }
+}
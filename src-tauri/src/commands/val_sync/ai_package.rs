@@ -561,7 +561,7 @@ pub struct AiTableCoverageResult {
 }
 
 /// Recursively flatten all tables from all_tables.json tree structure.
-fn flatten_domain_tables(nodes: &[serde_json::Value]) -> Vec<TableEntry> {
+pub(super) fn flatten_domain_tables(nodes: &[serde_json::Value]) -> Vec<TableEntry> {
     let mut tables = Vec::new();
     for node in nodes {
         let node_type = node.get("type").and_then(|t| t.as_str()).unwrap_or("");
@@ -55,7 +55,7 @@ impl ValApiError {
 /// - `base_url`: e.g. "https://koi.thinkval.io"
 /// - `token`: JWT auth token
 /// - `artifact_type`: one of: fields, all-queries, all-workflows, all-dashboards,
-///   all-tables, calc-fields, data-model, workflow, dashboard, query
+///   all-tables, all-integrations, calc-fields, data-model, workflow, dashboard, query
 /// - `id`: required for single-item fetches (data-model, workflow, dashboard, query)
 pub async fn val_api_fetch(
     base_url: &str,
@@ -96,6 +96,12 @@ pub async fn val_api_fetch(
             vec![],
             None,
         ),
+        "all-integrations" => (
+            "GET",
+            "/api/v1/integration/".to_string(),
+            vec![],
+            None,
+        ),
         "calc-fields" => (
             "POST",
             "/db/settings/customGetAdminUiSettings".to_string(),
@@ -0,0 +1,234 @@
+// VAL Sync Calc Fields - Validate edits to a calculated field's rules
+// Pull (local all_calculated_fields.json) → validate → diff. VAL has no
+// documented endpoint for writing calc-field settings (only the
+// customGetAdminUiSettings read), so the change itself is applied in the VAL
+// admin UI and picked up by the next calc-fields sync.
+
+use super::config::get_domain_config;
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use tauri::command;
+
+/// Rule types that pull from another table and so need table/column
+const LOOKUP_RULES: [&str; 2] = ["vlookup", "rollup"];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalcFieldUpdateResult {
+    pub domain: String,
+    pub table: String,
+    pub field: String,
+    pub rule_name: String,
+    /// Top-level rule keys whose values changed
+    pub changed_keys: Vec<String>,
+    pub before: Value,
+    /// The validated rules, with identity keys filled in
+    pub after: Value,
+    pub message: String,
+}
+
+// ============================================================================
+// Internal helpers
+// ============================================================================
+
+/// Find the calc field entry for `table` whose db_column_name or display name is `field`
+fn find_calc_field(items: &[Value], table: &str, field: &str) -> Option<usize> {
+    items.iter().position(|item| {
+        let table_match = item.get("id").and_then(|v| v.as_str()) == Some(table)
+            || item.get("temp_id").and_then(|v| v.as_str()) == Some(table);
+        if !table_match {
+            return false;
+        }
+        let rule_field = item.get("settings").and_then(|s| s.get("ruleField"));
+        let column = rule_field
+            .and_then(|rf| rf.get("rules"))
+            .and_then(|r| r.get("db_column_name"))
+            .and_then(|v| v.as_str());
+        let name = rule_field.and_then(|rf| rf.get("name")).and_then(|v| v.as_str());
+        column == Some(field) || name == Some(field)
+    })
+}
+
+/// Validate new rules against the existing rule's shape. Fills in
+/// `db_column_name` / `rule_name` when omitted so callers can send just the
+/// keys they're changing on top of a copy of the current rules.
+pub fn validate_rules(existing: &Value, new_rules: &mut Value) -> Result<(), String> {
+    let new_obj = new_rules
+        .as_object_mut()
+        .ok_or_else(|| "Rules must be a JSON object".to_string())?;
+    let existing_obj = existing.as_object().cloned().unwrap_or_default();
+
+    for key in ["db_column_name", "rule_name"] {
+        let current = existing_obj.get(key).and_then(|v| v.as_str());
+        match (new_obj.get(key), current) {
+            (None, Some(cur)) => {
+                new_obj.insert(key.to_string(), Value::String(cur.to_string()));
+            }
+            (Some(Value::String(new)), Some(cur)) if new != cur => {
+                return Err(format!(
+                    "'{}' cannot change ({} → {}); create a new field instead",
+                    key, cur, new
+                ));
+            }
+            (Some(v), _) if !v.is_string() => return Err(format!("'{}' must be a string", key)),
+            _ => {}
+        }
+    }
+
+    let rule_name = new_obj
+        .get("rule_name")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "'rule_name' is required".to_string())?
+        .to_string();
+
+    if LOOKUP_RULES.contains(&rule_name.as_str()) {
+        for key in ["table", "column"] {
+            match new_obj.get(key) {
+                Some(Value::String(s)) if !s.is_empty() => {}
+                _ => return Err(format!("{} rules need a non-empty '{}'", rule_name, key)),
+            }
+        }
+    }
+
+    if let Some(data) = new_obj.get("data") {
+        let arr = data.as_array().ok_or_else(|| "'data' must be an array".to_string())?;
+        if arr.iter().any(|d| !d.is_object()) {
+            return Err("'data' entries must be objects".to_string());
+        }
+    }
+
+    // Keys present before must keep their JSON type — catches "5" vs 5 style slips
+    for (key, old) in &existing_obj {
+        if let Some(new) = new_obj.get(key) {
+            let same_kind = std::mem::discriminant(old) == std::mem::discriminant(new)
+                || old.is_null()
+                || new.is_null();
+            if !same_kind {
+                return Err(format!("'{}' changed type (was {}, now {})", key, type_name(old), type_name(new)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn changed_keys(before: &Value, after: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let b = before.as_object().unwrap_or(&empty);
+    let a = after.as_object().unwrap_or(&empty);
+    let mut keys: Vec<String> = b
+        .keys()
+        .chain(a.keys())
+        .filter(|k| b.get(*k) != a.get(*k))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Validate new rules for a calculated field and diff them against the
+/// current ones.
+///
+/// `field` is the field's db_column_name (or display name). `new_rules` replaces
+/// the ruleField.rules object; omitted `db_column_name`/`rule_name` are kept.
+/// Nothing is written to VAL (see the module comment).
+#[command]
+pub async fn val_update_calc_field(
+    domain: String,
+    table: String,
+    field: String,
+    new_rules: Value,
+) -> CmdResult<CalcFieldUpdateResult> {
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = domain_config.global_path.clone();
+
+    // Pull: start from the freshest local copy
+    let source = format!("{}/schema/all_calculated_fields.json", global_path);
+    let data: Value = serde_json::from_str(&fs::read_to_string(&source).map_err(|_| {
+        CommandError::NotFound(format!("{} not found — run calc-fields sync first", source))
+    })?)?;
+    let items = data
+        .get("data")
+        .and_then(|d| d.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let idx = find_calc_field(&items, &table, &field)
+        .ok_or_else(|| CommandError::NotFound(format!("Calc field '{}' not found on table {}", field, table)))?;
+    let before = items[idx]
+        .pointer("/settings/ruleField/rules")
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    let mut after = new_rules;
+    validate_rules(&before, &mut after).map_err(|e| CommandError::Config(format!("Invalid rules: {}", e)))?;
+
+    let rule_name = after.get("rule_name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let changed_keys = changed_keys(&before, &after);
+    let message = if changed_keys.is_empty() {
+        "No changes".to_string()
+    } else {
+        format!(
+            "{} key(s) valid to change — apply them in the VAL admin UI, then re-run calc-fields sync",
+            changed_keys.len()
+        )
+    };
+
+    Ok(CalcFieldUpdateResult {
+        domain,
+        table,
+        field,
+        rule_name,
+        changed_keys,
+        before,
+        after,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_identity_keys_and_rejects_type_changes() {
+        let existing = json!({ "rule_name": "vlookup", "db_column_name": "cust_tier", "table": "t1", "column": "id", "limit": 5 });
+
+        let mut ok = json!({ "table": "t2", "column": "id", "limit": 10 });
+        assert!(validate_rules(&existing, &mut ok).is_ok());
+        assert_eq!(ok["db_column_name"], "cust_tier");
+        assert_eq!(ok["rule_name"], "vlookup");
+
+        let mut renamed = json!({ "db_column_name": "other", "table": "t1", "column": "id" });
+        assert!(validate_rules(&existing, &mut renamed).is_err());
+
+        let mut wrong_type = json!({ "table": "t1", "column": "id", "limit": "10" });
+        assert!(validate_rules(&existing, &mut wrong_type).is_err());
+
+        let mut missing_table = json!({ "column": "id" });
+        assert!(validate_rules(&existing, &mut missing_table).is_err());
+    }
+}
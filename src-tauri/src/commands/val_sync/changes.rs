@@ -0,0 +1,280 @@
+// VAL Sync Changes - Artifact ownership + change attribution
+// Extractors log every definition they (re)write so environment reviews can
+// see what changed in VAL and who touched it.
+
+use super::config::get_domain_config;
+use crate::commands::error::CmdResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use tauri::command;
+
+/// Change log is trimmed to this many entries on append
+const MAX_LOG_ENTRIES: usize = 5000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Who created / last changed an artifact, as far as the VAL API reports it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactOwnership {
+    pub created_by: Option<String>,
+    pub updated_by: Option<String>,
+    pub created_date: Option<String>,
+    pub updated_date: Option<String>,
+}
+
+impl ArtifactOwnership {
+    pub fn is_empty(&self) -> bool {
+        self.created_by.is_none()
+            && self.updated_by.is_none()
+            && self.created_date.is_none()
+            && self.updated_date.is_none()
+    }
+}
+
+/// One line of `changes/artifact_changes.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactChange {
+    pub artifact_type: String, // query | workflow | dashboard | table
+    pub artifact_id: String,
+    pub name: Option<String>,
+    pub change: String, // created | updated
+    #[serde(flatten)]
+    pub ownership: ArtifactOwnership,
+    /// Start of the extract run that picked the change up
+    pub run_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeAuthorSummary {
+    pub user: String,
+    pub count: usize,
+    pub artifact_types: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentChangesReport {
+    pub domain: String,
+    /// Cut-off used, or None when reporting each type's latest extract run
+    pub since: Option<String>,
+    pub total: usize,
+    pub by_author: Vec<ChangeAuthorSummary>,
+    pub by_type: BTreeMap<String, usize>,
+    pub changes: Vec<ArtifactChange>,
+}
+
+// ============================================================================
+// Ownership extraction
+// ============================================================================
+
+/// User fields come back as a name, an email, a numeric id, or a nested user object
+fn user_value(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(o) => ["name", "full_name", "email", "username", "id"]
+            .iter()
+            .find_map(|k| o.get(*k).and_then(user_value)),
+        _ => None,
+    }
+}
+
+fn first_of(item: &Value, keys: &[&str], read: fn(&Value) -> Option<String>) -> Option<String> {
+    keys.iter().find_map(|k| item.get(*k).and_then(read))
+}
+
+fn date_value(v: &Value) -> Option<String> {
+    v.as_str().filter(|s| !s.is_empty()).map(String::from)
+}
+
+/// Pull created/updated attribution from a raw VAL artifact
+pub fn ownership_from(item: &Value) -> ArtifactOwnership {
+    ArtifactOwnership {
+        created_by: first_of(item, &["created_by", "createdBy", "created_by_name", "creator", "owner"], user_value),
+        updated_by: first_of(
+            item,
+            &["updated_by", "updatedBy", "updated_by_name", "modified_by", "last_modified_by"],
+            user_value,
+        ),
+        created_date: first_of(item, &["created_date", "created_at", "createdAt"], date_value),
+        updated_date: first_of(item, &["updated_date", "updated_at", "updatedAt", "modified_date"], date_value),
+    }
+}
+
+/// Copy of the artifact with an `_ownership` block added, so extracts carry
+/// attribution in a stable shape regardless of how VAL named the fields
+pub fn with_ownership(item: &Value, ownership: &ArtifactOwnership) -> Value {
+    let mut out = item.clone();
+    if ownership.is_empty() {
+        return out;
+    }
+    if let (Some(obj), Ok(block)) = (out.as_object_mut(), serde_json::to_value(ownership)) {
+        obj.insert("_ownership".to_string(), block);
+    }
+    out
+}
+
+// ============================================================================
+// Change log
+// ============================================================================
+
+fn log_path(global_path: &str) -> String {
+    format!("{}/changes/artifact_changes.jsonl", global_path)
+}
+
+fn read_log(global_path: &str) -> Vec<ArtifactChange> {
+    fs::read_to_string(log_path(global_path))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Append changes from one extract run, trimming the log to MAX_LOG_ENTRIES
+pub fn record_changes(global_path: &str, changes: &[ArtifactChange]) -> CmdResult<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let path = log_path(global_path);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let existing = read_log(global_path);
+    if existing.len() + changes.len() > MAX_LOG_ENTRIES {
+        let keep = MAX_LOG_ENTRIES.saturating_sub(changes.len());
+        let mut content = String::new();
+        for entry in existing[existing.len().saturating_sub(keep)..].iter().chain(changes) {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        fs::write(&path, content)?;
+        return Ok(());
+    }
+
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    for entry in changes {
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    }
+    Ok(())
+}
+
+/// Keep changes after `since` (by VAL's updated date when known, else the run
+/// time). Without a cut-off, keep each artifact type's latest run only.
+fn select_recent(changes: Vec<ArtifactChange>, since: Option<&str>) -> Vec<ArtifactChange> {
+    match since {
+        Some(since) => changes
+            .into_iter()
+            .filter(|c| c.ownership.updated_date.as_deref().unwrap_or(&c.run_at) >= since)
+            .collect(),
+        None => {
+            let mut latest: HashMap<String, String> = HashMap::new();
+            for c in &changes {
+                let entry = latest.entry(c.artifact_type.clone()).or_default();
+                if c.run_at > *entry {
+                    *entry = c.run_at.clone();
+                }
+            }
+            changes
+                .into_iter()
+                .filter(|c| latest.get(&c.artifact_type) == Some(&c.run_at))
+                .collect()
+        }
+    }
+}
+
+fn summarize(domain: &str, since: Option<String>, mut changes: Vec<ArtifactChange>) -> RecentChangesReport {
+    changes.sort_by(|a, b| b.run_at.cmp(&a.run_at).then_with(|| b.ownership.updated_date.cmp(&a.ownership.updated_date)));
+
+    let mut authors: HashMap<String, ChangeAuthorSummary> = HashMap::new();
+    let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+    for c in &changes {
+        *by_type.entry(c.artifact_type.clone()).or_default() += 1;
+        let user = c
+            .ownership
+            .updated_by
+            .clone()
+            .or_else(|| c.ownership.created_by.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let summary = authors.entry(user.clone()).or_insert_with(|| ChangeAuthorSummary {
+            user,
+            count: 0,
+            artifact_types: BTreeMap::new(),
+        });
+        summary.count += 1;
+        *summary.artifact_types.entry(c.artifact_type.clone()).or_default() += 1;
+    }
+    let mut by_author: Vec<ChangeAuthorSummary> = authors.into_values().collect();
+    by_author.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.user.cmp(&b.user)));
+
+    RecentChangesReport {
+        domain: domain.to_string(),
+        since,
+        total: changes.len(),
+        by_author,
+        by_type,
+        changes,
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Summarize which artifacts changed and by whom. `since` is an ISO date or
+/// timestamp; omit it to report what the most recent extract runs picked up.
+#[command]
+pub fn val_get_recent_changes(domain: String, since: Option<String>) -> CmdResult<RecentChangesReport> {
+    let domain_config = get_domain_config(&domain)?;
+    let since = since.filter(|s| !s.trim().is_empty());
+    let changes = select_recent(read_log(&domain_config.global_path), since.as_deref());
+    Ok(summarize(&domain, since, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(kind: &str, id: &str, by: Option<&str>, run_at: &str) -> ArtifactChange {
+        ArtifactChange {
+            artifact_type: kind.to_string(),
+            artifact_id: id.to_string(),
+            name: None,
+            change: "updated".to_string(),
+            ownership: ArtifactOwnership { updated_by: by.map(String::from), ..Default::default() },
+            run_at: run_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_ownership_variants_and_groups_latest_run_by_author() {
+        let o = ownership_from(&json!({
+            "createdBy": { "email": "ana@example.com" },
+            "updated_by": 42,
+            "updated_date": "2026-10-01T09:00:00Z"
+        }));
+        assert_eq!(o.created_by.as_deref(), Some("ana@example.com"));
+        assert_eq!(o.updated_by.as_deref(), Some("42"));
+
+        let log = vec![
+            change("query", "1", Some("ana"), "2026-10-01T00:00:00Z"),
+            change("query", "2", Some("ben"), "2026-10-02T00:00:00Z"),
+            change("query", "3", Some("ben"), "2026-10-02T00:00:00Z"),
+            change("table", "t", None, "2026-09-01T00:00:00Z"),
+        ];
+        let report = summarize("lab", None, select_recent(log, None));
+        assert_eq!(report.total, 3);
+        assert_eq!(report.by_author[0].user, "ben");
+        assert_eq!(report.by_author[0].count, 2);
+        assert_eq!(report.by_author[1].user, "unknown");
+    }
+}
@@ -0,0 +1,469 @@
+// VAL Context Pack - Compact, size-budgeted domain context for AI agents.
+// Assembles table summaries (overview.md frontmatter + metadata table), key
+// categorical values (definition_categorical.json) and the workflow list
+// (schema/all_workflows.json) into one markdown/JSON pack. Sections are added
+// in priority order — tables, workflows, categoricals — until the token budget
+// is spent; whatever didn't fit is counted under `omitted`.
+
+use super::config::get_domain_config;
+use super::presence::matches_pattern;
+use super::sync::write_json;
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+const DEFAULT_TOKEN_BUDGET: usize = 8000;
+/// Distinct values listed per categorical column
+const MAX_VALUES_PER_COLUMN: usize = 12;
+/// Longer categorical values are cut to this many characters
+const MAX_VALUE_CHARS: usize = 40;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackTable {
+    pub table_id: String,
+    pub title: String,
+    pub summary: String,
+    pub table_type: Option<String>,
+    pub category: Option<String>,
+    pub usage_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackCategorical {
+    pub table_id: String,
+    pub column: String,
+    pub distinct_count: usize,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackWorkflow {
+    pub id: Option<i64>,
+    pub name: String,
+    pub schedule: Option<String>,
+    pub last_status: Option<String>,
+    pub target_tables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackOmitted {
+    pub tables: usize,
+    pub workflows: usize,
+    pub categoricals: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPack {
+    pub domain: String,
+    pub scope: String,
+    pub table_pattern: Option<String>,
+    pub generated_at: String,
+    pub token_budget: usize,
+    pub estimated_tokens: usize,
+    pub tables: Vec<PackTable>,
+    pub workflows: Vec<PackWorkflow>,
+    pub categoricals: Vec<PackCategorical>,
+    pub omitted: PackOmitted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPackResult {
+    pub json_path: String,
+    pub markdown_path: String,
+    pub estimated_tokens: usize,
+    pub token_budget: usize,
+    pub omitted: PackOmitted,
+    /// The pack itself, ready to drop into a prompt
+    pub markdown: String,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Rough token count (~4 characters per token for English/markdown)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+struct Budget {
+    max: usize,
+    used: usize,
+}
+
+impl Budget {
+    /// Reserve room for `text`; false (and nothing reserved) if it doesn't fit
+    fn take(&mut self, text: &str) -> bool {
+        let tokens = estimate_tokens(text);
+        if self.used + tokens > self.max {
+            return false;
+        }
+        self.used += tokens;
+        true
+    }
+}
+
+/// Pull the pack fields out of an overview.md: title/summary from frontmatter,
+/// type/category/usage from the `| **Property** | Value |` metadata table
+pub fn parse_overview(table_id: &str, content: &str) -> PackTable {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut lines = content.lines();
+
+    if content.starts_with("---") {
+        lines.next();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim().trim_matches('"').replace("\\\"", "\"");
+                fields.insert(key.trim().to_lowercase(), value);
+            }
+        }
+    }
+    for line in lines {
+        let parts: Vec<&str> = line.trim().split('|').collect();
+        if parts.len() < 4 || !parts[1].contains("**") {
+            continue;
+        }
+        let key = parts[1].trim().replace("**", "").to_lowercase();
+        fields.entry(key).or_insert_with(|| parts[2].trim().to_string());
+    }
+
+    let get = |key: &str| fields.get(key).filter(|v| !v.is_empty() && v.as_str() != "Unknown").cloned();
+    PackTable {
+        table_id: table_id.to_string(),
+        title: get("title").unwrap_or_else(|| table_id.to_string()),
+        summary: get("summary").unwrap_or_default(),
+        table_type: get("table type"),
+        category: get("data category"),
+        usage_status: get("usage status"),
+    }
+}
+
+pub(super) fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Table folders under data_models/ (table_{id}) matching the pattern
+pub(super) fn table_folders(global_path: &Path, pattern: &str) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(global_path.join("data_models")) else {
+        return Vec::new();
+    };
+    let mut out: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let id = name.strip_prefix("table_")?.to_string();
+            Some((id, e.path()))
+        })
+        .filter(|(id, _)| matches_pattern(pattern, id))
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+fn load_categoricals(table_id: &str, folder: &Path) -> Vec<PackCategorical> {
+    let Some(data) = read_json(&folder.join("definition_categorical.json")) else {
+        return Vec::new();
+    };
+    let Some(columns) = data["columns"].as_object() else {
+        return Vec::new();
+    };
+    let mut out: Vec<PackCategorical> = columns
+        .iter()
+        .filter(|(_, stat)| stat["isCategorical"].as_bool() == Some(true))
+        .filter_map(|(name, stat)| {
+            let values: Vec<String> = stat["distinctValues"]
+                .as_array()?
+                .iter()
+                .filter_map(|v| v.as_str())
+                .take(MAX_VALUES_PER_COLUMN)
+                .map(|v| v.chars().take(MAX_VALUE_CHARS).collect())
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+            let distinct_count = stat["distinctCount"].as_u64().map(|n| n as usize).unwrap_or(values.len());
+            Some(PackCategorical {
+                table_id: table_id.to_string(),
+                column: stat["displayName"].as_str().unwrap_or(name).to_string(),
+                distinct_count,
+                values,
+            })
+        })
+        .collect();
+    out.sort_by(|a, b| a.column.cmp(&b.column));
+    out
+}
+
+fn load_workflows(global_path: &Path) -> Vec<PackWorkflow> {
+    let Some(data) = read_json(&global_path.join("schema").join("all_workflows.json")) else {
+        return Vec::new();
+    };
+    let mut out: Vec<PackWorkflow> = data["data"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .map(|wf| {
+                    let mut targets: Vec<String> = wf["data"]["workflow"]["plugins"]
+                        .as_array()
+                        .map(|plugins| {
+                            plugins
+                                .iter()
+                                .filter_map(|p| p["params"]["target"]["table"].as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    targets.sort();
+                    targets.dedup();
+                    PackWorkflow {
+                        id: wf["id"].as_i64(),
+                        name: wf["name"].as_str().unwrap_or("(unnamed)").to_string(),
+                        schedule: wf["cron_expression"].as_str().filter(|c| !c.is_empty()).map(String::from),
+                        last_status: wf["latest_run_status"].as_str().map(String::from),
+                        target_tables: targets,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Scheduled workflows first — they're the ones that keep data moving
+    out.sort_by(|a, b| b.schedule.is_some().cmp(&a.schedule.is_some()).then(a.name.cmp(&b.name)));
+    out
+}
+
+fn table_line(t: &PackTable) -> String {
+    let tags: Vec<&str> = [&t.table_type, &t.category, &t.usage_status]
+        .into_iter()
+        .filter_map(|v| v.as_deref())
+        .collect();
+    let mut line = format!("- `{}` **{}**", t.table_id, t.title);
+    if !tags.is_empty() {
+        line.push_str(&format!(" [{}]", tags.join(" · ")));
+    }
+    if !t.summary.is_empty() {
+        line.push_str(&format!(" — {}", t.summary));
+    }
+    line.push('\n');
+    line
+}
+
+fn workflow_line(w: &PackWorkflow) -> String {
+    let mut line = format!("- {}", w.name);
+    if let Some(id) = w.id {
+        line.push_str(&format!(" (#{})", id));
+    }
+    if let Some(cron) = &w.schedule {
+        line.push_str(&format!(" · `{}`", cron));
+    }
+    if let Some(status) = &w.last_status {
+        line.push_str(&format!(" · last: {}", status));
+    }
+    if !w.target_tables.is_empty() {
+        line.push_str(&format!(" → {}", w.target_tables.join(", ")));
+    }
+    line.push('\n');
+    line
+}
+
+fn categorical_line(c: &PackCategorical) -> String {
+    let more = if c.distinct_count > c.values.len() {
+        format!(", … (+{})", c.distinct_count - c.values.len())
+    } else {
+        String::new()
+    };
+    format!("- `{}`.{}: {}{}\n", c.table_id, c.column, c.values.join(", "), more)
+}
+
+fn default_output_dir(domain: &str) -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val")
+        .join("context_packs")
+        .join(domain)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Build a token-budgeted context pack for a domain.
+///
+/// `scope` is "all" (default), "tables" (summaries + categoricals) or
+/// "workflows"; `table_pattern` narrows tables (wildcard `*`). Writes
+/// context_pack.json and context_pack.md and returns the markdown.
+#[command]
+pub async fn val_generate_context_pack(
+    domain: String,
+    scope: Option<String>,
+    table_pattern: Option<String>,
+    max_tokens: Option<usize>,
+    output_dir: Option<String>,
+) -> CmdResult<ContextPackResult> {
+    let scope = scope.unwrap_or_else(|| "all".to_string());
+    let (with_tables, with_workflows) = match scope.as_str() {
+        "all" => (true, true),
+        "tables" => (true, false),
+        "workflows" => (false, true),
+        other => {
+            return Err(CommandError::Config(format!(
+                "Unknown scope: {} (expected all, tables or workflows)",
+                other
+            )))
+        }
+    };
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = Path::new(&domain_config.global_path);
+    let pattern = table_pattern.clone().unwrap_or_else(|| "*".to_string());
+    let token_budget = max_tokens.unwrap_or(DEFAULT_TOKEN_BUDGET);
+
+    let folders = if with_tables { table_folders(global_path, &pattern) } else { Vec::new() };
+    let tables: Vec<PackTable> = folders
+        .iter()
+        .filter_map(|(id, folder)| {
+            let content = fs::read_to_string(folder.join("overview.md")).ok()?;
+            Some(parse_overview(id, &content))
+        })
+        .collect();
+    let workflows = if with_workflows { load_workflows(global_path) } else { Vec::new() };
+    let categoricals: Vec<PackCategorical> = folders
+        .iter()
+        .flat_map(|(id, folder)| load_categoricals(id, folder))
+        .collect();
+
+    let mut budget = Budget { max: token_budget, used: 0 };
+    let mut omitted = PackOmitted::default();
+    let mut md = format!(
+        "# {} — domain context\n\nScope: {} · generated {}\n\n",
+        domain,
+        scope,
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+    budget.take(&md);
+
+    let mut packed_tables = Vec::new();
+    if !tables.is_empty() {
+        let header = format!("## Tables ({})\n\n", tables.len());
+        if budget.take(&header) {
+            md.push_str(&header);
+        }
+        for t in tables {
+            let line = table_line(&t);
+            if budget.take(&line) {
+                md.push_str(&line);
+                packed_tables.push(t);
+            } else {
+                omitted.tables += 1;
+            }
+        }
+        md.push('\n');
+    }
+
+    let mut packed_workflows = Vec::new();
+    if !workflows.is_empty() {
+        let header = format!("## Workflows ({})\n\n", workflows.len());
+        if budget.take(&header) {
+            md.push_str(&header);
+        }
+        for w in workflows {
+            let line = workflow_line(&w);
+            if budget.take(&line) {
+                md.push_str(&line);
+                packed_workflows.push(w);
+            } else {
+                omitted.workflows += 1;
+            }
+        }
+        md.push('\n');
+    }
+
+    let mut packed_categoricals = Vec::new();
+    if !categoricals.is_empty() {
+        let header = "## Categorical values\n\n";
+        if budget.take(header) {
+            md.push_str(header);
+        }
+        for c in categoricals {
+            let line = categorical_line(&c);
+            if budget.take(&line) {
+                md.push_str(&line);
+                packed_categoricals.push(c);
+            } else {
+                omitted.categoricals += 1;
+            }
+        }
+        md.push('\n');
+    }
+
+    if omitted.tables + omitted.workflows + omitted.categoricals > 0 {
+        md.push_str(&format!(
+            "_Truncated to fit {} tokens: {} tables, {} workflows, {} categorical columns omitted._\n",
+            token_budget, omitted.tables, omitted.workflows, omitted.categoricals
+        ));
+    }
+
+    let estimated_tokens = estimate_tokens(&md);
+    let pack = ContextPack {
+        domain: domain.clone(),
+        scope,
+        table_pattern,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        token_budget,
+        estimated_tokens,
+        tables: packed_tables,
+        workflows: packed_workflows,
+        categoricals: packed_categoricals,
+        omitted: omitted.clone(),
+    };
+
+    let dir = output_dir.map(PathBuf::from).unwrap_or_else(|| default_output_dir(&domain));
+    let json_path = dir.join("context_pack.json").to_string_lossy().to_string();
+    let markdown_path = dir.join("context_pack.md");
+    write_json(&json_path, &serde_json::to_value(&pack)?)?;
+    fs::write(&markdown_path, &md)?;
+
+    Ok(ContextPackResult {
+        json_path,
+        markdown_path: markdown_path.to_string_lossy().to_string(),
+        estimated_tokens,
+        token_budget,
+        omitted,
+        markdown: md,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_overview_frontmatter_and_metadata_table() {
+        let doc = "---\ntitle: \"AP Invoices\"\nsummary: \"Supplier invoices with \\\"due\\\" dates\"\ntags: [data-model]\n---\n\n# AP Invoices\n\n| Property | Value |\n|----------|-------|\n| **Table Type** | Transactional |\n| **Data Category** | Finance |\n| **Data Source** | Unknown |\n";
+        let t = parse_overview("custom_tbl_1_2", doc);
+        assert_eq!(t.title, "AP Invoices");
+        assert_eq!(t.summary, "Supplier invoices with \"due\" dates");
+        assert_eq!(t.table_type.as_deref(), Some("Transactional"));
+        assert_eq!(t.category.as_deref(), Some("Finance"));
+        assert_eq!(t.usage_status, None);
+    }
+
+    #[test]
+    fn budget_rejects_items_that_do_not_fit() {
+        let mut b = Budget { max: 10, used: 0 };
+        assert!(b.take("12345678901234567890")); // 5 tokens
+        assert!(!b.take("123456789012345678901234")); // 6 tokens — over
+        assert!(b.take("1234")); // 1 token
+        assert_eq!(b.used, 6);
+    }
+}
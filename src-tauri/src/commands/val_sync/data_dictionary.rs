@@ -0,0 +1,424 @@
+// VAL Data Dictionary - One export of every documented table's columns.
+// Combines column names/types (definition_details.json), AI column
+// descriptions and table summaries (definition_analysis.json), review state
+// (overview.md frontmatter) and categorical values (definition_categorical.json)
+// into a single CSV, markdown or XLSX file for clients and analysts.
+
+use super::config::{get_domain_config, load_config_internal, DomainConfig};
+use super::context_pack::{parse_overview, read_json, table_folders};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::frontmatter::frontmatter_field;
+use crate::commands::tools::docgen::markdown_table_cell;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Categorical values listed per column (full lists live in definition_categorical.json)
+const MAX_VALUES_PER_COLUMN: usize = 50;
+
+const HEADERS: [&str; 11] = [
+    "Domain",
+    "Table",
+    "Table Name",
+    "Column",
+    "Column Name",
+    "Kind",
+    "Type",
+    "Description",
+    "Categorical Values",
+    "Distinct Values",
+    "Last Reviewed",
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictionaryTable {
+    pub domain: String,
+    pub table_name: String,
+    pub display_name: String,
+    pub summary: String,
+    /// overview.md `last_reviewed`, when someone has signed it off
+    pub last_reviewed: Option<String>,
+    pub columns: Vec<DictionaryColumn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictionaryColumn {
+    pub name: String,
+    pub column: String,
+    /// "system", "data" or "calculated"
+    pub kind: String,
+    pub data_type: String,
+    pub description: String,
+    pub values: Vec<String>,
+    pub distinct_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDictionaryResult {
+    pub format: String,
+    pub file_path: String,
+    pub domains: Vec<String>,
+    pub tables: usize,
+    pub columns: usize,
+    /// Columns with no description in definition_analysis.json
+    pub undescribed_columns: usize,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// `last_reviewed` from overview.md frontmatter, if filled in
+fn last_reviewed(overview: &str) -> Option<String> {
+    frontmatter_field(overview, "last_reviewed")
+}
+
+/// Build one table's dictionary entry from its data_models folder.
+/// Returns None for tables without definition_details.json (not yet documented).
+pub fn load_table(domain: &str, table_name: &str, folder: &Path) -> Option<DictionaryTable> {
+    let details = read_json(&folder.join("definition_details.json"))?;
+    let analysis = read_json(&folder.join("definition_analysis.json")).unwrap_or(Value::Null);
+    let categorical = read_json(&folder.join("definition_categorical.json")).unwrap_or(Value::Null);
+    let overview = fs::read_to_string(folder.join("overview.md")).ok();
+
+    let descriptions = analysis["columnDescriptions"].as_object();
+    let describe = |name: &str, column: &str| -> String {
+        // columnDescriptions is keyed by display name; older runs used the column name
+        descriptions
+            .and_then(|d| d.get(name).or_else(|| d.get(column)))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let mut columns = Vec::new();
+    for kind in ["system", "data", "calculated"] {
+        let Some(list) = details["columns"][kind].as_array() else {
+            continue;
+        };
+        for col in list {
+            let column = col["column"].as_str().unwrap_or_default().to_string();
+            let name = col["name"].as_str().unwrap_or(&column).to_string();
+            let stat = &categorical["columns"][&column];
+            let values: Vec<String> = if stat["isCategorical"].as_bool() == Some(true) {
+                stat["distinctValues"]
+                    .as_array()
+                    .map(|vals| {
+                        vals.iter()
+                            .filter_map(|v| v.as_str())
+                            .take(MAX_VALUES_PER_COLUMN)
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let distinct_count = stat["distinctCount"]
+                .as_u64()
+                .map(|n| n as usize)
+                .or_else(|| stat["distinctValues"].as_array().map(|v| v.len()));
+            columns.push(DictionaryColumn {
+                description: describe(&name, &column),
+                data_type: col["type"]
+                    .as_str()
+                    .or_else(|| col["colType"].as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                kind: kind.to_string(),
+                name,
+                column,
+                values,
+                distinct_count,
+            });
+        }
+    }
+
+    let from_overview = overview.as_deref().map(|o| parse_overview(table_name, o));
+    let summary = analysis["summary"]["short"]
+        .as_str()
+        .or_else(|| analysis["summary"].as_str())
+        .map(String::from)
+        .filter(|s| !s.is_empty())
+        .or_else(|| from_overview.as_ref().map(|o| o.summary.clone()))
+        .unwrap_or_default();
+
+    Some(DictionaryTable {
+        domain: domain.to_string(),
+        table_name: table_name.to_string(),
+        display_name: details["meta"]["displayName"]
+            .as_str()
+            .unwrap_or(table_name)
+            .to_string(),
+        summary,
+        last_reviewed: overview.as_deref().and_then(last_reviewed),
+        columns,
+    })
+}
+
+fn load_domain(config: &DomainConfig) -> Vec<DictionaryTable> {
+    table_folders(Path::new(&config.global_path), "*")
+        .into_iter()
+        .filter_map(|(table_name, folder)| load_table(&config.domain, &table_name, &folder))
+        .collect()
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn row_cells(table: &DictionaryTable, col: &DictionaryColumn) -> [String; 11] {
+    [
+        table.domain.clone(),
+        table.display_name.clone(),
+        table.table_name.clone(),
+        col.name.clone(),
+        col.column.clone(),
+        col.kind.clone(),
+        col.data_type.clone(),
+        col.description.clone(),
+        col.values.join("; "),
+        col.distinct_count.map(|n| n.to_string()).unwrap_or_default(),
+        table.last_reviewed.clone().unwrap_or_default(),
+    ]
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(|c: char| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn render_csv(tables: &[DictionaryTable]) -> String {
+    let mut out = HEADERS.join(",");
+    out.push('\n');
+    for table in tables {
+        for col in &table.columns {
+            let cells = row_cells(table, col);
+            out.push_str(&cells.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub fn render_markdown(tables: &[DictionaryTable], generated_at: &str) -> String {
+    let mut out = format!("# Data Dictionary\n\n*Generated {}*\n", generated_at);
+    let mut current_domain = "";
+    for table in tables {
+        if table.domain != current_domain {
+            current_domain = &table.domain;
+            out.push_str(&format!("\n## {}\n", current_domain));
+        }
+        out.push_str(&format!("\n### {} (`{}`)\n\n", table.display_name, table.table_name));
+        if !table.summary.is_empty() {
+            out.push_str(&format!("{}\n\n", table.summary));
+        }
+        if let Some(date) = &table.last_reviewed {
+            out.push_str(&format!("*Last reviewed: {}*\n\n", date));
+        }
+        out.push_str("| Column | Field | Type | Description | Values |\n");
+        out.push_str("|--------|-------|------|-------------|--------|\n");
+        for col in &table.columns {
+            let values = match col.distinct_count {
+                Some(n) if n > col.values.len() && !col.values.is_empty() => {
+                    format!("{}, … (+{})", col.values.join(", "), n - col.values.len())
+                }
+                _ => col.values.join(", "),
+            };
+            out.push_str(&format!(
+                "| {} | `{}` | {} | {} | {} |\n",
+                markdown_table_cell(&col.name),
+                col.column,
+                markdown_table_cell(&col.data_type),
+                markdown_table_cell(&col.description),
+                markdown_table_cell(&values)
+            ));
+        }
+    }
+    out
+}
+
+fn write_xlsx(tables: &[DictionaryTable], path: &Path) -> CmdResult<()> {
+    use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+    let xlsx_err = |e: XlsxError| CommandError::Io(format!("Failed to write workbook: {}", e));
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Data Dictionary").map_err(xlsx_err)?;
+
+    for (i, title) in HEADERS.iter().enumerate() {
+        sheet.write_string_with_format(0, i as u16, *title, &header).map_err(xlsx_err)?;
+    }
+    let mut row: u32 = 1;
+    for table in tables {
+        for col in &table.columns {
+            for (i, cell) in row_cells(table, col).iter().enumerate() {
+                if !cell.is_empty() {
+                    sheet.write_string(row, i as u16, cell).map_err(xlsx_err)?;
+                }
+            }
+            row += 1;
+        }
+    }
+
+    for (i, width) in [14, 28, 28, 28, 24, 10, 12, 60, 50, 10, 14].iter().enumerate() {
+        sheet.set_column_width(i as u16, *width).map_err(xlsx_err)?;
+    }
+    sheet.set_freeze_panes(1, 0).map_err(xlsx_err)?;
+    if row > 1 {
+        sheet
+            .autofilter(0, 0, row - 1, (HEADERS.len() - 1) as u16)
+            .map_err(xlsx_err)?;
+    }
+    workbook.save(path).map_err(xlsx_err)?;
+    Ok(())
+}
+
+fn default_output_path(domain: &str, ext: &str) -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val")
+        .join("data_dictionaries")
+        .join(format!("data_dictionary_{}.{}", domain, ext))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Export a data dictionary for one domain, or every configured domain with
+/// `domain = "all"`. `format` is "xlsx" (default), "csv" or "md"; the file goes
+/// to `output_path` or ~/.tv-client/val/data_dictionaries/.
+#[command]
+pub async fn val_export_data_dictionary(
+    domain: String,
+    format: Option<String>,
+    output_path: Option<String>,
+) -> CmdResult<DataDictionaryResult> {
+    let format = format.unwrap_or_else(|| "xlsx".to_string()).to_lowercase();
+    let ext = match format.as_str() {
+        "xlsx" | "csv" => format.clone(),
+        "md" | "markdown" => "md".to_string(),
+        other => {
+            return Err(CommandError::Config(format!(
+                "Unknown format: {} (expected xlsx, csv or md)",
+                other
+            )))
+        }
+    };
+
+    let configs = if domain == "all" {
+        load_config_internal()?.domains
+    } else {
+        vec![get_domain_config(&domain)?]
+    };
+    let mut domains: Vec<String> = Vec::new();
+    let mut tables: Vec<DictionaryTable> = Vec::new();
+    for config in &configs {
+        let loaded = load_domain(config);
+        if !loaded.is_empty() {
+            domains.push(config.domain.clone());
+            tables.extend(loaded);
+        }
+    }
+    if tables.is_empty() {
+        return Err(CommandError::NotFound(format!(
+            "No documented tables found for '{}' — run the table pipeline first",
+            domain
+        )));
+    }
+
+    let path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_output_path(&domain, &ext));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    match ext.as_str() {
+        "xlsx" => write_xlsx(&tables, &path)?,
+        "csv" => fs::write(&path, render_csv(&tables))?,
+        _ => {
+            let generated_at = chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string();
+            fs::write(&path, render_markdown(&tables, &generated_at))?
+        }
+    }
+
+    let columns: usize = tables.iter().map(|t| t.columns.len()).sum();
+    let undescribed_columns = tables
+        .iter()
+        .flat_map(|t| &t.columns)
+        .filter(|c| c.description.is_empty())
+        .count();
+
+    Ok(DataDictionaryResult {
+        format: ext,
+        file_path: path.to_string_lossy().to_string(),
+        domains,
+        tables: tables.len(),
+        columns,
+        undescribed_columns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merges_table_docs_into_dictionary_rows() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let write = |name: &str, value: Value| fs::write(dir.join(name), value.to_string()).unwrap();
+        write(
+            "definition_details.json",
+            json!({
+                "meta": { "displayName": "Orders" },
+                "columns": {
+                    "system": [{ "name": "ID", "column": "id", "type": "integer" }],
+                    "data": [{ "name": "Status", "column": "usr_status", "type": "text" }]
+                }
+            }),
+        );
+        write(
+            "definition_analysis.json",
+            json!({
+                "summary": { "short": "Customer orders" },
+                "columnDescriptions": { "Status": "Order state, e.g. \"open\", closed" }
+            }),
+        );
+        write(
+            "definition_categorical.json",
+            json!({ "columns": { "usr_status": {
+                "isCategorical": true, "distinctCount": 2, "distinctValues": ["closed", "open"]
+            } } }),
+        );
+        fs::write(dir.join("overview.md"), "---\ntitle: \"Orders\"\nlast_reviewed: 2026-09-01\n---\n").unwrap();
+
+        let table = load_table("acme", "custom_tbl_1_1", &dir).unwrap();
+
+        assert_eq!(table.display_name, "Orders");
+        assert_eq!(table.summary, "Customer orders");
+        assert_eq!(table.last_reviewed.as_deref(), Some("2026-09-01"));
+        assert_eq!(table.columns.len(), 2);
+        assert_eq!(table.columns[1].kind, "data");
+        assert_eq!(table.columns[1].values, vec!["closed", "open"]);
+
+        let csv = render_csv(&[table]);
+        assert_eq!(
+            csv.lines().nth(2),
+            Some("acme,Orders,custom_tbl_1_1,Status,usr_status,data,text,\"Order state, e.g. \"\"open\"\", closed\",closed; open,2,2026-09-01")
+        );
+    }
+}
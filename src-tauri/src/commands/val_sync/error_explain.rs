@@ -0,0 +1,497 @@
+// VAL Sync Errors - Plain-language explanations
+// Importer and integration errors arrive as raw platform messages (SQL errors,
+// HTTP failures, stack traces). Pattern rules map them to a cause and a
+// suggested fix, and repeated errors (same message once ids, numbers, dates
+// and quoted values are masked) are grouped into incidents with counts.
+// Incidents no rule recognises can be explained by the model on request;
+// those answers are cached by signature.
+
+use super::config::get_domain_config;
+use super::errors::{DATE_COLUMN, DOMAIN_COLUMN};
+use crate::commands::ai::anthropic_messages;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tauri::command;
+
+const MODEL: &str = "claude-haiku-4-5-20251001";
+/// Most incidents sent to the model per call
+const MAX_AI_INCIDENTS: usize = 10;
+/// Longest signature kept (long stack traces differ only in the tail)
+const MAX_SIGNATURE_LEN: usize = 200;
+
+/// Fields holding the message, best first (Supabase copies, then generic names)
+const MESSAGE_KEYS: [&str; 5] = ["error_summary", "error_detail", "error_message", "error", "message"];
+/// Fields naming the importer / connector an error came from
+const SOURCE_KEYS: [&str; 3] = ["importer_name", "connector", "source"];
+/// Fields holding when the error happened
+const TIME_KEYS: [&str; 3] = [DATE_COLUMN, "received_at", "triggered_at"];
+
+static QUOTED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"'[^']*'|"[^"]*"|`[^`]*`"#).unwrap());
+static ID_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b|\b[0-9a-f]{16,}\b").unwrap());
+static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+(?:[.,:/-]\d+)*").unwrap());
+static SPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+// ============================================================================
+// Rules
+// ============================================================================
+
+struct Rule {
+    id: &'static str,
+    category: &'static str,
+    /// Any group matches when all of its (lowercase) fragments appear
+    patterns: &'static [&'static [&'static str]],
+    cause: &'static str,
+    fix: &'static str,
+}
+
+/// Checked in order; the first match wins, so specific rules come first
+const RULES: &[Rule] = &[
+    Rule {
+        id: "duplicate_key",
+        category: "data",
+        patterns: &[&["duplicate key"], &["unique constraint"], &["already exists"]],
+        cause: "Rows repeat a value that must be unique in the target table, often the same file imported twice or a duplicated ID column.",
+        fix: "Remove the duplicate rows, or switch the importer to update existing rows on the key column.",
+    },
+    Rule {
+        id: "foreign_key",
+        category: "data",
+        patterns: &[&["foreign key"]],
+        cause: "Rows point at records that don't exist yet (for example a customer code missing from the customer table).",
+        fix: "Import the referenced records first, or correct the codes in the file.",
+    },
+    Rule {
+        id: "not_null",
+        category: "data",
+        patterns: &[&["null value in column"], &["not-null"], &["cannot be null"], &["required field"], &["is required"]],
+        cause: "A required column is empty in some rows.",
+        fix: "Fill the column in the source file, or give it a default in the importer mapping.",
+    },
+    Rule {
+        id: "missing_column",
+        category: "schema",
+        patterns: &[&["column", "does not exist"], &["unknown column"], &["no such column"], &["missing column"], &["header", "not found"]],
+        cause: "The file's columns don't match what the importer expects: a header was renamed, removed or misspelled.",
+        fix: "Compare the header row with the importer mapping and update whichever changed.",
+    },
+    Rule {
+        id: "date_format",
+        category: "data",
+        patterns: &[&["date/time field value out of range"], &["invalid date"], &["date format"], &["unparseable date"], &["invalid datetime"], &["invalid input syntax for type date"], &["invalid input syntax for type timestamp"]],
+        cause: "Dates are in a format the importer can't read (day/month order, text months, Excel serial numbers).",
+        fix: "Set the date format in the importer, or export dates as YYYY-MM-DD.",
+    },
+    Rule {
+        id: "type_mismatch",
+        category: "data",
+        patterns: &[&["invalid input syntax"], &["cannot convert"], &["could not convert"], &["numeric field overflow"], &["not a valid number"], &["invalid number"], &["out of range for type"]],
+        cause: "A value isn't the type its column expects, e.g. text in a number column or stray currency symbols and thousands separators.",
+        fix: "Clean the offending column in the source file, or map it as text.",
+    },
+    Rule {
+        id: "encoding",
+        category: "file",
+        patterns: &[&["invalid byte sequence"], &["invalid utf-8"], &["invalid utf8"], &["codec can't decode"], &["encoding"]],
+        cause: "The file isn't UTF-8, which is common with CSVs saved from Excel.",
+        fix: "Re-save the file as \"CSV UTF-8\" and send it again.",
+    },
+    Rule {
+        id: "empty_file",
+        category: "file",
+        patterns: &[&["empty file"], &["file is empty"], &["no rows"], &["no data found"], &["no records"]],
+        cause: "The file arrived without any data rows.",
+        fix: "Check the upstream export ran properly and re-send the file.",
+    },
+    Rule {
+        id: "file_missing",
+        category: "file",
+        patterns: &[&["no such file"], &["file not found"], &["sheet not found"], &["worksheet", "not found"], &["path not found"]],
+        cause: "The importer couldn't find the file or sheet it points at: it was moved or renamed, or the sheet tab changed.",
+        fix: "Point the importer at the current file and sheet name.",
+    },
+    Rule {
+        id: "too_large",
+        category: "file",
+        patterns: &[&["payload too large"], &["file too large"], &["exceeds the maximum"], &["too many rows"], &["request entity too large"]],
+        cause: "The file is over the importer's size limit.",
+        fix: "Split the file, or import only new rows each run.",
+    },
+    Rule {
+        id: "auth",
+        category: "access",
+        patterns: &[&["unauthorized"], &["unauthorised"], &["invalid_grant"], &["token expired"], &["expired token"], &["token has expired"], &["invalid credentials"], &["authentication failed"], &["login failed"]],
+        cause: "The integration's credentials were rejected: an expired token, a changed password or revoked access.",
+        fix: "Reconnect the integration in VAL with an account that's still active.",
+    },
+    Rule {
+        id: "permission",
+        category: "access",
+        patterns: &[&["permission denied"], &["access denied"], &["forbidden"], &["insufficient privileges"], &["not permitted"]],
+        cause: "The integration's account can sign in but isn't allowed to read or write that object.",
+        fix: "Grant the account access, or connect with one that has it.",
+    },
+    Rule {
+        id: "rate_limit",
+        category: "transient",
+        patterns: &[&["rate limit"], &["too many requests"], &["quota exceeded"], &["throttl"]],
+        cause: "The source system is rate-limiting requests.",
+        fix: "Usually clears on its own; if it repeats, run the integration less often or off-peak.",
+    },
+    Rule {
+        id: "timeout",
+        category: "transient",
+        patterns: &[&["timed out"], &["timeout"], &["deadline exceeded"], &["etimedout"]],
+        cause: "The source system took too long to answer.",
+        fix: "Usually passes on retry; if it repeats, narrow the date range per run or schedule it off-peak.",
+    },
+    Rule {
+        id: "connection",
+        category: "transient",
+        patterns: &[&["connection refused"], &["econnrefused"], &["connection reset"], &["econnreset"], &["enotfound"], &["getaddrinfo"], &["network is unreachable"], &["service unavailable"], &["bad gateway"]],
+        cause: "The source system couldn't be reached.",
+        fix: "Check the source system is up and that the host or URL in the integration settings is current.",
+    },
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorExplanation {
+    /// data, schema, file, access, transient or unknown
+    pub category: String,
+    pub cause: String,
+    pub fix: String,
+    /// "rule", "ai" or "unknown"
+    pub source: String,
+    pub rule_id: Option<String>,
+}
+
+/// Repeats of one error, grouped by importer/connector and masked message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorIncident {
+    pub signature: String,
+    pub source_name: Option<String>,
+    pub count: usize,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    /// One raw message, as received
+    pub sample: String,
+    pub explanation: ErrorExplanation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorExplainReport {
+    pub domain: String,
+    pub error_type: String,
+    pub file_path: String,
+    pub total_errors: usize,
+    /// Most frequent first
+    pub incidents: Vec<ErrorIncident>,
+    /// Incidents still without an explanation
+    pub unexplained: usize,
+}
+
+// ============================================================================
+// Classification
+// ============================================================================
+
+fn string_field<'a>(row: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().filter_map(|k| row.get(*k).and_then(Value::as_str)).find(|s| !s.trim().is_empty())
+}
+
+/// The error message in a row: known message fields joined, else the
+/// longest text value (the tv domain tables use opaque usr_ column names)
+pub fn error_text(row: &Value) -> String {
+    let known: Vec<&str> = MESSAGE_KEYS
+        .iter()
+        .filter_map(|k| row.get(*k).and_then(Value::as_str))
+        .filter(|s| !s.trim().is_empty())
+        .collect();
+    if !known.is_empty() {
+        return known.join("\n");
+    }
+    row.as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| k.as_str() != DOMAIN_COLUMN && !TIME_KEYS.contains(&k.as_str()))
+        .filter_map(|(_, v)| v.as_str())
+        .max_by_key(|s| s.len())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Mask the parts of a message that differ between repeats of the same error
+pub fn signature(message: &str) -> String {
+    let lower = message.to_lowercase();
+    let masked = QUOTED_RE.replace_all(&lower, "'…'");
+    let masked = ID_RE.replace_all(&masked, "<id>");
+    let masked = NUMBER_RE.replace_all(&masked, "#");
+    let masked = SPACE_RE.replace_all(masked.trim(), " ");
+    masked.chars().take(MAX_SIGNATURE_LEN).collect()
+}
+
+/// Rule-based explanation; `source: "unknown"` when no rule matches
+pub fn explain(message: &str) -> ErrorExplanation {
+    let lower = message.to_lowercase();
+    let rule = RULES
+        .iter()
+        .find(|r| r.patterns.iter().any(|group| group.iter().all(|p| lower.contains(p))));
+    match rule {
+        Some(rule) => ErrorExplanation {
+            category: rule.category.to_string(),
+            cause: rule.cause.to_string(),
+            fix: rule.fix.to_string(),
+            source: "rule".to_string(),
+            rule_id: Some(rule.id.to_string()),
+        },
+        None => ErrorExplanation {
+            category: "unknown".to_string(),
+            cause: "No known pattern matches this error.".to_string(),
+            fix: "Open the raw message; if it keeps recurring, raise it with the platform team.".to_string(),
+            source: "unknown".to_string(),
+            rule_id: None,
+        },
+    }
+}
+
+/// Group error rows into incidents, most frequent first
+pub fn group_incidents(errors: &[Value]) -> Vec<ErrorIncident> {
+    let mut incidents: Vec<ErrorIncident> = Vec::new();
+    let mut index: HashMap<(Option<String>, String), usize> = HashMap::new();
+
+    for row in errors {
+        let message = error_text(row);
+        let source_name = string_field(row, &SOURCE_KEYS).map(String::from);
+        let seen = string_field(row, &TIME_KEYS).map(String::from);
+        let sig = signature(&message);
+
+        let idx = *index.entry((source_name.clone(), sig.clone())).or_insert_with(|| {
+            incidents.push(ErrorIncident {
+                signature: sig,
+                source_name,
+                count: 0,
+                first_seen: None,
+                last_seen: None,
+                explanation: explain(&message),
+                sample: message,
+            });
+            incidents.len() - 1
+        });
+        let incident = &mut incidents[idx];
+        incident.count += 1;
+        if let Some(seen) = seen {
+            // ISO timestamps compare correctly as strings
+            incident.first_seen = Some(match incident.first_seen.take() {
+                Some(first) if first <= seen => first,
+                _ => seen.clone(),
+            });
+            incident.last_seen = Some(match incident.last_seen.take() {
+                Some(last) if last >= seen => last,
+                _ => seen,
+            });
+        }
+    }
+
+    incidents.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| b.last_seen.cmp(&a.last_seen)));
+    incidents
+}
+
+/// Attach an `_explanation` to each error row (objects only) for the synced file
+pub fn annotate_errors(errors: &mut [Value]) {
+    for row in errors.iter_mut() {
+        let explanation = explain(&error_text(row));
+        if let Some(obj) = row.as_object_mut() {
+            obj.insert("_explanation".to_string(), json!(explanation));
+        }
+    }
+}
+
+// ============================================================================
+// AI fallback
+// ============================================================================
+
+fn cache_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val_error_explanations.json")
+}
+
+fn load_cache() -> HashMap<String, ErrorExplanation> {
+    std::fs::read_to_string(cache_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(cache: &HashMap<String, ErrorExplanation>) -> CmdResult<()> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(cache)?)?;
+    Ok(())
+}
+
+const AI_SYSTEM_PROMPT: &str = "You explain data platform errors to operations staff who are not engineers. \
+The errors come from VAL importers (files loaded into tables) and integrations (connectors to external systems). \
+Reply with only a JSON object: {\"category\": one of data|schema|file|access|transient|unknown, \
+\"cause\": one plain sentence on what went wrong, \"fix\": one plain sentence on what to do}.";
+
+async fn ai_explain(api_key: &str, error_type: &str, incident: &ErrorIncident) -> CmdResult<ErrorExplanation> {
+    let user = format!(
+        "{} error{}:\n\n{}",
+        error_type,
+        incident.source_name.as_deref().map(|s| format!(" from {}", s)).unwrap_or_default(),
+        incident.sample.chars().take(4000).collect::<String>()
+    );
+    let text = anthropic_messages(api_key, MODEL, AI_SYSTEM_PROMPT, &user, 400, 0.0).await?;
+    // Tolerate prose or code fences around the object
+    let json_text = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if end > start => &text[start..=end],
+        _ => return Err(CommandError::Parse(format!("No JSON in model reply: {}", text))),
+    };
+    let reply: Value = serde_json::from_str(json_text)?;
+    let field = |k: &str| reply[k].as_str().unwrap_or_default().trim().to_string();
+    if field("cause").is_empty() {
+        return Err(CommandError::Parse("Model reply has no cause".to_string()));
+    }
+    Ok(ErrorExplanation {
+        category: Some(field("category")).filter(|c| !c.is_empty()).unwrap_or_else(|| "unknown".to_string()),
+        cause: field("cause"),
+        fix: field("fix"),
+        source: "ai".to_string(),
+        rule_id: None,
+    })
+}
+
+/// Newest synced file for this error type (files are named {prefix}_{date}.json)
+fn latest_errors_file(global_path: &str, prefix: &str) -> Option<PathBuf> {
+    std::fs::read_dir(Path::new(global_path).join("analytics"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy())
+                .is_some_and(|n| n.starts_with(&format!("{}_", prefix)) && n.ends_with(".json"))
+        })
+        .max()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Incidents from the latest synced importer or integration errors of a domain,
+/// each with a plain-language cause and fix. `error_type` is "importer" or
+/// "integration". With `use_ai`, incidents no rule recognises (up to 10 per
+/// call) are explained by the model; answers are cached per signature.
+#[command]
+pub async fn val_explain_errors(domain: String, error_type: String, use_ai: Option<bool>) -> CmdResult<ErrorExplainReport> {
+    let prefix = match error_type.as_str() {
+        "importer" => "importer_errors",
+        "integration" => "integration_errors",
+        _ => {
+            return Err(CommandError::Validation {
+                message: format!("Unknown error type '{}' (expected importer or integration)", error_type),
+                fields: vec!["error_type".to_string()],
+            })
+        }
+    };
+    let domain_config = get_domain_config(&domain)?;
+    let file = latest_errors_file(&domain_config.global_path, prefix).ok_or_else(|| {
+        CommandError::NotFound(format!("No synced {} errors for '{}'. Run the errors sync first.", error_type, domain))
+    })?;
+    let synced: Value = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+    let errors = synced["errors"].as_array().cloned().unwrap_or_default();
+
+    let mut incidents = group_incidents(&errors);
+
+    let mut cache = load_cache();
+    for incident in incidents.iter_mut().filter(|i| i.explanation.source == "unknown") {
+        if let Some(cached) = cache.get(&incident.signature) {
+            incident.explanation = cached.clone();
+        }
+    }
+
+    if use_ai.unwrap_or(false) {
+        let api_key = settings::settings_get_anthropic_key()?
+            .ok_or_else(|| CommandError::Config("Anthropic API key not configured. Add it in Settings.".to_string()))?;
+        let pending = incidents
+            .iter_mut()
+            .filter(|i| i.explanation.source == "unknown")
+            .take(MAX_AI_INCIDENTS);
+        for incident in pending {
+            match ai_explain(&api_key, &error_type, incident).await {
+                Ok(explanation) => {
+                    cache.insert(incident.signature.clone(), explanation.clone());
+                    incident.explanation = explanation;
+                }
+                // One bad reply shouldn't sink the report
+                Err(e) => eprintln!("[val:errors] AI explanation failed for '{}': {}", incident.signature, e),
+            }
+        }
+        save_cache(&cache)?;
+    }
+
+    let unexplained = incidents.iter().filter(|i| i.explanation.source == "unknown").count();
+    Ok(ErrorExplainReport {
+        domain,
+        error_type,
+        file_path: file.to_string_lossy().to_string(),
+        total_errors: errors.len(),
+        incidents,
+        unexplained,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_and_groups_errors() {
+        let errors = vec![
+            json!({ "importer_name": "Sales CSV", "error_summary": "duplicate key value violates unique constraint \"orders_pkey\"", "received_at": "2026-10-14T02:00:00Z" }),
+            json!({ "importer_name": "Sales CSV", "error_summary": "duplicate key value violates unique constraint \"orders_pkey\"", "received_at": "2026-10-15T02:00:00Z" }),
+            json!({ "importer_name": "Sales CSV", "error_summary": "Row 1532: invalid input syntax for type numeric: '1,200.00'", "received_at": "2026-10-15T02:00:00Z" }),
+            json!({ "importer_name": "Stock", "error_summary": "Row 88: invalid input syntax for type numeric: 'n/a'", "received_at": "2026-10-13T02:00:00Z" }),
+            json!({ "connector": "Xero", "error_summary": "Request timed out after 30000ms" }),
+            json!({ "usr_eaea000fefface_3": "acme", "usr_cccbbdad0fee0a": "2026-10-15", "usr_x": "Flux capacitor misaligned at stage 3" }),
+        ];
+
+        assert_eq!(explain(&error_text(&errors[0])).rule_id.as_deref(), Some("duplicate_key"));
+        assert_eq!(explain(&error_text(&errors[2])).rule_id.as_deref(), Some("type_mismatch"));
+        assert_eq!(explain(&error_text(&errors[4])).category, "transient");
+        assert_eq!(error_text(&errors[5]), "Flux capacitor misaligned at stage 3");
+        assert_eq!(explain(&error_text(&errors[5])).source, "unknown");
+        assert_eq!(
+            signature("Row 1532: invalid input syntax for type numeric: '1,200.00'"),
+            signature("Row 7: invalid input syntax for type numeric: 'abc'")
+        );
+
+        let incidents = group_incidents(&errors);
+        // Same masked message from different importers stays separate
+        assert_eq!(incidents.len(), 5);
+        let dup = &incidents[0];
+        assert_eq!((dup.count, dup.source_name.as_deref()), (2, Some("Sales CSV")));
+        assert_eq!(
+            (dup.first_seen.as_deref(), dup.last_seen.as_deref()),
+            (Some("2026-10-14T02:00:00Z"), Some("2026-10-15T02:00:00Z"))
+        );
+
+        let mut rows = errors.clone();
+        annotate_errors(&mut rows);
+        assert_eq!(rows[0]["_explanation"]["rule_id"], "duplicate_key");
+    }
+}
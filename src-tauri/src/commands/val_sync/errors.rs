@@ -2,6 +2,7 @@
 // Fetches error data from centralized tv domain and writes to target domain's analytics folder
 
 use super::config::{get_domain_config, load_config_internal};
+use super::error_explain::{annotate_errors, group_incidents, ErrorIncident};
 use super::metadata;
 use super::sync::{write_json, SyncResult};
 use crate::commands::error::{CmdResult, CommandError};
@@ -14,8 +15,8 @@ use tauri::command;
 // Table and column mappings
 const IMPORTER_ERRORS_TABLE: &str = "custom_tbl_892_1520";
 const INTEGRATION_ERRORS_TABLE: &str = "custom_tbl_892_1519";
-const DOMAIN_COLUMN: &str = "usr_eaea000fefface_3";
-const DATE_COLUMN: &str = "usr_cccbbdad0fee0a";
+pub(super) const DOMAIN_COLUMN: &str = "usr_eaea000fefface_3";
+pub(super) const DATE_COLUMN: &str = "usr_cccbbdad0fee0a";
 
 // ============================================================================
 // Types
@@ -41,6 +42,9 @@ struct ErrorsSyncOutput {
     summary: ErrorsSummary,
     #[serde(rename = "dailyErrors")]
     daily_errors: HashMap<String, u32>,
+    /// Repeated errors grouped, with rule-based explanations (see error_explain)
+    incidents: Vec<ErrorIncident>,
+    /// Each row carries an `_explanation`
     errors: Vec<serde_json::Value>,
 }
 
@@ -56,6 +60,8 @@ struct ErrorsSummary {
     total_days: usize,
     #[serde(rename = "totalErrors")]
     total_errors: usize,
+    #[serde(rename = "totalIncidents")]
+    total_incidents: usize,
 }
 
 // ============================================================================
@@ -192,7 +198,7 @@ async fn sync_errors_impl(
     // Build and execute query
     let sql = build_errors_query(table, &domain, &from, &to);
 
-    let errors = match fetch_all_errors(&token, &sql).await {
+    let mut errors = match fetch_all_errors(&token, &sql).await {
         Ok(data) => data,
         Err(e) if e.to_string().contains("auth") || e.to_string().contains("401") || e.to_string().contains("403") => {
             let (new_token, _) = auth::reauth(&tv_domain.domain).await?;
@@ -206,6 +212,10 @@ async fn sync_errors_impl(
     // Calculate daily breakdown
     let daily_errors = calculate_daily_breakdown(&errors);
 
+    // Explain each error and group repeats into incidents
+    let incidents = group_incidents(&errors);
+    annotate_errors(&mut errors);
+
     // Build output
     let output = ErrorsSyncOutput {
         synced_at: chrono::Utc::now().to_rfc3339(),
@@ -217,8 +227,10 @@ async fn sync_errors_impl(
         summary: ErrorsSummary {
             total_days: daily_errors.len(),
             total_errors: errors.len(),
+            total_incidents: incidents.len(),
         },
         daily_errors,
+        incidents,
         errors: errors.clone(),
     };
 
@@ -252,7 +264,10 @@ async fn sync_errors_impl(
         file_path,
         duration_ms,
         status: "ok".to_string(),
-        message: format!("Synced {} {} errors ({} days)", count, error_type, output.summary.total_days),
+        message: format!(
+            "Synced {} {} errors ({} days, {} incidents)",
+            count, error_type, output.summary.total_days, output.summary.total_incidents
+        ),
     })
 }
 
@@ -1,13 +1,17 @@
 // VAL Sync Extract - Transform synced JSON into structured definitions
 // 6 extract operations: queries, workflows, dashboards, tables, sql, calc-fields
+// (integrations are extracted separately in integrations.rs)
 
 use super::api::val_api_fetch;
 use super::auth;
+use super::changes::{self, ArtifactChange};
 use super::config::get_domain_config;
 use super::metadata;
+use super::workflow_versions;
 use crate::commands::error::{CmdResult, CommandError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -23,11 +27,36 @@ pub struct ExtractResult {
     pub domain: String,
     pub extract_type: String,
     pub count: usize,
+    /// Files rewritten because their normalized content changed (or were new)
+    #[serde(default)]
+    pub changed: usize,
+    /// Files left untouched because their normalized content hash matched
+    #[serde(default)]
+    pub unchanged: usize,
     pub duration_ms: u64,
     pub status: String,
     pub message: String,
 }
 
+/// Per-run write tally for an extractor
+#[derive(Debug, Default, Clone, Copy)]
+struct ExtractCounts {
+    count: usize,
+    changed: usize,
+    unchanged: usize,
+}
+
+impl ExtractCounts {
+    fn record(&mut self, written: bool) {
+        self.count += 1;
+        if written {
+            self.changed += 1;
+        } else {
+            self.unchanged += 1;
+        }
+    }
+}
+
 // ============================================================================
 // Internal helpers
 // ============================================================================
@@ -38,34 +67,47 @@ fn read_json(path: &str) -> CmdResult<Value> {
     Ok(serde_json::from_str(&content)?)
 }
 
-/// Write JSON to file, creating parent directories
-fn write_json(path: &str, value: &Value) -> CmdResult<()> {
-    let p = Path::new(path);
-    if let Some(dir) = p.parent() {
-        if !dir.exists() {
-            fs::create_dir_all(dir)?;
+/// Hash content after normalizing away noise that shouldn't count as a change:
+/// line endings, trailing whitespace, and the `-- Extracted: <date>` SQL header line.
+fn normalized_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    for line in content.lines() {
+        if line.starts_with("-- Extracted:") {
+            continue;
         }
+        hasher.update(line.trim_end().as_bytes());
+        hasher.update(b"\n");
     }
-    let content = serde_json::to_string_pretty(value)?;
-    fs::write(p, content)?;
-    Ok(())
+    format!("{:x}", hasher.finalize())
 }
 
-/// Write text to file, creating parent directories
-fn write_text(path: &str, content: &str) -> CmdResult<()> {
+/// Write text to file only if its normalized content differs from what's on disk.
+/// Returns true when the file was (re)written, false when it was left untouched.
+pub(super) fn write_text_if_changed(path: &str, content: &str) -> CmdResult<bool> {
     let p = Path::new(path);
+    if let Ok(existing) = fs::read_to_string(p) {
+        if normalized_hash(&existing) == normalized_hash(content) {
+            return Ok(false);
+        }
+    }
     if let Some(dir) = p.parent() {
         if !dir.exists() {
             fs::create_dir_all(dir)?;
         }
     }
     fs::write(p, content)?;
-    Ok(())
+    Ok(true)
+}
+
+/// Write JSON to file (pretty-printed) only if its content changed
+pub(super) fn write_json_if_changed(path: &str, value: &Value) -> CmdResult<bool> {
+    let content = serde_json::to_string_pretty(value)?;
+    write_text_if_changed(path, &content)
 }
 
 /// Extract items array from flexible JSON structure
 /// Tries: root array, .data array, .{key} array
-fn extract_array(data: &Value, fallback_key: &str) -> Vec<Value> {
+pub(super) fn extract_array(data: &Value, fallback_key: &str) -> Vec<Value> {
     if let Some(arr) = data.as_array() {
         return arr.clone();
     }
@@ -196,13 +238,14 @@ fn extract_sql_from_plugin(plugin: &Value) -> Vec<(String, String)> {
 /// user see N/M progress instead of one opaque "fetching" line.
 fn extract_simple_internal(
     domain: &str,
+    global_path: &str,
     items: &[serde_json::Value],
     output_dir: &str,
     item_prefix: &str,
     id_keys: &[&str],
     event_name: &str,
     app: Option<&tauri::AppHandle>,
-) -> CmdResult<usize> {
+) -> CmdResult<ExtractCounts> {
     use tauri::Emitter;
 
     let total = items.len();
@@ -218,7 +261,9 @@ fn extract_simple_internal(
         );
     }
 
-    let mut count: usize = 0;
+    let run_at = chrono::Utc::now().to_rfc3339();
+    let mut counts = ExtractCounts::default();
+    let mut changed = Vec::new();
     for item in items {
         let id = item
             .get(id_keys[0])
@@ -232,9 +277,33 @@ fn extract_simple_internal(
         }
 
         let path = format!("{}/{}_{}/definition.json", output_dir, item_prefix, id);
-        let success = write_json(&path, item).is_ok();
-        if success {
-            count += 1;
+        let existed = Path::new(&path).exists();
+        let ownership = changes::ownership_from(item);
+        let definition = changes::with_ownership(item, &ownership);
+        let baseline = if item_prefix == "workflow" && existed {
+            workflow_versions::baseline_before_write(global_path, &id, &path)
+        } else {
+            None
+        };
+        let result = write_json_if_changed(&path, &definition);
+        let success = result.is_ok();
+        if let Ok(written) = result {
+            counts.record(written);
+            if written && item_prefix == "workflow" {
+                if let Err(e) = workflow_versions::record_version(global_path, &id, baseline, &definition, &run_at) {
+                    eprintln!("Failed to save version of workflow {}: {}", id, e);
+                }
+            }
+            if written {
+                changed.push(ArtifactChange {
+                    artifact_type: item_prefix.to_string(),
+                    artifact_id: id.clone(),
+                    name: item.get("name").and_then(|v| v.as_str()).map(String::from),
+                    change: if existed { "updated" } else { "created" }.to_string(),
+                    ownership,
+                    run_at: run_at.clone(),
+                });
+            }
         }
 
         if let Some(a) = app {
@@ -244,7 +313,7 @@ fn extract_simple_internal(
                     "domain": domain,
                     "stage": "fetching",
                     "id": id,
-                    "done": count,
+                    "done": counts.count,
                     "total": total,
                     "ok": success,
                 }),
@@ -252,32 +321,39 @@ fn extract_simple_internal(
         }
     }
 
+    if let Err(e) = changes::record_changes(global_path, &changed) {
+        eprintln!("Failed to record {} changes: {}", item_prefix, e);
+    }
+
     if let Some(a) = app {
         let _ = a.emit(
             event_name,
             serde_json::json!({
                 "domain": domain,
                 "stage": "done",
-                "done": count,
+                "done": counts.count,
                 "total": total,
-                "succeeded": count,
+                "succeeded": counts.count,
+                "changed": counts.changed,
+                "unchanged": counts.unchanged,
             }),
         );
     }
 
-    Ok(count)
+    Ok(counts)
 }
 
 fn extract_queries_internal(
     domain: &str,
     global_path: &str,
     app: Option<&tauri::AppHandle>,
-) -> CmdResult<usize> {
+) -> CmdResult<ExtractCounts> {
     let input = format!("{}/schema/all_queries.json", global_path);
     let data = read_json(&input)?;
     let items = extract_array(&data, "queries");
     extract_simple_internal(
         domain,
+        global_path,
         &items,
         &format!("{}/queries", global_path),
         "query",
@@ -291,12 +367,13 @@ fn extract_workflows_internal(
     domain: &str,
     global_path: &str,
     app: Option<&tauri::AppHandle>,
-) -> CmdResult<usize> {
+) -> CmdResult<ExtractCounts> {
     let input = format!("{}/schema/all_workflows.json", global_path);
     let data = read_json(&input)?;
     let items = extract_array(&data, "workflows");
     extract_simple_internal(
         domain,
+        global_path,
         &items,
         &format!("{}/workflows", global_path),
         "workflow",
@@ -310,12 +387,13 @@ fn extract_dashboards_internal(
     domain: &str,
     global_path: &str,
     app: Option<&tauri::AppHandle>,
-) -> CmdResult<usize> {
+) -> CmdResult<ExtractCounts> {
     let input = format!("{}/schema/all_dashboards.json", global_path);
     let data = read_json(&input)?;
     let items = extract_array(&data, "dashboards");
     extract_simple_internal(
         domain,
+        global_path,
         &items,
         &format!("{}/dashboards", global_path),
         "dashboard",
@@ -330,7 +408,7 @@ async fn extract_tables_internal(
     domain: &str,
     global_path: &str,
     app: Option<&tauri::AppHandle>,
-) -> CmdResult<usize> {
+) -> CmdResult<ExtractCounts> {
     use futures::stream::{self, StreamExt};
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -361,6 +439,18 @@ async fn extract_tables_internal(
         .filter(|name| seen.insert(name.clone()))
         .collect();
 
+    // Tree nodes sometimes carry attribution the data-model payload lacks
+    let tree_ownership: Arc<HashMap<String, changes::ArtifactOwnership>> = Arc::new(
+        tables
+            .iter()
+            .filter_map(|t| {
+                let name = t.get("table_name").and_then(|v| v.as_str())?;
+                Some((name.to_string(), changes::ownership_from(t)))
+            })
+            .collect(),
+    );
+    let run_at = Arc::new(chrono::Utc::now().to_rfc3339());
+
     let domain_config = get_domain_config(domain)?;
     let base_url = Arc::new(format!("https://{}.thinkval.io", domain_config.api_domain()));
     let (token, _) = auth::ensure_auth(domain).await?;
@@ -386,32 +476,58 @@ async fn extract_tables_internal(
     let counter = Arc::new(AtomicUsize::new(0));
     let domain_owned = domain.to_string();
 
-    let results: Vec<bool> = stream::iter(table_names.into_iter())
+    // Some((written, change)) on success, None on fetch/write failure
+    let results: Vec<Option<(bool, Option<ArtifactChange>)>> = stream::iter(table_names.into_iter())
         .map(|table_name| {
             let base_url = base_url.clone();
             let token = token.clone();
             let output_dir = output_dir.clone();
+            let tree_ownership = tree_ownership.clone();
+            let run_at = run_at.clone();
             let counter = counter.clone();
             let app = app.cloned();
             let domain = domain_owned.clone();
             async move {
-                let success = match val_api_fetch(&base_url, &token, "data-model", Some(&table_name)).await {
+                let outcome = match val_api_fetch(&base_url, &token, "data-model", Some(&table_name)).await {
                     Ok(definition) => {
                         let sanitized = sanitize_table_name(&table_name);
                         let path = format!("{}/table_{}/definition.json", output_dir, sanitized);
-                        match write_json(&path, &definition) {
-                            Ok(()) => true,
+                        let existed = Path::new(&path).exists();
+                        let mut ownership = changes::ownership_from(&definition);
+                        if ownership.is_empty() {
+                            ownership = tree_ownership.get(&table_name).cloned().unwrap_or_default();
+                        }
+                        match write_json_if_changed(&path, &changes::with_ownership(&definition, &ownership)) {
+                            Ok(written) => {
+                                let change = if written {
+                                    Some(ArtifactChange {
+                                        artifact_type: "table".to_string(),
+                                        artifact_id: table_name.clone(),
+                                        name: definition
+                                            .get("display_name")
+                                            .and_then(|v| v.as_str())
+                                            .map(String::from),
+                                        change: if existed { "updated" } else { "created" }.to_string(),
+                                        ownership,
+                                        run_at: run_at.to_string(),
+                                    })
+                                } else {
+                                    None
+                                };
+                                Some((written, change))
+                            }
                             Err(e) => {
                                 eprintln!("write_json failed for {}: {}", table_name, e);
-                                false
+                                None
                             }
                         }
                     }
                     Err(e) => {
                         eprintln!("Failed to fetch table {}: {}", table_name, e);
-                        false
+                        None
                     }
                 };
+                let success = outcome.is_some();
                 let done = counter.fetch_add(1, Ordering::SeqCst) + 1;
                 // Emit every table for fine-grained progress. Volume is
                 // bounded by total tables (~1.3k for lab) and frontend
@@ -430,14 +546,24 @@ async fn extract_tables_internal(
                         }),
                     );
                 }
-                success
+                outcome
             }
         })
         .buffer_unordered(CONCURRENCY)
         .collect()
         .await;
 
-    let count = results.iter().filter(|s| **s).count();
+    let mut counts = ExtractCounts::default();
+    let mut changed = Vec::new();
+    for (written, change) in results.into_iter().flatten() {
+        counts.record(written);
+        changed.extend(change);
+    }
+    let count = counts.count;
+
+    if let Err(e) = changes::record_changes(global_path, &changed) {
+        eprintln!("Failed to record table changes: {}", e);
+    }
 
     if let Some(a) = app {
         let _ = a.emit(
@@ -449,22 +575,24 @@ async fn extract_tables_internal(
                 "total": total,
                 "succeeded": count,
                 "failed": total - count,
+                "changed": counts.changed,
+                "unchanged": counts.unchanged,
             }),
         );
     }
 
-    Ok(count)
+    Ok(counts)
 }
 
 /// SQL extraction from workflow definitions
-fn extract_sql_internal(global_path: &str) -> CmdResult<usize> {
+fn extract_sql_internal(global_path: &str) -> CmdResult<ExtractCounts> {
     let workflows_dir = format!("{}/workflows", global_path);
     let workflows_path = Path::new(&workflows_dir);
+    let mut counts = ExtractCounts::default();
     if !workflows_path.exists() {
-        return Ok(0);
+        return Ok(counts);
     }
 
-    let mut count = 0;
     let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
 
     let entries = fs::read_dir(workflows_path)?;
@@ -542,20 +670,21 @@ fn extract_sql_internal(global_path: &str) -> CmdResult<usize> {
             );
 
             let full_path = sql_dir.join(&filename);
-            write_text(&full_path.to_string_lossy(), &format!("{}{}", header, sql))?;
-            count += 1;
+            let written = write_text_if_changed(&full_path.to_string_lossy(), &format!("{}{}", header, sql))?;
+            counts.record(written);
         }
     }
 
-    Ok(count)
+    Ok(counts)
 }
 
 /// Calc fields: enrich data model definitions with ruleField
-fn extract_calc_fields_internal(global_path: &str) -> CmdResult<usize> {
+fn extract_calc_fields_internal(global_path: &str) -> CmdResult<ExtractCounts> {
+    let mut counts = ExtractCounts::default();
     let input = format!("{}/schema/all_calculated_fields.json", global_path);
     let data = match read_json(&input) {
         Ok(d) => d,
-        Err(_) => return Ok(0), // No calc fields file — skip
+        Err(_) => return Ok(counts), // No calc fields file — skip
     };
 
     let items = extract_array(&data, "data");
@@ -595,7 +724,7 @@ fn extract_calc_fields_internal(global_path: &str) -> CmdResult<usize> {
     let data_models_dir = format!("{}/data_models", global_path);
     let dm_path = Path::new(&data_models_dir);
     if !dm_path.exists() {
-        return Ok(0);
+        return Ok(counts);
     }
 
     let mut enriched_count = 0;
@@ -659,11 +788,18 @@ fn extract_calc_fields_internal(global_path: &str) -> CmdResult<usize> {
         }
 
         if modified {
-            write_json(&def_str, &definition)?;
+            let written = write_json_if_changed(&def_str, &definition)?;
+            if written {
+                counts.changed += 1;
+            } else {
+                counts.unchanged += 1;
+            }
         }
     }
 
-    Ok(enriched_count)
+    // Count stays "fields enriched"; changed/unchanged tally definition files
+    counts.count = enriched_count;
+    Ok(counts)
 }
 
 // ============================================================================
@@ -686,7 +822,7 @@ pub async fn run_extract_with_app(
     let domain_config = get_domain_config(domain)?;
     let global_path = &domain_config.global_path;
 
-    let count = match extract_type {
+    let counts = match extract_type {
         "queries" => extract_queries_internal(domain, global_path, app)?,
         "workflows" => extract_workflows_internal(domain, global_path, app)?,
         "dashboards" => extract_dashboards_internal(domain, global_path, app)?,
@@ -697,6 +833,7 @@ pub async fn run_extract_with_app(
     };
 
     let duration_ms = start.elapsed().as_millis() as u64;
+    let count = counts.count;
 
     metadata::update_extraction_sync(global_path, domain, extract_type, count, "ok", duration_ms).await;
 
@@ -704,9 +841,14 @@ pub async fn run_extract_with_app(
         domain: domain.to_string(),
         extract_type: extract_type.to_string(),
         count,
+        changed: counts.changed,
+        unchanged: counts.unchanged,
         duration_ms,
         status: "ok".to_string(),
-        message: format!("Extracted {} {} items", count, extract_type),
+        message: format!(
+            "Extracted {} {} items ({} changed, {} unchanged)",
+            count, extract_type, counts.changed, counts.unchanged
+        ),
     })
 }
 
@@ -0,0 +1,450 @@
+// VAL Sync Fix Sessions - Guided multi-step data fixes with an audit trail
+// Typical flow: health check → find bad rows → UPDATE → rerun workflow.
+// Each step is recorded to {global_path}/fix_sessions/{id}.json and the
+// session closes with a markdown report alongside it.
+
+use super::auth;
+use super::config::get_domain_config;
+use super::sql;
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixSession {
+    pub id: String,
+    pub domain: String,
+    pub table: String,
+    pub title: String,
+    pub status: String, // open | completed | abandoned
+    pub started_at: String,
+    pub completed_at: Option<String>,
+    pub summary: Option<String>,
+    pub steps: Vec<FixStep>,
+    pub report_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixStep {
+    pub kind: String, // query | write | workflow_rerun | note
+    pub at: String,
+    pub note: Option<String>,
+    pub sql: Option<String>,
+    pub rows_returned: Option<usize>,
+    pub rows_affected: Option<i64>,
+    pub workflow_id: Option<u64>,
+    pub execution_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixStepResult {
+    pub session: FixSession,
+    pub step: FixStep,
+    /// First rows of a SELECT, for display only — not persisted
+    pub preview: Vec<serde_json::Value>,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn sessions_dir(domain: &str) -> CmdResult<PathBuf> {
+    let config = get_domain_config(domain)?;
+    Ok(PathBuf::from(&config.global_path).join("fix_sessions"))
+}
+
+fn session_path(domain: &str, session_id: &str) -> CmdResult<PathBuf> {
+    if session_id.contains('/') || session_id.contains('\\') || session_id.contains("..") {
+        return Err(CommandError::Config(format!("Invalid session id: {}", session_id)));
+    }
+    Ok(sessions_dir(domain)?.join(format!("{}.json", session_id)))
+}
+
+fn load_session(domain: &str, session_id: &str) -> CmdResult<FixSession> {
+    let path = session_path(domain, session_id)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|_| CommandError::NotFound(format!("Fix session not found: {}", session_id)))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn save_session(session: &FixSession) -> CmdResult<()> {
+    let path = session_path(&session.domain, &session.id)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(session)?)?;
+    Ok(())
+}
+
+fn load_open_session(domain: &str, session_id: &str) -> CmdResult<FixSession> {
+    let session = load_session(domain, session_id)?;
+    if session.status != "open" {
+        return Err(CommandError::Config(format!(
+            "Fix session {} is {} — start a new session to make further changes",
+            session_id, session.status
+        )));
+    }
+    Ok(session)
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+fn empty_step(kind: &str) -> FixStep {
+    FixStep {
+        kind: kind.to_string(),
+        at: now(),
+        note: None,
+        sql: None,
+        rows_returned: None,
+        rows_affected: None,
+        workflow_id: None,
+        execution_id: None,
+        error: None,
+    }
+}
+
+/// Best-effort affected-row count from a write response. VAL returns
+/// different shapes depending on the statement, so check the common keys.
+fn rows_affected_from(data: &[serde_json::Value]) -> Option<i64> {
+    let first = data.first()?;
+    ["rowCount", "row_count", "affected_rows", "affectedRows", "count"]
+        .iter()
+        .find_map(|k| {
+            first.get(*k).and_then(|v| {
+                v.as_i64()
+                    .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+            })
+        })
+}
+
+/// Leading keywords of statements that change data or schema
+const WRITE_KEYWORDS: [&str; 8] = ["update", "delete", "insert", "merge", "truncate", "drop", "alter", "create"];
+
+/// `sql` without `--` and `/* */` comments (string literals are kept as-is)
+fn strip_sql_comments(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            in_string = c != '\'';
+            out.push(c);
+        } else if c == '\'' {
+            in_string = true;
+            out.push(c);
+        } else if c == '-' && chars.peek() == Some(&'-') {
+            for c in chars.by_ref() {
+                if c == '\n' {
+                    out.push('\n');
+                    break;
+                }
+            }
+        } else if c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut prev = ' ';
+            for c in chars.by_ref() {
+                if prev == '*' && c == '/' {
+                    break;
+                }
+                prev = c;
+            }
+            out.push(' ');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Lowercased identifier/keyword tokens of `sql`, comments removed
+fn sql_words(sql: &str) -> Vec<String> {
+    strip_sql_comments(sql)
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// A statement is a write when it starts with a write keyword, or is a CTE
+/// (`WITH ...`) containing one
+fn is_write_statement(sql: &str) -> bool {
+    let words = sql_words(sql);
+    match words.first().map(String::as_str) {
+        Some("with") => words.iter().any(|w| WRITE_KEYWORDS.contains(&w.as_str())),
+        Some(first) => WRITE_KEYWORDS.contains(&first),
+        None => false,
+    }
+}
+
+fn references_table(sql: &str, table: &str) -> bool {
+    let table = table.to_lowercase();
+    sql_words(sql).iter().any(|w| *w == table)
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+fn render_report(session: &FixSession) -> String {
+    let mut md = String::new();
+    md.push_str("---\n");
+    md.push_str(&format!("title: \"Fix: {}\"\n", session.title.replace('"', "'")));
+    md.push_str(&format!("domain: {}\n", session.domain));
+    md.push_str(&format!("table: {}\n", session.table));
+    md.push_str(&format!("status: {}\n", session.status));
+    md.push_str(&format!("started_at: {}\n", session.started_at));
+    if let Some(ref completed) = session.completed_at {
+        md.push_str(&format!("completed_at: {}\n", completed));
+    }
+    md.push_str("---\n\n");
+
+    md.push_str(&format!("# Fix: {}\n\n", session.title));
+    if let Some(ref summary) = session.summary {
+        md.push_str(&format!("{}\n\n", summary));
+    }
+
+    let queries = session.steps.iter().filter(|s| s.kind == "query").count();
+    let writes: Vec<&FixStep> = session.steps.iter().filter(|s| s.kind == "write").collect();
+    let total_affected: i64 = writes.iter().filter_map(|s| s.rows_affected).sum();
+    let reruns = session.steps.iter().filter(|s| s.kind == "workflow_rerun").count();
+
+    md.push_str("## Summary\n\n");
+    md.push_str("| Metric | Value |\n|---|---|\n");
+    md.push_str(&format!("| Domain | `{}` |\n", session.domain));
+    md.push_str(&format!("| Table | `{}` |\n", session.table));
+    md.push_str(&format!("| Queries run | {} |\n", queries));
+    md.push_str(&format!("| Write statements | {} |\n", writes.len()));
+    md.push_str(&format!("| Rows affected (reported) | {} |\n", total_affected));
+    md.push_str(&format!("| Workflow reruns | {} |\n\n", reruns));
+
+    md.push_str("## Steps\n\n");
+    for (i, step) in session.steps.iter().enumerate() {
+        md.push_str(&format!("### {}. {} — {}\n\n", i + 1, step.kind.replace('_', " "), step.at));
+        if let Some(ref note) = step.note {
+            md.push_str(&format!("{}\n\n", note));
+        }
+        if let Some(ref sql) = step.sql {
+            md.push_str(&format!("```sql\n{}\n```\n\n", sql.trim()));
+        }
+        if let Some(n) = step.rows_returned {
+            md.push_str(&format!("- Rows returned: {}\n", n));
+        }
+        if let Some(n) = step.rows_affected {
+            md.push_str(&format!("- Rows affected: {}\n", n));
+        }
+        if let Some(id) = step.workflow_id {
+            md.push_str(&format!("- Workflow: {}", id));
+            if let Some(exec) = step.execution_id {
+                md.push_str(&format!(" (execution {})", exec));
+            }
+            md.push('\n');
+        }
+        if let Some(ref err) = step.error {
+            md.push_str(&format!("- **Error:** {}\n", err));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Start a guided fix session for one table
+#[command]
+pub async fn val_start_fix_session(
+    domain: String,
+    table: String,
+    title: Option<String>,
+) -> CmdResult<FixSession> {
+    let started = chrono::Utc::now();
+    let session = FixSession {
+        id: format!("{}-{}", table, started.format("%Y%m%d-%H%M%S")),
+        domain,
+        title: title.unwrap_or_else(|| format!("{} data fix", table)),
+        table,
+        status: "open".to_string(),
+        started_at: started.to_rfc3339(),
+        completed_at: None,
+        summary: None,
+        steps: Vec::new(),
+        report_path: None,
+    };
+    save_session(&session)?;
+    Ok(session)
+}
+
+/// Run SQL inside a fix session. Reads run as usual; writes (UPDATE, DELETE, ...)
+/// require `allow_write` and must reference the session's table.
+#[command]
+pub async fn val_fix_session_run_sql(
+    domain: String,
+    session_id: String,
+    sql: String,
+    note: Option<String>,
+    allow_write: Option<bool>,
+) -> CmdResult<FixStepResult> {
+    let mut session = load_open_session(&domain, &session_id)?;
+    let write = is_write_statement(&sql);
+    let mut step = empty_step(if write { "write" } else { "query" });
+    step.sql = Some(sql.clone());
+    step.note = note;
+    let mut preview = Vec::new();
+
+    if write {
+        if !allow_write.unwrap_or(false) {
+            return Err(CommandError::Config(
+                "Write statements need allow_write=true — confirm before running".to_string(),
+            ));
+        }
+        if !references_table(&sql, &session.table) {
+            return Err(CommandError::Config(format!(
+                "Write statement does not reference session table '{}'",
+                session.table
+            )));
+        }
+
+        let config = get_domain_config(&domain)?;
+        let (token, _) = auth::ensure_auth(&domain).await?;
+        let result = match sql::execute_sql_internal(&token, config.api_domain(), &sql, 100).await {
+            Err(e) if e.contains("401") || e.contains("403") => {
+                let (new_token, _) = auth::reauth(&domain).await?;
+                sql::execute_sql_internal(&new_token, config.api_domain(), &sql, 100).await
+            }
+            other => other,
+        };
+        match result {
+            Ok(response) => {
+                step.rows_affected = rows_affected_from(&response.data.unwrap_or_default());
+            }
+            Err(e) => step.error = Some(e),
+        }
+    } else {
+        // A failed query is recorded as a step rather than ending the session
+        match sql::val_execute_sql(domain.clone(), sql, Some(1000)).await {
+            Ok(result) => {
+                step.rows_returned = Some(result.row_count);
+                step.error = result.error;
+                preview = result.data.into_iter().take(50).collect();
+            }
+            Err(e) => step.error = Some(e.to_string()),
+        }
+    }
+
+    session.steps.push(step.clone());
+    save_session(&session)?;
+    Ok(FixStepResult { session, step, preview })
+}
+
+/// Rerun a workflow as part of a fix session (e.g. to rebuild downstream tables)
+#[command]
+pub async fn val_fix_session_rerun_workflow(
+    domain: String,
+    session_id: String,
+    workflow_id: u64,
+    note: Option<String>,
+) -> CmdResult<FixStepResult> {
+    let mut session = load_open_session(&domain, &session_id)?;
+    let mut step = empty_step("workflow_rerun");
+    step.workflow_id = Some(workflow_id);
+    step.note = note;
+
+    match super::drive::val_workflow_rerun(domain, workflow_id).await {
+        Ok(r) => step.execution_id = Some(r.execution_id),
+        Err(e) => step.error = Some(e.to_string()),
+    }
+
+    session.steps.push(step.clone());
+    save_session(&session)?;
+    Ok(FixStepResult { session, step, preview: Vec::new() })
+}
+
+/// Add a free-text note (findings, decisions) to a fix session
+#[command]
+pub async fn val_fix_session_add_note(
+    domain: String,
+    session_id: String,
+    note: String,
+) -> CmdResult<FixSession> {
+    let mut session = load_open_session(&domain, &session_id)?;
+    let mut step = empty_step("note");
+    step.note = Some(note);
+    session.steps.push(step);
+    save_session(&session)?;
+    Ok(session)
+}
+
+#[command]
+pub async fn val_get_fix_session(domain: String, session_id: String) -> CmdResult<FixSession> {
+    load_session(&domain, &session_id)
+}
+
+/// List fix sessions for a domain, newest first
+#[command]
+pub async fn val_list_fix_sessions(domain: String) -> CmdResult<Vec<FixSession>> {
+    let dir = sessions_dir(&domain)?;
+    let mut sessions: Vec<FixSession> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("json"))
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .filter_map(|c| serde_json::from_str(&c).ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(sessions)
+}
+
+/// Close a fix session and write its markdown report next to the session file
+#[command]
+pub async fn val_complete_fix_session(
+    domain: String,
+    session_id: String,
+    summary: Option<String>,
+    abandoned: Option<bool>,
+) -> CmdResult<FixSession> {
+    let mut session = load_open_session(&domain, &session_id)?;
+    session.status = if abandoned.unwrap_or(false) { "abandoned" } else { "completed" }.to_string();
+    session.completed_at = Some(now());
+    session.summary = summary;
+
+    let report_path = sessions_dir(&domain)?.join(format!("{}.md", session.id));
+    fs::write(&report_path, render_report(&session))?;
+    session.report_path = Some(report_path.to_string_lossy().to_string());
+
+    save_session(&session)?;
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_check_reads_leading_keywords_without_comments() {
+        assert!(is_write_statement("-- fix dupes\nUPDATE orders SET status = 'x'"));
+        assert!(is_write_statement("/* cleanup */ delete from orders"));
+        assert!(is_write_statement("WITH d AS (SELECT id FROM orders) DELETE FROM orders USING d"));
+        assert!(!is_write_statement("SELECT updated_at FROM orders -- then update"));
+        assert!(!is_write_statement("select * from orders where note = 'delete me'"));
+    }
+
+    #[test]
+    fn table_reference_is_a_whole_word() {
+        assert!(references_table("UPDATE public.orders SET x = 1", "orders"));
+        assert!(!references_table("UPDATE orders_archive SET x = 1", "orders"));
+        assert!(!references_table("UPDATE other SET x = 1 -- orders", "orders"));
+    }
+}
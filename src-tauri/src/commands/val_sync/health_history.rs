@@ -0,0 +1,474 @@
+// VAL Sync Health History - Score trends and regression detection
+// Health results are overwritten on every run (domain_health_checks upserts,
+// definition_details.json, query_stats.json), so each recorded run appends
+// its scores per table / workflow / query / check to a local SQLite history
+// at ~/.tv-client/val/health_history.db. Runs are compared with the previous
+// score of each entity and drops beyond a threshold raise a notification.
+
+use super::config::get_domain_config;
+use super::context_pack::{read_json, table_folders};
+use super::query_stats::load_query_stats;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::senders::period_days;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{command, Emitter};
+
+/// Default score drop (0-100 scale) that counts as a regression
+const DEFAULT_REGRESSION_THRESHOLD: f64 = 20.0;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthScore {
+    /// table | workflow | query | check
+    pub entity_type: String,
+    pub entity_id: String,
+    pub label: String,
+    /// 0-100, higher is healthier
+    pub score: f64,
+    pub status: Option<String>,
+}
+
+/// A domain health check result as stored in domain_health_checks
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthCheckInput {
+    pub check_type: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthRegression {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub label: String,
+    pub previous_score: f64,
+    pub score: f64,
+    pub drop: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthRunSummary {
+    pub domain: String,
+    pub run_id: String,
+    pub recorded_at: String,
+    pub scores: usize,
+    pub regressions: Vec<HealthRegression>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthPoint {
+    pub recorded_at: String,
+    pub score: f64,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthTrend {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub label: String,
+    pub points: Vec<HealthPoint>,
+    pub latest: f64,
+    /// Latest minus first score in the period
+    pub change: f64,
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+pub struct HealthHistoryDb {
+    conn: Mutex<Connection>,
+}
+
+impl HealthHistoryDb {
+    pub fn open() -> CmdResult<Self> {
+        let path = get_db_path();
+        let dir = path.parent().unwrap_or(&path);
+        if !dir.exists() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let conn = Connection::open(&path).map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode=WAL;
+
+            CREATE TABLE IF NOT EXISTS health_scores (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                domain TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                label TEXT NOT NULL DEFAULT '',
+                score REAL NOT NULL,
+                status TEXT,
+                recorded_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_health_scores_entity
+                ON health_scores(domain, entity_type, entity_id, recorded_at);
+            CREATE INDEX IF NOT EXISTS idx_health_scores_run ON health_scores(run_id);
+            ",
+        )
+        .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    fn lock(&self) -> CmdResult<std::sync::MutexGuard<'_, Connection>> {
+        self.conn.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))
+    }
+
+    /// Most recent score per entity ("type:id") for a domain
+    pub fn latest_scores(&self, domain: &str) -> CmdResult<HashMap<String, f64>> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT h.entity_type, h.entity_id, h.score FROM health_scores h
+                 JOIN (SELECT entity_type, entity_id, MAX(recorded_at) AS at FROM health_scores
+                       WHERE domain = ?1 GROUP BY entity_type, entity_id) latest
+                   ON h.entity_type = latest.entity_type AND h.entity_id = latest.entity_id AND h.recorded_at = latest.at
+                 WHERE h.domain = ?1",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![domain], |row| {
+                Ok((
+                    format!("{}:{}", row.get::<_, String>(0)?, row.get::<_, String>(1)?),
+                    row.get::<_, f64>(2)?,
+                ))
+            })
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(rows.flatten().collect())
+    }
+
+    pub fn insert_run(&self, run_id: &str, domain: &str, recorded_at: &str, scores: &[HealthScore]) -> CmdResult<()> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        for s in scores {
+            tx.execute(
+                "INSERT INTO health_scores (run_id, domain, entity_type, entity_id, label, score, status, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![run_id, domain, s.entity_type, s.entity_id, s.label, s.score, s.status, recorded_at],
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        }
+        tx.commit().map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(())
+    }
+
+    /// Score history since `since`, oldest first. `entity` is either an entity
+    /// type ("table") or a single entity ("table:123").
+    pub fn history(&self, domain: &str, entity: Option<&str>, since: &str) -> CmdResult<Vec<(HealthScore, String)>> {
+        let (entity_type, entity_id) = match entity.and_then(|e| e.split_once(':')) {
+            Some((t, id)) => (Some(t.to_string()), Some(id.to_string())),
+            None => (entity.map(|e| e.to_string()), None),
+        };
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT entity_type, entity_id, label, score, status, recorded_at FROM health_scores
+                 WHERE domain = ?1 AND recorded_at >= ?2
+                   AND (?3 IS NULL OR entity_type = ?3) AND (?4 IS NULL OR entity_id = ?4)
+                 ORDER BY recorded_at ASC",
+            )
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        let rows = stmt
+            .query_map(params![domain, since, entity_type, entity_id], |row| {
+                Ok((
+                    HealthScore {
+                        entity_type: row.get(0)?,
+                        entity_id: row.get(1)?,
+                        label: row.get(2)?,
+                        score: row.get(3)?,
+                        status: row.get(4)?,
+                    },
+                    row.get::<_, String>(5)?,
+                ))
+            })
+            .map_err(|e| CommandError::Internal(format!("DB: {}", e)))?;
+        Ok(rows.flatten().collect())
+    }
+}
+
+fn get_db_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val")
+        .join("health_history.db")
+}
+
+// ============================================================================
+// Score collection
+// ============================================================================
+
+/// pass/warn/fail → score; errored checks say nothing about health
+pub fn check_status_score(status: &str) -> Option<f64> {
+    match status {
+        "pass" => Some(100.0),
+        "warn" => Some(60.0),
+        "fail" => Some(20.0),
+        _ => None,
+    }
+}
+
+/// Table scores from data_models/table_*/definition_details.json
+fn table_scores(global_path: &Path) -> Vec<HealthScore> {
+    table_folders(global_path, "*")
+        .into_iter()
+        .filter_map(|(id, folder)| {
+            let details = read_json(&folder.join("definition_details.json"))?;
+            let score = details["health"]["score"].as_f64()?;
+            Some(HealthScore {
+                entity_type: "table".to_string(),
+                label: details["meta"]["displayName"].as_str().unwrap_or(&id).to_string(),
+                entity_id: id,
+                score,
+                status: details["health"]["status"].as_str().map(|s| s.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Workflow scores from the last run status in schema/all_workflows.json
+fn workflow_scores(global_path: &Path) -> Vec<HealthScore> {
+    let Some(data) = read_json(&global_path.join("schema/all_workflows.json")) else {
+        return Vec::new();
+    };
+    let Some(workflows) = data["data"].as_array() else {
+        return Vec::new();
+    };
+    workflows
+        .iter()
+        .filter(|wf| !wf["deleted"].as_bool().unwrap_or(false))
+        .filter_map(|wf| {
+            let status = wf["latest_run_status"].as_str()?;
+            let score = match status {
+                "completed" => 100.0,
+                "failed" => 0.0,
+                _ => return None,
+            };
+            let id = wf["id"].as_i64().map(|i| i.to_string()).or_else(|| wf["id"].as_str().map(|s| s.to_string()))?;
+            Some(HealthScore {
+                entity_type: "workflow".to_string(),
+                label: wf["name"].as_str().unwrap_or(&id).to_string(),
+                entity_id: id,
+                score,
+                status: Some(status.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Query scores from schema/query_stats.json: success rate, 0 when broken
+fn query_scores(global_path: &str) -> Vec<HealthScore> {
+    let Some(stats) = load_query_stats(global_path) else {
+        return Vec::new();
+    };
+    stats
+        .queries
+        .into_iter()
+        .filter(|q| q.last_run_at.is_some())
+        .map(|q| {
+            let score = if q.broken { 0.0 } else { (1.0 - q.error_rate).clamp(0.0, 1.0) * 100.0 };
+            let status = if q.broken { "broken" } else if q.heavy { "heavy" } else { "ok" };
+            HealthScore {
+                entity_type: "query".to_string(),
+                entity_id: q.id,
+                label: q.name,
+                score,
+                status: Some(status.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Entities whose score fell by more than `threshold` since their previous run
+pub fn find_regressions(previous: &HashMap<String, f64>, current: &[HealthScore], threshold: f64) -> Vec<HealthRegression> {
+    let mut out: Vec<HealthRegression> = current
+        .iter()
+        .filter_map(|s| {
+            let before = *previous.get(&format!("{}:{}", s.entity_type, s.entity_id))?;
+            let drop = before - s.score;
+            if drop <= threshold {
+                return None;
+            }
+            Some(HealthRegression {
+                entity_type: s.entity_type.clone(),
+                entity_id: s.entity_id.clone(),
+                label: s.label.clone(),
+                previous_score: before,
+                score: s.score,
+                drop,
+            })
+        })
+        .collect();
+    out.sort_by(|a, b| b.drop.partial_cmp(&a.drop).unwrap_or(std::cmp::Ordering::Equal));
+    out
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Record one health run for a domain: table, workflow and query scores read
+/// from disk plus the domain check results passed in. Regressions (a drop of
+/// more than `threshold` points, default 20) trigger a desktop notification
+/// and a `val-health:regression` event.
+#[command]
+pub async fn val_record_health_run(
+    app: tauri::AppHandle,
+    domain: String,
+    checks: Option<Vec<HealthCheckInput>>,
+    threshold: Option<f64>,
+) -> CmdResult<HealthRunSummary> {
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = domain_config.global_path.clone();
+
+    let mut scores = table_scores(Path::new(&global_path));
+    scores.extend(workflow_scores(Path::new(&global_path)));
+    scores.extend(query_scores(&global_path));
+    for check in checks.unwrap_or_default() {
+        if let Some(score) = check_status_score(&check.status) {
+            scores.push(HealthScore {
+                entity_type: "check".to_string(),
+                label: check.check_type.replace('_', " "),
+                entity_id: check.check_type,
+                score,
+                status: Some(check.status),
+            });
+        }
+    }
+
+    let db = HealthHistoryDb::open()?;
+    let previous = db.latest_scores(&domain)?;
+    let regressions = find_regressions(&previous, &scores, threshold.unwrap_or(DEFAULT_REGRESSION_THRESHOLD));
+
+    let recorded_at = chrono::Utc::now().to_rfc3339();
+    let run_id = format!("{}-{}", domain, chrono::Utc::now().timestamp_millis());
+    db.insert_run(&run_id, &domain, &recorded_at, &scores)?;
+
+    if !regressions.is_empty() {
+        use tauri_plugin_notification::NotificationExt;
+        let worst: Vec<String> = regressions
+            .iter()
+            .take(3)
+            .map(|r| format!("{} {:.0}→{:.0}", r.label, r.previous_score, r.score))
+            .collect();
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!("{}: {} health regression(s)", domain, regressions.len()))
+            .body(worst.join(" · "))
+            .show();
+        let _ = app.emit(
+            "val-health:regression",
+            serde_json::json!({ "domain": domain, "regressions": regressions }),
+        );
+    }
+
+    Ok(HealthRunSummary {
+        domain,
+        run_id,
+        recorded_at,
+        scores: scores.len(),
+        regressions,
+    })
+}
+
+/// Score history per entity. `entity` filters by type ("table", "workflow",
+/// "query", "check") or a single entity ("table:123"); `period` is
+/// week | month (default) | quarter | year.
+#[command]
+pub async fn val_get_health_trends(
+    domain: String,
+    entity: Option<String>,
+    period: Option<String>,
+) -> CmdResult<Vec<HealthTrend>> {
+    let since = (chrono::Utc::now() - chrono::Duration::days(period_days(period.as_deref().unwrap_or("month"))?)).to_rfc3339();
+    let rows = HealthHistoryDb::open()?.history(&domain, entity.as_deref(), &since)?;
+
+    let mut order: Vec<String> = Vec::new();
+    let mut trends: HashMap<String, HealthTrend> = HashMap::new();
+    for (score, recorded_at) in rows {
+        let key = format!("{}:{}", score.entity_type, score.entity_id);
+        let trend = trends.entry(key.clone()).or_insert_with(|| {
+            order.push(key);
+            HealthTrend {
+                entity_type: score.entity_type.clone(),
+                entity_id: score.entity_id.clone(),
+                label: score.label.clone(),
+                points: Vec::new(),
+                latest: score.score,
+                change: 0.0,
+            }
+        });
+        trend.label = score.label;
+        trend.points.push(HealthPoint { recorded_at, score: score.score, status: score.status });
+    }
+
+    let mut out: Vec<HealthTrend> = order
+        .into_iter()
+        .filter_map(|key| trends.remove(&key))
+        .map(|mut t| {
+            let first = t.points.first().map(|p| p.score).unwrap_or_default();
+            t.latest = t.points.last().map(|p| p.score).unwrap_or_default();
+            t.change = t.latest - first;
+            t
+        })
+        .collect();
+    // Biggest declines first
+    out.sort_by(|a, b| a.change.partial_cmp(&b.change).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(entity_type: &str, id: &str, value: f64) -> HealthScore {
+        HealthScore {
+            entity_type: entity_type.to_string(),
+            entity_id: id.to_string(),
+            label: id.to_string(),
+            score: value,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn flags_drops_beyond_threshold_only() {
+        let previous: HashMap<String, f64> = [
+            ("table:orders".to_string(), 90.0),
+            ("table:items".to_string(), 80.0),
+            ("query:q1".to_string(), 100.0),
+        ]
+        .into_iter()
+        .collect();
+        let current = vec![
+            score("table", "orders", 40.0),  // -50
+            score("table", "items", 65.0),   // -15, under threshold
+            score("query", "q1", 70.0),      // -30
+            score("workflow", "new", 0.0),   // no history
+        ];
+
+        let regressions = find_regressions(&previous, &current, 20.0);
+        let ids: Vec<&str> = regressions.iter().map(|r| r.entity_id.as_str()).collect();
+        assert_eq!(ids, vec!["orders", "q1"]);
+        assert_eq!(regressions[0].drop, 50.0);
+        assert_eq!(check_status_score("error"), None);
+    }
+}
@@ -0,0 +1,337 @@
+// VAL Sync Integrations - Extract and document integration configurations
+// Fetches integration/connector definitions, masks credentials, and writes
+// integrations/integration_{id}/definition.json + overview.md, a summary.md
+// across all integrations, and index.json (used by table overviews whose
+// dataSource is "integration" to link back here).
+
+use super::api::val_api_fetch;
+use super::auth;
+use super::config::get_domain_config;
+use super::extract::{extract_array, write_json_if_changed, write_text_if_changed, ExtractResult};
+use super::metadata;
+use super::sync::write_json;
+use crate::commands::diagnostics::is_secret_setting;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::tools::docgen::markdown_table_cell;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use tauri::command;
+
+const MASK: &str = "********";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One row of integrations/index.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationSummary {
+    pub id: String,
+    pub name: String,
+    pub source_system: Option<String>,
+    pub schedule: Option<String>,
+    pub target_tables: Vec<String>,
+    pub enabled: Option<bool>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+
+/// Replace every secret value with a fixed mask. Everything under a secret
+/// key is masked, so nested `credentials: {user, password}` objects are covered.
+fn mask_credentials(value: &Value) -> Value {
+    fn mask_all(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), mask_all(v))).collect()),
+            Value::Array(items) => Value::Array(items.iter().map(mask_all).collect()),
+            Value::Null => Value::Null,
+            _ => Value::String(MASK.to_string()),
+        }
+    }
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let masked = if is_secret_setting(k) { mask_all(v) } else { mask_credentials(v) };
+                    (k.clone(), masked)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(mask_credentials).collect()),
+        other => other.clone(),
+    }
+}
+
+fn first_str(item: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|k| match item.get(k)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(obj) => obj
+            .get("name")
+            .or_else(|| obj.get("type"))
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        _ => None,
+    })
+}
+
+/// Table names from `target_table`, `target_tables`, `tables`, ... which may be
+/// a string, an array of strings, or objects carrying `table_name`
+fn target_tables(item: &Value) -> Vec<String> {
+    fn collect(value: &Value, out: &mut BTreeSet<String>) {
+        match value {
+            Value::String(s) if !s.trim().is_empty() => {
+                out.insert(s.trim().to_string());
+            }
+            Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+            Value::Object(obj) => {
+                if let Some(name) = ["table_name", "tableName", "table", "name"]
+                    .iter()
+                    .find_map(|k| obj.get(*k).and_then(|v| v.as_str()))
+                {
+                    out.insert(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut out = BTreeSet::new();
+    for key in ["target_table", "targetTable", "target_tables", "targetTables", "targets", "tables"] {
+        if let Some(v) = item.get(key) {
+            collect(v, &mut out);
+        }
+    }
+    out.into_iter().collect()
+}
+
+fn summarize(item: &Value) -> Option<IntegrationSummary> {
+    let id = first_str(item, &["id", "integration_id", "uuid"])?;
+    let config = item.get("config").or_else(|| item.get("settings")).unwrap_or(&Value::Null);
+    let lookup = |keys: &[&str]| first_str(item, keys).or_else(|| first_str(config, keys));
+
+    let mut tables = target_tables(item);
+    if tables.is_empty() {
+        tables = target_tables(config);
+    }
+
+    Some(IntegrationSummary {
+        name: lookup(&["name", "title", "label"]).unwrap_or_else(|| format!("Integration {}", id)),
+        source_system: lookup(&["source_system", "sourceSystem", "source", "connector", "provider", "type"]),
+        schedule: lookup(&["schedule", "cron", "frequency", "interval", "sync_frequency"]),
+        target_tables: tables,
+        enabled: item
+            .get("enabled")
+            .or_else(|| item.get("active"))
+            .or_else(|| item.get("is_active"))
+            .and_then(|v| v.as_bool()),
+        id,
+    })
+}
+
+fn status_label(enabled: Option<bool>) -> &'static str {
+    match enabled {
+        Some(true) => "Enabled",
+        Some(false) => "Disabled",
+        None => "Unknown",
+    }
+}
+
+fn integration_markdown(summary: &IntegrationSummary, masked: &Value, domain: &str) -> String {
+    let mut lines = vec![
+        "---".to_string(),
+        format!("title: \"{}\"", summary.name.replace('"', "\\\"")),
+        format!("summary: \"Integration configuration for {}\"", domain),
+        "author: \"tv-client\"".to_string(),
+        format!("tags: [integration, {}]", domain),
+        "ai_generated: false".to_string(),
+        "---".to_string(),
+        String::new(),
+        format!("# {}", summary.name),
+        String::new(),
+        "*Source: integration definition (credentials masked)*".to_string(),
+        String::new(),
+        "| Property | Value |".to_string(),
+        "|----------|-------|".to_string(),
+        format!("| **ID** | `{}` |", summary.id),
+        format!("| **Source System** | {} |", markdown_table_cell(summary.source_system.as_deref().unwrap_or("Unknown"))),
+        format!("| **Schedule** | {} |", markdown_table_cell(summary.schedule.as_deref().unwrap_or("Not scheduled"))),
+        format!("| **Status** | {} |", status_label(summary.enabled)),
+        String::new(),
+        "## Target Tables".to_string(),
+        String::new(),
+    ];
+    if summary.target_tables.is_empty() {
+        lines.push("No target tables found in the definition.".to_string());
+    }
+    for table in &summary.target_tables {
+        lines.push(format!("- [`{}`](../../data_models/table_{}/overview.md)", table, table));
+    }
+    lines.push(String::new());
+    lines.push("## Configuration".to_string());
+    lines.push(String::new());
+    lines.push("```json".to_string());
+    lines.push(serde_json::to_string_pretty(masked).unwrap_or_default());
+    lines.push("```".to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn summary_markdown(summaries: &[IntegrationSummary], domain: &str) -> String {
+    let mut lines = vec![
+        "---".to_string(),
+        format!("title: \"Integrations - {}\"", domain),
+        format!("summary: \"{} integration configurations for {}\"", summaries.len(), domain),
+        "author: \"tv-client\"".to_string(),
+        format!("tags: [integration, {}]", domain),
+        "---".to_string(),
+        String::new(),
+        "# Integrations".to_string(),
+        String::new(),
+        "| Integration | Source System | Schedule | Target Tables | Status |".to_string(),
+        "|-------------|---------------|----------|---------------|--------|".to_string(),
+    ];
+    for s in summaries {
+        let tables = if s.target_tables.is_empty() {
+            "-".to_string()
+        } else {
+            s.target_tables.iter().map(|t| format!("`{}`", t)).collect::<Vec<_>>().join(", ")
+        };
+        lines.push(format!(
+            "| [{}](integration_{}/overview.md) | {} | {} | {} | {} |",
+            markdown_table_cell(&s.name),
+            s.id,
+            markdown_table_cell(s.source_system.as_deref().unwrap_or("-")),
+            markdown_table_cell(s.schedule.as_deref().unwrap_or("-")),
+            tables,
+            status_label(s.enabled)
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Integrations feeding `table_name`, read from integrations/index.json
+pub(super) fn integrations_for_table(global_path: &str, table_name: &str) -> Vec<IntegrationSummary> {
+    let path = Path::new(global_path).join("integrations").join("index.json");
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Vec<IntegrationSummary>>(&raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| s.target_tables.iter().any(|t| t == table_name))
+        .collect()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Fetch integration definitions for a domain and document them under
+/// `integrations/`. Credentials are masked before anything touches disk.
+#[command]
+pub async fn val_extract_integrations(domain: String) -> CmdResult<ExtractResult> {
+    let start = Instant::now();
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = &domain_config.global_path;
+    let base_url = format!("https://{}.thinkval.io", domain_config.api_domain());
+
+    let (token, _) = auth::ensure_auth(&domain).await?;
+    let data = match val_api_fetch(&base_url, &token, "all-integrations", None).await {
+        Ok(data) => data,
+        Err(e) if e.is_auth_error() => {
+            let (new_token, _) = auth::reauth(&domain).await?;
+            val_api_fetch(&base_url, &new_token, "all-integrations", None)
+                .await
+                .map_err(|e| CommandError::Network(format!("Fetch integrations failed after reauth: {}", e)))?
+        }
+        Err(e) => return Err(CommandError::Network(format!("Fetch integrations failed: {}", e))),
+    };
+
+    let masked = mask_credentials(&data);
+    write_json(&format!("{}/schema/all_integrations.json", global_path), &masked)?;
+
+    let output_dir = format!("{}/integrations", global_path);
+    let mut summaries = Vec::new();
+    let (mut changed, mut unchanged) = (0, 0);
+    for item in extract_array(&masked, "integrations") {
+        let Some(summary) = summarize(&item) else {
+            continue;
+        };
+        let folder = format!("{}/integration_{}", output_dir, summary.id);
+        let wrote_def = write_json_if_changed(&format!("{}/definition.json", folder), &item)?;
+        let wrote_md = write_text_if_changed(
+            &format!("{}/overview.md", folder),
+            &integration_markdown(&summary, &item, &domain),
+        )?;
+        if wrote_def || wrote_md {
+            changed += 1;
+        } else {
+            unchanged += 1;
+        }
+        summaries.push(summary);
+    }
+    summaries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    write_json_if_changed(&format!("{}/index.json", output_dir), &serde_json::to_value(&summaries)?)?;
+    write_text_if_changed(&format!("{}/summary.md", output_dir), &summary_markdown(&summaries, &domain))?;
+
+    let count = summaries.len();
+    let duration_ms = start.elapsed().as_millis() as u64;
+    metadata::update_extraction_sync(global_path, &domain, "integrations", count, "ok", duration_ms).await;
+
+    Ok(ExtractResult {
+        domain,
+        extract_type: "integrations".to_string(),
+        count,
+        changed,
+        unchanged,
+        duration_ms,
+        status: "ok".to_string(),
+        message: format!(
+            "Extracted {} integrations ({} changed, {} unchanged)",
+            count, changed, unchanged
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masks_credentials_and_summarizes() {
+        let raw = json!({
+            "id": 42,
+            "name": "Xero invoices",
+            "connector": "xero",
+            "schedule": "0 2 * * *",
+            "is_active": true,
+            "config": {
+                "client_id": "abc",
+                "credentials": { "username": "ops", "refresh_token": "r-123" },
+                "api_key": "k-999",
+                "targets": [{ "table_name": "custom_tbl_1_2" }, "custom_tbl_1_3"]
+            }
+        });
+        let masked = mask_credentials(&raw);
+        assert_eq!(masked["config"]["client_id"], MASK);
+        assert_eq!(masked["config"]["targets"][1], "custom_tbl_1_3");
+        assert_eq!(masked["config"]["api_key"], MASK);
+        assert_eq!(masked["config"]["credentials"]["username"], MASK);
+        assert!(!masked.to_string().contains("r-123"));
+
+        let summary = summarize(&masked).unwrap();
+        assert_eq!(summary.id, "42");
+        assert_eq!(summary.source_system.as_deref(), Some("xero"));
+        assert_eq!(summary.schedule.as_deref(), Some("0 2 * * *"));
+        assert_eq!(summary.target_tables, vec!["custom_tbl_1_2", "custom_tbl_1_3"]);
+        assert_eq!(summary.enabled, Some(true));
+    }
+}
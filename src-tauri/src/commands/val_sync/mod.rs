@@ -6,18 +6,33 @@ pub mod ai_package;
 pub mod api;
 pub mod audit;
 pub mod auth;
+pub mod calc_fields;
+pub mod changes;
 pub mod claude_runner;
 pub mod config;
+pub mod context_pack;
+pub mod data_dictionary;
 pub mod dependencies;
 pub mod domain_model;
 pub mod drive;
+pub mod error_explain;
 pub mod errors;
 pub mod extract;
+pub mod fix_session;
+pub mod health_history;
+pub mod integrations;
 pub mod metadata;
 pub mod monitoring;
+pub mod notebook;
+pub mod presence;
+pub mod query_stats;
 pub mod recency;
 pub mod s3_sync;
+pub mod shape;
 pub mod sql;
 pub mod sql_gen;
 pub mod sync;
+pub mod table_compare;
+pub mod table_docs;
 pub mod table_pipeline;
+pub mod workflow_versions;
@@ -0,0 +1,789 @@
+// VAL Sync Notebook - Saved analyses combining SQL, notes and charts
+// A notebook is a markdown file in the knowledge folder. Prose stays as-is,
+// ```sql fences (optionally named: ```sql revenue) are queries run against the
+// domain in the frontmatter, and ```chart fences hold a val_shape_result spec
+// in YAML (plus `type` and `title`) drawn from a named SQL cell, or the one
+// above. Results are cached beside the notebook in .notebook-cache/, keyed by
+// domain and SQL, so reopening or exporting only re-queries changed cells.
+// An executed notebook exports to markdown or, through docgen, to PDF.
+
+use super::config::get_domain_config;
+use super::shape::{shape_rows, ShapeSpec, ShapedResult};
+use super::sql::val_execute_sql;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::tools::docgen::{generate_proposal_pdf, markdown_table_cell};
+use crate::commands::tools::docgen_theme::{escape_html, resolve_theme};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Rows kept (and cached) per SQL cell; charts are shaped from these
+const MAX_CELL_ROWS: usize = 2000;
+/// Rows of each result written into an export
+const MAX_EXPORT_ROWS: usize = 50;
+const CACHE_DIR: &str = ".notebook-cache";
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 240.0;
+const PLOT_LEFT: f64 = 56.0;
+const PLOT_TOP: f64 = 24.0;
+const PLOT_BOTTOM: f64 = 40.0;
+/// X labels drawn at most; the rest are skipped evenly
+const MAX_X_LABELS: usize = 12;
+const PALETTE: [&str; 6] = [
+    "var(--brand-primary, #00A0E3)",
+    "#F5A623",
+    "#7ED321",
+    "#9013FE",
+    "#D0021B",
+    "#4A4A4A",
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum NotebookCell {
+    Markdown {
+        text: String,
+    },
+    Sql {
+        /// From the fence (```sql revenue), else "query-<n>"
+        name: String,
+        sql: String,
+    },
+    Chart {
+        /// SQL cell the chart draws from; None when there's none above it
+        source: Option<String>,
+        /// YAML: a val_shape_result spec plus `type` and `title`
+        spec: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartType {
+    #[default]
+    Bar,
+    Line,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChartSpec {
+    #[serde(default, rename = "type")]
+    chart_type: ChartType,
+    title: Option<String>,
+    #[serde(flatten)]
+    shape: ShapeSpec,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NotebookMeta {
+    title: Option<String>,
+    domain: Option<String>,
+}
+
+#[derive(Debug)]
+struct Notebook {
+    meta: NotebookMeta,
+    cells: Vec<NotebookCell>,
+}
+
+/// One SQL cell's rows, as run (and cached)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlCellResult {
+    /// Rows the query returned, including any past MAX_CELL_ROWS
+    pub row_count: usize,
+    pub columns: Vec<String>,
+    pub rows: Vec<Value>,
+    pub truncated: bool,
+    pub executed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotebookChart {
+    pub chart_type: ChartType,
+    pub title: Option<String>,
+    pub data: ShapedResult,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotebookCellOutput {
+    /// SQL cells
+    pub result: Option<SqlCellResult>,
+    /// The result came from the cache rather than a fresh query
+    pub cached: bool,
+    /// Chart cells
+    pub chart: Option<NotebookChart>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotebookCellRun {
+    pub cell: NotebookCell,
+    /// None for markdown cells
+    pub output: Option<NotebookCellOutput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotebookRun {
+    pub path: String,
+    pub title: Option<String>,
+    pub domain: String,
+    pub cells: Vec<NotebookCellRun>,
+    /// SQL cells queried this run
+    pub executed: usize,
+    /// SQL cells answered from the cache
+    pub cached: usize,
+    /// Cells (SQL or chart) that failed
+    pub errors: usize,
+    pub ran_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotebookExportFormat {
+    Markdown,
+    Pdf,
+}
+
+/// Cached SQL results by cache_key
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotebookCache {
+    entries: HashMap<String, SqlCellResult>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// YAML frontmatter (between leading `---` lines) and the body after it
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) else {
+        return (None, content);
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            let body = after.split_once('\n').map(|(_, b)| b).unwrap_or_default();
+            (Some(&rest[..end]), body)
+        }
+        None => (None, content),
+    }
+}
+
+enum Fence {
+    /// Any other fenced block; part of the surrounding markdown
+    Other,
+    Sql(String),
+    Chart(Option<String>),
+}
+
+fn parse_notebook(content: &str) -> Notebook {
+    let (front, body) = split_frontmatter(content);
+    let meta = front.and_then(|f| serde_yaml::from_str(f).ok()).unwrap_or_default();
+
+    let mut cells = Vec::new();
+    let mut text = String::new();
+    let mut open: Option<(Fence, String)> = None;
+    let mut sql_count = 0;
+    let mut last_sql: Option<String> = None;
+    let flush = |text: &mut String, cells: &mut Vec<NotebookCell>| {
+        if !text.trim().is_empty() {
+            cells.push(NotebookCell::Markdown { text: text.trim().to_string() });
+        }
+        text.clear();
+    };
+
+    for line in body.lines() {
+        let trimmed = line.trim_end();
+        match open.take() {
+            None => {
+                let Some(info) = trimmed.strip_prefix("```") else {
+                    text.push_str(line);
+                    text.push('\n');
+                    continue;
+                };
+                let mut parts = info.split_whitespace();
+                let fence = match parts.next() {
+                    Some("sql") => {
+                        flush(&mut text, &mut cells);
+                        sql_count += 1;
+                        Fence::Sql(parts.next().map(String::from).unwrap_or_else(|| format!("query-{}", sql_count)))
+                    }
+                    Some("chart") => {
+                        flush(&mut text, &mut cells);
+                        Fence::Chart(parts.next().map(String::from))
+                    }
+                    _ => {
+                        text.push_str(line);
+                        text.push('\n');
+                        Fence::Other
+                    }
+                };
+                open = Some((fence, String::new()));
+            }
+            Some((Fence::Other, buf)) => {
+                text.push_str(line);
+                text.push('\n');
+                if trimmed != "```" {
+                    open = Some((Fence::Other, buf));
+                }
+            }
+            Some((fence, mut buf)) if trimmed != "```" => {
+                buf.push_str(line);
+                buf.push('\n');
+                open = Some((fence, buf));
+            }
+            Some((fence, buf)) => close_fence(fence, buf, &mut cells, &mut last_sql),
+        }
+    }
+    // An unclosed fence runs to the end of the file
+    if let Some((fence, buf)) = open {
+        close_fence(fence, buf, &mut cells, &mut last_sql);
+    }
+    flush(&mut text, &mut cells);
+
+    Notebook { meta, cells }
+}
+
+fn close_fence(fence: Fence, buf: String, cells: &mut Vec<NotebookCell>, last_sql: &mut Option<String>) {
+    match fence {
+        Fence::Other => {}
+        Fence::Sql(name) => {
+            *last_sql = Some(name.clone());
+            cells.push(NotebookCell::Sql { name, sql: buf.trim().to_string() });
+        }
+        Fence::Chart(source) => cells.push(NotebookCell::Chart {
+            source: source.or_else(|| last_sql.clone()),
+            spec: buf,
+        }),
+    }
+}
+
+// ============================================================================
+// Running
+// ============================================================================
+
+fn cache_path(notebook: &Path) -> PathBuf {
+    let name = notebook.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    notebook
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(CACHE_DIR)
+        .join(format!("{}.json", name))
+}
+
+fn cache_key(domain: &str, sql: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(domain.as_bytes());
+    hasher.update([0]);
+    hasher.update(sql.trim().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn load_cache(path: &Path) -> NotebookCache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Shape a chart cell from the rows of the SQL cell it names
+fn run_chart(source: Option<&str>, spec: &str, rows: &HashMap<String, Vec<Value>>) -> Result<NotebookChart, String> {
+    let source = source.ok_or("Chart has no SQL cell above it to draw from")?;
+    let rows = rows
+        .get(source)
+        .ok_or_else(|| format!("SQL cell '{}' has no results", source))?;
+    let spec: ChartSpec = serde_yaml::from_str(spec).map_err(|e| format!("Invalid chart spec: {}", e))?;
+    let data = shape_rows(rows, &spec.shape).map_err(|e| e.to_string())?;
+    Ok(NotebookChart {
+        chart_type: spec.chart_type,
+        title: spec.title,
+        data,
+    })
+}
+
+async fn run_notebook(path: &str, domain: Option<String>, refresh: bool) -> CmdResult<NotebookRun> {
+    let notebook_path = Path::new(path);
+    if !notebook_path.is_file() {
+        return Err(CommandError::NotFound(format!("Notebook not found: {}", path)));
+    }
+    let notebook = parse_notebook(&fs::read_to_string(notebook_path)?);
+    let domain = domain
+        .or_else(|| notebook.meta.domain.clone())
+        .filter(|d| !d.trim().is_empty())
+        .ok_or_else(|| CommandError::Validation {
+            message: "Notebook has no domain; add `domain:` to its frontmatter or pass one".to_string(),
+            fields: vec!["domain".to_string()],
+        })?;
+    get_domain_config(&domain)?;
+
+    let cache_file = cache_path(notebook_path);
+    let cache = load_cache(&cache_file);
+    // Only entries still used are written back, so edited cells drop out
+    let mut kept = NotebookCache::default();
+    let mut rows_by_cell: HashMap<String, Vec<Value>> = HashMap::new();
+    let (mut executed, mut cached, mut errors) = (0, 0, 0);
+    let mut cells = Vec::new();
+
+    for cell in notebook.cells {
+        let output = match &cell {
+            NotebookCell::Markdown { .. } => None,
+            NotebookCell::Sql { name, sql } => {
+                let key = cache_key(&domain, sql);
+                let mut output = NotebookCellOutput::default();
+                match cache.entries.get(&key).filter(|_| !refresh) {
+                    Some(hit) => {
+                        output.result = Some(hit.clone());
+                        output.cached = true;
+                        cached += 1;
+                    }
+                    None => {
+                        executed += 1;
+                        match val_execute_sql(domain.clone(), sql.clone(), Some(MAX_CELL_ROWS)).await {
+                            Ok(r) => match r.error {
+                                Some(e) => output.error = Some(e),
+                                None => {
+                                    output.result = Some(SqlCellResult {
+                                        row_count: r.row_count,
+                                        columns: r.columns,
+                                        rows: r.data,
+                                        truncated: r.truncated,
+                                        executed_at: chrono::Utc::now().to_rfc3339(),
+                                    })
+                                }
+                            },
+                            Err(e) => output.error = Some(e.to_string()),
+                        }
+                    }
+                }
+                match &output.result {
+                    Some(result) => {
+                        kept.entries.insert(key, result.clone());
+                        rows_by_cell.insert(name.clone(), result.rows.clone());
+                    }
+                    None => {
+                        errors += 1;
+                        rows_by_cell.remove(name);
+                    }
+                }
+                Some(output)
+            }
+            NotebookCell::Chart { source, spec } => {
+                let mut output = NotebookCellOutput::default();
+                match run_chart(source.as_deref(), spec, &rows_by_cell) {
+                    Ok(chart) => output.chart = Some(chart),
+                    Err(e) => {
+                        output.error = Some(e);
+                        errors += 1;
+                    }
+                }
+                Some(output)
+            }
+        };
+        cells.push(NotebookCellRun { cell, output });
+    }
+
+    if let Some(dir) = cache_file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&cache_file, serde_json::to_string(&kept)?)?;
+
+    Ok(NotebookRun {
+        path: path.to_string(),
+        title: notebook.meta.title,
+        domain,
+        cells,
+        executed,
+        cached,
+        errors,
+        ran_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn number_text(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        (value as i64).to_string()
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+fn markdown_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = format!(
+        "| {} |\n|{}|\n",
+        header.iter().map(|h| markdown_table_cell(h)).collect::<Vec<_>>().join(" | "),
+        vec![" --- "; header.len()].join("|")
+    );
+    for row in rows {
+        out.push_str(&format!("| {} |\n", row.iter().map(|c| markdown_table_cell(c)).collect::<Vec<_>>().join(" | ")));
+    }
+    out
+}
+
+fn result_table(result: &SqlCellResult) -> String {
+    if result.columns.is_empty() {
+        return "*No rows*\n".to_string();
+    }
+    let rows: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .take(MAX_EXPORT_ROWS)
+        .map(|row| result.columns.iter().map(|c| value_text(row.get(c))).collect())
+        .collect();
+    let shown = rows.len();
+    let note = if shown < result.row_count {
+        format!("*First {} of {} rows*", shown, result.row_count)
+    } else {
+        format!("*{} rows*", result.row_count)
+    };
+    format!("{}\n{}\n", markdown_table(&result.columns, &rows), note)
+}
+
+/// Categories down, one column per series
+fn chart_table(chart: &NotebookChart) -> String {
+    let mut header = vec![String::new()];
+    header.extend(chart.data.series.iter().map(|s| s.name.clone()));
+    let rows: Vec<Vec<String>> = chart
+        .data
+        .categories
+        .iter()
+        .enumerate()
+        .map(|(i, category)| {
+            let mut row = vec![category.clone()];
+            row.extend(
+                chart
+                    .data
+                    .series
+                    .iter()
+                    .map(|s| s.values.get(i).copied().flatten().map(number_text).unwrap_or_default()),
+            );
+            row
+        })
+        .collect();
+    markdown_table(&header, &rows)
+}
+
+/// Round an axis maximum up to 1, 2 or 5 × a power of ten
+fn nice_ceiling(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|v| *v >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Bar or line chart of the shaped series. Kept free of blank lines so
+/// markdown passes it through as one HTML block.
+fn chart_svg(chart: &NotebookChart) -> String {
+    let data = &chart.data;
+    let n = data.categories.len().max(1);
+    let values = data.series.iter().flat_map(|s| s.values.iter().flatten().copied());
+    let (min, max) = values.fold((0.0f64, 0.0f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let hi = nice_ceiling(max);
+    let lo = if min < 0.0 { -nice_ceiling(-min) } else { 0.0 };
+    let plot_w = CHART_WIDTH - PLOT_LEFT - 8.0;
+    let plot_h = CHART_HEIGHT - PLOT_TOP - PLOT_BOTTOM;
+    let y = |v: f64| PLOT_TOP + plot_h * (hi - v) / (hi - lo);
+    let slot = plot_w / n as f64;
+    let x_center = |i: usize| PLOT_LEFT + slot * (i as f64 + 0.5);
+
+    let mut parts = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="10">"#,
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT
+    )];
+    for v in [lo, (lo + hi) / 2.0, hi] {
+        parts.push(format!(
+            r##"<line x1="{x1}" y1="{y:.1}" x2="{x2}" y2="{y:.1}" stroke="#E5E5E5"/><text x="{tx}" y="{ty:.1}" text-anchor="end" fill="#666">{label}</text>"##,
+            x1 = PLOT_LEFT,
+            x2 = PLOT_LEFT + plot_w,
+            y = y(v),
+            tx = PLOT_LEFT - 6.0,
+            ty = y(v) + 3.0,
+            label = number_text(v)
+        ));
+    }
+    let step = n.div_ceil(MAX_X_LABELS);
+    for (i, category) in data.categories.iter().enumerate().filter(|(i, _)| i % step == 0) {
+        parts.push(format!(
+            r##"<text x="{:.1}" y="{:.1}" text-anchor="middle" fill="#666">{}</text>"##,
+            x_center(i),
+            CHART_HEIGHT - PLOT_BOTTOM + 14.0,
+            escape_html(&category.chars().take(14).collect::<String>())
+        ));
+    }
+
+    let series_count = data.series.len().max(1);
+    for (s, series) in data.series.iter().enumerate() {
+        let color = PALETTE[s % PALETTE.len()];
+        match chart.chart_type {
+            ChartType::Bar => {
+                let bar_w = slot * 0.8 / series_count as f64;
+                for (i, v) in series.values.iter().enumerate() {
+                    let Some(v) = v else { continue };
+                    let (top, bottom) = (y(v.max(0.0)), y(v.min(0.0)));
+                    parts.push(format!(
+                        r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                        PLOT_LEFT + slot * i as f64 + slot * 0.1 + bar_w * s as f64,
+                        top,
+                        bar_w,
+                        bottom - top,
+                        color
+                    ));
+                }
+            }
+            ChartType::Line => {
+                let points: Vec<String> = series
+                    .values
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, v)| v.map(|v| format!("{:.1},{:.1}", x_center(i), y(v))))
+                    .collect();
+                parts.push(format!(
+                    r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                    points.join(" "),
+                    color
+                ));
+            }
+        }
+        if data.series.len() > 1 {
+            let lx = PLOT_LEFT + 110.0 * s as f64;
+            parts.push(format!(
+                r##"<rect x="{:.1}" y="6" width="10" height="10" fill="{}"/><text x="{:.1}" y="15" fill="#333">{}</text>"##,
+                lx,
+                color,
+                lx + 14.0,
+                escape_html(&series.name.chars().take(16).collect::<String>())
+            ));
+        }
+    }
+    parts.push("</svg>".to_string());
+    format!(r#"<div class="chart">{}</div>"#, parts.concat())
+}
+
+/// The executed notebook as markdown. With `html_charts`, charts are inline
+/// SVG (for the PDF); otherwise a table of the charted values.
+fn render_notebook(run: &NotebookRun, html_charts: bool) -> String {
+    let mut out = String::new();
+    if let Some(title) = &run.title {
+        out.push_str(&format!("# {}\n\n", title));
+    }
+    let ran_at = chrono::DateTime::parse_from_rfc3339(&run.ran_at)
+        .map(|t| t.with_timezone(&chrono::Local).format("%-d %b %Y %H:%M").to_string())
+        .unwrap_or_else(|_| run.ran_at.clone());
+    out.push_str(&format!("*Domain: {} · Run {}*\n\n", run.domain, ran_at));
+
+    for cell_run in &run.cells {
+        let output = cell_run.output.as_ref();
+        match &cell_run.cell {
+            NotebookCell::Markdown { text } => out.push_str(&format!("{}\n\n", text)),
+            NotebookCell::Sql { sql, .. } => {
+                out.push_str(&format!("```sql\n{}\n```\n\n", sql));
+                if let Some(result) = output.and_then(|o| o.result.as_ref()) {
+                    out.push_str(&format!("{}\n", result_table(result)));
+                }
+            }
+            NotebookCell::Chart { .. } => {
+                if let Some(chart) = output.and_then(|o| o.chart.as_ref()) {
+                    if let Some(title) = &chart.title {
+                        out.push_str(&format!("**{}**\n\n", title));
+                    }
+                    let body = if html_charts { chart_svg(chart) } else { chart_table(chart) };
+                    out.push_str(&format!("{}\n\n", body));
+                }
+            }
+        }
+        if let Some(error) = output.and_then(|o| o.error.as_ref()) {
+            out.push_str(&format!("> **Error:** {}\n\n", markdown_table_cell(error)));
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Run a notebook: SQL cells against `domain` (default: the notebook's
+/// frontmatter `domain`), reusing cached results for unchanged SQL unless
+/// `refresh`, then chart cells from those results. Failed cells carry an
+/// error; the run itself only fails on a missing notebook or domain.
+#[command]
+pub async fn val_run_notebook(path: String, domain: Option<String>, refresh: Option<bool>) -> CmdResult<NotebookRun> {
+    run_notebook(&path, domain, refresh.unwrap_or(false)).await
+}
+
+/// Run a notebook (cached results allowed, as in val_run_notebook) and write
+/// the executed notebook to `output_path` — by default `<name>.executed.md` or
+/// `<name>.pdf` beside it. Returns the written path.
+#[command]
+pub async fn val_export_notebook(
+    path: String,
+    format: NotebookExportFormat,
+    output_path: Option<String>,
+    domain: Option<String>,
+    refresh: Option<bool>,
+) -> CmdResult<String> {
+    let run = run_notebook(&path, domain, refresh.unwrap_or(false)).await?;
+    let output_path = output_path.unwrap_or_else(|| {
+        let notebook = Path::new(&path);
+        let stem = notebook.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let name = match format {
+            NotebookExportFormat::Markdown => format!("{}.executed.md", stem),
+            NotebookExportFormat::Pdf => format!("{}.pdf", stem),
+        };
+        notebook.with_file_name(name).to_string_lossy().to_string()
+    });
+    if let Some(dir) = Path::new(&output_path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    match format {
+        NotebookExportFormat::Markdown => {
+            fs::write(&output_path, render_notebook(&run, false))?;
+            Ok(output_path)
+        }
+        NotebookExportFormat::Pdf => {
+            let theme = resolve_theme(Some(Path::new(&output_path)));
+            generate_proposal_pdf(&render_notebook(&run, true), &output_path, &theme)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::val_sync::shape::ShapedSeries;
+    use serde_json::json;
+
+    const NOTEBOOK: &str = "---\ntitle: Outlet revenue\ndomain: koi\n---\n\
+# Revenue\n\nMonthly numbers.\n\n\
+```sql revenue\nSELECT month, amount\nFROM sales\n```\n\n\
+```chart\ntype: line\ntitle: By month\nx: month\ny:\n  - column: amount\n    agg: sum\n```\n\n\
+Some code that isn't a cell:\n\n```python\nprint(1)\n```\n\n\
+```sql\nSELECT 1\n```\n";
+
+    #[test]
+    fn parses_cells_and_charts_from_rows() {
+        let notebook = parse_notebook(NOTEBOOK);
+        assert_eq!(notebook.meta.title.as_deref(), Some("Outlet revenue"));
+        assert_eq!(notebook.meta.domain.as_deref(), Some("koi"));
+        assert_eq!(notebook.cells.len(), 5);
+        assert_eq!(notebook.cells[0], NotebookCell::Markdown { text: "# Revenue\n\nMonthly numbers.".to_string() });
+        assert_eq!(
+            notebook.cells[1],
+            NotebookCell::Sql { name: "revenue".to_string(), sql: "SELECT month, amount\nFROM sales".to_string() }
+        );
+        let NotebookCell::Chart { source, spec } = &notebook.cells[2] else {
+            panic!("expected a chart cell");
+        };
+        assert_eq!(source.as_deref(), Some("revenue"));
+        // Other fences stay in the markdown
+        assert!(matches!(&notebook.cells[3], NotebookCell::Markdown { text } if text.ends_with("```python\nprint(1)\n```")));
+        assert_eq!(notebook.cells[4], NotebookCell::Sql { name: "query-2".to_string(), sql: "SELECT 1".to_string() });
+
+        let rows = HashMap::from([(
+            "revenue".to_string(),
+            vec![
+                json!({ "month": "2026-08", "amount": 10 }),
+                json!({ "month": "2026-09", "amount": 5 }),
+                json!({ "month": "2026-09", "amount": 2.5 }),
+            ],
+        )]);
+        let chart = run_chart(source.as_deref(), spec, &rows).unwrap();
+        assert_eq!(chart.chart_type, ChartType::Line);
+        assert_eq!(chart.data.series[0].values, vec![Some(10.0), Some(7.5)]);
+        assert!(run_chart(Some("missing"), spec, &rows).unwrap_err().contains("'missing'"));
+        assert!(run_chart(None, spec, &rows).is_err());
+
+        // Same SQL, other domain: a different cache entry
+        assert_eq!(cache_key("koi", "SELECT 1\n"), cache_key("koi", "SELECT 1"));
+        assert_ne!(cache_key("koi", "SELECT 1"), cache_key("suntec", "SELECT 1"));
+    }
+
+    #[test]
+    fn renders_results_charts_and_errors() {
+        let chart = NotebookChart {
+            chart_type: ChartType::Bar,
+            title: Some("By month".to_string()),
+            data: ShapedResult {
+                categories: vec!["2026-08".to_string(), "2026-09".to_string()],
+                series: vec![ShapedSeries {
+                    name: "sum(amount)".to_string(),
+                    metric: "sum(amount)".to_string(),
+                    values: vec![Some(10.0), Some(7.5)],
+                }],
+                row_count: 3,
+                skipped_rows: 0,
+            },
+        };
+        let run = NotebookRun {
+            path: "/kb/revenue.md".to_string(),
+            title: Some("Outlet revenue".to_string()),
+            domain: "koi".to_string(),
+            cells: vec![
+                NotebookCellRun {
+                    cell: NotebookCell::Sql { name: "revenue".to_string(), sql: "SELECT month, amount FROM sales".to_string() },
+                    output: Some(NotebookCellOutput {
+                        result: Some(SqlCellResult {
+                            row_count: 120,
+                            columns: vec!["month".to_string(), "amount".to_string()],
+                            rows: vec![json!({ "month": "2026-08", "amount": 10 }), json!({ "month": "2026-09", "amount": null })],
+                            truncated: false,
+                            executed_at: "2026-10-16T06:00:00Z".to_string(),
+                        }),
+                        cached: true,
+                        ..Default::default()
+                    }),
+                },
+                NotebookCellRun {
+                    cell: NotebookCell::Chart { source: Some("revenue".to_string()), spec: String::new() },
+                    output: Some(NotebookCellOutput { chart: Some(chart), ..Default::default() }),
+                },
+                NotebookCellRun {
+                    cell: NotebookCell::Sql { name: "broken".to_string(), sql: "SELECT nope".to_string() },
+                    output: Some(NotebookCellOutput { error: Some("SQL error (400): no | column".to_string()), ..Default::default() }),
+                },
+            ],
+            executed: 1,
+            cached: 1,
+            errors: 1,
+            ran_at: "2026-10-16T06:00:00Z".to_string(),
+        };
+
+        let markdown = render_notebook(&run, false);
+        assert!(markdown.starts_with("# Outlet revenue\n\n*Domain: koi · Run "));
+        assert!(markdown.contains("| month | amount |\n| --- | --- |\n| 2026-08 | 10 |\n| 2026-09 |  |\n\n*First 2 of 120 rows*"));
+        assert!(markdown.contains("**By month**\n\n|  | sum(amount) |\n| --- | --- |\n| 2026-08 | 10 |\n| 2026-09 | 7.50 |"));
+        assert!(markdown.contains("```sql\nSELECT nope\n```\n\n> **Error:** SQL error (400): no / column"));
+
+        let html = render_notebook(&run, true);
+        let svg = html.lines().find(|l| l.starts_with("<div class=\"chart\">")).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(!svg.contains("<polyline"));
+    }
+}
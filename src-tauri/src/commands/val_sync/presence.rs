@@ -0,0 +1,308 @@
+// VAL Presence Matrix - Which tables exist in which domains.
+// Built from each domain's schema/all_tables.json (tables defined in VAL) and
+// recency.json (physical tables with live row counts). With `live`, recency is
+// re-collected per domain first. Output: presence_matrix.json + presence_matrix.md.
+
+use super::ai_package::flatten_domain_tables;
+use super::config::load_config_internal;
+use super::recency::{val_collect_recency, RecencyReport};
+use super::sync::write_json;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::tools::docgen::markdown_table_cell;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::command;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceCell {
+    /// Defined in the domain's VAL table tree
+    pub defined: bool,
+    /// Physical table seen in recency stats
+    pub physical: bool,
+    pub row_count: Option<i64>,
+    pub last_updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceRow {
+    pub table_id: String,
+    pub display_name: String,
+    /// Number of domains where the table is present
+    pub present_in: usize,
+    pub domains: BTreeMap<String, PresenceCell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceMatrix {
+    pub generated_at: String,
+    pub table_pattern: String,
+    pub domains: Vec<String>,
+    pub tables: Vec<PresenceRow>,
+    /// Domains whose data could not be read (or live-refreshed)
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceMatrixResult {
+    pub json_path: String,
+    pub markdown_path: String,
+    pub domain_count: usize,
+    pub table_count: usize,
+    pub warnings: Vec<String>,
+    pub duration_ms: u64,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Case-insensitive wildcard match (`*` = any run of characters). A pattern
+/// without `*` matches as a substring.
+pub fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let text = text.to_lowercase();
+    if pattern.is_empty() || pattern == "*" {
+        return true;
+    }
+    if !pattern.contains('*') {
+        return text.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut pos = 0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i == 0 {
+            if !text.starts_with(part) {
+                return false;
+            }
+            pos = part.len();
+        } else if i == parts.len() - 1 {
+            return text.len() >= pos + part.len() && text[pos..].ends_with(part);
+        } else {
+            match text[pos..].find(part) {
+                Some(idx) => pos += idx + part.len(),
+                None => return false,
+            }
+        }
+    }
+    true
+}
+
+fn read_defined_tables(global_path: &Path) -> CmdResult<Vec<(String, String)>> {
+    let path = global_path.join("schema").join("all_tables.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let parsed: Vec<serde_json::Value> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    Ok(flatten_domain_tables(&parsed)
+        .into_iter()
+        .map(|t| (t.table_id, t.display_name))
+        .collect())
+}
+
+fn read_recency(global_path: &Path) -> Option<RecencyReport> {
+    let content = fs::read_to_string(global_path.join("recency.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn default_output_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val")
+        .join("presence")
+}
+
+fn format_count(n: i64) -> String {
+    match n {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+        n if n >= 10_000 => format!("{}k", n / 1000),
+        n => n.to_string(),
+    }
+}
+
+fn render_markdown(matrix: &PresenceMatrix) -> String {
+    let mut md = format!(
+        "# Table presence matrix\n\nPattern: `{}` · {} tables × {} domains · generated {}\n\n",
+        matrix.table_pattern,
+        matrix.tables.len(),
+        matrix.domains.len(),
+        matrix.generated_at
+    );
+    md.push_str("Cells show live row count; `def` = defined in VAL but no physical stats, `—` = absent.\n\n");
+
+    md.push_str("| Table | Present |");
+    for d in &matrix.domains {
+        md.push_str(&format!(" {} |", d));
+    }
+    md.push_str("\n|---|---|");
+    for _ in &matrix.domains {
+        md.push_str("---|");
+    }
+    md.push('\n');
+
+    for row in &matrix.tables {
+        let label = if row.display_name.is_empty() || row.display_name == row.table_id {
+            format!("`{}`", row.table_id)
+        } else {
+            format!("{} `{}`", markdown_table_cell(&row.display_name), row.table_id)
+        };
+        md.push_str(&format!("| {} | {}/{} |", label, row.present_in, matrix.domains.len()));
+        for d in &matrix.domains {
+            let cell = match row.domains.get(d) {
+                Some(c) if c.row_count.is_some() => format_count(c.row_count.unwrap_or(0)),
+                Some(c) if c.defined => "def".to_string(),
+                _ => "—".to_string(),
+            };
+            md.push_str(&format!(" {} |", cell));
+        }
+        md.push('\n');
+    }
+
+    if !matrix.warnings.is_empty() {
+        md.push_str("\n## Warnings\n\n");
+        for w in &matrix.warnings {
+            md.push_str(&format!("- {}\n", w));
+        }
+    }
+    md
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Build a cross-domain presence matrix for tables matching `table_pattern`
+/// (wildcard `*`, matched against table id and display name).
+#[command]
+pub async fn val_generate_presence_matrix(
+    table_pattern: String,
+    domains: Option<Vec<String>>,
+    live: Option<bool>,
+    output_dir: Option<String>,
+) -> CmdResult<PresenceMatrixResult> {
+    let start = Instant::now();
+    let config = load_config_internal()?;
+    let targets: Vec<_> = config
+        .domains
+        .iter()
+        .filter(|d| domains.as_ref().map(|list| list.contains(&d.domain)).unwrap_or(true))
+        .collect();
+    if targets.is_empty() {
+        return Err(CommandError::NotFound("No matching domains configured".to_string()));
+    }
+
+    let mut warnings = Vec::new();
+    let mut rows: HashMap<String, PresenceRow> = HashMap::new();
+    let domain_names: Vec<String> = targets.iter().map(|d| d.domain.clone()).collect();
+
+    for domain in &targets {
+        let global_path = Path::new(&domain.global_path);
+
+        if live.unwrap_or(false) {
+            if let Err(e) = val_collect_recency(domain.domain.clone()).await {
+                warnings.push(format!("{}: live refresh failed — using cached recency ({})", domain.domain, e));
+            }
+        }
+
+        let defined = match read_defined_tables(global_path) {
+            Ok(t) => t,
+            Err(e) => {
+                warnings.push(format!("{}: could not read all_tables.json ({})", domain.domain, e));
+                Vec::new()
+            }
+        };
+        let recency = read_recency(global_path);
+        if recency.is_none() {
+            warnings.push(format!("{}: no recency.json — row counts unavailable", domain.domain));
+        }
+
+        let mut cells: HashMap<String, (String, PresenceCell)> = HashMap::new();
+        for (table_id, display_name) in defined {
+            cells.entry(table_id).or_insert((
+                display_name,
+                PresenceCell { defined: true, physical: false, row_count: None, last_updated: None },
+            ));
+        }
+        if let Some(report) = &recency {
+            for (table_id, stats) in &report.tables {
+                let entry = cells.entry(table_id.clone()).or_insert((
+                    String::new(),
+                    PresenceCell { defined: false, physical: false, row_count: None, last_updated: None },
+                ));
+                entry.1.physical = true;
+                entry.1.row_count = Some(stats.row_count);
+                entry.1.last_updated = stats.last_autoanalyze.clone();
+            }
+        }
+
+        for (table_id, (display_name, cell)) in cells {
+            if !matches_pattern(&table_pattern, &table_id) && !matches_pattern(&table_pattern, &display_name) {
+                continue;
+            }
+            let row = rows.entry(table_id.clone()).or_insert_with(|| PresenceRow {
+                table_id,
+                display_name: String::new(),
+                present_in: 0,
+                domains: BTreeMap::new(),
+            });
+            if row.display_name.is_empty() {
+                row.display_name = display_name;
+            }
+            row.present_in += 1;
+            row.domains.insert(domain.domain.clone(), cell);
+        }
+    }
+
+    let mut tables: Vec<PresenceRow> = rows.into_values().collect();
+    tables.sort_by(|a, b| b.present_in.cmp(&a.present_in).then(a.table_id.cmp(&b.table_id)));
+
+    let matrix = PresenceMatrix {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        table_pattern: table_pattern.clone(),
+        domains: domain_names,
+        tables,
+        warnings,
+    };
+
+    let dir = output_dir.map(PathBuf::from).unwrap_or_else(default_output_dir);
+    let json_path = dir.join("presence_matrix.json").to_string_lossy().to_string();
+    let markdown_path = dir.join("presence_matrix.md");
+    write_json(&json_path, &serde_json::to_value(&matrix)?)?;
+    fs::write(&markdown_path, render_markdown(&matrix))?;
+
+    Ok(PresenceMatrixResult {
+        json_path,
+        markdown_path: markdown_path.to_string_lossy().to_string(),
+        domain_count: matrix.domains.len(),
+        table_count: matrix.tables.len(),
+        warnings: matrix.warnings,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(matches_pattern("*", "custom_tbl_1_2"));
+        assert!(matches_pattern("invoice", "AP Invoice Lines"));
+        assert!(matches_pattern("custom_tbl_*", "custom_tbl_12_34"));
+        assert!(matches_pattern("*_lines", "invoice_lines"));
+        assert!(matches_pattern("cust*tbl*34", "custom_tbl_12_34"));
+        assert!(!matches_pattern("custom_tbl_*", "tbl_custom"));
+        assert!(!matches_pattern("*_lines", "lines_total"));
+    }
+}
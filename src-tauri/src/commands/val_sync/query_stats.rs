@@ -0,0 +1,389 @@
+// VAL Sync Query Stats - Execution statistics for extracted queries
+// listAllDSQueries carries no run history, so each query gets a lightweight
+// COUNT(*) probe through the SQL endpoint. Results accumulate in
+// {globalPath}/schema/query_stats.json next to all_queries.json, keeping the
+// last few outcomes per query so error rates survive across runs.
+
+use super::auth;
+use super::config::get_domain_config;
+use super::extract::extract_array;
+use super::metadata;
+use super::sql::execute_sql_internal;
+use super::sync::{write_json, SyncResult};
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+use tauri::command;
+
+/// Probe outcomes kept per query for the error rate
+const HISTORY_LEN: usize = 10;
+/// Probes slower than this mark a query as heavy
+const HEAVY_DURATION_MS: u64 = 5_000;
+/// Probes returning more rows than this mark a query as heavy
+const HEAVY_ROWS: i64 = 1_000_000;
+const CONCURRENCY: usize = 4;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeOutcome {
+    pub at: String,
+    pub duration_ms: u64,
+    pub ok: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStats {
+    pub id: String,
+    pub name: String,
+    pub table_id: Option<String>,
+    /// "sql" (wrapped query SQL), "table" (count of the source table — an upper
+    /// bound, query filters are not applied) or "none" (nothing to probe)
+    pub probe: String,
+    pub last_run_at: Option<String>,
+    pub last_duration_ms: Option<u64>,
+    pub rows_returned: Option<i64>,
+    pub last_error: Option<String>,
+    pub error_rate: f64,
+    pub heavy: bool,
+    pub broken: bool,
+    #[serde(default)]
+    pub history: Vec<ProbeOutcome>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStatsFile {
+    pub domain: String,
+    pub generated_at: String,
+    pub queries: Vec<QueryStats>,
+}
+
+/// What to run for one query
+struct ProbeTarget {
+    id: String,
+    name: String,
+    table_id: Option<String>,
+    probe: &'static str,
+    sql: Option<String>,
+}
+
+struct ProbeResult {
+    duration_ms: u64,
+    rows: Option<i64>,
+    error: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn probe_target(query: &Value) -> Option<ProbeTarget> {
+    let id = match query.get("id") {
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::String(s)) if !s.is_empty() => s.clone(),
+        _ => return None,
+    };
+    let name = query
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&id)
+        .to_string();
+    let query_info = query.get("datasource").and_then(|ds| ds.get("queryInfo"));
+    let table_id = query_info
+        .and_then(|qi| qi.get("tableInfo"))
+        .and_then(|ti| ti.get("id"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+    let raw_sql = query_info
+        .and_then(|qi| qi.get("sql"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().trim_end_matches(';').to_string())
+        .filter(|s| is_select(s));
+
+    let (probe, sql) = match (raw_sql, &table_id) {
+        (Some(sql), _) => ("sql", Some(format!("SELECT COUNT(*) AS row_count FROM ({}) AS probe", sql))),
+        (None, Some(table)) if is_identifier(table) => {
+            ("table", Some(format!("SELECT COUNT(*) AS row_count FROM {}", table)))
+        }
+        _ => ("none", None),
+    };
+
+    Some(ProbeTarget {
+        id,
+        name,
+        table_id,
+        probe,
+        sql,
+    })
+}
+
+fn is_select(sql: &str) -> bool {
+    let upper = sql.to_uppercase();
+    upper.starts_with("SELECT") || upper.starts_with("WITH")
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_auth_error(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("401")
+        || lower.contains("403")
+        || lower.contains("unauthorized")
+        || lower.contains("token not authentic")
+        || lower.contains("jwt expired")
+}
+
+fn parse_count(row: &Value) -> Option<i64> {
+    match row.get("row_count")? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+async fn run_probe(token: &str, api_domain: &str, sql: &str) -> ProbeResult {
+    let start = Instant::now();
+    let outcome = execute_sql_internal(token, api_domain, sql, 1).await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+    match outcome {
+        Ok(response) => ProbeResult {
+            duration_ms,
+            rows: response
+                .data
+                .as_ref()
+                .and_then(|rows| rows.first())
+                .and_then(parse_count),
+            error: None,
+        },
+        Err(e) => ProbeResult {
+            duration_ms,
+            rows: None,
+            error: Some(e),
+        },
+    }
+}
+
+/// Fold a probe into the query's previous stats
+fn merge_stats(
+    target_id: &str,
+    name: &str,
+    previous: Option<QueryStats>,
+    probe: &str,
+    result: Option<&ProbeResult>,
+    at: &str,
+) -> QueryStats {
+    let mut history = previous.map(|p| p.history).unwrap_or_default();
+    if let Some(r) = result {
+        history.push(ProbeOutcome {
+            at: at.to_string(),
+            duration_ms: r.duration_ms,
+            ok: r.error.is_none(),
+        });
+        if history.len() > HISTORY_LEN {
+            history.drain(..history.len() - HISTORY_LEN);
+        }
+    }
+    let failures = history.iter().filter(|h| !h.ok).count();
+    let error_rate = if history.is_empty() {
+        0.0
+    } else {
+        failures as f64 / history.len() as f64
+    };
+    let rows_returned = result.and_then(|r| r.rows);
+    let last_duration_ms = result.map(|r| r.duration_ms);
+    let broken = result.is_some_and(|r| r.error.is_some());
+
+    QueryStats {
+        id: target_id.to_string(),
+        name: name.to_string(),
+        table_id: None,
+        probe: probe.to_string(),
+        last_run_at: result.map(|_| at.to_string()),
+        last_duration_ms,
+        rows_returned,
+        last_error: result.and_then(|r| r.error.clone()),
+        error_rate,
+        heavy: !broken
+            && (last_duration_ms.is_some_and(|d| d >= HEAVY_DURATION_MS)
+                || rows_returned.is_some_and(|r| r >= HEAVY_ROWS)),
+        broken,
+        history,
+    }
+}
+
+/// Broken first, then heavy, then by probe duration (slowest first)
+pub fn sort_by_priority(stats: &mut [QueryStats]) {
+    stats.sort_by(|a, b| {
+        b.broken
+            .cmp(&a.broken)
+            .then(b.heavy.cmp(&a.heavy))
+            .then(b.last_duration_ms.unwrap_or(0).cmp(&a.last_duration_ms.unwrap_or(0)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Load the stats written by the last run, if any
+pub fn load_query_stats(global_path: &str) -> Option<QueryStatsFile> {
+    let content = fs::read_to_string(format!("{}/schema/query_stats.json", global_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Probe every query in schema/all_queries.json and write schema/query_stats.json,
+/// ordered broken → heavy → slowest so docs and health checks can start at the top
+#[command]
+pub async fn val_sync_query_stats(domain: String) -> CmdResult<SyncResult> {
+    use futures::stream::{self, StreamExt};
+
+    let start = Instant::now();
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = &domain_config.global_path;
+    let api_domain = domain_config.api_domain().to_string();
+    let queries_path = format!("{}/schema/all_queries.json", global_path);
+    let content = fs::read_to_string(&queries_path).map_err(|_| {
+        CommandError::NotFound(format!("{} not found — sync queries first", queries_path))
+    })?;
+    let data: Value = serde_json::from_str(&content)?;
+    let targets: Vec<ProbeTarget> = extract_array(&data, "queries")
+        .iter()
+        .filter_map(probe_target)
+        .collect();
+
+    let (token, _) = auth::ensure_auth(&domain).await?;
+    let probe_all = |token: String, sqls: Vec<(usize, String)>| {
+        let api_domain = api_domain.clone();
+        async move {
+            stream::iter(sqls)
+                .map(|(i, sql)| {
+                    let token = token.clone();
+                    let api_domain = api_domain.clone();
+                    async move { (i, run_probe(&token, &api_domain, &sql).await) }
+                })
+                .buffer_unordered(CONCURRENCY)
+                .collect::<Vec<_>>()
+                .await
+        }
+    };
+
+    let sqls: Vec<(usize, String)> = targets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| t.sql.clone().map(|sql| (i, sql)))
+        .collect();
+    let mut results: HashMap<usize, ProbeResult> = probe_all(token, sqls).await.into_iter().collect();
+
+    // Token expired mid-run: re-auth once and redo the probes it failed
+    let auth_failed: Vec<(usize, String)> = results
+        .iter()
+        .filter(|(_, r)| r.error.as_deref().is_some_and(is_auth_error))
+        .filter_map(|(i, _)| targets[*i].sql.clone().map(|sql| (*i, sql)))
+        .collect();
+    if !auth_failed.is_empty() {
+        let (new_token, _) = auth::reauth(&domain).await?;
+        results.extend(probe_all(new_token, auth_failed).await);
+    }
+
+    let mut previous: HashMap<String, QueryStats> = load_query_stats(global_path)
+        .map(|f| f.queries.into_iter().map(|q| (q.id.clone(), q)).collect())
+        .unwrap_or_default();
+    let at = chrono::Utc::now().to_rfc3339();
+    let mut stats: Vec<QueryStats> = targets
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let mut s = merge_stats(&t.id, &t.name, previous.remove(&t.id), t.probe, results.get(&i), &at);
+            s.table_id = t.table_id.clone();
+            s
+        })
+        .collect();
+    sort_by_priority(&mut stats);
+
+    let probed = results.len();
+    let broken = stats.iter().filter(|s| s.broken).count();
+    let heavy = stats.iter().filter(|s| s.heavy).count();
+    let count = stats.len();
+    let file_path = format!("{}/schema/query_stats.json", global_path);
+    let file = QueryStatsFile {
+        domain: domain.clone(),
+        generated_at: at,
+        queries: stats,
+    };
+    write_json(&file_path, &serde_json::to_value(&file)?)?;
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+    metadata::update_artifact_sync(global_path, &domain, "query-stats", count, "ok", duration_ms).await;
+
+    Ok(SyncResult {
+        domain,
+        artifact_type: "query-stats".to_string(),
+        count,
+        file_path,
+        duration_ms,
+        status: "ok".to_string(),
+        message: format!(
+            "Probed {} of {} queries: {} broken, {} heavy",
+            probed, count, broken, heavy
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(duration_ms: u64, rows: Option<i64>, error: Option<&str>) -> ProbeResult {
+        ProbeResult {
+            duration_ms,
+            rows,
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn merges_history_and_flags_heavy_or_broken() {
+        let first = merge_stats("1", "q", None, "table", Some(&probe(120, Some(10), Some("SQL error (500)"))), "t1");
+        assert!(first.broken && !first.heavy);
+        assert_eq!(first.error_rate, 1.0);
+
+        let second = merge_stats("1", "q", Some(first), "table", Some(&probe(6_000, Some(10), None)), "t2");
+        assert!(!second.broken && second.heavy);
+        assert_eq!(second.error_rate, 0.5);
+        assert_eq!(second.history.len(), 2);
+
+        let unprobed = merge_stats("2", "r", None, "none", None, "t2");
+        assert!(unprobed.last_run_at.is_none() && !unprobed.broken);
+
+        let mut all = vec![unprobed, second, merge_stats("3", "s", None, "sql", Some(&probe(10, None, Some("x"))), "t2")];
+        sort_by_priority(&mut all);
+        let order: Vec<&str> = all.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(order, vec!["3", "1", "2"]);
+    }
+
+    #[test]
+    fn builds_probe_sql_from_query_definition() {
+        let table = probe_target(&serde_json::json!({
+            "id": 42, "name": "Orders",
+            "datasource": { "queryInfo": { "tableInfo": { "id": "custom_tbl_1_2" } } }
+        }))
+        .unwrap();
+        assert_eq!(table.probe, "table");
+        assert_eq!(table.sql.as_deref(), Some("SELECT COUNT(*) AS row_count FROM custom_tbl_1_2"));
+
+        let unsafe_name = probe_target(&serde_json::json!({
+            "id": "7", "datasource": { "queryInfo": { "tableInfo": { "id": "x; drop" } } }
+        }))
+        .unwrap();
+        assert_eq!(unsafe_name.probe, "none");
+    }
+}
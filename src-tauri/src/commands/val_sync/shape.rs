@@ -0,0 +1,361 @@
+// VAL Sync Shape - Aggregate SQL results into chart-ready series
+// Group by / pivot / date bucketing happens here so charts get compact series
+// instead of raw rows, and large result sets stay on the Rust side.
+
+use super::sql::stored_result;
+use crate::commands::error::{CmdResult, CommandError};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use tauri::command;
+
+const DEFAULT_MAX_SERIES: usize = 10;
+const OTHER: &str = "Other";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateBucket {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregate {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+    CountDistinct,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSpec {
+    /// Column to aggregate (not needed for count)
+    pub column: Option<String>,
+    pub agg: Aggregate,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShapeSpec {
+    /// Column for the x axis / categories
+    pub x: String,
+    /// Bucket x as a date (day | week | month | quarter | year)
+    pub bucket: Option<DateBucket>,
+    /// Pivot: one series per distinct value of this column
+    pub series: Option<String>,
+    /// Metrics to compute; defaults to a row count
+    #[serde(default)]
+    pub y: Vec<MetricSpec>,
+    /// "x" (ascending categories) or "value" (largest first). Defaults to
+    /// "x" for date buckets and "value" otherwise.
+    pub sort: Option<String>,
+    /// Keep only the first N categories after sorting
+    pub limit: Option<usize>,
+    /// Pivot series beyond this many (by total) fold into "Other"
+    pub max_series: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShapedSeries {
+    pub name: String,
+    pub metric: String,
+    /// Aligned with `categories`; None where the group had no rows
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShapedResult {
+    pub categories: Vec<String>,
+    pub series: Vec<ShapedSeries>,
+    pub row_count: usize,
+    /// Rows left out because x couldn't be read (or parsed as a date)
+    pub skipped_rows: usize,
+}
+
+// ============================================================================
+// Aggregation
+// ============================================================================
+
+#[derive(Default)]
+struct Acc {
+    sum: f64,
+    count: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+    distinct: HashSet<String>,
+}
+
+impl Acc {
+    fn add(&mut self, value: Option<&Value>, agg: Aggregate) {
+        match agg {
+            Aggregate::Count => self.count += 1,
+            Aggregate::CountDistinct => {
+                if let Some(v) = value.and_then(label_of) {
+                    self.distinct.insert(v);
+                }
+            }
+            _ => {
+                let Some(n) = value.and_then(number_of) else { return };
+                self.sum += n;
+                self.count += 1;
+                self.min = Some(self.min.map_or(n, |m| m.min(n)));
+                self.max = Some(self.max.map_or(n, |m| m.max(n)));
+            }
+        }
+    }
+
+    fn result(&self, agg: Aggregate) -> Option<f64> {
+        match agg {
+            Aggregate::Sum => Some(self.sum),
+            Aggregate::Count => Some(self.count as f64),
+            Aggregate::CountDistinct => Some(self.distinct.len() as f64),
+            Aggregate::Avg if self.count > 0 => Some(self.sum / self.count as f64),
+            Aggregate::Avg => None,
+            Aggregate::Min => self.min,
+            Aggregate::Max => self.max,
+        }
+    }
+}
+
+fn number_of(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+fn label_of(v: &Value) -> Option<String> {
+    match v {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn parse_date(v: &Value) -> Option<NaiveDate> {
+    if let Value::Number(n) = v {
+        // Epoch millis (or seconds, for small values)
+        let n = n.as_i64()?;
+        let millis = if n.abs() < 100_000_000_000 { n * 1000 } else { n };
+        return chrono::DateTime::from_timestamp_millis(millis).map(|d| d.date_naive());
+    }
+    let s = v.as_str()?.trim();
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|d| d.date_naive())
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").ok().map(|d| d.date()))
+        .or_else(|| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").ok().map(|d| d.date()))
+        .or_else(|| NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d").ok())
+}
+
+/// Bucket labels sort chronologically as plain strings
+fn bucket_label(date: NaiveDate, bucket: DateBucket) -> String {
+    match bucket {
+        DateBucket::Day => date.format("%Y-%m-%d").to_string(),
+        DateBucket::Week => {
+            let monday = date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64);
+            monday.format("%Y-%m-%d").to_string()
+        }
+        DateBucket::Month => date.format("%Y-%m").to_string(),
+        DateBucket::Quarter => format!("{}-Q{}", date.year(), (date.month() - 1) / 3 + 1),
+        DateBucket::Year => date.year().to_string(),
+    }
+}
+
+fn metric_name(m: &MetricSpec) -> String {
+    if let Some(label) = &m.label {
+        return label.clone();
+    }
+    let agg = serde_json::to_value(m.agg).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default();
+    match &m.column {
+        Some(col) => format!("{}({})", agg, col),
+        None => agg,
+    }
+}
+
+/// Group, pivot and aggregate `rows` per `spec`
+pub fn shape_rows(rows: &[Value], spec: &ShapeSpec) -> CmdResult<ShapedResult> {
+    let metrics = if spec.y.is_empty() {
+        vec![MetricSpec { column: None, agg: Aggregate::Count, label: Some("count".into()) }]
+    } else {
+        spec.y.clone()
+    };
+    if let Some(m) = metrics.iter().find(|m| m.column.is_none() && m.agg != Aggregate::Count) {
+        return Err(CommandError::Config(format!("Metric {} needs a column", metric_name(m))));
+    }
+
+    // (category, series) → one accumulator per metric
+    let mut groups: HashMap<(String, String), Vec<Acc>> = HashMap::new();
+    let mut categories: Vec<String> = Vec::new();
+    let mut series_names: Vec<String> = Vec::new();
+    let mut skipped_rows = 0;
+
+    for row in rows {
+        let x = row.get(&spec.x);
+        let category = match spec.bucket {
+            Some(bucket) => x.and_then(parse_date).map(|d| bucket_label(d, bucket)),
+            None => x.and_then(label_of),
+        };
+        let Some(category) = category else {
+            skipped_rows += 1;
+            continue;
+        };
+        let series = match &spec.series {
+            Some(col) => row.get(col).and_then(label_of).unwrap_or_else(|| "(empty)".to_string()),
+            None => String::new(),
+        };
+        if !categories.contains(&category) {
+            categories.push(category.clone());
+        }
+        if !series_names.contains(&series) {
+            series_names.push(series.clone());
+        }
+        let accs = groups
+            .entry((category, series))
+            .or_insert_with(|| metrics.iter().map(|_| Acc::default()).collect());
+        for (acc, m) in accs.iter_mut().zip(&metrics) {
+            acc.add(m.column.as_ref().and_then(|c| row.get(c)), m.agg);
+        }
+    }
+
+    // Rank by the first metric's total, used for value sort and series folding
+    let total = |filter: &dyn Fn(&(String, String)) -> bool| -> f64 {
+        groups
+            .iter()
+            .filter(|(k, _)| filter(k))
+            .filter_map(|(_, accs)| accs[0].result(metrics[0].agg))
+            .sum()
+    };
+
+    let sort_by_value = match spec.sort.as_deref() {
+        Some("value") => true,
+        Some("x") => false,
+        _ => spec.bucket.is_none(),
+    };
+    if sort_by_value {
+        let mut ranked: Vec<(f64, String)> = categories.iter().map(|c| (total(&|k| &k.0 == c), c.clone())).collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        categories = ranked.into_iter().map(|(_, c)| c).collect();
+    } else {
+        categories.sort();
+    }
+    if let Some(limit) = spec.limit {
+        categories.truncate(limit);
+    }
+
+    // Fold the smallest pivot series into "Other"
+    let max_series = spec.max_series.unwrap_or(DEFAULT_MAX_SERIES).max(1);
+    let mut folded: HashSet<String> = HashSet::new();
+    if spec.series.is_some() && series_names.len() > max_series {
+        let mut ranked: Vec<(f64, String)> = series_names.iter().map(|s| (total(&|k| &k.1 == s), s.clone())).collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        series_names = ranked.iter().take(max_series - 1).map(|(_, s)| s.clone()).collect();
+        folded = ranked.into_iter().skip(max_series - 1).map(|(_, s)| s).collect();
+        series_names.push(OTHER.to_string());
+    }
+
+    let mut series = Vec::new();
+    for name in &series_names {
+        for (mi, m) in metrics.iter().enumerate() {
+            let values = categories
+                .iter()
+                .map(|c| {
+                    if name == OTHER && !folded.is_empty() {
+                        // Sum/count-style metrics add up across folded series; others can't
+                        let parts: Vec<f64> = folded
+                            .iter()
+                            .filter_map(|s| groups.get(&(c.clone(), s.clone())))
+                            .filter_map(|accs| accs[mi].result(m.agg))
+                            .collect();
+                        match m.agg {
+                            _ if parts.is_empty() => None,
+                            Aggregate::Sum | Aggregate::Count => Some(parts.iter().sum()),
+                            Aggregate::Min => parts.iter().cloned().reduce(f64::min),
+                            Aggregate::Max => parts.iter().cloned().reduce(f64::max),
+                            Aggregate::Avg | Aggregate::CountDistinct => None,
+                        }
+                    } else {
+                        groups.get(&(c.clone(), name.clone())).and_then(|accs| accs[mi].result(m.agg))
+                    }
+                })
+                .collect();
+            series.push(ShapedSeries {
+                name: if name.is_empty() { metric_name(m) } else { name.clone() },
+                metric: metric_name(m),
+                values,
+            });
+        }
+    }
+
+    Ok(ShapedResult {
+        categories,
+        series,
+        row_count: rows.len(),
+        skipped_rows,
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Shape SQL rows for a chart. Pass `result_id` from val_execute_sql to work
+/// on the full stored result, or `rows` for small inline data.
+#[command]
+pub fn val_shape_result(result_id: Option<String>, rows: Option<Vec<Value>>, spec: ShapeSpec) -> CmdResult<ShapedResult> {
+    match (result_id, rows) {
+        (Some(id), _) => {
+            let rows = stored_result(&id)
+                .ok_or_else(|| CommandError::NotFound(format!("Result {} has expired; run the query again", id)))?;
+            shape_rows(&rows, &spec)
+        }
+        (None, Some(rows)) => shape_rows(&rows, &spec),
+        (None, None) => Err(CommandError::Config("Provide result_id or rows".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn buckets_dates_and_pivots_series() {
+        let rows = vec![
+            json!({ "day": "2026-01-05", "region": "north", "amount": 10 }),
+            json!({ "day": "2026-01-20", "region": "south", "amount": "5" }),
+            json!({ "day": "2026-02-02T08:00:00Z", "region": "north", "amount": 7 }),
+            json!({ "day": null, "region": "north", "amount": 1 }),
+        ];
+        let spec = ShapeSpec {
+            x: "day".into(),
+            bucket: Some(DateBucket::Month),
+            series: Some("region".into()),
+            y: vec![MetricSpec { column: Some("amount".into()), agg: Aggregate::Sum, label: None }],
+            sort: None,
+            limit: None,
+            max_series: None,
+        };
+        let shaped = shape_rows(&rows, &spec).unwrap();
+        assert_eq!(shaped.categories, vec!["2026-01", "2026-02"]);
+        assert_eq!(shaped.skipped_rows, 1);
+        assert_eq!(shaped.series[0].name, "north");
+        assert_eq!(shaped.series[0].values, vec![Some(10.0), Some(7.0)]);
+        assert_eq!(shaped.series[1].values, vec![Some(5.0), None]);
+        assert_eq!(shaped.series[0].metric, "sum(amount)");
+        assert_eq!(bucket_label(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(), DateBucket::Week), "2026-10-12");
+    }
+}
@@ -5,10 +5,20 @@ use super::auth;
 use super::config::get_domain_config;
 use crate::commands::error::{CmdResult, CommandError};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 use tauri::command;
 
+/// Recent full result sets kept for server-side shaping (val_shape_result)
+const MAX_STORED_RESULTS: usize = 20;
+
+static RESULTS: LazyLock<Mutex<VecDeque<(String, Arc<Vec<serde_json::Value>>)>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+static RESULT_SEQ: AtomicU64 = AtomicU64::new(0);
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -22,8 +32,8 @@ struct SqlQueryRequest {
 }
 
 #[derive(Debug, Deserialize)]
-struct SqlQueryResponse {
-    data: Option<Vec<serde_json::Value>>,
+pub(super) struct SqlQueryResponse {
+    pub(super) data: Option<Vec<serde_json::Value>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -35,13 +45,15 @@ pub struct SqlExecuteResult {
     pub data: Vec<serde_json::Value>,
     pub truncated: bool,
     pub error: Option<String>,
+    /// Handle for val_shape_result, covering every row the query returned
+    pub result_id: Option<String>,
 }
 
 // ============================================================================
 // Internal Helpers
 // ============================================================================
 
-async fn execute_sql_internal(
+pub(super) async fn execute_sql_internal(
     token: &str,
     domain: &str,
     sql: &str,
@@ -76,6 +88,51 @@ async fn execute_sql_internal(
         .map_err(|e| format!("Failed to parse SQL response: {}", e))
 }
 
+/// Keep a result set for later shaping; returns its id
+fn store_result(rows: Vec<serde_json::Value>) -> String {
+    let id = format!(
+        "sql-{}-{}",
+        chrono::Utc::now().timestamp_millis(),
+        RESULT_SEQ.fetch_add(1, Ordering::Relaxed)
+    );
+    if let Ok(mut results) = RESULTS.lock() {
+        if results.len() >= MAX_STORED_RESULTS {
+            results.pop_front();
+        }
+        results.push_back((id.clone(), Arc::new(rows)));
+    }
+    id
+}
+
+/// A result set stored by val_execute_sql, if it hasn't been evicted
+pub(super) fn stored_result(id: &str) -> Option<Arc<Vec<serde_json::Value>>> {
+    RESULTS
+        .lock()
+        .ok()?
+        .iter()
+        .find(|(rid, _)| rid == id)
+        .map(|(_, rows)| rows.clone())
+}
+
+fn success_result(domain: String, sql: String, data: Vec<serde_json::Value>, max_rows: usize) -> SqlExecuteResult {
+    let total_rows = data.len();
+    let truncated = total_rows > max_rows;
+    let limited_data: Vec<_> = data.iter().take(max_rows).cloned().collect();
+    let columns = extract_columns(&limited_data);
+    let result_id = Some(store_result(data));
+
+    SqlExecuteResult {
+        domain,
+        sql,
+        row_count: total_rows,
+        columns,
+        data: limited_data,
+        truncated,
+        error: None,
+        result_id,
+    }
+}
+
 fn extract_columns(data: &[serde_json::Value]) -> Vec<String> {
     if data.is_empty() {
         return Vec::new();
@@ -136,23 +193,7 @@ pub async fn val_execute_sql(
 
     // Execute query
     match execute_sql_internal(&token, api_domain, &actual_sql, max_rows).await {
-        Ok(response) => {
-            let data = response.data.unwrap_or_default();
-            let total_rows = data.len();
-            let truncated = total_rows > max_rows;
-            let limited_data: Vec<_> = data.into_iter().take(max_rows).collect();
-            let columns = extract_columns(&limited_data);
-
-            Ok(SqlExecuteResult {
-                domain,
-                sql: actual_sql,
-                row_count: total_rows,
-                columns,
-                data: limited_data,
-                truncated,
-                error: None,
-            })
-        }
+        Ok(response) => Ok(success_result(domain, actual_sql, response.data.unwrap_or_default(), max_rows)),
         Err(e) => {
             // Check for auth error and retry
             let lower_e = e.to_lowercase();
@@ -161,23 +202,7 @@ pub async fn val_execute_sql(
                 let (new_token, _) = auth::ensure_auth(&domain).await?;
 
                 match execute_sql_internal(&new_token, api_domain, &actual_sql, max_rows).await {
-                    Ok(response) => {
-                        let data = response.data.unwrap_or_default();
-                        let total_rows = data.len();
-                        let truncated = total_rows > max_rows;
-                        let limited_data: Vec<_> = data.into_iter().take(max_rows).collect();
-                        let columns = extract_columns(&limited_data);
-
-                        Ok(SqlExecuteResult {
-                            domain,
-                            sql: actual_sql,
-                            row_count: total_rows,
-                            columns,
-                            data: limited_data,
-                            truncated,
-                            error: None,
-                        })
-                    }
+                    Ok(response) => Ok(success_result(domain, actual_sql, response.data.unwrap_or_default(), max_rows)),
                     Err(e2) => Ok(SqlExecuteResult {
                         domain,
                         sql: actual_sql,
@@ -186,6 +211,7 @@ pub async fn val_execute_sql(
                         data: Vec::new(),
                         truncated: false,
                         error: Some(e2),
+                        result_id: None,
                     }),
                 }
             } else {
@@ -197,6 +223,7 @@ pub async fn val_execute_sql(
                     data: Vec::new(),
                     truncated: false,
                     error: Some(e),
+                    result_id: None,
                 })
             }
         }
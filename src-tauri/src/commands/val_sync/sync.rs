@@ -214,6 +214,8 @@ pub async fn val_sync_all(domain: String) -> CmdResult<SyncAllResult> {
                     domain: domain.clone(),
                     extract_type: extract_type.to_string(),
                     count: 0,
+                    changed: 0,
+                    unchanged: 0,
                     duration_ms: 0,
                     status: "error".to_string(),
                     message: e.to_string(),
@@ -0,0 +1,511 @@
+// VAL Table Compare - The same table side by side across domains.
+// Loads each domain's documented copy (definition_details.json,
+// definition_sample.json, definition_categorical.json) and reports what
+// differs: columns missing or retyped, row counts and coverage, sampled value
+// ranges, and categorical values — for rolling a standard table out to new
+// customer domains. Written as JSON + markdown.
+
+use super::config::get_domain_config;
+use super::context_pack::{read_json, table_folders};
+use super::data_dictionary::{load_table, DictionaryTable};
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Categorical values listed per domain in the markdown report
+const MAX_VALUES_IN_REPORT: usize = 20;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainTableProfile {
+    pub domain: String,
+    /// None when the domain has no documented table by that id or name
+    pub table_id: Option<String>,
+    pub display_name: Option<String>,
+    pub row_count: Option<i64>,
+    /// definition_details.json `coverage` (date range of the data)
+    pub coverage: Option<Value>,
+    pub column_count: usize,
+    pub sampled_rows: usize,
+    /// Pipeline outputs not found (run the table pipeline for these)
+    pub missing_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDiff {
+    pub column: String,
+    /// Display name per domain
+    pub names: BTreeMap<String, String>,
+    /// Data type per domain
+    pub types: BTreeMap<String, String>,
+    /// Domains (with the table) that lack this column
+    pub missing_from: Vec<String>,
+    pub type_mismatch: bool,
+    pub name_mismatch: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueRange {
+    /// "number" or "date"
+    pub kind: String,
+    pub min: String,
+    pub max: String,
+    /// Share of sampled rows that are null, 0–1
+    pub null_ratio: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnRanges {
+    pub column: String,
+    pub ranges: BTreeMap<String, ValueRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoricalDiff {
+    pub column: String,
+    /// Values every domain has
+    pub common: Vec<String>,
+    /// Values only some domains have, per domain
+    pub only_in: BTreeMap<String, Vec<String>>,
+    pub distinct_counts: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableComparison {
+    pub table_name: String,
+    pub generated_at: String,
+    pub domains: Vec<DomainTableProfile>,
+    /// Every column seen in any domain; differing ones first
+    pub columns: Vec<ColumnDiff>,
+    pub ranges: Vec<ColumnRanges>,
+    /// Categorical columns whose values differ between domains
+    pub categorical: Vec<CategoricalDiff>,
+    pub json_path: Option<String>,
+    pub markdown_path: Option<String>,
+}
+
+/// One domain's copy of the table, as read from disk
+struct LoadedDomain {
+    profile: DomainTableProfile,
+    table: Option<DictionaryTable>,
+    sample_rows: Vec<Value>,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Table folder by id, or by display name when ids differ between domains
+fn find_table(global_path: &Path, table_name: &str) -> Option<(String, PathBuf)> {
+    let folder = global_path.join("data_models").join(format!("table_{}", table_name));
+    if folder.is_dir() {
+        return Some((table_name.to_string(), folder));
+    }
+    table_folders(global_path, "*").into_iter().find(|(_, folder)| {
+        read_json(&folder.join("definition_details.json"))
+            .and_then(|d| d["meta"]["displayName"].as_str().map(|n| n.eq_ignore_ascii_case(table_name)))
+            .unwrap_or(false)
+    })
+}
+
+fn load_domain(domain: &str, table_name: &str) -> CmdResult<LoadedDomain> {
+    let config = get_domain_config(domain)?;
+    let mut profile = DomainTableProfile {
+        domain: domain.to_string(),
+        table_id: None,
+        display_name: None,
+        row_count: None,
+        coverage: None,
+        column_count: 0,
+        sampled_rows: 0,
+        missing_files: Vec::new(),
+    };
+    let Some((table_id, folder)) = find_table(Path::new(&config.global_path), table_name) else {
+        return Ok(LoadedDomain { profile, table: None, sample_rows: Vec::new() });
+    };
+
+    for file in ["definition_details.json", "definition_sample.json", "definition_categorical.json"] {
+        if !folder.join(file).exists() {
+            profile.missing_files.push(file.to_string());
+        }
+    }
+    let details = read_json(&folder.join("definition_details.json")).unwrap_or(Value::Null);
+    let sample = read_json(&folder.join("definition_sample.json")).unwrap_or(Value::Null);
+    let sample_rows = sample["rows"].as_array().cloned().unwrap_or_default();
+    let table = load_table(domain, &table_id, &folder);
+
+    profile.row_count = details["health"]["rowCount"]
+        .as_i64()
+        .or_else(|| sample["meta"]["totalRowCount"].as_i64());
+    profile.coverage = details.get("coverage").filter(|c| !c.is_null()).cloned();
+    profile.column_count = table.as_ref().map(|t| t.columns.len()).unwrap_or(0);
+    profile.display_name = table.as_ref().map(|t| t.display_name.clone());
+    profile.sampled_rows = sample_rows.len();
+    profile.table_id = Some(table_id);
+    Ok(LoadedDomain { profile, table, sample_rows })
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+/// Numeric or date range of one column in sampled rows; None for text columns
+fn value_range(rows: &[Value], column: &str) -> Option<ValueRange> {
+    let mut numbers: Vec<f64> = Vec::new();
+    let mut dates: Vec<String> = Vec::new();
+    let mut nulls = 0usize;
+    let mut other = false;
+    for row in rows {
+        match &row[column] {
+            Value::Null => nulls += 1,
+            Value::Number(n) => numbers.extend(n.as_f64()),
+            Value::String(s) => match s.parse::<f64>() {
+                Ok(n) => numbers.push(n),
+                // ISO dates and timestamps sort correctly as strings
+                Err(_) if chrono::NaiveDate::parse_from_str(s.get(..10).unwrap_or(""), "%Y-%m-%d").is_ok() => {
+                    dates.push(s.clone())
+                }
+                Err(_) => other = true,
+            },
+            _ => other = true,
+        }
+    }
+    if rows.is_empty() || other || (!numbers.is_empty() && !dates.is_empty()) {
+        return None;
+    }
+    let null_ratio = nulls as f64 / rows.len() as f64;
+    if !numbers.is_empty() {
+        let min = numbers.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        return Some(ValueRange { kind: "number".into(), min: min.to_string(), max: max.to_string(), null_ratio });
+    }
+    let min = dates.iter().min()?.clone();
+    let max = dates.iter().max()?.clone();
+    Some(ValueRange { kind: "date".into(), min, max, null_ratio })
+}
+
+fn compare(table_name: &str, loaded: &[LoadedDomain]) -> TableComparison {
+    let present: Vec<(&str, &DictionaryTable)> = loaded
+        .iter()
+        .filter_map(|l| l.table.as_ref().map(|t| (l.profile.domain.as_str(), t)))
+        .collect();
+
+    // Columns by db column name, in first-seen order
+    let mut order: Vec<String> = Vec::new();
+    let mut by_column: HashMap<String, ColumnDiff> = HashMap::new();
+    for (domain, table) in &present {
+        for col in &table.columns {
+            let diff = by_column.entry(col.column.clone()).or_insert_with(|| {
+                order.push(col.column.clone());
+                ColumnDiff {
+                    column: col.column.clone(),
+                    names: BTreeMap::new(),
+                    types: BTreeMap::new(),
+                    missing_from: Vec::new(),
+                    type_mismatch: false,
+                    name_mismatch: false,
+                }
+            });
+            diff.names.insert(domain.to_string(), col.name.clone());
+            diff.types.insert(domain.to_string(), col.data_type.clone());
+        }
+    }
+    let mut columns: Vec<ColumnDiff> = order
+        .iter()
+        .filter_map(|c| by_column.remove(c))
+        .map(|mut diff| {
+            diff.missing_from = present
+                .iter()
+                .filter(|(d, _)| !diff.names.contains_key(*d))
+                .map(|(d, _)| d.to_string())
+                .collect();
+            diff.type_mismatch = diff.types.values().collect::<BTreeSet<_>>().len() > 1;
+            diff.name_mismatch = diff.names.values().collect::<BTreeSet<_>>().len() > 1;
+            diff
+        })
+        .collect();
+    // Stable sort: differing columns first, otherwise table order
+    columns.sort_by_key(|c| !(c.type_mismatch || c.name_mismatch || !c.missing_from.is_empty()));
+
+    let ranges: Vec<ColumnRanges> = columns
+        .iter()
+        .filter_map(|c| {
+            let ranges: BTreeMap<String, ValueRange> = loaded
+                .iter()
+                .filter_map(|l| value_range(&l.sample_rows, &c.column).map(|r| (l.profile.domain.clone(), r)))
+                .collect();
+            (!ranges.is_empty()).then(|| ColumnRanges { column: c.column.clone(), ranges })
+        })
+        .collect();
+
+    let mut categorical = Vec::new();
+    for c in &columns {
+        let values: BTreeMap<&str, BTreeSet<&str>> = present
+            .iter()
+            .filter_map(|(domain, table)| {
+                let col = table.columns.iter().find(|col| col.column == c.column)?;
+                (!col.values.is_empty()).then(|| (*domain, col.values.iter().map(String::as_str).collect()))
+            })
+            .collect();
+        if values.len() < 2 {
+            continue;
+        }
+        let common: BTreeSet<&str> = values
+            .values()
+            .skip(1)
+            .fold(values.values().next().cloned().unwrap_or_default(), |acc, v| {
+                acc.intersection(v).cloned().collect()
+            });
+        let only_in: BTreeMap<String, Vec<String>> = values
+            .iter()
+            .map(|(d, v)| (d.to_string(), v.difference(&common).map(|s| s.to_string()).collect::<Vec<_>>()))
+            .filter(|(_, v)| !v.is_empty())
+            .collect();
+        if only_in.is_empty() {
+            continue;
+        }
+        categorical.push(CategoricalDiff {
+            column: c.column.clone(),
+            common: common.iter().map(|s| s.to_string()).collect(),
+            only_in,
+            distinct_counts: values.iter().map(|(d, v)| (d.to_string(), v.len())).collect(),
+        });
+    }
+
+    TableComparison {
+        table_name: table_name.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        domains: loaded.iter().map(|l| l.profile.clone()).collect(),
+        columns,
+        ranges,
+        categorical,
+        json_path: None,
+        markdown_path: None,
+    }
+}
+
+// ============================================================================
+// Markdown
+// ============================================================================
+
+fn render_markdown(cmp: &TableComparison) -> String {
+    let mut md = format!("# Table comparison: {}\n\n", cmp.table_name);
+    md.push_str(&format!("_Generated {}_\n\n", cmp.generated_at));
+
+    md.push_str("## Domains\n\n| Domain | Table | Rows | Columns | Coverage | Missing |\n|---|---|---|---|---|---|\n");
+    for d in &cmp.domains {
+        let coverage = d
+            .coverage
+            .as_ref()
+            .map(|c| format!("{} → {}", c["min"].as_str().unwrap_or("?"), c["max"].as_str().unwrap_or("?")))
+            .unwrap_or_default();
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            d.domain,
+            d.table_id.as_deref().unwrap_or("— not found"),
+            d.row_count.map(|n| n.to_string()).unwrap_or_default(),
+            d.column_count,
+            coverage,
+            d.missing_files.join(", ")
+        ));
+    }
+
+    let differing: Vec<&ColumnDiff> = cmp
+        .columns
+        .iter()
+        .filter(|c| c.type_mismatch || c.name_mismatch || !c.missing_from.is_empty())
+        .collect();
+    md.push_str(&format!(
+        "\n## Column differences\n\n{} of {} columns differ.\n\n",
+        differing.len(),
+        cmp.columns.len()
+    ));
+    if !differing.is_empty() {
+        md.push_str("| Column | Missing from | Names | Types |\n|---|---|---|---|\n");
+        for c in differing {
+            let join = |m: &BTreeMap<String, String>, mismatch: bool| {
+                if mismatch {
+                    m.iter().map(|(d, v)| format!("{}: {}", d, v)).collect::<Vec<_>>().join("; ")
+                } else {
+                    m.values().next().cloned().unwrap_or_default()
+                }
+            };
+            md.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                c.column,
+                c.missing_from.join(", "),
+                join(&c.names, c.name_mismatch),
+                join(&c.types, c.type_mismatch)
+            ));
+        }
+    }
+
+    if !cmp.ranges.is_empty() {
+        md.push_str("\n## Value ranges (sampled rows)\n\n| Column | Domain | Min | Max | Null % |\n|---|---|---|---|---|\n");
+        for r in &cmp.ranges {
+            for (domain, range) in &r.ranges {
+                md.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {:.0}% |\n",
+                    r.column,
+                    domain,
+                    range.min,
+                    range.max,
+                    range.null_ratio * 100.0
+                ));
+            }
+        }
+    }
+
+    md.push_str("\n## Categorical differences\n\n");
+    if cmp.categorical.is_empty() {
+        md.push_str("No differences in categorical values.\n");
+    }
+    for c in &cmp.categorical {
+        md.push_str(&format!("### `{}`\n\n", c.column));
+        md.push_str(&format!("- Shared by all: {} value(s)\n", c.common.len()));
+        for (domain, values) in &c.only_in {
+            let shown: Vec<&str> = values.iter().take(MAX_VALUES_IN_REPORT).map(String::as_str).collect();
+            let more = values.len().saturating_sub(MAX_VALUES_IN_REPORT);
+            md.push_str(&format!(
+                "- Only in {}: {}{}\n",
+                domain,
+                shown.join(", "),
+                if more > 0 { format!(" (+{} more)", more) } else { String::new() }
+            ));
+        }
+        md.push('\n');
+    }
+    md
+}
+
+fn default_output_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("val")
+        .join("table_comparisons")
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Compare one table (by id, or display name when ids differ) across domains.
+/// Reads the table pipeline outputs already on disk — run the pipeline for
+/// each domain first. Writes `{table}.json` and `{table}.md` to `output_dir`
+/// or ~/.tv-client/val/table_comparisons/.
+#[command]
+pub async fn val_compare_table_across_domains(
+    table_name: String,
+    domains: Vec<String>,
+    output_dir: Option<String>,
+) -> CmdResult<TableComparison> {
+    if domains.len() < 2 {
+        return Err(CommandError::Validation {
+            message: "Pick at least two domains to compare".to_string(),
+            fields: vec!["domains".to_string()],
+        });
+    }
+    let loaded = domains
+        .iter()
+        .map(|d| load_domain(d, &table_name))
+        .collect::<CmdResult<Vec<_>>>()?;
+    if loaded.iter().all(|l| l.table.is_none()) {
+        return Err(CommandError::NotFound(format!(
+            "No documented table '{}' in {}",
+            table_name,
+            domains.join(", ")
+        )));
+    }
+
+    let mut cmp = compare(&table_name, &loaded);
+    let dir = output_dir.map(PathBuf::from).unwrap_or_else(default_output_dir);
+    fs::create_dir_all(&dir)?;
+    let stem = format!("{}__{}", table_name.replace(['/', '\\', ' '], "_"), domains.join("_"));
+    let json_path = dir.join(format!("{}.json", stem));
+    let markdown_path = dir.join(format!("{}.md", stem));
+    cmp.json_path = Some(json_path.to_string_lossy().to_string());
+    cmp.markdown_path = Some(markdown_path.to_string_lossy().to_string());
+    fs::write(&json_path, serde_json::to_string_pretty(&cmp)?)?;
+    fs::write(&markdown_path, render_markdown(&cmp))?;
+    Ok(cmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::val_sync::data_dictionary::DictionaryColumn;
+    use serde_json::json;
+
+    fn column(column: &str, data_type: &str, values: &[&str]) -> DictionaryColumn {
+        DictionaryColumn {
+            name: column.to_string(),
+            column: column.to_string(),
+            kind: "data".into(),
+            data_type: data_type.into(),
+            description: String::new(),
+            values: values.iter().map(|v| v.to_string()).collect(),
+            distinct_count: None,
+        }
+    }
+
+    fn domain(name: &str, columns: Vec<DictionaryColumn>, rows: Vec<Value>) -> LoadedDomain {
+        LoadedDomain {
+            profile: DomainTableProfile {
+                domain: name.into(),
+                table_id: Some("custom_tbl_1_1".into()),
+                display_name: Some("Orders".into()),
+                row_count: None,
+                coverage: None,
+                column_count: columns.len(),
+                sampled_rows: rows.len(),
+                missing_files: Vec::new(),
+            },
+            table: Some(DictionaryTable {
+                domain: name.into(),
+                table_name: "custom_tbl_1_1".into(),
+                display_name: "Orders".into(),
+                summary: String::new(),
+                last_reviewed: None,
+                columns,
+            }),
+            sample_rows: rows,
+        }
+    }
+
+    #[test]
+    fn reports_column_range_and_categorical_differences() {
+        let a = domain(
+            "acme",
+            vec![column("usr_status", "text", &["open", "closed"]), column("usr_amount", "numeric", &[]), column("usr_region", "text", &[])],
+            vec![json!({"usr_amount": 5, "usr_status": "open"}), json!({"usr_amount": "12.5", "usr_status": null})],
+        );
+        let b = domain(
+            "globex",
+            vec![column("usr_status", "text", &["open", "void"]), column("usr_amount", "integer", &[])],
+            vec![json!({"usr_amount": 100}), json!({"usr_amount": null})],
+        );
+        let cmp = compare("custom_tbl_1_1", &[a, b]);
+
+        let region = cmp.columns.iter().find(|c| c.column == "usr_region").unwrap();
+        assert_eq!(region.missing_from, vec!["globex"]);
+        assert!(cmp.columns.iter().find(|c| c.column == "usr_amount").unwrap().type_mismatch);
+        assert_eq!(cmp.columns.last().unwrap().column, "usr_status");
+
+        let amount = &cmp.ranges.iter().find(|r| r.column == "usr_amount").unwrap().ranges;
+        assert_eq!((amount["acme"].min.as_str(), amount["acme"].max.as_str()), ("5", "12.5"));
+        assert_eq!(amount["globex"].null_ratio, 0.5);
+
+        assert_eq!(cmp.categorical.len(), 1);
+        assert_eq!(cmp.categorical[0].common, vec!["open"]);
+        assert_eq!(cmp.categorical[0].only_in["globex"], vec!["void"]);
+        assert!(render_markdown(&cmp).contains("Only in acme: closed"));
+    }
+}
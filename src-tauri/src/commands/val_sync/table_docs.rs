@@ -0,0 +1,401 @@
+// VAL Sync Table Docs - Materialized links between tables and documentation
+// Scans the knowledge folder for markdown that references a domain's tables
+// by wiki link ([[custom_tbl_1_2]], [[Orders|orders table]]) or code span
+// (`custom_tbl_1_2`), then writes a `docs` section into each table's
+// definition_details.json and a "Documentation" section into its overview.md.
+
+use super::config::get_domain_config;
+use super::extract::{write_json_if_changed, write_text_if_changed};
+use super::table_pipeline::{build_table_display_names, get_tables_to_process};
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::files::{frontmatter_field, parse_frontmatter};
+use crate::AppState;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+use tauri::{command, State};
+use walkdir::WalkDir;
+
+/// Skip documents bigger than this (exports, generated dumps)
+const MAX_DOC_BYTES: u64 = 1_000_000;
+const SECTION_HEADING: &str = "## Documentation";
+/// Start of the footer val_generate_table_overview_md writes
+const OVERVIEW_FOOTER: &str = "---\n\n*Generated by tv-client";
+
+static WIKI_LINK_RE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"!?\[\[([^\]|#\n]+)(?:#[^\]|\n]*)?(?:\|[^\]\n]*)?\]\]").unwrap());
+static CODE_SPAN_RE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One entry of the `docs` array in definition_details.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableDocLink {
+    /// Relative to the knowledge root
+    pub path: String,
+    pub title: String,
+    /// "sop", "runbook" or "doc"
+    pub kind: String,
+    /// "wiki" or "code" — how the table is referenced (wiki wins when both)
+    pub reference: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableDocsResult {
+    pub domain: String,
+    pub documents_scanned: usize,
+    /// Tables with at least one linked document
+    pub tables_linked: usize,
+    pub links: usize,
+    pub details_updated: usize,
+    pub overviews_updated: usize,
+    pub duration_ms: u64,
+}
+
+// ============================================================================
+// Scanning
+// ============================================================================
+
+/// Lowercased name → table id, from ids and display names. A display name
+/// shared by several tables is ambiguous and dropped.
+fn build_name_index(table_ids: &[String], display_names: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut index: HashMap<String, Option<String>> = HashMap::new();
+    for id in table_ids {
+        index.insert(id.to_lowercase(), Some(id.clone()));
+    }
+    for id in table_ids {
+        let Some(name) = display_names.get(id) else { continue };
+        let key = name.trim().to_lowercase();
+        if key.is_empty() || key == id.to_lowercase() {
+            continue;
+        }
+        index
+            .entry(key)
+            .and_modify(|existing| {
+                if existing.as_deref() != Some(id.as_str()) {
+                    *existing = None;
+                }
+            })
+            .or_insert_with(|| Some(id.clone()));
+    }
+    index.into_iter().filter_map(|(k, v)| Some((k, v?))).collect()
+}
+
+/// Table ids referenced in `content`, with how ("wiki" beats "code").
+/// Wiki targets match by their last path segment, so `[[.../table_x/overview]]`
+/// links to table x.
+fn find_references(content: &str, index: &HashMap<String, String>) -> BTreeMap<String, &'static str> {
+    let mut found = BTreeMap::new();
+    for caps in WIKI_LINK_RE.captures_iter(content) {
+        let target = caps[1].trim().trim_end_matches(".md");
+        let segments: Vec<&str> = target.split('/').collect();
+        let candidates = [
+            Some(target),
+            segments.last().copied(),
+            segments.iter().rev().find_map(|s| s.strip_prefix("table_")),
+        ];
+        if let Some(id) = candidates.into_iter().flatten().find_map(|c| index.get(&c.to_lowercase())) {
+            found.insert(id.clone(), "wiki");
+        }
+    }
+    for caps in CODE_SPAN_RE.captures_iter(content) {
+        if let Some(id) = index.get(&caps[1].trim().to_lowercase()) {
+            found.entry(id.clone()).or_insert("code");
+        }
+    }
+    found
+}
+
+/// "runbook", "sop" or "doc", from frontmatter type/category/tags and the file name
+fn doc_kind(content: &str, path: &Path) -> &'static str {
+    let frontmatter = parse_frontmatter(content).ok().flatten().unwrap_or_default();
+    let mut words: Vec<String> = ["type", "category", "tags"]
+        .iter()
+        .filter_map(|k| frontmatter.get(*k))
+        .flat_map(|v| match v {
+            Value::Array(items) => items.iter().filter_map(|i| i.as_str().map(String::from)).collect(),
+            Value::String(s) => vec![s.clone()],
+            _ => vec![],
+        })
+        .collect();
+    words.push(path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+    let tokens: Vec<String> = words
+        .iter()
+        .flat_map(|w| w.split(|c: char| !c.is_ascii_alphanumeric()).map(|t| t.to_lowercase()).collect::<Vec<_>>())
+        .collect();
+    if tokens.iter().any(|t| t == "runbook" || t == "runbooks" || t == "playbook") {
+        "runbook"
+    } else if tokens.iter().any(|t| t == "sop" || t == "sops") {
+        "sop"
+    } else {
+        "doc"
+    }
+}
+
+/// Links per table id, sorted SOPs first, then runbooks, then by title
+fn scan_documents(knowledge_root: &Path, skip: &Path, index: &HashMap<String, String>) -> (usize, HashMap<String, Vec<TableDocLink>>) {
+    let mut scanned = 0;
+    let mut links: HashMap<String, Vec<TableDocLink>> = HashMap::new();
+
+    let walker = WalkDir::new(knowledge_root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let hidden = e.depth() > 0 && e.file_name().to_string_lossy().starts_with('.');
+            !hidden && e.path() != skip && e.file_name() != "node_modules"
+        });
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        if entry.metadata().map(|m| m.len() > MAX_DOC_BYTES).unwrap_or(true) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        scanned += 1;
+
+        let references = find_references(&content, index);
+        if references.is_empty() {
+            continue;
+        }
+        let relative = path.strip_prefix(knowledge_root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        let title = frontmatter_field(&content, "title")
+            .unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+        let kind = doc_kind(&content, path);
+        for (table_id, reference) in references {
+            links.entry(table_id).or_default().push(TableDocLink {
+                path: relative.clone(),
+                title: title.clone(),
+                kind: kind.to_string(),
+                reference: reference.to_string(),
+            });
+        }
+    }
+
+    let rank = |kind: &str| match kind {
+        "sop" => 0,
+        "runbook" => 1,
+        _ => 2,
+    };
+    for docs in links.values_mut() {
+        docs.sort_by(|a, b| {
+            rank(&a.kind)
+                .cmp(&rank(&b.kind))
+                .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+        });
+    }
+    (scanned, links)
+}
+
+// ============================================================================
+// Overview section
+// ============================================================================
+
+/// `to` relative to the directory `from`, e.g. ../../../sops/close.md
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
+
+/// The "Documentation" section for a table overview, from the `docs` array of
+/// definition_details.json. None when nothing links to the table.
+pub(super) fn docs_section(details: &Value, table_folder: &Path, knowledge_root: &Path) -> Option<String> {
+    let docs: Vec<TableDocLink> = serde_json::from_value(details.get("docs")?.clone()).ok()?;
+    if docs.is_empty() {
+        return None;
+    }
+    let label = |kind: &str| match kind {
+        "sop" => "SOP",
+        "runbook" => "Runbook",
+        _ => "Doc",
+    };
+    let mut lines = vec![
+        SECTION_HEADING.to_string(),
+        String::new(),
+        "*Source: definition_details.json (docs, from val_link_table_docs)*".to_string(),
+        String::new(),
+    ];
+    for doc in &docs {
+        lines.push(format!(
+            "- **{}** [{}]({})",
+            label(&doc.kind),
+            doc.title,
+            relative_path(table_folder, &knowledge_root.join(&doc.path))
+        ));
+    }
+    lines.push(String::new());
+    Some(lines.join("\n"))
+}
+
+/// Knowledge root from settings, for callers without AppState
+pub(super) fn knowledge_root() -> Option<PathBuf> {
+    crate::commands::settings::load_settings()
+        .ok()
+        .and_then(|s| s.keys.get(crate::commands::settings::KEY_KNOWLEDGE_PATH).cloned())
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Replace the Documentation section of an overview (or remove it when
+/// `section` is None). A new section goes just above the generated footer.
+fn upsert_section(overview: &str, section: Option<&str>) -> String {
+    let heading = format!("\n{}\n", SECTION_HEADING);
+    let (before, after) = match overview.find(&heading) {
+        Some(start) => {
+            let body = start + heading.len();
+            let end = [overview[body..].find("\n## "), overview[body..].find(&format!("\n{}", OVERVIEW_FOOTER))]
+                .into_iter()
+                .flatten()
+                .min()
+                .map(|i| body + i + 1)
+                .unwrap_or(overview.len());
+            (&overview[..start + 1], &overview[end..])
+        }
+        None => match overview.rfind(OVERVIEW_FOOTER) {
+            Some(footer) => (&overview[..footer], &overview[footer..]),
+            None => (overview, ""),
+        },
+    };
+    let mut out = before.to_string();
+    if let Some(section) = section {
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push_str(if out.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        out.push_str(section);
+        out.push('\n');
+    }
+    out.push_str(after);
+    out
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Link a domain's tables to the knowledge documents that mention them.
+/// Rewrites only files whose content changed; tables without
+/// definition_details.json are skipped.
+#[command]
+pub async fn val_link_table_docs(state: State<'_, AppState>, domain: String) -> CmdResult<TableDocsResult> {
+    let start = Instant::now();
+    let domain_config = get_domain_config(&domain)?;
+    let global_path = &domain_config.global_path;
+    let knowledge_root = PathBuf::from(&state.knowledge_path);
+    if !knowledge_root.is_dir() {
+        return Err(CommandError::NotFound(format!("Knowledge folder not found: {}", state.knowledge_path)));
+    }
+
+    let data_models_path = Path::new(global_path).join("data_models");
+    let table_ids = get_tables_to_process(&data_models_path, "all");
+    let index = build_name_index(&table_ids, &build_table_display_names(global_path));
+    // Generated table docs mention their own table everywhere
+    let (documents_scanned, mut links) = scan_documents(&knowledge_root, &data_models_path, &index);
+
+    let mut result = TableDocsResult {
+        domain,
+        documents_scanned,
+        tables_linked: 0,
+        links: 0,
+        details_updated: 0,
+        overviews_updated: 0,
+        duration_ms: 0,
+    };
+    for table_id in &table_ids {
+        let table_folder = data_models_path.join(format!("table_{}", table_id));
+        let details_path = table_folder.join("definition_details.json");
+        let Some(mut details) = fs::read_to_string(&details_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+        else {
+            continue;
+        };
+        let Some(obj) = details.as_object_mut() else { continue };
+
+        let docs = links.remove(table_id).unwrap_or_default();
+        if !docs.is_empty() {
+            result.tables_linked += 1;
+            result.links += docs.len();
+        }
+        obj.insert("docs".to_string(), serde_json::to_value(&docs)?);
+        if write_json_if_changed(&details_path.to_string_lossy(), &details)? {
+            result.details_updated += 1;
+        }
+
+        let overview_path = table_folder.join("overview.md");
+        if let Ok(overview) = fs::read_to_string(&overview_path) {
+            let section = docs_section(&details, &table_folder, &knowledge_root);
+            let updated = upsert_section(&overview, section.as_deref());
+            if write_text_if_changed(&overview_path.to_string_lossy(), &updated)? {
+                result.overviews_updated += 1;
+            }
+        }
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_wiki_and_code_references() {
+        let ids = vec!["custom_tbl_1_2".to_string(), "custom_tbl_1_3".to_string(), "custom_tbl_1_4".to_string()];
+        let names = HashMap::from([
+            ("custom_tbl_1_2".to_string(), "Orders".to_string()),
+            ("custom_tbl_1_3".to_string(), "Payments".to_string()),
+            ("custom_tbl_1_4".to_string(), "Payments".to_string()),
+        ]);
+        let index = build_name_index(&ids, &names);
+        assert!(!index.contains_key("payments"));
+
+        let content = "See [[Orders|the orders table]] and `custom_tbl_1_3`.\n\
+                       Also [[domains/acme/data_models/table_custom_tbl_1_4/overview]], `Orders`, `Payments`.";
+        let refs = find_references(content, &index);
+        assert_eq!(
+            refs.into_iter().collect::<Vec<_>>(),
+            vec![("custom_tbl_1_2".to_string(), "wiki"), ("custom_tbl_1_3".to_string(), "code"), ("custom_tbl_1_4".to_string(), "wiki")]
+        );
+    }
+
+    #[test]
+    fn classifies_and_links_documents() {
+        assert_eq!(doc_kind("---\ntags: [finance, sop]\n---\n", Path::new("month-close.md")), "sop");
+        assert_eq!(doc_kind("", Path::new("ops/ingest-runbook.md")), "runbook");
+        assert_eq!(doc_kind("", Path::new("notes.md")), "doc");
+        assert_eq!(
+            relative_path(Path::new("/kb/domains/acme/data_models/table_x"), Path::new("/kb/sops/close.md")),
+            "../../../../sops/close.md"
+        );
+    }
+
+    #[test]
+    fn replaces_or_inserts_documentation_section() {
+        let overview = "# Orders\n\n## Column Reference\n\nrows\n\n---\n\n*Generated by tv-client on 2026-01-01*";
+        let inserted = upsert_section(overview, Some("## Documentation\n\n- **SOP** [Close](a.md)\n"));
+        assert_eq!(
+            inserted,
+            "# Orders\n\n## Column Reference\n\nrows\n\n## Documentation\n\n- **SOP** [Close](a.md)\n\n---\n\n*Generated by tv-client on 2026-01-01*"
+        );
+
+        let replaced = upsert_section(&inserted, Some("## Documentation\n\n- **Doc** [Notes](b.md)\n"));
+        assert!(replaced.contains("[Notes](b.md)") && !replaced.contains("[Close](a.md)"));
+        assert!(replaced.ends_with("*Generated by tv-client on 2026-01-01*"));
+
+        assert_eq!(upsert_section(&inserted, None), overview);
+    }
+}
@@ -380,7 +380,7 @@ fn traverse_all_tables(
     }
 }
 
-fn get_tables_to_process(data_models_path: &Path, table_name: &str) -> Vec<String> {
+pub(super) fn get_tables_to_process(data_models_path: &Path, table_name: &str) -> Vec<String> {
     if table_name == "all" {
         fs::read_dir(data_models_path)
             .ok()
@@ -410,7 +410,7 @@ pub struct TableInfo {
 }
 
 /// Build a lookup map of table_name -> display_name from all_tables.json
-fn build_table_display_names(global_path: &str) -> HashMap<String, String> {
+pub(crate) fn build_table_display_names(global_path: &str) -> HashMap<String, String> {
     let all_tables_path = Path::new(global_path).join("schema/all_tables.json");
     let mut names: HashMap<String, String> = HashMap::new();
 
@@ -3434,6 +3434,26 @@ pub async fn val_generate_table_overview_md(
         }
     }
 
+    // Source integrations (documented by val_extract_integrations)
+    if details["meta"]["dataSource"].as_str() == Some("integration") {
+        lines.push("### Source Integrations".to_string());
+        lines.push(String::new());
+        let integrations = super::integrations::integrations_for_table(global_path, &table_name);
+        if integrations.is_empty() {
+            lines.push("*No integration configuration found for this table (see integrations/summary.md)*".to_string());
+        }
+        for integration in &integrations {
+            lines.push(format!(
+                "- [{}](../../integrations/integration_{}/overview.md) — {}, {}",
+                integration.name,
+                integration.id,
+                integration.source_system.as_deref().unwrap_or("unknown source"),
+                integration.schedule.as_deref().unwrap_or("not scheduled")
+            ));
+        }
+        lines.push(String::new());
+    }
+
     lines.push("---".to_string());
     lines.push(String::new());
 
@@ -3858,6 +3878,13 @@ pub async fn val_generate_table_overview_md(
         }
     }
 
+    // Documentation (linked by val_link_table_docs)
+    if let Some(section) = super::table_docs::knowledge_root()
+        .and_then(|root| super::table_docs::docs_section(&details, &table_folder, &root))
+    {
+        lines.push(section);
+    }
+
     // Footer
     lines.push("---".to_string());
     lines.push(String::new());
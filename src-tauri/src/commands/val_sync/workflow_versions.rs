@@ -0,0 +1,463 @@
+// VAL Sync Workflow Versions - Versioned workflow definitions + structured diffs
+// The workflow extractor snapshots each definition it rewrites, so when a
+// workflow breaks we can see which plugins and params changed between syncs.
+
+use super::config::get_domain_config;
+use crate::commands::error::{CmdResult, CommandError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Oldest versions are pruned beyond this many per workflow
+const MAX_VERSIONS: usize = 50;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One file under `workflow_versions/workflow_{id}/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowVersion {
+    pub version: u32,
+    /// Start of the extract run that saved it
+    pub saved_at: String,
+    pub definition: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowVersionInfo {
+    pub version: u32,
+    pub saved_at: String,
+    pub updated_by: Option<String>,
+    pub updated_date: Option<String>,
+    pub plugin_count: usize,
+}
+
+/// A single value that differs, addressed like `params.input.tabs[0].name`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueChange {
+    pub path: String,
+    pub change: String, // added | removed | changed
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginRef {
+    pub key: String,
+    pub name: String,
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginChange {
+    pub key: String,
+    pub name: String,
+    pub from_position: usize,
+    pub to_position: usize,
+    pub changes: Vec<ValueChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDiff {
+    pub domain: String,
+    pub workflow_id: String,
+    pub from: WorkflowVersionInfo,
+    pub to: WorkflowVersionInfo,
+    pub plugins_added: Vec<PluginRef>,
+    pub plugins_removed: Vec<PluginRef>,
+    /// Plugins present in both versions whose params changed or that moved
+    pub plugins_changed: Vec<PluginChange>,
+    /// Workflow-level fields outside the plugin list (schedule, name, ...)
+    pub workflow_changes: Vec<ValueChange>,
+    pub identical: bool,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn versions_dir(global_path: &str, workflow_id: &str) -> PathBuf {
+    Path::new(global_path)
+        .join("workflow_versions")
+        .join(format!("workflow_{}", workflow_id))
+}
+
+/// Version numbers on disk, ascending
+fn version_numbers(dir: &Path) -> Vec<u32> {
+    let mut numbers: Vec<u32> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.strip_prefix('v')?.strip_suffix(".json")?.parse().ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    numbers.sort_unstable();
+    numbers
+}
+
+fn version_file(dir: &Path, version: u32) -> PathBuf {
+    dir.join(format!("v{:04}.json", version))
+}
+
+fn read_version(dir: &Path, version: u32) -> CmdResult<WorkflowVersion> {
+    let path = version_file(dir, version);
+    let content = fs::read_to_string(&path)
+        .map_err(|_| CommandError::NotFound(format!("Workflow version {} not found", version)))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Definition currently on disk, but only when the workflow has no versions
+/// yet — it becomes the baseline the first recorded change is compared to.
+pub fn baseline_before_write(global_path: &str, workflow_id: &str, definition_path: &str) -> Option<Value> {
+    if !version_numbers(&versions_dir(global_path, workflow_id)).is_empty() {
+        return None;
+    }
+    fs::read_to_string(definition_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Save a rewritten definition as the next version (after `baseline`, if
+/// given), pruning the oldest beyond MAX_VERSIONS.
+pub fn record_version(
+    global_path: &str,
+    workflow_id: &str,
+    baseline: Option<Value>,
+    definition: &Value,
+    run_at: &str,
+) -> CmdResult<()> {
+    let dir = versions_dir(global_path, workflow_id);
+    fs::create_dir_all(&dir)?;
+    let mut next = version_numbers(&dir).last().copied().unwrap_or(0) + 1;
+
+    let mut to_write = Vec::new();
+    if let Some(baseline) = baseline {
+        let saved_at = baseline
+            .pointer("/_ownership/updated_date")
+            .and_then(|v| v.as_str())
+            .unwrap_or(run_at)
+            .to_string();
+        to_write.push((saved_at, baseline));
+    }
+    to_write.push((run_at.to_string(), definition.clone()));
+
+    for (saved_at, definition) in to_write {
+        let version = WorkflowVersion { version: next, saved_at, definition };
+        fs::write(version_file(&dir, next), serde_json::to_string_pretty(&version)?)?;
+        next += 1;
+    }
+
+    let numbers = version_numbers(&dir);
+    if numbers.len() > MAX_VERSIONS {
+        for old in &numbers[..numbers.len() - MAX_VERSIONS] {
+            let _ = fs::remove_file(version_file(&dir, *old));
+        }
+    }
+    Ok(())
+}
+
+fn plugins(definition: &Value) -> &[Value] {
+    definition
+        .pointer("/data/workflow/plugins")
+        .and_then(|p| p.as_array())
+        .map(|a| a.as_slice())
+        .unwrap_or(&[])
+}
+
+fn version_info(version: &WorkflowVersion) -> WorkflowVersionInfo {
+    let ownership = version.definition.get("_ownership");
+    let field = |key: &str| {
+        ownership
+            .and_then(|o| o.get(key))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+    WorkflowVersionInfo {
+        version: version.version,
+        saved_at: version.saved_at.clone(),
+        updated_by: field("updated_by"),
+        updated_date: field("updated_date"),
+        plugin_count: plugins(&version.definition).len(),
+    }
+}
+
+// ============================================================================
+// Diff
+// ============================================================================
+
+fn join_path(base: &str, key: &str) -> String {
+    if base.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", base, key)
+    }
+}
+
+/// Recursive leaf-level diff. Arrays are compared by index.
+fn diff_values(path: &str, before: &Value, after: &Value, out: &mut Vec<ValueChange>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let child = join_path(path, key);
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_values(&child, x, y, out),
+                    (Some(x), None) => out.push(ValueChange {
+                        path: child,
+                        change: "removed".to_string(),
+                        before: Some(x.clone()),
+                        after: None,
+                    }),
+                    (None, Some(y)) => out.push(ValueChange {
+                        path: child,
+                        change: "added".to_string(),
+                        before: None,
+                        after: Some(y.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(x), Some(y)) => diff_values(&child, x, y, out),
+                    (Some(x), None) => out.push(ValueChange {
+                        path: child,
+                        change: "removed".to_string(),
+                        before: Some(x.clone()),
+                        after: None,
+                    }),
+                    (None, Some(y)) => out.push(ValueChange {
+                        path: child,
+                        change: "added".to_string(),
+                        before: None,
+                        after: Some(y.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if before != after => out.push(ValueChange {
+            path: path.to_string(),
+            change: "changed".to_string(),
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn plugin_name(plugin: &Value) -> String {
+    plugin
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+/// Stable identity for matching plugins across versions: an explicit id when
+/// the plugin has one, else its name plus occurrence (`SQLQueryExecutorPlugin#2`)
+fn plugin_keys(plugins: &[Value]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    plugins
+        .iter()
+        .map(|p| {
+            let id = ["id", "plugin_id", "key", "uuid"].iter().find_map(|k| {
+                p.get(*k).and_then(|v| {
+                    v.as_str().map(String::from).or_else(|| v.as_u64().map(|n| n.to_string()))
+                })
+            });
+            match id {
+                Some(id) => id,
+                None => {
+                    let name = plugin_name(p);
+                    let n = seen.entry(name.clone()).or_insert(0);
+                    *n += 1;
+                    format!("{}#{}", name, n)
+                }
+            }
+        })
+        .collect()
+}
+
+struct DefinitionDiff {
+    added: Vec<PluginRef>,
+    removed: Vec<PluginRef>,
+    changed: Vec<PluginChange>,
+    workflow: Vec<ValueChange>,
+}
+
+/// Compare two workflow definitions plugin by plugin
+fn diff_definitions(before: &Value, after: &Value) -> DefinitionDiff {
+    let (old_plugins, new_plugins) = (plugins(before), plugins(after));
+    let (old_keys, new_keys) = (plugin_keys(old_plugins), plugin_keys(new_plugins));
+    let old_index: HashMap<&str, usize> = old_keys.iter().enumerate().map(|(i, k)| (k.as_str(), i)).collect();
+    let new_index: HashMap<&str, usize> = new_keys.iter().enumerate().map(|(i, k)| (k.as_str(), i)).collect();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (to_position, key) in new_keys.iter().enumerate() {
+        let plugin = &new_plugins[to_position];
+        match old_index.get(key.as_str()) {
+            None => added.push(PluginRef { key: key.clone(), name: plugin_name(plugin), position: to_position }),
+            Some(&from_position) => {
+                let mut changes = Vec::new();
+                diff_values("", &old_plugins[from_position], plugin, &mut changes);
+                if !changes.is_empty() || from_position != to_position {
+                    changed.push(PluginChange {
+                        key: key.clone(),
+                        name: plugin_name(plugin),
+                        from_position,
+                        to_position,
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    let removed: Vec<PluginRef> = old_keys
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| !new_index.contains_key(k.as_str()))
+        .map(|(i, k)| PluginRef { key: k.clone(), name: plugin_name(&old_plugins[i]), position: i })
+        .collect();
+
+    // Everything else, minus the plugin list and sync bookkeeping
+    let strip = |definition: &Value| {
+        let mut v = definition.clone();
+        if let Some(obj) = v.as_object_mut() {
+            obj.remove("_ownership");
+        }
+        if let Some(workflow) = v.pointer_mut("/data/workflow").and_then(|w| w.as_object_mut()) {
+            workflow.remove("plugins");
+        }
+        v
+    };
+    let mut workflow_changes = Vec::new();
+    diff_values("", &strip(before), &strip(after), &mut workflow_changes);
+
+    DefinitionDiff { added, removed, changed, workflow: workflow_changes }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Saved versions of a workflow definition, newest first
+#[command]
+pub fn val_list_workflow_versions(domain: String, workflow_id: String) -> CmdResult<Vec<WorkflowVersionInfo>> {
+    let domain_config = get_domain_config(&domain)?;
+    let dir = versions_dir(&domain_config.global_path, &workflow_id);
+    let mut infos = Vec::new();
+    for number in version_numbers(&dir).into_iter().rev() {
+        infos.push(version_info(&read_version(&dir, number)?));
+    }
+    Ok(infos)
+}
+
+/// Structured diff between two saved versions. `to` defaults to the latest
+/// version and `from` to the one before `to`.
+#[command]
+pub fn val_diff_workflow_versions(
+    domain: String,
+    workflow_id: String,
+    from: Option<u32>,
+    to: Option<u32>,
+) -> CmdResult<WorkflowDiff> {
+    let domain_config = get_domain_config(&domain)?;
+    let dir = versions_dir(&domain_config.global_path, &workflow_id);
+    let numbers = version_numbers(&dir);
+
+    let to = match to.or_else(|| numbers.last().copied()) {
+        Some(v) => v,
+        None => {
+            return Err(CommandError::NotFound(format!(
+                "No saved versions for workflow {} — run a workflow extract first",
+                workflow_id
+            )))
+        }
+    };
+    let from = match from.or_else(|| numbers.iter().rev().find(|n| **n < to).copied()) {
+        Some(v) => v,
+        None => {
+            return Err(CommandError::NotFound(format!(
+                "Workflow {} has no version before {} to compare with",
+                workflow_id, to
+            )))
+        }
+    };
+
+    let (before, after) = (read_version(&dir, from)?, read_version(&dir, to)?);
+    let diff = diff_definitions(&before.definition, &after.definition);
+    let identical =
+        diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty() && diff.workflow.is_empty();
+
+    Ok(WorkflowDiff {
+        domain,
+        workflow_id,
+        from: version_info(&before),
+        to: version_info(&after),
+        plugins_added: diff.added,
+        plugins_removed: diff.removed,
+        plugins_changed: diff.changed,
+        workflow_changes: diff.workflow,
+        identical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_plugins_by_name_occurrence_and_params() {
+        let before = json!({
+            "_ownership": { "updated_by": "ana" },
+            "data": { "workflow": {
+                "cron": "0 1 * * *",
+                "plugins": [
+                    { "name": "SQLQueryExecutorPlugin", "params": { "sql_query": "select 1" } },
+                    { "name": "ClearTableRecordsPlugin", "params": { "table": "custom_tbl_1" } }
+                ]
+            }}
+        });
+        let after = json!({
+            "_ownership": { "updated_by": "ben" },
+            "data": { "workflow": {
+                "cron": "0 2 * * *",
+                "plugins": [
+                    { "name": "SQLQueryExecutorPlugin", "params": { "sql_query": "select 2", "limit": 10 } },
+                    { "name": "WorkflowExecutorPlugin", "params": { "workflow_id": [7] } }
+                ]
+            }}
+        });
+
+        let diff = diff_definitions(&before, &after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, "WorkflowExecutorPlugin#1");
+        assert_eq!(diff.removed[0].name, "ClearTableRecordsPlugin");
+
+        assert_eq!(diff.changed.len(), 1);
+        let paths: Vec<(&str, &str)> = diff.changed[0]
+            .changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.change.as_str()))
+            .collect();
+        assert_eq!(paths, vec![("params.limit", "added"), ("params.sql_query", "changed")]);
+
+        // Ownership is bookkeeping, not a workflow change
+        assert_eq!(diff.workflow.len(), 1);
+        assert_eq!(diff.workflow[0].path, "data.workflow.cron");
+    }
+}
@@ -0,0 +1,201 @@
+// Work Module - AI assist for task descriptions
+// Expands a one-liner into a structured description, drafts acceptance
+// criteria, or summarizes the task's discussion thread. Results are written
+// into the description and the task is flagged ai_generated until a person
+// edits the description (see work_update_task).
+
+use super::tasks::{apply_task_update, work_get_task};
+use super::types::*;
+use crate::commands::discussions::Discussion;
+use crate::commands::ai::anthropic_messages;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::settings;
+use crate::commands::supabase::get_client;
+use serde::{Deserialize, Serialize};
+
+const MODEL: &str = "claude-haiku-4-5-20251001";
+const CRITERIA_HEADING: &str = "Acceptance criteria";
+const SUMMARY_HEADING: &str = "Thread summary";
+/// Comments beyond this are dropped from the oldest end
+const MAX_THREAD_CHARS: usize = 40_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiAssistResult {
+    pub task: Task,
+    pub mode: String,
+    /// The generated text, as written into the description
+    pub content: String,
+}
+
+// ============================================================================
+// Description sections
+// ============================================================================
+
+fn is_heading(line: &str, heading: &str) -> bool {
+    line.trim()
+        .strip_prefix("## ")
+        .is_some_and(|h| h.trim().eq_ignore_ascii_case(heading))
+}
+
+/// Body of a "## heading" section, if present
+pub fn section(description: &str, heading: &str) -> Option<String> {
+    let mut lines = description.lines().skip_while(|l| !is_heading(l, heading));
+    lines.next()?;
+    let body: Vec<&str> = lines.take_while(|l| !l.trim_start().starts_with("## ")).collect();
+    Some(body.join("\n").trim().to_string())
+}
+
+/// Replace a "## heading" section in place, or append it
+pub fn upsert_section(description: &str, heading: &str, content: &str) -> String {
+    let block = format!("## {}\n\n{}", heading, content.trim());
+    let lines: Vec<&str> = description.lines().collect();
+    let Some(start) = lines.iter().position(|l| is_heading(l, heading)) else {
+        let base = description.trim_end();
+        return if base.is_empty() { block } else { format!("{}\n\n{}", base, block) };
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with("## "))
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    let before = lines[..start].join("\n");
+    let after = lines[end..].join("\n");
+    [before.trim_end(), block.as_str(), after.trim()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// ============================================================================
+// Prompting
+// ============================================================================
+
+fn task_context(task: &Task) -> String {
+    let mut ctx = format!("Task: {}", task.title);
+    if let Some(project) = &task.project {
+        ctx.push_str(&format!("\nProject: {}", project.name));
+    }
+    if let Some(desc) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        ctx.push_str(&format!("\n\nCurrent description:\n{}", desc));
+    }
+    ctx
+}
+
+fn thread_text(comments: &[Discussion]) -> String {
+    let mut lines: Vec<String> = comments
+        .iter()
+        .map(|c| format!("[{}] {}: {}", c.created_at.get(..16).unwrap_or(&c.created_at), c.author, c.body))
+        .collect();
+    let mut total: usize = lines.iter().map(|l| l.len()).sum();
+    while total > MAX_THREAD_CHARS && lines.len() > 1 {
+        total -= lines.remove(0).len();
+    }
+    lines.join("\n\n")
+}
+
+async fn generate(api_key: &str, system: &str, user: &str) -> CmdResult<String> {
+    anthropic_messages(api_key, MODEL, system, user, 2048, 0.3).await
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Run an AI assist on a task and write the result into its description.
+/// `mode`: "expand" (structured description from the title/one-liner),
+/// "acceptance_criteria" (adds/replaces an Acceptance criteria section) or
+/// "summarize" (adds/replaces a Thread summary of the task's discussion).
+#[tauri::command]
+pub async fn work_ai_assist_task(task_id: String, mode: String) -> CmdResult<AiAssistResult> {
+    let api_key = settings::settings_get_anthropic_key()?
+        .ok_or_else(|| CommandError::Config("Anthropic API key not configured. Go to Settings (⌘,) to add it.".into()))?;
+    let task = work_get_task(task_id.clone()).await?;
+    let current = task.description.clone().unwrap_or_default();
+    let client = get_client().await?;
+
+    let (content, description) = match mode.as_str() {
+        "expand" => {
+            let system = "You turn terse task notes into clear task descriptions for a small consulting and \
+                software team. Write GitHub-flavoured markdown with short sections: ## Context, ## Goal, \
+                ## Approach (bullets), ## Open questions (omit if none). Keep every fact from the existing \
+                description, invent no names, numbers or deadlines, and stay under 250 words. Output only the \
+                description.";
+            let content = generate(&api_key, system, &task_context(&task)).await?;
+            // Sections added by the other modes survive a re-expand
+            let mut description = content.clone();
+            for heading in [CRITERIA_HEADING, SUMMARY_HEADING] {
+                if let Some(body) = section(&current, heading).filter(|b| !b.is_empty()) {
+                    description = upsert_section(&description, heading, &body);
+                }
+            }
+            (content, description)
+        }
+        "acceptance_criteria" => {
+            let system = "You write acceptance criteria for a task. Output only a markdown checklist \
+                (\"- [ ] ...\") of 3-7 concrete, testable criteria based on the task; no heading, no preamble.";
+            let content = generate(&api_key, system, &task_context(&task)).await?;
+            let description = upsert_section(&current, CRITERIA_HEADING, &content);
+            (content, description)
+        }
+        "summarize" => {
+            let comments: Vec<Discussion> = client
+                .select(
+                    "discussions",
+                    &format!("entity_type=eq.task&entity_id=eq.{}&order=created_at.asc", task_id),
+                )
+                .await?;
+            if comments.is_empty() {
+                return Err(CommandError::NotFound("This task has no comments to summarize".to_string()));
+            }
+            let system = "You summarize a task's comment thread for someone catching up. Output markdown \
+                bullets only: the current state, decisions made (with who made them), and open questions or \
+                next steps. At most 8 bullets, no heading, no preamble.";
+            let user = format!("{}\n\nComments (oldest first):\n{}", task_context(&task), thread_text(&comments));
+            let content = generate(&api_key, system, &user).await?;
+            let description = upsert_section(&current, SUMMARY_HEADING, &content);
+            (content, description)
+        }
+        other => {
+            return Err(CommandError::Config(format!(
+                "Unknown assist mode: {} (expected expand, acceptance_criteria or summarize)",
+                other
+            )))
+        }
+    };
+
+    let update = UpdateTask {
+        description: Some(description),
+        ai_generated: Some(true),
+        ai_generated_at: Some(chrono::Utc::now().to_rfc3339()),
+        ..Default::default()
+    };
+    let task = apply_task_update(&client, task_id, &update).await?;
+
+    Ok(AiAssistResult { task, mode, content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upserts_sections_without_touching_the_rest() {
+        let desc = "Ship the export.\n\n## Acceptance criteria\n\n- [ ] old\n\n## Notes\n\nkeep me";
+        let updated = upsert_section(desc, CRITERIA_HEADING, "- [ ] new");
+        assert_eq!(
+            updated,
+            "Ship the export.\n\n## Acceptance criteria\n\n- [ ] new\n\n## Notes\n\nkeep me"
+        );
+        assert_eq!(section(&updated, "notes").as_deref(), Some("keep me"));
+
+        assert_eq!(upsert_section("", SUMMARY_HEADING, "- done"), "## Thread summary\n\n- done");
+        assert_eq!(
+            upsert_section("One-liner", SUMMARY_HEADING, "- done"),
+            "One-liner\n\n## Thread summary\n\n- done"
+        );
+        assert!(section("no sections", CRITERIA_HEADING).is_none());
+    }
+}
@@ -0,0 +1,476 @@
+// Work Module - Working hours, team availability and due reminders
+// Each user can set a timezone, a daily working window and a holiday country
+// on their users row. Due dates and SLA timers are read on the assignee's own
+// clock: a task due Friday is due when *their* Friday's working hours end.
+// Users without settings work Singapore hours on the SG calendar.
+
+use super::calendar::{country_code, load_calendar, parse_date, BusinessCalendar};
+use super::notifier::{muted_recipients, task_key, NOTIFIER_ACTOR};
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::{HashMap, HashSet};
+use tauri::Emitter;
+
+const DEFAULT_TIMEZONE: Tz = chrono_tz::Asia::Singapore;
+const DEFAULT_START: (u32, u32) = (9, 0);
+const DEFAULT_END: (u32, u32) = (18, 0);
+/// How far ahead to look for someone's next working window
+const MAX_SCAN_DAYS: i64 = 366;
+/// Overdue reminders only go out for tasks that fell due this recently, so
+/// turning the feature on doesn't flood everyone with years-old tasks
+const OVERDUE_LOOKBACK_DAYS: i64 = 2;
+const REMINDER_INTERVAL_SECS: u64 = 15 * 60;
+
+const TASK_SELECT: &str =
+    "select=*,project:projects(*),status:task_statuses(*),assignees:task_assignees(user:users(*))";
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(s.trim(), "%H:%M"))
+        .ok()
+}
+
+/// A user's working hours in their own timezone
+#[derive(Debug, Clone)]
+pub struct WorkingHours {
+    tz: Tz,
+    start: NaiveTime,
+    end: NaiveTime,
+    country: String,
+}
+
+impl WorkingHours {
+    pub fn new(tz: Tz, start: NaiveTime, end: NaiveTime, country: &str) -> Self {
+        Self { tz, start, end, country: country.to_string() }
+    }
+
+    /// Settings from the users row; unset or unparseable fields use the defaults
+    pub fn for_user(user: &User) -> Self {
+        let time = |v: Option<&str>, (h, m): (u32, u32)| {
+            v.and_then(parse_time)
+                .unwrap_or_else(|| NaiveTime::from_hms_opt(h, m, 0).unwrap())
+        };
+        Self {
+            tz: user.timezone.as_deref().and_then(|t| t.parse().ok()).unwrap_or(DEFAULT_TIMEZONE),
+            start: time(user.work_start.as_deref(), DEFAULT_START),
+            end: time(user.work_end.as_deref(), DEFAULT_END),
+            country: country_code(user.country.clone()),
+        }
+    }
+
+    pub fn timezone(&self) -> &str {
+        self.tz.name()
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    /// Shifts ending before they start run past midnight
+    fn overnight(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// A local wall-clock time as UTC. Inside a DST gap the clock has jumped
+    /// forward, so the time an hour later is used.
+    fn at(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        let local = date.and_time(time);
+        match self.tz.from_local_datetime(&local) {
+            LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => t.with_timezone(&Utc),
+            LocalResult::None => self
+                .tz
+                .from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|| Utc.from_utc_datetime(&local)),
+        }
+    }
+
+    pub fn local_date(&self, t: DateTime<Utc>) -> NaiveDate {
+        t.with_timezone(&self.tz).date_naive()
+    }
+
+    /// The working window starting on local `date`, or None on their days off
+    pub fn window(&self, date: NaiveDate, calendar: &BusinessCalendar) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if !calendar.is_working_day(date) {
+            return None;
+        }
+        let end_date = if self.overnight() { date + Duration::days(1) } else { date };
+        Some((self.at(date, self.start), self.at(end_date, self.end)))
+    }
+
+    /// The window `now` falls in (yesterday's, for an overnight shift)
+    pub fn current_window(&self, now: DateTime<Utc>, calendar: &BusinessCalendar) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let today = self.local_date(now);
+        [today - Duration::days(1), today]
+            .into_iter()
+            .filter_map(|d| self.window(d, calendar))
+            .find(|(start, end)| *start <= now && now < *end)
+    }
+
+    /// Start of the next working window after `now`
+    pub fn next_start(&self, now: DateTime<Utc>, calendar: &BusinessCalendar) -> Option<DateTime<Utc>> {
+        let today = self.local_date(now);
+        (0..MAX_SCAN_DAYS)
+            .filter_map(|i| self.window(today + Duration::days(i), calendar))
+            .map(|(start, _)| start)
+            .find(|start| *start > now)
+    }
+
+    /// When their working day starts on the due date (reminder time)
+    pub fn day_start(&self, due: NaiveDate) -> DateTime<Utc> {
+        self.at(due, self.start)
+    }
+
+    /// When their working day ends on the due date (the deadline)
+    pub fn due_at(&self, due: NaiveDate) -> DateTime<Utc> {
+        let end_date = if self.overnight() { due + Duration::days(1) } else { due };
+        self.at(end_date, self.end)
+    }
+
+    /// Working minutes between two instants; negative when `to` is earlier
+    pub fn working_minutes_between(&self, from: DateTime<Utc>, to: DateTime<Utc>, calendar: &BusinessCalendar) -> i64 {
+        if to < from {
+            return -self.working_minutes_between(to, from, calendar);
+        }
+        let mut total = 0;
+        let mut date = self.local_date(from) - Duration::days(1);
+        let last = self.local_date(to);
+        while date <= last {
+            if let Some((start, end)) = self.window(date, calendar) {
+                let (start, end) = (start.max(from), end.min(to));
+                if end > start {
+                    total += (end - start).num_minutes();
+                }
+            }
+            date += Duration::days(1);
+        }
+        total
+    }
+}
+
+/// One calendar per country the users work in
+async fn load_calendars<'a>(
+    client: &SupabaseClient,
+    hours: impl Iterator<Item = &'a WorkingHours>,
+) -> CmdResult<HashMap<String, BusinessCalendar>> {
+    let mut calendars = HashMap::new();
+    for h in hours {
+        if !calendars.contains_key(h.country()) {
+            calendars.insert(h.country().to_string(), load_calendar(client, h.country()).await?);
+        }
+    }
+    Ok(calendars)
+}
+
+fn availability(user: &User, hours: &WorkingHours, calendar: &BusinessCalendar, date: NaiveDate, now: DateTime<Utc>) -> UserAvailability {
+    let current = hours.current_window(now, calendar);
+    let window = hours.window(date, calendar);
+    let status = if calendar.is_holiday(date) {
+        "holiday"
+    } else if window.is_none() {
+        "day_off"
+    } else if date == hours.local_date(now) && current.is_none() {
+        "off_hours"
+    } else {
+        "working"
+    };
+    UserAvailability {
+        user_id: user.id.clone(),
+        name: user.name.clone(),
+        timezone: hours.timezone().to_string(),
+        country: hours.country().to_string(),
+        local_time: now.with_timezone(&hours.tz).to_rfc3339(),
+        working_now: current.is_some(),
+        status: status.to_string(),
+        window_start: window.map(|(s, _)| s.to_rfc3339()),
+        window_end: window.map(|(_, e)| e.to_rfc3339()),
+        next_available_at: match current {
+            Some(_) => None,
+            None => hours.next_start(now, calendar).map(|t| t.to_rfc3339()),
+        },
+    }
+}
+
+fn task_assignees(task: &Task) -> impl Iterator<Item = &User> {
+    task.assignees
+        .iter()
+        .flatten()
+        .filter_map(|a| a.user.as_ref())
+        .filter(|u| u.user_type == "human")
+}
+
+fn is_closed(task: &Task) -> bool {
+    task.completed_at.is_some()
+        || task
+            .status
+            .as_ref()
+            .is_some_and(|s| s.status_type == "completed" || s.status_type == "canceled")
+}
+
+// ============================================================================
+// Due reminders
+// ============================================================================
+
+/// Send "due today" reminders once each assignee's working day starts on the
+/// due date, and "overdue" ones once it has ended. Each goes out once per
+/// task, person and due date, and never to someone who muted the project.
+async fn send_due_reminders(client: &SupabaseClient, now: DateTime<Utc>) -> CmdResult<DueReminderResult> {
+    let today = now.date_naive();
+    // UTC ±1 day covers every timezone's "today"
+    let query = format!(
+        "{}&completed_at=is.null&due_date=gte.{}&due_date=lte.{}",
+        TASK_SELECT,
+        today - Duration::days(OVERDUE_LOOKBACK_DAYS + 1),
+        today + Duration::days(1),
+    );
+    let tasks: Vec<Task> = client.select("tasks", &query).await?;
+
+    let mut result = DueReminderResult { due_today: 0, overdue: 0 };
+    let mut muted: HashMap<String, HashSet<String>> = HashMap::new();
+    for task in tasks.iter().filter(|t| !is_closed(t)) {
+        let Some(due) = task.due_date.as_deref().and_then(|d| parse_date(d).ok()) else {
+            continue;
+        };
+        if !muted.contains_key(&task.project_id) {
+            let names = muted_recipients(client, &task.project_id).await?;
+            muted.insert(task.project_id.clone(), names);
+        }
+        let project_muted = &muted[&task.project_id];
+        for user in task_assignees(task).filter(|u| !project_muted.contains(&u.name)) {
+            let hours = WorkingHours::for_user(user);
+            let due_at = hours.due_at(due);
+            let local_due = due_at.with_timezone(&hours.tz).format("%a %H:%M");
+            let (kind, since, preview) = if now >= due_at {
+                if now - due_at > Duration::days(OVERDUE_LOOKBACK_DAYS) {
+                    continue;
+                }
+                ("overdue", due_at, format!("{}{} — overdue since {}", task_key(task), task.title, local_due))
+            } else if now >= hours.day_start(due) {
+                ("due_today", hours.day_start(due), format!("{}{} — due today by {}", task_key(task), task.title, local_due))
+            } else {
+                continue;
+            };
+
+            let sent: Vec<serde_json::Value> = client
+                .select(
+                    "notifications",
+                    &format!(
+                        "select=id&entity_id=eq.{}&type=eq.{}&recipient=eq.{}&created_at=gte.{}&limit=1",
+                        task.id,
+                        kind,
+                        urlencoding::encode(&user.name),
+                        urlencoding::encode(&since.to_rfc3339()),
+                    ),
+                )
+                .await?;
+            if !sent.is_empty() {
+                continue;
+            }
+
+            let row = serde_json::json!({
+                "recipient": user.name,
+                "type": kind,
+                "entity_type": "task",
+                "entity_id": task.id,
+                "actor": NOTIFIER_ACTOR,
+                "body_preview": preview,
+            });
+            let _: serde_json::Value = client.insert("notifications", &row).await?;
+            if kind == "overdue" {
+                result.overdue += 1;
+            } else {
+                result.due_today += 1;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Check for due reminders every 15 minutes. Call from main.rs setup hook.
+pub fn start_due_reminders(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(90)).await;
+        loop {
+            let result = match get_client().await {
+                Ok(client) => send_due_reminders(&client, Utc::now()).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(r) if r.due_today + r.overdue > 0 => {
+                    let _ = app_handle.emit("work:due-reminders", &r);
+                }
+                Ok(_) => {}
+                // Usually just "Supabase not configured" before sign-in
+                Err(e) => eprintln!("[work:due-reminders] Skipped: {}", e),
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(REMINDER_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Set a user's timezone (IANA name), working hours (HH:MM, local) and
+/// holiday country. Omitted fields are cleared back to the defaults.
+#[tauri::command]
+pub async fn work_update_user_working_hours(
+    user_id: String,
+    timezone: Option<String>,
+    work_start: Option<String>,
+    work_end: Option<String>,
+    country: Option<String>,
+) -> CmdResult<User> {
+    let invalid = |field: &str, message: String| CommandError::Validation {
+        message,
+        fields: vec![field.to_string()],
+    };
+    let timezone = timezone.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    if let Some(tz) = &timezone {
+        tz.parse::<Tz>()
+            .map_err(|_| invalid("timezone", format!("Unknown timezone '{}' (use an IANA name like Europe/London)", tz)))?;
+    }
+    let mut times = Vec::new();
+    for (field, value) in [("work_start", &work_start), ("work_end", &work_end)] {
+        let parsed = match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(v) => Some(parse_time(v).ok_or_else(|| invalid(field, format!("Invalid time '{}' (expected HH:MM)", v)))?),
+            None => None,
+        };
+        times.push(parsed);
+    }
+    if let [Some(start), Some(end)] = times[..] {
+        if start == end {
+            return Err(invalid("work_end", "Working hours must not start and end at the same time".to_string()));
+        }
+    }
+
+    let client = get_client().await?;
+    let row = serde_json::json!({
+        "timezone": timezone,
+        "work_start": times[0].map(|t| t.format("%H:%M").to_string()),
+        "work_end": times[1].map(|t| t.format("%H:%M").to_string()),
+        "country": country.map(|c| country_code(Some(c))),
+        "updated_at": Utc::now().to_rfc3339(),
+    });
+    client.update("users", &format!("id=eq.{}", user_id), &row).await
+}
+
+/// Who is working right now, and each person's working window on `date`
+/// (YYYY-MM-DD, default today) in their own timezone. `status` describes
+/// `date`; `working_now` is always about this moment.
+#[tauri::command]
+pub async fn work_get_team_availability(date: Option<String>) -> CmdResult<Vec<UserAvailability>> {
+    let now = Utc::now();
+    let date = date.as_deref().map(parse_date).transpose()?;
+    let client = get_client().await?;
+    let users: Vec<User> = client.select("users", "type=eq.human&order=name.asc").await?;
+    let hours: Vec<WorkingHours> = users.iter().map(WorkingHours::for_user).collect();
+    let calendars = load_calendars(&client, hours.iter()).await?;
+
+    Ok(users
+        .iter()
+        .zip(&hours)
+        .map(|(user, h)| {
+            let date = date.unwrap_or_else(|| h.local_date(now));
+            availability(user, h, &calendars[h.country()], date, now)
+        })
+        .collect())
+}
+
+/// A task's deadline for each assignee — the end of their working day on the
+/// due date — and the working time they have left (the SLA timer)
+#[tauri::command]
+pub async fn work_get_task_due_status(task_id: String) -> CmdResult<Vec<AssigneeDueStatus>> {
+    let client = get_client().await?;
+    let task: Task = client
+        .select_single("tasks", &format!("{}&id=eq.{}", TASK_SELECT, task_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Task not found: {}", task_id)))?;
+    let Some(due) = task.due_date.as_deref() else {
+        return Ok(Vec::new());
+    };
+    let due = parse_date(due)?;
+
+    let now = Utc::now();
+    let users: Vec<&User> = task_assignees(&task).collect();
+    let hours: Vec<WorkingHours> = users.iter().map(|u| WorkingHours::for_user(u)).collect();
+    let calendars = load_calendars(&client, hours.iter()).await?;
+
+    Ok(users
+        .iter()
+        .zip(&hours)
+        .map(|(user, h)| {
+            let due_at = h.due_at(due);
+            AssigneeDueStatus {
+                user_id: user.id.clone(),
+                name: user.name.clone(),
+                timezone: h.timezone().to_string(),
+                due_at: due_at.to_rfc3339(),
+                overdue: now >= due_at,
+                working_minutes_remaining: h.working_minutes_between(now, due_at, &calendars[h.country()]),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn t(s: &str) -> NaiveTime {
+        parse_time(s).unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn windows_follow_the_users_own_clock() {
+        let calendar = BusinessCalendar::new(vec![6, 7], HashSet::from([d("2026-12-25")]));
+        let london = WorkingHours::new(chrono_tz::Europe::London, t("09:00"), t("17:30"), "GB");
+
+        // BST (UTC+1) in July, GMT in November
+        assert_eq!(london.due_at(d("2026-07-03")), utc("2026-07-03T16:30:00Z"));
+        assert_eq!(london.due_at(d("2026-11-06")), utc("2026-11-06T17:30:00Z"));
+        assert!(london.window(d("2026-07-04"), &calendar).is_none());
+        assert!(london.window(d("2026-12-25"), &calendar).is_none());
+
+        // 08:30 UTC on a Friday is 09:30 in London; 17:00 UTC is after hours
+        assert!(london.current_window(utc("2026-07-03T08:30:00Z"), &calendar).is_some());
+        assert!(london.current_window(utc("2026-07-03T17:00:00Z"), &calendar).is_none());
+        assert_eq!(london.next_start(utc("2026-07-03T17:00:00Z"), &calendar), Some(utc("2026-07-06T08:00:00Z")));
+
+        // Friday 15:30 UTC to Monday 09:00 UTC: 1h Friday + 1h Monday, nothing over the weekend
+        assert_eq!(
+            london.working_minutes_between(utc("2026-07-03T15:30:00Z"), utc("2026-07-06T09:00:00Z"), &calendar),
+            120
+        );
+        assert_eq!(
+            london.working_minutes_between(utc("2026-07-06T09:00:00Z"), utc("2026-07-03T15:30:00Z"), &calendar),
+            -120
+        );
+    }
+
+    #[test]
+    fn overnight_shifts_end_the_next_day() {
+        let calendar = BusinessCalendar::new(vec![6, 7], HashSet::new());
+        let night = WorkingHours::new(chrono_tz::Asia::Singapore, t("22:00"), t("06:00"), "SG");
+
+        // Due Monday: the Monday night shift ends Tuesday 06:00 SGT
+        assert_eq!(night.due_at(d("2026-07-06")), utc("2026-07-06T22:00:00Z"));
+        // Tuesday 02:00 SGT is still Monday's shift
+        assert!(night.current_window(utc("2026-07-06T18:00:00Z"), &calendar).is_some());
+        assert!(night.current_window(utc("2026-07-06T06:00:00Z"), &calendar).is_none());
+    }
+}
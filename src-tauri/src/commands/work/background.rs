@@ -0,0 +1,30 @@
+// Work Module - Background rollup recompute
+// Keeps initiative progress summaries fresh without the UI polling for them.
+
+use tauri::Emitter;
+
+/// Recompute interval. Rollups are cheap (a few PostgREST reads per initiative)
+/// but nothing about them is urgent.
+const REFRESH_INTERVAL_SECS: u64 = 10 * 60;
+
+/// Start the initiative rollup loop. Call from main.rs setup hook.
+pub fn start_rollup_refresh(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Wait 45s before first run (let auth + workspace settle)
+        tokio::time::sleep(std::time::Duration::from_secs(45)).await;
+
+        loop {
+            match super::initiatives::refresh_initiative_summaries().await {
+                Ok(summaries) => {
+                    let _ = app_handle.emit("work:initiative-summaries", &summaries);
+                }
+                Err(e) => {
+                    // Usually just "Supabase not configured" before sign-in
+                    eprintln!("[work:rollups] Refresh skipped: {}", e);
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(REFRESH_INTERVAL_SECS)).await;
+        }
+    });
+}
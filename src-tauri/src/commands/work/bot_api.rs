@@ -0,0 +1,729 @@
+// Work Module - Bot API
+// Local HTTP entry point for external bots (users with type=bot) to file
+// tasks and project updates under their own identity.
+//
+// Each bot gets its own bearer token, a list of projects it may touch and a
+// per-minute rate limit. Config lives in ~/.tv-client/bot_api.json and only
+// stores token hashes; the plaintext token is shown once when issued. Every
+// call — accepted or refused — is written to bot_api_activity. The MCP REST
+// bridge authenticates with the same tokens and counts against the same limits.
+//
+//   GET   /me                          Bot identity + allowed projects
+//   GET   /projects                    Projects the bot may write to
+//   POST  /tasks                       Create a task (created_by = bot)
+//   PATCH /tasks/{id}                  Update a task in an allowed project
+//   POST  /projects/{id}/updates       Post a project status update
+
+use super::projects::work_create_project_update;
+use super::tasks::{default_status_id, work_create_task, work_get_task, work_update_task};
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::http_util::{read_request, respond, HttpRequest};
+use crate::commands::supabase::get_client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BotApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub bots: Vec<BotAccess>,
+}
+
+impl Default for BotApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 3850,
+            bots: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotAccess {
+    /// users.id of the bot
+    pub bot_id: String,
+    pub bot_name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Projects the bot may create tasks and updates in, and reach through
+    /// MCP bridge tool calls. Empty = none.
+    #[serde(default)]
+    pub allowed_project_ids: Vec<String>,
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit_per_minute: u32,
+    /// sha256 of the bearer token; never the token itself
+    #[serde(default)]
+    pub token_hash: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_rate_limit() -> u32 {
+    30
+}
+
+impl BotAccess {
+    pub fn allows_project(&self, project_id: &str) -> bool {
+        self.allowed_project_ids.iter().any(|p| p == project_id)
+    }
+}
+
+/// Token shown once after issuing or rotating
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotToken {
+    pub bot_id: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotApiStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub base_url: Option<String>,
+    pub bots: usize,
+    pub requests_served: u64,
+    pub requests_rejected: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotActivity {
+    pub id: String,
+    pub bot_id: String,
+    pub action: String,
+    pub method: String,
+    pub path: String,
+    pub status_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BotCreateTask {
+    project_id: String,
+    title: String,
+    description: Option<String>,
+    status_id: Option<String>,
+    priority: Option<i32>,
+    due_date: Option<String>,
+    session_ref: Option<String>,
+    requires_review: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BotUpdateTask {
+    title: Option<String>,
+    description: Option<String>,
+    status_id: Option<String>,
+    priority: Option<i32>,
+    due_date: Option<String>,
+    session_ref: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BotProjectUpdate {
+    content: String,
+    health: Option<String>,
+}
+
+#[derive(Default)]
+struct BotApiRuntime {
+    running_port: Option<u16>,
+    requests_served: u64,
+    requests_rejected: u64,
+    last_error: Option<String>,
+    /// Sliding window per bot id
+    recent_requests: HashMap<String, VecDeque<Instant>>,
+}
+
+static RUNTIME: std::sync::LazyLock<Mutex<BotApiRuntime>> =
+    std::sync::LazyLock::new(|| Mutex::new(BotApiRuntime::default()));
+
+/// Outcome of a routed call, kept for the activity log
+struct Handled {
+    status: &'static str,
+    body: serde_json::Value,
+    action: &'static str,
+    project_id: Option<String>,
+    entity_id: Option<String>,
+    error: Option<String>,
+}
+
+impl Handled {
+    fn ok(action: &'static str, body: serde_json::Value, project_id: Option<String>, entity_id: Option<String>) -> Self {
+        Self { status: "200 OK", body, action, project_id, entity_id, error: None }
+    }
+
+    fn refused(status: &'static str, action: &'static str, project_id: Option<String>, error: String) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": error }),
+            action,
+            project_id,
+            entity_id: None,
+            error: Some(error),
+        }
+    }
+}
+
+// ============================================================================
+// Config
+// ============================================================================
+
+fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("bot_api.json")
+}
+
+fn load_config() -> BotApiConfig {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_config(config: &BotApiConfig) -> CmdResult<()> {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// 32 bytes from the OS CSPRNG, hex-encoded
+fn generate_token() -> CmdResult<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| CommandError::Internal(format!("Failed to generate token: {}", e)))?;
+    Ok(format!("tvbot_{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()))
+}
+
+fn set_error(message: Option<String>) {
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.last_error = message;
+    }
+}
+
+// ============================================================================
+// Rate limiting
+// ============================================================================
+
+/// Sliding one-minute window: drop entries older than 60s, then admit if
+/// under `limit` (0 = unlimited)
+fn admit(window: &mut VecDeque<Instant>, now: Instant, limit: u32) -> bool {
+    while window
+        .front()
+        .map(|t| now.duration_since(*t).as_secs() >= 60)
+        .unwrap_or(false)
+    {
+        window.pop_front();
+    }
+    if limit > 0 && window.len() >= limit as usize {
+        return false;
+    }
+    window.push_back(now);
+    true
+}
+
+/// Count a call against the bot's window, whichever listener it came in on
+pub(crate) fn allow_request(bot: &BotAccess) -> bool {
+    let Ok(mut rt) = RUNTIME.lock() else {
+        return false;
+    };
+    let window = rt.recent_requests.entry(bot.bot_id.clone()).or_default();
+    admit(window, Instant::now(), bot.rate_limit_per_minute)
+}
+
+/// The enabled bot whose token the request carries. Config is re-read per
+/// call so grants and revocations apply immediately.
+pub(crate) fn authenticate(request: &HttpRequest) -> Option<BotAccess> {
+    let hash = hash_token(request.bearer_token()?);
+    load_config()
+        .bots
+        .into_iter()
+        .find(|b| b.enabled && b.token_hash.as_deref() == Some(hash.as_str()))
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+fn parse_body<T: serde::de::DeserializeOwned>(request: &HttpRequest) -> Result<T, String> {
+    serde_json::from_slice(&request.body).map_err(|e| format!("Invalid JSON body: {}", e))
+}
+
+async fn list_allowed_projects(bot: &BotAccess) -> CmdResult<Vec<Project>> {
+    if bot.allowed_project_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let client = get_client().await?;
+    client
+        .select(
+            "projects",
+            &format!("id=in.({})&order=name.asc", bot.allowed_project_ids.join(",")),
+        )
+        .await
+}
+
+async fn create_task(bot: &BotAccess, input: BotCreateTask) -> CmdResult<Task> {
+    let status_id = match input.status_id {
+        Some(id) => id,
+        None => default_status_id(&get_client().await?).await?,
+    };
+    let task = work_create_task(CreateTask {
+        project_id: input.project_id,
+        status_id,
+        title: input.title,
+        description: input.description,
+        priority: input.priority,
+        due_date: input.due_date,
+        assignee_ids: None,
+        milestone_id: None,
+        depends_on: None,
+        session_ref: input.session_ref,
+        requires_review: input.requires_review,
+        company_id: None,
+        contact_id: None,
+        task_type: None,
+    })
+    .await?;
+
+    let client = get_client().await?;
+    let _: serde_json::Value = client
+        .update(
+            "tasks",
+            &format!("id=eq.{}", task.id),
+            &serde_json::json!({ "created_by": bot.bot_id }),
+        )
+        .await?;
+    work_get_task(task.id).await
+}
+
+async fn route(bot: &BotAccess, request: &HttpRequest) -> Handled {
+    let method = request.method.as_str();
+    let path = request.path.trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["me"]) => Handled::ok(
+            "me",
+            serde_json::json!({
+                "botId": bot.bot_id,
+                "botName": bot.bot_name,
+                "allowedProjectIds": bot.allowed_project_ids,
+                "rateLimitPerMinute": bot.rate_limit_per_minute,
+            }),
+            None,
+            None,
+        ),
+        ("GET", ["projects"]) => match list_allowed_projects(bot).await {
+            Ok(projects) => Handled::ok("list_projects", serde_json::json!({ "projects": projects }), None, None),
+            Err(e) => Handled::refused("502 Bad Gateway", "list_projects", None, e.to_string()),
+        },
+        ("POST", ["tasks"]) => {
+            let input: BotCreateTask = match parse_body(request) {
+                Ok(v) => v,
+                Err(e) => return Handled::refused("400 Bad Request", "create_task", None, e),
+            };
+            let project_id = input.project_id.clone();
+            if !bot.allows_project(&project_id) {
+                return Handled::refused(
+                    "403 Forbidden",
+                    "create_task",
+                    Some(project_id),
+                    "Bot is not allowed to write to this project".into(),
+                );
+            }
+            match create_task(bot, input).await {
+                Ok(task) => {
+                    let id = task.id.clone();
+                    Handled::ok("create_task", serde_json::to_value(&task).unwrap_or_default(), Some(project_id), Some(id))
+                }
+                Err(e) => Handled::refused("502 Bad Gateway", "create_task", Some(project_id), e.to_string()),
+            }
+        }
+        ("PATCH", ["tasks", task_id]) => {
+            let input: BotUpdateTask = match parse_body(request) {
+                Ok(v) => v,
+                Err(e) => return Handled::refused("400 Bad Request", "update_task", None, e),
+            };
+            // Scope is checked against the task's current project, so a bot
+            // can't reach into other projects by guessing task ids
+            let existing = match work_get_task(task_id.to_string()).await {
+                Ok(task) => task,
+                Err(CommandError::NotFound(msg)) => return Handled::refused("404 Not Found", "update_task", None, msg),
+                Err(e) => return Handled::refused("502 Bad Gateway", "update_task", None, e.to_string()),
+            };
+            let project_id = existing.project_id.clone();
+            if !bot.allows_project(&project_id) {
+                return Handled::refused(
+                    "403 Forbidden",
+                    "update_task",
+                    Some(project_id),
+                    "Bot is not allowed to write to this project".into(),
+                );
+            }
+            let data = UpdateTask {
+                title: input.title,
+                description: input.description,
+                status_id: input.status_id,
+                priority: input.priority,
+                due_date: input.due_date,
+                session_ref: input.session_ref,
+                updated_by: Some(bot.bot_id.clone()),
+                ..Default::default()
+            };
+            match work_update_task(existing.id.clone(), data).await {
+                Ok(task) => Handled::ok(
+                    "update_task",
+                    serde_json::to_value(&task).unwrap_or_default(),
+                    Some(project_id),
+                    Some(existing.id),
+                ),
+                Err(e) => Handled::refused("502 Bad Gateway", "update_task", Some(project_id), e.to_string()),
+            }
+        }
+        ("POST", ["projects", project_id, "updates"]) => {
+            let project_id = project_id.to_string();
+            if !bot.allows_project(&project_id) {
+                return Handled::refused(
+                    "403 Forbidden",
+                    "project_update",
+                    Some(project_id),
+                    "Bot is not allowed to write to this project".into(),
+                );
+            }
+            let input: BotProjectUpdate = match parse_body(request) {
+                Ok(v) => v,
+                Err(e) => return Handled::refused("400 Bad Request", "project_update", Some(project_id), e),
+            };
+            let data = CreateProjectUpdate {
+                content: input.content,
+                health: input.health,
+                created_by: Some(bot.bot_id.clone()),
+            };
+            match work_create_project_update(project_id.clone(), data).await {
+                Ok(update) => {
+                    let id = update.id.clone();
+                    Handled::ok(
+                        "project_update",
+                        serde_json::to_value(&update).unwrap_or_default(),
+                        Some(project_id),
+                        Some(id),
+                    )
+                }
+                Err(e) => Handled::refused("502 Bad Gateway", "project_update", Some(project_id), e.to_string()),
+            }
+        }
+        _ => Handled::refused("404 Not Found", "unknown", None, "Not found".into()),
+    }
+}
+
+/// Best-effort write to the per-bot activity log
+async fn log_activity(bot_id: &str, request: &HttpRequest, handled: &Handled) {
+    let status_code: i32 = handled
+        .status
+        .split_whitespace()
+        .next()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+    let row = serde_json::json!({
+        "bot_id": bot_id,
+        "action": handled.action,
+        "method": request.method,
+        "path": request.path,
+        "status_code": status_code,
+        "project_id": handled.project_id,
+        "entity_id": handled.entity_id,
+        "error": handled.error,
+    });
+    let result = match get_client().await {
+        Ok(client) => client.insert::<_, serde_json::Value>("bot_api_activity", &row).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("[bot-api] Failed to log activity for {}: {}", bot_id, e);
+    }
+}
+
+fn handle_connection(mut stream: std::net::TcpStream) {
+    let Some(request) = read_request(&mut stream) else {
+        return;
+    };
+
+    let Some(bot) = authenticate(&request) else {
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.requests_rejected += 1;
+        }
+        respond(&mut stream, "401 Unauthorized", &serde_json::json!({ "error": "Missing or invalid bot token" }));
+        return;
+    };
+
+    let handled = if allow_request(&bot) {
+        tauri::async_runtime::block_on(route(&bot, &request))
+    } else {
+        Handled::refused("429 Too Many Requests", "rate_limited", None, "Rate limit exceeded".into())
+    };
+
+    if handled.status.starts_with('2') {
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.requests_served += 1;
+        }
+    } else if handled.status.starts_with("429") {
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.requests_rejected += 1;
+        }
+    } else if handled.status.starts_with("502") {
+        set_error(handled.error.clone());
+    }
+    respond(&mut stream, handled.status, &handled.body);
+    tauri::async_runtime::block_on(log_activity(&bot.bot_id, &request, &handled));
+}
+
+/// Start the HTTP listener if enabled and not already running. Port changes
+/// take effect on next app start.
+fn start_server(config: &BotApiConfig) -> CmdResult<()> {
+    {
+        let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+        if rt.running_port.is_some() {
+            return Ok(());
+        }
+    }
+
+    let listener = std::net::TcpListener::bind(format!("127.0.0.1:{}", config.port))
+        .map_err(|e| CommandError::Io(format!("Failed to bind bot API on port {}: {}", config.port, e)))?;
+
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.running_port = Some(config.port);
+    }
+    eprintln!("[bot-api] Listening on 127.0.0.1:{}", config.port);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if !load_config().enabled {
+                let mut stream = stream;
+                respond(&mut stream, "503 Service Unavailable", &serde_json::json!({ "error": "Bot API disabled" }));
+                continue;
+            }
+            std::thread::spawn(move || handle_connection(stream));
+        }
+        if let Ok(mut rt) = RUNTIME.lock() {
+            rt.running_port = None;
+        }
+    });
+
+    Ok(())
+}
+
+/// Called from app setup — starts the listener only if the user enabled it
+pub fn start_bot_api() {
+    let config = load_config();
+    if !config.enabled {
+        return;
+    }
+    if let Err(e) = start_server(&config) {
+        eprintln!("[bot-api] {}", e);
+        set_error(Some(e.to_string()));
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn work_bot_api_get_config() -> CmdResult<BotApiConfig> {
+    Ok(load_config())
+}
+
+/// Enable/disable the listener and change its port. Bot grants are managed
+/// with work_bot_api_grant / work_bot_api_revoke.
+#[tauri::command]
+pub async fn work_bot_api_save_config(enabled: bool, port: Option<u16>) -> CmdResult<BotApiStatus> {
+    let mut config = load_config();
+    config.enabled = enabled;
+    if let Some(port) = port {
+        config.port = port;
+    }
+    write_config(&config)?;
+    if config.enabled {
+        start_server(&config)?;
+    }
+    work_bot_api_status().await
+}
+
+/// Grant (or update) a bot's access. Issues a token the first time; returns
+/// `None` when the bot already has one.
+#[tauri::command]
+pub async fn work_bot_api_grant(
+    bot_id: String,
+    allowed_project_ids: Vec<String>,
+    rate_limit_per_minute: Option<u32>,
+    enabled: Option<bool>,
+) -> CmdResult<Option<BotToken>> {
+    let client = get_client().await?;
+    let user: User = client
+        .select_single("users", &format!("id=eq.{}&type=eq.bot", bot_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Bot not found: {}", bot_id)))?;
+
+    let mut config = load_config();
+    let index = match config.bots.iter().position(|b| b.bot_id == bot_id) {
+        Some(i) => i,
+        None => {
+            config.bots.push(BotAccess {
+                bot_id: bot_id.clone(),
+                bot_name: user.name.clone(),
+                enabled: true,
+                allowed_project_ids: Vec::new(),
+                rate_limit_per_minute: default_rate_limit(),
+                token_hash: None,
+            });
+            config.bots.len() - 1
+        }
+    };
+
+    let bot = &mut config.bots[index];
+    bot.bot_name = user.name;
+    bot.allowed_project_ids = allowed_project_ids;
+    if let Some(limit) = rate_limit_per_minute {
+        bot.rate_limit_per_minute = limit;
+    }
+    if let Some(enabled) = enabled {
+        bot.enabled = enabled;
+    }
+    let issued = if bot.token_hash.is_none() {
+        let token = generate_token()?;
+        bot.token_hash = Some(hash_token(&token));
+        Some(BotToken { bot_id, token })
+    } else {
+        None
+    };
+
+    write_config(&config)?;
+    Ok(issued)
+}
+
+/// Replace a bot's token; the old one stops working immediately
+#[tauri::command]
+pub async fn work_bot_api_rotate_token(bot_id: String) -> CmdResult<BotToken> {
+    let mut config = load_config();
+    let bot = config
+        .bots
+        .iter_mut()
+        .find(|b| b.bot_id == bot_id)
+        .ok_or_else(|| CommandError::NotFound(format!("Bot has no API access: {}", bot_id)))?;
+    let token = generate_token()?;
+    bot.token_hash = Some(hash_token(&token));
+    write_config(&config)?;
+    Ok(BotToken { bot_id, token })
+}
+
+/// Remove a bot's access entirely
+#[tauri::command]
+pub async fn work_bot_api_revoke(bot_id: String) -> CmdResult<()> {
+    let mut config = load_config();
+    config.bots.retain(|b| b.bot_id != bot_id);
+    write_config(&config)?;
+    if let Ok(mut rt) = RUNTIME.lock() {
+        rt.recent_requests.remove(&bot_id);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn work_bot_api_status() -> CmdResult<BotApiStatus> {
+    let bots = load_config().bots.len();
+    let rt = RUNTIME.lock().map_err(|e| CommandError::Internal(format!("Lock error: {}", e)))?;
+    Ok(BotApiStatus {
+        running: rt.running_port.is_some(),
+        port: rt.running_port,
+        base_url: rt.running_port.map(|p| format!("http://127.0.0.1:{}", p)),
+        bots,
+        requests_served: rt.requests_served,
+        requests_rejected: rt.requests_rejected,
+        last_error: rt.last_error.clone(),
+    })
+}
+
+/// Activity log for one bot, newest first
+#[tauri::command]
+pub async fn work_bot_api_list_activity(bot_id: String, limit: Option<u32>) -> CmdResult<Vec<BotActivity>> {
+    let client = get_client().await?;
+    let query = format!(
+        "bot_id=eq.{}&order=created_at.desc&limit={}",
+        bot_id,
+        limit.unwrap_or(100)
+    );
+    client.select("bot_api_activity", &query).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn rate_limit_window_slides_per_minute() {
+        let start = Instant::now();
+        let mut window = VecDeque::new();
+        assert!(admit(&mut window, start, 2));
+        assert!(admit(&mut window, start + Duration::from_secs(1), 2));
+        assert!(!admit(&mut window, start + Duration::from_secs(30), 2));
+        // First request has aged out
+        assert!(admit(&mut window, start + Duration::from_secs(60), 2));
+        // 0 = unlimited
+        assert!(admit(&mut VecDeque::from(vec![start; 500]), start, 0));
+    }
+
+    #[test]
+    fn tokens_are_random() {
+        let a = generate_token().unwrap();
+        let b = generate_token().unwrap();
+        assert_eq!(a.len(), "tvbot_".len() + 64);
+        assert!(a.starts_with("tvbot_") && a != b);
+    }
+
+    #[test]
+    fn project_scope_is_explicit() {
+        let bot = BotAccess {
+            bot_id: "b1".into(),
+            bot_name: "Bot".into(),
+            enabled: true,
+            allowed_project_ids: vec!["p1".into()],
+            rate_limit_per_minute: 30,
+            token_hash: None,
+        };
+        assert!(bot.allows_project("p1"));
+        assert!(!bot.allows_project("p2"));
+        assert!(!BotAccess { allowed_project_ids: Vec::new(), ..bot }.allows_project("p1"));
+    }
+}
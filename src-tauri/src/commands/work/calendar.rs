@@ -0,0 +1,192 @@
+// Work Module - Working-days Calendar
+// Weekends and public holidays per country, so due dates computed by the app
+// (recurring tasks, SLA timers, schedules) skip days nobody is working.
+// Countries without a work_calendars row get a Saturday/Sunday weekend.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashSet;
+
+/// The team works on Singapore time and holidays
+pub const DEFAULT_COUNTRY: &str = "SG";
+const DEFAULT_WEEKEND: [u32; 2] = [6, 7];
+/// Guards against a calendar with every weekday marked off
+const MAX_SCAN_DAYS: i64 = 3660;
+
+/// Loaded calendar for date arithmetic
+#[derive(Debug, Clone)]
+pub struct BusinessCalendar {
+    weekend_days: Vec<u32>,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl BusinessCalendar {
+    pub fn new(weekend_days: Vec<u32>, holidays: HashSet<NaiveDate>) -> Self {
+        Self { weekend_days, holidays }
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        !self.weekend_days.contains(&date.weekday().number_from_monday()) && !self.holidays.contains(&date)
+    }
+
+    /// `date` itself if it's a working day, otherwise the next one
+    pub fn next_working_day(&self, date: NaiveDate) -> CmdResult<NaiveDate> {
+        let mut d = date;
+        for _ in 0..MAX_SCAN_DAYS {
+            if self.is_working_day(d) {
+                return Ok(d);
+            }
+            d += Duration::days(1);
+        }
+        Err(CommandError::Config("Calendar has no working days".into()))
+    }
+
+    /// Move `n` working days from `date` (backwards when negative). Zero rolls
+    /// a non-working `date` forward to the next working day.
+    pub fn add_business_days(&self, date: NaiveDate, n: i64) -> CmdResult<NaiveDate> {
+        if n == 0 {
+            return self.next_working_day(date);
+        }
+        let step = Duration::days(n.signum());
+        let mut d = date;
+        let mut remaining = n.abs();
+        let mut scanned = 0;
+        while remaining > 0 {
+            d += step;
+            scanned += 1;
+            if scanned > MAX_SCAN_DAYS + n.abs() {
+                return Err(CommandError::Config("Calendar has no working days".into()));
+            }
+            if self.is_working_day(d) {
+                remaining -= 1;
+            }
+        }
+        Ok(d)
+    }
+}
+
+pub(super) fn parse_date(date: &str) -> CmdResult<NaiveDate> {
+    NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d")
+        .map_err(|_| CommandError::Config(format!("Invalid date (expected YYYY-MM-DD): {}", date)))
+}
+
+pub(super) fn country_code(country: Option<String>) -> String {
+    country
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| DEFAULT_COUNTRY.to_string())
+}
+
+/// Load a country's weekend and holidays (all years — the table is small)
+pub async fn load_calendar(client: &SupabaseClient, country: &str) -> CmdResult<BusinessCalendar> {
+    let calendar: Option<WorkCalendar> = client
+        .select_single("work_calendars", &format!("country=eq.{}", urlencoding::encode(country)))
+        .await?;
+    let holidays: Vec<WorkHoliday> = client
+        .select("work_holidays", &format!("country=eq.{}", urlencoding::encode(country)))
+        .await?;
+    Ok(BusinessCalendar::new(
+        calendar.map(|c| c.weekend_days).unwrap_or_else(|| DEFAULT_WEEKEND.to_vec()),
+        holidays.iter().filter_map(|h| parse_date(&h.date).ok()).collect(),
+    ))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn work_list_calendars() -> CmdResult<Vec<WorkCalendar>> {
+    let client = get_client().await?;
+    client.select("work_calendars", "order=country.asc").await
+}
+
+/// Create or update a country's weekend days (ISO weekdays, 1 = Monday)
+#[tauri::command]
+pub async fn work_save_calendar(country: String, weekend_days: Vec<u32>) -> CmdResult<WorkCalendar> {
+    let country = country_code(Some(country));
+    if let Some(bad) = weekend_days.iter().find(|d| !(1..=7).contains(*d)) {
+        return Err(CommandError::Config(format!("Invalid weekday {} (use 1 = Monday … 7 = Sunday)", bad)));
+    }
+    if weekend_days.len() >= 7 {
+        return Err(CommandError::Config("A calendar needs at least one working day".into()));
+    }
+    let client = get_client().await?;
+    let row = serde_json::json!({
+        "country": country,
+        "weekend_days": weekend_days,
+        "updated_at": chrono::Utc::now().to_rfc3339(),
+    });
+    client.upsert_on("work_calendars", &row, Some("country")).await
+}
+
+/// Holidays for a country, optionally limited to one year
+#[tauri::command]
+pub async fn work_list_holidays(country: Option<String>, year: Option<i32>) -> CmdResult<Vec<WorkHoliday>> {
+    let country = country_code(country);
+    let mut query = format!("country=eq.{}&order=date.asc", urlencoding::encode(&country));
+    if let Some(year) = year {
+        query.push_str(&format!("&date=gte.{}-01-01&date=lte.{}-12-31", year, year));
+    }
+    let client = get_client().await?;
+    client.select("work_holidays", &query).await
+}
+
+/// Add (or rename) a holiday
+#[tauri::command]
+pub async fn work_add_holiday(country: String, date: String, name: String) -> CmdResult<WorkHoliday> {
+    let country = country_code(Some(country));
+    let date = parse_date(&date)?;
+    if name.trim().is_empty() {
+        return Err(CommandError::Config("Holiday name is required".into()));
+    }
+    let client = get_client().await?;
+    let row = serde_json::json!({
+        "country": country,
+        "date": date.format("%Y-%m-%d").to_string(),
+        "name": name.trim(),
+    });
+    client.upsert_on("work_holidays", &row, Some("country,date")).await
+}
+
+#[tauri::command]
+pub async fn work_delete_holiday(holiday_id: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    client.delete("work_holidays", &format!("id=eq.{}", holiday_id)).await
+}
+
+/// Date `n` working days after `date` (before, when negative) in a country's
+/// calendar. Returns YYYY-MM-DD.
+#[tauri::command]
+pub async fn work_add_business_days(date: String, n: i64, country: Option<String>) -> CmdResult<String> {
+    let start = parse_date(&date)?;
+    let client = get_client().await?;
+    let calendar = load_calendar(&client, &country_code(country)).await?;
+    Ok(calendar.add_business_days(start, n)?.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_weekends_and_holidays() {
+        let d = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        // CNY 2026 falls on Tue 17 / Wed 18 Feb
+        let cal = BusinessCalendar::new(vec![6, 7], [d("2026-02-17"), d("2026-02-18")].into_iter().collect());
+
+        assert_eq!(cal.add_business_days(d("2026-02-13"), 1).unwrap(), d("2026-02-16")); // Fri → Mon
+        assert_eq!(cal.add_business_days(d("2026-02-16"), 1).unwrap(), d("2026-02-19")); // over CNY
+        assert_eq!(cal.add_business_days(d("2026-02-19"), -1).unwrap(), d("2026-02-16"));
+        assert_eq!(cal.add_business_days(d("2026-02-15"), 0).unwrap(), d("2026-02-16")); // Sun rolls forward
+
+        let closed = BusinessCalendar::new((1..=7).collect(), HashSet::new());
+        assert!(closed.add_business_days(d("2026-02-16"), 1).is_err());
+    }
+}
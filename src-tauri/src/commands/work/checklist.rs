@@ -0,0 +1,439 @@
+// Work Module - Checklist import
+// Turns a markdown checklist into tasks, nested items becoming subtasks:
+//   - [ ] Launch prep @melvin fri
+//     - [x] Draft announcement
+// Item text goes through the quick-add parser, so due dates, p1..p4,
+// #labels and @assignees work inline. Each imported line gets a
+// `<!-- tv:<task id> -->` marker; importing the same file again re-syncs it:
+// new lines become tasks and checkbox state flows both ways (ticked in the
+// file completes the task, completed in Work ticks the line). Only completion
+// is synced after the first import; titles are left alone.
+
+use super::quick_add::{parse_quick_add, resolve_label, resolve_user, QuickAddParse};
+use super::tasks::{default_status_id, work_add_task_labels, work_create_task, work_update_task};
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// `- [ ] text`, `* [x] text`, `1. [ ] text`
+static ITEM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\s*)(?:[-*+]|\d+[.)])\s+\[([ xX])\]\s+(.*)$").unwrap());
+static MARKER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*<!--\s*tv:([0-9a-fA-F-]+)\s*-->\s*$").unwrap());
+
+/// Tabs count as this many spaces of indent
+const TAB_WIDTH: usize = 4;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One checkbox line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// 0-based line in the markdown
+    pub line: usize,
+    pub depth: usize,
+    /// Index of the enclosing item in the parsed list
+    pub parent: Option<usize>,
+    pub checked: bool,
+    /// Item text without the marker
+    pub text: String,
+    /// From the `<!-- tv:… -->` marker, once imported
+    pub task_id: Option<String>,
+}
+
+/// What an import / re-sync did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChecklistSyncResult {
+    pub path: Option<String>,
+    /// The checklist with markers and updated checkboxes (written back when imported from a file)
+    pub markdown: String,
+    pub created: Vec<Task>,
+    /// Tasks completed / reopened because their box changed in the file
+    pub completed: Vec<String>,
+    pub reopened: Vec<String>,
+    /// Lines whose box changed because the task changed in Work
+    pub checked_lines: Vec<usize>,
+    pub unchecked_lines: Vec<usize>,
+    /// Markers pointing at tasks that no longer exist
+    pub missing: Vec<String>,
+    pub unresolved_mentions: Vec<String>,
+    pub unresolved_labels: Vec<String>,
+}
+
+/// Which way a checkbox change flows
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reconcile {
+    InSync,
+    /// File changed: set the task's completion to this
+    ToWork(bool),
+    /// Task changed: set the checkbox to this
+    ToFile(bool),
+}
+
+/// Per-file sync state: the project it feeds and each task's completion at the last sync
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChecklistLink {
+    project_id: String,
+    milestone_id: Option<String>,
+    items: BTreeMap<String, bool>,
+    synced_at: Option<String>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+fn indent_width(indent: &str) -> usize {
+    indent.chars().map(|c| if c == '\t' { TAB_WIDTH } else { 1 }).sum()
+}
+
+/// Checkbox lines with their nesting. Other lines (headings, prose, plain
+/// bullets) are ignored; an item's parent is the nearest item above it with
+/// less indentation.
+pub fn parse_checklist(markdown: &str) -> Vec<ChecklistItem> {
+    let mut items: Vec<ChecklistItem> = Vec::new();
+    // (indent, item index) of the open ancestors
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+
+    for (line, raw) in markdown.lines().enumerate() {
+        let Some(caps) = ITEM_RE.captures(raw.trim_end_matches('\r')) else {
+            continue;
+        };
+        let indent = indent_width(&caps[1]);
+        while stack.last().is_some_and(|(i, _)| *i >= indent) {
+            stack.pop();
+        }
+        let rest = &caps[3];
+        let (text, task_id) = match MARKER_RE.captures(rest) {
+            Some(m) => (rest[..m.get(0).map_or(rest.len(), |g| g.start())].to_string(), Some(m[1].to_lowercase())),
+            None => (rest.trim_end().to_string(), None),
+        };
+
+        items.push(ChecklistItem {
+            line,
+            depth: stack.len(),
+            parent: stack.last().map(|(_, idx)| *idx),
+            checked: &caps[2] != " ",
+            text,
+            task_id,
+        });
+        stack.push((indent, items.len() - 1));
+    }
+    items
+}
+
+/// Rewrite one item line with the given checkbox state and marker
+fn set_item_line(markdown: &str, line: usize, checked: bool, task_id: &str) -> String {
+    let mut lines: Vec<String> = markdown.split('\n').map(String::from).collect();
+    if let Some(raw) = lines.get_mut(line) {
+        let cr = if raw.ends_with('\r') { "\r" } else { "" };
+        let body = raw.trim_end_matches('\r');
+        if let Some(caps) = ITEM_RE.captures(body) {
+            let start = caps.get(2).map_or(0, |g| g.start());
+            let rest = &caps[3];
+            let text = match MARKER_RE.find(rest) {
+                Some(m) => &rest[..m.start()],
+                None => rest.trim_end(),
+            };
+            *raw = format!(
+                "{}{}] {} <!-- tv:{} -->{}",
+                &body[..start],
+                if checked { 'x' } else { ' ' },
+                text,
+                task_id,
+                cr
+            );
+        }
+    }
+    lines.join("\n")
+}
+
+/// Decide a checkbox against its task. `last` is the state both agreed on at the
+/// previous sync; whichever side moved away from it wins. Without a previous
+/// sync the file wins.
+fn reconcile(file: bool, work: bool, last: Option<bool>) -> Reconcile {
+    if file == work {
+        return Reconcile::InSync;
+    }
+    match last {
+        Some(last) if file == last => Reconcile::ToFile(work),
+        _ => Reconcile::ToWork(file),
+    }
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn links_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".tv-client")
+        .join("checklist_sync.json")
+}
+
+fn load_links() -> HashMap<String, ChecklistLink> {
+    std::fs::read_to_string(links_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_links(links: &HashMap<String, ChecklistLink>) -> CmdResult<()> {
+    let path = links_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(links)?)?;
+    Ok(())
+}
+
+fn link_key(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+// ============================================================================
+// Sync
+// ============================================================================
+
+async fn sync_checklist(
+    path: Option<String>,
+    markdown: String,
+    link: &mut ChecklistLink,
+    locale: Option<&str>,
+) -> CmdResult<ChecklistSyncResult> {
+    let items = parse_checklist(&markdown);
+    if items.is_empty() {
+        return Err(CommandError::Validation {
+            message: "No checklist items found (expected lines like \"- [ ] task\")".to_string(),
+            fields: vec![if path.is_some() { "path" } else { "text" }.to_string()],
+        });
+    }
+
+    let client = get_client().await?;
+    let statuses: Vec<TaskStatus> = client.select("task_statuses", "order=sort_order.asc").await?;
+    let completed_status = statuses
+        .iter()
+        .find(|s| s.status_type == "completed")
+        .map(|s| s.id.clone())
+        .ok_or_else(|| CommandError::Config("No completed task status configured".into()))?;
+    let open_status = default_status_id(&client).await?;
+
+    let known_ids: Vec<&str> = items.iter().filter_map(|i| i.task_id.as_deref()).collect();
+    let existing: Vec<Task> = if known_ids.is_empty() {
+        Vec::new()
+    } else {
+        client
+            .select(
+                "tasks",
+                &format!("select=*,status:task_statuses(*)&id=in.({})", known_ids.join(",")),
+            )
+            .await?
+    };
+    let is_done = |t: &Task| t.status.as_ref().is_some_and(|s| s.status_type == "completed");
+
+    let today = chrono::Local::now().date_naive();
+    let parsed: Vec<_> = items.iter().map(|i| parse_quick_add(&i.text, today, locale)).collect();
+    let needs_lookup = |f: fn(&QuickAddParse) -> bool| {
+        items.iter().zip(&parsed).any(|(i, p)| i.task_id.is_none() && f(p))
+    };
+    let all_users: Vec<User> = if needs_lookup(|p| !p.mentions.is_empty()) {
+        client.select("users", "order=name.asc").await?
+    } else {
+        Vec::new()
+    };
+    let all_labels: Vec<Label> = if needs_lookup(|p| !p.labels.is_empty()) {
+        client.select("labels", "order=name.asc").await?
+    } else {
+        Vec::new()
+    };
+
+    let mut result = ChecklistSyncResult { path: path.clone(), ..Default::default() };
+    let mut markdown = markdown;
+    // Task id per item, so children can find their parent's task
+    let mut task_ids: Vec<Option<String>> = vec![None; items.len()];
+    let mut synced: BTreeMap<String, bool> = BTreeMap::new();
+
+    for (idx, (item, parse)) in items.iter().zip(&parsed).enumerate() {
+        if let Some(task_id) = &item.task_id {
+            let Some(task) = existing.iter().find(|t| &t.id == task_id) else {
+                result.missing.push(task_id.clone());
+                continue;
+            };
+            task_ids[idx] = Some(task.id.clone());
+            let work_done = is_done(task);
+            let done = match reconcile(item.checked, work_done, link.items.get(&task.id).copied()) {
+                Reconcile::InSync => work_done,
+                Reconcile::ToWork(done) => {
+                    let status_id = if done { completed_status.clone() } else { open_status.clone() };
+                    work_update_task(task.id.clone(), UpdateTask { status_id: Some(status_id), ..Default::default() }).await?;
+                    let list = if done { &mut result.completed } else { &mut result.reopened };
+                    list.push(task.id.clone());
+                    done
+                }
+                Reconcile::ToFile(done) => {
+                    markdown = set_item_line(&markdown, item.line, done, &task.id);
+                    let list = if done { &mut result.checked_lines } else { &mut result.unchecked_lines };
+                    list.push(item.line);
+                    done
+                }
+            };
+            synced.insert(task.id.clone(), done);
+            continue;
+        }
+
+        if parse.title.trim().is_empty() {
+            continue;
+        }
+        let mut assignee_ids: Vec<String> = Vec::new();
+        for mention in &parse.mentions {
+            match resolve_user(mention, &all_users) {
+                Some(user) if !assignee_ids.contains(&user.id) => assignee_ids.push(user.id.clone()),
+                Some(_) => {}
+                None => result.unresolved_mentions.push(mention.clone()),
+            }
+        }
+        let mut label_ids: Vec<String> = Vec::new();
+        for name in &parse.labels {
+            match resolve_label(name, &all_labels) {
+                Some(label) if !label_ids.contains(&label.id) => label_ids.push(label.id.clone()),
+                Some(_) => {}
+                None => result.unresolved_labels.push(name.clone()),
+            }
+        }
+
+        let task = work_create_task(CreateTask {
+            project_id: link.project_id.clone(),
+            status_id: if item.checked { completed_status.clone() } else { open_status.clone() },
+            title: parse.title.clone(),
+            description: None,
+            priority: parse.priority,
+            due_date: parse.due_date.clone(),
+            assignee_ids: Some(assignee_ids),
+            milestone_id: link.milestone_id.clone(),
+            depends_on: None,
+            session_ref: None,
+            requires_review: None,
+            company_id: None,
+            contact_id: None,
+            task_type: None,
+        })
+        .await?;
+        if !label_ids.is_empty() {
+            work_add_task_labels(task.id.clone(), label_ids).await?;
+        }
+
+        // Nesting, file order and origin aren't part of CreateTask
+        let parent_task_id = item.parent.and_then(|p| task_ids[p].clone());
+        let mut extra = serde_json::json!({
+            "parent_task_id": parent_task_id,
+            "sort_order": item.line as i32,
+            "linked_document_path": path,
+        });
+        if item.checked {
+            extra["completed_at"] = serde_json::Value::String(chrono::Utc::now().to_rfc3339());
+        }
+        let task: Task = client.update("tasks", &format!("id=eq.{}", task.id), &extra).await?;
+
+        markdown = set_item_line(&markdown, item.line, item.checked, &task.id);
+        task_ids[idx] = Some(task.id.clone());
+        synced.insert(task.id.clone(), item.checked);
+        result.created.push(task);
+    }
+
+    link.items = synced;
+    link.synced_at = Some(chrono::Utc::now().to_rfc3339());
+    result.markdown = markdown;
+    Ok(result)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Import a markdown checklist (`path` to a file, or `text`) into tasks under
+/// `project_id` / `milestone_id`, nesting becoming subtasks. A file gets
+/// task markers written back and is remembered, so importing it again (or
+/// work_sync_checklist) re-syncs completion both ways and picks up new lines.
+#[tauri::command]
+pub async fn work_import_checklist(
+    path: Option<String>,
+    text: Option<String>,
+    project_id: String,
+    milestone_id: Option<String>,
+    locale: Option<String>,
+) -> CmdResult<ChecklistSyncResult> {
+    let markdown = match (&path, text) {
+        (Some(p), None) => std::fs::read_to_string(p).map_err(|e| CommandError::Io(format!("Failed to read {}: {}", p, e)))?,
+        (None, Some(text)) => text,
+        _ => {
+            return Err(CommandError::Validation {
+                message: "Pass either a checklist file path or text".to_string(),
+                fields: vec!["path".to_string(), "text".to_string()],
+            })
+        }
+    };
+
+    let mut links = load_links();
+    let key = path.as_deref().map(link_key);
+    let mut link = key
+        .as_ref()
+        .and_then(|k| links.get(k).cloned())
+        .filter(|l| l.project_id == project_id)
+        .unwrap_or_default();
+    link.project_id = project_id;
+    link.milestone_id = milestone_id;
+
+    let result = sync_checklist(path.clone(), markdown, &mut link, locale.as_deref()).await?;
+    if let (Some(path), Some(key)) = (&path, key) {
+        std::fs::write(path, &result.markdown).map_err(|e| CommandError::Io(format!("Failed to write {}: {}", path, e)))?;
+        links.insert(key, link);
+        save_links(&links)?;
+    }
+    Ok(result)
+}
+
+/// Re-sync a previously imported checklist file with its project
+#[tauri::command]
+pub async fn work_sync_checklist(path: String, locale: Option<String>) -> CmdResult<ChecklistSyncResult> {
+    let key = link_key(&path);
+    let link = load_links()
+        .remove(&key)
+        .ok_or_else(|| CommandError::NotFound(format!("{} hasn't been imported as a checklist", path)))?;
+    work_import_checklist(Some(path), None, link.project_id, link.milestone_id, locale).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nesting_and_reconciles() {
+        let md = "# Launch\n- [ ] Prep @melvin fri\n  - [x] Draft post <!-- tv:ABC-1 -->\n\t- [ ] Review\nnotes\n- [X] Ship\r\n";
+        let items = parse_checklist(md);
+        let shape: Vec<_> = items.iter().map(|i| (i.line, i.depth, i.parent, i.checked)).collect();
+        assert_eq!(shape, vec![(1, 0, None, false), (2, 1, Some(0), true), (3, 2, Some(1), false), (5, 0, None, true)]);
+        assert_eq!((items[1].text.as_str(), items[1].task_id.as_deref()), ("Draft post", Some("abc-1")));
+        assert_eq!(items[3].text, "Ship");
+
+        let updated = set_item_line(md, 2, false, "abc-1");
+        assert!(updated.contains("\n  - [ ] Draft post <!-- tv:abc-1 -->\n"));
+        let updated = set_item_line(&updated, 5, true, "t9");
+        assert!(updated.ends_with("- [x] Ship <!-- tv:t9 -->\r\n"));
+        assert_eq!(parse_checklist(&updated).len(), 4);
+
+        assert_eq!(reconcile(true, true, Some(false)), Reconcile::InSync);
+        assert_eq!(reconcile(true, false, Some(false)), Reconcile::ToWork(true));
+        assert_eq!(reconcile(false, true, Some(false)), Reconcile::ToFile(true));
+        assert_eq!(reconcile(false, true, Some(true)), Reconcile::ToWork(false));
+        assert_eq!(reconcile(true, false, None), Reconcile::ToWork(true));
+    }
+}
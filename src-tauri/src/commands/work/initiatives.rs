@@ -2,7 +2,16 @@
 
 use super::types::*;
 use crate::commands::error::{CmdResult, CommandError};
-use crate::commands::supabase::get_client;
+use crate::commands::supabase::{get_client, SupabaseClient};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Summaries older than this are recomputed on read
+const SUMMARY_TTL_SECS: i64 = 15 * 60;
+
+/// Rollup cache — filled on demand and by the background recompute loop
+static SUMMARY_CACHE: std::sync::LazyLock<Mutex<HashMap<String, InitiativeSummary>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// List all initiatives
 #[tauri::command]
@@ -133,6 +142,233 @@ pub async fn work_list_initiative_projects(initiative_id: String) -> CmdResult<V
     Ok(rows.into_iter().map(|r| r.project).collect())
 }
 
+// ============================================================================
+// Rollups
+// ============================================================================
+
+fn health_rank(health: Option<&str>) -> u8 {
+    match health {
+        Some("off_track") => 2,
+        Some("at_risk") => 1,
+        _ => 0,
+    }
+}
+
+fn excerpt(content: &str, max_chars: usize) -> String {
+    let flat = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max_chars {
+        flat
+    } else {
+        format!("{}…", flat.chars().take(max_chars).collect::<String>())
+    }
+}
+
+/// Compute an initiative's rollup from its child projects, their tasks, and updates
+pub async fn compute_initiative_summary(
+    client: &SupabaseClient,
+    initiative_id: &str,
+) -> CmdResult<InitiativeSummary> {
+    let initiative: Initiative = client
+        .select_single(
+            "initiatives",
+            &format!("id=eq.{}", initiative_id),
+        )
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Initiative not found: {}", initiative_id)))?;
+
+    #[derive(serde::Deserialize)]
+    struct JunctionRow {
+        project: Project,
+    }
+    let rows: Vec<JunctionRow> = client
+        .select(
+            "initiative_projects",
+            &format!("select=project:projects(*)&initiative_id=eq.{}", initiative_id),
+        )
+        .await?;
+    let projects: Vec<Project> = rows
+        .into_iter()
+        .map(|r| r.project)
+        .filter(|p| p.archived_at.is_none())
+        .collect();
+
+    let mut summary = InitiativeSummary {
+        initiative_id: initiative.id.clone(),
+        name: initiative.name.clone(),
+        project_count: projects.len(),
+        total_tasks: 0,
+        completed_tasks: 0,
+        percent_complete: 0.0,
+        derived_status: initiative.status.clone(),
+        derived_health: initiative.health.clone(),
+        at_risk: false,
+        at_risk_reasons: Vec::new(),
+        latest_update: None,
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if projects.is_empty() {
+        return Ok(summary);
+    }
+
+    let project_ids = projects.iter().map(|p| p.id.as_str()).collect::<Vec<_>>().join(",");
+
+    // Task counts — canceled tasks don't count toward the total
+    #[derive(serde::Deserialize)]
+    struct TaskStatusRow {
+        status: Option<StatusTypeRow>,
+    }
+    #[derive(serde::Deserialize)]
+    struct StatusTypeRow {
+        #[serde(rename = "type")]
+        status_type: String,
+    }
+    let tasks: Vec<TaskStatusRow> = client
+        .select_all(
+            "tasks",
+            &format!("select=status:task_statuses(type)&project_id=in.({})&order=id.asc", project_ids),
+        )
+        .await?;
+    for task in &tasks {
+        match task.status.as_ref().map(|s| s.status_type.as_str()) {
+            Some("canceled") => {}
+            Some("completed") => {
+                summary.total_tasks += 1;
+                summary.completed_tasks += 1;
+            }
+            _ => summary.total_tasks += 1,
+        }
+    }
+    if summary.total_tasks > 0 {
+        summary.percent_complete =
+            (summary.completed_tasks as f64 / summary.total_tasks as f64 * 1000.0).round() / 10.0;
+    }
+
+    // Latest project update across all child projects
+    let updates: Vec<ProjectUpdate> = client
+        .select(
+            "project_updates",
+            &format!("project_id=in.({})&order=created_at.desc&limit=1", project_ids),
+        )
+        .await
+        .unwrap_or_default();
+    if let Some(update) = updates.into_iter().next() {
+        let project_name = projects
+            .iter()
+            .find(|p| p.id == update.project_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+        summary.latest_update = Some(InitiativeUpdateExcerpt {
+            project_id: update.project_id,
+            project_name,
+            health: update.health,
+            excerpt: excerpt(&update.content, 280),
+            created_at: update.created_at,
+        });
+    }
+
+    // Status inheritance: all completed → completed, any active → active
+    let statuses: Vec<&str> = projects.iter().filter_map(|p| p.status.as_deref()).collect();
+    if !statuses.is_empty() && statuses.len() == projects.len() && statuses.iter().all(|s| *s == "completed") {
+        summary.derived_status = Some("completed".to_string());
+    } else if statuses.contains(&"active") {
+        summary.derived_status = Some("active".to_string());
+    }
+
+    // Health inheritance: worst child health wins over the initiative's own
+    let worst = projects
+        .iter()
+        .filter(|p| p.status.as_deref() != Some("completed"))
+        .max_by_key(|p| health_rank(p.health.as_deref()));
+    if let Some(p) = worst {
+        if health_rank(p.health.as_deref()) > health_rank(summary.derived_health.as_deref()) {
+            summary.derived_health = p.health.clone();
+        }
+    }
+
+    // At-risk: unhealthy child projects or past-due target dates with work outstanding
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    for p in &projects {
+        if p.status.as_deref() == Some("completed") {
+            continue;
+        }
+        if health_rank(p.health.as_deref()) > 0 {
+            summary.at_risk_reasons.push(format!(
+                "{} is {}",
+                p.name,
+                p.health.as_deref().unwrap_or_default().replace('_', " ")
+            ));
+        }
+        if let Some(target) = p.target_date.as_deref() {
+            if target.get(..10).is_some_and(|d| d < today.as_str()) {
+                summary.at_risk_reasons.push(format!("{} is past its target date ({})", p.name, &target[..10]));
+            }
+        }
+    }
+    if let Some(target) = initiative.target_date.as_deref() {
+        if target.get(..10).is_some_and(|d| d < today.as_str()) && summary.percent_complete < 100.0 {
+            summary
+                .at_risk_reasons
+                .push(format!("Initiative is past its target date ({})", &target[..10]));
+        }
+    }
+    summary.at_risk = !summary.at_risk_reasons.is_empty();
+
+    Ok(summary)
+}
+
+/// Recompute and cache rollups for every active initiative. Used by the background loop.
+pub async fn refresh_initiative_summaries() -> CmdResult<Vec<InitiativeSummary>> {
+    let client = get_client().await?;
+    let initiatives: Vec<Initiative> = client
+        .select("initiatives", "select=id,name&archived_at=is.null")
+        .await?;
+
+    let mut summaries = Vec::new();
+    for initiative in initiatives {
+        match compute_initiative_summary(&client, &initiative.id).await {
+            Ok(summary) => summaries.push(summary),
+            Err(e) => eprintln!("[work:rollups] Failed for {}: {}", initiative.id, e),
+        }
+    }
+
+    if let Ok(mut cache) = SUMMARY_CACHE.lock() {
+        cache.clear();
+        for summary in &summaries {
+            cache.insert(summary.initiative_id.clone(), summary.clone());
+        }
+    }
+    Ok(summaries)
+}
+
+fn cached_summary(initiative_id: &str) -> Option<InitiativeSummary> {
+    let cache = SUMMARY_CACHE.lock().ok()?;
+    let summary = cache.get(initiative_id)?;
+    let computed = chrono::DateTime::parse_from_rfc3339(&summary.computed_at).ok()?;
+    let age = chrono::Utc::now().signed_duration_since(computed).num_seconds();
+    (age < SUMMARY_TTL_SECS).then(|| summary.clone())
+}
+
+/// Get progress rollup for an initiative (served from cache unless stale or `refresh`)
+#[tauri::command]
+pub async fn work_get_initiative_summary(
+    initiative_id: String,
+    refresh: Option<bool>,
+) -> CmdResult<InitiativeSummary> {
+    if !refresh.unwrap_or(false) {
+        if let Some(summary) = cached_summary(&initiative_id) {
+            return Ok(summary);
+        }
+    }
+
+    let client = get_client().await?;
+    let summary = compute_initiative_summary(&client, &initiative_id).await?;
+    if let Ok(mut cache) = SUMMARY_CACHE.lock() {
+        cache.insert(initiative_id, summary.clone());
+    }
+    Ok(summary)
+}
+
 // Helper function to create URL-friendly slug
 fn slugify(name: &str) -> String {
     name.to_lowercase()
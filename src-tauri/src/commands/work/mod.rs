@@ -6,8 +6,19 @@ pub mod projects;
 pub mod tasks;
 pub mod milestones;
 pub mod initiatives;
+pub mod background;
 pub mod labels;
+pub mod wip;
+pub mod calendar;
+pub mod availability;
+pub mod transitions;
+pub mod notifier;
+pub mod ai_assist;
 pub mod users;
+pub mod bot_api;
+pub mod status_report;
+pub mod quick_add;
+pub mod checklist;
 #[allow(dead_code)]
 pub mod sessions;
 #[allow(dead_code)]
@@ -20,7 +31,17 @@ pub use tasks::*;
 pub use milestones::*;
 pub use initiatives::*;
 pub use labels::*;
+pub use wip::*;
+pub use calendar::*;
+pub use availability::*;
+pub use transitions::*;
+pub use notifier::*;
+pub use ai_assist::*;
 pub use users::*;
+pub use bot_api::*;
+pub use status_report::*;
+pub use quick_add::*;
+pub use checklist::*;
 #[allow(unused_imports)]
 pub use sessions::*;
 #[allow(unused_imports)]
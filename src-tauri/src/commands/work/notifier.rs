@@ -0,0 +1,410 @@
+// Work Module - Notifier and per-project notification preferences
+// Users pick a level per project: "all" (task activity + mentions), "mentions"
+// (the default when no row exists) or "mute" (nothing, mentions included).
+// Every path that inserts notifications about a project's tasks checks
+// `muted_recipients` first. Opting into weekly_digest sends a weekly project
+// summary through Outlook.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::outlook::types::EmailAddress;
+use crate::commands::supabase::{get_client, SupabaseClient};
+use crate::commands::tools::docgen_theme::escape_html;
+use std::collections::HashSet;
+use tauri::Emitter;
+
+const LEVELS: [&str; 3] = ["all", "mentions", "mute"];
+const DIGEST_INTERVAL_DAYS: i64 = 7;
+/// Actor recorded on activity notifications
+pub(super) const NOTIFIER_ACTOR: &str = "Work";
+
+async fn load_users(client: &SupabaseClient, ids: &[&str]) -> CmdResult<Vec<User>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    client
+        .select("users", &format!("id=in.({})", ids.join(",")))
+        .await
+}
+
+/// Names (the notifications `recipient`) of users who muted `project_id`
+pub(super) async fn muted_recipients(client: &SupabaseClient, project_id: &str) -> CmdResult<HashSet<String>> {
+    let prefs: Vec<ProjectNotificationPref> = client
+        .select(
+            "project_notification_prefs",
+            &format!("project_id=eq.{}&level=eq.mute", project_id),
+        )
+        .await?;
+    let ids: Vec<&str> = prefs.iter().map(|p| p.user_id.as_str()).collect();
+    Ok(load_users(client, &ids).await?.into_iter().map(|u| u.name).collect())
+}
+
+/// Describe what a task update changed, for the notification preview
+fn describe_update(task: &Task, data: &UpdateTask) -> Option<String> {
+    let mut changes = Vec::new();
+    if data.status_id.is_some() {
+        let status = task.status.as_ref().map(|s| s.name.as_str()).unwrap_or("a new status");
+        changes.push(format!("moved to {}", status));
+    }
+    if data.assignee_ids.is_some() {
+        let names: Vec<&str> = task
+            .assignees
+            .iter()
+            .flatten()
+            .filter_map(|a| a.user.as_ref().map(|u| u.name.as_str()))
+            .collect();
+        changes.push(if names.is_empty() {
+            "unassigned".to_string()
+        } else {
+            format!("assigned to {}", names.join(", "))
+        });
+    }
+    if let Some(due) = &data.due_date {
+        changes.push(format!("due {}", due.get(..10).unwrap_or(due)));
+    }
+    if changes.is_empty() {
+        return None;
+    }
+    Some(format!("{}{} — {}", task_key(task), task.title, changes.join(", ")))
+}
+
+/// "PROJ-12 " style prefix, empty when the task has no number
+pub(super) fn task_key(task: &Task) -> String {
+    match (task.project.as_ref().and_then(|p| p.identifier_prefix.as_deref()), task.task_number) {
+        (Some(prefix), Some(n)) => format!("{}-{} ", prefix, n),
+        _ => String::new(),
+    }
+}
+
+async fn insert_activity(client: &SupabaseClient, recipient: &str, task: &Task, preview: &str) -> CmdResult<()> {
+    let row = serde_json::json!({
+        "recipient": recipient,
+        "type": "activity",
+        "entity_type": "task",
+        "entity_id": task.id,
+        "actor": NOTIFIER_ACTOR,
+        "body_preview": preview,
+    });
+    let _: serde_json::Value = client.insert("notifications", &row).await?;
+    Ok(())
+}
+
+/// Fan out an activity notification for a task update to users following the
+/// project at level "all", except whoever made the change. Best-effort:
+/// failures are logged, never returned.
+pub(super) async fn notify_task_activity(client: &SupabaseClient, task: &Task, data: &UpdateTask) {
+    let Some(preview) = describe_update(task, data) else {
+        return;
+    };
+    let result: CmdResult<()> = async {
+        let prefs: Vec<ProjectNotificationPref> = client
+            .select(
+                "project_notification_prefs",
+                &format!("project_id=eq.{}&level=eq.all", task.project_id),
+            )
+            .await?;
+        let ids: Vec<&str> = prefs
+            .iter()
+            .map(|p| p.user_id.as_str())
+            .filter(|id| data.updated_by.as_deref() != Some(*id))
+            .collect();
+        for user in load_users(client, &ids).await? {
+            insert_activity(client, &user.name, task, &preview).await?;
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        eprintln!("[work:notifier] Failed to notify for task {}: {}", task.id, e);
+    }
+}
+
+/// Tell a dependent task's assignees that `blocker` completed and it's ready,
+/// skipping anyone who muted the project. Returns how many were notified;
+/// failures are logged.
+pub(super) async fn notify_unblocked(client: &SupabaseClient, task: &Task, blocker: &Task) -> usize {
+    let preview = format!(
+        "{}{} — unblocked: {}{} completed",
+        task_key(task),
+        task.title,
+        task_key(blocker),
+        blocker.title
+    );
+    let muted = match muted_recipients(client, &task.project_id).await {
+        Ok(muted) => muted,
+        Err(e) => {
+            eprintln!("[work:notifier] Failed to load mutes for task {}: {}", task.id, e);
+            return 0;
+        }
+    };
+    let mut notified = 0;
+    for user in task
+        .assignees
+        .iter()
+        .flatten()
+        .filter_map(|a| a.user.as_ref())
+        .filter(|u| !muted.contains(&u.name))
+    {
+        match insert_activity(client, &user.name, task, &preview).await {
+            Ok(()) => notified += 1,
+            Err(e) => eprintln!("[work:notifier] Failed to notify {} for task {}: {}", user.name, task.id, e),
+        }
+    }
+    notified
+}
+
+// ============================================================================
+// Weekly digest
+// ============================================================================
+
+fn task_line(t: &Task) -> String {
+    let key = match (t.project.as_ref().and_then(|p| p.identifier_prefix.as_deref()), t.task_number) {
+        (Some(prefix), Some(n)) => format!("<code>{}-{}</code> ", prefix, n),
+        _ => String::new(),
+    };
+    let due = t
+        .due_date
+        .as_deref()
+        .map(|d| format!(" <span style=\"color:#888\">(due {})</span>", d.get(..10).unwrap_or(d)))
+        .unwrap_or_default();
+    format!("<li>{}{}{}</li>", key, escape_html(&t.title), due)
+}
+
+fn section(title: &str, tasks: &[&Task]) -> String {
+    if tasks.is_empty() {
+        return String::new();
+    }
+    let items: String = tasks.iter().map(|t| task_line(t)).collect();
+    format!("<h3>{} ({})</h3><ul>{}</ul>", title, tasks.len(), items)
+}
+
+/// HTML body for one project's weekly summary
+fn build_project_digest(project: &Project, tasks: &[Task], since: &str, today: &str) -> String {
+    let is_done = |t: &Task| {
+        t.status
+            .as_ref()
+            .map(|s| s.status_type == "completed" || s.status_type == "canceled")
+            .unwrap_or(false)
+    };
+    let completed: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.completed_at.as_deref().is_some_and(|c| c >= since))
+        .collect();
+    let created: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| !is_done(*t) && t.created_at.as_deref().is_some_and(|c| c >= since))
+        .collect();
+    let overdue: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| !is_done(*t) && t.due_date.as_deref().is_some_and(|d| d.get(..10).unwrap_or(d) < today))
+        .collect();
+    let in_progress: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status.as_ref().map(|s| s.status_type == "started").unwrap_or(false))
+        .collect();
+
+    let mut body = format!(
+        "<h2>{}</h2><p>Week ending {}{}</p>",
+        escape_html(&project.name),
+        today,
+        project
+            .health
+            .as_deref()
+            .map(|h| format!(" · health: <b>{}</b>", escape_html(h)))
+            .unwrap_or_default()
+    );
+    let sections = [
+        section("Completed", &completed),
+        section("New", &created),
+        section("Overdue", &overdue),
+        section("In progress", &in_progress),
+    ]
+    .concat();
+    if sections.is_empty() {
+        body.push_str("<p>No activity this week.</p>");
+    } else {
+        body.push_str(&sections);
+    }
+    body
+}
+
+/// Send due weekly digests. With `force`, ignores the weekly interval.
+async fn send_digests(force: bool) -> CmdResult<ProjectDigestResult> {
+    let client = get_client().await?;
+    let prefs: Vec<ProjectNotificationPref> = client
+        .select(
+            "project_notification_prefs",
+            "weekly_digest=eq.true&level=neq.mute",
+        )
+        .await?;
+
+    let now = chrono::Utc::now();
+    let cutoff = now - chrono::Duration::days(DIGEST_INTERVAL_DAYS);
+    let due: Vec<&ProjectNotificationPref> = prefs
+        .iter()
+        .filter(|p| {
+            force
+                || p.last_digest_at
+                    .as_deref()
+                    .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                    .map(|d| d.with_timezone(&chrono::Utc) <= cutoff)
+                    .unwrap_or(true)
+        })
+        .collect();
+
+    let mut result = ProjectDigestResult {
+        sent: 0,
+        skipped: prefs.len() - due.len(),
+        errors: Vec::new(),
+    };
+    if due.is_empty() {
+        return Ok(result);
+    }
+
+    let user_ids: Vec<&str> = due.iter().map(|p| p.user_id.as_str()).collect();
+    let users = load_users(&client, &user_ids).await?;
+    let graph = crate::commands::outlook::graph::GraphClient::new();
+    let since = cutoff.to_rfc3339();
+    let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+
+    for pref in due {
+        let Some(user) = users.iter().find(|u| u.id == pref.user_id) else {
+            result.skipped += 1;
+            continue;
+        };
+        let Some(email) = user.email.as_deref().filter(|e| !e.is_empty()) else {
+            result.skipped += 1;
+            continue;
+        };
+
+        let sent: CmdResult<()> = async {
+            let project: Project = client
+                .select_single("projects", &format!("id=eq.{}", pref.project_id))
+                .await?
+                .ok_or_else(|| CommandError::NotFound(format!("Project {}", pref.project_id)))?;
+            let tasks: Vec<Task> = client
+                .select_all(
+                    "tasks",
+                    &format!(
+                        "select=*,project:projects(*),status:task_statuses(*)&project_id=eq.{}&order=updated_at.desc,id.asc",
+                        pref.project_id
+                    ),
+                )
+                .await?;
+            let html = build_project_digest(&project, &tasks, &since, &today);
+            let to = [EmailAddress {
+                name: user.name.clone(),
+                email: email.to_string(),
+            }];
+            graph
+                .send_email(&to, &[], &format!("Weekly digest: {}", project.name), &html)
+                .await?;
+            let _: serde_json::Value = client
+                .update(
+                    "project_notification_prefs",
+                    &format!("id=eq.{}", pref.id),
+                    &serde_json::json!({ "last_digest_at": now.to_rfc3339() }),
+                )
+                .await?;
+            Ok(())
+        }
+        .await;
+
+        match sent {
+            Ok(()) => result.sent += 1,
+            Err(e) => result.errors.push(format!("{} / {}: {}", user.name, pref.project_id, e)),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Check for due weekly digests every 6 hours. Call from main.rs setup hook.
+pub fn start_project_digest_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+        loop {
+            // Digests go out through the user's own mailbox — skip until Outlook is connected
+            if crate::commands::outlook::auth::load_tokens().is_some() {
+                let started_at = chrono::Utc::now().to_rfc3339();
+                match send_digests(false).await {
+                    Ok(r) if r.sent > 0 || !r.errors.is_empty() => {
+                        let status = if r.errors.is_empty() { "completed" } else { "failed" };
+                        let _ = app_handle.emit("jobs:update", serde_json::json!({
+                            "id": format!("work-digest-{}", chrono::Utc::now().timestamp_millis()),
+                            "name": "Weekly project digests",
+                            "status": status,
+                            "message": format!("{} sent, {} failed", r.sent, r.errors.len()),
+                            "startedAt": started_at,
+                        }));
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("[work:digest] Failed: {}", e),
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(6 * 3600)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Set a user's notification level and weekly digest opt-in for a project
+#[tauri::command]
+pub async fn work_set_project_notifications(
+    user_id: String,
+    project_id: String,
+    level: String,
+    weekly_digest: Option<bool>,
+) -> CmdResult<ProjectNotificationPref> {
+    if !LEVELS.contains(&level.as_str()) {
+        return Err(CommandError::Config(format!(
+            "Unknown notification level: {} (expected all, mentions or mute)",
+            level
+        )));
+    }
+    let client = get_client().await?;
+    let existing: Option<ProjectNotificationPref> = client
+        .select_single(
+            "project_notification_prefs",
+            &format!("user_id=eq.{}&project_id=eq.{}", user_id, project_id),
+        )
+        .await?;
+
+    let data = UpsertProjectNotificationPref {
+        user_id,
+        project_id,
+        level,
+        weekly_digest: weekly_digest
+            .or(existing.as_ref().map(|p| p.weekly_digest))
+            .unwrap_or(false),
+    };
+    match existing {
+        Some(pref) => {
+            client
+                .update("project_notification_prefs", &format!("id=eq.{}", pref.id), &data)
+                .await
+        }
+        None => client.insert("project_notification_prefs", &data).await,
+    }
+}
+
+/// List a user's per-project notification preferences (projects without a row use "mentions")
+#[tauri::command]
+pub async fn work_list_project_notifications(user_id: String) -> CmdResult<Vec<ProjectNotificationPref>> {
+    let client = get_client().await?;
+    client
+        .select(
+            "project_notification_prefs",
+            &format!("user_id=eq.{}", user_id),
+        )
+        .await
+}
+
+/// Send weekly project digests now. `force` ignores the weekly interval.
+#[tauri::command]
+pub async fn work_send_project_digests(force: Option<bool>) -> CmdResult<ProjectDigestResult> {
+    send_digests(force.unwrap_or(false)).await
+}
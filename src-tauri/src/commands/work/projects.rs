@@ -177,7 +177,27 @@ pub async fn work_update_project(project_id: String, data: UpdateProject) -> Cmd
         if let Some(new_stage) = &data.deal_stage {
             if let Some(old_stage) = &current.deal_stage {
                 if old_stage != new_stage {
+                    // Gated stages need their required fields filled (current values + this update)
+                    let mut merged = serde_json::to_value(&current)?;
+                    if let (Some(obj), Some(changes)) = (merged.as_object_mut(), update_data.as_object()) {
+                        obj.extend(changes.clone());
+                    }
+                    crate::commands::crm::validate_stage_requirements(&client, new_stage, &merged).await?;
+
+                    // Closing requires a reason from the won/lost taxonomy
+                    let closing = new_stage == "won" || new_stage == "lost";
+                    if closing {
+                        let reason = data
+                            .deal_close_reason
+                            .as_deref()
+                            .or(current.deal_close_reason.as_deref());
+                        crate::commands::crm::validate_close_reason(&client, new_stage, reason).await?;
+                    }
+
                     if let Some(obj) = update_data.as_object_mut() {
+                        if closing && old_stage != "won" && old_stage != "lost" {
+                            obj.insert("deal_stage_at_close".to_string(), serde_json::Value::String(old_stage.clone()));
+                        }
                         if !data.preserve_stage_date.unwrap_or(false) {
                             obj.insert("deal_stage_changed_at".to_string(), serde_json::Value::String(now.clone()));
                         }
@@ -345,7 +365,7 @@ pub async fn work_delete_project_update(update_id: String) -> CmdResult<()> {
 }
 
 // Helper function to create URL-friendly slug
-fn slugify(name: &str) -> String {
+pub fn slugify(name: &str) -> String {
     name.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
@@ -0,0 +1,426 @@
+// Work Module - Quick add
+// Deterministic parser for capture-box shorthand:
+//   "tomorrow p1 #bug @melvin fix login"
+// Due dates (today, fri, next week, in 3 days, 20 oct, 20/10), priority
+// (p1 urgent .. p4 low), #labels and @assignees are pulled out; the rest is
+// the title. Nothing is created unless `create` is set, so the capture box
+// can show the interpretation for confirmation first.
+
+use super::tasks::{default_status_id, work_add_task_labels, work_create_task, work_get_task};
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// Regions that write numeric dates month-first (10/20 = Oct 20)
+const MONTH_FIRST_REGIONS: [&str; 2] = ["us", "ph"];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// What the parser pulled out of the text, before names are resolved
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuickAddParse {
+    pub title: String,
+    /// YYYY-MM-DD
+    pub due_date: Option<String>,
+    /// The words that produced `due_date`, for highlighting in the capture box
+    pub due_text: Option<String>,
+    pub priority: Option<i32>,
+    pub labels: Vec<String>,
+    pub mentions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickAddResult {
+    pub parsed: QuickAddParse,
+    pub project_id: String,
+    pub status_id: String,
+    pub labels: Vec<Label>,
+    pub assignees: Vec<User>,
+    pub unresolved_labels: Vec<String>,
+    pub unresolved_mentions: Vec<String>,
+    /// Set when called with `create: true`
+    pub task: Option<Task>,
+}
+
+// ============================================================================
+// Date parsing
+// ============================================================================
+
+fn clean(word: &str) -> String {
+    word.trim_end_matches([',', '.', ';']).to_lowercase()
+}
+
+fn weekday_from(word: &str) -> Option<Weekday> {
+    if word.len() < 3 {
+        return None;
+    }
+    WEEKDAYS.iter().find(|(name, _)| name.starts_with(word)).map(|(_, d)| *d)
+}
+
+fn month_from(word: &str) -> Option<u32> {
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS.iter().position(|m| m.starts_with(word)).map(|i| i as u32 + 1)
+}
+
+/// "20", "20th", "1st"
+fn day_from(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if digits.len() != word.len() && !matches!(&word[digits.len()..], "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn year_from(word: &str) -> Option<i32> {
+    if word.len() != 4 {
+        return None;
+    }
+    word.parse().ok()
+}
+
+/// Next `weekday` strictly after `today`
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64 + 7) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { ahead })
+}
+
+fn add_months(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    date.checked_add_months(chrono::Months::new(months))
+}
+
+fn last_day_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    add_months(date.with_day(1)?, 1).map(|d| d - Duration::days(1))
+}
+
+/// A day/month without a year means the next time that date comes round
+fn upcoming(today: NaiveDate, month: u32, day: u32, year: Option<i32>) -> Option<NaiveDate> {
+    if let Some(y) = year {
+        return NaiveDate::from_ymd_opt(y, month, day);
+    }
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+/// "20/10", "20/10/2026" (or "10/20" month-first locales), "2026-10-20"
+fn numeric_date(word: &str, today: NaiveDate, month_first: bool) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let parts: Vec<&str> = word.split('/').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    let a: u32 = parts[0].parse().ok()?;
+    let b: u32 = parts[1].parse().ok()?;
+    let (month, day) = if month_first { (a, b) } else { (b, a) };
+    let year = match parts.get(2) {
+        Some(y) if y.len() == 2 => Some(2000 + y.parse::<i32>().ok()?),
+        Some(y) => Some(y.parse::<i32>().ok()?),
+        None => None,
+    };
+    upcoming(today, month, day, year)
+}
+
+/// Try to read a date phrase starting at `words[i]`; returns the date and
+/// how many words it used
+fn parse_date_at(words: &[String], i: usize, today: NaiveDate, month_first: bool) -> Option<(NaiveDate, usize)> {
+    let w = words.get(i)?.as_str();
+    let next = words.get(i + 1).map(|s| s.as_str());
+
+    // Filler words only count when a date follows ("due fri", "by 20 oct")
+    if matches!(w, "due" | "by" | "on") {
+        return parse_date_at(words, i + 1, today, month_first).map(|(d, n)| (d, n + 1));
+    }
+
+    match w {
+        "today" | "tod" => return Some((today, 1)),
+        "tomorrow" | "tmr" | "tmrw" => return Some((today + Duration::days(1), 1)),
+        "eow" => {
+            let friday = if today.weekday() == Weekday::Fri { today } else { next_weekday(today, Weekday::Fri) };
+            return Some((friday, 1));
+        }
+        "eom" => return last_day_of_month(today).map(|d| (d, 1)),
+        "next" => {
+            let target = next?;
+            let monday = next_weekday(today, Weekday::Mon);
+            if target == "week" {
+                return Some((monday, 2));
+            }
+            if target == "month" {
+                return add_months(today.with_day(1)?, 1).map(|d| (d, 2));
+            }
+            let weekday = weekday_from(target)?;
+            return Some((monday + Duration::days(weekday.num_days_from_monday() as i64), 2));
+        }
+        "in" => {
+            let (n, unit) = match (next?, words.get(i + 2).map(|s| s.as_str())) {
+                ("a" | "one", Some(unit)) => (1, unit),
+                (n, Some(unit)) => (n.parse::<u32>().ok()?, unit),
+                _ => return None,
+            };
+            let date = match unit.trim_end_matches('s') {
+                "day" => today + Duration::days(n as i64),
+                "week" => today + Duration::weeks(n as i64),
+                "month" => add_months(today, n)?,
+                _ => return None,
+            };
+            return Some((date, 3));
+        }
+        _ => {}
+    }
+
+    if let Some(weekday) = weekday_from(w) {
+        return Some((next_weekday(today, weekday), 1));
+    }
+    if let Some(date) = numeric_date(w, today, month_first) {
+        return Some((date, 1));
+    }
+
+    // "20 oct [2026]" / "oct 20 [2026]"
+    let (month, day) = match (day_from(w), next.and_then(month_from)) {
+        (Some(day), Some(month)) => (month, day),
+        _ => (month_from(w)?, next.and_then(day_from)?),
+    };
+    let year = words.get(i + 2).and_then(|y| year_from(y));
+    let date = upcoming(today, month, day, year)?;
+    Some((date, if year.is_some() { 3 } else { 2 }))
+}
+
+fn is_month_first(locale: Option<&str>) -> bool {
+    let region = locale
+        .and_then(|l| l.rsplit(['-', '_']).next())
+        .map(|r| r.to_lowercase())
+        .unwrap_or_default();
+    MONTH_FIRST_REGIONS.contains(&region.as_str())
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Split `text` into title and attributes. Only the first date phrase and the
+/// first priority token are taken; later ones stay in the title.
+pub fn parse_quick_add(text: &str, today: NaiveDate, locale: Option<&str>) -> QuickAddParse {
+    let raw: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = raw.iter().map(|w| clean(w)).collect();
+    let month_first = is_month_first(locale);
+    let mut used = vec![false; raw.len()];
+    let mut parsed = QuickAddParse::default();
+
+    let mut i = 0;
+    while i < raw.len() {
+        let token = raw[i];
+        if let Some(label) = token.strip_prefix('#').filter(|l| !l.is_empty()) {
+            parsed.labels.push(label.to_string());
+            used[i] = true;
+        } else if let Some(mention) = token.strip_prefix('@').filter(|m| !m.is_empty()) {
+            parsed.mentions.push(mention.trim_end_matches([',', '.']).to_string());
+            used[i] = true;
+        } else if parsed.priority.is_none() && words[i].len() == 2 && words[i].starts_with('p') {
+            if let Some(p) = words[i][1..].parse::<i32>().ok().filter(|p| (1..=4).contains(p)) {
+                parsed.priority = Some(p);
+                used[i] = true;
+            }
+        } else if parsed.due_date.is_none() {
+            if let Some((date, len)) = parse_date_at(&words, i, today, month_first) {
+                parsed.due_date = Some(date.format("%Y-%m-%d").to_string());
+                parsed.due_text = Some(raw[i..i + len].join(" "));
+                used[i..i + len].iter_mut().for_each(|u| *u = true);
+                i += len;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    parsed.title = raw
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|(w, _)| *w)
+        .collect::<Vec<_>>()
+        .join(" ");
+    parsed
+}
+
+fn normalize_name(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+pub(super) fn resolve_label<'a>(name: &str, labels: &'a [Label]) -> Option<&'a Label> {
+    let wanted = normalize_name(name);
+    labels.iter().find(|l| normalize_name(&l.name) == wanted)
+}
+
+/// Full name, first name, GitHub username or email local part; must be unique
+pub(super) fn resolve_user<'a>(mention: &str, users: &'a [User]) -> Option<&'a User> {
+    let wanted = normalize_name(mention);
+    let exact: Vec<&User> = users
+        .iter()
+        .filter(|u| {
+            normalize_name(&u.name) == wanted
+                || u.github_username.as_deref().is_some_and(|g| normalize_name(g) == wanted)
+                || u.email.as_deref().and_then(|e| e.split('@').next()).is_some_and(|e| normalize_name(e) == wanted)
+        })
+        .collect();
+    if exact.len() == 1 {
+        return Some(exact[0]);
+    }
+    let by_first: Vec<&User> = users
+        .iter()
+        .filter(|u| u.name.split_whitespace().next().is_some_and(|f| normalize_name(f) == wanted))
+        .collect();
+    if by_first.len() == 1 {
+        Some(by_first[0])
+    } else {
+        None
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Parse capture-box shorthand into a task. Returns the interpretation with
+/// labels and assignees resolved; pass `create: true` to also create it.
+/// `locale` (e.g. "en-US") decides whether 10/11 is Oct 11 or 10 Nov.
+#[tauri::command]
+pub async fn work_quick_add(
+    text: String,
+    project_id: String,
+    locale: Option<String>,
+    create: Option<bool>,
+) -> CmdResult<QuickAddResult> {
+    let today = chrono::Local::now().date_naive();
+    let parsed = parse_quick_add(&text, today, locale.as_deref());
+
+    let client = get_client().await?;
+    let status_id = default_status_id(&client).await?;
+
+    let all_labels: Vec<Label> = if parsed.labels.is_empty() {
+        Vec::new()
+    } else {
+        client.select("labels", "order=name.asc").await?
+    };
+    let all_users: Vec<User> = if parsed.mentions.is_empty() {
+        Vec::new()
+    } else {
+        client.select("users", "order=name.asc").await?
+    };
+
+    let mut labels = Vec::new();
+    let mut unresolved_labels = Vec::new();
+    for name in &parsed.labels {
+        match resolve_label(name, &all_labels) {
+            Some(label) if !labels.iter().any(|l: &Label| l.id == label.id) => labels.push(label.clone()),
+            Some(_) => {}
+            None => unresolved_labels.push(name.clone()),
+        }
+    }
+    let mut assignees = Vec::new();
+    let mut unresolved_mentions = Vec::new();
+    for mention in &parsed.mentions {
+        match resolve_user(mention, &all_users) {
+            Some(user) if !assignees.iter().any(|u: &User| u.id == user.id) => assignees.push(user.clone()),
+            Some(_) => {}
+            None => unresolved_mentions.push(mention.clone()),
+        }
+    }
+
+    let mut result = QuickAddResult {
+        parsed,
+        project_id,
+        status_id,
+        labels,
+        assignees,
+        unresolved_labels,
+        unresolved_mentions,
+        task: None,
+    };
+    if !create.unwrap_or(false) {
+        return Ok(result);
+    }
+
+    if result.parsed.title.trim().is_empty() {
+        return Err(CommandError::Validation {
+            message: "Quick add needs a title".to_string(),
+            fields: vec!["text".to_string()],
+        });
+    }
+    let task = work_create_task(CreateTask {
+        project_id: result.project_id.clone(),
+        status_id: result.status_id.clone(),
+        title: result.parsed.title.clone(),
+        description: None,
+        priority: result.parsed.priority,
+        due_date: result.parsed.due_date.clone(),
+        assignee_ids: Some(result.assignees.iter().map(|u| u.id.clone()).collect()),
+        milestone_id: None,
+        depends_on: None,
+        session_ref: None,
+        requires_review: None,
+        company_id: None,
+        contact_id: None,
+        task_type: None,
+    })
+    .await?;
+    if !result.labels.is_empty() {
+        work_add_task_labels(task.id.clone(), result.labels.iter().map(|l| l.id.clone()).collect()).await?;
+    }
+    result.task = Some(work_get_task(task.id).await?);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shorthand_into_task_fields() {
+        // Friday 16 Oct 2026
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let p = parse_quick_add("tomorrow p1 #bug @melvin fix login", today, None);
+        assert_eq!(p.title, "fix login");
+        assert_eq!(p.due_date.as_deref(), Some("2026-10-17"));
+        assert_eq!(p.priority, Some(1));
+        assert_eq!((p.labels, p.mentions), (vec!["bug".to_string()], vec!["melvin".to_string()]));
+
+        let due = |text: &str, locale: Option<&str>| parse_quick_add(text, today, locale).due_date;
+        assert_eq!(due("call acme fri", None).as_deref(), Some("2026-10-23"));
+        assert_eq!(due("ship next week", None).as_deref(), Some("2026-10-19"));
+        assert_eq!(due("renew in 2 weeks", None).as_deref(), Some("2026-10-30"));
+        assert_eq!(due("report due 3 jan", None).as_deref(), Some("2027-01-03"));
+        assert_eq!(due("report 11/12", None).as_deref(), Some("2026-12-11"));
+        assert_eq!(due("report 11/12", Some("en-US")).as_deref(), Some("2026-11-12"));
+
+        // Words that merely look date-ish stay in the title
+        let p = parse_quick_add("work on monitoring", today, None);
+        assert_eq!((p.title.as_str(), p.due_date), ("work on monitoring", None));
+    }
+}
@@ -0,0 +1,284 @@
+// Work Module - Project status report PDF
+// Composes a formal status report as markdown and renders it through docgen
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::get_client;
+use crate::commands::tools::docgen::{generate_proposal_pdf, markdown_table_cell};
+use crate::commands::tools::docgen_theme::resolve_theme;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::path::Path;
+
+/// Cap on the planned-work list so long backlogs don't swamp the report
+const MAX_PLANNED_TASKS: usize = 25;
+
+/// Reporting window resolved from a period string
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReportPeriod {
+    pub(crate) start: NaiveDate,
+    pub(crate) end: NaiveDate,
+    pub(crate) label: String,
+}
+
+/// "week" | "month" | "quarter" are trailing windows ending today;
+/// "YYYY-MM" is that calendar month.
+pub(crate) fn resolve_period(period: &str, today: NaiveDate) -> CmdResult<ReportPeriod> {
+    let trailing = |days: i64, label: &str| ReportPeriod {
+        start: today - Duration::days(days - 1),
+        end: today,
+        label: label.to_string(),
+    };
+    match period {
+        "week" => Ok(trailing(7, "Last 7 days")),
+        "" | "month" => Ok(trailing(30, "Last 30 days")),
+        "quarter" => Ok(trailing(90, "Last 90 days")),
+        other => {
+            let start = NaiveDate::parse_from_str(&format!("{}-01", other), "%Y-%m-%d")
+                .map_err(|_| CommandError::Config(format!("Unknown period '{}' (use week, month, quarter or YYYY-MM)", other)))?;
+            let next = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            }
+            .ok_or_else(|| CommandError::Config(format!("Invalid period '{}'", other)))?;
+            Ok(ReportPeriod {
+                start,
+                end: next - Duration::days(1),
+                label: start.format("%B %Y").to_string(),
+            })
+        }
+    }
+}
+
+fn day(ts: Option<&str>) -> Option<NaiveDate> {
+    ts.and_then(|s| NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d").ok())
+}
+
+fn pretty_date(d: NaiveDate) -> String {
+    d.format("%-d %b %Y").to_string()
+}
+
+fn status_type(task: &Task) -> &str {
+    task.status.as_ref().map(|s| s.status_type.as_str()).unwrap_or("unstarted")
+}
+
+fn task_ref(project: &Project, task: &Task) -> String {
+    match (project.identifier_prefix.as_deref(), task.task_number) {
+        (Some(prefix), Some(n)) => format!("{}-{} {}", prefix, n, task.title),
+        _ => task.title.clone(),
+    }
+}
+
+fn health_label(health: Option<&str>) -> &'static str {
+    match health {
+        Some("on_track") => "On track",
+        Some("at_risk") => "At risk",
+        Some("off_track") => "Off track",
+        _ => "Not set",
+    }
+}
+
+/// Build the report markdown. Updates and tasks are expected newest-first / any order.
+fn compose_status_report(
+    project: &Project,
+    milestones: &[Milestone],
+    tasks: &[Task],
+    updates: &[ProjectUpdate],
+    period: &ReportPeriod,
+    today: NaiveDate,
+) -> String {
+    let in_period = |d: Option<NaiveDate>| d.map(|d| d >= period.start && d <= period.end).unwrap_or(false);
+    let live: Vec<&Task> = tasks.iter().filter(|t| status_type(t) != "canceled").collect();
+    let done_total = live.iter().filter(|t| status_type(t) == "completed").count();
+
+    let mut md = format!("# {}\n\n## Status Report — {}\n\n", project.name, period.label);
+    md.push_str(&format!(
+        "**Period:** {} – {}  \n**Health:** {}  \n**Status:** {}  \n",
+        pretty_date(period.start),
+        pretty_date(period.end),
+        health_label(project.health.as_deref()),
+        project.status.as_deref().unwrap_or("active"),
+    ));
+    if let Some(target) = day(project.target_date.as_deref()) {
+        md.push_str(&format!("**Target date:** {}  \n", pretty_date(target)));
+    }
+    if !live.is_empty() {
+        md.push_str(&format!(
+            "**Progress:** {} of {} tasks complete ({}%)\n",
+            done_total,
+            live.len(),
+            done_total * 100 / live.len()
+        ));
+    }
+
+    // Summary: latest update inside the period, else the project description
+    md.push_str("\n## Summary\n\n");
+    let period_updates: Vec<&ProjectUpdate> =
+        updates.iter().filter(|u| in_period(day(u.created_at.as_deref()))).collect();
+    match period_updates.first() {
+        Some(u) => md.push_str(u.content.trim()),
+        None => md.push_str(
+            project
+                .description
+                .as_deref()
+                .filter(|d| !d.trim().is_empty())
+                .unwrap_or("No status update was posted during this period."),
+        ),
+    }
+    md.push('\n');
+
+    // Milestones
+    if !milestones.is_empty() {
+        md.push_str("\n## Milestones\n\n| Milestone | Target | Progress | Status |\n|---|---|---|---|\n");
+        for m in milestones {
+            let ms_tasks: Vec<&&Task> = live.iter().filter(|t| t.milestone_id.as_deref() == Some(m.id.as_str())).collect();
+            let ms_done = ms_tasks.iter().filter(|t| status_type(t) == "completed").count();
+            let target = day(m.target_date.as_deref());
+            let status = if !ms_tasks.is_empty() && ms_done == ms_tasks.len() {
+                "Complete"
+            } else if target.map(|d| d < today).unwrap_or(false) {
+                "Overdue"
+            } else if ms_done > 0 {
+                "In progress"
+            } else {
+                "Not started"
+            };
+            md.push_str(&format!(
+                "| {} | {} | {}/{} | {} |\n",
+                markdown_table_cell(&m.name),
+                target.map(pretty_date).unwrap_or_else(|| "-".to_string()),
+                ms_done,
+                ms_tasks.len(),
+                status
+            ));
+        }
+    }
+
+    // Completed in period
+    let mut completed: Vec<&&Task> = live
+        .iter()
+        .filter(|t| status_type(t) == "completed" && in_period(day(t.completed_at.as_deref())))
+        .collect();
+    completed.sort_by(|a, b| a.completed_at.cmp(&b.completed_at));
+    md.push_str("\n## Completed This Period\n\n");
+    if completed.is_empty() {
+        md.push_str("No tasks were completed during this period.\n");
+    }
+    for t in &completed {
+        let when = day(t.completed_at.as_deref()).map(pretty_date).unwrap_or_default();
+        md.push_str(&format!("- {} — {}\n", task_ref(project, t), when));
+    }
+
+    // Planned: in-flight work first, then open tasks by due date
+    let mut planned: Vec<&&Task> = live
+        .iter()
+        .filter(|t| !matches!(status_type(t), "completed" | "canceled"))
+        .collect();
+    planned.sort_by_key(|t| {
+        let in_flight = matches!(status_type(t), "started" | "review");
+        (!in_flight, day(t.due_date.as_deref()).is_none(), t.due_date.clone())
+    });
+    md.push_str("\n## Planned\n\n");
+    if planned.is_empty() {
+        md.push_str("No open tasks.\n");
+    }
+    for t in planned.iter().take(MAX_PLANNED_TASKS) {
+        let mut line = format!("- {}", task_ref(project, t));
+        if let Some(due) = day(t.due_date.as_deref()) {
+            line.push_str(&format!(" — due {}", pretty_date(due)));
+        }
+        if matches!(status_type(t), "started" | "review") {
+            line.push_str(" *(in progress)*");
+        }
+        md.push_str(&line);
+        md.push('\n');
+    }
+    if planned.len() > MAX_PLANNED_TASKS {
+        md.push_str(&format!("- …and {} more\n", planned.len() - MAX_PLANNED_TASKS));
+    }
+
+    // Risks: unhealthy updates this period, overdue milestones and tasks
+    let mut risks: Vec<String> = period_updates
+        .iter()
+        .filter(|u| matches!(u.health.as_deref(), Some("at_risk") | Some("off_track")))
+        .map(|u| {
+            let when = day(u.created_at.as_deref()).map(pretty_date).unwrap_or_default();
+            let first_para = u.content.trim().split("\n\n").next().unwrap_or_default();
+            format!("**{}** ({}): {}", health_label(u.health.as_deref()), when, markdown_table_cell(first_para))
+        })
+        .collect();
+    let overdue_tasks = planned
+        .iter()
+        .filter(|t| day(t.due_date.as_deref()).map(|d| d < today).unwrap_or(false))
+        .count();
+    if overdue_tasks > 0 {
+        risks.push(format!("{} open task(s) are past their due date", overdue_tasks));
+    }
+    if let Some(target) = day(project.target_date.as_deref()) {
+        if target < today && project.status.as_deref() != Some("completed") {
+            risks.push(format!("Project target date ({}) has passed", pretty_date(target)));
+        }
+    }
+    md.push_str("\n## Risks\n\n");
+    if risks.is_empty() {
+        md.push_str("No risks were raised during this period.\n");
+    }
+    for r in risks {
+        md.push_str(&format!("- {}\n", r));
+    }
+
+    md
+}
+
+/// Generate a status report PDF for a project. `period` is week | month |
+/// quarter (trailing, default month) or a calendar month as YYYY-MM.
+#[tauri::command]
+pub async fn work_generate_status_report_pdf(
+    project_id: String,
+    period: Option<String>,
+    output_path: String,
+) -> CmdResult<String> {
+    let client = get_client().await?;
+    let today = chrono::Local::now().date_naive();
+    let period = resolve_period(period.as_deref().unwrap_or("month"), today)?;
+
+    let project: Project = client
+        .select_single("projects", &format!("id=eq.{}", project_id))
+        .await?
+        .ok_or_else(|| CommandError::NotFound(format!("Project not found: {}", project_id)))?;
+    let milestones: Vec<Milestone> = client
+        .select("milestones", &format!("project_id=eq.{}&order=sort_order.asc,target_date.asc", project_id))
+        .await?;
+    let tasks: Vec<Task> = client
+        .select("tasks", &format!("select=*,status:task_statuses(*)&project_id=eq.{}", project_id))
+        .await?;
+    let updates: Vec<ProjectUpdate> = client
+        .select("project_updates", &format!("project_id=eq.{}&order=created_at.desc", project_id))
+        .await?;
+
+    let markdown = compose_status_report(&project, &milestones, &tasks, &updates, &period, today);
+
+    if let Some(dir) = Path::new(&output_path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let theme = resolve_theme(Some(Path::new(&output_path)));
+    generate_proposal_pdf(&markdown, &output_path, &theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_calendar_and_trailing_periods() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let dec = resolve_period("2026-12", today).unwrap();
+        assert_eq!(dec.start, NaiveDate::from_ymd_opt(2026, 12, 1).unwrap());
+        assert_eq!(dec.end, NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
+        assert_eq!(dec.label, "December 2026");
+
+        let week = resolve_period("week", today).unwrap();
+        assert_eq!(week.start, NaiveDate::from_ymd_opt(2026, 10, 10).unwrap());
+        assert!(resolve_period("fortnight", today).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 // Work Module - Task Commands
 
 use super::types::*;
+use super::notifier::notify_task_activity;
+use super::transitions::{apply_completion_policy, is_completed};
+use super::wip::check_wip_for_move;
 use crate::commands::error::{CmdResult, CommandError};
-use crate::commands::supabase::get_client;
+use crate::commands::supabase::{get_client, SupabaseClient};
 
 /// List tasks with optional filters
 #[tauri::command]
@@ -59,6 +62,18 @@ pub async fn work_get_task(task_id: String) -> CmdResult<Task> {
         .ok_or_else(|| CommandError::NotFound(format!("Task not found: {}", task_id)))
 }
 
+/// First backlog (else unstarted) status — where tasks filed by automations land
+pub(crate) async fn default_status_id(client: &SupabaseClient) -> CmdResult<String> {
+    let statuses: Vec<TaskStatus> = client.select("task_statuses", "order=sort_order.asc").await?;
+    statuses
+        .iter()
+        .find(|s| s.status_type == "backlog")
+        .or_else(|| statuses.iter().find(|s| s.status_type == "unstarted"))
+        .or(statuses.first())
+        .map(|s| s.id.clone())
+        .ok_or_else(|| CommandError::Config("No task statuses configured".into()))
+}
+
 /// Create a new task
 #[tauri::command]
 pub async fn work_create_task(data: CreateTask) -> CmdResult<Task> {
@@ -123,8 +138,40 @@ pub async fn work_create_task(data: CreateTask) -> CmdResult<Task> {
 
 /// Update a task
 #[tauri::command]
-pub async fn work_update_task(task_id: String, data: UpdateTask) -> CmdResult<Task> {
+pub async fn work_update_task(task_id: String, mut data: UpdateTask) -> CmdResult<Task> {
     let client = get_client().await?;
+    // A description edited by hand is no longer AI output
+    if data.description.is_some() && data.ai_generated.is_none() {
+        data.ai_generated = Some(false);
+    }
+    // Re-saving an already completed task must not re-run the policy
+    let was_completed = match &data.status_id {
+        Some(_) => is_completed(&client, &task_id).await?,
+        None => true,
+    };
+    let mut task = apply_task_update(&client, task_id, &data).await?;
+    notify_task_activity(&client, &task, &data).await;
+
+    // Completion runs the project's transition policy; the update itself has
+    // already landed, so policy failures are logged rather than returned
+    let completed = task.status.as_ref().is_some_and(|s| s.status_type == "completed");
+    if !was_completed && completed {
+        match apply_completion_policy(&client, &task).await {
+            Ok(unblocked) if !unblocked.is_empty() => task.unblocked = Some(unblocked),
+            Ok(_) => {}
+            Err(e) => eprintln!("[work:transitions] Policy failed for task {}: {}", task.id, e),
+        }
+    }
+    Ok(task)
+}
+
+pub(super) async fn apply_task_update(client: &SupabaseClient, task_id: String, data: &UpdateTask) -> CmdResult<Task> {
+    // WIP limits: a hard limit rejects the move before anything is written,
+    // soft limits ride along on the returned task
+    let wip_warnings = match &data.status_id {
+        Some(status_id) => check_wip_for_move(client, &task_id, status_id).await?,
+        None => Vec::new(),
+    };
 
     // Handle assignee replacement first (independent of other fields)
     if let Some(assignee_ids) = &data.assignee_ids {
@@ -143,7 +190,7 @@ pub async fn work_update_task(task_id: String, data: UpdateTask) -> CmdResult<Ta
 
     // If task_type is changing, update task_type_changed_at
     if data.task_type.is_some() {
-        let mut update_data = serde_json::to_value(&data)?;
+        let mut update_data = serde_json::to_value(data)?;
         if let Some(obj) = update_data.as_object_mut() {
             obj.insert(
                 "task_type_changed_at".to_string(),
@@ -153,7 +200,7 @@ pub async fn work_update_task(task_id: String, data: UpdateTask) -> CmdResult<Ta
         let _: serde_json::Value = client
             .update("tasks", &format!("id=eq.{}", task_id), &update_data)
             .await?;
-        return work_get_task(task_id).await;
+        return attach_wip_warnings(work_get_task(task_id).await, wip_warnings);
     }
 
     // Check if status is changing to completed
@@ -166,7 +213,7 @@ pub async fn work_update_task(task_id: String, data: UpdateTask) -> CmdResult<Ta
         if let Some(s) = status {
             if s.status_type == "completed" {
                 // Set completed_at timestamp
-                let mut update_data = serde_json::to_value(&data)?;
+                let mut update_data = serde_json::to_value(data)?;
                 if let Some(obj) = update_data.as_object_mut() {
                     obj.insert(
                         "completed_at".to_string(),
@@ -177,16 +224,24 @@ pub async fn work_update_task(task_id: String, data: UpdateTask) -> CmdResult<Ta
                 let _: Task = client
                     .update("tasks", &format!("id=eq.{}", task_id), &update_data)
                     .await?;
-                return work_get_task(task_id).await;
+                return attach_wip_warnings(work_get_task(task_id).await, wip_warnings);
             }
         }
     }
 
     let _: serde_json::Value = client
-        .update("tasks", &format!("id=eq.{}", task_id), &data)
+        .update("tasks", &format!("id=eq.{}", task_id), data)
         .await?;
 
-    work_get_task(task_id).await
+    attach_wip_warnings(work_get_task(task_id).await, wip_warnings)
+}
+
+fn attach_wip_warnings(task: CmdResult<Task>, warnings: Vec<WipWarning>) -> CmdResult<Task> {
+    let mut task = task?;
+    if !warnings.is_empty() {
+        task.wip_warnings = Some(warnings);
+    }
+    Ok(task)
 }
 
 /// Delete a task
@@ -0,0 +1,230 @@
+// Work Module - Dependency-aware transition policies
+// When a task completes, its dependents (tasks listing it in depends_on) whose
+// blockers are now all done can be moved to a ready status ("Todo" by default)
+// and their assignees notified. Configured per project; no row = no policy.
+
+use super::notifier::notify_unblocked;
+use super::tasks::apply_task_update;
+use super::types::*;
+use crate::commands::error::CmdResult;
+use crate::commands::supabase::{get_client, SupabaseClient};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Status types that count as a finished blocker
+const DONE_TYPES: [&str; 2] = ["completed", "canceled"];
+/// Status types a dependent may be moved out of — anything further along is left alone
+const WAITING_TYPES: [&str; 2] = ["backlog", "unstarted"];
+/// Status name treated as waiting regardless of its type
+const BLOCKED_STATUS_NAME: &str = "blocked";
+
+#[derive(Debug, Deserialize)]
+struct BlockerRow {
+    id: String,
+    status: Option<BlockerStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockerStatus {
+    #[serde(rename = "type")]
+    status_type: String,
+}
+
+fn is_done(status: Option<&TaskStatus>) -> bool {
+    status.is_some_and(|s| DONE_TYPES.contains(&s.status_type.as_str()))
+}
+
+/// Whether `task_id` currently sits in a completed status
+pub(super) async fn is_completed(client: &SupabaseClient, task_id: &str) -> CmdResult<bool> {
+    let row: Option<BlockerRow> = client
+        .select_single("tasks", &format!("select=id,status:task_statuses(type)&id=eq.{}", task_id))
+        .await?;
+    Ok(row.and_then(|r| r.status).is_some_and(|s| s.status_type == "completed"))
+}
+
+/// Whether an unblocked dependent should be moved into `target_status_id`
+pub fn should_move(status: Option<&TaskStatus>, target_status_id: &str) -> bool {
+    match status {
+        Some(s) => {
+            s.id != target_status_id
+                && (WAITING_TYPES.contains(&s.status_type.as_str())
+                    || s.name.eq_ignore_ascii_case(BLOCKED_STATUS_NAME))
+        }
+        None => true,
+    }
+}
+
+/// Evaluate the project's policy for a task that just completed. Returns the
+/// dependents that are now unblocked, with what was done for each.
+pub(super) async fn apply_completion_policy(client: &SupabaseClient, task: &Task) -> CmdResult<Vec<UnblockedTask>> {
+    let policy: Option<TransitionPolicy> = client
+        .select_single(
+            "task_transition_policies",
+            &format!("project_id=eq.{}", task.project_id),
+        )
+        .await?;
+    let Some(policy) = policy.filter(|p| p.enabled && (p.unblock_dependents || p.notify_assignees)) else {
+        return Ok(Vec::new());
+    };
+
+    let dependents: Vec<Task> = client
+        .select_all(
+            "tasks",
+            &format!(
+                "select=*,project:projects(*),status:task_statuses(*),assignees:task_assignees(user:users(*))&depends_on=cs.{{{}}}&order=id.asc",
+                task.id
+            ),
+        )
+        .await?;
+    let open: Vec<Task> = dependents
+        .into_iter()
+        .filter(|d| !is_done(d.status.as_ref()))
+        .collect();
+    if open.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Status of every blocker of every open dependent, in one query
+    let blocker_ids: HashSet<&str> = open
+        .iter()
+        .flat_map(|d| d.depends_on.iter().flatten())
+        .map(|id| id.as_str())
+        .collect();
+    let blocker_list: Vec<&str> = blocker_ids.into_iter().collect();
+    let blockers: Vec<BlockerRow> = client
+        .select(
+            "tasks",
+            &format!("select=id,status:task_statuses(type)&id=in.({})", blocker_list.join(",")),
+        )
+        .await?;
+    let done: HashSet<&str> = blockers
+        .iter()
+        .filter(|b| b.status.as_ref().is_some_and(|s| DONE_TYPES.contains(&s.status_type.as_str())))
+        .map(|b| b.id.as_str())
+        .chain(std::iter::once(task.id.as_str()))
+        .collect();
+
+    let target: Option<TaskStatus> = if policy.unblock_dependents {
+        let matches: Vec<TaskStatus> = client
+            .select(
+                "task_statuses",
+                &format!(
+                    "name=ilike.{}&order=sort_order.asc",
+                    urlencoding::encode(&policy.unblock_status_name)
+                ),
+            )
+            .await?;
+        matches.into_iter().next()
+    } else {
+        None
+    };
+
+    let mut unblocked = Vec::new();
+    for dep in open {
+        let deps = dep.depends_on.as_deref().unwrap_or_default();
+        if !deps.iter().all(|id| done.contains(id.as_str())) {
+            continue;
+        }
+
+        let mut entry = UnblockedTask {
+            task_id: dep.id.clone(),
+            title: dep.title.clone(),
+            moved_to: None,
+            notified: 0,
+            error: None,
+        };
+        if policy.unblock_dependents {
+            match &target {
+                Some(status) if should_move(dep.status.as_ref(), &status.id) => {
+                    let update = UpdateTask {
+                        status_id: Some(status.id.clone()),
+                        ..Default::default()
+                    };
+                    // Goes through the normal update path so WIP limits still apply
+                    match apply_task_update(client, dep.id.clone(), &update).await {
+                        Ok(_) => entry.moved_to = Some(status.name.clone()),
+                        Err(e) => entry.error = Some(e.to_string()),
+                    }
+                }
+                Some(_) => {}
+                None => entry.error = Some(format!("No status named '{}'", policy.unblock_status_name)),
+            }
+        }
+        if policy.notify_assignees {
+            entry.notified = notify_unblocked(client, &dep, task).await;
+        }
+        unblocked.push(entry);
+    }
+
+    Ok(unblocked)
+}
+
+/// Get a project's transition policy (None when not configured)
+#[tauri::command]
+pub async fn work_get_transition_policy(project_id: String) -> CmdResult<Option<TransitionPolicy>> {
+    let client = get_client().await?;
+    client
+        .select_single(
+            "task_transition_policies",
+            &format!("project_id=eq.{}", project_id),
+        )
+        .await
+}
+
+/// Create or update a project's transition policy. Omitted fields keep their
+/// current value (or the table default for a new policy).
+#[tauri::command]
+pub async fn work_set_transition_policy(
+    project_id: String,
+    data: UpsertTransitionPolicy,
+) -> CmdResult<TransitionPolicy> {
+    let client = get_client().await?;
+    let existing: Option<TransitionPolicy> = client
+        .select_single(
+            "task_transition_policies",
+            &format!("project_id=eq.{}", project_id),
+        )
+        .await?;
+
+    match existing {
+        Some(policy) => {
+            client
+                .update("task_transition_policies", &format!("id=eq.{}", policy.id), &data)
+                .await
+        }
+        None => {
+            let mut row = serde_json::to_value(&data)?;
+            if let Some(obj) = row.as_object_mut() {
+                obj.insert("project_id".to_string(), serde_json::Value::String(project_id));
+            }
+            client.insert("task_transition_policies", &row).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: &str, name: &str, status_type: &str) -> TaskStatus {
+        TaskStatus {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            color: None,
+            icon: None,
+            status_type: status_type.to_string(),
+            sort_order: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn moves_only_waiting_dependents() {
+        assert!(should_move(Some(&status("b", "Backlog", "backlog")), "todo"));
+        assert!(should_move(Some(&status("x", "Blocked", "started")), "todo"));
+        assert!(!should_move(Some(&status("todo", "Todo", "unstarted")), "todo"));
+        assert!(!should_move(Some(&status("p", "In Progress", "started")), "todo"));
+        assert!(!should_move(Some(&status("r", "In Review", "review")), "todo"));
+    }
+}
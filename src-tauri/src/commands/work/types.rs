@@ -71,6 +71,14 @@ pub struct Project {
     pub deal_lost_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deal_won_notes: Option<String>,
+    /// lookup_values code (deal_won_reason / deal_lost_reason), required on close
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_close_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_competitor: Option<String>,
+    /// Stage the deal was in when it moved to won/lost (set automatically)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_stage_at_close: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deal_stage_changed_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -194,6 +202,11 @@ pub struct UpdateProject {
     pub deal_lost_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deal_won_notes: Option<String>,
+    /// Required when deal_stage moves to won/lost (unless already set on the deal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_close_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal_competitor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deal_notes: Option<String>,
     /// Manually set deal_stage_changed_at (use with preserve_stage_date to override automatic update)
@@ -281,9 +294,17 @@ pub struct Task {
     pub triage_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_triaged_at: Option<String>,
+    // Set when the description was written by work_ai_assist_task; cleared on human edit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_generated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_generated_at: Option<String>,
     // Notion sync
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notion_page_id: Option<String>,
+    // Subtask of (checklist import keeps nesting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_task_id: Option<String>,
     // Nested data (from joins)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project: Option<Box<Project>>,
@@ -293,6 +314,12 @@ pub struct Task {
     pub assignees: Option<Vec<TaskAssignee>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub labels: Option<Vec<Label>>,
+    // Set by work_update_task when a move exceeds a soft WIP limit (not a DB column)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_warnings: Option<Vec<WipWarning>>,
+    // Set by work_update_task when completing this task unblocked dependents (not a DB column)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unblocked: Option<Vec<UnblockedTask>>,
 }
 
 // Junction table wrapper for task_assignees join
@@ -345,6 +372,9 @@ pub struct UpdateTask {
     pub due_date: Option<String>,
     #[serde(skip_serializing)]
     pub assignee_ids: Option<Vec<String>>,
+    /// User making the change; they aren't notified about it (not a DB column)
+    #[serde(skip_serializing)]
+    pub updated_by: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub milestone_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -370,6 +400,219 @@ pub struct UpdateTask {
     pub triage_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_triaged_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_generated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_generated_at: Option<String>,
+}
+
+// ============================================================================
+// WIP Limits
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipLimit {
+    pub id: String,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_id: Option<String>,
+    pub max_tasks: i32,
+    pub enforcement: String, // soft | hard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertWipLimit {
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_id: Option<String>,
+    pub max_tasks: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipWarning {
+    pub scope: String, // status | label
+    pub scope_id: String,
+    pub scope_name: String,
+    pub max_tasks: i32,
+    /// Task count in the scope if the move goes through
+    pub count_after: usize,
+    pub enforcement: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipLoad {
+    pub scope: String, // status | label
+    pub scope_id: String,
+    pub scope_name: String,
+    pub count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tasks: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<String>,
+    pub over_limit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipStatus {
+    pub project_id: String,
+    /// One entry per status column, in board order
+    pub columns: Vec<WipLoad>,
+    /// Only labels that have a limit configured
+    pub labels: Vec<WipLoad>,
+}
+
+// ============================================================================
+// Working-days Calendar
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkCalendar {
+    pub country: String, // ISO 3166-1 alpha-2, e.g. SG
+    /// ISO weekdays that are off (1 = Monday … 7 = Sunday)
+    pub weekend_days: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkHoliday {
+    pub id: String,
+    pub country: String,
+    pub date: String, // YYYY-MM-DD
+    pub name: String,
+}
+
+// ============================================================================
+// Working Hours / Team Availability
+// ============================================================================
+
+/// One person's working day, resolved to their own timezone and calendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserAvailability {
+    pub user_id: String,
+    pub name: String,
+    pub timezone: String,
+    pub country: String,
+    /// Wall-clock time for them right now (RFC 3339 with their offset)
+    pub local_time: String,
+    /// Inside their working hours right now
+    pub working_now: bool,
+    /// working | off_hours | day_off | holiday
+    pub status: String,
+    /// Their working window on the requested date, in UTC (absent on days off)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_end: Option<String>,
+    /// Next time their working hours start, when they're not working now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_available_at: Option<String>,
+}
+
+/// A task's due deadline and remaining SLA time for one assignee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssigneeDueStatus {
+    pub user_id: String,
+    pub name: String,
+    pub timezone: String,
+    /// End of their working hours on the due date, in UTC
+    pub due_at: String,
+    pub overdue: bool,
+    /// Working minutes left until due_at (negative once overdue, counting
+    /// working minutes since)
+    pub working_minutes_remaining: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueReminderResult {
+    pub due_today: usize,
+    pub overdue: usize,
+}
+
+// ============================================================================
+// Notification Preferences
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectNotificationPref {
+    pub id: String,
+    pub user_id: String,
+    pub project_id: String,
+    pub level: String, // all | mentions | mute
+    pub weekly_digest: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_digest_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertProjectNotificationPref {
+    pub user_id: String,
+    pub project_id: String,
+    pub level: String,
+    pub weekly_digest: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDigestResult {
+    pub sent: usize,
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Transition Policies
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionPolicy {
+    pub id: String,
+    pub project_id: String,
+    pub enabled: bool,
+    /// On completion, move dependents whose blockers are all done into `unblock_status_name`
+    pub unblock_dependents: bool,
+    pub unblock_status_name: String,
+    /// On completion, notify assignees of newly unblocked dependents
+    pub notify_assignees: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UpsertTransitionPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unblock_dependents: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unblock_status_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_assignees: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnblockedTask {
+    pub task_id: String,
+    pub title: String,
+    /// Status the task was moved to, if the policy moved it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_to: Option<String>,
+    pub notified: usize,
+    /// Why the move was skipped (e.g. a hard WIP limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 // ============================================================================
@@ -489,6 +732,39 @@ pub struct UpdateInitiative {
     pub target_date: Option<String>,
 }
 
+/// Computed rollup for an initiative — derived from child projects, not stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeSummary {
+    pub initiative_id: String,
+    pub name: String,
+    pub project_count: usize,
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub percent_complete: f64,
+    /// Status inherited from child projects (completed when all are, active when any is)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derived_status: Option<String>,
+    /// Worst health among child projects: off_track > at_risk > on_track
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derived_health: Option<String>,
+    pub at_risk: bool,
+    pub at_risk_reasons: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_update: Option<InitiativeUpdateExcerpt>,
+    pub computed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitiativeUpdateExcerpt {
+    pub project_id: String,
+    pub project_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+    pub excerpt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
 // ============================================================================
 // Labels
 // ============================================================================
@@ -538,6 +814,15 @@ pub struct User {
     pub bot_folder_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bot_department: Option<String>,
+    // Working hours (NULL = Singapore 09:00–18:00)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>, // IANA, e.g. Europe/London
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_start: Option<String>, // HH:MM[:SS] local
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>, // work_calendars country for weekends/holidays
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
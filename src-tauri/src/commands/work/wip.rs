@@ -0,0 +1,268 @@
+// Work Module - WIP Limits
+// Per-project caps on tasks per status column, and on in-progress tasks per label.
+// Status limits count every task in the column; label limits count only tasks
+// in started/review statuses, since that's where work-in-progress piles up.
+
+use super::types::*;
+use crate::commands::error::{CmdResult, CommandError};
+use crate::commands::supabase::{get_client, SupabaseClient};
+use serde::Deserialize;
+
+/// Status types that count toward label limits
+const IN_PROGRESS_TYPES: [&str; 2] = ["started", "review"];
+
+#[derive(Debug, Deserialize)]
+struct TaskLoadRow {
+    id: String,
+    #[serde(default)]
+    project_id: String,
+    status_id: String,
+    #[serde(default)]
+    task_labels: Vec<TaskLabelRow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskLabelRow {
+    label_id: String,
+}
+
+async fn load_project_tasks(client: &SupabaseClient, project_id: &str) -> CmdResult<Vec<TaskLoadRow>> {
+    client
+        .select_all(
+            "tasks",
+            &format!("select=id,status_id,task_labels(label_id)&project_id=eq.{}&order=id.asc", project_id),
+        )
+        .await
+}
+
+fn in_progress_status_ids(statuses: &[TaskStatus]) -> Vec<&str> {
+    statuses
+        .iter()
+        .filter(|s| IN_PROGRESS_TYPES.contains(&s.status_type.as_str()))
+        .map(|s| s.id.as_str())
+        .collect()
+}
+
+/// Check whether moving `task_id` into `target_status_id` breaks any limit in its
+/// project. Returns soft warnings; a hard limit returns an error instead.
+pub(super) async fn check_wip_for_move(
+    client: &SupabaseClient,
+    task_id: &str,
+    target_status_id: &str,
+) -> CmdResult<Vec<WipWarning>> {
+    let task: Option<TaskLoadRow> = client
+        .select_single(
+            "tasks",
+            &format!("select=id,status_id,project_id,task_labels(label_id)&id=eq.{}", task_id),
+        )
+        .await?;
+    let Some(task) = task else {
+        return Ok(Vec::new());
+    };
+    if task.status_id == target_status_id {
+        return Ok(Vec::new());
+    }
+
+    let project_id = task.project_id.clone();
+    let limits: Vec<WipLimit> = client
+        .select("wip_limits", &format!("project_id=eq.{}", project_id))
+        .await?;
+    if limits.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let statuses: Vec<TaskStatus> = client.select("task_statuses", "order=sort_order.asc").await?;
+    let in_progress = in_progress_status_ids(&statuses);
+    let project_tasks = load_project_tasks(client, &project_id).await?;
+    let others: Vec<&TaskLoadRow> = project_tasks.iter().filter(|t| t.id != task.id).collect();
+
+    let mut warnings = Vec::new();
+    for limit in &limits {
+        let (scope, scope_id, scope_name, count_after) = if let Some(status_id) = &limit.status_id {
+            if status_id != target_status_id {
+                continue;
+            }
+            let count = others.iter().filter(|t| &t.status_id == status_id).count() + 1;
+            let name = statuses
+                .iter()
+                .find(|s| &s.id == status_id)
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| status_id.clone());
+            ("status", status_id.clone(), name, count)
+        } else if let Some(label_id) = &limit.label_id {
+            // Only matters when the task carries the label and enters in-progress
+            // from outside it
+            let has_label = task.task_labels.iter().any(|l| &l.label_id == label_id);
+            let entering = in_progress.contains(&target_status_id)
+                && !in_progress.contains(&task.status_id.as_str());
+            if !has_label || !entering {
+                continue;
+            }
+            let count = others
+                .iter()
+                .filter(|t| {
+                    in_progress.contains(&t.status_id.as_str())
+                        && t.task_labels.iter().any(|l| &l.label_id == label_id)
+                })
+                .count()
+                + 1;
+            let label: Option<Label> = client
+                .select_single("labels", &format!("id=eq.{}", label_id))
+                .await?;
+            let name = label.map(|l| l.name).unwrap_or_else(|| label_id.clone());
+            ("label", label_id.clone(), name, count)
+        } else {
+            continue;
+        };
+
+        if count_after as i32 <= limit.max_tasks {
+            continue;
+        }
+        let message = format!(
+            "WIP limit for {} '{}' is {} — this move makes {}",
+            scope, scope_name, limit.max_tasks, count_after
+        );
+        if limit.enforcement == "hard" {
+            return Err(CommandError::Config(message));
+        }
+        warnings.push(WipWarning {
+            scope: scope.to_string(),
+            scope_id,
+            scope_name,
+            max_tasks: limit.max_tasks,
+            count_after,
+            enforcement: limit.enforcement.clone(),
+            message,
+        });
+    }
+
+    Ok(warnings)
+}
+
+/// List WIP limits configured for a project
+#[tauri::command]
+pub async fn work_list_wip_limits(project_id: String) -> CmdResult<Vec<WipLimit>> {
+    let client = get_client().await?;
+    client
+        .select("wip_limits", &format!("project_id=eq.{}", project_id))
+        .await
+}
+
+/// Create or replace the limit for a project's status column or label
+#[tauri::command]
+pub async fn work_set_wip_limit(data: UpsertWipLimit) -> CmdResult<WipLimit> {
+    if data.status_id.is_some() == data.label_id.is_some() {
+        return Err(CommandError::Config(
+            "A WIP limit needs exactly one of status_id or label_id".to_string(),
+        ));
+    }
+    if data.max_tasks < 1 {
+        return Err(CommandError::Config("max_tasks must be at least 1".to_string()));
+    }
+    if let Some(e) = &data.enforcement {
+        if e != "soft" && e != "hard" {
+            return Err(CommandError::Config(format!("Unknown enforcement: {} (expected soft or hard)", e)));
+        }
+    }
+
+    let client = get_client().await?;
+    let scope_filter = match (&data.status_id, &data.label_id) {
+        (Some(sid), _) => format!("status_id=eq.{}", sid),
+        (_, Some(lid)) => format!("label_id=eq.{}", lid),
+        _ => unreachable!(),
+    };
+    let existing: Option<WipLimit> = client
+        .select_single(
+            "wip_limits",
+            &format!("project_id=eq.{}&{}", data.project_id, scope_filter),
+        )
+        .await?;
+
+    match existing {
+        Some(limit) => {
+            client
+                .update("wip_limits", &format!("id=eq.{}", limit.id), &data)
+                .await
+        }
+        None => client.insert("wip_limits", &data).await,
+    }
+}
+
+/// Remove a WIP limit
+#[tauri::command]
+pub async fn work_delete_wip_limit(limit_id: String) -> CmdResult<()> {
+    let client = get_client().await?;
+    client.delete("wip_limits", &format!("id=eq.{}", limit_id)).await
+}
+
+/// Current load per status column (and per limited label) for the board header
+#[tauri::command]
+pub async fn work_get_wip_status(project_id: String) -> CmdResult<WipStatus> {
+    let client = get_client().await?;
+
+    let statuses: Vec<TaskStatus> = client.select("task_statuses", "order=sort_order.asc").await?;
+    let limits: Vec<WipLimit> = client
+        .select("wip_limits", &format!("project_id=eq.{}", project_id))
+        .await?;
+    let tasks = load_project_tasks(&client, &project_id).await?;
+    let in_progress = in_progress_status_ids(&statuses);
+
+    let columns = statuses
+        .iter()
+        .map(|s| {
+            let count = tasks.iter().filter(|t| t.status_id == s.id).count();
+            let limit = limits.iter().find(|l| l.status_id.as_deref() == Some(s.id.as_str()));
+            WipLoad {
+                scope: "status".to_string(),
+                scope_id: s.id.clone(),
+                scope_name: s.name.clone(),
+                count,
+                max_tasks: limit.map(|l| l.max_tasks),
+                enforcement: limit.map(|l| l.enforcement.clone()),
+                over_limit: limit.map(|l| count as i32 > l.max_tasks).unwrap_or(false),
+            }
+        })
+        .collect();
+
+    let label_ids: Vec<&str> = limits.iter().filter_map(|l| l.label_id.as_deref()).collect();
+    let labels: Vec<Label> = if label_ids.is_empty() {
+        Vec::new()
+    } else {
+        client
+            .select("labels", &format!("id=in.({})", label_ids.join(",")))
+            .await?
+    };
+
+    let label_loads = limits
+        .iter()
+        .filter_map(|l| l.label_id.as_ref().map(|lid| (l, lid)))
+        .map(|(limit, label_id)| {
+            let count = tasks
+                .iter()
+                .filter(|t| {
+                    in_progress.contains(&t.status_id.as_str())
+                        && t.task_labels.iter().any(|tl| &tl.label_id == label_id)
+                })
+                .count();
+            WipLoad {
+                scope: "label".to_string(),
+                scope_id: label_id.clone(),
+                scope_name: labels
+                    .iter()
+                    .find(|lb| &lb.id == label_id)
+                    .map(|lb| lb.name.clone())
+                    .unwrap_or_else(|| label_id.clone()),
+                count,
+                max_tasks: Some(limit.max_tasks),
+                enforcement: Some(limit.enforcement.clone()),
+                over_limit: count as i32 > limit.max_tasks,
+            }
+        })
+        .collect();
+
+    Ok(WipStatus {
+        project_id,
+        columns,
+        labels: label_loads,
+    })
+}
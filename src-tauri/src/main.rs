@@ -51,7 +51,19 @@ fn create_new_window(app: &tauri::AppHandle) {
 fn main() {
     env_logger::init();
 
-    tauri::Builder::default()
+    let builder = tauri::Builder::default();
+
+    // Must be the first plugin: a second launch (e.g. opening a tvclient:// link
+    // on Windows/Linux) hands its URL to this instance and exits
+    #[cfg(any(windows, target_os = "linux"))]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.set_focus();
+        }
+    }));
+
+    builder
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -85,6 +97,13 @@ fn main() {
 
             // Terminal sessions state
             app.manage(commands::terminal::TerminalSessions::default());
+            // Kill shells left running by a previous crash
+            std::thread::spawn(|| {
+                let cleaned = commands::terminal::cleanup_stale_sessions();
+                if !cleaned.sessions.is_empty() {
+                    eprintln!("[terminal] Killed {} orphaned shell(s) from a previous run", cleaned.sessions.len());
+                }
+            });
 
             // Reset any jobs stuck in "running" from a previous crash (async)
             tauri::async_runtime::spawn(async move {
@@ -94,6 +113,18 @@ fn main() {
             // Start Outlook background sync
             commands::outlook::background::start_background_sync(app.handle().clone());
 
+            // Start Outlook push notifications (no-op unless enabled)
+            commands::outlook::push::start_push(app.handle().clone());
+
+            // Start Outlook cache cleanup schedule (no-op unless enabled)
+            commands::outlook::cleanup::start_cleanup_scheduler(app.handle().clone());
+
+            // Start CRM activity auto-capture from Outlook (no-op unless enabled)
+            commands::outlook::activity_capture::start_activity_capture(app.handle().clone());
+
+            // Start file automation watchers (no-op without saved rules)
+            commands::files::start_file_automations(app.handle().clone());
+
             // Start Notion background sync
             commands::notion::background::start_background_sync(app.handle().clone());
 
@@ -101,20 +132,69 @@ fn main() {
             // Start GA4 Analytics background sync
             commands::analytics::background::start_background_sync(app.handle().clone());
 
+            // Start monthly analytics archival (no-op unless enabled)
+            commands::analytics::archive::start_archive_scheduler(app.handle().clone());
+
             // Start Public Data background sync (daily — MCF job postings etc.)
             commands::public_data::background::start_background_sync(app.handle().clone());
 
+            // Start Work initiative rollup refresh
+            commands::work::background::start_rollup_refresh(app.handle().clone());
+
+            // Start weekly per-project digest emails (only for users who opted in)
+            commands::work::notifier::start_project_digest_scheduler(app.handle().clone());
+
+            // Start due-today / overdue reminders (in each assignee's working hours)
+            commands::work::availability::start_due_reminders(app.handle().clone());
+
+            // Start periodic HubSpot pull (gated by bg_sync_hubspot, after an initial import)
+            commands::crm::import::hubspot::start_hubspot_pull(app.handle().clone());
+
+            // Start CRM segment recomputation (each segment on its own refresh interval)
+            commands::crm::segments::start_segment_refresh(app.handle().clone());
+
+            // Start daily pipeline snapshots for the pipeline history chart
+            commands::crm::pipeline_history::start_pipeline_snapshots(app.handle().clone());
+
+            // Start scheduled knowledge backups (no-op unless enabled)
+            commands::backup::commands::start_backup_scheduler(app.handle().clone());
+
             // Start Scheduler background loop
             commands::scheduler::background::start_scheduler(
                 app.handle().clone(),
                 "0_Platform/sod-reports".to_string(),
             );
 
+            // tvclient:// deep links → resolve and route to the right window
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                // Installers register the scheme; dev builds on Windows/Linux need it at runtime
+                #[cfg(all(debug_assertions, any(windows, target_os = "linux")))]
+                let _ = app.deep_link().register_all();
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    for url in urls {
+                        commands::links::open_deep_link(app.handle().clone(), url.to_string());
+                    }
+                }
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        commands::links::open_deep_link(handle.clone(), url.to_string());
+                    }
+                });
+            }
+
             // Auto-register tv-mcp with Claude Code if path is stale or missing
             tauri::async_runtime::spawn(async {
                 commands::claude_setup::ensure_mcp_registered().await;
             });
 
+            // Start MCP REST bridge (no-op unless enabled in ~/.tv-mcp/bridge.json)
+            commands::mcp_bridge::start_bridge();
+
+            // Start bot API (no-op unless enabled in ~/.tv-client/bot_api.json)
+            commands::work::bot_api::start_bot_api();
+
             // Build native macOS menu bar
             let handle = app.handle();
 
@@ -237,22 +317,79 @@ fn main() {
             commands::claude_setup::claude_mcp_uninstall,
             // MCP Tools registry sync
             commands::mcp_tools::sync_mcp_tools_command,
+            // Command palette registry
+            commands::palette::palette_list_actions,
+            commands::palette::palette_search,
+            commands::mcp_bridge::mcp_bridge_get_config,
+            commands::mcp_bridge::mcp_bridge_save_config,
+            commands::mcp_bridge::mcp_bridge_status,
+            commands::mcp_bridge::mcp_bridge_list_tools,
+            commands::mcp_bridge::mcp_bridge_openapi,
+            // MCP tool context
+            commands::mcp_context::mcp_set_active_context,
+            commands::mcp_context::mcp_get_active_context,
+            commands::mcp_context::mcp_clear_active_context,
+            commands::mcp_context::mcp_context_get_profiles,
+            commands::mcp_context::mcp_context_save_profiles,
+            commands::mcp_context::mcp_list_tools_for_context,
+            // MCP dry-run mode
+            commands::mcp_dry_run::mcp_dry_run_get_config,
+            commands::mcp_dry_run::mcp_dry_run_save_config,
+            commands::mcp_dry_run::mcp_dry_run_preview,
             // File operations (Rust native)
             commands::files::read_file,
+            commands::files::read_file_text,
+            commands::files::read_file_range,
+            commands::files::read_file_lines,
+            commands::files::files_get_line_count,
             commands::files::write_file,
+            commands::files::get_file_stamp,
             commands::files::write_file_base64,
+            commands::files::list_versions,
+            commands::files::restore_version,
             commands::files::delete_file,
+            commands::files::restore_from_trash,
             commands::files::list_directory,
+            commands::files::list_directory_page,
             commands::files::get_file_tree,
             commands::files::create_directory,
             commands::files::rename_path,
+            commands::files::bulk_rename,
+            commands::files::copy_path,
+            commands::files::move_path,
             commands::files::get_file_info,
+            commands::files::get_permissions,
+            commands::files::set_permissions,
+            commands::files::get_directory_size,
             commands::files::watch_directory,
             commands::files::unwatch_directory,
             commands::files::open_in_finder,
             commands::files::open_with_default_app,
+            commands::files::list_recent_files,
+            commands::files::clear_recent_files,
             commands::files::read_file_binary,
             commands::files::get_folder_files,
+            commands::files::files_validate_markdown,
+            commands::files::files_compare_folders,
+            commands::files::diff_files,
+            commands::files::find_duplicates,
+            commands::files::hash_file,
+            commands::files::generate_manifest,
+            commands::files::verify_tree,
+            commands::files::get_frontmatter,
+            commands::files::update_frontmatter,
+            commands::files::knowledge_generate_index,
+            commands::files::knowledge_stop_index_watch,
+            commands::files::knowledge_find_related,
+            commands::files::knowledge_find_near_duplicates,
+            commands::files::get_backlinks,
+            commands::files::get_link_graph,
+            commands::files::knowledge_render_document,
+            commands::files::files_automation_list_rules,
+            commands::files::files_automation_save_rule,
+            commands::files::files_automation_delete_rule,
+            commands::files::files_automation_run_rule,
+            commands::files::files_automation_list_runs,
             // Folder Chat (AI-powered folder Q&A)
             commands::folder_chat::folder_chat_ask,
             // Help Chat (in-app help bot)
@@ -260,6 +397,19 @@ fn main() {
             // Search operations (Rust native)
             commands::search::search_files,
             commands::search::search_content,
+            commands::search::search_content_stream,
+            commands::search::search_content_grouped,
+            commands::search::cancel_search,
+            commands::search::search_frontmatter,
+            commands::search::replace_content,
+            commands::search::search_index_build,
+            commands::search::search_index_status,
+            commands::search::search_index_query,
+            commands::search::search_index_delete,
+            commands::search::search_semantic_index_build,
+            commands::search::search_semantic_index_status,
+            commands::search::search_semantic,
+            commands::search::search_val_artifacts,
             // Auth operations (GitHub OAuth + Microsoft 365)
             commands::auth::github_oauth_start,
             commands::auth::github_get_user,
@@ -282,13 +432,17 @@ fn main() {
             commands::settings::settings_get_path,
             commands::settings::settings_import_from_file,
             commands::settings::settings_export_to_file,
+            commands::connection_test::settings_test_connection,
             // Terminal operations (PTY)
             commands::terminal::terminal_create,
             commands::terminal::terminal_write,
             commands::terminal::terminal_read,
+            commands::terminal::terminal_copy_output,
             commands::terminal::terminal_resize,
             commands::terminal::terminal_close,
             commands::terminal::terminal_list,
+            commands::terminal::terminal_get_resource_usage,
+            commands::terminal::terminal_kill_orphans,
             // Gamma API (presentations)
             commands::tools::gamma::gamma_create_generation,
             commands::tools::gamma::gamma_get_status,
@@ -302,6 +456,12 @@ fn main() {
             commands::tools::nanobanana::nanobanana_parse_config,
             commands::tools::nanobanana::nanobanana_generate_from_file,
             commands::tools::nanobanana::nanobanana_list_models,
+            commands::tools::nanobanana_prompts::nanobanana_list_prompt_templates,
+            commands::tools::nanobanana_prompts::nanobanana_list_style_presets,
+            commands::tools::nanobanana_prompts::nanobanana_save_style_presets,
+            commands::tools::nanobanana_prompts::nanobanana_render_prompt,
+            commands::tools::nanobanana_prompts::nanobanana_generate_from_template,
+            commands::tools::nanobanana_prompts::nanobanana_list_history,
             // Seedance video generation (OpenRouter)
             commands::tools::seedance::seedance_distill_md,
             commands::tools::seedance::seedance_create_config,
@@ -312,6 +472,9 @@ fn main() {
             commands::tools::docgen::generate_order_form_pdf_cmd,
             commands::tools::docgen::generate_proposal_pdf_cmd,
             commands::tools::docgen::html_to_pdf_cmd,
+            commands::tools::docgen_theme::docgen_get_theme,
+            commands::tools::docgen_theme::docgen_save_theme,
+            commands::tools::docgen_theme::docgen_preview_theme,
             // Intercom API (help center publishing)
             commands::tools::intercom::intercom_list_collections,
             commands::tools::intercom::intercom_publish_article,
@@ -327,6 +490,7 @@ fn main() {
             commands::work::work_list_project_updates,
             commands::work::work_create_project_update,
             commands::work::work_delete_project_update,
+            commands::work::work_generate_status_report_pdf,
             // Work Module - Tasks
             commands::work::work_list_tasks,
             commands::work::work_get_task,
@@ -337,6 +501,9 @@ fn main() {
             commands::work::work_remove_task_labels,
             commands::work::work_add_task_assignees,
             commands::work::work_remove_task_assignees,
+            commands::work::work_quick_add,
+            commands::work::work_import_checklist,
+            commands::work::work_sync_checklist,
             // Work Module - Milestones
             commands::work::work_list_milestones,
             commands::work::work_get_milestone,
@@ -352,12 +519,35 @@ fn main() {
             commands::work::work_add_project_to_initiative,
             commands::work::work_remove_project_from_initiative,
             commands::work::work_list_initiative_projects,
+            commands::work::work_get_initiative_summary,
             // Work Module - Labels
             commands::work::work_list_labels,
             commands::work::work_get_label,
             commands::work::work_create_label,
             commands::work::work_update_label,
             commands::work::work_delete_label,
+            // Work Module - WIP Limits
+            commands::work::work_list_wip_limits,
+            commands::work::work_set_wip_limit,
+            commands::work::work_delete_wip_limit,
+            commands::work::work_get_wip_status,
+            commands::work::work_list_calendars,
+            commands::work::work_save_calendar,
+            commands::work::work_list_holidays,
+            commands::work::work_add_holiday,
+            commands::work::work_delete_holiday,
+            commands::work::work_add_business_days,
+            commands::work::work_update_user_working_hours,
+            commands::work::work_get_team_availability,
+            commands::work::work_get_task_due_status,
+            commands::work::work_get_transition_policy,
+            commands::work::work_set_transition_policy,
+            // Work Module - AI Assist
+            commands::work::work_ai_assist_task,
+            // Work Module - Notification Preferences
+            commands::work::work_set_project_notifications,
+            commands::work::work_list_project_notifications,
+            commands::work::work_send_project_digests,
             // Work Module - Users
             commands::work::work_list_users,
             commands::work::work_list_humans,
@@ -366,6 +556,14 @@ fn main() {
             commands::work::work_find_user_by_email,
             commands::work::work_find_user_by_github,
             commands::work::work_find_bot_by_folder,
+            // Work Module - Bot API
+            commands::work::work_bot_api_get_config,
+            commands::work::work_bot_api_save_config,
+            commands::work::work_bot_api_grant,
+            commands::work::work_bot_api_rotate_token,
+            commands::work::work_bot_api_revoke,
+            commands::work::work_bot_api_status,
+            commands::work::work_bot_api_list_activity,
             // Public Data Module
             commands::public_data::classify_job_postings,
             // CRM Module - Companies
@@ -386,6 +584,7 @@ fn main() {
             commands::crm::crm_list_activities,
             commands::crm::crm_log_activity,
             commands::crm::crm_delete_activity,
+            commands::crm::crm_capture_meeting_notes,
             // Apollo Module - Prospect Search & Import
             commands::apollo::apollo_search_people,
             commands::apollo::apollo_enrich_person,
@@ -397,6 +596,34 @@ fn main() {
             commands::crm::crm_link_email,
             commands::crm::crm_unlink_email,
             commands::crm::crm_auto_link_email,
+            commands::crm::crm_list_deal_suggestions,
+            commands::crm::crm_accept_deal_suggestion,
+            commands::crm::crm_dismiss_deal_suggestion,
+            // CRM Module - Privacy
+            commands::crm::crm_export_person_data,
+            commands::crm::crm_forget_person,
+            // CRM Module - Close Reasons
+            commands::crm::crm_list_close_reasons,
+            commands::crm::crm_get_close_reason_report,
+            // CRM Module - Stage requirements
+            commands::crm::crm_list_stage_requirements,
+            commands::crm::crm_set_stage_requirement,
+            commands::crm::crm_delete_stage_requirement,
+            commands::crm::crm_check_deal_stage,
+            // CRM Module - Segments
+            commands::crm::crm_list_segments,
+            commands::crm::crm_create_segment,
+            commands::crm::crm_update_segment,
+            commands::crm::crm_delete_segment,
+            commands::crm::crm_recompute_segment,
+            commands::crm::crm_list_segment_members,
+            // CRM Module - Pipeline history
+            commands::crm::crm_snapshot_pipeline,
+            commands::crm::crm_get_pipeline_history,
+            // CRM Module - HubSpot import
+            commands::crm::crm_hubspot_get_config,
+            commands::crm::crm_hubspot_save_config,
+            commands::crm::crm_hubspot_import,
             // VAL Sync - Config
             commands::val_sync::config::val_sync_load_config,
             commands::val_sync::config::val_sync_save_config,
@@ -417,6 +644,7 @@ fn main() {
             commands::val_sync::sync::val_sync_tables,
             commands::val_sync::sync::val_sync_calc_fields,
             commands::val_sync::sync::val_sync_all,
+            commands::val_sync::query_stats::val_sync_query_stats,
             // VAL Sync - Monitoring operations
             commands::val_sync::monitoring::val_sync_workflow_executions,
             commands::val_sync::monitoring::val_sync_sod_tables_status,
@@ -424,6 +652,7 @@ fn main() {
             // VAL Sync - Error sync operations
             commands::val_sync::errors::val_sync_importer_errors,
             commands::val_sync::errors::val_sync_integration_errors,
+            commands::val_sync::error_explain::val_explain_errors,
             // VAL Sync - Extract operations
             commands::val_sync::extract::val_extract_queries,
             commands::val_sync::extract::val_extract_workflows,
@@ -431,10 +660,23 @@ fn main() {
             commands::val_sync::extract::val_extract_tables,
             commands::val_sync::extract::val_extract_sql,
             commands::val_sync::extract::val_extract_calc_fields,
+            commands::val_sync::integrations::val_extract_integrations,
+            commands::val_sync::changes::val_get_recent_changes,
+            commands::val_sync::workflow_versions::val_list_workflow_versions,
+            commands::val_sync::workflow_versions::val_diff_workflow_versions,
+            commands::val_sync::calc_fields::val_update_calc_field,
             // VAL Sync - Dependencies & Recency
             commands::val_sync::dependencies::val_compute_dependencies,
             commands::val_sync::dependencies::val_sync_get_dependencies,
             commands::val_sync::recency::val_collect_recency,
+            // VAL Sync - Presence Matrix (tables × domains)
+            commands::val_sync::presence::val_generate_presence_matrix,
+            commands::val_sync::context_pack::val_generate_context_pack,
+            commands::val_sync::data_dictionary::val_export_data_dictionary,
+            commands::val_sync::table_compare::val_compare_table_across_domains,
+            // VAL Sync - Health history
+            commands::val_sync::health_history::val_record_health_run,
+            commands::val_sync::health_history::val_get_health_trends,
             // VAL Sync - Claude Runner
             commands::val_sync::claude_runner::claude_run,
             commands::val_sync::claude_runner::claude_run_cancel,
@@ -479,6 +721,18 @@ fn main() {
             commands::val_sync::drive::val_drive_scan_results_save,
             // VAL Sync - SQL execution
             commands::val_sync::sql::val_execute_sql,
+            commands::val_sync::shape::val_shape_result,
+            // VAL Sync - Analysis notebooks
+            commands::val_sync::notebook::val_run_notebook,
+            commands::val_sync::notebook::val_export_notebook,
+            // VAL Sync - Guided fix sessions
+            commands::val_sync::fix_session::val_start_fix_session,
+            commands::val_sync::fix_session::val_fix_session_run_sql,
+            commands::val_sync::fix_session::val_fix_session_rerun_workflow,
+            commands::val_sync::fix_session::val_fix_session_add_note,
+            commands::val_sync::fix_session::val_get_fix_session,
+            commands::val_sync::fix_session::val_list_fix_sessions,
+            commands::val_sync::fix_session::val_complete_fix_session,
             // VAL Sync - SQL generation (AI)
             commands::val_sync::sql_gen::val_generate_sql,
             // VAL Sync - Table Pipeline (generate overview.md)
@@ -493,6 +747,7 @@ fn main() {
             commands::val_sync::table_pipeline::val_run_table_pipeline,
             commands::val_sync::table_pipeline::val_list_domain_tables,
             commands::val_sync::table_pipeline::val_scan_category_library,
+            commands::val_sync::table_docs::val_link_table_docs,
             // GA4 Analytics - Auth
             commands::analytics::auth::ga4_auth_start,
             commands::analytics::auth::ga4_auth_check,
@@ -502,7 +757,22 @@ fn main() {
             commands::analytics::ga4::ga4_check_config,
             commands::analytics::ga4::ga4_fetch_analytics,
             commands::analytics::ga4::ga4_fetch_website_analytics,
+            // Analytics - Retention cohorts
+            commands::analytics::retention::analytics_compute_retention,
+            commands::analytics::archive::analytics_archive_get_config,
+            commands::analytics::archive::analytics_archive_save_config,
+            commands::analytics::archive::analytics_archive_get_status,
+            commands::analytics::archive::analytics_archive_run,
+            commands::analytics::archive::analytics_archive_list,
+            commands::analytics::archive::analytics_archive_restore,
+            commands::analytics::company_usage::analytics_get_company_usage,
+            commands::analytics::company_usage::analytics_list_domain_mappings,
+            commands::analytics::company_usage::analytics_set_domain_mapping,
+            commands::analytics::company_usage::analytics_remove_domain_mapping,
+            commands::analytics::company_usage::analytics_list_unmapped_domains,
             commands::analytics::ga4::ga4_list_dimensions,
+            commands::analytics::realtime::analytics_get_realtime,
+            commands::analytics::report::analytics_export_dashboard_pdf,
             // Settings - MS Graph credentials
             commands::settings::settings_get_ms_graph_credentials,
             commands::settings::settings_get_anthropic_key,
@@ -512,6 +782,18 @@ fn main() {
             commands::settings::settings_import_val_credentials,
             commands::settings::settings_export_val_credentials,
             commands::settings::settings_switch_workspace,
+            // Diagnostics - Support bundle
+            commands::diagnostics::diagnostics_collect,
+            // Links - Entity resolver + deep links
+            commands::links::resolve_entity,
+            commands::links::deep_link_take_pending,
+            // Knowledge backup
+            commands::backup::commands::backup_get_config,
+            commands::backup::commands::backup_save_config,
+            commands::backup::commands::backup_get_status,
+            commands::backup::commands::backup_run_now,
+            commands::backup::commands::backup_list,
+            commands::backup::commands::backup_restore,
             // Outlook - Auth
             commands::outlook::auth::outlook_auth_start,
             commands::outlook::auth::outlook_oauth_code,
@@ -527,8 +809,35 @@ fn main() {
             commands::outlook::commands::outlook_mark_read,
             commands::outlook::commands::outlook_archive_email,
             commands::outlook::commands::outlook_send_email,
+            commands::outlook::mail_merge::outlook_bulk_send,
+            commands::outlook::mail_merge::outlook_bulk_send_status,
+            commands::outlook::mail_merge::outlook_bulk_send_abort,
+            // Outlook - Compose assist
+            commands::outlook::compose::outlook_draft_reply,
             // Outlook - User lookup
             commands::outlook::commands::outlook_lookup_user,
+            // Outlook - Push notifications (Graph webhooks)
+            commands::outlook::push::outlook_push_get_config,
+            commands::outlook::push::outlook_push_save_config,
+            commands::outlook::push::outlook_push_status,
+            // Outlook - Daily digest
+            commands::outlook::digest::outlook_generate_digest,
+            commands::outlook::digest::outlook_digest_get_config,
+            commands::outlook::digest::outlook_digest_save_config,
+            // Outlook - Cache cleanup
+            commands::outlook::cleanup::outlook_run_cleanup,
+            commands::outlook::cleanup::outlook_cleanup_get_policy,
+            commands::outlook::cleanup::outlook_cleanup_save_policy,
+            // Outlook - Sync diagnostics
+            commands::outlook::diagnostics::outlook_get_sync_diagnostics,
+            commands::outlook::diagnostics::outlook_repair_folder,
+            // Outlook - CRM activity auto-capture
+            commands::outlook::activity_capture::outlook_activity_capture_run,
+            commands::outlook::activity_capture::outlook_activity_capture_get_config,
+            commands::outlook::activity_capture::outlook_activity_capture_save_config,
+            // Outlook - Sender stats
+            commands::outlook::senders::outlook_get_sender_stats,
+            commands::outlook::senders::outlook_accept_sender_rule,
             // GitHub Sync
             commands::github_sync::config::github_sync_load_config,
             commands::github_sync::config::github_sync_save_config,
@@ -10,6 +10,12 @@ pub struct FileEntry {
     pub is_directory: bool,
     pub size: u64,
     pub modified: Option<String>,
+    /// Entry is a symlink; is_directory and size describe what it points to
+    #[serde(default)]
+    pub is_symlink: bool,
+    /// Link target as written in the symlink (relative targets stay relative)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
     // Optional metadata for markdown files (from frontmatter)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -17,6 +23,16 @@ pub struct FileEntry {
     pub summary: Option<String>,
 }
 
+/// One page of a sorted directory listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryPage {
+    pub entries: Vec<FileEntry>,
+    pub offset: usize,
+    /// Visible entries in the whole directory
+    pub total: usize,
+    pub has_more: bool,
+}
+
 /// Detailed file information with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileInfo {
@@ -27,6 +43,44 @@ pub struct FileInfo {
     pub created: Option<String>,
     pub modified: Option<String>,
     pub extension: Option<String>,
+    /// Entry is a symlink; is_directory and size describe what it points to
+    #[serde(default)]
+    pub is_symlink: bool,
+    /// Link target as written in the symlink (relative targets stay relative)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Symlink whose target doesn't exist
+    #[serde(default)]
+    pub is_broken_link: bool,
+}
+
+/// Byte range of a file, for paging through files too large to read whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChunk {
+    pub content: String,
+    pub offset: u64,
+    /// Bytes consumed; may be short of the request so UTF-8 characters aren't split
+    pub bytes_read: u64,
+    pub total_size: u64,
+    pub eof: bool,
+}
+
+/// Run of lines from a file, for line-oriented paging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLines {
+    pub lines: Vec<String>,
+    pub start_line: usize,
+    /// Lines longer than the per-line cap are cut short
+    pub truncated_lines: Vec<usize>,
+    pub total_size: u64,
+    pub eof: bool,
+}
+
+/// Line total for a file, so viewers can size a scrollbar before paging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLineCount {
+    pub total_lines: usize,
+    pub total_size: u64,
 }
 
 /// Tree node for recursive file tree
@@ -35,7 +89,15 @@ pub struct TreeNode {
     pub name: String,
     pub path: String,
     pub is_directory: bool,
+    /// None for directories left unexpanded (depth limit, symlink not followed,
+    /// or a symlink looping back into its own ancestry)
     pub children: Option<Vec<TreeNode>>,
+    /// Entry is a symlink; is_directory describes what it points to
+    #[serde(default)]
+    pub is_symlink: bool,
+    /// Link target as written in the symlink (relative targets stay relative)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
 }
 
 /// Search result from file or content search
@@ -48,4 +110,66 @@ pub struct SearchResult {
     pub match_type: String, // "filename" or "content"
     pub preview: Option<String>,
     pub line_number: Option<usize>,
+    /// Relevance score (content search and fuzzy filename search); results are sorted by it descending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// Exact position of the first match (content search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<MatchSpan>,
+}
+
+/// Where a content match sits, for jumping to and highlighting it in an editor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    /// Byte offsets into the file, end exclusive
+    pub start: usize,
+    pub end: usize,
+    /// 1-based column on `line_number`, in characters (Unicode scalar values)
+    pub column: usize,
+    /// 1-based line and column just past the match (differ from the start
+    /// line only for multiline regexes)
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// Highlighted span of a previewed line, in characters (Unicode scalar values)
+/// from the start of `LineMatch::text`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// One matching line within a grouped search result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineMatch {
+    /// 1-based
+    pub line_number: usize,
+    /// The line trimmed and clipped around its first match ("…" marks a cut)
+    pub text: String,
+    pub ranges: Vec<MatchRange>,
+}
+
+/// Content search matches for one file, VS Code search-panel style
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResultGroup {
+    pub name: String,
+    pub path: String,
+    /// Same relevance score as the flat content search; groups are sorted by it descending
+    pub score: f64,
+    /// Every match in the file
+    pub match_count: usize,
+    /// Lines with at least one match
+    pub line_count: usize,
+    /// The first few matching lines, in file order
+    pub lines: Vec<LineMatch>,
+}
+
+/// Grouped content search response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupedSearchResults {
+    pub groups: Vec<SearchResultGroup>,
+    /// Across every matching file, including any cut by max_results
+    pub total_files: usize,
+    pub total_matches: usize,
 }
@@ -6,6 +6,8 @@ import { getCurrentWebview } from "@tauri-apps/api/webview";
 import { Shell } from "./shell/Shell";
 import { useAutoBriefing } from "./hooks/feed";
 import { useTaskAdvisor } from "./hooks/chat";
+import { useDeepLinks } from "./hooks/useDeepLinks";
+import { useMcpToolContextSync } from "./hooks/mcp-tools/useMcpToolContext";
 
 // Core modules (loaded eagerly — most likely first screen)
 import { HomeModule } from "./modules/home/HomeModule";
@@ -306,6 +308,12 @@ export default function App() {
   // Task advisor: bot-mel check-ins via chat on load + every 2 hours
   useTaskAdvisor();
 
+  // tvclient:// links opened from outside the app
+  useDeepLinks();
+
+  // Tell tv-mcp which module is active so tool lists lead with relevant tools
+  useMcpToolContextSync();
+
   // Redirect to first visible module if active module is hidden.
   // Uses `ignoreMode: true` so cross-mode tabs (explicitly opened via
   // shortcut or deep link) aren't force-closed on every mode switch — the
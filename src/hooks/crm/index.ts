@@ -4,3 +4,8 @@ export * from "./useContacts";
 export * from "./useDeals";
 export * from "./useActivities";
 export * from "./usePipeline";
+export * from "./usePipelineHistory";
+export * from "./useCompanyUsage";
+export * from "./useStageRequirements";
+export * from "./useSegments";
+export * from "./useDealSuggestions";
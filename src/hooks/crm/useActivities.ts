@@ -1,6 +1,7 @@
 // CRM Activities CRUD hooks
 
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
 import { supabase } from "../../lib/supabase";
 import type {
   Activity,
@@ -8,6 +9,7 @@ import type {
   ActivityFilters,
 } from "../../lib/crm/types";
 import { crmKeys } from "./keys";
+import { workKeys } from "../work/keys";
 
 export function useActivities(filters?: ActivityFilters) {
   return useQuery({
@@ -120,3 +122,32 @@ export function useDeleteActivity() {
     },
   });
 }
+
+export interface MeetingActionItem {
+  title: string;
+  owner: string | null;
+  due_date: string | null;
+  task_id: string | null;
+  task_identifier: string | null;
+}
+
+export interface MeetingNotesResult {
+  notes_path: string;
+  summary: string;
+  action_items: MeetingActionItem[];
+  activity_id: string;
+}
+
+/** Save deal meeting notes, summarize them, file action items as tasks and log the meeting */
+export function useCaptureMeetingNotes() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { dealId: string; rawNotes: string; title?: string; meetingDate?: string }) =>
+      invoke<MeetingNotesResult>("crm_capture_meeting_notes", args),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: crmKeys.activities() });
+      queryClient.invalidateQueries({ queryKey: workKeys.tasks() });
+    },
+  });
+}
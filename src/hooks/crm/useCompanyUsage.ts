@@ -0,0 +1,92 @@
+// CRM company product usage (analytics page views via domain → company mapping)
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { crmKeys } from "./keys";
+
+export type UsagePeriod = "week" | "month" | "quarter" | "year";
+
+export interface DomainCompanyMapping {
+  id: string;
+  domain: string;
+  company_id: string;
+  created_at: string | null;
+}
+
+export interface CompanyUsage {
+  company_id: string;
+  period: string;
+  since: string;
+  domains: string[];
+  total_views: number;
+  active_users: number;
+  active_days: number;
+  previous_views: number;
+  previous_active_users: number;
+  views_change_pct: number | null;
+  last_seen: string | null;
+  top_pages: { page_path: string; views: number; users: number }[];
+  daily: { date: string; views: number; users: number }[];
+}
+
+export interface UnmappedDomain {
+  domain: string;
+  views: number;
+  last_seen: string;
+}
+
+const usageKeys = {
+  usage: (companyId: string, period: UsagePeriod) =>
+    [...crmKeys.company(companyId), "usage", period] as const,
+  mappings: (companyId: string) => [...crmKeys.company(companyId), "domain-mappings"] as const,
+  unmapped: () => [...crmKeys.all, "unmapped-domains"] as const,
+};
+
+export function useCompanyUsage(companyId: string, period: UsagePeriod = "month") {
+  return useQuery({
+    queryKey: usageKeys.usage(companyId, period),
+    queryFn: () => invoke<CompanyUsage>("analytics_get_company_usage", { companyId, period }),
+    enabled: !!companyId,
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useCompanyDomainMappings(companyId: string) {
+  return useQuery({
+    queryKey: usageKeys.mappings(companyId),
+    queryFn: () => invoke<DomainCompanyMapping[]>("analytics_list_domain_mappings", { companyId }),
+    enabled: !!companyId,
+  });
+}
+
+export function useUnmappedDomains(enabled = true) {
+  return useQuery({
+    queryKey: usageKeys.unmapped(),
+    queryFn: () => invoke<UnmappedDomain[]>("analytics_list_unmapped_domains"),
+    enabled,
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useSetDomainMapping(companyId: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (domain: string) =>
+      invoke<DomainCompanyMapping>("analytics_set_domain_mapping", { domain, companyId }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: crmKeys.company(companyId) });
+      queryClient.invalidateQueries({ queryKey: usageKeys.unmapped() });
+    },
+  });
+}
+
+export function useRemoveDomainMapping(companyId: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (domain: string) => invoke("analytics_remove_domain_mapping", { domain }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: crmKeys.company(companyId) });
+      queryClient.invalidateQueries({ queryKey: usageKeys.unmapped() });
+    },
+  });
+}
@@ -0,0 +1,81 @@
+// CRM deal suggestions — proposed deals detected in inbound email
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { Company } from "../../lib/crm/types";
+import type { Project } from "../../lib/work/types";
+import { crmKeys } from "./keys";
+
+export type DealSignal = "pricing" | "quote" | "demo" | "trial" | "proposal" | "purchase";
+
+export interface DealSuggestion {
+  id: string;
+  email_id: string;
+  company_id: string;
+  contact_id?: string;
+  email_subject?: string;
+  sender_email?: string;
+  signals: DealSignal[];
+  reason?: string;
+  /** 0-1, the model's confidence that this is a new opportunity */
+  confidence: number;
+  suggested_name: string;
+  suggested_stage: string;
+  suggested_value?: number;
+  suggested_currency?: string;
+  suggested_solution?: string;
+  status: "pending" | "accepted" | "dismissed";
+  deal_id?: string;
+  created_at?: string;
+  resolved_at?: string;
+  company?: Company;
+}
+
+/** Overrides applied on accept; unset fields keep the suggested values */
+export interface AcceptDealSuggestionEdits {
+  name?: string;
+  stage?: string;
+  value?: number;
+  currency?: string;
+  solution?: string;
+}
+
+export const dealSuggestionKeys = {
+  all: () => [...crmKeys.all, "deal_suggestions"] as const,
+  list: (status: string, companyId?: string) =>
+    [...dealSuggestionKeys.all(), status, companyId ?? "all"] as const,
+};
+
+export function useDealSuggestions(status: DealSuggestion["status"] = "pending", companyId?: string) {
+  return useQuery({
+    queryKey: dealSuggestionKeys.list(status, companyId),
+    queryFn: () =>
+      invoke<DealSuggestion[]>("crm_list_deal_suggestions", {
+        status,
+        companyId: companyId ?? null,
+      }),
+    staleTime: 1000 * 60,
+  });
+}
+
+export function useAcceptDealSuggestion() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ suggestionId, edits }: { suggestionId: string; edits?: AcceptDealSuggestionEdits }) =>
+      invoke<Project>("crm_accept_deal_suggestion", { suggestionId, edits: edits ?? null }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: dealSuggestionKeys.all() });
+      queryClient.invalidateQueries({ queryKey: crmKeys.deals() });
+      queryClient.invalidateQueries({ queryKey: crmKeys.pipeline() });
+      queryClient.invalidateQueries({ queryKey: crmKeys.activities() });
+    },
+  });
+}
+
+export function useDismissDealSuggestion() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (suggestionId: string) => invoke<void>("crm_dismiss_deal_suggestion", { suggestionId }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: dealSuggestionKeys.all() }),
+  });
+}
@@ -9,6 +9,7 @@ import type {
   DealWithTaskInfo,
 } from "../../lib/crm/types";
 import { crmKeys } from "./keys";
+import { checkDealStage, type StageValidationError } from "./useStageRequirements";
 
 // Deal projected from unified projects table
 export interface DealFromProject {
@@ -281,6 +282,19 @@ export function useUpdateDeal() {
       if (updates.stale_snoozed_until !== undefined) updateData.deal_stale_snoozed_until = updates.stale_snoozed_until;
       if (updates.stage_changed_at !== undefined) updateData.deal_stage_changed_at = updates.stage_changed_at;
 
+      // Gated stages need their required fields filled before the move
+      if (oldProject && updates.stage && oldProject.deal_stage !== updates.stage) {
+        const check = await checkDealStage(id, updates.stage, updateData);
+        if (check.missing_fields.length > 0) {
+          const error: StageValidationError = {
+            code: "validation",
+            message: `Can't move deal to ${updates.stage} without: ${check.missing_fields.join(", ")}`,
+            fields: check.missing_fields,
+          };
+          throw error;
+        }
+      }
+
       // Reset deal_stage_changed_at if stage is changing
       if (oldProject && updates.stage && oldProject.deal_stage !== updates.stage) {
         updateData.deal_stage_changed_at = new Date().toISOString();
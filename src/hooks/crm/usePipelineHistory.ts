@@ -0,0 +1,63 @@
+// Pipeline history (daily per-stage snapshots in crm_pipeline_snapshots)
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { crmKeys } from "./keys";
+
+export type PipelineHistoryGranularity = "day" | "week" | "month";
+
+export interface PipelineSnapshotRow {
+  snapshot_date: string;
+  stage: string;
+  deal_count: number;
+  total_value: number;
+  weighted_value: number;
+  probability: number;
+}
+
+export interface PipelineHistoryPoint {
+  /** Bucket start: the day, the Monday of the week, or the 1st of the month */
+  period_start: string;
+  /** Day of the snapshot used for this bucket (the latest in it) */
+  snapshot_date: string;
+  by_stage: PipelineSnapshotRow[];
+  deal_count: number;
+  total_value: number;
+  weighted_value: number;
+}
+
+export interface PipelineHistory {
+  period: string;
+  granularity: PipelineHistoryGranularity;
+  start: string | null;
+  end: string | null;
+  points: PipelineHistoryPoint[];
+}
+
+const pipelineHistoryKeys = {
+  all: () => [...crmKeys.pipeline(), "history"] as const,
+  history: (period: string, granularity: PipelineHistoryGranularity) =>
+    [...pipelineHistoryKeys.all(), period, granularity] as const,
+};
+
+/** period: all, 90d, ytd, 2026, 2026-Q3 or 2026-07 */
+export function usePipelineHistory(
+  period: string = "90d",
+  granularity: PipelineHistoryGranularity = "day"
+) {
+  return useQuery({
+    queryKey: pipelineHistoryKeys.history(period, granularity),
+    queryFn: () =>
+      invoke<PipelineHistory>("crm_get_pipeline_history", { period, granularity }),
+    staleTime: 1000 * 60 * 30,
+  });
+}
+
+/** Record today's snapshot now instead of waiting for the background job */
+export function useSnapshotPipeline() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => invoke<PipelineSnapshotRow[]>("crm_snapshot_pipeline"),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: pipelineHistoryKeys.all() }),
+  });
+}
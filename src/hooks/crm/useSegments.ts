@@ -0,0 +1,126 @@
+// CRM segments — saved ICP filters over companies/contacts with materialized members
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { Company, Contact } from "../../lib/crm/types";
+import { crmKeys } from "./keys";
+
+export type SegmentOp =
+  | "eq"
+  | "neq"
+  | "in"
+  | "not_in"
+  | "contains"
+  | "gt"
+  | "gte"
+  | "lt"
+  | "lte"
+  | "exists"
+  | "not_exists";
+
+/**
+ * `field` is a dotted path: company columns (`industry`, `employee_count`),
+ * `engagement.activities_90d` / `engagement.last_activity_days`, and
+ * `usage.views_30d` / `usage.active_users_30d` / `usage.last_seen_days`.
+ * Contact segments reach company fields via `company.<field>`.
+ */
+export interface SegmentCondition {
+  field: string;
+  op: SegmentOp;
+  value?: unknown;
+  weight?: number;
+}
+
+export interface SegmentFilter {
+  conditions: SegmentCondition[];
+  /** 0-100, default 100 (every condition must match) */
+  min_score?: number;
+}
+
+export interface Segment {
+  id: string;
+  name: string;
+  description?: string;
+  entity_type: "company" | "contact";
+  filter: SegmentFilter;
+  refresh_interval_hours: number;
+  member_count: number;
+  last_computed_at?: string;
+  created_at?: string;
+  updated_at?: string;
+}
+
+export interface SegmentInput {
+  name: string;
+  description?: string;
+  entity_type: "company" | "contact";
+  filter: SegmentFilter;
+  refresh_interval_hours?: number;
+}
+
+export interface SegmentMember {
+  id: string;
+  segment_id: string;
+  company_id?: string;
+  contact_id?: string;
+  score: number;
+  computed_at: string;
+  company?: Company;
+  contact?: Contact;
+}
+
+export const segmentKeys = {
+  all: () => [...crmKeys.all, "segments"] as const,
+  members: (segmentId: string) => [...segmentKeys.all(), segmentId, "members"] as const,
+};
+
+export function useSegments() {
+  return useQuery({
+    queryKey: segmentKeys.all(),
+    queryFn: () => invoke<Segment[]>("crm_list_segments"),
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+/** Members sorted by ICP score, highest first */
+export function useSegmentMembers(segmentId: string | null, limit?: number) {
+  return useQuery({
+    queryKey: segmentKeys.members(segmentId ?? ""),
+    queryFn: () => invoke<SegmentMember[]>("crm_list_segment_members", { segmentId, limit }),
+    enabled: !!segmentId,
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+export function useCreateSegment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (data: SegmentInput) => invoke<Segment>("crm_create_segment", { data }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: segmentKeys.all() }),
+  });
+}
+
+export function useUpdateSegment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ segmentId, data }: { segmentId: string; data: SegmentInput }) =>
+      invoke<Segment>("crm_update_segment", { segmentId, data }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: segmentKeys.all() }),
+  });
+}
+
+export function useDeleteSegment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (segmentId: string) => invoke<void>("crm_delete_segment", { segmentId }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: segmentKeys.all() }),
+  });
+}
+
+export function useRecomputeSegment() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (segmentId: string) => invoke<Segment>("crm_recompute_segment", { segmentId }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: segmentKeys.all() }),
+  });
+}
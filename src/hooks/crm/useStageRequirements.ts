@@ -0,0 +1,62 @@
+// Deal stage-gated required fields (rules in deal_stage_requirements)
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { crmKeys } from "./keys";
+
+export interface DealStageRequirement {
+  stage: string;
+  required_fields: string[];
+  updated_at?: string;
+}
+
+export interface DealStageCheck {
+  stage: string;
+  missing_fields: string[];
+}
+
+/** Error thrown by stage-gated updates; `fields` are the empty deal columns */
+export interface StageValidationError {
+  code: "validation";
+  message: string;
+  fields: string[];
+}
+
+export function isStageValidationError(error: unknown): error is StageValidationError {
+  return (error as StageValidationError | null)?.code === "validation";
+}
+
+const stageRequirementKeys = {
+  all: () => [...crmKeys.all, "stage-requirements"] as const,
+};
+
+export function useStageRequirements() {
+  return useQuery({
+    queryKey: stageRequirementKeys.all(),
+    queryFn: () => invoke<DealStageRequirement[]>("crm_list_stage_requirements"),
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+/** Save a stage's required fields; an empty list removes the gate */
+export function useSetStageRequirement() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ stage, requiredFields }: { stage: string; requiredFields: string[] }) =>
+      invoke<DealStageRequirement | null>("crm_set_stage_requirement", { stage, requiredFields }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: stageRequirementKeys.all() }),
+  });
+}
+
+export function useDeleteStageRequirement() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (stage: string) => invoke<void>("crm_delete_stage_requirement", { stage }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: stageRequirementKeys.all() }),
+  });
+}
+
+/** Which required fields a deal is missing for `stage`, given pending column changes */
+export function checkDealStage(dealId: string, stage: string, updates?: Record<string, unknown>) {
+  return invoke<DealStageCheck>("crm_check_deal_stage", { dealId, stage, updates });
+}
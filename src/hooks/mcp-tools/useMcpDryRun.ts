@@ -0,0 +1,56 @@
+// MCP dry-run mode — while on, mutation tools (create/update/delete…) called
+// through the REST bridge return a preview of what would change instead of
+// persisting. Agent sessions call tv-mcp directly and are not covered.
+// Stored in ~/.tv-mcp/dry_run.json.
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { mcpToolKeys } from "./keys";
+
+export type McpDryRunModule = "work" | "crm" | "val";
+
+export interface McpDryRunConfig {
+  enabled: boolean;
+  modules: McpDryRunModule[];
+  updatedAt: string | null;
+}
+
+export interface McpDryRunPreview {
+  tool: string;
+  /** "unknown" for mutations outside Work/CRM/VAL, previewed whenever dry-run is on */
+  module: McpDryRunModule | "unknown";
+  action: string;
+  entity: string;
+  valid: boolean;
+  errors: string[];
+  payload: unknown;
+  /** "key=value" for each id in the arguments */
+  affectedIds: string[];
+  summary: string;
+}
+
+const dryRunKey = [...mcpToolKeys.all, "dry-run"] as const;
+
+export function useMcpDryRunConfig() {
+  return useQuery({
+    queryKey: dryRunKey,
+    queryFn: () => invoke<McpDryRunConfig>("mcp_dry_run_get_config"),
+  });
+}
+
+export function useSaveMcpDryRunConfig() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (config: McpDryRunConfig) =>
+      invoke<McpDryRunConfig>("mcp_dry_run_save_config", { config }),
+    onSuccess: (config) => queryClient.setQueryData(dryRunKey, config),
+  });
+}
+
+/** Describe what a tool call would change, whether or not dry-run is on */
+export function usePreviewMcpCall() {
+  return useMutation({
+    mutationFn: ({ tool, args }: { tool: string; args: Record<string, unknown> }) =>
+      invoke<McpDryRunPreview>("mcp_dry_run_preview", { tool, arguments: args }),
+  });
+}
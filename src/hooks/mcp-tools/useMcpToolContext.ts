@@ -0,0 +1,80 @@
+// MCP tool context — reports the active module (and selected entity) to the
+// backend so tv-mcp and the REST bridge can rank tools for what the user is
+// doing. Re-sent when this window regains focus so the focused window wins.
+
+import { useEffect } from "react";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { useAppStore } from "../../stores/appStore";
+import { useSelectedEntityStore } from "../../stores/selectedEntityStore";
+import { mcpToolKeys } from "./keys";
+
+export interface McpActiveContext {
+  module: string;
+  window: string | null;
+  entityId: string | null;
+  updatedAt: string;
+}
+
+export interface McpContextProfile {
+  module: string;
+  keywords: string[];
+  always: string[];
+  trim: boolean;
+  maxTools: number | null;
+}
+
+export interface McpContextTools {
+  module: string | null;
+  prioritized: string[];
+  trimmed: number;
+  tools: { name: string; description?: string | null; inputSchema: unknown }[];
+}
+
+/** Keep the backend's active MCP context in step with this window. Mount once in App. */
+export function useMcpToolContextSync() {
+  const activeModule = useAppStore((s) => s.activeModule);
+  const entityId = useSelectedEntityStore((s) => s.current?.id ?? null);
+
+  useEffect(() => {
+    const window = getCurrentWindow();
+    const report = () =>
+      invoke("mcp_set_active_context", { module: activeModule, window: window.label, entityId }).catch(() => {});
+    report();
+
+    let unlisten: (() => void) | undefined;
+    window
+      .onFocusChanged(({ payload: focused }) => {
+        if (focused) report();
+      })
+      .then((fn) => {
+        unlisten = fn;
+      });
+    return () => unlisten?.();
+  }, [activeModule, entityId]);
+}
+
+export function useMcpContextProfiles() {
+  return useQuery({
+    queryKey: [...mcpToolKeys.all, "context-profiles"],
+    queryFn: () => invoke<McpContextProfile[]>("mcp_context_get_profiles"),
+  });
+}
+
+export function useSaveMcpContextProfiles() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (profiles: McpContextProfile[]) =>
+      invoke<McpContextProfile[]>("mcp_context_save_profiles", { profiles }),
+    onSuccess: () => qc.invalidateQueries({ queryKey: mcpToolKeys.all }),
+  });
+}
+
+/** Preview the ranked tool list for a module (default: the active context) */
+export function useMcpToolsForContext(module?: string) {
+  return useQuery({
+    queryKey: [...mcpToolKeys.all, "for-context", module ?? "active"],
+    queryFn: () => invoke<McpContextTools>("mcp_list_tools_for_context", { module }),
+  });
+}
@@ -0,0 +1,140 @@
+// React Query hooks for analytics data retention (rollups + archived months)
+// All data comes from Rust backend via Tauri IPC
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+
+// ============================================================================
+// Types (match Rust types)
+// ============================================================================
+
+export interface ArchiveDestination {
+  kind: "local" | "s3";
+  /** Local folder; empty = <knowledge>/_archive/analytics */
+  path: string | null;
+  bucket: string | null;
+  prefix: string | null;
+  endpoint_url: string | null;
+  region: string | null;
+}
+
+export interface AnalyticsArchiveConfig {
+  enabled: boolean;
+  keepMonths: number;
+  rollupPeriod: "week" | "month";
+  destination: ArchiveDestination;
+}
+
+export interface AnalyticsArchiveStatus {
+  lastRunAt: string | null;
+  lastError: string | null;
+}
+
+/** Row from the analytics_archives table */
+export interface AnalyticsArchive {
+  id: string;
+  month: string;
+  file_name: string;
+  location: string;
+  destination_kind: "local" | "s3";
+  row_count: number;
+  total_views: number;
+  size_bytes: number;
+  archived_at: string;
+  restored_at: string | null;
+}
+
+export interface ArchivedMonth {
+  month: string;
+  rowCount: number;
+  totalViews: number;
+  rollupRows: number;
+  location: string | null;
+}
+
+export interface ArchiveRunResult {
+  cutoff: string;
+  dryRun: boolean;
+  months: ArchivedMonth[];
+}
+
+export interface ArchiveRestoreResult {
+  month: string;
+  rowsRestored: number;
+}
+
+// ============================================================================
+// Query keys
+// ============================================================================
+
+export const analyticsArchiveKeys = {
+  all: ["analytics-archive"] as const,
+  config: () => ["analytics-archive", "config"] as const,
+  status: () => ["analytics-archive", "status"] as const,
+  list: () => ["analytics-archive", "list"] as const,
+};
+
+// ============================================================================
+// Hooks
+// ============================================================================
+
+export function useAnalyticsArchiveConfig() {
+  return useQuery({
+    queryKey: analyticsArchiveKeys.config(),
+    queryFn: () => invoke<AnalyticsArchiveConfig>("analytics_archive_get_config"),
+  });
+}
+
+export function useSaveAnalyticsArchiveConfig() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (config: AnalyticsArchiveConfig) =>
+      invoke<AnalyticsArchiveConfig>("analytics_archive_save_config", { config }),
+    onSuccess: (config) => {
+      queryClient.setQueryData(analyticsArchiveKeys.config(), config);
+    },
+  });
+}
+
+export function useAnalyticsArchiveStatus() {
+  return useQuery({
+    queryKey: analyticsArchiveKeys.status(),
+    queryFn: () => invoke<AnalyticsArchiveStatus>("analytics_archive_get_status"),
+  });
+}
+
+export function useAnalyticsArchives() {
+  return useQuery({
+    queryKey: analyticsArchiveKeys.list(),
+    queryFn: () => invoke<AnalyticsArchive[]>("analytics_archive_list"),
+  });
+}
+
+/** Archive every whole month older than keepMonths. dryRun only reports. */
+export function useRunAnalyticsArchive() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (dryRun: boolean = false) =>
+      invoke<ArchiveRunResult>("analytics_archive_run", { dryRun }),
+    onSuccess: (result) => {
+      if (!result.dryRun) {
+        queryClient.invalidateQueries({ queryKey: analyticsArchiveKeys.all });
+      }
+    },
+  });
+}
+
+/** Re-import an archived month (YYYY-MM-01) into analytics_page_views */
+export function useRestoreAnalyticsArchive() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (month: string) =>
+      invoke<ArchiveRestoreResult>("analytics_archive_restore", { month }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: analyticsArchiveKeys.list() });
+    },
+  });
+}
@@ -0,0 +1,59 @@
+// src/hooks/useDeepLinks.ts
+// Routes tvclient:// links resolved by the backend to the module and entity they point at
+
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import type { ModuleId } from "../stores/appStore";
+import { useModuleTabStore } from "../stores/moduleTabStore";
+import { useNotificationNavStore } from "../stores/notificationNavStore";
+import { toast } from "../stores/toastStore";
+
+export interface ResolvedEntity {
+  kind: string;
+  id: string;
+  title: string;
+  subtitle: string | null;
+  module: string;
+  nav_entity_type: string;
+  nav_entity_id: string;
+  link: string;
+}
+
+export function openResolvedEntity(entity: ResolvedEntity) {
+  useModuleTabStore.getState().openTab(entity.module as ModuleId);
+  useNotificationNavStore.getState().setTarget(entity.nav_entity_type, entity.nav_entity_id, false);
+}
+
+export function useDeepLinks() {
+  useEffect(() => {
+    let cancelled = false;
+    const unlisteners: (() => void)[] = [];
+
+    // Link that launched the app arrives before the webview is listening
+    invoke<ResolvedEntity | null>("deep_link_take_pending")
+      .then((entity) => { if (!cancelled && entity) openResolvedEntity(entity); })
+      .catch(() => {});
+
+    // Targeted at the window showing the link's module, so listen on this window only
+    getCurrentWebviewWindow().listen<ResolvedEntity>("deep-link:open", (event) => {
+      if (cancelled) return;
+      // Delivered live, so drop the cold-start copy
+      invoke("deep_link_take_pending").catch(() => {});
+      openResolvedEntity(event.payload);
+    }).then((unlisten) => { if (cancelled) unlisten(); else unlisteners.push(unlisten); });
+
+    if (getCurrentWebviewWindow().label === "main") {
+      listen<{ url: string; error: string }>("deep-link:error", (event) => {
+        if (cancelled) return;
+        toast.error(`Couldn't open ${event.payload.url}: ${event.payload.error}`);
+      }).then((unlisten) => { if (cancelled) unlisten(); else unlisteners.push(unlisten); });
+    }
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((u) => u());
+    };
+  }, []);
+}
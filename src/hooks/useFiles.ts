@@ -13,6 +13,10 @@ export interface FileEntry {
   is_directory: boolean;
   size: number;
   modified: string | null;
+  /** Symlink; is_directory/size describe what it points to */
+  is_symlink?: boolean;
+  /** Link target as written in the symlink */
+  target?: string;
 }
 
 export interface FileInfo {
@@ -23,13 +27,42 @@ export interface FileInfo {
   created: string | null;
   modified: string | null;
   extension: string | null;
+  /** Symlink; is_directory/size describe what it points to */
+  is_symlink?: boolean;
+  /** Link target as written in the symlink */
+  target?: string;
+  is_broken_link?: boolean;
 }
 
 export interface TreeNode {
   name: string;
   path: string;
   is_directory: boolean;
+  /** null = not expanded (depth limit, unfollowed or looping symlink) */
   children: TreeNode[] | null;
+  is_symlink?: boolean;
+  target?: string;
+}
+
+export interface FileChunk {
+  content: string;
+  offset: number;
+  bytes_read: number;
+  total_size: number;
+  eof: boolean;
+}
+
+export interface FileLines {
+  lines: string[];
+  start_line: number;
+  truncated_lines: number[];
+  total_size: number;
+  eof: boolean;
+}
+
+export interface FileLineCount {
+  total_lines: number;
+  total_size: number;
 }
 
 // Generic invoke wrapper with error handling
@@ -54,16 +87,125 @@ export function useReadFile(path: string | undefined) {
   });
 }
 
-// Write file content
+export interface FileStamp {
+  modified_ms: number | null;
+  /** SHA-256 of the raw bytes */
+  hash: string;
+  size: number;
+}
+
+export interface DecodedText {
+  content: string;
+  encoding: string;
+  had_bom: boolean;
+  is_binary: boolean;
+  lossy: boolean;
+  /** Pass back to useWriteFile as `expected` to detect concurrent edits */
+  stamp: FileStamp | null;
+}
+
+/** Thrown by write_file when the file changed since it was read */
+export interface WriteConflictError {
+  code: "conflict";
+  message: string;
+  current_content: string;
+  current_mtime: number | null;
+  current_hash: string | null;
+}
+
+export function isWriteConflictError(error: unknown): error is WriteConflictError {
+  return (error as WriteConflictError | null)?.code === "conflict";
+}
+
+// Read file content with encoding detection (latin-1, UTF-16, binary)
+export function useReadFileText(path: string | undefined) {
+  return useQuery({
+    queryKey: ["fileText", path],
+    queryFn: () => tauriInvoke<DecodedText>("read_file_text", { path }),
+    enabled: !!path,
+  });
+}
+
+// Read a byte range of a large file (continue from offset + bytes_read)
+export function useReadFileRange(path: string | undefined, offset: number, len: number) {
+  return useQuery({
+    queryKey: ["file", path, "range", offset, len],
+    queryFn: () => tauriInvoke<FileChunk>("read_file_range", { path, offset, len }),
+    enabled: !!path,
+  });
+}
+
+// Read a page of lines from a large file (start_line is zero-based)
+export function useReadFileLines(path: string | undefined, startLine: number, count: number) {
+  return useQuery({
+    queryKey: ["file", path, "lines", startLine, count],
+    queryFn: () => tauriInvoke<FileLines>("read_file_lines", { path, startLine, count }),
+    enabled: !!path,
+  });
+}
+
+// Count lines in a large file (also indexes it so deep line pages load fast)
+export function useFileLineCount(path: string | undefined) {
+  return useQuery({
+    queryKey: ["file", path, "lineCount"],
+    queryFn: () => tauriInvoke<FileLineCount>("files_get_line_count", { path }),
+    enabled: !!path,
+  });
+}
+
+export interface DiffLine {
+  kind: "context" | "add" | "remove";
+  old_line: number | null;
+  new_line: number | null;
+  content: string;
+}
+
+export interface DiffHunk {
+  old_start: number;
+  old_lines: number;
+  new_start: number;
+  new_lines: number;
+  header: string;
+  lines: DiffLine[];
+}
+
+export interface FileDiff {
+  path_a: string;
+  path_b: string;
+  identical: boolean;
+  additions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+}
+
+// Structured diff between two text files (A → B)
+export function useFileDiff(pathA: string | undefined, pathB: string | undefined, context?: number) {
+  return useQuery({
+    queryKey: ["fileDiff", pathA, pathB, context],
+    queryFn: () => tauriInvoke<FileDiff>("diff_files", { pathA, pathB, context }),
+    enabled: !!pathA && !!pathB,
+  });
+}
+
+// Write file content (atomically). With `expected` (the stamp the content was
+// read with), a concurrent change elsewhere fails with a WriteConflictError
+// instead of being overwritten.
 export function useWriteFile() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ path, content }: { path: string; content: string }) =>
-      tauriInvoke<void>("write_file", { path, content }),
+    mutationFn: ({ path, content, expected }: { path: string; content: string; expected?: FileStamp | null }) =>
+      tauriInvoke<FileStamp>("write_file", {
+        path,
+        content,
+        expectedMtime: expected?.modified_ms ?? null,
+        expectedHash: expected?.hash ?? null,
+      }),
     onSuccess: (_, { path }) => {
       // Invalidate the file cache
       queryClient.invalidateQueries({ queryKey: ["file", path] });
+      queryClient.invalidateQueries({ queryKey: ["fileText", path] });
+      queryClient.invalidateQueries({ queryKey: ["fileVersions", path] });
       // Invalidate directory listing
       const dir = path.substring(0, path.lastIndexOf("/"));
       queryClient.invalidateQueries({ queryKey: ["directory", dir] });
@@ -71,7 +213,61 @@ export function useWriteFile() {
   });
 }
 
-// Delete file or directory
+export interface FileVersion {
+  id: string;
+  snapshot_path: string;
+  size: number;
+  created_at: string;
+}
+
+// Snapshots taken before each write (when file versioning is enabled), newest first
+export function useFileVersions(path: string | undefined) {
+  return useQuery({
+    queryKey: ["fileVersions", path],
+    queryFn: () => tauriInvoke<FileVersion[]>("list_versions", { path }),
+    enabled: !!path,
+  });
+}
+
+export function useRestoreVersion() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ path, versionId }: { path: string; versionId: string }) =>
+      tauriInvoke<void>("restore_version", { path, versionId }),
+    onSuccess: (_, { path }) => {
+      queryClient.invalidateQueries({ queryKey: ["file", path] });
+      queryClient.invalidateQueries({ queryKey: ["fileVersions", path] });
+    },
+  });
+}
+
+// Parsed YAML frontmatter of a markdown file ({} when it has none)
+export function useFrontmatter(path: string | undefined) {
+  return useQuery({
+    queryKey: ["frontmatter", path],
+    queryFn: () => tauriInvoke<Record<string, unknown>>("get_frontmatter", { path }),
+    enabled: !!path,
+  });
+}
+
+// Patch frontmatter fields (null removes a key); body and key order are kept
+export function useUpdateFrontmatter() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ path, patch }: { path: string; patch: Record<string, unknown> }) =>
+      tauriInvoke<Record<string, unknown>>("update_frontmatter", { path, patch }),
+    onSuccess: (frontmatter, { path }) => {
+      queryClient.setQueryData(["frontmatter", path], frontmatter);
+      queryClient.invalidateQueries({ queryKey: ["file", path] });
+      const dir = path.substring(0, path.lastIndexOf("/"));
+      queryClient.invalidateQueries({ queryKey: ["directory", dir] });
+    },
+  });
+}
+
+// Delete file or directory (moved to the OS trash unless permanent)
 export function useDeleteFile() {
   const queryClient = useQueryClient();
 
@@ -86,6 +282,20 @@ export function useDeleteFile() {
   });
 }
 
+// Put a trashed file or directory back where it was
+export function useRestoreFromTrash() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (path: string) => tauriInvoke<string>("restore_from_trash", { path }),
+    onSuccess: (_, path) => {
+      const dir = path.substring(0, path.lastIndexOf("/"));
+      queryClient.invalidateQueries({ queryKey: ["directory", dir] });
+      queryClient.invalidateQueries({ queryKey: ["fileTree"] });
+    },
+  });
+}
+
 // List directory contents
 export function useListDirectory(path: string | undefined) {
   return useQuery({
@@ -95,14 +305,61 @@ export function useListDirectory(path: string | undefined) {
   });
 }
 
-// Get file tree (recursive)
-export function useFileTree(path?: string, maxDepth?: number) {
+export interface DirectoryPage {
+  entries: FileEntry[];
+  offset: number;
+  total: number;
+  has_more: boolean;
+}
+
+export interface DirectoryPageOptions {
+  offset?: number;
+  limit?: number;
+  sortBy?: "name" | "size" | "modified";
+  sortDesc?: boolean;
+  /** Keep folders above files (default true) */
+  dirsFirst?: boolean;
+}
+
+// One sorted page of a large directory (for virtualized listings)
+export function useDirectoryPage(path: string | undefined, options: DirectoryPageOptions = {}) {
+  return useQuery({
+    // Shares the "directory" prefix so file mutations invalidate pages too
+    queryKey: ["directory", path, "page", options],
+    queryFn: () =>
+      tauriInvoke<DirectoryPage>("list_directory_page", {
+        path,
+        offset: options.offset ?? 0,
+        limit: options.limit ?? 200,
+        sortBy: options.sortBy ?? "name",
+        sortDesc: options.sortDesc ?? false,
+        dirsFirst: options.dirsFirst ?? true,
+      }),
+    enabled: !!path,
+    placeholderData: (previous) => previous,
+  });
+}
+
+export interface FileTreeOptions {
+  /** Apply .gitignore rules (default true) */
+  respectGitignore?: boolean;
+  /** Extra globs to leave out, e.g. "dist/" or "*.log" */
+  ignore?: string[];
+  /** Expand symlinked folders (default true); loops are never expanded */
+  followSymlinks?: boolean;
+}
+
+// Get file tree (recursive). Hidden files and node_modules are always skipped.
+export function useFileTree(path?: string, maxDepth?: number, options?: FileTreeOptions) {
   return useQuery({
-    queryKey: ["fileTree", path, maxDepth],
+    queryKey: ["fileTree", path, maxDepth, options],
     queryFn: () =>
       tauriInvoke<TreeNode>("get_file_tree", {
         path: path || null,
-        max_depth: maxDepth || 3,
+        maxDepth: maxDepth || 3,
+        respectGitignore: options?.respectGitignore ?? true,
+        ignore: options?.ignore ?? null,
+        followSymlinks: options?.followSymlinks ?? true,
       }),
   });
 }
@@ -116,6 +373,40 @@ export function useFileInfo(path: string | undefined) {
   });
 }
 
+export interface FilePermissions {
+  path: string;
+  /** Permission bits (e.g. 493 = 0o755); null on Windows */
+  mode: number | null;
+  /** e.g. "755" */
+  octal: string | null;
+  /** e.g. "rwxr-xr-x" */
+  symbolic: string | null;
+  readonly: boolean;
+  is_executable: boolean;
+}
+
+export function useFilePermissions(path: string | undefined) {
+  return useQuery({
+    queryKey: ["filePermissions", path],
+    queryFn: () => tauriInvoke<FilePermissions>("get_permissions", { path }),
+    enabled: !!path,
+  });
+}
+
+// Change permissions: mode is chmod-style ("755", "+x", "go-w"; Unix only),
+// readonly works on every platform
+export function useSetFilePermissions() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ path, mode, readonly }: { path: string; mode?: string; readonly?: boolean }) =>
+      tauriInvoke<FilePermissions>("set_permissions", { path, mode, readonly }),
+    onSuccess: (perms) => {
+      queryClient.setQueryData(["filePermissions", perms.path], perms);
+    },
+  });
+}
+
 // Create directory
 export function useCreateDirectory() {
   const queryClient = useQueryClient();
@@ -148,6 +439,375 @@ export function useRenamePath() {
   });
 }
 
+export interface RenamePattern {
+  /** Regex to find; replace may use $1 / ${name} */
+  find?: string;
+  replace?: string;
+  case_insensitive?: boolean;
+  prefix?: string;
+  suffix?: string;
+  /** {name} = stem after earlier steps, {n} = sequence number */
+  sequence?: { template?: string; start?: number; step?: number; padding?: number };
+  include_extension?: boolean;
+}
+
+export interface RenamePreview {
+  from: string;
+  to: string;
+  status: "rename" | "unchanged" | "conflict" | "invalid";
+  message: string | null;
+}
+
+export interface BulkRenameResult {
+  dry_run: boolean;
+  renamed: number;
+  items: RenamePreview[];
+}
+
+// Preview a bulk rename without touching anything
+export function useBulkRenamePreview(paths: string[], pattern: RenamePattern, enabled = true) {
+  return useQuery({
+    queryKey: ["bulkRenamePreview", paths, pattern],
+    queryFn: () => tauriInvoke<BulkRenameResult>("bulk_rename", { paths, pattern, dryRun: true }),
+    enabled: enabled && paths.length > 0,
+  });
+}
+
+// Apply a bulk rename (all or nothing — rolled back on failure)
+export function useBulkRename() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ paths, pattern }: { paths: string[]; pattern: RenamePattern }) =>
+      tauriInvoke<BulkRenameResult>("bulk_rename", { paths, pattern, dryRun: false }),
+    onSuccess: (_, { paths }) => {
+      for (const path of paths) {
+        queryClient.invalidateQueries({ queryKey: ["file", path] });
+        const dir = path.substring(0, path.lastIndexOf("/"));
+        queryClient.invalidateQueries({ queryKey: ["directory", dir] });
+      }
+      queryClient.invalidateQueries({ queryKey: ["fileTree"] });
+      queryClient.invalidateQueries({ queryKey: ["folderChildren"] });
+    },
+  });
+}
+
+export interface DuplicateSet {
+  hash: string;
+  size: number;
+  paths: string[];
+  wasted_bytes: number;
+}
+
+export interface DuplicateReport {
+  root: string;
+  files_scanned: number;
+  sets: DuplicateSet[];
+  total_wasted_bytes: number;
+}
+
+// Scan a folder for identical files (on demand — hashing can take a while)
+export function useFindDuplicates(root: string | undefined, enabled = true) {
+  return useQuery({
+    queryKey: ["duplicates", root],
+    queryFn: () => tauriInvoke<DuplicateReport>("find_duplicates", { root }),
+    enabled: enabled && !!root,
+    staleTime: Infinity,
+  });
+}
+
+export type HashAlgorithm = "sha256" | "blake3";
+
+export interface FileHash {
+  path: string;
+  algorithm: HashAlgorithm;
+  hash: string;
+  size: number;
+}
+
+export interface TreeManifest {
+  algorithm: HashAlgorithm;
+  generated_at: string;
+  files: Record<string, { hash: string; size: number }>;
+}
+
+export interface TreeVerification {
+  root: string;
+  ok: boolean;
+  matched: number;
+  modified: string[];
+  missing: string[];
+  added: string[];
+}
+
+// Checksum a single file
+export function useFileHash(path: string | undefined, algorithm: HashAlgorithm = "sha256") {
+  return useQuery({
+    queryKey: ["fileHash", path, algorithm],
+    queryFn: () => tauriInvoke<FileHash>("hash_file", { path, algorithm }),
+    enabled: !!path,
+  });
+}
+
+// Snapshot a folder's checksums (optionally saved to `output` for later verification)
+export function useGenerateManifest() {
+  return useMutation({
+    mutationFn: ({ root, algorithm, output }: { root: string; algorithm?: HashAlgorithm; output?: string }) =>
+      tauriInvoke<TreeManifest>("generate_manifest", { root, algorithm: algorithm ?? null, output: output ?? null }),
+  });
+}
+
+// Check a folder against a saved manifest file
+export function useVerifyTree() {
+  return useMutation({
+    mutationFn: ({ root, manifest }: { root: string; manifest: string }) =>
+      tauriInvoke<TreeVerification>("verify_tree", { root, manifest }),
+  });
+}
+
+export interface RelatedDocument {
+  path: string;
+  title: string;
+  score: number;
+  /** Similarity ≥ 0.95 — probably another copy of the same document */
+  near_duplicate: boolean;
+}
+
+export interface NearDuplicatePair {
+  path_a: string;
+  title_a: string;
+  path_b: string;
+  title_b: string;
+  score: number;
+}
+
+// Semantically similar knowledge documents (embeds new/edited docs on demand)
+export function useRelatedDocuments(path: string | undefined, topK = 5) {
+  return useQuery({
+    queryKey: ["relatedDocuments", path, topK],
+    queryFn: () => tauriInvoke<RelatedDocument[]>("knowledge_find_related", { path, topK }),
+    enabled: !!path && (path.endsWith(".md") || path.endsWith(".markdown")),
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+// Pairs of knowledge documents that are near-identical in content
+export function useNearDuplicateDocuments(enabled = true, threshold?: number) {
+  return useQuery({
+    queryKey: ["nearDuplicateDocuments", threshold],
+    queryFn: () =>
+      tauriInvoke<NearDuplicatePair[]>("knowledge_find_near_duplicates", { threshold: threshold ?? null }),
+    enabled,
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export interface Backlink {
+  source: string;
+  title: string;
+  line: number;
+  kind: "markdown" | "wiki";
+  context: string;
+}
+
+export interface OutgoingLink {
+  /** Null when the link doesn't resolve to a document */
+  target: string | null;
+  raw: string;
+  kind: "markdown" | "wiki";
+  line: number;
+}
+
+export interface Backlinks {
+  path: string;
+  title: string | null;
+  backlinks: Backlink[];
+  outgoing: OutgoingLink[];
+}
+
+export interface LinkGraph {
+  root: string;
+  nodes: { path: string; title: string; inbound: number; outbound: number }[];
+  edges: { source: string; target: string; count: number }[];
+  broken_links: number;
+}
+
+// Knowledge documents linking to `path` (markdown links and [[wiki-links]])
+export function useBacklinks(path: string | undefined) {
+  return useQuery({
+    queryKey: ["backlinks", path],
+    queryFn: () => tauriInvoke<Backlinks>("get_backlinks", { path }),
+    enabled: !!path && (path.endsWith(".md") || path.endsWith(".markdown")),
+    staleTime: 30 * 1000,
+  });
+}
+
+// Link graph of a knowledge folder (whole knowledge base when root is omitted)
+export function useLinkGraph(root?: string, enabled = true) {
+  return useQuery({
+    queryKey: ["linkGraph", root],
+    queryFn: () => tauriInvoke<LinkGraph>("get_link_graph", { root: root ?? null }),
+    enabled,
+    staleTime: 60 * 1000,
+  });
+}
+
+export interface TransclusionWarning {
+  source: string;
+  line: number;
+  embed: string;
+  reason: "not_found" | "section_not_found" | "cycle" | "depth_limit" | "embed_limit";
+}
+
+export interface RenderedDocument {
+  path: string;
+  /** Body with ![[doc]] / ![[doc#Section]] embeds expanded */
+  markdown: string;
+  html: string;
+  embedded: string[];
+  warnings: TransclusionWarning[];
+}
+
+// Knowledge doc with ![[doc#Section]] transclusions resolved
+export function useRenderedDocument(path: string | undefined) {
+  return useQuery({
+    queryKey: ["renderedDocument", path],
+    queryFn: () => tauriInvoke<RenderedDocument>("knowledge_render_document", { path }),
+    enabled: !!path && (path.endsWith(".md") || path.endsWith(".markdown")),
+    staleTime: 30 * 1000,
+  });
+}
+
+export interface RecentFileEntry {
+  path: string;
+  name: string;
+  opened_at: string | null;
+  modified_at: string | null;
+  open_count: number;
+}
+
+// Files opened or saved through the app, shared across windows and launches
+export function useRecentFilesHistory(limit = 20, kind?: "opened" | "modified") {
+  return useQuery({
+    queryKey: ["recentFiles", limit, kind],
+    queryFn: () =>
+      tauriInvoke<RecentFileEntry[]>("list_recent_files", { limit, kind: kind ?? null }),
+    staleTime: 10 * 1000,
+  });
+}
+
+export function useClearRecentFiles() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => tauriInvoke<void>("clear_recent_files"),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["recentFiles"] });
+    },
+  });
+}
+
+export type ConflictStrategy = "overwrite" | "skip" | "merge";
+
+export interface TransferResult {
+  files_transferred: number;
+  files_skipped: number;
+  bytes_transferred: number;
+}
+
+export interface TransferProgress {
+  source: string;
+  destination: string;
+  files_done: number;
+  files_total: number;
+  bytes_done: number;
+  bytes_total: number;
+  current: string | null;
+}
+
+interface TransferArgs {
+  source: string;
+  destination: string;
+  conflict?: ConflictStrategy;
+}
+
+function invalidateTransfer(queryClient: ReturnType<typeof useQueryClient>, paths: string[]) {
+  for (const path of paths) {
+    queryClient.invalidateQueries({ queryKey: ["file", path] });
+    queryClient.invalidateQueries({ queryKey: ["directory", path.substring(0, path.lastIndexOf("/"))] });
+  }
+  queryClient.invalidateQueries({ queryKey: ["folderChildren"] });
+  queryClient.invalidateQueries({ queryKey: ["fileTree"] });
+}
+
+// Copy file or directory (recursive) to a full destination path
+export function useCopyPath() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ source, destination, conflict }: TransferArgs) =>
+      tauriInvoke<TransferResult>("copy_path", { source, destination, conflict }),
+    onSuccess: (_, { destination }) => invalidateTransfer(queryClient, [destination]),
+  });
+}
+
+// Move file or directory to a full destination path
+export function useMovePath() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ source, destination, conflict }: TransferArgs) =>
+      tauriInvoke<TransferResult>("move_path", { source, destination, conflict }),
+    onSuccess: (_, { source, destination }) => invalidateTransfer(queryClient, [source, destination]),
+  });
+}
+
+// Subscribe to copy/move progress events (throttled on the Rust side)
+export function useTransferProgress(onProgress: (progress: TransferProgress) => void) {
+  useEffect(() => {
+    let unlisten: UnlistenFn | undefined;
+    listen<TransferProgress>("file-transfer:progress", (event) => onProgress(event.payload)).then((fn) => {
+      unlisten = fn;
+    });
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [onProgress]);
+}
+
+export interface DirectorySize {
+  path: string;
+  total_bytes: number;
+  file_count: number;
+  dir_count: number;
+  /** Entries that couldn't be read */
+  skipped: number;
+  /** false on progress events, true on the final result */
+  done: boolean;
+}
+
+// Recursive disk usage of a folder; progress arrives via useDirectorySizeProgress
+export function useDirectorySize(path: string | undefined) {
+  return useQuery({
+    queryKey: ["directorySize", path],
+    queryFn: () => tauriInvoke<DirectorySize>("get_directory_size", { path }),
+    enabled: !!path,
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+// Subscribe to running totals while get_directory_size walks (throttled on the Rust side)
+export function useDirectorySizeProgress(onProgress: (progress: DirectorySize) => void) {
+  useEffect(() => {
+    let unlisten: UnlistenFn | undefined;
+    listen<DirectorySize>("directory-size:progress", (event) => onProgress(event.payload)).then((fn) => {
+      unlisten = fn;
+    });
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [onProgress]);
+}
+
 // Load folder children on demand (for lazy-loaded tree nodes)
 export function useFolderChildren(path: string | undefined, enabled: boolean) {
   return useQuery({
@@ -155,7 +815,7 @@ export function useFolderChildren(path: string | undefined, enabled: boolean) {
     queryFn: async () => {
       const result = await tauriInvoke<TreeNode>("get_file_tree", {
         path,
-        max_depth: 1,
+        maxDepth: 1,
       });
       return result.children || [];
     },
@@ -164,12 +824,36 @@ export function useFolderChildren(path: string | undefined, enabled: boolean) {
   });
 }
 
-// Watch directory for changes
+export interface WatchOptions {
+  /** Watch subfolders too (default true) */
+  recursive?: boolean;
+  /** Only report paths matching one of these globs, e.g. "*.md" */
+  include?: string[];
+  /** Never report paths matching these globs, e.g. ".git/**" */
+  exclude?: string[];
+  /** Quiet period before a batch of changes is emitted (default 200ms) */
+  debounce_ms?: number;
+  /** Report changes inside symlinked folders, under the link's path (default true) */
+  follow_symlinks?: boolean;
+}
+
+export type FileWatchEventType = "file-created" | "file-modified" | "file-deleted" | "file-renamed";
+
+export interface FileWatchEvent {
+  root: string;
+  path: string;
+  /** Previous path, for renames */
+  from: string | null;
+}
+
+// Watch directory for changes (debounced; see useFileWatchEvents for typed events)
 export function useWatchDirectory(
   path: string | undefined,
-  onFileChange?: (paths: string[]) => void
+  onFileChange?: (paths: string[]) => void,
+  options?: WatchOptions
 ) {
   const queryClient = useQueryClient();
+  const optionsKey = JSON.stringify(options ?? {});
 
   useEffect(() => {
     if (!path) return;
@@ -177,9 +861,9 @@ export function useWatchDirectory(
     let unlisten: UnlistenFn | undefined;
 
     // Start watching
-    tauriInvoke<void>("watch_directory", { path }).catch(console.error);
+    tauriInvoke<void>("watch_directory", { path, options: JSON.parse(optionsKey) }).catch(console.error);
 
-    // Listen for file change events
+    // Listen for batched file change events
     listen<string[]>("file-change", (event) => {
       // Invalidate relevant queries
       queryClient.invalidateQueries({ queryKey: ["fileTree"] });
@@ -207,5 +891,32 @@ export function useWatchDirectory(
       // Stop the Rust file watcher for this path
       tauriInvoke<void>("unwatch_directory", { path }).catch(() => {});
     };
-  }, [path, queryClient, onFileChange]);
+  }, [path, queryClient, onFileChange, optionsKey]);
+}
+
+// Typed created/modified/deleted/renamed events from watched directories under `root`
+export function useFileWatchEvents(
+  root: string | undefined,
+  onEvent: (type: FileWatchEventType, event: FileWatchEvent) => void
+) {
+  useEffect(() => {
+    if (!root) return;
+    const types: FileWatchEventType[] = ["file-created", "file-modified", "file-deleted", "file-renamed"];
+    const unlisteners: UnlistenFn[] = [];
+    let disposed = false;
+
+    for (const type of types) {
+      listen<FileWatchEvent>(type, (event) => {
+        if (event.payload.path.startsWith(root)) onEvent(type, event.payload);
+      }).then((fn) => {
+        if (disposed) fn();
+        else unlisteners.push(fn);
+      });
+    }
+
+    return () => {
+      disposed = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, [root, onEvent]);
 }
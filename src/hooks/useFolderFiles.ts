@@ -1,7 +1,7 @@
 // src/hooks/useFolderFiles.ts
 // Hook to fetch files in a folder, sorted by modified time
 
-import { useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 
 export interface FolderFile {
@@ -41,6 +41,26 @@ export function useFolderFiles(folderPath: string | null, limit: number = 20) {
   });
 }
 
+/**
+ * Re-read frontmatter for a folder, bypassing the backend's mtime cache
+ * (e.g. after a sync tool rewrote files without touching their mtime).
+ */
+export function useRefreshFolderFiles() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ folderPath, limit = 20 }: { folderPath: string; limit?: number }) =>
+      invoke<FolderFile[]>("get_folder_files", {
+        path: folderPath,
+        limit,
+        forceRefresh: true,
+      }),
+    onSuccess: (files, { folderPath, limit = 20 }) => {
+      queryClient.setQueryData(["folder-files", folderPath, limit], files);
+    },
+  });
+}
+
 /**
  * Fetch all entries (files and directories) in a folder.
  * Directories are listed first, then files, both sorted alphabetically.
@@ -21,6 +21,20 @@ export interface Ga4ConfigStatus {
   isAuthenticated: boolean;
 }
 
+export interface RealtimeCount {
+  name: string;
+  activeUsers: number;
+}
+
+export interface RealtimeSnapshot {
+  domain: string;
+  activeUsers: number;
+  topPages: RealtimeCount[];
+  sources: RealtimeCount[];
+  fetchedAt: string;
+  warnings: string[];
+}
+
 export interface AnalyticsSyncResult {
   source: string;
   rowsUpserted: number;
@@ -35,6 +49,7 @@ export const ga4Keys = {
   all: ["ga4"] as const,
   auth: () => ["ga4", "auth"] as const,
   config: () => ["ga4", "config"] as const,
+  realtime: (domain: string) => ["ga4", "realtime", domain] as const,
 };
 
 // ============================================================================
@@ -93,3 +108,37 @@ export function useGA4Config() {
     staleTime: 1000 * 60 * 5,
   });
 }
+
+// ============================================================================
+// Realtime
+// ============================================================================
+
+/** Live active users for a tenant domain ("website" or "" for whole properties).
+ * The backend caches for 60s, so polling from several windows is cheap. */
+export function useRealtimeAnalytics(domain: string, enabled = true) {
+  return useQuery({
+    queryKey: ga4Keys.realtime(domain),
+    queryFn: () => invoke<RealtimeSnapshot>("analytics_get_realtime", { domain }),
+    enabled,
+    staleTime: 1000 * 60,
+    refetchInterval: 1000 * 60,
+  });
+}
+
+// ============================================================================
+// Dashboard snapshot PDF
+// ============================================================================
+
+export type DashboardReportPeriod = "week" | "month" | "quarter" | `${number}-${number}`;
+
+/** Render a domain's dashboard (summary, trend, top pages, anomalies) to a branded PDF */
+export function useExportDashboardPdf() {
+  return useMutation({
+    mutationFn: (args: { domain: string; period?: DashboardReportPeriod; outputPath: string }) =>
+      invoke<string>("analytics_export_dashboard_pdf", {
+        domain: args.domain,
+        period: args.period,
+        outputPath: args.outputPath,
+      }),
+  });
+}
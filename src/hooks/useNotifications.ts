@@ -7,7 +7,7 @@ import { supabase } from "../lib/supabase";
 export interface Notification {
   id: string;
   recipient: string;
-  type: string; // 'mention' | 'reply'
+  type: string; // 'mention' | 'reply' | 'activity'
   discussion_id: string | null;
   entity_type: string;
   entity_id: string;
@@ -194,6 +194,25 @@ export function useArchiveEmail() {
   });
 }
 
+export interface DraftReply {
+  emailId: string;
+  draftId: string;
+  webLink: string | null;
+  body: string;
+  companyName: string | null;
+  threadMessages: number;
+  deals: number;
+  activities: number;
+}
+
+/** AI-drafted reply saved to Outlook Drafts — never sent automatically */
+export function useDraftReply() {
+  return useMutation({
+    mutationFn: ({ emailId, instruction }: { emailId: string; instruction?: string }) =>
+      invoke<DraftReply>("outlook_draft_reply", { emailId, instruction }),
+  });
+}
+
 // ============================================================================
 // Sync hooks
 // ============================================================================
@@ -280,3 +299,226 @@ export function useCalendarSyncStart() {
   });
 }
 
+
+// ============================================================================
+// Cache cleanup hooks
+// ============================================================================
+
+export interface CleanupPolicy {
+  enabled: boolean;
+  cron: string;
+  keepBodyMonths: number;
+  maxAttachmentKb: number | null;
+  vacuum: boolean;
+}
+
+export interface CleanupReport {
+  dryRun: boolean;
+  bodiesRemoved: number;
+  bodyBytes: number;
+  orphansRemoved: number;
+  orphanBytes: number;
+  attachmentsPurged: number;
+  attachmentBytes: number;
+  databaseBytes: number;
+  totalBytes: number;
+}
+
+export function useCleanupPolicy() {
+  return useQuery({
+    queryKey: ["outlook", "cleanup-policy"],
+    queryFn: () => invoke<CleanupPolicy>("outlook_cleanup_get_policy"),
+  });
+}
+
+export function useSaveCleanupPolicy() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (policy: CleanupPolicy) => invoke<CleanupPolicy>("outlook_cleanup_save_policy", { policy }),
+    onSuccess: (policy) => {
+      queryClient.setQueryData(["outlook", "cleanup-policy"], policy);
+    },
+  });
+}
+
+/** Dry run by default — pass dryRun: false to actually reclaim space */
+export function useRunCleanup() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args?: { dryRun?: boolean; policy?: CleanupPolicy }) =>
+      invoke<CleanupReport>("outlook_run_cleanup", { dryRun: args?.dryRun ?? true, policy: args?.policy }),
+    onSuccess: (report) => {
+      if (!report.dryRun) {
+        queryClient.invalidateQueries({ queryKey: ["outlook", "stats"] });
+        queryClient.invalidateQueries({ queryKey: ["outlook", "body"] });
+      }
+    },
+  });
+}
+
+// ============================================================================
+// Sync diagnostics hooks
+// ============================================================================
+
+export interface SyncRun {
+  id: number;
+  kind: "email_initial" | "email_incremental" | "calendar" | "token_refresh" | "folder_repair";
+  trigger: "setup" | "manual" | "background" | "push" | "auth";
+  startedAt: string;
+  durationMs: number;
+  items: number;
+  ok: boolean;
+  error: string | null;
+  errorCode: string | null;
+}
+
+export interface SyncKindHealth {
+  kind: SyncRun["kind"];
+  lastRunAt: string | null;
+  lastSuccessAt: string | null;
+  consecutiveFailures: number;
+  lastError: string | null;
+  avgDurationMs: number;
+}
+
+export interface SyncDiagnostics {
+  runs: SyncRun[];
+  health: SyncKindHealth[];
+  cursors: { key: string; value: string | null; ageSecs: number | null }[];
+  folders: {
+    folderId: string;
+    displayName: string;
+    cachedMessages: number;
+    newestMessageAt: string | null;
+    newestAgeSecs: number | null;
+    lastRepairedAt: string | null;
+  }[];
+  tokenExpiresAt: string | null;
+  hasRefreshToken: boolean;
+}
+
+export interface FolderRepairReport {
+  folderId: string;
+  displayName: string;
+  refetched: number;
+  removed: number;
+  since: string;
+}
+
+export function useSyncDiagnostics(limit?: number) {
+  return useQuery({
+    queryKey: ["outlook", "sync-diagnostics", limit],
+    queryFn: () => invoke<SyncDiagnostics>("outlook_get_sync_diagnostics", { limit }),
+  });
+}
+
+export function useRepairFolder() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { folderId: string; days?: number }) =>
+      invoke<FolderRepairReport>("outlook_repair_folder", args),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["outlook", "sync-diagnostics"] });
+      queryClient.invalidateQueries({ queryKey: ["outlook", "emails"] });
+      queryClient.invalidateQueries({ queryKey: ["outlook", "stats"] });
+    },
+  });
+}
+
+// ============================================================================
+// Mail merge (personalised bulk send)
+// ============================================================================
+
+export interface MergeTemplate {
+  /** {{field}} or {{field|fallback}}; CRM fields include first_name, company, company.<column> */
+  subject: string;
+  body: string;
+  plainText?: boolean;
+}
+
+export interface MergeRecipient {
+  contactId?: string;
+  email?: string;
+  name?: string;
+  fields?: Record<string, unknown>;
+}
+
+export interface MergePreview {
+  email: string;
+  name: string;
+  subject: string;
+  body: string;
+  missingFields: string[];
+  error: string | null;
+}
+
+export interface BulkSendRecipientStatus {
+  email: string;
+  name: string;
+  status: "pending" | "sent" | "failed" | "skipped" | "aborted";
+  error: string | null;
+  sentAt: string | null;
+}
+
+export interface BulkSendJob {
+  id: string;
+  status: "running" | "completed" | "aborted";
+  perMinute: number;
+  total: number;
+  sent: number;
+  failed: number;
+  skipped: number;
+  startedAt: string;
+  finishedAt: string | null;
+  recipients: BulkSendRecipientStatus[];
+}
+
+export interface BulkSendResult {
+  dryRun: boolean;
+  jobId: string | null;
+  previews: MergePreview[];
+}
+
+/** Dry run by default (previews only) — pass dryRun: false to start sending */
+export function useBulkSend() {
+  return useMutation({
+    mutationFn: (args: {
+      template: MergeTemplate;
+      recipients?: MergeRecipient[];
+      /** CRM segment whose members are added to `recipients` */
+      segmentId?: string;
+      perMinute?: number;
+      dryRun?: boolean;
+    }) =>
+      invoke<BulkSendResult>("outlook_bulk_send", {
+        template: args.template,
+        recipientList: args.recipients ?? [],
+        segmentId: args.segmentId ?? null,
+        throttle: args.perMinute ?? null,
+        dryRun: args.dryRun ?? true,
+      }),
+  });
+}
+
+export function useBulkSendStatus(jobId: string | null) {
+  return useQuery({
+    queryKey: ["outlook", "bulk-send", jobId],
+    queryFn: () => invoke<BulkSendJob>("outlook_bulk_send_status", { jobId }),
+    enabled: !!jobId,
+    refetchInterval: (query) => (query.state.data?.status === "running" ? 3000 : false),
+  });
+}
+
+export function useAbortBulkSend() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (jobId: string) => invoke<boolean>("outlook_bulk_send_abort", { jobId }),
+    onSuccess: (_, jobId) => {
+      queryClient.invalidateQueries({ queryKey: ["outlook", "bulk-send", jobId] });
+    },
+  });
+}
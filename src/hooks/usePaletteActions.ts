@@ -0,0 +1,60 @@
+// Backend command palette registry — actions the Rust side can run, ranked server-side
+
+import { useQuery, keepPreviousData } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface PaletteArg {
+  /** camelCase, as passed to invoke */
+  name: string;
+  kind: "string" | "boolean" | "number" | "path" | "domain" | "date";
+  required: boolean;
+  description: string;
+}
+
+export interface PaletteAction {
+  id: string;
+  title: string;
+  description: string;
+  /** ModuleId the action belongs to */
+  module: string;
+  /** Tauri command to invoke */
+  command: string;
+  args: PaletteArg[];
+  keywords: string[];
+}
+
+export interface PaletteMatch {
+  action: PaletteAction;
+  score: number;
+  matched_field: "title" | "keyword" | "module" | "command";
+  /** Char positions in the title to highlight */
+  title_matches: number[];
+}
+
+export function usePaletteActions() {
+  return useQuery({
+    queryKey: ["palette", "actions"],
+    queryFn: () => invoke<PaletteAction[]>("palette_list_actions"),
+    staleTime: Infinity,
+  });
+}
+
+/** Fuzzy-ranked actions for the palette query (all actions when empty) */
+export function usePaletteSearch(query: string, module?: string, enabled = true) {
+  return useQuery({
+    queryKey: ["palette", "search", query, module ?? "all"],
+    queryFn: () => invoke<PaletteMatch[]>("palette_search", { query, module: module ?? null }),
+    enabled,
+    placeholderData: keepPreviousData,
+    staleTime: Infinity,
+  });
+}
+
+/** Run an action once its required args are filled in */
+export function runPaletteAction<T = unknown>(action: PaletteAction, args: Record<string, unknown> = {}) {
+  const missing = action.args.filter((a) => a.required && (args[a.name] === undefined || args[a.name] === ""));
+  if (missing.length > 0) {
+    return Promise.reject(new Error(`Missing ${missing.map((a) => a.name).join(", ")}`));
+  }
+  return invoke<T>(action.command, args);
+}
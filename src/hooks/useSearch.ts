@@ -2,7 +2,9 @@
 // React hooks for search operations via Tauri IPC
 
 import { invoke } from "@tauri-apps/api/core";
-import { useQuery } from "@tanstack/react-query";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import { useQuery, useMutation, useQueryClient, keepPreviousData } from "@tanstack/react-query";
+import { useEffect, useState } from "react";
 
 // Types matching Rust models
 export interface SearchResult {
@@ -13,6 +15,46 @@ export interface SearchResult {
   match_type: "filename" | "content";
   preview: string | null;
   line_number: number | null;
+  /** Relevance score — content and fuzzy filename results, sorted best-first */
+  score?: number;
+  /** Where the first match is — content search only */
+  span?: MatchSpan;
+}
+
+/** Exact position of a content match, for jumping to and highlighting it */
+export interface MatchSpan {
+  /** Byte offsets into the file, end exclusive */
+  start: number;
+  end: number;
+  /** 1-based column on line_number, in characters */
+  column: number;
+  /** 1-based line and column just past the match */
+  end_line: number;
+  end_column: number;
+}
+
+/** Unset case: plain queries are case-insensitive, regexes case-sensitive */
+export interface MatchOptions {
+  /** "smart" is insensitive unless the query has an uppercase letter */
+  case?: "insensitive" | "sensitive" | "smart";
+  /** Only match where the query isn't part of a longer word */
+  whole_word?: boolean;
+}
+
+/** Structured filters for both search commands; unset fields don't filter */
+export interface SearchFilters {
+  /** Case-insensitive, with or without the dot */
+  extensions?: string[];
+  /** Bytes, inclusive */
+  min_size?: number;
+  max_size?: number;
+  /** YYYY-MM-DD or RFC 3339 */
+  modified_after?: string;
+  modified_before?: string;
+  /** "30m", "12h", "7d", "2w", "today", "this week" or "this month" */
+  modified_within?: string;
+  /** Relative to the search root, or absolute */
+  path_prefixes?: string[];
 }
 
 // Generic invoke wrapper
@@ -36,24 +78,59 @@ export function useFileSearch(
     extensions?: string[];
     maxResults?: number;
     enabled?: boolean;
+    /** Search inside symlinked folders (default true) */
+    followSymlinks?: boolean;
+    /** fzf-style matching on the path, ranked best-first */
+    fuzzy?: boolean;
+    filters?: SearchFilters;
   }
 ) {
-  const { extensions, maxResults = 100, enabled = true } = options || {};
+  const {
+    extensions,
+    maxResults = 100,
+    enabled = true,
+    followSymlinks = true,
+    fuzzy = false,
+    filters,
+  } = options || {};
 
   return useQuery({
-    queryKey: ["searchFiles", root, query, extensions, maxResults],
+    queryKey: ["searchFiles", root, query, extensions, maxResults, followSymlinks, fuzzy, filters],
     queryFn: () =>
       tauriInvoke<SearchResult[]>("search_files", {
         root,
         query,
         extensions: extensions || null,
         max_results: maxResults,
+        followSymlinks,
+        fuzzy,
+        filters: filters ?? null,
       }),
-    enabled: enabled && !!root && query.length >= 2,
+    enabled: enabled && !!root && query.length >= (fuzzy ? 1 : 2),
     staleTime: 1000 * 60, // 1 minute
   });
 }
 
+// Quick open: fuzzy filename matches for a palette, keeping the previous
+// list on screen while the next keystroke's results load
+export function useQuickOpen(root: string | undefined, query: string, maxResults = 30) {
+  return useQuery({
+    queryKey: ["quickOpen", root, query, maxResults],
+    queryFn: () =>
+      tauriInvoke<SearchResult[]>("search_files", {
+        root,
+        query,
+        extensions: null,
+        max_results: maxResults,
+        followSymlinks: true,
+        fuzzy: true,
+      }),
+    enabled: !!root && query.trim().length >= 1,
+    placeholderData: keepPreviousData,
+    staleTime: 1000 * 30,
+  });
+}
+
 // Search file content (full-text)
 export function useContentSearch(
   root: string | undefined,
@@ -62,24 +139,333 @@ export function useContentSearch(
     extensions?: string[];
     maxResults?: number;
     enabled?: boolean;
+    /** Search inside symlinked folders (default true) */
+    followSymlinks?: boolean;
+    /** Treat the query as a regex (case-sensitive unless it starts with `(?i)`) */
+    regex?: boolean;
+    /** Let a regex span lines (`^`/`$` match per line, `.` crosses newlines) */
+    multiline?: boolean;
+    filters?: SearchFilters;
+    matchOptions?: MatchOptions;
   }
 ) {
-  const { extensions, maxResults = 50, enabled = true } = options || {};
+  const {
+    extensions,
+    maxResults = 50,
+    enabled = true,
+    followSymlinks = true,
+    regex = false,
+    multiline = false,
+    filters,
+    matchOptions,
+  } = options || {};
 
   return useQuery({
-    queryKey: ["searchContent", root, query, extensions, maxResults],
+    queryKey: ["searchContent", root, query, extensions, maxResults, followSymlinks, regex, multiline, filters, matchOptions],
     queryFn: () =>
       tauriInvoke<SearchResult[]>("search_content", {
         root,
         query,
         extensions: extensions || null,
         max_results: maxResults,
+        followSymlinks,
+        regex,
+        multiline,
+        filters: filters ?? null,
+        matchOptions: matchOptions ?? null,
       }),
     enabled: enabled && !!root && query.length >= 3,
     staleTime: 1000 * 60, // 1 minute
   });
 }
 
+/** Highlighted span of a previewed line, in characters from the start of `text` */
+export interface MatchRange {
+  start: number;
+  end: number;
+}
+
+export interface LineMatch {
+  line_number: number;
+  /** Trimmed and clipped around the first match ("…" marks a cut) */
+  text: string;
+  ranges: MatchRange[];
+}
+
+/** Content matches for one file, VS Code search-panel style */
+export interface SearchResultGroup {
+  name: string;
+  path: string;
+  score: number;
+  match_count: number;
+  line_count: number;
+  /** The first few matching lines, in file order */
+  lines: LineMatch[];
+}
+
+export interface GroupedSearchResults {
+  groups: SearchResultGroup[];
+  /** Across every matching file, including any cut by maxResults */
+  total_files: number;
+  total_matches: number;
+}
+
+// Search file content, grouped per file with highlighted line previews
+export function useGroupedContentSearch(
+  root: string | undefined,
+  query: string,
+  options?: {
+    extensions?: string[];
+    maxResults?: number;
+    /** Matching lines previewed per file (default 5) */
+    maxLinesPerFile?: number;
+    enabled?: boolean;
+    followSymlinks?: boolean;
+    regex?: boolean;
+    multiline?: boolean;
+    filters?: SearchFilters;
+    matchOptions?: MatchOptions;
+  }
+) {
+  const {
+    extensions,
+    maxResults = 50,
+    maxLinesPerFile = 5,
+    enabled = true,
+    followSymlinks = true,
+    regex = false,
+    multiline = false,
+    filters,
+    matchOptions,
+  } = options || {};
+
+  return useQuery({
+    queryKey: ["searchContentGrouped", root, query, extensions, maxResults, maxLinesPerFile, followSymlinks, regex, multiline, filters, matchOptions],
+    queryFn: () =>
+      tauriInvoke<GroupedSearchResults>("search_content_grouped", {
+        root,
+        query,
+        extensions: extensions || null,
+        maxResults,
+        maxLinesPerFile,
+        followSymlinks,
+        regex,
+        multiline,
+        filters: filters ?? null,
+        matchOptions: matchOptions ?? null,
+      }),
+    enabled: enabled && !!root && query.length >= 3,
+    staleTime: 1000 * 60,
+    placeholderData: keepPreviousData,
+  });
+}
+
+export interface SearchComplete {
+  search_id: string;
+  matched: number;
+  /** Stopped by cancel_search or a newer search */
+  cancelled: boolean;
+  /** Stopped at maxResults */
+  truncated: boolean;
+  elapsed_ms: number;
+}
+
+let nextSearchId = 0;
+
+// Content search that fills in as files match. Each new query cancels the
+// previous scan; results arrive in walk order, so sort by score to rank.
+export function useStreamingContentSearch(
+  root: string | undefined,
+  query: string,
+  options?: {
+    extensions?: string[];
+    maxResults?: number;
+    enabled?: boolean;
+    followSymlinks?: boolean;
+    regex?: boolean;
+    multiline?: boolean;
+    filters?: SearchFilters;
+    matchOptions?: MatchOptions;
+  }
+) {
+  const {
+    extensions,
+    maxResults = 50,
+    enabled = true,
+    followSymlinks = true,
+    regex = false,
+    multiline = false,
+    filters,
+    matchOptions,
+  } = options || {};
+  const [results, setResults] = useState<SearchResult[]>([]);
+  const [complete, setComplete] = useState<SearchComplete | null>(null);
+  const [error, setError] = useState<unknown>(null);
+  const active = enabled && !!root && query.length >= 3;
+  const filtersKey = JSON.stringify(filters ?? null);
+  const matchOptionsKey = JSON.stringify(matchOptions ?? null);
+  const extensionsKey = JSON.stringify(extensions ?? null);
+
+  useEffect(() => {
+    setResults([]);
+    setComplete(null);
+    setError(null);
+    if (!active) return;
+
+    const searchId = `search-${Date.now()}-${nextSearchId++}`;
+    const unlisteners: Promise<UnlistenFn>[] = [
+      listen<{ search_id: string; result: SearchResult }>("search-result", (event) => {
+        if (event.payload.search_id === searchId) {
+          setResults((prev) => [...prev, event.payload.result]);
+        }
+      }),
+      listen<SearchComplete>("search-complete", (event) => {
+        if (event.payload.search_id === searchId) setComplete(event.payload);
+      }),
+    ];
+
+    Promise.all(unlisteners).then(() =>
+      invoke<SearchComplete>("search_content_stream", {
+        searchId,
+        root,
+        query,
+        extensions: extensions || null,
+        max_results: maxResults,
+        followSymlinks,
+        regex,
+        multiline,
+        filters: filters ?? null,
+        matchOptions: matchOptions ?? null,
+      }).catch((e) => setError(e))
+    );
+
+    return () => {
+      invoke("cancel_search", { searchId }).catch(() => {});
+      unlisteners.forEach((p) => p.then((fn) => fn()));
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [active, root, query, extensionsKey, maxResults, followSymlinks, regex, multiline, filtersKey, matchOptionsKey]);
+
+  return { results, complete, isSearching: active && !complete && !error, error };
+}
+
+export interface FrontmatterMatch {
+  name: string;
+  path: string;
+  /** The fields named in the conditions, by dotted key */
+  fields: Record<string, unknown>;
+}
+
+// Find documents by frontmatter, e.g. ["status: published", "tags contains data-model",
+// "ai_generated: true", "owner missing"]. All conditions must hold unless matchAny.
+export function useFrontmatterSearch(
+  root: string | undefined,
+  conditions: string[],
+  options?: {
+    matchAny?: boolean;
+    maxResults?: number;
+    enabled?: boolean;
+    followSymlinks?: boolean;
+    filters?: SearchFilters;
+  }
+) {
+  const { matchAny = false, maxResults = 200, enabled = true, followSymlinks = true, filters } = options || {};
+
+  return useQuery({
+    queryKey: ["searchFrontmatter", root, conditions, matchAny, maxResults, followSymlinks, filters],
+    queryFn: () =>
+      tauriInvoke<FrontmatterMatch[]>("search_frontmatter", {
+        root,
+        conditions,
+        matchAny,
+        max_results: maxResults,
+        followSymlinks,
+        filters: filters ?? null,
+      }),
+    enabled: enabled && !!root && conditions.some((c) => c.trim().length > 0),
+    staleTime: 1000 * 60,
+  });
+}
+
+/** Options for replace_content; fields are snake_case like SearchFilters */
+export interface ReplaceOptions {
+  root: string;
+  regex?: boolean;
+  multiline?: boolean;
+  /** Plain queries are case-insensitive unless set */
+  case_sensitive?: boolean;
+  extensions?: string[];
+  filters?: SearchFilters;
+  follow_symlinks?: boolean;
+  /** Only touch these files, e.g. the ones kept from the preview */
+  paths?: string[];
+  /** Snapshot originals into their version store (default true) */
+  backup?: boolean;
+  /** Files listed in a preview (default 500) */
+  max_files?: number;
+}
+
+export interface ReplaceFileResult {
+  path: string;
+  replacements: number;
+  changes: { line: number; before: string; after: string }[];
+  /** Snapshot of the original; pass to restore_version to undo */
+  version_id: string | null;
+  /** Applying failed; the file was left untouched */
+  error: string | null;
+}
+
+export interface ReplaceResult {
+  dry_run: boolean;
+  files_matched: number;
+  files_changed: number;
+  replacements: number;
+  files: ReplaceFileResult[];
+  truncated: boolean;
+}
+
+// Preview a search-and-replace without touching any file
+export function useReplacePreview(
+  query: string,
+  replacement: string,
+  options: ReplaceOptions | undefined,
+  enabled = true
+) {
+  return useQuery({
+    queryKey: ["replacePreview", query, replacement, options],
+    queryFn: () =>
+      tauriInvoke<ReplaceResult>("replace_content", {
+        query,
+        replacement,
+        options: { ...options, dry_run: true },
+      }),
+    enabled: enabled && !!options?.root && query.length > 0,
+    staleTime: 0,
+  });
+}
+
+// Apply a search-and-replace (originals snapshotted unless backup: false)
+export function useReplaceContent() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ query, replacement, options }: { query: string; replacement: string; options: ReplaceOptions }) =>
+      tauriInvoke<ReplaceResult>("replace_content", {
+        query,
+        replacement,
+        options: { ...options, dry_run: false },
+      }),
+    onSuccess: (result) => {
+      for (const file of result.files) {
+        queryClient.invalidateQueries({ queryKey: ["file", file.path] });
+        queryClient.invalidateQueries({ queryKey: ["fileVersions", file.path] });
+      }
+      queryClient.invalidateQueries({ queryKey: ["searchContent"] });
+      queryClient.invalidateQueries({ queryKey: ["replacePreview"] });
+    },
+  });
+}
+
 // Combined search hook for both filename and content
 export function useSearch(
   root: string | undefined,
@@ -133,3 +519,204 @@ export function useSearch(
   };
 }
 
+// ============================================================================
+// Persistent full-text index (tantivy) — build once, then query in milliseconds.
+// Watched folders keep it current; search_index_build refreshes changed files.
+// ============================================================================
+
+export interface SearchIndexStats {
+  root: string;
+  documents: number;
+  added: number;
+  updated: number;
+  removed: number;
+  elapsed_ms: number;
+}
+
+export interface SearchIndexStatus {
+  root: string;
+  documents: number;
+  built_at: string | null;
+  size_bytes: number;
+}
+
+export function useSearchIndexStatus(root: string | undefined) {
+  return useQuery({
+    queryKey: ["searchIndexStatus", root],
+    queryFn: () => tauriInvoke<SearchIndexStatus | null>("search_index_status", { root }),
+    enabled: !!root,
+  });
+}
+
+// Build or refresh the index; rebuild re-reads every file
+export function useBuildSearchIndex() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ root, rebuild }: { root: string; rebuild?: boolean }) =>
+      tauriInvoke<SearchIndexStats>("search_index_build", { root, rebuild }),
+    onSuccess: (_, { root }) => {
+      queryClient.invalidateQueries({ queryKey: ["searchIndexStatus", root] });
+      queryClient.invalidateQueries({ queryKey: ["searchIndexed", root] });
+    },
+  });
+}
+
+export function useDeleteSearchIndex() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (root: string) => tauriInvoke<boolean>("search_index_delete", { root }),
+    onSuccess: (_, root) => {
+      queryClient.invalidateQueries({ queryKey: ["searchIndexStatus", root] });
+    },
+  });
+}
+
+// Ranked content search against the index (tantivy query syntax: "exact phrase", -exclude)
+export function useIndexedSearch(
+  root: string | undefined,
+  query: string,
+  options?: { maxResults?: number; enabled?: boolean }
+) {
+  const { maxResults = 50, enabled = true } = options || {};
+
+  return useQuery({
+    queryKey: ["searchIndexed", root, query, maxResults],
+    queryFn: () =>
+      tauriInvoke<SearchResult[]>("search_index_query", { root, query, maxResults }),
+    enabled: enabled && !!root && query.trim().length >= 2,
+    staleTime: 1000 * 30,
+  });
+}
+
+// ============================================================================
+// Semantic index — markdown chunks embedded with the configured provider
+// (Settings: embedding_provider / embedding_model), ranked by meaning.
+// Root defaults to the knowledge path.
+// ============================================================================
+
+export interface SemanticIndexStats {
+  root: string;
+  /** "provider:model" the vectors came from */
+  embedder: string;
+  files: number;
+  chunks: number;
+  embedded: number;
+  removed: number;
+  elapsed_ms: number;
+}
+
+export interface SemanticIndexStatus {
+  root: string;
+  embedder: string | null;
+  files: number;
+  chunks: number;
+  built_at: string | null;
+}
+
+export interface SemanticChunk {
+  path: string;
+  relative_path: string;
+  title: string;
+  heading: string | null;
+  start_line: number;
+  end_line: number;
+  text: string;
+  score: number;
+}
+
+export function useSemanticIndexStatus(root?: string) {
+  return useQuery({
+    queryKey: ["semanticIndexStatus", root],
+    queryFn: () =>
+      tauriInvoke<SemanticIndexStatus | null>("search_semantic_index_status", { root }),
+  });
+}
+
+// Embed new and changed docs; rebuild re-embeds everything
+export function useBuildSemanticIndex() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ root, rebuild }: { root?: string; rebuild?: boolean }) =>
+      tauriInvoke<SemanticIndexStats>("search_semantic_index_build", { root, rebuild }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["semanticIndexStatus"] });
+      queryClient.invalidateQueries({ queryKey: ["searchSemantic"] });
+    },
+  });
+}
+
+// Passages closest in meaning to the query, best first
+export function useSemanticSearch(
+  query: string,
+  options?: { folder?: string; topK?: number; enabled?: boolean }
+) {
+  const { folder, topK = 8, enabled = true } = options || {};
+
+  return useQuery({
+    queryKey: ["searchSemantic", folder, query, topK],
+    queryFn: () =>
+      tauriInvoke<SemanticChunk[]>("search_semantic", { query, topK, folder }),
+    enabled: enabled && query.trim().length >= 3,
+    staleTime: 1000 * 60,
+  });
+}
+
+// ============================================================================
+// VAL artifacts — typed hits across a domain's synced tables, workflows,
+// queries and dashboards, with per-kind facet counts
+// ============================================================================
+
+export type ValArtifactKind = "table" | "workflow" | "query" | "dashboard";
+
+export interface ValArtifactFile {
+  /** Relative to the artifact folder, e.g. "sql/workflow_42_definition.sql" */
+  relative_path: string;
+  match_count: number;
+  lines: LineMatch[];
+}
+
+export interface ValArtifactHit {
+  kind: ValArtifactKind;
+  /** Table name or workflow / query / dashboard ID */
+  id: string;
+  name: string | null;
+  path: string;
+  name_match: boolean;
+  match_count: number;
+  files: ValArtifactFile[];
+}
+
+export interface ValArtifactSearch {
+  domain: string;
+  query: string;
+  hits: ValArtifactHit[];
+  /** Counts for every kind, whatever kind was asked for */
+  facets: { kind: ValArtifactKind; count: number }[];
+  total: number;
+  scanned: number;
+}
+
+export function useValArtifactSearch(
+  domain: string | undefined,
+  query: string,
+  options?: { kind?: ValArtifactKind; maxResults?: number; enabled?: boolean }
+) {
+  const { kind, maxResults = 100, enabled = true } = options || {};
+
+  return useQuery({
+    queryKey: ["searchValArtifacts", domain, query, kind, maxResults],
+    queryFn: () =>
+      tauriInvoke<ValArtifactSearch>("search_val_artifacts", {
+        domain,
+        kind: kind ?? null,
+        query,
+        maxResults,
+      }),
+    enabled: enabled && !!domain && query.trim().length >= 2,
+    staleTime: 1000 * 30,
+    placeholderData: keepPreviousData,
+  });
+}
@@ -182,3 +182,57 @@ export function useSupabaseCredentials() {
 
   return { url, anonKey, loading, isConfigured: url !== null && anonKey !== null };
 }
+
+export type ConnectionService =
+  | "supabase"
+  | "val"
+  | "ms_graph"
+  | "anthropic"
+  | "gamma"
+  | "intercom"
+  | "github";
+
+export interface ConnectionTestResult {
+  service: string;
+  ok: boolean;
+  message: string;
+  endpoint: string | null;
+  status: number | null;
+  latency_ms: number;
+  missing_keys: string[];
+  detail: string | null;
+}
+
+/**
+ * Hook for verifying an integration's saved keys with a live call
+ */
+export function useConnectionTest() {
+  const [results, setResults] = useState<Partial<Record<ConnectionService, ConnectionTestResult>>>({});
+  const [testing, setTesting] = useState<ConnectionService | null>(null);
+
+  const test = useCallback(async (service: ConnectionService, domain?: string) => {
+    setTesting(service);
+    try {
+      const result = await invoke<ConnectionTestResult>("settings_test_connection", { service, domain });
+      setResults((prev) => ({ ...prev, [service]: result }));
+      return result;
+    } catch (e) {
+      const result: ConnectionTestResult = {
+        service,
+        ok: false,
+        message: formatError(e),
+        endpoint: null,
+        status: null,
+        latency_ms: 0,
+        missing_keys: [],
+        detail: null,
+      };
+      setResults((prev) => ({ ...prev, [service]: result }));
+      return result;
+    } finally {
+      setTesting(null);
+    }
+  }, []);
+
+  return { results, testing, test };
+}
@@ -162,3 +162,33 @@ export function useTerminalList() {
 
   return { terminals, refresh };
 }
+
+export type TerminalCopyRange =
+  | { type: "all" }
+  | { type: "last_lines"; count: number }
+  | { type: "last_commands"; count: number };
+
+export type TerminalCopyMode = "plain" | "redacted";
+
+export interface TerminalCopyOutput {
+  text: string;
+  line_count: number;
+  redactions: number;
+  redacted_rules: string[];
+}
+
+// Copy a session's output to the clipboard — escapes stripped, prompts
+// collapsed, secrets redacted unless mode is "plain"
+export async function copyTerminalOutput(
+  sessionId: string,
+  range: TerminalCopyRange = { type: "all" },
+  mode: TerminalCopyMode = "redacted"
+): Promise<TerminalCopyOutput> {
+  const output = await invoke<TerminalCopyOutput>("terminal_copy_output", {
+    sessionId,
+    range,
+    mode,
+  });
+  await navigator.clipboard.writeText(output.text);
+  return output;
+}
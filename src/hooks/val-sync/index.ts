@@ -2,6 +2,7 @@ export * from "./types";
 export * from "./useValSyncCore";
 export * from "./useValSyncBulk";
 export * from "./useValSql";
+export * from "./useValNotebook";
 export * from "./useValTablePipeline";
 export * from "./useValDomainModel";
 export * from "./useValAiPackage";
@@ -11,3 +12,4 @@ export * from "./healthChecks";
 export * from "./useDomainHealthChecks";
 export * from "./useValDependencies";
 export * from "./useSchemaResources";
+export * from "./useValChanges";
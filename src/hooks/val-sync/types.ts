@@ -46,6 +46,8 @@ export interface ExtractResult {
   domain: string;
   extract_type: string;
   count: number;
+  changed: number;
+  unchanged: number;
   duration_ms: number;
   status: string;
   message: string;
@@ -137,6 +139,33 @@ export interface SyncAllDomainsProgress {
   isRunning: boolean;
 }
 
+export interface ValErrorExplanation {
+  category: "data" | "schema" | "file" | "access" | "transient" | "unknown" | string;
+  cause: string;
+  fix: string;
+  source: "rule" | "ai" | "unknown";
+  rule_id: string | null;
+}
+
+export interface ValErrorIncident {
+  signature: string;
+  source_name: string | null;
+  count: number;
+  first_seen: string | null;
+  last_seen: string | null;
+  sample: string;
+  explanation: ValErrorExplanation;
+}
+
+export interface ValErrorExplainReport {
+  domain: string;
+  error_type: "importer" | "integration";
+  file_path: string;
+  total_errors: number;
+  incidents: ValErrorIncident[];
+  unexplained: number;
+}
+
 // ============================================================
 // Query keys
 // ============================================================
@@ -150,4 +179,6 @@ export const valSyncKeys = {
   credentials: (domain: string) => [...valSyncKeys.all, "credentials", domain] as const,
   status: (domain: string) => [...valSyncKeys.all, "status", domain] as const,
   outputStatus: (domain: string) => [...valSyncKeys.all, "output-status", domain] as const,
+  errorIncidents: (domain: string, errorType: string) =>
+    [...valSyncKeys.all, "error-incidents", domain, errorType] as const,
 };
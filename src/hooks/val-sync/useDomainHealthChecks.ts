@@ -28,8 +28,25 @@ import {
 export const healthCheckKeys = {
   all: ["domain-health-checks"] as const,
   list: () => [...healthCheckKeys.all, "list"] as const,
+  trends: (domain: string, entity?: string, period?: HealthTrendPeriod) =>
+    [...healthCheckKeys.all, "trends", domain, entity, period] as const,
 };
 
+// ============================================================
+// Types
+// ============================================================
+
+export type HealthTrendPeriod = "week" | "month" | "quarter" | "year";
+
+export interface HealthTrend {
+  entityType: "table" | "workflow" | "query" | "check";
+  entityId: string;
+  label: string;
+  points: { recordedAt: string; score: number; status: string | null }[];
+  latest: number;
+  change: number;
+}
+
 // ============================================================
 // Helpers
 // ============================================================
@@ -252,6 +269,13 @@ export function useDomainHealthCheckRunner() {
             .upsert(results, { onConflict: "domain,check_type" });
 
           if (error) throw error;
+
+          // 5. Append this run to the local score history (flags regressions)
+          try {
+            await invoke("val_record_health_run", { domain, checks: results });
+          } catch (err) {
+            console.warn(`[health] history not recorded for ${domain}:`, err);
+          }
           completed.push(domain);
         } catch {
           failed.push(domain);
@@ -308,3 +332,17 @@ export function useDomainHealthChecks() {
     staleTime: 60_000,
   });
 }
+
+// ============================================================
+// Query: score history per table / workflow / query / check
+// ============================================================
+
+/** entity: a type ("table") or a single entity ("table:123") */
+export function useHealthTrends(domain: string | null, entity?: string, period: HealthTrendPeriod = "month") {
+  return useQuery({
+    queryKey: healthCheckKeys.trends(domain ?? "", entity, period),
+    queryFn: () => invoke<HealthTrend[]>("val_get_health_trends", { domain, entity, period }),
+    enabled: !!domain,
+    staleTime: 60_000,
+  });
+}
@@ -0,0 +1,130 @@
+// VAL change attribution hooks — which artifacts changed, by whom, and how
+
+import { useQuery } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { valSyncKeys } from "./types";
+
+// ============================================================
+// Types (mirror Rust RecentChangesReport structs)
+// ============================================================
+
+export interface ArtifactChange {
+  artifact_type: "query" | "workflow" | "dashboard" | "table";
+  artifact_id: string;
+  name: string | null;
+  change: "created" | "updated";
+  created_by: string | null;
+  updated_by: string | null;
+  created_date: string | null;
+  updated_date: string | null;
+  run_at: string;
+}
+
+export interface ChangeAuthorSummary {
+  user: string;
+  count: number;
+  artifact_types: Record<string, number>;
+}
+
+export interface RecentChangesReport {
+  domain: string;
+  since: string | null;
+  total: number;
+  by_author: ChangeAuthorSummary[];
+  by_type: Record<string, number>;
+  changes: ArtifactChange[];
+}
+
+// ============================================================
+// Query key
+// ============================================================
+
+export const changeKeys = {
+  recent: (domain: string, since?: string) => [...valSyncKeys.all, "recent-changes", domain, since ?? "last-sync"] as const,
+};
+
+// ============================================================
+// Hooks
+// ============================================================
+
+/** Artifacts changed since `since` (ISO date), or by the latest extract runs when omitted */
+export function useValRecentChanges(domain: string | null, since?: string) {
+  return useQuery({
+    queryKey: changeKeys.recent(domain ?? "", since),
+    queryFn: () => invoke<RecentChangesReport>("val_get_recent_changes", { domain, since }),
+    enabled: !!domain,
+    staleTime: 60_000,
+  });
+}
+
+// ============================================================
+// Workflow versions (mirror Rust workflow_versions structs)
+// ============================================================
+
+export interface WorkflowVersionInfo {
+  version: number;
+  saved_at: string;
+  updated_by: string | null;
+  updated_date: string | null;
+  plugin_count: number;
+}
+
+export interface WorkflowValueChange {
+  path: string;
+  change: "added" | "removed" | "changed";
+  before: unknown;
+  after: unknown;
+}
+
+export interface WorkflowPluginRef {
+  key: string;
+  name: string;
+  position: number;
+}
+
+export interface WorkflowPluginChange {
+  key: string;
+  name: string;
+  from_position: number;
+  to_position: number;
+  changes: WorkflowValueChange[];
+}
+
+export interface WorkflowDiff {
+  domain: string;
+  workflow_id: string;
+  from: WorkflowVersionInfo;
+  to: WorkflowVersionInfo;
+  plugins_added: WorkflowPluginRef[];
+  plugins_removed: WorkflowPluginRef[];
+  plugins_changed: WorkflowPluginChange[];
+  workflow_changes: WorkflowValueChange[];
+  identical: boolean;
+}
+
+export const workflowVersionKeys = {
+  list: (domain: string, workflowId: string) => [...valSyncKeys.all, "workflow-versions", domain, workflowId] as const,
+  diff: (domain: string, workflowId: string, from?: number, to?: number) =>
+    [...workflowVersionKeys.list(domain, workflowId), "diff", from ?? "prev", to ?? "latest"] as const,
+};
+
+/** Saved versions of a workflow definition, newest first */
+export function useWorkflowVersions(domain: string | null, workflowId: string | null) {
+  return useQuery({
+    queryKey: workflowVersionKeys.list(domain ?? "", workflowId ?? ""),
+    queryFn: () => invoke<WorkflowVersionInfo[]>("val_list_workflow_versions", { domain, workflowId }),
+    enabled: !!domain && !!workflowId,
+    staleTime: 60_000,
+  });
+}
+
+/** Plugin/param diff between two versions (defaults: latest vs the one before it) */
+export function useWorkflowDiff(domain: string | null, workflowId: string | null, from?: number, to?: number) {
+  return useQuery({
+    queryKey: workflowVersionKeys.diff(domain ?? "", workflowId ?? "", from, to),
+    queryFn: () => invoke<WorkflowDiff>("val_diff_workflow_versions", { domain, workflowId, from, to }),
+    enabled: !!domain && !!workflowId,
+    staleTime: 60_000,
+    retry: false,
+  });
+}
@@ -0,0 +1,102 @@
+// Analysis notebook hooks — markdown files in the knowledge folder with
+// ```sql and ```chart cells, run against a VAL domain
+
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { ShapedResult } from "./useValSql";
+
+// ============================================================
+// Types
+// ============================================================
+
+export type NotebookCell =
+  | { kind: "markdown"; text: string }
+  | { kind: "sql"; name: string; sql: string }
+  /** spec is YAML: a ShapeSpec plus `type` (bar | line) and `title` */
+  | { kind: "chart"; source: string | null; spec: string };
+
+export interface SqlCellResult {
+  row_count: number;
+  columns: string[];
+  rows: Record<string, unknown>[];
+  truncated: boolean;
+  executed_at: string;
+}
+
+export interface NotebookChart {
+  chart_type: "bar" | "line";
+  title: string | null;
+  data: ShapedResult;
+}
+
+export interface NotebookCellOutput {
+  result: SqlCellResult | null;
+  /** Answered from the notebook's result cache */
+  cached: boolean;
+  chart: NotebookChart | null;
+  error: string | null;
+}
+
+export interface NotebookRun {
+  path: string;
+  title: string | null;
+  domain: string;
+  cells: { cell: NotebookCell; output: NotebookCellOutput | null }[];
+  executed: number;
+  cached: number;
+  errors: number;
+  ran_at: string;
+}
+
+export type NotebookExportFormat = "markdown" | "pdf";
+
+// ============================================================
+// Hooks
+// ============================================================
+
+/** Run a notebook, reusing cached results for unchanged SQL */
+export function useValNotebook(path: string | undefined, domain?: string) {
+  return useQuery({
+    queryKey: ["valNotebook", path, domain],
+    queryFn: () =>
+      invoke<NotebookRun>("val_run_notebook", { path, domain: domain ?? null, refresh: false }),
+    enabled: !!path,
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+/** Re-run every SQL cell, ignoring the cache */
+export function useValRefreshNotebook() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ path, domain }: { path: string; domain?: string }) =>
+      invoke<NotebookRun>("val_run_notebook", { path, domain: domain ?? null, refresh: true }),
+    onSuccess: (run, { path, domain }) => {
+      queryClient.setQueryData(["valNotebook", path, domain], run);
+    },
+  });
+}
+
+/** Export the executed notebook; resolves to the written file's path */
+export function useValExportNotebook() {
+  return useMutation({
+    mutationFn: ({
+      path,
+      format,
+      outputPath,
+      domain,
+    }: {
+      path: string;
+      format: NotebookExportFormat;
+      outputPath?: string;
+      domain?: string;
+    }) =>
+      invoke<string>("val_export_notebook", {
+        path,
+        format,
+        outputPath: outputPath ?? null,
+        domain: domain ?? null,
+        refresh: false,
+      }),
+  });
+}
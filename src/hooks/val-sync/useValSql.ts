@@ -14,9 +14,32 @@ export interface SqlExecuteResult {
   columns: string[];
   data: Record<string, unknown>[];
   truncated: boolean;
+  /** Handle for val_shape_result; null on error or once evicted */
+  result_id: string | null;
   error: string | null;
 }
 
+export type DateBucket = "day" | "week" | "month" | "quarter" | "year";
+export type ShapeAggregate = "sum" | "avg" | "min" | "max" | "count" | "count_distinct";
+
+export interface ShapeSpec {
+  x: string;
+  bucket?: DateBucket | null;
+  /** Pivot column: one series per distinct value */
+  series?: string | null;
+  y?: { column?: string | null; agg: ShapeAggregate; label?: string | null }[];
+  sort?: "x" | "value" | null;
+  limit?: number | null;
+  max_series?: number | null;
+}
+
+export interface ShapedResult {
+  categories: string[];
+  series: { name: string; metric: string; values: (number | null)[] }[];
+  row_count: number;
+  skipped_rows: number;
+}
+
 export interface SqlGenerateResult {
   domain: string;
   prompt: string;
@@ -38,6 +61,21 @@ export function useValExecuteSql() {
   });
 }
 
+/** Shape a stored SQL result (or inline rows) into chart series */
+export function useValShapeResult() {
+  return useMutation({
+    mutationFn: ({
+      resultId,
+      rows,
+      spec,
+    }: {
+      resultId?: string | null;
+      rows?: Record<string, unknown>[] | null;
+      spec: ShapeSpec;
+    }) => invoke<ShapedResult>("val_shape_result", { resultId: resultId ?? null, rows: rows ?? null, spec }),
+  });
+}
+
 /** Generate SQL from natural language using Claude Haiku */
 export function useValGenerateSql() {
   return useMutation({
@@ -8,11 +8,13 @@ import {
   type DiscoveredDomain,
   type AuthResult,
   type SyncResult,
+  type ExtractResult,
   type SyncAllResult,
   type SyncMetadata,
   type ValCredentials,
   type ValSyncConfig,
   type OutputStatusResult,
+  type ValErrorExplainReport,
 } from "./types";
 
 // ============================================================
@@ -133,6 +135,7 @@ export function useValSyncArtifact() {
         tables: "val_sync_tables",
         "all-tables": "val_sync_tables",
         "calc-fields": "val_sync_calc_fields",
+        "query-stats": "val_sync_query_stats",
       };
       const cmd = cmdMap[artifactType];
       if (!cmd) throw new Error(`Unknown artifact type: ${artifactType}`);
@@ -305,6 +308,7 @@ export function useValSyncImporterErrors() {
       invoke<SyncResult>("val_sync_importer_errors", { domain, from, to }),
     onSuccess: (_data, { domain }) => {
       qc.invalidateQueries({ queryKey: valSyncKeys.status(domain) });
+      qc.invalidateQueries({ queryKey: valSyncKeys.errorIncidents(domain, "importer") });
     },
   });
 }
@@ -317,6 +321,42 @@ export function useValSyncIntegrationErrors() {
       invoke<SyncResult>("val_sync_integration_errors", { domain, from, to }),
     onSuccess: (_data, { domain }) => {
       qc.invalidateQueries({ queryKey: valSyncKeys.status(domain) });
+      qc.invalidateQueries({ queryKey: valSyncKeys.errorIncidents(domain, "integration") });
+    },
+  });
+}
+
+/** Synced importer/integration errors grouped into incidents with plain-language explanations */
+export function useValErrorIncidents(domain: string | null, errorType: "importer" | "integration") {
+  return useQuery({
+    queryKey: valSyncKeys.errorIncidents(domain ?? "", errorType),
+    queryFn: () =>
+      invoke<ValErrorExplainReport>("val_explain_errors", { domain, errorType, useAi: false }),
+    enabled: !!domain,
+    staleTime: 60_000,
+  });
+}
+
+/** Ask the model to explain incidents no rule recognises (answers are cached) */
+export function useValExplainErrorsWithAi() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: ({ domain, errorType }: { domain: string; errorType: "importer" | "integration" }) =>
+      invoke<ValErrorExplainReport>("val_explain_errors", { domain, errorType, useAi: true }),
+    onSuccess: (report, { domain, errorType }) => {
+      qc.setQueryData(valSyncKeys.errorIncidents(domain, errorType), report);
+    },
+  });
+}
+
+/** Extract integration configs (credentials masked) into integrations/ */
+export function useValExtractIntegrations() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (domain: string) =>
+      invoke<ExtractResult>("val_extract_integrations", { domain }),
+    onSuccess: (_data, domain) => {
+      qc.invalidateQueries({ queryKey: valSyncKeys.status(domain) });
     },
   });
 }
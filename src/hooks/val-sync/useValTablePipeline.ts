@@ -276,3 +276,90 @@ export function useScanCategoryLibrary() {
     staleTime: 5 * 60_000, // Cache for 5 minutes
   });
 }
+
+// ============================================================
+// Cross-domain comparison
+// ============================================================
+
+export interface DomainTableProfile {
+  domain: string;
+  table_id: string | null;
+  display_name: string | null;
+  row_count: number | null;
+  coverage: Record<string, unknown> | null;
+  column_count: number;
+  sampled_rows: number;
+  missing_files: string[];
+}
+
+export interface ColumnDiff {
+  column: string;
+  names: Record<string, string>;
+  types: Record<string, string>;
+  missing_from: string[];
+  type_mismatch: boolean;
+  name_mismatch: boolean;
+}
+
+export interface ValueRange {
+  kind: "number" | "date";
+  min: string;
+  max: string;
+  null_ratio: number;
+}
+
+export interface CategoricalDiff {
+  column: string;
+  common: string[];
+  only_in: Record<string, string[]>;
+  distinct_counts: Record<string, number>;
+}
+
+export interface TableComparison {
+  table_name: string;
+  generated_at: string;
+  domains: DomainTableProfile[];
+  columns: ColumnDiff[];
+  ranges: { column: string; ranges: Record<string, ValueRange> }[];
+  categorical: CategoricalDiff[];
+  json_path: string | null;
+  markdown_path: string | null;
+}
+
+/** Compare one table (id or display name) across domains from pipeline outputs on disk */
+export function useCompareTableAcrossDomains() {
+  return useMutation({
+    mutationFn: ({ tableName, domains, outputDir }: { tableName: string; domains: string[]; outputDir?: string }) =>
+      invoke<TableComparison>("val_compare_table_across_domains", {
+        tableName,
+        domains,
+        outputDir: outputDir ?? null,
+      }),
+  });
+}
+
+// ============================================================
+// Table ↔ documentation links
+// ============================================================
+
+export interface TableDocsResult {
+  domain: string;
+  documents_scanned: number;
+  tables_linked: number;
+  links: number;
+  details_updated: number;
+  overviews_updated: number;
+  duration_ms: number;
+}
+
+/** Link tables to knowledge docs that mention them (wiki links / code spans);
+ *  writes `docs` into definition_details.json and a Documentation section into overview.md */
+export function useLinkTableDocs() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (domain: string) => invoke<TableDocsResult>("val_link_table_docs", { domain }),
+    onSuccess: (_data, domain) => {
+      qc.invalidateQueries({ queryKey: valSyncKeys.outputStatus(domain) });
+    },
+  });
+}
@@ -8,3 +8,5 @@ export * from "./useInitiatives";
 export * from "./useMilestones";
 export * from "./useProjectUpdates";
 export * from "./useTeams";
+export * from "./useCalendar";
+export * from "./useAvailability";
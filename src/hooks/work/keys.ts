@@ -22,4 +22,11 @@ export const workKeys = {
   whatsappSummaries: (initiativeId: string) =>
     [...workKeys.all, "whatsapp_summaries", initiativeId] as const,
   teams: () => [...workKeys.all, "teams"] as const,
+  calendars: () => [...workKeys.all, "calendars"] as const,
+  holidays: (country: string, year?: number) =>
+    [...workKeys.all, "holidays", country, year ?? "all"] as const,
+  teamAvailability: (date?: string) =>
+    [...workKeys.all, "availability", date ?? "today"] as const,
+  taskDueStatus: (taskId: string) =>
+    [...workKeys.all, "due_status", taskId] as const,
 };
@@ -0,0 +1,81 @@
+// Working hours, team availability and per-assignee due deadlines
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import type { User } from "../../lib/work/types";
+import { workKeys } from "./keys";
+
+export interface UserAvailability {
+  user_id: string;
+  name: string;
+  timezone: string;
+  country: string;
+  /** Their wall-clock time right now (RFC 3339 with their offset) */
+  local_time: string;
+  working_now: boolean;
+  /** Status on the requested date */
+  status: "working" | "off_hours" | "day_off" | "holiday";
+  /** Working window on the requested date, UTC */
+  window_start?: string;
+  window_end?: string;
+  next_available_at?: string;
+}
+
+export interface AssigneeDueStatus {
+  user_id: string;
+  name: string;
+  timezone: string;
+  /** End of their working day on the due date, UTC */
+  due_at: string;
+  overdue: boolean;
+  /** Working minutes left (negative once overdue) */
+  working_minutes_remaining: number;
+}
+
+export interface WorkingHoursInput {
+  userId: string;
+  /** IANA name, e.g. "Europe/London" */
+  timezone?: string | null;
+  /** HH:MM local */
+  workStart?: string | null;
+  workEnd?: string | null;
+  country?: string | null;
+}
+
+/** Who is in working hours now; `date` (YYYY-MM-DD) picks the windows shown */
+export function useTeamAvailability(date?: string) {
+  return useQuery({
+    queryKey: workKeys.teamAvailability(date),
+    queryFn: () =>
+      invoke<UserAvailability[]>("work_get_team_availability", { date: date ?? null }),
+    refetchInterval: 5 * 60 * 1000,
+  });
+}
+
+export function useTaskDueStatus(taskId: string | null) {
+  return useQuery({
+    queryKey: workKeys.taskDueStatus(taskId ?? ""),
+    queryFn: () => invoke<AssigneeDueStatus[]>("work_get_task_due_status", { taskId }),
+    enabled: !!taskId,
+  });
+}
+
+export function useUpdateWorkingHours() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ userId, timezone, workStart, workEnd, country }: WorkingHoursInput) =>
+      invoke<User>("work_update_user_working_hours", {
+        userId,
+        timezone: timezone ?? null,
+        workStart: workStart ?? null,
+        workEnd: workEnd ?? null,
+        country: country ?? null,
+      }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: workKeys.users() });
+      queryClient.invalidateQueries({ queryKey: [...workKeys.all, "availability"] });
+      queryClient.invalidateQueries({ queryKey: [...workKeys.all, "due_status"] });
+    },
+  });
+}
@@ -0,0 +1,74 @@
+// Working-days calendar hooks (weekends + public holidays per country)
+
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { workKeys } from "./keys";
+
+export interface WorkCalendar {
+  country: string;
+  /** ISO weekdays that are off (1 = Monday … 7 = Sunday) */
+  weekend_days: number[];
+  updated_at?: string;
+}
+
+export interface WorkHoliday {
+  id: string;
+  country: string;
+  date: string;
+  name: string;
+}
+
+export function useWorkCalendars() {
+  return useQuery({
+    queryKey: workKeys.calendars(),
+    queryFn: () => invoke<WorkCalendar[]>("work_list_calendars"),
+  });
+}
+
+export function useWorkHolidays(country?: string, year?: number) {
+  return useQuery({
+    queryKey: workKeys.holidays(country ?? "SG", year),
+    queryFn: () =>
+      invoke<WorkHoliday[]>("work_list_holidays", { country: country ?? null, year: year ?? null }),
+  });
+}
+
+export function useSaveWorkCalendar() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ country, weekendDays }: { country: string; weekendDays: number[] }) =>
+      invoke<WorkCalendar>("work_save_calendar", { country, weekendDays }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: workKeys.calendars() });
+    },
+  });
+}
+
+export function useAddWorkHoliday() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ country, date, name }: { country: string; date: string; name: string }) =>
+      invoke<WorkHoliday>("work_add_holiday", { country, date, name }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: [...workKeys.all, "holidays"] });
+    },
+  });
+}
+
+export function useDeleteWorkHoliday() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (holidayId: string) => invoke<void>("work_delete_holiday", { holidayId }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: [...workKeys.all, "holidays"] });
+    },
+  });
+}
+
+/** Date `n` working days from `date` (YYYY-MM-DD), skipping weekends and holidays */
+export function addBusinessDays(date: string, n: number, country?: string): Promise<string> {
+  return invoke<string>("work_add_business_days", { date, n, country: country ?? null });
+}
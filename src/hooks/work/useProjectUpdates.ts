@@ -1,6 +1,7 @@
 // Work Project Updates hooks
 
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
 import { supabase } from "../../lib/supabase";
 import type {
   ProjectUpdateInsert,
@@ -63,3 +64,17 @@ export function useCreateProjectUpdate() {
     },
   });
 }
+
+export type StatusReportPeriod = "week" | "month" | "quarter" | `${number}-${number}`;
+
+/** Render a project status report PDF (summary, milestones, tasks, risks) */
+export function useGenerateStatusReport() {
+  return useMutation({
+    mutationFn: (args: { projectId: string; period?: StatusReportPeriod; outputPath: string }) =>
+      invoke<string>("work_generate_status_report_pdf", {
+        projectId: args.projectId,
+        period: args.period,
+        outputPath: args.outputPath,
+      }),
+  });
+}
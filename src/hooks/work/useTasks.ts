@@ -4,10 +4,12 @@ import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 import { supabase } from "../../lib/supabase";
 import type {
+  Label,
   Task,
   TaskInsert,
   TaskUpdate,
   TaskWithRelations,
+  User,
 } from "../../lib/work/types";
 import { workKeys } from "./keys";
 import { toast } from "../../stores/toastStore";
@@ -334,3 +336,96 @@ export function useDeleteTask() {
     },
   });
 }
+
+export interface QuickAddParse {
+  title: string;
+  due_date: string | null;
+  due_text: string | null;
+  priority: number | null;
+  labels: string[];
+  mentions: string[];
+}
+
+export interface QuickAddResult {
+  parsed: QuickAddParse;
+  project_id: string;
+  status_id: string;
+  labels: Label[];
+  assignees: User[];
+  unresolved_labels: string[];
+  unresolved_mentions: string[];
+  task: TaskWithRelations | null;
+}
+
+/** Parse capture-box shorthand ("tomorrow p1 #bug @name fix login");
+ *  pass create: true once the user confirms the interpretation */
+export function useQuickAddTask() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ text, projectId, create }: { text: string; projectId: string; create?: boolean }) =>
+      invoke<QuickAddResult>("work_quick_add", {
+        text,
+        projectId,
+        locale: navigator.language,
+        create: create ?? false,
+      }),
+    onSuccess: (result) => {
+      if (result.task) {
+        queryClient.invalidateQueries({ queryKey: workKeys.tasks() });
+      }
+    },
+  });
+}
+
+export interface ChecklistSyncResult {
+  path: string | null;
+  /** The checklist with task markers and updated checkboxes */
+  markdown: string;
+  created: TaskWithRelations[];
+  completed: string[];
+  reopened: string[];
+  checked_lines: number[];
+  unchecked_lines: number[];
+  missing: string[];
+  unresolved_mentions: string[];
+  unresolved_labels: string[];
+}
+
+/** Import a markdown checklist (file path or pasted text) as tasks and subtasks.
+ *  Importing the same file again re-syncs completion both ways. */
+export function useImportChecklist() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (params: {
+      projectId: string;
+      milestoneId?: string | null;
+      path?: string;
+      text?: string;
+    }) =>
+      invoke<ChecklistSyncResult>("work_import_checklist", {
+        path: params.path ?? null,
+        text: params.text ?? null,
+        projectId: params.projectId,
+        milestoneId: params.milestoneId ?? null,
+        locale: navigator.language,
+      }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: workKeys.tasks() });
+    },
+  });
+}
+
+/** Re-sync a previously imported checklist file */
+export function useSyncChecklist() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (path: string) =>
+      invoke<ChecklistSyncResult>("work_sync_checklist", { path, locale: navigator.language }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: workKeys.tasks() });
+    },
+  });
+}
@@ -5198,6 +5198,7 @@ export type Database = {
           linked_document_repo: string | null
           milestone_id: string | null
           notion_page_id: string | null
+          parent_task_id: string | null
           priority: number | null
           project_id: string
           requires_review: boolean | null
@@ -5236,6 +5237,7 @@ export type Database = {
           linked_document_repo?: string | null
           milestone_id?: string | null
           notion_page_id?: string | null
+          parent_task_id?: string | null
           priority?: number | null
           project_id: string
           requires_review?: boolean | null
@@ -5274,6 +5276,7 @@ export type Database = {
           linked_document_repo?: string | null
           milestone_id?: string | null
           notion_page_id?: string | null
+          parent_task_id?: string | null
           priority?: number | null
           project_id?: string
           requires_review?: boolean | null
@@ -5464,6 +5467,7 @@ export type Database = {
           avatar_url: string | null
           bot_department: string | null
           bot_folder_id: string | null
+          country: string | null
           created_at: string | null
           email: string | null
           github_id: number | null
@@ -5475,15 +5479,19 @@ export type Database = {
           name: string
           role: string
           team_folder: string | null
+          timezone: string | null
           type: string
           updated_at: string | null
           visible_modules: string[] | null
+          work_end: string | null
+          work_start: string | null
         }
         Insert: {
           auth_user_id?: string | null
           avatar_url?: string | null
           bot_department?: string | null
           bot_folder_id?: string | null
+          country?: string | null
           created_at?: string | null
           email?: string | null
           github_id?: number | null
@@ -5495,15 +5503,19 @@ export type Database = {
           name: string
           role?: string
           team_folder?: string | null
+          timezone?: string | null
           type?: string
           updated_at?: string | null
           visible_modules?: string[] | null
+          work_end?: string | null
+          work_start?: string | null
         }
         Update: {
           auth_user_id?: string | null
           avatar_url?: string | null
           bot_department?: string | null
           bot_folder_id?: string | null
+          country?: string | null
           created_at?: string | null
           email?: string | null
           github_id?: number | null
@@ -5515,9 +5527,12 @@ export type Database = {
           name?: string
           role?: string
           team_folder?: string | null
+          timezone?: string | null
           type?: string
           updated_at?: string | null
           visible_modules?: string[] | null
+          work_end?: string | null
+          work_start?: string | null
         }
         Relationships: []
       }
@@ -13,6 +13,7 @@ import { DealTasks } from "./DealTasks";
 import { CompanyForm } from "./CompanyForm";
 import { ContactForm } from "./ContactForm";
 import { DealForm } from "./DealForm";
+import { CompanyUsagePanel } from "./CompanyUsagePanel";
 import {
   X,
   Pencil,
@@ -23,6 +24,7 @@ import {
   MessageSquare,
   Mail,
   Clock,
+  BarChart3,
 } from "lucide-react";
 import { DiscussionPanel } from "../../components/discussions/DiscussionPanel";
 import { useDiscussionCount } from "../../hooks/useDiscussions";
@@ -59,7 +61,7 @@ interface CompanyDetailPanelProps {
   onCompanyDeleted?: () => void;
 }
 
-type TabId = "timeline" | "contacts" | "deals" | "emails" | "discussion";
+type TabId = "timeline" | "contacts" | "deals" | "usage" | "emails" | "discussion";
 
 export function CompanyDetailPanel({
   companyId,
@@ -81,7 +83,7 @@ export function CompanyDetailPanel({
   // Report company + sub-tab to help bot
   const setViewDetail = useViewContextStore((s) => s.setDetail);
   useEffect(() => {
-    const tabLabels: Record<TabId, string> = { timeline: "Timeline", contacts: "Contacts", deals: "Deals", emails: "Emails", discussion: "Discussion" };
+    const tabLabels: Record<TabId, string> = { timeline: "Timeline", contacts: "Contacts", deals: "Deals", usage: "Usage", emails: "Emails", discussion: "Discussion" };
     const name = company?.display_name || company?.name;
     if (name) setViewDetail(`${name} → ${tabLabels[activeTab]}`);
   }, [company, activeTab, setViewDetail]);
@@ -128,6 +130,7 @@ export function CompanyDetailPanel({
     { key: "timeline", label: "Timeline", icon: null },
     { key: "contacts", label: "Contacts", icon: null, badge: company.contacts?.length || 0 },
     { key: "deals", label: "Deals", icon: null, badge: company.deals?.length || 0 },
+    { key: "usage", label: "Usage", icon: BarChart3 },
     { key: "emails", label: "Emails", icon: Mail, badge: emailCount ?? 0 },
     { key: "discussion", label: "Discussion", icon: MessageSquare, badge: discussionCount ?? 0 },
   ];
@@ -340,6 +343,7 @@ export function CompanyDetailPanel({
             </div>
           </div>
         )}
+        {activeTab === "usage" && <CompanyUsagePanel companyId={companyId} />}
         {activeTab === "emails" && (
           <EmailsPanel entityType="company" entityId={companyId} />
         )}
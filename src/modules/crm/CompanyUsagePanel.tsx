@@ -0,0 +1,181 @@
+// src/modules/crm/CompanyUsagePanel.tsx
+// Product usage for a company, from analytics page views on its mapped domains
+
+import { useState } from "react";
+import { X } from "lucide-react";
+import {
+  useCompanyUsage,
+  useCompanyDomainMappings,
+  useUnmappedDomains,
+  useSetDomainMapping,
+  useRemoveDomainMapping,
+  type UsagePeriod,
+} from "../../hooks/crm";
+import { Button } from "../../components/ui";
+import { toast } from "../../stores/toastStore";
+import { formatError } from "../../lib/formatError";
+import { cn } from "../../lib/cn";
+
+const PERIODS: { key: UsagePeriod; label: string }[] = [
+  { key: "week", label: "7d" },
+  { key: "month", label: "30d" },
+  { key: "quarter", label: "90d" },
+  { key: "year", label: "1y" },
+];
+
+function Stat({ label, value, hint }: { label: string; value: string | number; hint?: string }) {
+  return (
+    <div className="flex-1 rounded-md bg-white dark:bg-zinc-900 border border-zinc-200/60 dark:border-zinc-800/60 px-3 py-2 text-center">
+      <div className="text-sm font-semibold text-zinc-800 dark:text-zinc-200">{value}</div>
+      <div className="text-xs text-zinc-400 dark:text-zinc-500">{label}</div>
+      {hint && <div className="text-[10px] text-zinc-400 dark:text-zinc-500 mt-0.5">{hint}</div>}
+    </div>
+  );
+}
+
+export function CompanyUsagePanel({ companyId }: { companyId: string }) {
+  const [period, setPeriod] = useState<UsagePeriod>("month");
+  const [newDomain, setNewDomain] = useState("");
+  const { data: usage, isLoading, error } = useCompanyUsage(companyId, period);
+  const { data: mappings = [] } = useCompanyDomainMappings(companyId);
+  const { data: unmapped = [] } = useUnmappedDomains(mappings.length === 0);
+  const setMapping = useSetDomainMapping(companyId);
+  const removeMapping = useRemoveDomainMapping(companyId);
+
+  async function addDomain(domain: string) {
+    if (!domain.trim()) return;
+    try {
+      await setMapping.mutateAsync(domain);
+      setNewDomain("");
+    } catch (e) {
+      toast.error(`Failed to map domain: ${formatError(e)}`);
+    }
+  }
+
+  const change = usage?.views_change_pct;
+  const maxDaily = Math.max(1, ...(usage?.daily.map((d) => d.views) ?? []));
+
+  return (
+    <div className="p-4 space-y-4">
+      {/* Mapped domains */}
+      <div className="space-y-2">
+        <div className="text-xs font-medium text-zinc-500 dark:text-zinc-400">Domains</div>
+        <div className="flex flex-wrap gap-1.5">
+          {mappings.map((m) => (
+            <span
+              key={m.id}
+              className="inline-flex items-center gap-1 text-xs px-2 py-0.5 rounded bg-zinc-100 dark:bg-zinc-800 text-zinc-600 dark:text-zinc-300"
+            >
+              {m.domain}
+              <button
+                onClick={() => removeMapping.mutate(m.domain)}
+                className="text-zinc-400 hover:text-red-500"
+                title="Remove mapping"
+              >
+                <X size={11} />
+              </button>
+            </span>
+          ))}
+          {mappings.length === 0 && (
+            <span className="text-xs text-zinc-400">No domains mapped — usage can't be attributed yet</span>
+          )}
+        </div>
+        <form
+          className="flex gap-2"
+          onSubmit={(e) => {
+            e.preventDefault();
+            addDomain(newDomain);
+          }}
+        >
+          <input
+            value={newDomain}
+            onChange={(e) => setNewDomain(e.target.value)}
+            placeholder="e.g. acme.thinkval.io"
+            list={`unmapped-domains-${companyId}`}
+            className="flex-1 text-xs px-2 py-1 rounded-md border border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-900 text-zinc-700 dark:text-zinc-300"
+          />
+          <datalist id={`unmapped-domains-${companyId}`}>
+            {unmapped.map((d) => (
+              <option key={d.domain} value={d.domain}>
+                {d.views} views
+              </option>
+            ))}
+          </datalist>
+          <Button size="sm" type="submit" disabled={!newDomain.trim() || setMapping.isPending}>
+            Map
+          </Button>
+        </form>
+      </div>
+
+      {/* Period */}
+      <div className="flex items-center gap-1">
+        {PERIODS.map((p) => (
+          <button
+            key={p.key}
+            onClick={() => setPeriod(p.key)}
+            className={cn(
+              "px-2 py-1 rounded-md text-xs font-medium transition-colors",
+              period === p.key
+                ? "bg-teal-50 dark:bg-teal-950/30 text-teal-700 dark:text-teal-300"
+                : "text-zinc-500 hover:bg-zinc-100 dark:hover:bg-zinc-800/50",
+            )}
+          >
+            {p.label}
+          </button>
+        ))}
+      </div>
+
+      {isLoading && <p className="text-xs text-zinc-400">Loading usage…</p>}
+      {error && <p className="text-xs text-red-500">{formatError(error)}</p>}
+
+      {usage && usage.domains.length > 0 && (
+        <>
+          <div className="flex gap-3">
+            <Stat
+              label="Views"
+              value={usage.total_views.toLocaleString()}
+              hint={change == null ? undefined : `${change >= 0 ? "+" : ""}${change.toFixed(0)}% vs prior`}
+            />
+            <Stat
+              label="Active users"
+              value={usage.active_users}
+              hint={`${usage.previous_active_users} prior`}
+            />
+            <Stat label="Active days" value={usage.active_days} />
+            <Stat label="Last seen" value={usage.last_seen ?? "—"} />
+          </div>
+
+          {usage.daily.length > 0 && (
+            <div className="flex items-end gap-px h-16" title="Daily views">
+              {usage.daily.map((d) => (
+                <div
+                  key={d.date}
+                  className="flex-1 bg-teal-400/70 dark:bg-teal-500/60 rounded-sm min-h-[2px]"
+                  style={{ height: `${(d.views / maxDaily) * 100}%` }}
+                  title={`${d.date}: ${d.views} views, ${d.users} users`}
+                />
+              ))}
+            </div>
+          )}
+
+          <div className="space-y-1">
+            <div className="text-xs font-medium text-zinc-500 dark:text-zinc-400">Top pages</div>
+            {usage.top_pages.map((p) => (
+              <div key={p.page_path} className="flex items-center justify-between text-xs">
+                <span className="truncate text-zinc-600 dark:text-zinc-300 font-mono" title={p.page_path}>
+                  {p.page_path}
+                </span>
+                <span className="text-zinc-400 flex-shrink-0 ml-2">
+                  {p.views} views · {p.users} users
+                </span>
+              </div>
+            ))}
+            {usage.top_pages.length === 0 && (
+              <p className="text-xs text-zinc-400">No views in this period</p>
+            )}
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
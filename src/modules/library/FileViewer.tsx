@@ -700,7 +700,7 @@ export function FileViewer({ path, basePath, onNavigate }: FileViewerProps) {
 
   // Handle delete
   const handleDelete = async () => {
-    const confirmed = await confirm(`Are you sure you want to delete "${filename}"?`, {
+    const confirmed = await confirm(`Move "${filename}" to the Trash?`, {
       title: "Delete File",
       kind: "warning",
     });
@@ -708,7 +708,7 @@ export function FileViewer({ path, basePath, onNavigate }: FileViewerProps) {
     if (confirmed) {
       try {
         await invoke("delete_file", { path });
-        showToast("File deleted", "success");
+        showToast("File moved to Trash", "success");
         // Navigate to parent folder
         const parentPath = path.split("/").slice(0, -1).join("/");
         onNavigate(parentPath || basePath);
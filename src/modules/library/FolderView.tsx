@@ -295,7 +295,7 @@ export function FolderView({
 
   // Handle delete folder
   const handleDelete = async () => {
-    const confirmed = await confirm(`Are you sure you want to delete the folder "${folderName}" and all its contents?`, {
+    const confirmed = await confirm(`Move the folder "${folderName}" and all its contents to the Trash?`, {
       title: "Delete Folder",
       kind: "warning",
     });
@@ -303,7 +303,7 @@ export function FolderView({
     if (confirmed) {
       try {
         await invoke("delete_file", { path });
-        showToastMessage("Folder deleted", "success");
+        showToastMessage("Folder moved to Trash", "success");
         // Navigate to parent folder
         const parentPath = path.split("/").slice(0, -1).join("/");
         onNavigate(parentPath || basePath);
@@ -28,6 +28,9 @@ import { useFolderExpansion } from "../../stores/folderExpansionStore";
 import { cn } from "../../lib/cn";
 import { InlineLoading } from "../../components/ui/DetailStates";
 
+// Skip VCS and dependency churn so checkouts/installs don't refresh the tree
+const LIBRARY_WATCH_OPTIONS = { exclude: [".git/**", "node_modules/**"] };
+
 interface SidebarProps {
   knowledgePath: string;
   selectedPath: string | null;
@@ -60,7 +63,7 @@ export function Sidebar({ knowledgePath, selectedPath, onFileSelect, onPinSelect
   const { data: fileTree, isLoading: treeLoading, isFetching } = useFileTree(knowledgePath, 4);
 
   // Watch for file changes and auto-refresh
-  useWatchDirectory(knowledgePath);
+  useWatchDirectory(knowledgePath, undefined, LIBRARY_WATCH_OPTIONS);
 
   // Full refresh - invalidates all file-related queries
   const handleRefresh = useCallback(() => {
@@ -197,7 +197,7 @@ export function FieldGrid({ fields, onUpdate, companyId, contacts }: {
 // ── Sub-tab type ────────────────────────────────────────────────────────────
 
 type SubTab = "companies" | "contacts" | "initiatives" | "labels" | "users" | "partners"
-  | "deal_stage" | "deal_solution" | "company_stage" | "activity_type" | "project_status" | "project_health" | "project_type"
+  | "deal_stage" | "deal_solution" | "deal_won_reason" | "deal_lost_reason" | "company_stage" | "activity_type" | "project_status" | "project_health" | "project_type"
   | "domain_type" | "initiative_status" | "task_status_type" | "task_statuses"
   // Layer 2 classification controlled vocabularies (used by Skills + all 4
   // artifact tabs — Tables, Queries, Dashboards, Workflows).
@@ -977,7 +977,7 @@ export function MetadataView() {
   ], []);
 
   const isLookupTab = [
-    "deal_stage", "deal_solution", "company_stage", "activity_type", "project_status", "project_health", "project_type", "domain_type", "initiative_status", "task_status_type",
+    "deal_stage", "deal_solution", "deal_won_reason", "deal_lost_reason", "company_stage", "activity_type", "project_status", "project_health", "project_type", "domain_type", "initiative_status", "task_status_type",
     // Layer 2 classification controlled vocabularies — backed by lookup_values too.
     "data_category", "data_sub_category", "data_type", "usage_status", "action",
     "data_source", "source_system", "solution", "sitemap_group_1", "sitemap_group_2", "tag",
@@ -1043,6 +1043,8 @@ export function MetadataView() {
         { id: "labels", label: "Labels", icon: Tag, count: labels.length },
         { id: "deal_stage", label: "Deal Stages", icon: Settings, count: lookupValues.filter(l => l.type === "deal_stage").length },
         { id: "deal_solution", label: "Solutions", icon: Settings, count: lookupValues.filter(l => l.type === "deal_solution").length },
+        { id: "deal_won_reason", label: "Won Reasons", icon: Settings, count: lookupValues.filter(l => l.type === "deal_won_reason").length },
+        { id: "deal_lost_reason", label: "Lost Reasons", icon: Settings, count: lookupValues.filter(l => l.type === "deal_lost_reason").length },
         { id: "company_stage", label: "Co. Stages", icon: Settings, count: lookupValues.filter(l => l.type === "company_stage").length },
         { id: "activity_type", label: "Activity Types", icon: Settings, count: lookupValues.filter(l => l.type === "activity_type").length },
         { id: "project_status", label: "Proj. Statuses", icon: Settings, count: lookupValues.filter(l => l.type === "project_status").length },
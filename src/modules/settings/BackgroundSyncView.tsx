@@ -27,6 +27,11 @@ const SYNC_TOGGLES = [
     label: "GA4 Analytics Sync",
     description: "Sync GA4 platform and website analytics every 6 hours (when stale >24h)",
   },
+  {
+    key: "bg_sync_hubspot",
+    label: "HubSpot CRM Pull",
+    description: "Pull changed HubSpot companies, contacts, deals and notes every 6 hours (after an initial import)",
+  },
 ] as const;
 
 type SyncKey = (typeof SYNC_TOGGLES)[number]["key"];
@@ -112,6 +117,7 @@ export function BackgroundSyncView() {
     bg_sync_outlook_email: false,
     bg_sync_outlook_calendar: false,
     bg_sync_ga4: false,
+    bg_sync_hubspot: false,
   });
   const [loading, setLoading] = useState(true);
   const cronQuery = useCronJobs();
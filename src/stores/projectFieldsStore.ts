@@ -58,6 +58,8 @@ export const DEAL_BUILT_IN_FIELDS: { key: string; label: string; type: FieldType
   { key: "deal_actual_close", label: "Actual Close", type: "date" },
   { key: "deal_lost_reason", label: "Lost Reason", type: "text" },
   { key: "deal_won_notes", label: "Won Notes", type: "text" },
+  { key: "deal_close_reason", label: "Close Reason", type: "text" },
+  { key: "deal_competitor", label: "Competitor", type: "text" },
   { key: "deal_notes", label: "Notes", type: "textarea" },
 ];
 
@@ -72,7 +74,7 @@ export type ProjectType = typeof PROJECT_TYPES[number]["key"];
 /** Default enabled fields per type */
 const DEFAULT_CONFIGS: Record<ProjectType, string[]> = {
   work: ["health", "lead", "target_date"],
-  deal: ["deal_stage", "deal_mrr", "deal_setup_fee", "deal_arr", "deal_year_1_total", "deal_currency", "deal_solution", "deal_expected_close", "deal_actual_close", "deal_lost_reason", "deal_won_notes", "deal_notes"],
+  deal: ["deal_stage", "deal_mrr", "deal_setup_fee", "deal_arr", "deal_year_1_total", "deal_currency", "deal_solution", "deal_expected_close", "deal_actual_close", "deal_lost_reason", "deal_won_notes", "deal_close_reason", "deal_competitor", "deal_notes"],
 };
 
 /** Custom field definition (user-created) */
@@ -0,0 +1,44 @@
+-- Analytics data retention. Raw analytics_page_views rows older than the
+-- configured window are rolled up into weekly or monthly aggregates, written
+-- to gzipped JSONL archives (knowledge folder or S3) one file per month, and
+-- then deleted. analytics_archives records each file so it can be restored.
+
+CREATE TABLE IF NOT EXISTS analytics_page_view_rollups (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  period TEXT NOT NULL CHECK (period IN ('week', 'month')),
+  period_start DATE NOT NULL,
+  source TEXT NOT NULL,
+  -- '' rather than NULL so the unique key below matches on upsert
+  domain TEXT NOT NULL DEFAULT '',
+  page_path TEXT NOT NULL,
+  is_internal BOOLEAN NOT NULL DEFAULT false,
+  views BIGINT NOT NULL DEFAULT 0,
+  unique_users INT NOT NULL DEFAULT 0,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (period, period_start, source, domain, page_path, is_internal)
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_rollups_period
+  ON analytics_page_view_rollups(period, period_start);
+
+CREATE TABLE IF NOT EXISTS analytics_archives (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  -- First day of the archived month
+  month DATE NOT NULL UNIQUE,
+  file_name TEXT NOT NULL,
+  location TEXT NOT NULL,
+  destination_kind TEXT NOT NULL CHECK (destination_kind IN ('local', 's3')),
+  row_count INT NOT NULL DEFAULT 0,
+  total_views BIGINT NOT NULL DEFAULT 0,
+  size_bytes BIGINT NOT NULL DEFAULT 0,
+  archived_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  restored_at TIMESTAMPTZ
+);
+
+ALTER TABLE analytics_page_view_rollups ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "analytics_page_view_rollups_all" ON analytics_page_view_rollups
+  FOR ALL USING (true) WITH CHECK (true);
+
+ALTER TABLE analytics_archives ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "analytics_archives_all" ON analytics_archives
+  FOR ALL USING (true) WITH CHECK (true);
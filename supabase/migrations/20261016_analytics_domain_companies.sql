@@ -0,0 +1,21 @@
+-- Maps analytics tenant domains (analytics_page_views.domain) to CRM companies
+-- so product usage can be rolled up per customer. A domain belongs to at most
+-- one company; a company can have several domains.
+
+CREATE TABLE IF NOT EXISTS analytics_domain_companies (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  domain TEXT NOT NULL UNIQUE,
+  company_id UUID NOT NULL REFERENCES crm_companies(id) ON DELETE CASCADE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_domain_companies_company
+  ON analytics_domain_companies(company_id);
+
+ALTER TABLE analytics_domain_companies ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "analytics_domain_companies_all" ON analytics_domain_companies
+  FOR ALL USING (true) WITH CHECK (true);
+
+-- Per-domain usage lookups
+CREATE INDEX IF NOT EXISTS idx_analytics_page_views_domain_date
+  ON analytics_page_views(domain, view_date);
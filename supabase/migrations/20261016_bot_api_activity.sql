@@ -0,0 +1,25 @@
+-- Bot API activity log: one row per call a bot makes against the local bot
+-- API (tv-client), accepted or refused. action is the routed operation
+-- (create_task, update_task, project_update, ...); entity_id is the task or
+-- project update written, if any. project_id is kept as text: refused
+-- calls log whatever id the bot asked for.
+
+CREATE TABLE IF NOT EXISTS bot_api_activity (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  bot_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  action TEXT NOT NULL,
+  method TEXT NOT NULL,
+  path TEXT NOT NULL,
+  status_code INTEGER NOT NULL,
+  project_id TEXT,
+  entity_id UUID,
+  error TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_bot_api_activity_bot_created
+  ON bot_api_activity(bot_id, created_at DESC);
+
+ALTER TABLE bot_api_activity ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "bot_api_activity_all" ON bot_api_activity
+  FOR ALL USING (true) WITH CHECK (true);
@@ -0,0 +1,12 @@
+-- Activity auto-capture: meetings and email exchanges logged from Outlook.
+-- source:     'manual' for anything a person (or bot) logged, 'auto_capture'
+--             for rows written by the capture job
+-- source_ref: stable key of the captured item ("meeting:{event_id}",
+--             "email:{conversation_id}") so re-runs never double-log
+
+ALTER TABLE crm_activities ADD COLUMN IF NOT EXISTS source TEXT NOT NULL DEFAULT 'manual';
+ALTER TABLE crm_activities ADD COLUMN IF NOT EXISTS source_ref TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_crm_activities_source_ref
+  ON crm_activities(company_id, source_ref)
+  WHERE source_ref IS NOT NULL;
@@ -0,0 +1,33 @@
+-- Deal suggestions from inbound email. When an auto-linked email reads like a
+-- new opportunity (pricing question, demo request, RFQ…) for a company with no
+-- open deal, the detector queues a proposed deal here for one-click accept.
+-- status: 'pending' | 'accepted' (deal_id set) | 'dismissed'
+-- One suggestion per email, so re-running detection never duplicates.
+
+CREATE TABLE IF NOT EXISTS crm_deal_suggestions (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  email_id TEXT NOT NULL UNIQUE,
+  company_id UUID NOT NULL REFERENCES crm_companies(id) ON DELETE CASCADE,
+  contact_id UUID REFERENCES crm_contacts(id) ON DELETE SET NULL,
+  email_subject TEXT,
+  sender_email TEXT,
+  signals TEXT[] NOT NULL DEFAULT '{}',
+  reason TEXT,
+  confidence NUMERIC NOT NULL DEFAULT 0,
+  suggested_name TEXT NOT NULL,
+  suggested_stage TEXT NOT NULL DEFAULT 'lead',
+  suggested_value NUMERIC,
+  suggested_currency TEXT,
+  suggested_solution TEXT,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'dismissed')),
+  deal_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_crm_deal_suggestions_status
+  ON crm_deal_suggestions(status, created_at DESC);
+
+ALTER TABLE crm_deal_suggestions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "crm_deal_suggestions_all" ON crm_deal_suggestions
+  FOR ALL USING (true) WITH CHECK (true);
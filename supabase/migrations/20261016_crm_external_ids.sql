@@ -0,0 +1,25 @@
+-- External-ID tracking for CRM imports (HubSpot first).
+-- One row per imported record: maps the source system's ID to our local row
+-- so re-running an import upserts instead of duplicating.
+-- external_updated_at: source-side modification time at last import
+-- synced_at:           when we last wrote the local row — a local updated_at
+--                      later than this means someone edited it since.
+
+CREATE TABLE IF NOT EXISTS crm_external_ids (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  source TEXT NOT NULL,                -- 'hubspot'
+  entity_type TEXT NOT NULL CHECK (entity_type IN ('company', 'contact', 'deal', 'note')),
+  external_id TEXT NOT NULL,
+  local_id UUID NOT NULL,
+  external_updated_at TIMESTAMPTZ,
+  synced_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (source, entity_type, external_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_crm_external_ids_local
+  ON crm_external_ids(entity_type, local_id);
+
+ALTER TABLE crm_external_ids ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "crm_external_ids_all" ON crm_external_ids
+  FOR ALL USING (true) WITH CHECK (true);
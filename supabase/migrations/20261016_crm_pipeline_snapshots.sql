@@ -0,0 +1,22 @@
+-- Nightly pipeline snapshots: one row per active deal stage per day, so the
+-- pipeline can be charted over time. Written by the app's snapshot job
+-- (crm::pipeline_history); re-running a day overwrites that day's rows.
+
+CREATE TABLE IF NOT EXISTS crm_pipeline_snapshots (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  snapshot_date DATE NOT NULL,
+  stage TEXT NOT NULL,
+  deal_count INTEGER NOT NULL DEFAULT 0,
+  total_value NUMERIC NOT NULL DEFAULT 0,
+  weighted_value NUMERIC NOT NULL DEFAULT 0,
+  -- Stage weight (lookup_values.weight) at snapshot time
+  probability NUMERIC NOT NULL DEFAULT 0,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (snapshot_date, stage)
+);
+
+CREATE INDEX IF NOT EXISTS idx_crm_pipeline_snapshots_date ON crm_pipeline_snapshots(snapshot_date);
+
+ALTER TABLE crm_pipeline_snapshots ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "crm_pipeline_snapshots_all" ON crm_pipeline_snapshots
+  FOR ALL USING (true) WITH CHECK (true);
@@ -0,0 +1,39 @@
+-- Saved CRM segments: ICP-style filter expressions over companies or contacts.
+-- filter is {"conditions": [{"field", "op", "value", "weight"}], "min_score"};
+-- each member is scored 0-100 by the weight of the conditions it matches.
+-- Members are materialized so bulk email and reports can read them directly,
+-- and recomputed in the background every refresh_interval_hours.
+
+CREATE TABLE IF NOT EXISTS crm_segments (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name TEXT NOT NULL,
+  description TEXT,
+  entity_type TEXT NOT NULL CHECK (entity_type IN ('company', 'contact')),
+  filter JSONB NOT NULL DEFAULT '{"conditions": []}'::jsonb,
+  refresh_interval_hours INT NOT NULL DEFAULT 24 CHECK (refresh_interval_hours > 0),
+  member_count INT NOT NULL DEFAULT 0,
+  last_computed_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS crm_segment_members (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  segment_id UUID NOT NULL REFERENCES crm_segments(id) ON DELETE CASCADE,
+  company_id UUID REFERENCES crm_companies(id) ON DELETE CASCADE,
+  contact_id UUID REFERENCES crm_contacts(id) ON DELETE CASCADE,
+  score NUMERIC NOT NULL DEFAULT 100,
+  computed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (segment_id, company_id, contact_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_crm_segment_members_segment
+  ON crm_segment_members(segment_id, score DESC);
+
+ALTER TABLE crm_segments ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "crm_segments_all" ON crm_segments
+  FOR ALL USING (true) WITH CHECK (true);
+
+ALTER TABLE crm_segment_members ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "crm_segment_members_all" ON crm_segment_members
+  FOR ALL USING (true) WITH CHECK (true);
@@ -0,0 +1,30 @@
+-- Won/lost close reasons for deals.
+-- deal_close_reason: value from lookup_values (type deal_won_reason / deal_lost_reason),
+--   required by the app when a deal moves to won or lost.
+-- deal_competitor: who we lost to (or beat) — free text.
+-- deal_stage_at_close: stage the deal was in right before it closed.
+-- deal_lost_reason / deal_won_notes stay as free-text commentary.
+
+ALTER TABLE projects
+  ADD COLUMN IF NOT EXISTS deal_close_reason text,
+  ADD COLUMN IF NOT EXISTS deal_competitor text,
+  ADD COLUMN IF NOT EXISTS deal_stage_at_close text;
+
+CREATE INDEX IF NOT EXISTS idx_projects_deal_close_reason
+  ON projects(deal_close_reason) WHERE deal_close_reason IS NOT NULL;
+
+-- Seed taxonomy (edit in Metadata → Lookups)
+INSERT INTO lookup_values (type, value, label, color, sort_order) VALUES
+  ('deal_won_reason', 'product_fit', 'Product Fit', 'green', 0),
+  ('deal_won_reason', 'price', 'Price', 'blue', 1),
+  ('deal_won_reason', 'relationship', 'Relationship', 'purple', 2),
+  ('deal_won_reason', 'speed_to_value', 'Speed to Value', 'cyan', 3),
+  ('deal_won_reason', 'other', 'Other', 'zinc', 99),
+  ('deal_lost_reason', 'price', 'Price', 'red', 0),
+  ('deal_lost_reason', 'competitor', 'Went with Competitor', 'orange', 1),
+  ('deal_lost_reason', 'no_budget', 'No Budget', 'yellow', 2),
+  ('deal_lost_reason', 'no_decision', 'No Decision', 'gray', 3),
+  ('deal_lost_reason', 'product_gap', 'Product Gap', 'purple', 4),
+  ('deal_lost_reason', 'timing', 'Timing', 'blue', 5),
+  ('deal_lost_reason', 'other', 'Other', 'zinc', 99)
+ON CONFLICT (type, value) DO NOTHING;
@@ -0,0 +1,19 @@
+-- Stage-gated required fields for deals (projects with project_type = 'deal').
+-- A deal can't move into `stage` until every column in required_fields is
+-- filled in. Enforced by the app on stage change; edit rules from CRM settings.
+
+CREATE TABLE IF NOT EXISTS deal_stage_requirements (
+  stage TEXT PRIMARY KEY,
+  required_fields TEXT[] NOT NULL DEFAULT '{}',
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+ALTER TABLE deal_stage_requirements ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "deal_stage_requirements_all" ON deal_stage_requirements
+  FOR ALL USING (true) WITH CHECK (true);
+
+-- Proposal and later need a value, a close date and a decision maker contact
+INSERT INTO deal_stage_requirements (stage, required_fields) VALUES
+  ('proposal', ARRAY['deal_value', 'deal_expected_close', 'deal_contact_ids']),
+  ('negotiation', ARRAY['deal_value', 'deal_expected_close', 'deal_contact_ids'])
+ON CONFLICT (stage) DO NOTHING;
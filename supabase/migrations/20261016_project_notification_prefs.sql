@@ -0,0 +1,38 @@
+-- Per-user, per-project notification preferences.
+-- level: 'all'      — activity notifications (status/assignee/due-date changes) + mentions
+--        'mentions' — only @mentions (default when no row exists)
+--        'mute'     — nothing from this project, mentions included (filtered by
+--                     the app before inserting, see work/notifier.rs)
+-- weekly_digest: send a weekly project summary email to the user's address.
+
+CREATE TABLE IF NOT EXISTS project_notification_prefs (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  level TEXT NOT NULL DEFAULT 'mentions' CHECK (level IN ('all', 'mentions', 'mute')),
+  weekly_digest BOOLEAN NOT NULL DEFAULT false,
+  last_digest_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (user_id, project_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_notification_prefs_project
+  ON project_notification_prefs(project_id);
+
+CREATE OR REPLACE FUNCTION set_project_notification_prefs_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+  NEW.updated_at := now();
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_project_notification_prefs_updated_at ON project_notification_prefs;
+CREATE TRIGGER trg_project_notification_prefs_updated_at
+  BEFORE UPDATE ON project_notification_prefs
+  FOR EACH ROW EXECUTE FUNCTION set_project_notification_prefs_updated_at();
+
+ALTER TABLE project_notification_prefs ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "project_notification_prefs_all" ON project_notification_prefs
+  FOR ALL USING (true) WITH CHECK (true);
@@ -0,0 +1,6 @@
+-- Marks task descriptions written by the AI assist (work_ai_assist_task).
+-- ai_generated stays true until someone edits the description by hand, so the
+-- UI can flag AI text that still needs a human pass.
+
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS ai_generated BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS ai_generated_at TIMESTAMPTZ;
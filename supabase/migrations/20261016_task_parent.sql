@@ -0,0 +1,6 @@
+-- Subtasks: a task can sit under another task in the same project.
+-- Used by the markdown checklist import (work_import_checklist), which keeps
+-- the checklist's nesting. Deleting a parent deletes its subtasks.
+
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS parent_task_id UUID REFERENCES tasks(id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_task_id ON tasks(parent_task_id) WHERE parent_task_id IS NOT NULL;
@@ -0,0 +1,38 @@
+-- Per-project transition policies, evaluated when a task moves to a completed status.
+-- Dependents are tasks whose depends_on contains the completed task; one is
+-- "unblocked" once every task it depends on is completed.
+-- unblock_dependents:  move unblocked dependents sitting in backlog/unstarted
+--                      (or a status named 'Blocked') to unblock_status_name
+-- notify_assignees:    send an activity notification to their assignees
+-- enabled is the per-project toggle; no row means no policy.
+
+CREATE TABLE IF NOT EXISTS task_transition_policies (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL UNIQUE REFERENCES projects(id) ON DELETE CASCADE,
+  enabled BOOLEAN NOT NULL DEFAULT true,
+  unblock_dependents BOOLEAN NOT NULL DEFAULT true,
+  unblock_status_name TEXT NOT NULL DEFAULT 'Todo',
+  notify_assignees BOOLEAN NOT NULL DEFAULT true,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE FUNCTION set_task_transition_policies_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+  NEW.updated_at := now();
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_task_transition_policies_updated_at ON task_transition_policies;
+CREATE TRIGGER trg_task_transition_policies_updated_at
+  BEFORE UPDATE ON task_transition_policies
+  FOR EACH ROW EXECUTE FUNCTION set_task_transition_policies_updated_at();
+
+ALTER TABLE task_transition_policies ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "task_transition_policies_all" ON task_transition_policies
+  FOR ALL USING (true) WITH CHECK (true);
+
+-- Dependents lookup (depends_on @> ARRAY[task_id])
+CREATE INDEX IF NOT EXISTS idx_tasks_depends_on ON tasks USING GIN (depends_on);
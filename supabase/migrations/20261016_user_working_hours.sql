@@ -0,0 +1,15 @@
+-- Per-user timezone and working hours, so due-date reminders and SLA timers
+-- run on the assignee's local clock. timezone is an IANA name
+-- (e.g. 'Europe/London'); work_start/work_end are local wall-clock times
+-- (an end before the start is an overnight shift); country picks the
+-- work_calendars weekend and holidays. NULLs fall back to Singapore
+-- 09:00–18:00 on the SG calendar.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS timezone TEXT;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS work_start TIME;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS work_end TIME;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS country TEXT;
+
+-- Due reminder dedupe: "already sent this one to this person for this task?"
+CREATE INDEX IF NOT EXISTS idx_notifications_entity_type_recipient
+  ON notifications(entity_id, type, recipient);
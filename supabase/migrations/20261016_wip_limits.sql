@@ -0,0 +1,38 @@
+-- WIP limits — cap how many tasks a project may hold in a status column,
+-- or how many in-progress tasks may carry a label.
+-- Exactly one of status_id / label_id is set per row.
+-- enforcement: 'soft' warns on the move, 'hard' rejects it.
+
+CREATE TABLE IF NOT EXISTS wip_limits (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  status_id UUID REFERENCES task_statuses(id) ON DELETE CASCADE,
+  label_id UUID REFERENCES labels(id) ON DELETE CASCADE,
+  max_tasks INTEGER NOT NULL CHECK (max_tasks > 0),
+  enforcement TEXT NOT NULL DEFAULT 'soft' CHECK (enforcement IN ('soft', 'hard')),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  CHECK (num_nonnulls(status_id, label_id) = 1)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uniq_wip_limits_status
+  ON wip_limits(project_id, status_id) WHERE status_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uniq_wip_limits_label
+  ON wip_limits(project_id, label_id) WHERE label_id IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION set_wip_limits_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+  NEW.updated_at := now();
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_wip_limits_updated_at ON wip_limits;
+CREATE TRIGGER trg_wip_limits_updated_at
+  BEFORE UPDATE ON wip_limits
+  FOR EACH ROW EXECUTE FUNCTION set_wip_limits_updated_at();
+
+ALTER TABLE wip_limits ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "wip_limits_all" ON wip_limits
+  FOR ALL USING (true) WITH CHECK (true);
@@ -0,0 +1,41 @@
+-- Working-days calendar for due-date calculations. Weekends are per country
+-- (ISO weekday numbers, 1 = Monday … 7 = Sunday); holidays are dated rows.
+-- Countries without a row fall back to a Saturday/Sunday weekend.
+
+CREATE TABLE IF NOT EXISTS work_calendars (
+  country TEXT PRIMARY KEY,
+  weekend_days INTEGER[] NOT NULL DEFAULT '{6,7}',
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS work_holidays (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  country TEXT NOT NULL,
+  date DATE NOT NULL,
+  name TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (country, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_holidays_country_date ON work_holidays (country, date);
+
+ALTER TABLE work_calendars ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "work_calendars_all" ON work_calendars
+  FOR ALL USING (true) WITH CHECK (true);
+
+ALTER TABLE work_holidays ENABLE ROW LEVEL SECURITY;
+CREATE POLICY "work_holidays_all" ON work_holidays
+  FOR ALL USING (true) WITH CHECK (true);
+
+-- Singapore, with the 2026 fixed-date holidays and Chinese New Year
+INSERT INTO work_calendars (country, weekend_days) VALUES ('SG', '{6,7}')
+ON CONFLICT (country) DO NOTHING;
+
+INSERT INTO work_holidays (country, date, name) VALUES
+  ('SG', '2026-01-01', 'New Year''s Day'),
+  ('SG', '2026-02-17', 'Chinese New Year'),
+  ('SG', '2026-02-18', 'Chinese New Year'),
+  ('SG', '2026-05-01', 'Labour Day'),
+  ('SG', '2026-08-10', 'National Day (observed)'),
+  ('SG', '2026-12-25', 'Christmas Day')
+ON CONFLICT (country, date) DO NOTHING;